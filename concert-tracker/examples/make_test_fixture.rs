@@ -223,6 +223,7 @@ fn auto_timestamps(fc: &FixtureConcert) -> Vec<concert_types::SongTimestamp> {
                 start_time: start,
                 end_time: end,
                 duration: end - start,
+                boundary: None,
             }
         })
         .collect()
@@ -24,6 +24,7 @@ export type SplitTimestampsResponse = Schemas["SplitTimestampsResponse"];
 export type SplitStartStatus = Schemas["SplitStartStatus"];
 export type SplitStartResponse = Schemas["SplitStartResponse"];
 export type SongTimestamp = Schemas["SongTimestamp"];
+export type BoundarySource = Schemas["BoundarySource"];
 export type TimestampPayload = Schemas["TimestampPayload"];
 export type TimestampPayloadSong = Schemas["TimestampPayloadSong"];
 
@@ -187,19 +188,35 @@ export async function playlistNestedIn(childPlaylistId: number): Promise<Members
 
 // ── Playback / media-info / prepare (player.ts) ─────────────────────────────
 
+/** Published rendition heights (`live_set_splitter::cut::Rendition`). */
+const RENDITION_HEIGHTS = [360, 480, 720, 1080] as const;
+
+/** The `?quality=` for this screen: the smallest rendition at least as tall as
+ *  its shorter side, so a phone streams a downscaled video where a desktop gets
+ *  the original. The server never exceeds the configured video quality. */
+function qualityQuery(): string {
+  const side = Math.min(window.screen.width, window.screen.height);
+  const height = RENDITION_HEIGHTS.find((h) => h >= side);
+  return `?quality=${height === undefined ? "original" : `${height}p`}`;
+}
+
 export async function getMediaInfo(concertId: number): Promise<MediaInfo> {
   return getJson<MediaInfo>(`/concerts/${concertId}/media-info`);
 }
 
 export async function getTrackMediaInfo(concertId: number, trackIdx: number): Promise<MediaInfo> {
-  return getJson<MediaInfo>(`/concerts/${concertId}/tracks/${trackIdx}/media-info`);
+  return getJson<MediaInfo>(
+    `/concerts/${concertId}/tracks/${trackIdx}/media-info${qualityQuery()}`,
+  );
 }
 
 export async function getTrackMediaInfoOrNull(
   concertId: number,
   trackIdx: number,
 ): Promise<MediaInfo | null> {
-  return getJsonOrNull<MediaInfo>(`/concerts/${concertId}/tracks/${trackIdx}/media-info`);
+  return getJsonOrNull<MediaInfo>(
+    `/concerts/${concertId}/tracks/${trackIdx}/media-info${qualityQuery()}`,
+  );
 }
 
 export async function getNextTrackMediaInfo(
@@ -207,9 +224,10 @@ export async function getNextTrackMediaInfo(
   trackIdx: number,
   signal?: AbortSignal,
 ): Promise<MediaInfo> {
-  return getJson<MediaInfo>(`/concerts/${concertId}/tracks/${trackIdx}/next-media-info`, {
-    signal: signal ?? null,
-  });
+  return getJson<MediaInfo>(
+    `/concerts/${concertId}/tracks/${trackIdx}/next-media-info${qualityQuery()}`,
+    { signal: signal ?? null },
+  );
 }
 
 /** Like getNextTrackMediaInfo, but treats the "no later playable track" 404 as
@@ -219,9 +237,10 @@ export async function getNextTrackMediaInfoOrNull(
   trackIdx: number,
   signal?: AbortSignal,
 ): Promise<MediaInfo | null> {
-  return getJsonNullOn404<MediaInfo>(`/concerts/${concertId}/tracks/${trackIdx}/next-media-info`, {
-    signal: signal ?? null,
-  });
+  return getJsonNullOn404<MediaInfo>(
+    `/concerts/${concertId}/tracks/${trackIdx}/next-media-info${qualityQuery()}`,
+    { signal: signal ?? null },
+  );
 }
 
 export async function getPrevTrackMediaInfo(
@@ -229,13 +248,16 @@ export async function getPrevTrackMediaInfo(
   trackIdx: number,
   signal?: AbortSignal,
 ): Promise<MediaInfo> {
-  return getJson<MediaInfo>(`/concerts/${concertId}/tracks/${trackIdx}/prev-media-info`, {
-    signal: signal ?? null,
-  });
+  return getJson<MediaInfo>(
+    `/concerts/${concertId}/tracks/${trackIdx}/prev-media-info${qualityQuery()}`,
+    { signal: signal ?? null },
+  );
 }
 
 export async function getConcertPlayback(concertId: number): Promise<ConcertPlaybackResponse> {
-  return getJson<ConcertPlaybackResponse>(`/concerts/${concertId}/concert-playback`);
+  return getJson<ConcertPlaybackResponse>(
+    `/concerts/${concertId}/concert-playback${qualityQuery()}`,
+  );
 }
 
 export async function postPrepare(concertId: number): Promise<Response> {
@@ -313,6 +313,26 @@ export interface components {
             track_index?: number | null;
             type: string;
         };
+        /** @description Where a song's start came from and how much to trust it. */
+        BoundaryProvenance: {
+            /**
+             * Format: double
+             * @description 0 (a guess) to 1 (certain).
+             */
+            confidence: number;
+            refinement?: null | components["schemas"]["BoundaryRefinement"];
+            source: components["schemas"]["BoundarySource"];
+        };
+        /**
+         * @description An adjustment applied to a start after it was found.
+         * @enum {string}
+         */
+        BoundaryRefinement: "snapped_to_silence" | "after_crowd" | "pulled_back";
+        /**
+         * @description How a song's start was found.
+         * @enum {string}
+         */
+        BoundarySource: "recording_start" | "supplied" | "overlay" | "fuzzy_overlay" | "title_only" | "overlay_cluster" | "silence" | "crowd" | "equal_split";
         /** @description Tagged-union response for `GET /concerts/:id/concert-playback`. */
         ConcertPlaybackResponse: {
             /** @enum {string} */
@@ -337,6 +357,12 @@ export interface components {
         };
         MediaInfo: {
             artist: string;
+            /**
+             * Format: double
+             * @description dB the player applies under the configured ReplayGain mode; `None`
+             *     when the mode is off or the concert has no loudness measurements.
+             */
+            gain_db?: number | null;
             /**
              * @description Whether a playable track exists after this one in the same concert, so the
              *     player can disable its Next button when there is nothing left to advance to.
@@ -348,6 +374,11 @@ export interface components {
              *     Always false for whole-album playback (no per-track navigation).
              */
             has_prev: boolean;
+            /**
+             * @description Master HLS playlist for this concert or track, when the concert was
+             *     packaged for HLS; the player prefers it where the browser plays HLS natively.
+             */
+            hls_url?: string | null;
             is_video: boolean;
             /**
              * @description Whether this track is liked, so the player bar can show its like star.
@@ -355,6 +386,11 @@ export interface components {
              */
             liked: boolean;
             playable: boolean;
+            /**
+             * @description The track's poster frame for the player bar. Always `None` for
+             *     whole-album playback.
+             */
+            poster_url?: string | null;
             title: string;
             track_index?: number | null;
             url: string;
@@ -373,10 +409,26 @@ export interface components {
         /** @description JSON payload for a single item in the reconstruction sequence. */
         PlaybackItemJson: {
             artist: string;
+            /**
+             * Format: double
+             * @description dB the player applies under the configured ReplayGain mode; `None`
+             *     when the mode is off or the concert has no loudness measurements.
+             */
+            gain_db?: number | null;
+            /**
+             * @description Master HLS playlist for this concert or track, when the concert was
+             *     packaged for HLS; the player prefers it where the browser plays HLS natively.
+             */
+            hls_url?: string | null;
             interlude_index?: number | null;
             is_video: boolean;
             kind: string;
             liked: boolean;
+            /**
+             * @description The song's poster frame for the player bar; `None` for interludes and
+             *     songs split without posters.
+             */
+            poster_url?: string | null;
             title: string;
             track_index?: number | null;
             url: string;
@@ -451,6 +503,7 @@ export interface components {
             track_index: number;
         };
         SongTimestamp: {
+            boundary?: null | components["schemas"]["BoundaryProvenance"];
             /** Format: double */
             duration: number;
             /** Format: double */
@@ -955,7 +1008,10 @@ export interface operations {
     };
     concert_playback: {
         parameters: {
-            query?: never;
+            query?: {
+                /** @description Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed */
+                quality?: string;
+            };
             header?: never;
             path: {
                 /** @description Concert ID */
@@ -1316,7 +1372,10 @@ export interface operations {
     };
     track_media_info: {
         parameters: {
-            query?: never;
+            query?: {
+                /** @description Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed */
+                quality?: string;
+            };
             header?: never;
             path: {
                 /** @description Concert ID */
@@ -1348,7 +1407,10 @@ export interface operations {
     };
     next_track_media_info: {
         parameters: {
-            query?: never;
+            query?: {
+                /** @description Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed */
+                quality?: string;
+            };
             header?: never;
             path: {
                 /** @description Concert ID */
@@ -1380,7 +1442,10 @@ export interface operations {
     };
     prev_track_media_info: {
         parameters: {
-            query?: never;
+            query?: {
+                /** @description Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed */
+                quality?: string;
+            };
             header?: never;
             path: {
                 /** @description Concert ID */
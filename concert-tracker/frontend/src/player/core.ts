@@ -254,6 +254,20 @@ export function formatTime(seconds: number): string {
   return m + ":" + (s < 10 ? "0" : "") + s;
 }
 
+// End (seconds) of a `#t=start,end` media fragment, as on a song played from
+// a concert's cue-indexed album; null when the URL has no fragment end.
+export function clipEndFromUrl(url: string): number | null {
+  const match = /#t=[\d.]*,([\d.]+)$/.exec(url);
+  return match ? Number(match[1]) : null;
+}
+
+// Did a `pause` come from playback reaching the clip's end? The browser stops
+// at a fragment end without firing `ended`, so this pause ends the track.
+export function reachedClipEnd(url: string, currentTime: number): boolean {
+  const end = clipEndFromUrl(url);
+  return end !== null && currentTime >= end - 0.25;
+}
+
 // Does a click on `target` fall on dead space outside `container`, and so
 // should dismiss the video? (false for clicks inside the container or on any
 // interactive control, per INTERACTIVE_SELECTOR)
@@ -1,10 +1,12 @@
 import { describe, expect, test } from "vitest";
 
 import {
+  clipEndFromUrl,
   isEditableTarget,
   isKeyboardShortcutIgnoredTarget,
   isPlayerPlaybackShortcutTarget,
   nativeClickShouldWin,
+  reachedClipEnd,
 } from "./core";
 
 // Builds the DOM shape the keyboard-target predicates walk with closest():
@@ -193,3 +195,19 @@ describe("nativeClickShouldWin", () => {
     expect(nativeClickShouldWin(click(), anchor({ download: "" }))).toBe(true);
   });
 });
+
+describe("reachedClipEnd", () => {
+  const url = "/concert-files/Album/Album.album.flac#t=60.000,90.000";
+
+  test("reads the fragment end", () => {
+    expect(clipEndFromUrl(url)).toBe(90);
+    expect(clipEndFromUrl("/concert-files/Album/Album.album.flac#t=60.000")).toBeNull();
+    expect(clipEndFromUrl("/concert-files/Album/Song.m4a")).toBeNull();
+  });
+
+  test("only a pause at the fragment end ends the clip", () => {
+    expect(reachedClipEnd(url, 89.9)).toBe(true);
+    expect(reachedClipEnd(url, 75)).toBe(false);
+    expect(reachedClipEnd("/concert-files/Album/Song.m4a", 200)).toBe(false);
+  });
+});
@@ -0,0 +1,30 @@
+// ReplayGain for #player-audio. The server resolves the configured mode
+// (off/track/album) to one `gain_db` per file (MediaInfo/PlaybackItemJson);
+// this applies it.
+//
+// An element's `volume` can only attenuate and track gains are often
+// positive, so the element is routed through a Web Audio GainNode instead.
+// createMediaElementSource may be called only once per element, so the graph
+// is built lazily on the first non-null gain and kept for the page's life;
+// after that every load sets the gain, with null meaning unity. Browsers
+// without Web Audio (and happy-dom) just play unadjusted.
+interface GainGraph {
+  element: HTMLMediaElement;
+  context: AudioContext;
+  gain: GainNode;
+}
+
+let graph: GainGraph | null = null;
+
+export function applyGain(element: HTMLMediaElement, gainDb: number | null): void {
+  if (!graph || graph.element !== element) {
+    if (gainDb === null || typeof AudioContext === "undefined") return;
+    const context = new AudioContext();
+    const gain = context.createGain();
+    context.createMediaElementSource(element).connect(gain).connect(context.destination);
+    graph = { element, context, gain };
+  }
+  graph.gain.gain.value = gainDb === null ? 1 : Math.pow(10, gainDb / 20);
+  // Created or suspended outside a user gesture; PlayAudio runs from one.
+  if (graph.context.state === "suspended") void graph.context.resume();
+}
@@ -20,6 +20,7 @@ import {
 } from "../../api/client";
 import { clampSidebarWidth, PREPARE_POLL_MS, SIDEBAR_WIDTH_KEY } from "../core";
 import { byIdOfOrNull, byIdOrNull } from "../../shared/dom";
+import { applyGain } from "../gain";
 import { setNowPlaying } from "../mirror";
 import {
   Acked,
@@ -432,17 +433,22 @@ export const OpenExternalRequest = Command.define(
 // actually loaded right now," independent of when this Command's Effect
 // happens to run relative to the model update that triggered it (see
 // model.ts's audioLoadGen doc comment for the race this closes).
+//
+// `gainDb` is the server-resolved ReplayGain for this file (null = unity),
+// applied before play() so a track never starts at the previous one's level.
 export const PlayAudio = Command.define(
   "PlayAudio",
-  { url: S.String, loadGen: S.Number },
+  { url: S.String, hlsUrl: S.NullOr(S.String), loadGen: S.Number, gainDb: S.NullOr(S.Number) },
   Acked,
   RejectedAudioPlay,
-)(({ url, loadGen }) =>
+)(({ url, hlsUrl, loadGen, gainDb }) =>
   Effect.sync(() => byIdOfOrNull("player-audio", HTMLMediaElement)).pipe(
     Effect.flatMap((audio) => {
       if (!audio) return Effect.succeed(RejectedAudioPlay());
-      audio.src = url;
+      // No HLS library is bundled: only browsers that play HLS natively get the stream.
+      audio.src = hlsUrl !== null && audio.canPlayType("application/vnd.apple.mpegurl") !== "" ? hlsUrl : url;
       audio.dataset.audioLoadGen = String(loadGen);
+      applyGain(audio, gainDb);
       return Effect.tryPromise(() => audio.play()).pipe(
         Effect.as(Acked()),
         Effect.catch(() => Effect.succeed(RejectedAudioPlay())),
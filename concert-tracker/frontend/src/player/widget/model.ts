@@ -36,11 +36,14 @@ export type SidebarTrackList = typeof SidebarTrackList.Type;
 
 export const MediaInfo = S.Struct({
   artist: S.String,
+  gain_db: S.optionalKey(S.NullOr(S.Number)),
   has_next: S.Boolean,
   has_prev: S.Boolean,
+  hls_url: S.optionalKey(S.NullOr(S.String)),
   is_video: S.Boolean,
   liked: S.Boolean,
   playable: S.Boolean,
+  poster_url: S.optionalKey(S.NullOr(S.String)),
   title: S.String,
   track_index: S.optionalKey(S.NullOr(S.Number)),
   url: S.String,
@@ -57,10 +60,13 @@ export type PrepareStatus = typeof PrepareStatus.Type;
 
 export const PlaybackItem = S.Struct({
   artist: S.String,
+  gain_db: S.optionalKey(S.NullOr(S.Number)),
+  hls_url: S.optionalKey(S.NullOr(S.String)),
   interlude_index: S.optionalKey(S.NullOr(S.Number)),
   is_video: S.Boolean,
   kind: S.String,
   liked: S.Boolean,
+  poster_url: S.optionalKey(S.NullOr(S.String)),
   title: S.String,
   track_index: S.optionalKey(S.NullOr(S.Number)),
   url: S.String,
@@ -112,6 +118,9 @@ export const Playback = S.Struct({
   hasNext: S.Boolean,
   hasPrev: S.Boolean,
   liked: S.Boolean,
+  /** The track's poster frame, shown beside the title; null for whole-album
+   *  playback, interludes and tracks split without posters. */
+  posterUrl: S.NullOr(S.String),
   /** True once the current track/album has played to its natural end with
    *  nothing to auto-advance to. Distinct from `!isPlaying` — a user-paused
    *  mid-track is NOT idle (see core "playerIdle" semantics in player.ts);
@@ -135,6 +144,7 @@ export const initialPlayback: Playback = {
   hasNext: false,
   hasPrev: false,
   liked: false,
+  posterUrl: null,
   ended: false,
   concert: Option.none(),
   playlistLabel: null,
@@ -14,7 +14,7 @@ describe("PlayAudio", () => {
   test("resolves to RejectedAudioPlay instead of throwing when #player-audio is absent", async () => {
     document.getElementById("player-audio")?.remove();
 
-    const result = await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", loadGen: 1 }).effect);
+    const result = await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", hlsUrl: null, loadGen: 1, gainDb: null }).effect);
 
     expect(result).toEqual(RejectedAudioPlay());
   });
@@ -26,7 +26,7 @@ describe("PlayAudio", () => {
     audio.play = () => Promise.resolve();
     document.body.appendChild(audio);
 
-    const result = await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", loadGen: 1 }).effect);
+    const result = await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", hlsUrl: null, loadGen: 1, gainDb: null }).effect);
 
     expect(result).toEqual(Acked());
     expect(audio.src).toBe("https://example.com/a.mp3");
@@ -50,7 +50,7 @@ describe("PlayAudio", () => {
     };
     document.body.appendChild(audio);
 
-    await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", loadGen: 7 }).effect);
+    await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", hlsUrl: null, loadGen: 7, gainDb: null }).effect);
 
     expect(srcAtPlayCall).toBe("https://example.com/a.mp3");
     expect(loadGenAtPlayCall).toBe("7");
@@ -71,7 +71,7 @@ describe("PlayAudio", () => {
     };
     document.body.appendChild(audio);
 
-    await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", loadGen: 4 }).effect);
+    await Effect.runPromise(PlayAudio({ url: "https://example.com/a.mp3", hlsUrl: null, loadGen: 4, gainDb: null }).effect);
 
     expect(loadGenAtPlayCall).toBe("4");
     expect(audio.dataset.audioLoadGen).toBe("4");
@@ -79,3 +79,49 @@ describe("PlayAudio", () => {
     audio.remove();
   });
 });
+
+describe("PlayAudio gain", () => {
+  test("plays unadjusted where Web Audio is unavailable", async () => {
+    document.getElementById("player-audio")?.remove();
+    const audio = document.createElement("audio");
+    audio.id = "player-audio";
+    audio.play = () => Promise.resolve();
+    document.body.appendChild(audio);
+
+    // happy-dom has no AudioContext: a gain must not turn into a rejection.
+    const result = await Effect.runPromise(
+      PlayAudio({ url: "https://example.com/a.mp3", hlsUrl: null, loadGen: 1, gainDb: -4.5 }).effect,
+    );
+
+    expect(result).toEqual(Acked());
+    expect(audio.src).toBe("https://example.com/a.mp3");
+
+    audio.remove();
+  });
+});
+
+describe("PlayAudio HLS", () => {
+  const playWith = async (canPlayHls: boolean) => {
+    document.getElementById("player-audio")?.remove();
+    const audio = document.createElement("audio");
+    audio.id = "player-audio";
+    audio.play = () => Promise.resolve();
+    audio.canPlayType = (type: string) => (canPlayHls && type === "application/vnd.apple.mpegurl" ? "maybe" : "");
+    document.body.appendChild(audio);
+    await Effect.runPromise(
+      PlayAudio({ url: "https://example.com/a.mp3", hlsUrl: "https://example.com/a.m3u8", loadGen: 1, gainDb: null })
+        .effect,
+    );
+    const src = audio.src;
+    audio.remove();
+    return src;
+  };
+
+  test("streams the HLS playlist where the browser plays HLS natively", async () => {
+    expect(await playWith(true)).toBe("https://example.com/a.m3u8");
+  });
+
+  test("falls back to the file URL where it does not", async () => {
+    expect(await playWith(false)).toBe("https://example.com/a.mp3");
+  });
+});
@@ -7,6 +7,7 @@ import {
   isKeyboardShortcutIgnoredTarget,
   isPlainEscapeKey,
   isPlainSpaceKey,
+  reachedClipEnd,
   SIDEBAR_MIN_WIDTH,
   VIDEO_CONTROLS_IDLE_MS,
 } from "../core";
@@ -142,7 +143,17 @@ export const subscriptions = Subscription.make<Model, Message>()((entry) => ({
           [
             Stream.merge(
               Stream.fromEventListener(audio, "play").pipe(Stream.map(() => StartedAudio())),
-              Stream.fromEventListener(audio, "pause").pipe(Stream.map(() => PausedAudio())),
+              // A clip of a cue-indexed album pauses at its fragment end
+              // instead of firing `ended`, so that pause also ends the track.
+              Stream.fromEventListener(audio, "pause").pipe(
+                Stream.flatMap(() =>
+                  Stream.fromIterable(
+                    reachedClipEnd(audio.src, audio.currentTime)
+                      ? [PausedAudio(), EndedAudio()]
+                      : [PausedAudio()],
+                  ),
+                ),
+              ),
             ),
             Stream.merge(
               Stream.fromEventListener(audio, "ended").pipe(Stream.map(() => EndedAudio())),
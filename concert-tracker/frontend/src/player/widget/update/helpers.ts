@@ -157,6 +157,7 @@ export const beginPlayback = (
       hasNext: info.has_next,
       hasPrev: info.has_prev,
       liked: info.liked,
+      posterUrl: info.poster_url ?? null,
       ended: false,
       // Cleared here; playConcertItemPure restores it after this returns.
       concert: Option.none(),
@@ -179,7 +180,7 @@ export const beginPlayback = (
     onSome: (seconds) => [SeekAudio({ seconds })],
   });
   const commands: Command<Message>[] = [
-    PlayAudio({ url: info.url, loadGen: newLoadGen }),
+    PlayAudio({ url: info.url, hlsUrl: info.hls_url ?? null, loadGen: newLoadGen, gainDb: info.gain_db ?? null }),
     MarkPlayingExternal({ concertId, trackIdx: Option.fromNullishOr(trackIdx) }),
     ClearPreparingExternal(),
     ...videoPanelToggle,
@@ -303,11 +304,14 @@ export function playConcertItemPure(model: Model, pos: number): UpdateReturn {
       const { hasPrev, hasNext } = concertItemNav(concert.items, pos);
       const info: MediaInfo = {
         artist: item.artist,
+        gain_db: item.gain_db ?? null,
         has_next: hasNext,
         has_prev: hasPrev,
+        hls_url: item.hls_url ?? null,
         is_video: item.is_video,
         liked: item.liked,
         playable: true,
+        poster_url: item.poster_url ?? null,
         title: item.title,
         track_index: trackIdx,
         url: item.url,
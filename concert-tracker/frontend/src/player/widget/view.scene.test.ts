@@ -188,6 +188,20 @@ describe("player-bar view", () => {
     );
   });
 
+  test("poster frame shows beside the title only when the track has one", () => {
+    Scene.scene(
+      { update, view },
+      Scene.with(trackModel({ posterUrl: "/concert-files/A/Blue Train.poster.jpg" })),
+      Scene.expect(Scene.selector("#player-poster")).toHaveAttr("src", "/concert-files/A/Blue Train.poster.jpg"),
+      Scene.expect(Scene.selector("#player-poster")).toBeVisible(),
+    );
+    Scene.scene(
+      { update, view },
+      Scene.with(trackModel({ posterUrl: null })),
+      Scene.expect(Scene.selector("#player-poster")).not.toBeVisible(),
+    );
+  });
+
   test("watchUrl present but isVideo false — open visible, watch hidden", () => {
     Scene.scene(
       { update, view },
@@ -376,6 +376,14 @@ function playerBarView(model: Model): Html {
         ],
       ),
 
+      // ── Poster frame ────────────────────────────────────────────
+      h.img([
+        h.Id("player-poster"),
+        h.Src(p.posterUrl ?? ""),
+        h.Alt(""),
+        h.Style({ display: hasMedia && p.posterUrl !== null ? "" : "none" }),
+      ]),
+
       // ── Info: title-line + artist + playlist ────────────────────
       h.div(
         [h.Id("player-info")],
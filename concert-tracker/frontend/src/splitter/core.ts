@@ -9,13 +9,22 @@
 // js-tests/splitter.test.ts, which now imports straight from this module
 // instead of requiring the compiled bundle). No DOM access here — see
 // ./index.ts for the interaction layer.
-import type { SongTimestamp, SplitTimestampsResponse, TimestampPayload } from "../api/client";
+import type {
+  BoundarySource,
+  SongTimestamp,
+  SplitTimestampsResponse,
+  TimestampPayload,
+} from "../api/client";
 
 // Mirror of MIN_SONG_DURATION_SECONDS in concert-tracker/src/split_timestamps.rs.
 // The server rejects any track shorter than this, so the editor never lets a
 // segment shrink below it.
 export const MIN_SEG = 1.0;
 
+// Mirror of concert_types::LOW_CONFIDENCE. Automatic boundaries the splitter
+// was less sure of than this are flagged for review on the timeline.
+export const LOW_CONFIDENCE = 0.6;
+
 // Two boundary times within this many seconds are treated as a single linked
 // split point when loading stored timestamps.
 const LINK_EPS = 0.05;
@@ -31,6 +40,11 @@ export interface EditorState {
   tracks: EditorTrack[];
   /** linked[i] describes the boundary between tracks[i] and tracks[i + 1]. */
   linked: boolean[];
+  /**
+   * review[i] says why tracks[i]'s automatic start deserves a second look, or
+   * is null when the splitter was confident (or the start was hand-entered).
+   */
+  review: (string | null)[];
 }
 
 export type HandleKind = "start" | "end";
@@ -88,6 +102,29 @@ export function formatTimecode(sec: number): string {
   return m + ":" + s;
 }
 
+const BOUNDARY_SOURCE_LABELS: Record<BoundarySource, string> = {
+  recording_start: "start of recording",
+  supplied: "supplied timestamp",
+  overlay: "title overlay",
+  fuzzy_overlay: "approximate title overlay",
+  title_only: "title without artist overlay",
+  overlay_cluster: "unreadable title overlay",
+  silence: "silence",
+  crowd: "end of applause/talk",
+  equal_split: "equal split",
+};
+
+/**
+ * Why the splitter's start for `t` needs review, or null when its confidence
+ * is at least LOW_CONFIDENCE or it carries no provenance.
+ */
+export function reviewNote(t: SongTimestamp): string | null {
+  const boundary = t.boundary;
+  if (!boundary || boundary.confidence >= LOW_CONFIDENCE) return null;
+  const percent = Math.round(boundary.confidence * 100);
+  return `Low confidence (${percent}%): ${BOUNDARY_SOURCE_LABELS[boundary.source]}`;
+}
+
 /**
  * Build editor state from the GET response. Prefers user timestamps over
  * auto. `duration` falls back to the last end time when media_duration is
@@ -114,7 +151,7 @@ export function initState(resp: SplitTimestampsResponse): EditorState | null {
     // Non-null: i and i+1 are both < tracks.length by the loop bound.
     linked.push(Math.abs(tracks[i]!.end - tracks[i + 1]!.start) <= LINK_EPS);
   }
-  return { duration, tracks, linked };
+  return { duration, tracks, linked, review: chosen.map(reviewNote) };
 }
 
 /**
@@ -24,6 +24,7 @@ export const EditorState = S.Struct({
   // exactly, not just structurally up to readonly-ness.
   tracks: S.mutable(S.Array(EditorTrack)),
   linked: S.mutable(S.Array(S.Boolean)),
+  review: S.mutable(S.Array(S.NullOr(S.String))),
 });
 export type EditorState = typeof EditorState.Type;
 
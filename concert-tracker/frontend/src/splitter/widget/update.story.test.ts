@@ -52,6 +52,7 @@ const editor = (over?: Partial<EditorState>): EditorState => ({
     { title: "Two", start: 40, end: 100 },
   ],
   linked: [true],
+  review: [null, null],
   ...over,
 });
 
@@ -18,6 +18,7 @@ const editor = (over?: Partial<EditorState>): EditorState => ({
     { title: "Two", start: 40, end: 100 },
   ],
   linked: [true],
+  review: [null, null],
   ...over,
 });
 
@@ -110,6 +111,22 @@ describe("splitter view", () => {
     );
   });
 
+  test("a low-confidence boundary shows why it needs review", () => {
+    Scene.scene(
+      { update, view },
+      Scene.with(ready({ editorState: editor({ review: [null, "Low confidence (20%): equal split"] }) })),
+      Scene.expect(Scene.text("Low confidence (20%): equal split")).toExist(),
+    );
+  });
+
+  test("confident boundaries show no review note", () => {
+    Scene.scene(
+      { update, view },
+      Scene.with(ready()),
+      Scene.expect(Scene.text("Low confidence (20%): equal split")).toBeAbsent(),
+    );
+  });
+
   test("an unsupported source format shows the format-specific preview note", () => {
     Scene.scene(
       { update, view },
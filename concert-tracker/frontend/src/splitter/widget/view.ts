@@ -137,15 +137,34 @@ const gapView = (editor: EditorState, boundaryIndex: number): Html => {
   );
 };
 
+/** Why track `trackIndex`'s automatic start needs review, if it does. */
+const reviewOf = (editor: EditorState, trackIndex: number): Option.Option<string> =>
+  Option.flatMap(Array.get(editor.review, trackIndex), Option.fromNullishOr);
+
+/** The track whose start `handle` moves: its own track for a start handle, the
+ *  next one for a linked boundary, none for a detached end or the tail. */
+const startedTrack = (handle: Handle): Option.Option<number> =>
+  handle.kind === "start"
+    ? Option.some(handle.track)
+    : handle.linked
+      ? Option.some(handle.track + 1)
+      : Option.none();
+
 const handleView = (editor: EditorState, handle: Handle, index: number, dragState: DragState): Html => {
   const h = html<Message>();
 
   const isDragging = dragState._tag === "Dragging" && dragState.handleIndex === index;
+  const review = Option.flatMap(startedTrack(handle), (track) => reviewOf(editor, track));
 
   return h.keyed("div")(
     `handle-${index}`,
     [
-      h.Class(`splitter-handle splitter-handle-${handle.kind}${isDragging ? " dragging" : ""}`),
+      h.Class(
+        `splitter-handle splitter-handle-${handle.kind}${isDragging ? " dragging" : ""}${
+          Option.isSome(review) ? " splitter-handle-review" : ""
+        }`,
+      ),
+      ...Option.match(review, { onNone: () => [], onSome: (note) => [h.Title(note)] }),
       h.Style({ left: `${percentOf(editor.duration, handleTime(editor, handle))}%` }),
       h.OnPointerDown(() => Option.some(PressedHandle({ handleIndex: index }))),
     ],
@@ -229,6 +248,10 @@ const boundariesView = (editor: EditorState, busy: boolean): Html => {
         [h.Class("splitter-boundary")],
         [
           h.span([h.Class("splitter-boundary-label")], [`${fromTitle} → ${toTitle}`]),
+          ...Option.match(reviewOf(editor, boundaryIndex + 1), {
+            onNone: () => [],
+            onSome: (note) => [h.span([h.Class("splitter-review")], [note])],
+          }),
           h.button(
             [
               h.Class("splitter-detach"),
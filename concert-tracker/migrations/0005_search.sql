@@ -0,0 +1,76 @@
+-- Full-text search over concerts: artist, title, description, set list,
+-- musicians and notes, so "that concert with the pedal steel player" is a
+-- query instead of a scroll through month dividers.
+--
+-- The index is a regular (self-contained) FTS5 table rather than an
+-- external-content one: set_list_json / musicians_json are JSON, and indexing
+-- them raw would make the keys ("name", "instruments") match every concert.
+-- concert_search_text flattens the JSON into plain text; it is the single
+-- definition the triggers and the backfill below all read from.
+--
+-- json_valid() guards: concert_from_row tolerates malformed JSON columns, so
+-- the triggers must too — json_each() on invalid JSON would raise and abort
+-- the concerts write that fired the trigger.
+
+CREATE VIEW IF NOT EXISTS concert_search_text AS
+SELECT
+    c.id AS id,
+    c.artist AS artist,
+    c.title AS title,
+    c.description AS description,
+    (SELECT group_concat(s.value, ' ')
+       FROM json_each(CASE WHEN json_valid(c.set_list_json) THEN c.set_list_json ELSE '[]' END) s
+    ) AS set_list,
+    (SELECT group_concat(
+                COALESCE(json_extract(m.value, '$.name'), '') || ' ' ||
+                COALESCE((SELECT group_concat(i.value, ' ')
+                            FROM json_each(m.value, '$.instruments') i), ''),
+                ' ')
+       FROM json_each(CASE WHEN json_valid(c.musicians_json) THEN c.musicians_json ELSE '[]' END) m
+    ) AS musicians,
+    c.notes AS notes
+FROM concerts c;
+
+-- remove_diacritics so "Bjork" finds "Björk" (and vice versa).
+CREATE VIRTUAL TABLE IF NOT EXISTS concerts_fts USING fts5(
+    artist,
+    title,
+    description,
+    set_list,
+    musicians,
+    notes,
+    tokenize = 'unicode61 remove_diacritics 2'
+);
+
+CREATE TRIGGER IF NOT EXISTS concerts_fts_insert
+AFTER INSERT ON concerts
+BEGIN
+    INSERT INTO concerts_fts (rowid, artist, title, description, set_list, musicians, notes)
+    SELECT id, artist, title, description, set_list, musicians, notes
+    FROM concert_search_text WHERE id = NEW.id;
+END;
+
+-- Scoped to the indexed columns so the audit-timestamp trigger's
+-- `UPDATE ... SET updated_at` (migration 0003) and lifecycle writes don't
+-- rewrite the index row on every state transition.
+CREATE TRIGGER IF NOT EXISTS concerts_fts_update
+AFTER UPDATE OF artist, title, description, set_list_json, musicians_json, notes ON concerts
+BEGIN
+    DELETE FROM concerts_fts WHERE rowid = OLD.id;
+    INSERT INTO concerts_fts (rowid, artist, title, description, set_list, musicians, notes)
+    SELECT id, artist, title, description, set_list, musicians, notes
+    FROM concert_search_text WHERE id = NEW.id;
+END;
+
+CREATE TRIGGER IF NOT EXISTS concerts_fts_delete
+AFTER DELETE ON concerts
+BEGIN
+    DELETE FROM concerts_fts WHERE rowid = OLD.id;
+END;
+
+-- Backfill concerts that predate the index. Idempotent: rows already indexed
+-- (by an earlier startup or by the triggers) are skipped.
+INSERT INTO concerts_fts (rowid, artist, title, description, set_list, musicians, notes)
+SELECT id, artist, title, description, set_list, musicians, notes
+FROM concert_search_text
+WHERE id NOT IN (SELECT rowid FROM concerts_fts);
@@ -0,0 +1,14 @@
+-- Followed artists: a new listing whose artist matches one is marked wanted
+-- as it is imported (db::concerts::upsert_listing).
+--
+-- Matching is on normalized_name, the display name folded by
+-- db::followed_artists::normalize_artist (diacritics to ASCII, alphanumerics
+-- only, lowercased), so "Beyoncé", "beyonce" and "BEYONCÉ!" are one follow.
+-- Rows are never edited in place — unfollow deletes — so there is no
+-- updated_at column or audit trigger.
+CREATE TABLE IF NOT EXISTS followed_artists (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    normalized_name TEXT NOT NULL UNIQUE CHECK (normalized_name != ''),
+    inserted_at TEXT NOT NULL DEFAULT (datetime('now'))
+);
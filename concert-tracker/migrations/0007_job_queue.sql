@@ -0,0 +1,18 @@
+-- Accepted Job Runs waiting for a concurrency slot (concert-web's
+-- --max-downloads / --max-splits / --max-archives). A row exists from
+-- acceptance until the run gets its slot and starts executing; the in-memory
+-- JobRegistry queue is authoritative while the server runs, and this table is
+-- what a restart resumes from (db::job_queue, jobs::resume_queued).
+--
+-- kind is JobKind::as_str ("download", "split", "archive"). position orders
+-- runs of one kind, lowest first; reorder rewrites it. payload is whatever
+-- the request needs besides its key to be resubmitted (the split mode and
+-- its timestamps, the archive location), as JSON or NULL.
+CREATE TABLE IF NOT EXISTS job_queue (
+    concert_id INTEGER NOT NULL REFERENCES concerts(id) ON DELETE CASCADE,
+    kind TEXT NOT NULL CHECK (kind IN ('download', 'split', 'archive')),
+    position INTEGER NOT NULL,
+    payload TEXT,
+    inserted_at TEXT NOT NULL DEFAULT (datetime('now')),
+    PRIMARY KEY (concert_id, kind)
+);
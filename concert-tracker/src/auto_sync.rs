@@ -0,0 +1,314 @@
+//! Scheduled month sync: the unattended version of the listing's Sync button.
+//!
+//! `concert-web` runs [`spawn`]'s loop for its whole lifetime. Every
+//! [`TICK`] it re-reads the `settings` row, so schedule edits on the settings
+//! page apply without a restart. When a run is due it syncs the current
+//! month, hands unscraped concerts to the [`ScrapeQueue`], and — if
+//! auto-download is on — waits for those scrapes and then starts a download
+//! with a queued split for every concert of the month that is wanted or by a
+//! followed artist. The outcome is recorded in
+//! `settings.last_auto_sync_at` / `last_auto_sync_status`.
+//!
+//! Only concerts that were never downloaded (and have no failed download) are
+//! picked, so a run never re-fetches or retries on its own: a failure stays on
+//! the Jobs page for the user to act on.
+
+use std::collections::HashSet;
+use std::time::Duration;
+
+use anyhow::Result;
+use chrono::{NaiveDateTime, Utc};
+
+use crate::db;
+use crate::db::followed_artists::{listing_artist, normalize_artist};
+use crate::db::settings::AutoSync;
+use crate::jobs::download::{self, StartOutcome};
+use crate::jobs::{JobKey, JobKind};
+use crate::model::Concert;
+use crate::sync::{concerts_needing_scrape, sync_month, YearMonth};
+use crate::web::AppState;
+
+/// How often the loop checks whether a run is due.
+const TICK: Duration = Duration::from_secs(60);
+
+/// Upper bound on waiting for a run's scrapes before picking downloads; a
+/// concert still unscraped after this is picked up by the next run.
+const SCRAPE_WAIT: Duration = Duration::from_secs(10 * 60);
+
+/// Start the scheduler loop. Fire-and-forget: it lives as long as the runtime.
+pub fn spawn(state: AppState) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(TICK);
+        loop {
+            ticker.tick().await;
+            let auto_sync = {
+                let conn = state.db.lock().unwrap();
+                db::settings::get_settings(&conn).map(|s| s.auto_sync)
+            };
+            let auto_sync = match auto_sync {
+                Ok(auto_sync) => auto_sync,
+                Err(e) => {
+                    tracing::warn!("auto sync: failed to read settings: {e:#}");
+                    continue;
+                }
+            };
+            if !is_due(&auto_sync, Utc::now().naive_utc()) {
+                continue;
+            }
+            let status = match run(&state, &auto_sync).await {
+                Ok(status) => status,
+                Err(e) => {
+                    tracing::warn!("auto sync failed: {e:#}");
+                    format!("failed: {e:#}")
+                }
+            };
+            let conn = state.db.lock().unwrap();
+            if let Err(e) =
+                db::settings::record_auto_sync_run(&conn, &db::time::now_string(), &status)
+            {
+                tracing::warn!("auto sync: failed to record run: {e:#}");
+            }
+        }
+    });
+}
+
+/// Whether the schedule is on and its interval has elapsed since the last run.
+/// A last-run time that doesn't parse counts as never run.
+fn is_due(auto_sync: &AutoSync, now: NaiveDateTime) -> bool {
+    if !auto_sync.enabled() {
+        return false;
+    }
+    let Some(last) = auto_sync
+        .last_run_at
+        .as_deref()
+        .and_then(|at| NaiveDateTime::parse_from_str(at, "%Y-%m-%d %H:%M:%S").ok())
+    else {
+        return true;
+    };
+    now - last >= chrono::Duration::hours(i64::from(auto_sync.interval_hours))
+}
+
+/// One scheduled run. Returns the status line stored in settings.
+async fn run(state: &AppState, auto_sync: &AutoSync) -> Result<String> {
+    let ym = YearMonth::current();
+    tracing::info!("auto sync started for {}/{:02}", ym.year, ym.month);
+
+    let db = state.db.clone();
+    let month = ym.clone();
+    let synced = tokio::task::spawn_blocking(move || {
+        let conn = db.lock().unwrap();
+        sync_month(&conn, &month)
+    })
+    .await??;
+
+    let to_scrape = concerts_needing_scrape(&synced);
+    let scrape_count = to_scrape.len();
+    let queued: Vec<i64> = to_scrape
+        .into_iter()
+        .filter(|(id, url)| state.scrape_queue.enqueue(*id, url.clone()))
+        .map(|(id, _)| id)
+        .collect();
+    let mut status = format!(
+        "{}: {} to scrape, {} queued",
+        ym.display_label(),
+        scrape_count,
+        queued.len()
+    );
+
+    if auto_sync.download {
+        wait_for_scrapes(state, &queued).await;
+        let (candidates, followed) = {
+            let conn = state.db.lock().unwrap();
+            (
+                db::concerts::list_concerts(&conn)?,
+                db::followed_artists::followed_keys(&conn)?,
+            )
+        };
+        let mut started = 0usize;
+        for concert in candidates
+            .iter()
+            .filter(|c| wants_auto_download(c, &ym, &followed))
+        {
+            match start_download_and_split(state, concert.id).await {
+                Ok(StartOutcome::Spawned) => started += 1,
+                Ok(StartOutcome::AlreadyRunning) => {}
+                Err(e) => tracing::warn!(
+                    "auto sync: failed to start download for concert {}: {e:#}",
+                    concert.id
+                ),
+            }
+        }
+        status.push_str(&format!(", {started} download(s) started"));
+    }
+
+    tracing::info!("auto sync completed: {status}");
+    Ok(status)
+}
+
+/// Poll until none of `ids` is pending in the scrape queue, or [`SCRAPE_WAIT`].
+async fn wait_for_scrapes(state: &AppState, ids: &[i64]) {
+    let deadline = tokio::time::Instant::now() + SCRAPE_WAIT;
+    while ids.iter().any(|id| state.scrape_queue.is_pending(*id))
+        && tokio::time::Instant::now() < deadline
+    {
+        tokio::time::sleep(Duration::from_secs(2)).await;
+    }
+}
+
+/// A concert of month `ym` that has metadata, was never downloaded or
+/// attempted, isn't ignored, and is either wanted or by a followed artist.
+/// Follows normally mark new listings wanted on import already; checking the
+/// follow list too picks up listings imported before the follow.
+fn wants_auto_download(concert: &Concert, ym: &YearMonth, followed: &HashSet<String>) -> bool {
+    let in_month = concert
+        .concert_date
+        .as_deref()
+        .and_then(YearMonth::from_date_str)
+        .is_some_and(|month| &month == ym);
+    let untouched = concert.metadata_scraped_at.is_some()
+        && !concert.ignored
+        && concert.download_started_at.is_none()
+        && concert.downloaded_at.is_none()
+        && concert.download_errors.is_empty();
+    in_month && untouched && (concert.wanted || by_followed_artist(concert, followed))
+}
+
+/// Whether the concert's artist (or, before its metadata scrape, its listing
+/// title's artist) is in `followed`, a set of [`normalize_artist`] keys.
+fn by_followed_artist(concert: &Concert, followed: &HashSet<String>) -> bool {
+    let artist = concert
+        .artist
+        .as_deref()
+        .unwrap_or_else(|| listing_artist(&concert.title));
+    followed.contains(&normalize_artist(artist))
+}
+
+/// Start a download with its split queued behind it — the same edge
+/// `jobs::prepare` adds, so the split runs as soon as the download succeeds
+/// and is dropped if it fails.
+async fn start_download_and_split(state: &AppState, concert_id: i64) -> Result<StartOutcome> {
+    let download_key = JobKey {
+        concert_id,
+        kind: JobKind::Download,
+    };
+    let split_key = JobKey {
+        concert_id,
+        kind: JobKind::Split,
+    };
+    state
+        .registry
+        .add_dependent(download_key, split_key.clone());
+    let outcome = download::start_download(
+        state.db.clone(),
+        state.registry.clone(),
+        state.jobs.clone(),
+        concert_id,
+    )
+    .await;
+    if outcome.is_err() {
+        state.registry.drop_dependency_edges(&split_key);
+    }
+    tracing::info!("auto sync: download requested for concert {concert_id}");
+    outcome
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{connection::open_in_memory, tests::seed_with_album};
+
+    fn at(s: &str) -> NaiveDateTime {
+        NaiveDateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S").unwrap()
+    }
+
+    fn schedule(interval_hours: u32, last_run_at: Option<&str>) -> AutoSync {
+        AutoSync {
+            interval_hours,
+            last_run_at: last_run_at.map(str::to_string),
+            ..AutoSync::default()
+        }
+    }
+
+    fn concert() -> Concert {
+        let conn = open_in_memory().unwrap();
+        let id = seed_with_album(&conn);
+        let mut concert = db::concerts::get_concert(&conn, id).unwrap();
+        concert.concert_date = Some("2024-06-01".to_string());
+        concert.artist = Some("The  Band".to_string());
+        concert.metadata_scraped_at = Some("2024-06-02 00:00:00".to_string());
+        concert.download_started_at = None;
+        concert.downloaded_at = None;
+        concert.wanted = false;
+        concert
+    }
+
+    const JUNE: YearMonth = YearMonth {
+        year: 2024,
+        month: 6,
+    };
+
+    #[test]
+    fn is_due_follows_interval_and_off() {
+        let now = at("2024-06-01 12:00:00");
+        assert!(!is_due(&schedule(0, None), now));
+        assert!(is_due(&schedule(6, None), now));
+        assert!(is_due(&schedule(6, Some("garbage")), now));
+        assert!(!is_due(&schedule(6, Some("2024-06-01 07:00:00")), now));
+        assert!(is_due(&schedule(6, Some("2024-06-01 06:00:00")), now));
+    }
+
+    fn follows(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|name| normalize_artist(name)).collect()
+    }
+
+    #[test]
+    fn followed_artists_match_through_the_follow_key() {
+        let c = concert();
+        assert!(by_followed_artist(&c, &follows(&["the band"])));
+        assert!(by_followed_artist(&c, &follows(&["Other", " THE BAND! "])));
+        assert!(!by_followed_artist(&c, &follows(&["The Bandits"])));
+        assert!(!by_followed_artist(&c, &HashSet::new()));
+
+        let mut unscraped = concert();
+        unscraped.artist = None;
+        unscraped.title = "The Band: Tiny Desk Concert".to_string();
+        assert!(by_followed_artist(&unscraped, &follows(&["the band"])));
+    }
+
+    #[test]
+    fn auto_download_picks_wanted_or_followed_untouched_concerts_of_the_month() {
+        let none = HashSet::new();
+        assert!(!wants_auto_download(&concert(), &JUNE, &none));
+        assert!(wants_auto_download(
+            &concert(),
+            &JUNE,
+            &follows(&["The Band"])
+        ));
+        let wanted = Concert {
+            wanted: true,
+            ..concert()
+        };
+        assert!(wants_auto_download(&wanted, &JUNE, &none));
+
+        let other_month = YearMonth {
+            year: 2024,
+            month: 7,
+        };
+        assert!(!wants_auto_download(&wanted, &other_month, &none));
+        let ignored = Concert {
+            ignored: true,
+            ..wanted.clone()
+        };
+        assert!(!wants_auto_download(&ignored, &JUNE, &none));
+        let downloaded = Concert {
+            downloaded_at: Some("2024-06-03 00:00:00".to_string()),
+            ..wanted.clone()
+        };
+        assert!(!wants_auto_download(&downloaded, &JUNE, &none));
+        let unscraped = Concert {
+            metadata_scraped_at: None,
+            ..wanted
+        };
+        assert!(!wants_auto_download(&unscraped, &JUNE, &none));
+    }
+}
@@ -12,6 +12,7 @@ use concert_tracker::jobs::{
     SplitTarget,
 };
 use concert_tracker::model::{sanitize_album, Concert};
+use concert_tracker::playlist_file::{self, PathStyle, PlaylistFormat};
 use concert_tracker::scan::scan;
 use concert_tracker::scrape::{ensure_thumbnail, scrape_url, ThumbOutcome};
 use concert_tracker::sync::{sync_months, YearMonth};
@@ -65,10 +66,35 @@ enum Command {
         #[arg(long, default_value = "all")]
         filter: String,
     },
+    /// Full-text search across artist, title, description, set list,
+    /// musicians and notes
+    Search {
+        /// Words to search for; each is matched as a prefix, all must match
+        query: Vec<String>,
+        #[arg(long, default_value_t = db::search::DEFAULT_SEARCH_LIMIT)]
+        limit: usize,
+    },
+    /// Export or import playlist files (M3U8/XSPF)
+    Playlist {
+        #[command(subcommand)]
+        command: PlaylistCommand,
+    },
     /// Toggle ignored flag on a concert
     Ignore { id: i64 },
     /// Toggle wanted flag on a concert
     Want { id: i64 },
+    /// Follow an artist: their new listings are marked wanted when synced
+    Follow {
+        #[arg(required = true)]
+        name: Vec<String>,
+    },
+    /// Stop following an artist
+    Unfollow {
+        #[arg(required = true)]
+        name: Vec<String>,
+    },
+    /// List followed artists
+    Following,
     /// Clear stale in-progress download/split flags
     ResetInProgress,
     /// Reset stale download errors on downloads that were deleted after erroring
@@ -129,6 +155,31 @@ enum Command {
     },
 }
 
+#[derive(Subcommand)]
+enum PlaylistCommand {
+    /// Write a playlist as M3U8 or XSPF. Tracks with no split file are left
+    /// out (and counted on stderr).
+    Export {
+        id: i64,
+        /// m3u8 or xspf
+        #[arg(long, default_value = "m3u8")]
+        format: String,
+        /// relative (to <workdir>/concerts) or absolute
+        #[arg(long, default_value = "relative")]
+        paths: String,
+        /// Output file; stdout when omitted
+        #[arg(long, short)]
+        output: Option<PathBuf>,
+    },
+    /// Create a playlist from an M3U/M3U8 file, matching entries to split tracks
+    Import {
+        file: PathBuf,
+        /// Playlist name; defaults to the file's #PLAYLIST: line, then its file name
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
 fn main() -> Result<()> {
     let cli = Cli::parse();
     // Apply the proxy setting before any scrape builds an HTTP client.
@@ -205,6 +256,71 @@ fn main() -> Result<()> {
             }
         }
 
+        Command::Search { query, limit } => {
+            let Some(query) = db::search::SearchQuery::parse(&query.join(" ")) else {
+                anyhow::bail!("search query must contain at least one word");
+            };
+            for hit in db::search::search_concerts(&conn, &query, limit)? {
+                let c = &hit.concert;
+                println!(
+                    "[{}] {} | {} | {}",
+                    c.id,
+                    c.title,
+                    c.concert_date.as_deref().unwrap_or("-"),
+                    hit.snippet_text()
+                );
+            }
+        }
+
+        Command::Playlist { command } => match command {
+            PlaylistCommand::Export {
+                id,
+                format,
+                paths,
+                output,
+            } => {
+                let exported = playlist_file::export_playlist(
+                    &conn,
+                    &cli.workdir,
+                    id,
+                    PlaylistFormat::parse(&format)?,
+                    PathStyle::parse(&paths)?,
+                )?;
+                match output {
+                    Some(path) => std::fs::write(&path, &exported.body)
+                        .with_context(|| format!("writing {}", path.display()))?,
+                    None => print!("{}", exported.body),
+                }
+                if exported.skipped > 0 {
+                    eprintln!(
+                        "Skipped {} track(s) with no split file on disk",
+                        exported.skipped
+                    );
+                }
+            }
+            PlaylistCommand::Import { file, name } => {
+                let contents = std::fs::read_to_string(&file)
+                    .with_context(|| format!("reading {}", file.display()))?;
+                // The file name is the last resort, after --name and the
+                // file's own #PLAYLIST: directive (which import_m3u reads).
+                let has_directive = contents.lines().any(|l| l.starts_with("#PLAYLIST:"));
+                let name = name.or_else(|| {
+                    (!has_directive)
+                        .then(|| file.file_stem().map(|s| s.to_string_lossy().into_owned()))
+                        .flatten()
+                });
+                let report =
+                    playlist_file::import_m3u(&conn, &cli.workdir, name.as_deref(), &contents)?;
+                println!(
+                    "Created playlist {} with {} track(s)",
+                    report.playlist_id, report.added
+                );
+                for entry in &report.unresolved {
+                    eprintln!("Unresolved: {entry}");
+                }
+            }
+        },
+
         Command::Ignore { id } => {
             db::concerts::toggle_ignored(&conn, id)?;
             println!("Toggled ignored for concert {}", id);
@@ -215,6 +331,29 @@ fn main() -> Result<()> {
             println!("Toggled wanted for concert {}", id);
         }
 
+        Command::Follow { name } => {
+            let name = name.join(" ");
+            if db::followed_artists::follow(&conn, &name)? {
+                println!("Following {name}");
+            } else {
+                println!("Already following {name}");
+            }
+        }
+
+        Command::Unfollow { name } => {
+            let name = name.join(" ");
+            if !db::followed_artists::unfollow_name(&conn, &name)? {
+                anyhow::bail!("not following {name}");
+            }
+            println!("Unfollowed {name}");
+        }
+
+        Command::Following => {
+            for artist in db::followed_artists::list_followed(&conn)? {
+                println!("{}\t(since {})", artist.name, artist.inserted_at);
+            }
+        }
+
         Command::ResetInProgress => {
             let count = concert_tracker::lifecycle::reset_in_progress(&conn)?;
             println!("Cleared {} stale in-progress rows", count);
@@ -6,7 +6,8 @@ use std::sync::{Arc, Mutex};
 
 use concert_tracker::db;
 use concert_tracker::jobs::{
-    check_dependencies, resolve_splitter_cli, JobConfig, JobRegistry, SplitTarget, SplitterCli,
+    check_dependencies, resolve_splitter_cli, JobConfig, JobLimits, JobRegistry, RetryPolicy,
+    SplitTarget, SplitterCli,
 };
 #[cfg(feature = "test-control")]
 use concert_tracker::test_control::job_driver::{JobDriver, TestControlJobRunner};
@@ -98,6 +99,31 @@ struct Cli {
     #[arg(long, default_value_t = false, conflicts_with = "no_proxy")]
     proxy_from_env: bool,
 
+    /// How many downloads (yt-dlp processes) run at once; further accepted
+    /// downloads wait in a queue shown on the Jobs page. 0 means unlimited.
+    #[arg(long, default_value_t = 2)]
+    max_downloads: usize,
+
+    /// How many splits run at once. Each split is CPU-heavy (OCR over the
+    /// whole video), so the default runs one at a time. 0 means unlimited.
+    #[arg(long, default_value_t = 1)]
+    max_splits: usize,
+
+    /// How many archive moves run at once. 0 means unlimited.
+    #[arg(long, default_value_t = 1)]
+    max_archives: usize,
+
+    /// How many times a download is attempted in total when it fails
+    /// transiently (CDN errors, timeouts, yt-dlp extractor breakage). Permanent
+    /// failures such as a 404 are never retried. 1 disables retries.
+    #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u32).range(1..))]
+    download_attempts: u32,
+
+    /// Seconds to wait before the first download retry; each later retry
+    /// waits twice as long as the one before, up to 15 minutes.
+    #[arg(long, default_value_t = 30)]
+    download_retry_delay: u64,
+
     /// Dev mode: serve static/*.js from disk (no recompile needed for JS edits)
     /// and inject a livereload script so the browser auto-refreshes whenever
     /// this process restarts (e.g. under `just dev` / cargo-watch). Templates
@@ -205,6 +231,12 @@ async fn main() -> Result<()> {
     let db = Arc::new(Mutex::new(conn));
     let workdir = cli.workdir;
 
+    let download_retry = RetryPolicy {
+        max_attempts: cli.download_attempts,
+        initial_delay: std::time::Duration::from_secs(cli.download_retry_delay),
+        max_delay: std::time::Duration::from_secs(15 * 60),
+    };
+
     // The Job Driver and Scrape Driver only replace their production
     // counterparts when the Test Control API is actually going to be started
     // (feature compiled in AND --test-control-port passed) — a test-control
@@ -236,23 +268,42 @@ async fn main() -> Result<()> {
         }
         None => (
             concert_tracker::jobs::scrape_queue::ScrapeQueue::start(db.clone(), workdir.clone()),
-            JobConfig::production(workdir.clone(), split_target, cli.open_cmd.clone()),
+            JobConfig::production(workdir.clone(), split_target, cli.open_cmd.clone())
+                .with_download_retry(download_retry),
             None,
         ),
     };
     #[cfg(not(feature = "test-control"))]
     let (scrape_queue, jobs) = (
         concert_tracker::jobs::scrape_queue::ScrapeQueue::start(db.clone(), workdir.clone()),
-        JobConfig::production(workdir.clone(), split_target, cli.open_cmd.clone()),
+        JobConfig::production(workdir.clone(), split_target, cli.open_cmd.clone())
+            .with_download_retry(download_retry),
     );
 
+    let limit = |max: usize| (max > 0).then_some(max);
     let state = AppState {
         db,
-        registry: Arc::new(JobRegistry::new()),
+        registry: Arc::new(JobRegistry::with_limits(JobLimits {
+            download: limit(cli.max_downloads),
+            split: limit(cli.max_splits),
+            archive: limit(cli.max_archives),
+        })),
         jobs,
         scrape_queue,
     };
 
+    // Jobs that were waiting for a slot when the last process stopped keep
+    // their place: resubmit them before the router accepts new work.
+    let resumed = concert_tracker::jobs::resume_queued(
+        state.db.clone(),
+        state.registry.clone(),
+        state.jobs.clone(),
+    )
+    .await?;
+    if resumed > 0 {
+        tracing::info!("resumed {} queued job(s)", resumed);
+    }
+
     // Bound to a top-level `main` local (not `_ = ...`) so the handle outlives
     // this statement: dropping a jsonrpsee `ServerHandle` stops that server.
     #[cfg(feature = "test-control")]
@@ -274,6 +325,9 @@ async fn main() -> Result<()> {
         None => None,
     };
 
+    // Scheduled month sync; idles until enabled on the settings page.
+    concert_tracker::auto_sync::spawn(state.clone());
+
     let app = router_with_opts(state.clone(), RouterOpts { dev: cli.dev });
     let addr = SocketAddr::from((cli.host, cli.port));
     let listener = tokio::net::TcpListener::bind(addr).await?;
@@ -0,0 +1,138 @@
+//! Measures splitter boundary accuracy against user-corrected timestamps (see
+//! `concert_tracker::split_eval`).
+//!
+//! ```sh
+//! split-eval export --out corpus.json
+//! split-eval run --corpus corpus.json --save baseline.json
+//! # ...change the splitter...
+//! split-eval run --corpus corpus.json --baseline baseline.json
+//! ```
+//!
+//! Detection frames are kept under `temp_frames/` in the current directory and
+//! reused by later runs, so run it from the same directory each time.
+
+use anyhow::{Context, Result};
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::path::PathBuf;
+
+use concert_tracker::db;
+use concert_tracker::split_eval::{self, EvalSource, DEFAULT_TOLERANCE_SECONDS};
+
+#[derive(Parser)]
+#[command(
+    name = "split-eval",
+    about = "Evaluate split boundaries against user-corrected timestamps"
+)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Write every concert with user-corrected timestamps to a corpus file
+    Export {
+        #[arg(long, default_value = "concerts.db")]
+        db: PathBuf,
+        /// Corpus file to write
+        #[arg(long, short, default_value = "split-eval-corpus.json")]
+        out: PathBuf,
+    },
+    /// Score a corpus and print a table of the errors
+    Run {
+        #[arg(long, default_value = "split-eval-corpus.json")]
+        corpus: PathBuf,
+        /// Working directory where downloaded media lives.
+        #[arg(long, default_value = ".")]
+        workdir: PathBuf,
+        /// Score the automated timestamps stored in the corpus instead of
+        /// re-running detection.
+        #[arg(long)]
+        stored: bool,
+        /// Only these concert ids (repeatable).
+        #[arg(long = "id")]
+        ids: Vec<i64>,
+        /// A start within this many seconds of the corrected one matches.
+        #[arg(long, default_value_t = DEFAULT_TOLERANCE_SECONDS)]
+        tolerance: f64,
+        /// Report regressions against this saved report.
+        #[arg(long)]
+        baseline: Option<PathBuf>,
+        /// Save the report as JSON (e.g. as the next baseline).
+        #[arg(long)]
+        save: Option<PathBuf>,
+        /// Exit with status 1 when there are regressions against the baseline.
+        #[arg(long, requires = "baseline")]
+        fail_on_regression: bool,
+    },
+}
+
+fn main() -> Result<()> {
+    // Splitter warnings go to stderr so the report on stdout stays clean.
+    tracing_subscriber::fmt()
+        .with_env_filter(
+            tracing_subscriber::EnvFilter::try_from_default_env()
+                .unwrap_or_else(|_| tracing_subscriber::EnvFilter::new("info")),
+        )
+        .with_writer(std::io::stderr)
+        .init();
+
+    let cli = Cli::parse();
+    match cli.command {
+        Command::Export { db, out } => {
+            let conn = db::connection::open(&db)?;
+            let corpus = split_eval::export_corpus(&conn)?;
+            fs::write(&out, serde_json::to_string_pretty(&corpus)?)
+                .with_context(|| format!("Failed to write {}", out.display()))?;
+            println!(
+                "Exported {} concerts to {}",
+                corpus.cases.len(),
+                out.display()
+            );
+        }
+
+        Command::Run {
+            corpus,
+            workdir,
+            stored,
+            ids,
+            tolerance,
+            baseline,
+            save,
+            fail_on_regression,
+        } => {
+            let mut corpus = split_eval::read_corpus(&corpus)?;
+            if !ids.is_empty() {
+                corpus.cases.retain(|case| ids.contains(&case.concert_id));
+            }
+            let baseline = baseline
+                .as_deref()
+                .map(split_eval::read_report)
+                .transpose()?;
+            let source = if stored {
+                EvalSource::Stored
+            } else {
+                EvalSource::Detect
+            };
+            let report = split_eval::evaluate(&corpus, &workdir, source, tolerance, &mut |line| {
+                eprintln!("{}", line)
+            });
+            let regressions = baseline
+                .as_ref()
+                .map(|baseline| split_eval::regressions(baseline, &report));
+            print!(
+                "{}",
+                split_eval::render_table(&report, regressions.as_deref())
+            );
+            if let Some(path) = &save {
+                fs::write(path, serde_json::to_string_pretty(&report)?)
+                    .with_context(|| format!("Failed to write {}", path.display()))?;
+            }
+            if fail_on_regression && regressions.is_some_and(|r| !r.is_empty()) {
+                std::process::exit(1);
+            }
+        }
+    }
+    Ok(())
+}
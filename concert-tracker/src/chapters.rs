@@ -0,0 +1,154 @@
+//! Chaptered copy of a concert's downloaded source: the concert-detail
+//! "Write chapters" action. Remuxes the source (no re-encode) with one chapter
+//! per song and interlude from the stored split timestamps, so it runs without
+//! a split job. The file lands next to the source in the concert dir as
+//! `<album>.chapters.<ext>` — the same name the splitter's `--chapters` option
+//! publishes, so either path replaces the other's output.
+
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use concert_types::SongTimestamp;
+use live_set_splitter::chapters::{
+    chaptered_file_name, chaptered_file_stem, chapters_for, write_chaptered_copy,
+};
+
+use crate::concert_media::find_downloaded_file;
+use crate::model::{concert_dir, Concert};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WriteChaptersOutcome {
+    Written { filename: String },
+    NoSource,
+    NoTimestamps,
+}
+
+/// Filename of an existing chaptered copy in the concert dir, if any. Matched
+/// by prefix because the extension follows whatever container the source was.
+pub fn find_chapters_file(working_dir: &Path, concert: &Concert) -> Option<String> {
+    let album = concert.album.as_deref()?;
+    let info = crate::jobs::split::build_concert_info(concert);
+    let prefix = format!("{}.", chaptered_file_stem(&info));
+    let mut names: Vec<String> = std::fs::read_dir(concert_dir(working_dir, album))
+        .ok()?
+        .flatten()
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .filter(|name| name.starts_with(&prefix) && name.len() > prefix.len())
+        .collect();
+    names.sort();
+    names.into_iter().next()
+}
+
+/// Write (or overwrite) the chaptered copy. `timestamps` are the effective
+/// split timestamps (user, else auto); interlude chapters need the concert's
+/// persisted `media_duration` and are omitted without it. Blocking — call from
+/// `spawn_blocking`.
+///
+/// The remux goes to a hidden temp file in the concert dir and is renamed into
+/// place, all under the shared publication lock so it never interleaves with a
+/// Concert Split publishing into the same directory.
+pub fn write_chapters(
+    working_dir: &Path,
+    concert: &Concert,
+    timestamps: Option<&[SongTimestamp]>,
+) -> Result<WriteChaptersOutcome> {
+    let Some(source) = concert
+        .album
+        .as_deref()
+        .and_then(|album| find_downloaded_file(working_dir, album))
+    else {
+        return Ok(WriteChaptersOutcome::NoSource);
+    };
+    let Some(timestamps) = timestamps.filter(|ts| !ts.is_empty()) else {
+        return Ok(WriteChaptersOutcome::NoTimestamps);
+    };
+    let info = crate::jobs::split::build_concert_info(concert);
+    let filename = chaptered_file_name(&info, &source);
+    let chapters = chapters_for(timestamps, concert.media_duration);
+    let dir = source
+        .parent()
+        .context("downloaded source has no parent directory")?
+        .to_path_buf();
+
+    live_set_splitter::publication::with_shared_lock(&dir, || {
+        let extension = source
+            .extension()
+            .and_then(|ext| ext.to_str())
+            .unwrap_or("mkv");
+        let staged = tempfile::Builder::new()
+            .prefix(".chapters-")
+            .suffix(&format!(".{extension}"))
+            .tempfile_in(&dir)
+            .context("Failed to create chaptered copy temp file")?;
+        write_chaptered_copy(&source, staged.path(), &info, &chapters)?;
+        staged
+            .persist(dir.join(&filename))
+            .with_context(|| format!("Failed to move chaptered copy into place: {filename}"))?;
+        Ok(())
+    })?;
+    tracing::info!(
+        concert_id = concert.id,
+        file = %filename,
+        chapters = chapters.len(),
+        "wrote chaptered copy"
+    );
+    Ok(WriteChaptersOutcome::Written { filename })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::{self, connection::open_in_memory, tests::seed_with_album};
+
+    fn seeded() -> Concert {
+        let conn = open_in_memory().unwrap();
+        let id = seed_with_album(&conn);
+        db::concerts::get_concert(&conn, id).unwrap()
+    }
+
+    fn ts() -> Vec<SongTimestamp> {
+        vec![SongTimestamp {
+            title: "Song A".to_string(),
+            start_time: 0.0,
+            end_time: 10.0,
+            duration: 10.0,
+            boundary: None,
+        }]
+    }
+
+    #[test]
+    fn write_chapters_requires_a_downloaded_source() {
+        let wd = tempfile::tempdir().unwrap();
+        let outcome = write_chapters(wd.path(), &seeded(), Some(&ts())).unwrap();
+        assert_eq!(outcome, WriteChaptersOutcome::NoSource);
+    }
+
+    #[test]
+    fn write_chapters_requires_timestamps() {
+        let wd = tempfile::tempdir().unwrap();
+        let dir = concert_dir(wd.path(), "Test Album");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Test Album.mp4"), b"media").unwrap();
+        let concert = seeded();
+        let outcome = write_chapters(wd.path(), &concert, None).unwrap();
+        assert_eq!(outcome, WriteChaptersOutcome::NoTimestamps);
+        let outcome = write_chapters(wd.path(), &concert, Some(&[])).unwrap();
+        assert_eq!(outcome, WriteChaptersOutcome::NoTimestamps);
+    }
+
+    #[test]
+    fn find_chapters_file_matches_any_container_but_not_the_source() {
+        let wd = tempfile::tempdir().unwrap();
+        let dir = concert_dir(wd.path(), "Test Album");
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("Test Album.mp4"), b"media").unwrap();
+        let concert = seeded();
+        assert_eq!(find_chapters_file(wd.path(), &concert), None);
+
+        std::fs::write(dir.join("Test Album.chapters.webm"), b"media").unwrap();
+        assert_eq!(
+            find_chapters_file(wd.path(), &concert).as_deref(),
+            Some("Test Album.chapters.webm")
+        );
+    }
+}
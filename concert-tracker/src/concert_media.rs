@@ -16,9 +16,16 @@
 //! (scraping, archiving, downloading).
 
 use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
 
+use live_set_splitter::concert_split::AudioFormat;
+use live_set_splitter::cue::{self, CueSheet};
+use live_set_splitter::cut::Rendition;
+use live_set_splitter::{hls, poster};
+
+use crate::db::settings::VideoQuality;
 use crate::model::{
-    concert_dir, is_browser_playable, is_track_available, sanitize_filename, PlaybackItem,
+    concert_dir, is_browser_playable, is_track_available, sanitize_filename, Clip, PlaybackItem,
     PlaybackItemKind, TrackDetailItem, TrackInfo,
 };
 
@@ -26,12 +33,17 @@ pub fn is_video_extension(ext: &str) -> bool {
     matches!(ext.to_lowercase().as_str(), "mp4" | "webm")
 }
 
+/// Extensions probed when looking for a song's track file (priority order):
+/// the splitter's video and `.m4a`, then its smaller Opus/MP3 encodes ahead of
+/// FLAC so playback streams the lighter file, then anything else playable.
+pub const TRACK_EXTENSIONS: &[&str] = &[
+    "mp4", "m4a", "opus", "mp3", "flac", "webm", "ogg", "wav", "mkv",
+];
+
 pub fn find_track_file(working_dir: &Path, album: &str, title: &str) -> Option<String> {
     let stem = sanitize_filename(title);
     let dir = concert_dir(working_dir, album);
-    for ext in &[
-        "mp4", "m4a", "webm", "mp3", "ogg", "opus", "wav", "flac", "mkv",
-    ] {
+    for ext in TRACK_EXTENSIONS {
         let filename = format!("{stem}.{ext}");
         if dir.join(&filename).exists() {
             return Some(filename);
@@ -40,17 +52,27 @@ pub fn find_track_file(working_dir: &Path, album: &str, title: &str) -> Option<S
     None
 }
 
-/// Extensions probed when looking for or deleting interlude files (priority order).
-pub const INTERLUDE_EXTENSIONS: &[&str] = &["mp4", "m4a"];
+/// Extensions the splitter writes for a track (every `--audio-format`, the
+/// video and its `--renditions`), probed when looking for or deleting song and interlude files
+/// (priority order: the video, then the audio formats with FLAC last, as in
+/// [`TRACK_EXTENSIONS`]).
+pub static SPLIT_TRACK_EXTENSIONS: LazyLock<Vec<&'static str>> = LazyLock::new(|| {
+    let mut audio = AudioFormat::ALL;
+    audio.sort_by_key(|format| *format == AudioFormat::Flac);
+    std::iter::once("mp4")
+        .chain(audio.map(AudioFormat::extension))
+        .chain(Rendition::ALL.map(Rendition::extension))
+        .collect()
+});
 
 /// Return the filename (stem + extension) of the interlude file for `index` if
-/// it exists on disk, or `None`. Probes `.mp4` then `.m4a`. Uses
+/// it exists on disk, or `None`. Probes [`SPLIT_TRACK_EXTENSIONS`] in order. Uses
 /// [`concert_types::interlude_filename_stem`] so the name always matches what
 /// the splitter writes.
 pub fn find_interlude_track_file(working_dir: &Path, album: &str, index: usize) -> Option<String> {
     let stem = concert_types::interlude_filename_stem(index);
     let dir = concert_dir(working_dir, album);
-    for ext in INTERLUDE_EXTENSIONS {
+    for ext in SPLIT_TRACK_EXTENSIONS.iter() {
         let filename = format!("{stem}.{ext}");
         if dir.join(&filename).exists() {
             return Some(filename);
@@ -59,13 +81,252 @@ pub fn find_interlude_track_file(working_dir: &Path, album: &str, index: usize)
     None
 }
 
-/// Return true if an interlude file for `index` exists on disk (either `.mp4`
-/// or `.m4a`). Uses [`concert_types::interlude_filename_stem`] so the name
+/// Return true if an interlude file for `index` exists on disk (any of
+/// [`SPLIT_TRACK_EXTENSIONS`]). Uses [`concert_types::interlude_filename_stem`] so the name
 /// always matches what the splitter writes.
 pub fn find_interlude_file(working_dir: &Path, album: &str, index: usize) -> bool {
     find_interlude_track_file(working_dir, album, index).is_some()
 }
 
+// ── Video renditions ─────────────────────────────────────────────────────────
+
+/// The downscaled video renditions (`<stem>.<height>p.mp4`, written by the
+/// splitter's `--renditions`) playback may stream under the configured
+/// [`VideoQuality`], loaded once per request.
+pub struct VideoRenditions {
+    dir: PathBuf,
+    /// Published rendition heights within the quality cap, tallest first.
+    heights: Vec<u32>,
+}
+
+impl VideoRenditions {
+    pub fn load(working_dir: &Path, album: &str, quality: VideoQuality) -> Self {
+        let dir = concert_dir(working_dir, album);
+        let heights = match quality {
+            VideoQuality::Original => Vec::new(),
+            VideoQuality::AtMost(cap) => {
+                match live_set_splitter::publication::published_renditions(&dir) {
+                    Ok(heights) => heights
+                        .into_iter()
+                        .filter(|height| *height <= cap.height())
+                        .collect(),
+                    Err(error) => {
+                        tracing::warn!(dir = %dir.display(), %error, "ignoring unreadable split manifest");
+                        Vec::new()
+                    }
+                }
+            }
+        };
+        VideoRenditions { dir, heights }
+    }
+
+    /// The file to stream for the video track `filename`: its tallest
+    /// rendition within the cap that exists on disk, else `filename` itself.
+    pub fn for_file(&self, filename: &str) -> String {
+        if let Some(stem) = filename.strip_suffix(".mp4") {
+            for height in &self.heights {
+                let rendition = format!("{stem}.{height}p.mp4");
+                if self.dir.join(&rendition).is_file() {
+                    return rendition;
+                }
+            }
+        }
+        filename.to_string()
+    }
+}
+
+// ── HLS package ──────────────────────────────────────────────────────────────
+
+/// Filename of the concert's HLS master playlist (`{album}.hls.m3u8`, written
+/// by the splitter's `--hls`) when it exists.
+pub fn find_hls_playlist(working_dir: &Path, album: &str) -> Option<String> {
+    let name = format!(
+        "{}.{}.m3u8",
+        crate::model::sanitize_album(album),
+        hls::HLS_INFIX
+    );
+    concert_dir(working_dir, album)
+        .join(&name)
+        .is_file()
+        .then_some(name)
+}
+
+/// `playlist` of the concert's HLS package narrowed to the date range
+/// `range_id` (a song or interlude). The master playlist passes the range on
+/// to its variants; a variant keeps only the segments covering the range, with
+/// their URIs under `/concert-files/`. `None` for a name outside the package,
+/// an unknown range or an unreadable playlist.
+pub fn hls_range_playlist(
+    working_dir: &Path,
+    album: &str,
+    playlist: &str,
+    range_id: &str,
+) -> Option<String> {
+    let sanitized_album = crate::model::sanitize_album(album);
+    let prefix = format!("{sanitized_album}.{}.", hls::HLS_INFIX);
+    let valid_range = !range_id.is_empty()
+        && range_id
+            .chars()
+            .all(|ch| ch.is_ascii_alphanumeric() || ch == '-');
+    if !valid_range
+        || !playlist.starts_with(&prefix)
+        || !playlist.ends_with(".m3u8")
+        || playlist.contains(['/', '\\'])
+    {
+        return None;
+    }
+    let text = std::fs::read_to_string(concert_dir(working_dir, album).join(playlist)).ok()?;
+    if find_hls_playlist(working_dir, album).as_deref() == Some(playlist) {
+        return Some(hls::with_variant_query(&text, &format!("range={range_id}")));
+    }
+    let mut clipped = match hls::MediaPlaylist::parse(&text) {
+        Ok(media) => media.clipped(range_id)?,
+        Err(error) => {
+            tracing::warn!(album, playlist, %error, "ignoring unreadable HLS playlist");
+            return None;
+        }
+    };
+    let served = |uri: &str| format!("/concert-files/{sanitized_album}/{uri}");
+    clipped.init_uri = served(&clipped.init_uri);
+    for segment in &mut clipped.segments {
+        segment.uri = served(&segment.uri);
+    }
+    Some(clipped.render())
+}
+
+// ── Poster frames ────────────────────────────────────────────────────────────
+
+/// `/concert-files/` URL of `name` in the concert directory, when it exists.
+fn concert_file_url(working_dir: &Path, album: &str, name: &str) -> Option<String> {
+    concert_dir(working_dir, album)
+        .join(name)
+        .is_file()
+        .then(|| {
+            format!(
+                "/concert-files/{}/{}",
+                crate::model::sanitize_album(album),
+                name
+            )
+        })
+}
+
+/// URL of the song `title`'s poster frame (`{title}.poster.jpg`, written by
+/// the splitter's `--posters`) when it exists.
+pub fn poster_url(working_dir: &Path, album: &str, title: &str) -> Option<String> {
+    concert_file_url(working_dir, album, &poster::poster_file_name(title))
+}
+
+/// URL of the concert's contact sheet (`{album}.contact.jpg`) when it exists.
+pub fn contact_sheet_url(working_dir: &Path, album: &str) -> Option<String> {
+    let name = format!(
+        "{}.{}.jpg",
+        crate::model::sanitize_album(album),
+        poster::CONTACT_SHEET_INFIX
+    );
+    concert_file_url(working_dir, album, &name)
+}
+
+/// Fill in each track's [`TrackInfo::poster_url`] from disk.
+pub fn attach_posters(working_dir: &Path, album: &str, tracks: &mut [TrackInfo]) {
+    for track in tracks {
+        track.poster_url = poster_url(working_dir, album, &track.title);
+    }
+}
+
+// ── Cue-indexed album ────────────────────────────────────────────────────────
+
+/// The concert's single-file album (`{album}.album.{flac,m4a}`, written by the
+/// splitter's `--album`) and the CUE sheet indexing its songs.
+#[derive(Debug, Clone)]
+pub struct CueAlbum {
+    /// Filename of the album within the concert directory.
+    pub filename: String,
+    pub sheet: CueSheet,
+}
+
+impl CueAlbum {
+    /// Where `set_list[index]` plays in the album. A sheet that no longer
+    /// matches the set list (edited since the split) indexes nothing.
+    pub fn song_clip(&self, set_list: &[String], index: usize) -> Option<Clip> {
+        // The splitter writes `"` as `'` in CUE values.
+        let matches_set_list = self.sheet.tracks.len() == set_list.len()
+            && self
+                .sheet
+                .tracks
+                .iter()
+                .zip(set_list)
+                .all(|(track, title)| track.title == title.replace('"', "'"));
+        if !matches_set_list {
+            return None;
+        }
+        let (start_time, end_time) = self.sheet.track_span(index)?;
+        Some(Clip {
+            start_time,
+            end_time,
+        })
+    }
+}
+
+/// Find the concert's cue-indexed album: `{sanitize_album(album)}.album.cue`
+/// whose `FILE` exists beside it. An unreadable sheet is logged and ignored.
+pub fn find_cue_album(working_dir: &Path, album: &str) -> Option<CueAlbum> {
+    let dir = concert_dir(working_dir, album);
+    let cue_name = format!(
+        "{}.{}.{}",
+        crate::model::sanitize_album(album),
+        cue::ALBUM_INFIX,
+        cue::CUE_EXTENSION
+    );
+    let text = std::fs::read_to_string(dir.join(&cue_name)).ok()?;
+    let sheet = match CueSheet::parse(&text) {
+        Ok(sheet) => sheet,
+        Err(error) => {
+            tracing::warn!(album, %error, "ignoring unreadable CUE sheet");
+            return None;
+        }
+    };
+    // Only a file beside the sheet: the name is served under the concert dir.
+    let is_bare_name =
+        Path::new(&sheet.file).file_name().and_then(|n| n.to_str()) == Some(sheet.file.as_str());
+    if !is_bare_name || !dir.join(&sheet.file).is_file() {
+        return None;
+    }
+    Some(CueAlbum {
+        filename: sheet.file.clone(),
+        sheet,
+    })
+}
+
+/// Where `set_list[index]` plays from: its own track file, else its clip of
+/// `cue_album`.
+fn song_source(
+    working_dir: &Path,
+    album: &str,
+    cue_album: Option<&CueAlbum>,
+    set_list: &[String],
+    index: usize,
+) -> Option<(String, Option<Clip>)> {
+    let title = set_list.get(index)?;
+    if let Some(filename) = find_track_file(working_dir, album, title) {
+        return Some((filename, None));
+    }
+    let cue_album = cue_album?;
+    let clip = cue_album.song_clip(set_list, index)?;
+    Some((cue_album.filename.clone(), Some(clip)))
+}
+
+/// The file `set_list[index]` plays from and, when that is the cue-indexed
+/// album rather than a track file of its own, the clip of it to play.
+pub fn find_track_source(
+    working_dir: &Path,
+    album: &str,
+    set_list: &[String],
+    index: usize,
+) -> Option<(String, Option<Clip>)> {
+    let cue_album = find_cue_album(working_dir, album);
+    song_source(working_dir, album, cue_album.as_ref(), set_list, index)
+}
+
 /// Extensions probed when looking for the downloaded source file.
 const DOWNLOADED_MEDIA_EXTENSIONS: &[&str] = &[
     "mp4", "m4a", "webm", "mkv", "mp3", "ogg", "opus", "wav", "flac",
@@ -143,7 +404,9 @@ pub fn source_redundant(
 // ── Reconstruction playback ──────────────────────────────────────────────────
 
 /// Build the time-ordered playback sequence for whole-concert reconstruction
-/// (source file absent, tracks + interlude files present).
+/// (source file absent, tracks + interlude files present). Songs and interludes
+/// without a file of their own play their span of the cue-indexed album, when
+/// there is one.
 ///
 /// When `user_timestamps` is `None`, falls back to songs-only order (no
 /// interludes) — mirrors the case before the user has ever adjusted timestamps.
@@ -164,20 +427,43 @@ pub fn build_reconstruction(
     user_timestamps: Option<&[concert_types::SongTimestamp]>,
     media_duration: Option<f64>,
 ) -> Vec<PlaybackItem> {
+    let cue_album = find_cue_album(working_dir, album);
+    let cue_album = cue_album.as_ref();
+
     // No-user-ts fallback: songs only, no interludes.
     let Some(songs) = user_timestamps else {
-        return songs_only(working_dir, album, set_list, tracks_present, tracks_liked);
+        return songs_only(
+            working_dir,
+            album,
+            cue_album,
+            set_list,
+            tracks_present,
+            tracks_liked,
+        );
     };
 
     let duration = match media_duration {
         Some(d) if d > 0.0 => d,
         _ => {
             // Can't derive interludes without a known duration; fall back to songs only.
-            return songs_only(working_dir, album, set_list, tracks_present, tracks_liked);
+            return songs_only(
+                working_dir,
+                album,
+                cue_album,
+                set_list,
+                tracks_present,
+                tracks_liked,
+            );
         }
     };
 
     let interludes = concert_types::derive_interludes(songs, duration);
+    // The CUE sheet keeps the tail in the last track; here the tail interlude
+    // is an item of its own, so an album clip of the last song stops before it.
+    let tail_start = interludes
+        .last()
+        .filter(|il| songs.iter().all(|ts| ts.start_time < il.start_time))
+        .map(|il| il.start_time);
 
     // Build a unified list of (start_time, slot) merged in time order.
     // Songs and interludes are already non-overlapping by construction.
@@ -195,16 +481,14 @@ pub fn build_reconstruction(
     slots.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
 
     // First pass: determine which songs are "kept" (present on disk + browser-playable).
-    let song_kept: Vec<bool> = set_list
-        .iter()
-        .enumerate()
-        .map(|(i, title)| {
+    let song_kept: Vec<bool> = (0..set_list.len())
+        .map(|i| {
             let present = tracks_present.get(i).copied().unwrap_or(false);
             if !present {
                 return false;
             }
-            match find_track_file(working_dir, album, title) {
-                Some(f) => {
+            match song_source(working_dir, album, cue_album, set_list, i) {
+                Some((f, _)) => {
                     let ext = f.rsplit('.').next().unwrap_or("");
                     is_browser_playable(ext)
                 }
@@ -222,9 +506,15 @@ pub fn build_reconstruction(
                     continue;
                 }
                 let title = &set_list[*i];
-                let Some(filename) = find_track_file(working_dir, album, title) else {
+                let Some((filename, clip)) =
+                    song_source(working_dir, album, cue_album, set_list, *i)
+                else {
                     continue;
                 };
+                let clip = clip.map(|clip| Clip {
+                    end_time: clip.end_time.or(tail_start),
+                    ..clip
+                });
                 let is_video = {
                     let ext = filename.rsplit('.').next().unwrap_or("");
                     is_video_extension(ext)
@@ -238,6 +528,7 @@ pub fn build_reconstruction(
                     title: title.clone(),
                     filename,
                     is_video,
+                    clip,
                 });
             }
             Slot::Interlude(idx) => {
@@ -253,7 +544,19 @@ pub fn build_reconstruction(
                 if next_song_deleted {
                     continue;
                 }
-                let Some(filename) = find_interlude_track_file(working_dir, album, *idx) else {
+                let source = match find_interlude_track_file(working_dir, album, *idx) {
+                    Some(filename) => Some((filename, None)),
+                    None => cue_album
+                        .zip(interludes.iter().find(|il| il.index == *idx))
+                        .map(|(cue_album, il)| {
+                            let clip = Clip {
+                                start_time: il.start_time,
+                                end_time: Some(il.end_time),
+                            };
+                            (cue_album.filename.clone(), Some(clip))
+                        }),
+                };
+                let Some((filename, clip)) = source else {
                     continue;
                 };
                 let is_video = {
@@ -268,6 +571,7 @@ pub fn build_reconstruction(
                     title: "interlude".to_string(),
                     filename,
                     is_video,
+                    clip,
                 });
             }
         }
@@ -279,6 +583,7 @@ pub fn build_reconstruction(
 fn songs_only(
     working_dir: &Path,
     album: &str,
+    cue_album: Option<&CueAlbum>,
     set_list: &[String],
     tracks_present: &[bool],
     tracks_liked: &[bool],
@@ -291,7 +596,7 @@ fn songs_only(
             if !present {
                 return None;
             }
-            let filename = find_track_file(working_dir, album, title)?;
+            let (filename, clip) = song_source(working_dir, album, cue_album, set_list, i)?;
             let is_video = {
                 let ext = filename.rsplit('.').next().unwrap_or("");
                 if !is_browser_playable(ext) {
@@ -308,6 +613,7 @@ fn songs_only(
                 title: title.clone(),
                 filename,
                 is_video,
+                clip,
             })
         })
         .collect()
@@ -317,9 +623,7 @@ fn songs_only(
 
 fn track_file_extension(dir: &Path, title: &str) -> Option<&'static str> {
     let stem = sanitize_filename(title);
-    for ext in &[
-        "mp4", "m4a", "webm", "mp3", "ogg", "opus", "wav", "flac", "mkv",
-    ] {
+    for ext in TRACK_EXTENSIONS {
         if dir.join(format!("{stem}.{ext}")).exists() {
             return Some(ext);
         }
@@ -339,6 +643,7 @@ pub fn list_tracks(working_dir: &Path, album: &str, set_list: &[String]) -> Vec<
                 available: true,
                 is_video: is_video_extension(ext),
                 liked: false,
+                poster_url: None,
             })
         })
         .collect()
@@ -357,6 +662,7 @@ pub fn list_all_tracks(working_dir: &Path, album: &str, set_list: &[String]) ->
                 available: ext.is_some(),
                 is_video: ext.is_some_and(is_video_extension),
                 liked: false,
+                poster_url: None,
             }
         })
         .collect()
@@ -395,21 +701,23 @@ pub fn list_all_track_details(
 
 // ── all-tracks-present ───────────────────────────────────────────────────────
 
-/// Filesystem presence for every title in `set_list`, in order. This is the
-/// single scan loop shared by prepare/scan/split call sites that previously
-/// duplicated `find_track_file(...).is_some()` inline.
+/// Filesystem presence for every title in `set_list`, in order: a track file
+/// of its own or a clip of the cue-indexed album. This is the single scan loop
+/// shared by prepare/scan/split call sites that previously duplicated
+/// `find_track_file(...).is_some()` inline.
 pub fn tracks_present_on_disk(working_dir: &Path, album: &str, set_list: &[String]) -> Vec<bool> {
-    set_list
-        .iter()
-        .map(|title| find_track_file(working_dir, album, title).is_some())
+    let cue_album = find_cue_album(working_dir, album);
+    (0..set_list.len())
+        .map(|i| song_source(working_dir, album, cue_album.as_ref(), set_list, i).is_some())
         .collect()
 }
 
-/// Whether every title in `set_list` currently has a file on disk.
+/// Whether every title in `set_list` currently has a file on disk (see
+/// [`tracks_present_on_disk`]).
 pub fn all_tracks_present_on_disk(working_dir: &Path, album: &str, set_list: &[String]) -> bool {
-    set_list
-        .iter()
-        .all(|title| find_track_file(working_dir, album, title).is_some())
+    tracks_present_on_disk(working_dir, album, set_list)
+        .into_iter()
+        .all(|present| present)
 }
 
 // ── ConcertMediaInventory ────────────────────────────────────────────────────
@@ -493,6 +801,15 @@ impl<'a> ConcertMediaInventory<'a> {
         self.with_published_split(None, || find_track_file(self.working_dir, album, title))
     }
 
+    /// The file the song at `index` plays from, with its clip when that is the
+    /// cue-indexed album. See [`find_track_source`].
+    pub fn find_track_source(&self, index: usize) -> Option<(String, Option<Clip>)> {
+        let album = self.album?;
+        self.with_published_split(None, || {
+            find_track_source(self.working_dir, album, self.set_list, index)
+        })
+    }
+
     /// The interlude file for `index`, if any.
     pub fn find_interlude_track_file(&self, index: usize) -> Option<String> {
         let album = self.album?;
@@ -692,6 +1009,108 @@ mod tests {
         assert!(find_downloaded_file(dir.path(), "No Such Album").is_none());
     }
 
+    // ---------- VideoRenditions ----------
+
+    #[test]
+    fn video_renditions_pick_the_tallest_published_rendition_within_the_cap() {
+        use live_set_splitter::cut::Rendition;
+
+        let dir = tempfile::tempdir().unwrap();
+        let album = "Test Album";
+        let cd = concert_dir(dir.path(), album);
+        std::fs::create_dir_all(&cd).unwrap();
+        for file in ["Song.mp4", "Song.720p.mp4", "Song.480p.mp4", "Other.mp4"] {
+            std::fs::write(cd.join(file), b"data").unwrap();
+        }
+        std::fs::write(
+            cd.join(live_set_splitter::publication::MANIFEST_NAME),
+            r#"{"files":["Song.mp4","Song.720p.mp4","Song.480p.mp4","Other.mp4"],"renditions":[720,480]}"#,
+        )
+        .unwrap();
+
+        let original = VideoRenditions::load(dir.path(), album, VideoQuality::Original);
+        assert_eq!(original.for_file("Song.mp4"), "Song.mp4");
+        let capped =
+            VideoRenditions::load(dir.path(), album, VideoQuality::AtMost(Rendition::P720));
+        assert_eq!(capped.for_file("Song.mp4"), "Song.720p.mp4");
+        assert_eq!(capped.for_file("Other.mp4"), "Other.mp4");
+        assert_eq!(capped.for_file("Song.m4a"), "Song.m4a");
+        let small = VideoRenditions::load(dir.path(), album, VideoQuality::AtMost(Rendition::P360));
+        assert_eq!(small.for_file("Song.mp4"), "Song.mp4");
+    }
+
+    // ---------- HLS package ----------
+
+    #[test]
+    fn hls_range_playlist_clips_variants_and_forwards_the_range_from_the_master() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = "Test: Album";
+        let cd = concert_dir(dir.path(), album);
+        std::fs::create_dir_all(&cd).unwrap();
+        std::fs::write(
+            cd.join("Test Album.hls.m3u8"),
+            "#EXTM3U\n#EXT-X-STREAM-INF:BANDWIDTH=1000\nTest Album.hls.source.m3u8\n",
+        )
+        .unwrap();
+        std::fs::write(
+            cd.join("Test Album.hls.source.m3u8"),
+            "#EXTM3U\n#EXT-X-TARGETDURATION:6\n\
+             #EXT-X-MAP:URI=\"Test Album.hls.source.init.mp4\"\n\
+             #EXT-X-PROGRAM-DATE-TIME:1970-01-01T00:00:00.000Z\n\
+             #EXT-X-DATERANGE:ID=\"song-1\",START-DATE=\"1970-01-01T00:00:07.000Z\",DURATION=3.000,X-TITLE=\"One\"\n\
+             #EXTINF:6.0,\nTest Album.hls.source.00000.m4s\n\
+             #EXTINF:6.0,\nTest Album.hls.source.00001.m4s\n#EXT-X-ENDLIST\n",
+        )
+        .unwrap();
+
+        assert_eq!(
+            find_hls_playlist(dir.path(), album).as_deref(),
+            Some("Test Album.hls.m3u8")
+        );
+        let master =
+            hls_range_playlist(dir.path(), album, "Test Album.hls.m3u8", "song-1").unwrap();
+        assert!(master.contains("Test Album.hls.source.m3u8?range=song-1\n"));
+
+        let variant =
+            hls_range_playlist(dir.path(), album, "Test Album.hls.source.m3u8", "song-1").unwrap();
+        assert!(variant.contains(
+            "#EXT-X-MAP:URI=\"/concert-files/Test Album/Test Album.hls.source.init.mp4\""
+        ));
+        assert!(variant.contains("\n/concert-files/Test Album/Test Album.hls.source.00001.m4s\n"));
+        assert!(!variant.contains("00000.m4s"));
+        assert!(variant.contains("#EXT-X-START:TIME-OFFSET=1.000,PRECISE=YES"));
+
+        assert!(
+            hls_range_playlist(dir.path(), album, "Test Album.hls.source.m3u8", "song-2").is_none()
+        );
+        assert!(hls_range_playlist(dir.path(), album, "../secret.m3u8", "song-1").is_none());
+    }
+
+    // ---------- Poster frames ----------
+
+    #[test]
+    fn posters_and_contact_sheet_resolve_only_when_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = "Test: Album";
+        let cd = concert_dir(dir.path(), album);
+        std::fs::create_dir_all(&cd).unwrap();
+        std::fs::write(cd.join("Song_ One.poster.jpg"), b"jpg").unwrap();
+        std::fs::write(cd.join("Test Album.contact.jpg"), b"jpg").unwrap();
+
+        let mut tracks = list_all_tracks(dir.path(), album, &["Song: One".into(), "Two".into()]);
+        attach_posters(dir.path(), album, &mut tracks);
+        assert_eq!(
+            tracks[0].poster_url.as_deref(),
+            Some("/concert-files/Test Album/Song_ One.poster.jpg")
+        );
+        assert_eq!(tracks[1].poster_url, None);
+        assert_eq!(
+            contact_sheet_url(dir.path(), album).as_deref(),
+            Some("/concert-files/Test Album/Test Album.contact.jpg")
+        );
+        assert_eq!(contact_sheet_url(dir.path(), "Other"), None);
+    }
+
     // ---------- find_track_file ----------
 
     #[test]
@@ -723,6 +1142,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_track_file_prefers_lighter_audio_encodes_over_flac() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = "Test Album";
+        let cd = concert_dir(dir.path(), album);
+        std::fs::create_dir_all(&cd).unwrap();
+        std::fs::write(cd.join("Song.flac"), b"data").unwrap();
+        assert_eq!(
+            find_track_file(dir.path(), album, "Song"),
+            Some("Song.flac".to_string())
+        );
+
+        std::fs::write(cd.join("Song.opus"), b"data").unwrap();
+        assert_eq!(
+            find_track_file(dir.path(), album, "Song"),
+            Some("Song.opus".to_string())
+        );
+    }
+
     #[test]
     fn find_track_file_returns_none_when_missing() {
         let dir = tempfile::tempdir().unwrap();
@@ -868,6 +1306,7 @@ mod tests {
             start_time: start,
             end_time: end,
             duration: end - start,
+            boundary: None,
         }
     }
 
@@ -1053,6 +1492,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn find_interlude_track_file_finds_every_split_audio_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = "Test";
+        let cd = concert_dir(dir.path(), album);
+        std::fs::create_dir_all(&cd).unwrap();
+
+        for ext in ["flac", "mp3", "opus"] {
+            let name = format!("interlude_01.{ext}");
+            std::fs::write(cd.join(&name), b"audio").unwrap();
+            assert_eq!(find_interlude_track_file(dir.path(), album, 1), Some(name));
+        }
+    }
+
     // ---------- build_reconstruction ----------
 
     /// Helper: create a concert directory and write stub files for the given song
@@ -1075,12 +1528,34 @@ mod tests {
         cd
     }
 
+    /// Write `Album.album.flac` and a sheet indexing `tracks` as
+    /// (title, pregap start, start) in `cd`.
+    fn write_cue_album(cd: &Path, tracks: &[(&str, Option<f64>, f64)]) {
+        std::fs::write(cd.join("Album.album.flac"), b"data").unwrap();
+        let sheet = CueSheet {
+            performer: Some("Artist".to_string()),
+            title: Some("Album".to_string()),
+            file: "Album.album.flac".to_string(),
+            tracks: tracks
+                .iter()
+                .map(|&(title, pregap_start, start_time)| cue::CueTrack {
+                    title: title.to_string(),
+                    performer: None,
+                    pregap_start,
+                    start_time,
+                })
+                .collect(),
+        };
+        std::fs::write(cd.join("Album.album.cue"), sheet.render()).unwrap();
+    }
+
     fn ts(start: f64, end: f64, title: &str) -> concert_types::SongTimestamp {
         concert_types::SongTimestamp {
             title: title.to_string(),
             start_time: start,
             end_time: end,
             duration: end - start,
+            boundary: None,
         }
     }
 
@@ -1175,6 +1650,80 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn build_reconstruction_plays_album_clips_where_files_are_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = "Album";
+        // Head gap [0,5), songs at [5,50) and [60,90), gap [50,60), tail [90,100).
+        let cd = setup_reconstruction_dir(dir.path(), album, &["Song A"], &[]);
+        write_cue_album(
+            &cd,
+            &[("Song A", Some(0.0), 5.0), ("Song B", Some(50.0), 60.0)],
+        );
+        let set_list = vec!["Song A".to_string(), "Song B".to_string()];
+        let songs_ts = vec![ts(5.0, 50.0, "Song A"), ts(60.0, 90.0, "Song B")];
+        let items = build_reconstruction(
+            dir.path(),
+            album,
+            &set_list,
+            &[true, true],
+            &[],
+            Some(&songs_ts),
+            Some(100.0),
+        );
+        let played: Vec<(&str, Option<Clip>)> = items
+            .iter()
+            .map(|item| (item.filename.as_str(), item.clip))
+            .collect();
+        let clip = |start_time, end_time| {
+            Some(Clip {
+                start_time,
+                end_time: Some(end_time),
+            })
+        };
+        assert_eq!(
+            played,
+            vec![
+                ("Album.album.flac", clip(0.0, 5.0)),
+                ("Song A.m4a", None),
+                ("Album.album.flac", clip(50.0, 60.0)),
+                // The tail is its own item, so Song B stops where it starts.
+                ("Album.album.flac", clip(60.0, 90.0)),
+                ("Album.album.flac", clip(90.0, 100.0)),
+            ]
+        );
+    }
+
+    #[test]
+    fn tracks_present_on_disk_counts_cue_indexed_songs_of_a_matching_sheet() {
+        let dir = tempfile::tempdir().unwrap();
+        let album = "Album";
+        let cd = setup_reconstruction_dir(dir.path(), album, &[], &[]);
+        write_cue_album(&cd, &[("Song A", None, 0.0), ("Song \"B\"", None, 60.0)]);
+        let set_list = vec!["Song A".to_string(), "Song \"B\"".to_string()];
+        assert_eq!(
+            tracks_present_on_disk(dir.path(), album, &set_list),
+            vec![true, true]
+        );
+        assert_eq!(
+            find_track_source(dir.path(), album, &set_list, 1),
+            Some((
+                "Album.album.flac".to_string(),
+                Some(Clip {
+                    start_time: 60.0,
+                    end_time: None
+                })
+            ))
+        );
+
+        // A sheet from before the set list changed indexes nothing.
+        let edited = vec!["Song A".to_string(), "Song C".to_string()];
+        assert_eq!(
+            tracks_present_on_disk(dir.path(), album, &edited),
+            vec![false, false]
+        );
+    }
+
     #[test]
     fn build_reconstruction_deleted_song_drops_preceding_interlude() {
         let dir = tempfile::tempdir().unwrap();
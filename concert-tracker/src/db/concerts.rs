@@ -1,6 +1,7 @@
 use anyhow::{Context, Result};
 use rusqlite::{params, Connection, Row};
 
+use crate::db::followed_artists;
 use crate::events::{self, Event};
 use crate::model::{Concert, ErrorEntry, Musician};
 
@@ -109,11 +110,37 @@ pub fn upsert_listing(conn: &Connection, listing: &NewListing) -> Result<()> {
     if is_new {
         let id = conn.last_insert_rowid();
         events::record_now(conn, id, Event::Import, None);
+        want_if_followed(conn, id, &listing.title)?;
     }
 
     Ok(())
 }
 
+/// Mark a just-imported concert wanted when its listing artist is followed,
+/// recording the follow as the `Wanted` event's reason.
+fn want_if_followed(conn: &Connection, id: i64, title: &str) -> Result<()> {
+    let artist = followed_artists::listing_artist(title);
+    let Some(followed) = followed_artists::find_followed(conn, artist)? else {
+        return Ok(());
+    };
+    conn.execute(
+        "UPDATE concerts SET wanted = 1, ignored = 0 WHERE id = ?1",
+        params![id],
+    )
+    .context("Failed to mark followed artist's concert wanted")?;
+    let reason = serde_json::json!({
+        "reason": "followed_artist",
+        "artist": followed.name,
+    });
+    events::record_now(conn, id, Event::Wanted, Some(&reason.to_string()));
+    tracing::info!(
+        "concert {} marked wanted: followed artist {:?}",
+        id,
+        followed.name
+    );
+    Ok(())
+}
+
 pub fn update_metadata(conn: &Connection, id: i64, update: &MetadataUpdate) -> Result<()> {
     let set_list_json = serde_json::to_string(&update.set_list)?;
     let musicians_json = serde_json::to_string(&update.musicians)?;
@@ -282,6 +309,57 @@ mod tests {
         assert_eq!(concerts[0].concert_date, Some("2024-06-01".to_string()));
     }
 
+    #[test]
+    fn upsert_listing_wants_new_listings_of_followed_artists() {
+        let conn = open_in_memory().unwrap();
+        followed_artists::follow(&conn, "Artíst A").unwrap();
+        upsert_listing(
+            &conn,
+            &listing("https://npr.org/c/1", "Artist A: Tiny Desk (Home) Concert"),
+        )
+        .unwrap();
+        upsert_listing(
+            &conn,
+            &listing("https://npr.org/c/2", "Artist B: Tiny Desk Concert"),
+        )
+        .unwrap();
+        let followed = get_concert_by_url(&conn, "https://npr.org/c/1")
+            .unwrap()
+            .unwrap();
+        assert!(followed.wanted);
+        let (_, json) = events_for(&conn, followed.id)
+            .into_iter()
+            .find(|(event, _)| event == "wanted")
+            .expect("wanted event");
+        let reason: serde_json::Value = serde_json::from_str(&json.unwrap()).unwrap();
+        assert_eq!(reason["reason"], "followed_artist");
+        assert_eq!(reason["artist"], "Artíst A");
+        let other = get_concert_by_url(&conn, "https://npr.org/c/2")
+            .unwrap()
+            .unwrap();
+        assert!(!other.wanted);
+    }
+
+    #[test]
+    fn upsert_listing_only_wants_on_first_import() {
+        let conn = open_in_memory().unwrap();
+        upsert_listing(
+            &conn,
+            &listing("https://npr.org/c/1", "Artist A: Tiny Desk"),
+        )
+        .unwrap();
+        followed_artists::follow(&conn, "Artist A").unwrap();
+        upsert_listing(
+            &conn,
+            &listing("https://npr.org/c/1", "Artist A: Tiny Desk"),
+        )
+        .unwrap();
+        let c = get_concert_by_url(&conn, "https://npr.org/c/1")
+            .unwrap()
+            .unwrap();
+        assert!(!c.wanted);
+    }
+
     #[test]
     fn upsert_listing_updates_title_on_conflict() {
         let conn = open_in_memory().unwrap();
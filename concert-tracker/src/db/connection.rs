@@ -8,6 +8,9 @@ const MIGRATION: &str = include_str!("../../migrations/0001_init.sql");
 const MIGRATION_002: &str = include_str!("../../migrations/0002_archive.sql");
 const MIGRATION_003: &str = include_str!("../../migrations/0003_audit_timestamps.sql");
 const MIGRATION_004: &str = include_str!("../../migrations/0004_playlists.sql");
+const MIGRATION_005: &str = include_str!("../../migrations/0005_search.sql");
+const MIGRATION_006: &str = include_str!("../../migrations/0006_followed_artists.sql");
+const MIGRATION_007: &str = include_str!("../../migrations/0007_job_queue.sql");
 
 pub fn open(path: &Path) -> Result<Connection> {
     let conn = Connection::open(path).context("Failed to open database")?;
@@ -69,6 +72,52 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         "theme",
         "TEXT NOT NULL DEFAULT 'system' CHECK (theme IN ('system','light','dark'))",
     )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "replay_gain",
+        "TEXT NOT NULL DEFAULT 'off' CHECK (replay_gain IN ('off','track','album'))",
+    )?;
+    // Scheduled month sync (crate::auto_sync). 0 hours = disabled.
+    add_column_if_missing(
+        conn,
+        "settings",
+        "auto_sync_hours",
+        "INTEGER NOT NULL DEFAULT 0 CHECK (auto_sync_hours >= 0)",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "auto_download",
+        "INTEGER NOT NULL DEFAULT 0 CHECK (auto_download IN (0, 1))",
+    )?;
+    add_column_if_missing(conn, "settings", "last_auto_sync_at", "TEXT")?;
+    add_column_if_missing(conn, "settings", "last_auto_sync_status", "TEXT")?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "download_format",
+        "TEXT NOT NULL DEFAULT 'video' CHECK (download_format IN ('video','audio'))",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "audio_formats",
+        "TEXT NOT NULL DEFAULT 'm4a'",
+    )?;
+    add_column_if_missing(conn, "settings", "renditions", "TEXT NOT NULL DEFAULT ''")?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "video_quality",
+        "TEXT NOT NULL DEFAULT 'original'",
+    )?;
+    add_column_if_missing(
+        conn,
+        "settings",
+        "hls_packaging",
+        "INTEGER NOT NULL DEFAULT 0 CHECK (hls_packaging IN (0, 1))",
+    )?;
     conn.execute_batch(
         "UPDATE concerts SET downloaded_extension = 'mp4'
          WHERE downloaded_at IS NOT NULL AND downloaded_extension IS NULL",
@@ -84,6 +133,12 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         .context("Failed to run migration 003")?;
     conn.execute_batch(MIGRATION_004)
         .context("Failed to run migration 004")?;
+    conn.execute_batch(MIGRATION_005)
+        .context("Failed to run migration 005")?;
+    conn.execute_batch(MIGRATION_006)
+        .context("Failed to run migration 006")?;
+    conn.execute_batch(MIGRATION_007)
+        .context("Failed to run migration 007")?;
     Ok(())
 }
 
@@ -207,6 +262,20 @@ mod tests {
         assert_eq!(c.downloaded_extension.as_deref(), Some("mp4"));
     }
 
+    #[test]
+    fn hls_packaging_check_rejects_non_boolean_values() {
+        let conn = open_in_memory().unwrap();
+        let set = |value: i64| {
+            conn.execute(
+                "UPDATE settings SET hls_packaging = ?1 WHERE id = 1",
+                [value],
+            )
+        };
+        assert!(set(1).is_ok());
+        assert!(set(0).is_ok());
+        assert!(set(2).is_err());
+    }
+
     #[test]
     fn backfill_audit_timestamps_uses_latest_event() {
         let conn = open_in_memory().unwrap();
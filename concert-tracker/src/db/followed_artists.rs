@@ -0,0 +1,167 @@
+//! Followed artists: names whose new listings are marked wanted on import.
+//! Matching compares [`normalize_artist`] forms, so a follow survives NPR's
+//! inconsistent spacing, punctuation and accents in listing titles.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
+use rusqlite::{params, Connection, OptionalExtension, Row};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FollowedArtist {
+    pub id: i64,
+    /// The name as the user entered it.
+    pub name: String,
+    pub inserted_at: String,
+}
+
+/// Match key for an artist name: the splitter's OCR normalization (diacritics
+/// folded to ASCII, non-alphanumerics dropped, lowercased).
+pub fn normalize_artist(name: &str) -> String {
+    live_set_splitter::ocr::normalize_text(name)
+}
+
+/// The artist part of an archive listing title — everything before the first
+/// `:` ("Artist: Tiny Desk Concert", "Artist: Tiny Desk (Home) Concert").
+pub fn listing_artist(title: &str) -> &str {
+    title.split(':').next().unwrap_or_default().trim()
+}
+
+fn followed_from_row(row: &Row) -> rusqlite::Result<FollowedArtist> {
+    Ok(FollowedArtist {
+        id: row.get("id")?,
+        name: row.get("name")?,
+        inserted_at: row.get("inserted_at")?,
+    })
+}
+
+/// Follow `name`. Returns `false` when an artist with the same normalized name
+/// is already followed (the existing display name is kept).
+pub fn follow(conn: &Connection, name: &str) -> Result<bool> {
+    let name = name.trim();
+    let normalized = normalize_artist(name);
+    if normalized.is_empty() {
+        bail!("artist name must contain letters or digits: {name:?}");
+    }
+    let inserted = conn
+        .execute(
+            "INSERT OR IGNORE INTO followed_artists (name, normalized_name) VALUES (?1, ?2)",
+            params![name, normalized],
+        )
+        .context("Failed to follow artist")?;
+    Ok(inserted > 0)
+}
+
+/// Unfollow by id. Returns whether a row was removed.
+pub fn unfollow(conn: &Connection, id: i64) -> Result<bool> {
+    let removed = conn
+        .execute("DELETE FROM followed_artists WHERE id = ?1", params![id])
+        .context("Failed to unfollow artist")?;
+    Ok(removed > 0)
+}
+
+/// Unfollow by (normalized) name. Returns whether a row was removed.
+pub fn unfollow_name(conn: &Connection, name: &str) -> Result<bool> {
+    let removed = conn
+        .execute(
+            "DELETE FROM followed_artists WHERE normalized_name = ?1",
+            params![normalize_artist(name)],
+        )
+        .context("Failed to unfollow artist")?;
+    Ok(removed > 0)
+}
+
+/// All followed artists, alphabetically by match key.
+pub fn list_followed(conn: &Connection) -> Result<Vec<FollowedArtist>> {
+    let mut stmt = conn
+        .prepare("SELECT id, name, inserted_at FROM followed_artists ORDER BY normalized_name")?;
+    let rows = stmt.query_map([], followed_from_row)?;
+    rows.collect::<rusqlite::Result<_>>()
+        .context("Failed to list followed artists")
+}
+
+/// Match keys of every followed artist, for checking many concerts without a
+/// query each.
+pub fn followed_keys(conn: &Connection) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare("SELECT normalized_name FROM followed_artists")?;
+    let rows = stmt.query_map([], |row| row.get(0))?;
+    rows.collect::<rusqlite::Result<_>>()
+        .context("Failed to list followed artist keys")
+}
+
+/// The follow matching `artist`, if any.
+pub fn find_followed(conn: &Connection, artist: &str) -> Result<Option<FollowedArtist>> {
+    let normalized = normalize_artist(artist);
+    if normalized.is_empty() {
+        return Ok(None);
+    }
+    conn.query_row(
+        "SELECT id, name, inserted_at FROM followed_artists WHERE normalized_name = ?1",
+        params![normalized],
+        followed_from_row,
+    )
+    .optional()
+    .context("Failed to look up followed artist")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::open_in_memory;
+
+    #[test]
+    fn follow_dedupes_on_the_normalized_name() {
+        let conn = open_in_memory().unwrap();
+        assert!(follow(&conn, "  Beyoncé ").unwrap());
+        assert!(!follow(&conn, "BEYONCE!").unwrap());
+        let followed = list_followed(&conn).unwrap();
+        assert_eq!(followed.len(), 1);
+        assert_eq!(followed[0].name, "Beyoncé");
+        assert!(follow(&conn, "?!").is_err());
+    }
+
+    #[test]
+    fn find_followed_matches_loosely() {
+        let conn = open_in_memory().unwrap();
+        follow(&conn, "Kes the Band").unwrap();
+        assert_eq!(
+            find_followed(&conn, "KestheBand").unwrap().unwrap().name,
+            "Kes the Band"
+        );
+        assert!(find_followed(&conn, "Kes").unwrap().is_none());
+        assert!(find_followed(&conn, "").unwrap().is_none());
+    }
+
+    #[test]
+    fn followed_keys_are_the_normalized_names() {
+        let conn = open_in_memory().unwrap();
+        follow(&conn, "Kes the Band").unwrap();
+        follow(&conn, "Beyoncé").unwrap();
+        let keys = followed_keys(&conn).unwrap();
+        assert_eq!(keys.len(), 2);
+        assert!(keys.contains(&normalize_artist("KES THE BAND")));
+        assert!(keys.contains("beyonce"));
+    }
+
+    #[test]
+    fn unfollow_by_id_and_name() {
+        let conn = open_in_memory().unwrap();
+        follow(&conn, "Artist One").unwrap();
+        follow(&conn, "Artist Two").unwrap();
+        let id = list_followed(&conn).unwrap()[0].id;
+        assert!(unfollow(&conn, id).unwrap());
+        assert!(!unfollow(&conn, id).unwrap());
+        assert!(unfollow_name(&conn, "artist two").unwrap());
+        assert!(list_followed(&conn).unwrap().is_empty());
+    }
+
+    #[test]
+    fn listing_artist_is_the_title_prefix() {
+        assert_eq!(listing_artist("Artist: Tiny Desk Concert"), "Artist");
+        assert_eq!(
+            listing_artist("The Band : Tiny Desk (Home) Concert"),
+            "The Band"
+        );
+        assert_eq!(listing_artist("No colon"), "No colon");
+    }
+}
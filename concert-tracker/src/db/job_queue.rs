@@ -0,0 +1,174 @@
+//! Persisted mirror of the Job Run waiting queue (see `JobRegistry::enqueue`).
+//! While the server runs the registry's in-memory order is authoritative and
+//! this table is rewritten to match it; at startup `jobs::resume_queued`
+//! resubmits what's here, in order.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection, Row};
+
+use crate::jobs::{JobKey, JobKind};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueuedJob {
+    pub key: JobKey,
+    pub position: i64,
+    pub payload: Option<String>,
+}
+
+fn queued_from_row(row: &Row) -> rusqlite::Result<QueuedJob> {
+    let kind: String = row.get("kind")?;
+    Ok(QueuedJob {
+        key: JobKey {
+            concert_id: row.get("concert_id")?,
+            kind: JobKind::parse(&kind).ok_or_else(|| {
+                rusqlite::Error::FromSqlConversionFailure(
+                    0,
+                    rusqlite::types::Type::Text,
+                    format!("unknown job kind {kind:?}").into(),
+                )
+            })?,
+        },
+        position: row.get("position")?,
+        payload: row.get("payload")?,
+    })
+}
+
+/// Append `key` to the end of its kind's queue. Re-enqueueing an existing key
+/// replaces its payload and moves it to the end.
+pub fn enqueue(conn: &Connection, key: &JobKey, payload: Option<&str>) -> Result<()> {
+    conn.execute(
+        "INSERT OR REPLACE INTO job_queue (concert_id, kind, position, payload)
+         VALUES (?1, ?2, (SELECT COALESCE(MAX(position), 0) + 1 FROM job_queue WHERE kind = ?2), ?3)",
+        params![key.concert_id, key.kind.as_str(), payload],
+    )
+    .context("Failed to enqueue job")?;
+    Ok(())
+}
+
+/// Remove `key`. Returns whether it was queued.
+pub fn remove(conn: &Connection, key: &JobKey) -> Result<bool> {
+    let removed = conn
+        .execute(
+            "DELETE FROM job_queue WHERE concert_id = ?1 AND kind = ?2",
+            params![key.concert_id, key.kind.as_str()],
+        )
+        .context("Failed to remove queued job")?;
+    Ok(removed > 0)
+}
+
+/// Rewrite `kind`'s positions to follow `concert_ids` (head first). Ids not
+/// in the table are ignored.
+pub fn reorder(conn: &Connection, kind: JobKind, concert_ids: &[i64]) -> Result<()> {
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to begin queue reorder")?;
+    for (index, concert_id) in concert_ids.iter().enumerate() {
+        tx.execute(
+            "UPDATE job_queue SET position = ?1 WHERE concert_id = ?2 AND kind = ?3",
+            params![index as i64 + 1, concert_id, kind.as_str()],
+        )?;
+    }
+    tx.commit().context("Failed to commit queue reorder")?;
+    Ok(())
+}
+
+/// Every queued job, grouped by kind and in queue order within each.
+pub fn list_queued(conn: &Connection) -> Result<Vec<QueuedJob>> {
+    let mut stmt = conn.prepare(
+        "SELECT concert_id, kind, position, payload FROM job_queue
+         ORDER BY kind, position, inserted_at",
+    )?;
+    let rows = stmt.query_map([], queued_from_row)?;
+    rows.collect::<rusqlite::Result<_>>()
+        .context("Failed to list queued jobs")
+}
+
+/// Whether `key` has a queue row.
+pub fn is_queued(conn: &Connection, key: &JobKey) -> Result<bool> {
+    conn.query_row(
+        "SELECT EXISTS (SELECT 1 FROM job_queue WHERE concert_id = ?1 AND kind = ?2)",
+        params![key.concert_id, key.kind.as_str()],
+        |row| row.get(0),
+    )
+    .context("Failed to check job queue")
+}
+
+pub fn clear(conn: &Connection) -> Result<usize> {
+    conn.execute("DELETE FROM job_queue", [])
+        .context("Failed to clear job queue")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::open_in_memory;
+    use crate::db::tests::listing;
+
+    fn seed(conn: &Connection, url: &str) -> i64 {
+        crate::db::concerts::upsert_listing(conn, &listing(url, "Artist: Tiny Desk Concert"))
+            .unwrap();
+        conn.query_row(
+            "SELECT id FROM concerts WHERE source_url = ?1",
+            [url],
+            |row| row.get(0),
+        )
+        .unwrap()
+    }
+
+    fn key(concert_id: i64, kind: JobKind) -> JobKey {
+        JobKey { concert_id, kind }
+    }
+
+    fn order(conn: &Connection, kind: JobKind) -> Vec<i64> {
+        list_queued(conn)
+            .unwrap()
+            .into_iter()
+            .filter(|q| q.key.kind == kind)
+            .map(|q| q.key.concert_id)
+            .collect()
+    }
+
+    #[test]
+    fn enqueue_appends_per_kind_and_reorder_rewrites_positions() {
+        let conn = open_in_memory().unwrap();
+        let a = seed(&conn, "https://example.com/a");
+        let b = seed(&conn, "https://example.com/b");
+        let c = seed(&conn, "https://example.com/c");
+        enqueue(&conn, &key(a, JobKind::Download), None).unwrap();
+        enqueue(&conn, &key(b, JobKind::Download), None).unwrap();
+        enqueue(
+            &conn,
+            &key(a, JobKind::Split),
+            Some(r#"{"mode":"analyze"}"#),
+        )
+        .unwrap();
+        enqueue(&conn, &key(c, JobKind::Download), None).unwrap();
+        assert_eq!(order(&conn, JobKind::Download), vec![a, b, c]);
+        assert_eq!(order(&conn, JobKind::Split), vec![a]);
+
+        reorder(&conn, JobKind::Download, &[c, a, b]).unwrap();
+        assert_eq!(order(&conn, JobKind::Download), vec![c, a, b]);
+
+        let split = list_queued(&conn)
+            .unwrap()
+            .into_iter()
+            .find(|q| q.key.kind == JobKind::Split)
+            .unwrap();
+        assert_eq!(split.payload.as_deref(), Some(r#"{"mode":"analyze"}"#));
+    }
+
+    #[test]
+    fn remove_and_is_queued() {
+        let conn = open_in_memory().unwrap();
+        let a = seed(&conn, "https://example.com/a");
+        let download = key(a, JobKind::Download);
+        assert!(!is_queued(&conn, &download).unwrap());
+        enqueue(&conn, &download, None).unwrap();
+        assert!(is_queued(&conn, &download).unwrap());
+        assert!(!is_queued(&conn, &key(a, JobKind::Split)).unwrap());
+        assert!(remove(&conn, &download).unwrap());
+        assert!(!remove(&conn, &download).unwrap());
+        enqueue(&conn, &download, None).unwrap();
+        assert_eq!(clear(&conn).unwrap(), 1);
+    }
+}
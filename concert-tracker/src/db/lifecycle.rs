@@ -62,6 +62,41 @@ pub fn mark_download_failed(conn: &Connection, id: i64, error: &str) -> Result<(
     Ok(())
 }
 
+/// Record that download attempt `attempt` failed transiently and the next
+/// one starts after `retry_in_secs`. The download stays in progress.
+pub fn record_download_retry(
+    conn: &Connection,
+    id: i64,
+    attempt: u32,
+    retry_in_secs: u64,
+    error: &str,
+) -> Result<()> {
+    let json = serde_json::json!({
+        "attempt": attempt,
+        "retry_in_secs": retry_in_secs,
+        "error": error,
+    })
+    .to_string();
+    events::try_record_now(conn, id, Event::DownloadRetry, Some(&json))
+        .context("Failed to record download_retry event")
+}
+
+/// 1-based attempt number of the concert's current (or latest) download:
+/// one plus the `download_retry` events since its last `download_started`.
+pub fn download_attempt(conn: &Connection, id: i64) -> Result<u32> {
+    let retries: u32 = conn
+        .query_row(
+            "SELECT COUNT(*) FROM events
+             WHERE concert_id = ?1 AND event = 'download_retry'
+               AND id > COALESCE((SELECT MAX(id) FROM events
+                                  WHERE concert_id = ?1 AND event = 'download_started'), 0)",
+            params![id],
+            |row| row.get(0),
+        )
+        .context("Failed to count download retries")?;
+    Ok(retries + 1)
+}
+
 /// Returns false if split is already in progress or concert is not yet downloaded.
 pub fn try_mark_split_started(conn: &Connection, id: i64) -> Result<bool> {
     let rows = conn
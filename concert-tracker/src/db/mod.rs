@@ -17,8 +17,11 @@
 pub mod concerts;
 pub mod connection;
 pub mod failed_jobs;
+pub mod followed_artists;
+pub mod job_queue;
 pub mod lifecycle;
 pub mod playlists;
+pub mod search;
 #[cfg(any(test, feature = "test-control"))]
 pub mod seeds;
 pub mod settings;
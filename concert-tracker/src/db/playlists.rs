@@ -275,8 +275,20 @@ pub fn add_playlist_item(
     let tx = conn
         .unchecked_transaction()
         .context("Failed to begin add-item transaction")?;
+    let id = insert_playlist_item(&tx, playlist_id, kind)?;
+    tx.commit().context("Failed to commit add-item")?;
+    Ok(id)
+}
 
-    if !playlist_exists(&tx, playlist_id)? {
+/// The validate-then-insert body of [`add_playlist_item`], for callers that
+/// already hold a transaction spanning several inserts (SQLite can't nest
+/// `BEGIN`, so they can't go through `add_playlist_item`).
+pub(crate) fn insert_playlist_item(
+    tx: &Connection,
+    playlist_id: i64,
+    kind: &PlaylistItemKind,
+) -> std::result::Result<i64, PlaylistError> {
+    if !playlist_exists(tx, playlist_id)? {
         return Err(PlaylistError::NotFound);
     }
     match kind {
@@ -284,7 +296,7 @@ pub fn add_playlist_item(
             concert_id,
             track_index,
         } => {
-            let len = concert_set_list_len(&tx, *concert_id)?
+            let len = concert_set_list_len(tx, *concert_id)?
                 .ok_or_else(|| PlaylistError::Invalid(format!("concert {concert_id} not found")))?;
             if *track_index >= len {
                 return Err(PlaylistError::Invalid(format!(
@@ -293,19 +305,19 @@ pub fn add_playlist_item(
             }
         }
         PlaylistItemKind::Concert { concert_id } => {
-            if concert_set_list_len(&tx, *concert_id)?.is_none() {
+            if concert_set_list_len(tx, *concert_id)?.is_none() {
                 return Err(PlaylistError::Invalid(format!(
                     "concert {concert_id} not found"
                 )));
             }
         }
         PlaylistItemKind::Playlist { child_playlist_id } => {
-            if !playlist_exists(&tx, *child_playlist_id)? {
+            if !playlist_exists(tx, *child_playlist_id)? {
                 return Err(PlaylistError::Invalid(format!(
                     "playlist {child_playlist_id} not found"
                 )));
             }
-            if would_create_cycle(&tx, playlist_id, *child_playlist_id)? {
+            if would_create_cycle(tx, playlist_id, *child_playlist_id)? {
                 return Err(PlaylistError::Invalid(
                     "adding this playlist would create a cycle".into(),
                 ));
@@ -347,9 +359,7 @@ pub fn add_playlist_item(
         ],
     )
     .context("Failed to insert playlist item")?;
-    let id = tx.last_insert_rowid();
-    tx.commit().context("Failed to commit add-item")?;
-    Ok(id)
+    Ok(tx.last_insert_rowid())
 }
 
 /// Remove one item from a playlist. Sibling positions are left as-is (gaps are
@@ -0,0 +1,311 @@
+//! Full-text concert search over the `concerts_fts` FTS5 index (migration
+//! 0005). The index is maintained entirely by triggers on `concerts`, so this
+//! module is read-only: it turns user input into a safe FTS5 query and ranks
+//! the matches.
+
+use anyhow::{Context, Result};
+use rusqlite::{params, Connection};
+
+use super::concerts::concert_from_row;
+use crate::model::Concert;
+
+/// Default number of hits returned when the caller doesn't ask for a limit.
+pub const DEFAULT_SEARCH_LIMIT: usize = 50;
+
+/// Hard ceiling on a caller-supplied limit, so `?limit=1000000` can't turn one
+/// request into a full-table snippet render.
+pub const MAX_SEARCH_LIMIT: usize = 200;
+
+/// Approximate number of tokens FTS5 keeps around a match in a snippet.
+const SNIPPET_TOKENS: i64 = 12;
+
+/// Snippet highlight delimiters. Control characters rather than `<mark>` so
+/// the raw snippet is plain text that can't collide with (or smuggle) markup
+/// from concert notes/descriptions; [`parse_snippet`] splits on them and each
+/// caller applies its own highlighting (escaped `<mark>` in HTML, nothing in
+/// JSON/CLI).
+const HIGHLIGHT_START: char = '\u{2}';
+const HIGHLIGHT_END: char = '\u{3}';
+
+/// A validated FTS5 MATCH expression built from free-form user input.
+///
+/// Raw input is never passed to MATCH: FTS5 query syntax treats `"`, `*`,
+/// `:`, `-`, `(`, `AND`/`OR`/`NOT` etc. specially, so a search for `AC/DC` or
+/// `"Weird Al"` would be a syntax error (or a different query). Instead each
+/// whitespace-separated word becomes a quoted prefix term, and terms are
+/// implicitly ANDed — "pedal steel" finds concerts mentioning both words, and
+/// "ped" finds "pedal".
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchQuery(String);
+
+impl SearchQuery {
+    /// `None` when the input has no searchable words (empty, whitespace, or
+    /// only quote characters) — callers treat that as "no search", not an
+    /// error.
+    pub fn parse(input: &str) -> Option<Self> {
+        let terms: Vec<String> = input
+            .split_whitespace()
+            .map(|word| word.replace('"', ""))
+            .filter(|word| !word.is_empty())
+            .map(|word| format!("\"{word}\"*"))
+            .collect();
+        if terms.is_empty() {
+            None
+        } else {
+            Some(SearchQuery(terms.join(" ")))
+        }
+    }
+
+    pub fn as_fts(&self) -> &str {
+        &self.0
+    }
+}
+
+/// One run of snippet text, highlighted when it is (part of) a matched term.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnippetSegment {
+    pub text: String,
+    pub highlighted: bool,
+}
+
+#[derive(Debug, Clone)]
+pub struct SearchHit {
+    pub concert: Concert,
+    /// Context around the best-matching column, split into highlighted and
+    /// plain runs.
+    pub snippet: Vec<SnippetSegment>,
+}
+
+impl SearchHit {
+    /// The snippet as plain text, highlight markers dropped.
+    pub fn snippet_text(&self) -> String {
+        self.snippet.iter().map(|s| s.text.as_str()).collect()
+    }
+}
+
+/// Ranked full-text search. Ordering is FTS5 `bm25` with column weights in
+/// `concerts_fts` column order (artist, title, description, set_list,
+/// musicians, notes): an artist-name hit outranks the same word buried in a
+/// description.
+pub fn search_concerts(
+    conn: &Connection,
+    query: &SearchQuery,
+    limit: usize,
+) -> Result<Vec<SearchHit>> {
+    let limit = limit.min(MAX_SEARCH_LIMIT);
+    let sql = format!(
+        "SELECT c.*, snippet(concerts_fts, -1, '{HIGHLIGHT_START}', '{HIGHLIGHT_END}', '…', {SNIPPET_TOKENS}) AS search_snippet
+         FROM concerts_fts
+         JOIN concerts c ON c.id = concerts_fts.rowid
+         WHERE concerts_fts MATCH ?1
+         ORDER BY bm25(concerts_fts, 10.0, 5.0, 1.0, 3.0, 3.0, 2.0), c.concert_date DESC
+         LIMIT ?2"
+    );
+    let mut stmt = conn.prepare(&sql)?;
+    let hits = stmt
+        .query_map(params![query.as_fts(), limit as i64], |row| {
+            let concert = concert_from_row(row)?;
+            let snippet: Option<String> = row.get("search_snippet")?;
+            Ok(SearchHit {
+                concert,
+                snippet: parse_snippet(snippet.as_deref().unwrap_or_default()),
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()
+        .context("Failed to search concerts")?;
+    tracing::debug!(query = query.as_fts(), hits = hits.len(), "concert search");
+    Ok(hits)
+}
+
+fn parse_snippet(raw: &str) -> Vec<SnippetSegment> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut highlighted = false;
+    for ch in raw.chars() {
+        let toggles_to = match ch {
+            HIGHLIGHT_START => Some(true),
+            HIGHLIGHT_END => Some(false),
+            _ => None,
+        };
+        match toggles_to {
+            Some(next) => {
+                if !current.is_empty() {
+                    segments.push(SnippetSegment {
+                        text: std::mem::take(&mut current),
+                        highlighted,
+                    });
+                }
+                highlighted = next;
+            }
+            None => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        segments.push(SnippetSegment {
+            text: current,
+            highlighted,
+        });
+    }
+    segments
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::concerts::{
+        get_concert_by_url, set_notes, update_metadata, upsert_listing, MetadataUpdate,
+    };
+    use crate::db::connection::open_in_memory;
+    use crate::db::tests::{listing, seed, seed_with_album};
+    use crate::model::Musician;
+
+    fn search(conn: &Connection, input: &str) -> Vec<SearchHit> {
+        search_concerts(
+            conn,
+            &SearchQuery::parse(input).unwrap(),
+            DEFAULT_SEARCH_LIMIT,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_rejects_input_without_words() {
+        assert_eq!(SearchQuery::parse(""), None);
+        assert_eq!(SearchQuery::parse("   "), None);
+        assert_eq!(SearchQuery::parse("\"\" \""), None);
+    }
+
+    #[test]
+    fn parse_quotes_each_word_as_a_prefix_term() {
+        let q = SearchQuery::parse(" pedal  \"steel ").unwrap();
+        assert_eq!(q.as_fts(), "\"pedal\"* \"steel\"*");
+    }
+
+    #[test]
+    fn finds_concerts_by_set_list_and_musician_instrument() {
+        let conn = open_in_memory().unwrap();
+        let id = seed_with_album(&conn);
+
+        let by_song = search(&conn, "Song B");
+        assert_eq!(by_song.len(), 1);
+        assert_eq!(by_song[0].concert.id, id);
+
+        let by_instrument = search(&conn, "guitar");
+        assert_eq!(by_instrument.len(), 1);
+        assert!(by_instrument[0]
+            .snippet
+            .iter()
+            .any(|s| s.highlighted && s.text == "guitar"));
+
+        // JSON keys of musicians_json must not be indexed as words.
+        assert!(search(&conn, "instruments").is_empty());
+    }
+
+    #[test]
+    fn prefix_and_diacritic_insensitive_matching() {
+        let conn = open_in_memory().unwrap();
+        let id = seed(&conn);
+        update_metadata(
+            &conn,
+            id,
+            &MetadataUpdate {
+                artist: "Björk".to_string(),
+                album: "Album".to_string(),
+                description: None,
+                set_list: vec![],
+                musicians: vec![Musician {
+                    name: "Zed".to_string(),
+                    instruments: vec!["pedal steel".to_string()],
+                }],
+            },
+        )
+        .unwrap();
+        assert_eq!(search(&conn, "bjork").len(), 1);
+        assert_eq!(search(&conn, "ped ste").len(), 1);
+        assert!(search(&conn, "pedal trombone").is_empty());
+    }
+
+    #[test]
+    fn notes_update_reindexes_the_row() {
+        let conn = open_in_memory().unwrap();
+        let id = seed(&conn);
+        assert!(search(&conn, "encore").is_empty());
+        set_notes(&conn, id, "Incredible encore").unwrap();
+        assert_eq!(search(&conn, "encore").len(), 1);
+        set_notes(&conn, id, "Forgettable").unwrap();
+        assert!(search(&conn, "encore").is_empty());
+    }
+
+    #[test]
+    fn fts_syntax_characters_are_searched_literally() {
+        let conn = open_in_memory().unwrap();
+        seed_with_album(&conn);
+        for input in ["AC/DC", "a:b", "NOT", "(x", "-y", "*", "\"Weird Al"] {
+            search_concerts(&conn, &SearchQuery::parse(input).unwrap(), 10)
+                .unwrap_or_else(|e| panic!("{input:?} should not be a query error: {e:#}"));
+        }
+    }
+
+    #[test]
+    fn deleted_concert_leaves_the_index() {
+        let conn = open_in_memory().unwrap();
+        let id = seed_with_album(&conn);
+        conn.execute("DELETE FROM concerts WHERE id = ?1", params![id])
+            .unwrap();
+        assert!(search(&conn, "guitar").is_empty());
+        let indexed: i64 = conn
+            .query_row("SELECT COUNT(*) FROM concerts_fts", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(indexed, 0);
+    }
+
+    #[test]
+    fn malformed_json_columns_do_not_block_writes() {
+        let conn = open_in_memory().unwrap();
+        let id = seed(&conn);
+        conn.execute(
+            "UPDATE concerts SET set_list_json = 'not json', notes = 'still indexed' WHERE id = ?1",
+            params![id],
+        )
+        .unwrap();
+        assert_eq!(search(&conn, "indexed").len(), 1);
+    }
+
+    #[test]
+    fn limit_caps_results() {
+        let conn = open_in_memory().unwrap();
+        let a = seed(&conn);
+        set_notes(&conn, a, "shared word").unwrap();
+        upsert_listing(&conn, &listing("https://npr.org/c/2", "Second")).unwrap();
+        let b = get_concert_by_url(&conn, "https://npr.org/c/2")
+            .unwrap()
+            .unwrap()
+            .id;
+        set_notes(&conn, b, "shared word").unwrap();
+        let q = SearchQuery::parse("shared").unwrap();
+        assert_eq!(search_concerts(&conn, &q, 1).unwrap().len(), 1);
+        assert_eq!(search_concerts(&conn, &q, 10).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn parse_snippet_splits_highlighted_runs() {
+        let raw = format!("a {HIGHLIGHT_START}b{HIGHLIGHT_END} c");
+        assert_eq!(
+            parse_snippet(&raw),
+            vec![
+                SnippetSegment {
+                    text: "a ".to_string(),
+                    highlighted: false
+                },
+                SnippetSegment {
+                    text: "b".to_string(),
+                    highlighted: true
+                },
+                SnippetSegment {
+                    text: " c".to_string(),
+                    highlighted: false
+                },
+            ]
+        );
+    }
+}
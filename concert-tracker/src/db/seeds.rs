@@ -104,6 +104,7 @@ pub(crate) fn write_legacy_timestamps_json(
         preview_image_url: None,
         teaser: None,
         timestamps: Some(songs.to_vec()),
+        interludes: None,
     };
     let json = serde_json::to_string(&info)?;
     std::fs::write(output_dir.join("timestamps.json"), json)?;
@@ -128,6 +129,7 @@ pub(crate) fn fake_analysis_timestamps(titles: &[String]) -> Vec<SongTimestamp>
                 start_time: start,
                 end_time: end,
                 duration: end - start,
+                boundary: None,
             }
         })
         .collect()
@@ -1116,6 +1118,7 @@ mod tests {
             start_time: 0.0,
             end_time: 10.0,
             duration: 10.0,
+            boundary: None,
         };
         let concert = seeds
             .seed_lifecycle_concert(SeedLifecycleConcert {
@@ -1298,12 +1301,14 @@ mod tests {
                 start_time: 0.0,
                 end_time: 55.0,
                 duration: 55.0,
+                boundary: None,
             },
             concert_types::SongTimestamp {
                 title: "Song B".to_string(),
                 start_time: 60.0,
                 end_time: 115.0,
                 duration: 55.0,
+                boundary: None,
             },
         ];
         let concert = seeds
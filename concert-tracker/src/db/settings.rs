@@ -1,4 +1,6 @@
 use anyhow::{anyhow, Context, Result};
+use live_set_splitter::concert_split::AudioFormat;
+use live_set_splitter::cut::Rendition;
 use rusqlite::{params, Connection};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -34,27 +36,244 @@ impl Theme {
     }
 }
 
+/// Which `loudness.json` gain the player applies: none, each track's own,
+/// or the concert-wide (album) gain that keeps a set's dynamics intact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReplayGainMode {
+    Off,
+    Track,
+    Album,
+}
+
+impl ReplayGainMode {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ReplayGainMode::Off => "off",
+            ReplayGainMode::Track => "track",
+            ReplayGainMode::Album => "album",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<ReplayGainMode> {
+        match s {
+            "off" => Ok(ReplayGainMode::Off),
+            "track" => Ok(ReplayGainMode::Track),
+            "album" => Ok(ReplayGainMode::Album),
+            other => Err(anyhow!("unknown replay gain mode: {other}")),
+        }
+    }
+}
+
+/// Which video the player streams: the full-resolution track, or the tallest
+/// published rendition no taller than a cap, for phones and slow links.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VideoQuality {
+    Original,
+    AtMost(Rendition),
+}
+
+impl VideoQuality {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            VideoQuality::Original => "original",
+            VideoQuality::AtMost(rendition) => rendition.name(),
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<VideoQuality> {
+        match s {
+            "original" => Ok(VideoQuality::Original),
+            other => Rendition::from_name(other)
+                .map(VideoQuality::AtMost)
+                .ok_or_else(|| anyhow!("unknown video quality: {other}")),
+        }
+    }
+
+    /// The lower of two caps, e.g. the configured quality and the one a
+    /// small-screened player asks for.
+    pub fn min(self, other: VideoQuality) -> VideoQuality {
+        match (self, other) {
+            (VideoQuality::Original, quality) | (quality, VideoQuality::Original) => quality,
+            (VideoQuality::AtMost(a), VideoQuality::AtMost(b)) => {
+                VideoQuality::AtMost(if a.height() <= b.height() { a } else { b })
+            }
+        }
+    }
+}
+
+/// What yt-dlp fetches for a concert: the video (cut into `.mp4` and `.m4a`
+/// tracks) or only its best audio stream (`.m4a` tracks only, a fraction of
+/// the download).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DownloadFormat {
+    Video,
+    Audio,
+}
+
+impl DownloadFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DownloadFormat::Video => "video",
+            DownloadFormat::Audio => "audio",
+        }
+    }
+
+    pub fn parse(s: &str) -> Result<DownloadFormat> {
+        match s {
+            "video" => Ok(DownloadFormat::Video),
+            "audio" => Ok(DownloadFormat::Audio),
+            other => Err(anyhow!("unknown download format: {other}")),
+        }
+    }
+}
+
+/// The audio files splits write per track, stored as comma-separated
+/// extensions (`m4a,flac`). Unknown names are dropped and an empty list falls
+/// back to `m4a`, so a split always has one.
+pub fn parse_audio_formats(s: &str) -> Vec<AudioFormat> {
+    let mut formats = Vec::new();
+    for format in s
+        .split(',')
+        .filter_map(|ext| AudioFormat::from_extension(ext.trim()))
+    {
+        if !formats.contains(&format) {
+            formats.push(format);
+        }
+    }
+    if formats.is_empty() {
+        formats.push(AudioFormat::M4a);
+    }
+    formats
+}
+
+pub fn audio_formats_str(formats: &[AudioFormat]) -> String {
+    formats
+        .iter()
+        .map(|format| format.extension())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Parse the stored `renditions` list (rendition names, comma-separated),
+/// dropping unknown names and duplicates. Empty means none.
+pub fn parse_renditions(s: &str) -> Vec<Rendition> {
+    let mut renditions = Vec::new();
+    for rendition in s
+        .split(',')
+        .filter_map(|name| Rendition::from_name(name.trim()))
+    {
+        if !renditions.contains(&rendition) {
+            renditions.push(rendition);
+        }
+    }
+    renditions
+}
+
+pub fn renditions_str(renditions: &[Rendition]) -> String {
+    renditions
+        .iter()
+        .map(|rendition| rendition.name())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Intervals offered for the scheduled month sync, in hours. `0` is off.
+pub const AUTO_SYNC_INTERVALS: &[u32] = &[0, 1, 6, 24];
+
+/// Scheduled month sync (see `crate::auto_sync`): how often it runs, whether
+/// it also downloads (and splits) wanted and followed artists' concerts, and
+/// what the last run did.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AutoSync {
+    /// Hours between runs; `0` disables the scheduler.
+    pub interval_hours: u32,
+    pub download: bool,
+    pub last_run_at: Option<String>,
+    pub last_run_status: Option<String>,
+}
+
+impl AutoSync {
+    pub fn enabled(&self) -> bool {
+        self.interval_hours > 0
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct Settings {
     pub archive_location: Option<String>,
     pub theme: Theme,
+    pub replay_gain: ReplayGainMode,
+    pub download_format: DownloadFormat,
+    /// Audio files each split writes per track; never empty.
+    pub audio_formats: Vec<AudioFormat>,
+    /// Downscaled video copies each split writes per track; may be empty.
+    pub renditions: Vec<Rendition>,
+    pub video_quality: VideoQuality,
+    /// Whether each split also packages the concert for HLS streaming.
+    pub hls_packaging: bool,
+    pub auto_sync: AutoSync,
 }
 
 pub fn get_settings(conn: &Connection) -> Result<Settings> {
     conn.query_row(
-        "SELECT archive_location, theme FROM settings WHERE id = 1",
+        "SELECT archive_location, theme, replay_gain, auto_sync_hours, auto_download, \
+         last_auto_sync_at, last_auto_sync_status, download_format, \
+         audio_formats, renditions, video_quality, hls_packaging FROM settings WHERE id = 1",
         [],
         |row| {
             let archive_location: Option<String> = row.get(0)?;
             let theme_str: String = row.get(1)?;
-            Ok((archive_location, theme_str))
+            let replay_gain_str: String = row.get(2)?;
+            let download_format_str: String = row.get(7)?;
+            let audio_formats_str: String = row.get(8)?;
+            let renditions_str: String = row.get(9)?;
+            let video_quality_str: String = row.get(10)?;
+            let hls_packaging: bool = row.get(11)?;
+            let auto_sync = AutoSync {
+                interval_hours: row.get(3)?,
+                download: row.get(4)?,
+                last_run_at: row.get(5)?,
+                last_run_status: row.get(6)?,
+            };
+            Ok((
+                archive_location,
+                theme_str,
+                replay_gain_str,
+                download_format_str,
+                audio_formats_str,
+                renditions_str,
+                video_quality_str,
+                hls_packaging,
+                auto_sync,
+            ))
         },
     )
     .context("Failed to read settings")
-    .map(|(archive_location, theme_str)| Settings {
-        archive_location,
-        theme: Theme::parse(&theme_str).unwrap_or(Theme::System),
-    })
+    .map(
+        |(
+            archive_location,
+            theme_str,
+            replay_gain_str,
+            download_format_str,
+            audio_formats_str,
+            renditions_str,
+            video_quality_str,
+            hls_packaging,
+            auto_sync,
+        )| Settings {
+            archive_location,
+            theme: Theme::parse(&theme_str).unwrap_or(Theme::System),
+            replay_gain: ReplayGainMode::parse(&replay_gain_str).unwrap_or(ReplayGainMode::Off),
+            download_format: DownloadFormat::parse(&download_format_str)
+                .unwrap_or(DownloadFormat::Video),
+            audio_formats: parse_audio_formats(&audio_formats_str),
+            renditions: parse_renditions(&renditions_str),
+            video_quality: VideoQuality::parse(&video_quality_str)
+                .unwrap_or(VideoQuality::Original),
+            hls_packaging,
+            auto_sync,
+        },
+    )
 }
 
 pub fn update_archive_location(conn: &Connection, location: &str) -> Result<()> {
@@ -81,6 +300,99 @@ pub fn update_theme(conn: &Connection, theme: Theme) -> Result<()> {
     Ok(())
 }
 
+pub fn update_replay_gain(conn: &Connection, mode: ReplayGainMode) -> Result<()> {
+    tracing::debug!("update_replay_gain: {}", mode.as_str());
+    conn.execute(
+        "UPDATE settings SET replay_gain = ?1 WHERE id = 1",
+        params![mode.as_str()],
+    )
+    .context("Failed to update replay gain mode")?;
+    Ok(())
+}
+
+pub fn update_download_format(conn: &Connection, format: DownloadFormat) -> Result<()> {
+    tracing::debug!("update_download_format: {}", format.as_str());
+    conn.execute(
+        "UPDATE settings SET download_format = ?1 WHERE id = 1",
+        params![format.as_str()],
+    )
+    .context("Failed to update download format")?;
+    Ok(())
+}
+
+pub fn update_audio_formats(conn: &Connection, formats: &[AudioFormat]) -> Result<()> {
+    let value = audio_formats_str(formats);
+    tracing::debug!("update_audio_formats: {value}");
+    conn.execute(
+        "UPDATE settings SET audio_formats = ?1 WHERE id = 1",
+        params![value],
+    )
+    .context("Failed to update audio formats")?;
+    Ok(())
+}
+
+pub fn update_renditions(conn: &Connection, renditions: &[Rendition]) -> Result<()> {
+    let value = renditions_str(renditions);
+    tracing::debug!("update_renditions: {value}");
+    conn.execute(
+        "UPDATE settings SET renditions = ?1 WHERE id = 1",
+        params![value],
+    )
+    .context("Failed to update renditions")?;
+    Ok(())
+}
+
+pub fn update_video_quality(conn: &Connection, quality: VideoQuality) -> Result<()> {
+    tracing::debug!("update_video_quality: {}", quality.as_str());
+    conn.execute(
+        "UPDATE settings SET video_quality = ?1 WHERE id = 1",
+        params![quality.as_str()],
+    )
+    .context("Failed to update video quality")?;
+    Ok(())
+}
+
+pub fn update_hls_packaging(conn: &Connection, enabled: bool) -> Result<()> {
+    tracing::debug!("update_hls_packaging: {enabled}");
+    conn.execute(
+        "UPDATE settings SET hls_packaging = ?1 WHERE id = 1",
+        params![enabled],
+    )
+    .context("Failed to update HLS packaging")?;
+    Ok(())
+}
+
+/// Save the sync interval; the last-run fields are left to
+/// [`record_auto_sync_run`].
+pub fn update_auto_sync_hours(conn: &Connection, interval_hours: u32) -> Result<()> {
+    tracing::debug!("update_auto_sync_hours: every {interval_hours}h");
+    conn.execute(
+        "UPDATE settings SET auto_sync_hours = ?1 WHERE id = 1",
+        params![interval_hours],
+    )
+    .context("Failed to update auto sync interval")?;
+    Ok(())
+}
+
+pub fn update_auto_download(conn: &Connection, download: bool) -> Result<()> {
+    tracing::debug!("update_auto_download: {download}");
+    conn.execute(
+        "UPDATE settings SET auto_download = ?1 WHERE id = 1",
+        params![download],
+    )
+    .context("Failed to update auto download")?;
+    Ok(())
+}
+
+pub fn record_auto_sync_run(conn: &Connection, at: &str, status: &str) -> Result<()> {
+    conn.execute(
+        "UPDATE settings SET last_auto_sync_at = ?1, last_auto_sync_status = ?2 WHERE id = 1",
+        params![at, status],
+    )
+    .context("Failed to record auto sync run")?;
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -107,6 +419,97 @@ mod tests {
         assert_eq!(get_settings(&conn).unwrap().theme, Theme::Light);
         update_theme(&conn, Theme::System).unwrap();
         assert_eq!(get_settings(&conn).unwrap().theme, Theme::System);
+
+        assert_eq!(s.replay_gain, ReplayGainMode::Off);
+        update_replay_gain(&conn, ReplayGainMode::Album).unwrap();
+        assert_eq!(
+            get_settings(&conn).unwrap().replay_gain,
+            ReplayGainMode::Album
+        );
+
+        assert_eq!(s.download_format, DownloadFormat::Video);
+        update_download_format(&conn, DownloadFormat::Audio).unwrap();
+        assert_eq!(
+            get_settings(&conn).unwrap().download_format,
+            DownloadFormat::Audio
+        );
+
+        assert_eq!(s.audio_formats, vec![AudioFormat::M4a]);
+        update_audio_formats(&conn, &[AudioFormat::Flac, AudioFormat::Opus]).unwrap();
+        assert_eq!(
+            get_settings(&conn).unwrap().audio_formats,
+            vec![AudioFormat::Flac, AudioFormat::Opus]
+        );
+
+        assert!(s.renditions.is_empty());
+        update_renditions(&conn, &[Rendition::P720, Rendition::P480]).unwrap();
+        assert_eq!(
+            get_settings(&conn).unwrap().renditions,
+            vec![Rendition::P720, Rendition::P480]
+        );
+
+        assert_eq!(s.video_quality, VideoQuality::Original);
+        update_video_quality(&conn, VideoQuality::AtMost(Rendition::P480)).unwrap();
+        assert_eq!(
+            get_settings(&conn).unwrap().video_quality,
+            VideoQuality::AtMost(Rendition::P480)
+        );
+
+        assert!(!s.hls_packaging);
+        update_hls_packaging(&conn, true).unwrap();
+        assert!(get_settings(&conn).unwrap().hls_packaging);
+    }
+
+    #[test]
+    fn video_quality_min_keeps_the_lower_cap() {
+        let p720 = VideoQuality::AtMost(Rendition::P720);
+        let p480 = VideoQuality::AtMost(Rendition::P480);
+        assert_eq!(VideoQuality::Original.min(p720), p720);
+        assert_eq!(p720.min(VideoQuality::Original), p720);
+        assert_eq!(p720.min(p480), p480);
+        assert_eq!(p480.min(p720), p480);
+        assert_eq!(
+            VideoQuality::Original.min(VideoQuality::Original),
+            VideoQuality::Original
+        );
+    }
+
+    #[test]
+    fn parse_audio_formats_drops_unknown_and_never_returns_empty() {
+        assert_eq!(
+            parse_audio_formats("flac, mp3,wav,flac"),
+            vec![AudioFormat::Flac, AudioFormat::Mp3]
+        );
+        assert_eq!(parse_audio_formats(""), vec![AudioFormat::M4a]);
+        assert_eq!(
+            audio_formats_str(&[AudioFormat::M4a, AudioFormat::Opus]),
+            "m4a,opus"
+        );
+    }
+
+    #[test]
+    fn auto_sync_roundtrip() {
+        let conn = open_in_memory().unwrap();
+        let auto = get_settings(&conn).unwrap().auto_sync;
+        assert_eq!(auto, AutoSync::default());
+        assert!(!auto.enabled());
+
+        update_auto_sync_hours(&conn, 6).unwrap();
+        update_auto_download(&conn, true).unwrap();
+        record_auto_sync_run(&conn, "2026-07-01 12:00:00", "2 new").unwrap();
+        let auto = get_settings(&conn).unwrap().auto_sync;
+        assert!(auto.enabled());
+        assert!(auto.download);
+        assert_eq!(auto.last_run_at.as_deref(), Some("2026-07-01 12:00:00"));
+        assert_eq!(auto.last_run_status.as_deref(), Some("2 new"));
+
+        // Saving the schedule leaves the last-run record alone.
+        update_auto_sync_hours(&conn, 0).unwrap();
+        update_auto_download(&conn, false).unwrap();
+        let auto = get_settings(&conn).unwrap().auto_sync;
+        assert!(!auto.enabled());
+        assert!(!auto.download);
+        assert_eq!(auto.last_run_status.as_deref(), Some("2 new"));
     }
 
     #[test]
@@ -141,7 +544,16 @@ mod tests {
         let conn = open_in_memory().unwrap();
         conn.execute_batch(
             "CREATE TABLE settings_tmp (id INTEGER PRIMARY KEY CHECK (id = 1), \
-             archive_location TEXT, theme TEXT NOT NULL DEFAULT 'system'); \
+             archive_location TEXT, theme TEXT NOT NULL DEFAULT 'system', \
+             replay_gain TEXT NOT NULL DEFAULT 'off', \
+             auto_sync_hours INTEGER NOT NULL DEFAULT 0, \
+             auto_download INTEGER NOT NULL DEFAULT 0, \
+             last_auto_sync_at TEXT, last_auto_sync_status TEXT, \
+             download_format TEXT NOT NULL DEFAULT 'video', \
+             audio_formats TEXT NOT NULL DEFAULT 'm4a', \
+             renditions TEXT NOT NULL DEFAULT '', \
+             video_quality TEXT NOT NULL DEFAULT 'original', \
+             hls_packaging INTEGER NOT NULL DEFAULT 0); \
              INSERT INTO settings_tmp (id, theme) VALUES (1, 'solarized'); \
              DROP TABLE settings; ALTER TABLE settings_tmp RENAME TO settings;",
         )
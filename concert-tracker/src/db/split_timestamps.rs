@@ -166,6 +166,19 @@ pub fn list_concerts_needing_tracks_backfill(conn: &Connection) -> Result<Vec<Co
     Ok(concerts)
 }
 
+/// Concerts with user-corrected timestamps, by id: the ground truth for the
+/// `split-eval` corpus.
+pub fn list_concerts_with_user_split_timestamps(conn: &Connection) -> Result<Vec<Concert>> {
+    let mut stmt = conn
+        .prepare("SELECT * FROM concerts WHERE user_split_timestamps_json IS NOT NULL ORDER BY id")
+        .context("Failed to prepare user timestamps query")?;
+    let concerts = stmt
+        .query_map([], concert_from_row)?
+        .filter_map(|r| r.ok())
+        .collect();
+    Ok(concerts)
+}
+
 /// Concerts split before the `media_duration` column existed (or whose value was
 /// never persisted). Candidates for the `concert_db backfill-media-duration` CLI.
 pub fn list_concerts_missing_media_duration(conn: &Connection) -> Result<Vec<Concert>> {
@@ -327,12 +340,14 @@ pub(crate) mod tests {
                 start_time: 0.0,
                 end_time: 120.0,
                 duration: 120.0,
+                boundary: None,
             },
             concert_types::SongTimestamp {
                 title: "Song B".to_string(),
                 start_time: 125.0,
                 end_time: 250.0,
                 duration: 125.0,
+                boundary: None,
             },
         ]
     }
@@ -474,6 +489,7 @@ pub(crate) mod tests {
             start_time: start,
             end_time: end,
             duration: end - start,
+            boundary: None,
         }
     }
 
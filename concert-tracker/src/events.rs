@@ -36,6 +36,10 @@ pub enum Event {
     /// `tracks_from_events` (which parses `track_index` from every `track_delete`
     /// row) is not corrupted by interlude deletions.
     InterludeDelete,
+    /// A chaptered copy of the source was written from the concert detail page.
+    ChaptersWritten,
+    /// A download attempt failed transiently and another is scheduled.
+    DownloadRetry,
 }
 
 impl Event {
@@ -43,7 +47,7 @@ impl Event {
     /// (Test Control's `assert_concert_events`) against the real vocabulary
     /// instead of accepting an arbitrary typo'd string that would then
     /// vacuously never match.
-    pub const ALL: [Event; 27] = [
+    pub const ALL: [Event; 29] = [
         Event::Listen,
         Event::Import,
         Event::Scraped,
@@ -71,6 +75,8 @@ impl Event {
         Event::SplitTimestampsReset,
         Event::SourceRedundantDelete,
         Event::InterludeDelete,
+        Event::ChaptersWritten,
+        Event::DownloadRetry,
     ];
 
     /// Parse an event name (the same string `as_str` produces) back into an
@@ -108,6 +114,8 @@ impl Event {
             Event::SplitTimestampsReset => "split_timestamps_reset",
             Event::SourceRedundantDelete => "source_redundant_delete",
             Event::InterludeDelete => "interlude_delete",
+            Event::ChaptersWritten => "chapters_written",
+            Event::DownloadRetry => "download_retry",
         }
     }
 }
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 
 use crate::db;
+use crate::jobs::progress::ProgressReporter;
 use crate::jobs::run::{self, Admission, JobCancellation, JobRequest};
 use crate::jobs::{JobKey, JobKind, JobRegistry, JobRunFuture, JobStepFailure, JobStepOutcome};
 use crate::model::{concert_dir, sanitize_album};
@@ -58,6 +59,35 @@ pub async fn start_archive(
     }
 }
 
+/// Resubmit an archive left waiting in `job_queue` by a previous process.
+/// The payload is the archive location it was accepted with.
+pub async fn resume_archive(
+    db: Arc<Mutex<Connection>>,
+    registry: Arc<JobRegistry>,
+    working_dir: &Path,
+    concert_id: i64,
+    payload: Option<&str>,
+) -> Result<StartOutcome> {
+    let Some(archive_location) = payload else {
+        let error = anyhow::anyhow!("queued archive has no archive location");
+        run::fail_unresumable(&db, &ArchiveCancellation::new(concert_id), &error);
+        return Err(error);
+    };
+    let request = ArchiveRequest::new(
+        concert_id,
+        working_dir.to_path_buf(),
+        archive_location.to_string(),
+    );
+    match run::resume(db, registry, request).await {
+        Ok(Admission::Accepted) => Ok(StartOutcome::Spawned),
+        Ok(Admission::AlreadyRunning) => Ok(StartOutcome::AlreadyRunning),
+        Err(error) if error.downcast_ref::<ArchiveValidationError>().is_some() => {
+            Ok(StartOutcome::NothingToArchive)
+        }
+        Err(error) => Err(error),
+    }
+}
+
 /// The archive [`JobRequest`]. `Setup` is the identity of `Input`
 /// (`ArchiveJob`) — archive has no separate post-acceptance preparation step,
 /// matching download.
@@ -174,6 +204,7 @@ impl JobRequest for ArchiveRequest {
         &'a self,
         setup: &'a ArchiveJob,
         _log_file: Option<&'a Path>,
+        _progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome> {
         // do_archive is real (blocking) filesystem work: rename-or-copy plus a
         // symlink. Run it on a blocking thread, same as the pre-#127 code, and
@@ -198,6 +229,10 @@ impl JobRequest for ArchiveRequest {
     fn commit_success(&self, conn: &Connection, _facts: ()) -> Result<()> {
         db::lifecycle::mark_archive_succeeded(conn, self.concert_id)
     }
+
+    fn queue_payload(&self) -> Option<String> {
+        Some(self.archive_location.clone())
+    }
 }
 
 fn do_archive(job: &ArchiveJob) -> anyhow::Result<()> {
@@ -2,12 +2,14 @@ use anyhow::Result;
 use rusqlite::Connection;
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use crate::db;
+use crate::jobs::progress::ProgressReporter;
 use crate::jobs::run::{self, Admission, JobCancellation, JobRequest};
 use crate::jobs::{
     download_job_from_concert, DownloadJob, JobConfig, JobKey, JobKind, JobRegistry, JobRunFuture,
-    JobStepOutcome,
+    JobStepFailure, JobStepOutcome,
 };
 
 pub enum StartOutcome {
@@ -32,6 +34,78 @@ pub async fn start_download(
     }
 }
 
+/// Whether a failed download is worth another attempt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureClass {
+    /// Network, CDN, or rate-limit trouble, or an extractor hiccup: likely to
+    /// pass on its own.
+    Transient,
+    /// The video is gone or unreachable for good, the URL is unsupported, or
+    /// the failure isn't recognized. Retrying would only repeat it.
+    Permanent,
+}
+
+/// Checked first: any of these makes a failure permanent even if the output
+/// also contains a transient marker (yt-dlp often reports a 404 after some
+/// retried fragment errors).
+const PERMANENT_MARKERS: &[&str] = &[
+    "http error 401",
+    "http error 403",
+    "http error 404",
+    "http error 410",
+    "video unavailable",
+    "private video",
+    "unsupported url",
+    "not available in your country",
+    "no space left on device",
+    "spawn error",
+];
+
+const TRANSIENT_MARKERS: &[&str] = &[
+    "http error 429",
+    "http error 500",
+    "http error 502",
+    "http error 503",
+    "http error 504",
+    "timed out",
+    "connection reset",
+    "connection refused",
+    "connection aborted",
+    "remote end closed connection",
+    "temporary failure in name resolution",
+    "incompleteread",
+    "unable to download webpage",
+    "unable to download json metadata",
+    "unable to extract",
+    "giving up after",
+];
+
+/// Classify a download failure from its message — `exit <code>: <stderr
+/// tail>` as built from `run_with_logging`'s captured tail.
+pub fn classify_failure(message: &str) -> FailureClass {
+    let message = message.to_lowercase();
+    let has = |markers: &[&str]| markers.iter().any(|marker| message.contains(marker));
+    if has(PERMANENT_MARKERS) || !has(TRANSIENT_MARKERS) {
+        FailureClass::Permanent
+    } else {
+        FailureClass::Transient
+    }
+}
+
+/// Resubmit a download left waiting in `job_queue` by a previous process.
+pub async fn resume_download(
+    db: Arc<Mutex<Connection>>,
+    registry: Arc<JobRegistry>,
+    config: JobConfig,
+    concert_id: i64,
+) -> Result<StartOutcome> {
+    let request = DownloadRequest::new(concert_id, config);
+    match run::resume(db, registry, request).await? {
+        Admission::Accepted => Ok(StartOutcome::Spawned),
+        Admission::AlreadyRunning => Ok(StartOutcome::AlreadyRunning),
+    }
+}
+
 /// The download [`JobRequest`]. `Setup` is the identity of `Input`
 /// (`DownloadJob`) — download has no separate post-acceptance preparation
 /// step; split uses `Setup` for temp files and output paths.
@@ -118,7 +192,8 @@ impl JobRequest for DownloadRequest {
 
     fn validate(&self, conn: &Connection) -> Result<DownloadJob> {
         let concert = db::concerts::get_concert(conn, self.concert_id)?;
-        download_job_from_concert(&concert, &self.config.working_dir)
+        let format = db::settings::get_settings(conn)?.download_format;
+        download_job_from_concert(&concert, &self.config.working_dir, format)
     }
 
     fn try_mark_started(&self, conn: &Connection) -> Result<bool> {
@@ -133,8 +208,9 @@ impl JobRequest for DownloadRequest {
         &'a self,
         setup: &'a DownloadJob,
         log_file: Option<&'a Path>,
+        progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome> {
-        Box::pin(self.config.run_download(setup, log_file))
+        Box::pin(self.config.run_download(setup, log_file, progress))
     }
 
     fn gather_success_facts(&self, setup: &DownloadJob) -> Result<String> {
@@ -160,6 +236,29 @@ impl JobRequest for DownloadRequest {
     fn spawn_dependents(&self, db: Arc<Mutex<Connection>>, registry: Arc<JobRegistry>) {
         crate::jobs::spawn_dependents(db, registry, self.config.clone(), &self.key());
     }
+
+    fn retry_delay(&self, failure: &JobStepFailure, attempt: u32) -> Option<Duration> {
+        match classify_failure(failure.message()) {
+            FailureClass::Transient => self.config.download_retry().delay_after(attempt),
+            FailureClass::Permanent => None,
+        }
+    }
+
+    fn record_retry(
+        &self,
+        conn: &Connection,
+        attempt: u32,
+        delay: Duration,
+        failure: &JobStepFailure,
+    ) -> Result<()> {
+        db::lifecycle::record_download_retry(
+            conn,
+            self.concert_id,
+            attempt,
+            delay.as_secs(),
+            failure.message(),
+        )
+    }
 }
 
 #[cfg(test)]
@@ -501,4 +600,186 @@ mod tests {
             .unwrap();
         assert!(matches!(r2, StartOutcome::AlreadyRunning));
     }
+
+    #[test]
+    fn failures_classify_permanent_unless_only_transient_markers_appear() {
+        for transient in [
+            "exit 1: ERROR: unable to download video data: HTTP Error 503: Service Unavailable",
+            "exit 1: ERROR: [npr] 12345: Unable to extract video url; please report this issue",
+            "exit 1: ERROR: Read timed out.",
+            "exit 1: <urlopen error [Errno -3] Temporary failure in name resolution>",
+        ] {
+            assert_eq!(
+                classify_failure(transient),
+                FailureClass::Transient,
+                "{transient}"
+            );
+        }
+        for permanent in [
+            "exit 1: ERROR: unable to download video data: HTTP Error 404: Not Found",
+            "exit 1: ERROR: HTTP Error 503, then HTTP Error 404: Not Found",
+            "exit 1: ERROR: Unsupported URL: https://example.com/",
+            "exit 7: boom",
+            "spawn error: No such file or directory (os error 2)",
+        ] {
+            assert_eq!(
+                classify_failure(permanent),
+                FailureClass::Permanent,
+                "{permanent}"
+            );
+        }
+    }
+
+    fn retrying(config: JobConfig, max_attempts: u32) -> JobConfig {
+        config.with_download_retry(crate::jobs::RetryPolicy {
+            max_attempts,
+            initial_delay: std::time::Duration::from_millis(1),
+            max_delay: std::time::Duration::from_millis(5),
+        })
+    }
+
+    fn retry_events(db: &Arc<Mutex<Connection>>, id: i64) -> Vec<crate::events::EventRow> {
+        crate::events::list_for_concert(&db.lock().unwrap(), id)
+            .into_iter()
+            .filter(|e| e.event == "download_retry")
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn transient_failure_is_retried_until_success() {
+        let tmp = tempfile::tempdir().unwrap();
+        let marker = tmp.path().join("failed-once");
+        let (db, id) = seeded_db();
+        let config = retrying(
+            JobConfig::from_commands(
+                PathBuf::from("/tmp"),
+                Arc::new(move |_| {
+                    let mut cmd = Command::new("sh");
+                    cmd.arg("-c").arg(format!(
+                        "[ -f '{0}' ] && exit 0; touch '{0}'; \
+                         echo 'ERROR: HTTP Error 503: Service Unavailable' >&2; exit 1",
+                        marker.display()
+                    ));
+                    cmd
+                }),
+                Arc::new(|_| unreachable!()),
+                Arc::new(|_| Command::new("true")),
+            ),
+            3,
+        );
+        start_download(db.clone(), Arc::new(JobRegistry::new()), config, id)
+            .await
+            .unwrap();
+        wait_for(&db, id, |c| c.downloaded_at.is_some()).await;
+
+        let concert = db::concerts::get_concert(&db.lock().unwrap(), id).unwrap();
+        assert!(concert.downloaded_at.is_some());
+        assert!(concert.download_errors.is_empty());
+        let retries = retry_events(&db, id);
+        assert_eq!(retries.len(), 1);
+        let json: serde_json::Value =
+            serde_json::from_str(retries[0].json.as_deref().unwrap()).unwrap();
+        assert_eq!(json["attempt"], 1);
+        assert!(json["error"].as_str().unwrap().contains("HTTP Error 503"));
+        assert_eq!(
+            db::lifecycle::download_attempt(&db.lock().unwrap(), id).unwrap(),
+            2
+        );
+    }
+
+    #[tokio::test]
+    async fn retries_stop_at_the_limit_and_skip_permanent_failures() {
+        let (db, id) = seeded_db();
+        let always_503 = JobConfig::from_commands(
+            PathBuf::from("/tmp"),
+            Arc::new(|_| {
+                let mut cmd = Command::new("sh");
+                cmd.args(["-c", "echo 'HTTP Error 503' >&2; exit 1"]);
+                cmd
+            }),
+            Arc::new(|_| unreachable!()),
+            Arc::new(|_| Command::new("true")),
+        );
+        start_download(
+            db.clone(),
+            Arc::new(JobRegistry::new()),
+            retrying(always_503, 2),
+            id,
+        )
+        .await
+        .unwrap();
+        wait_for(&db, id, |c| !c.download_errors.is_empty()).await;
+        let concert = db::concerts::get_concert(&db.lock().unwrap(), id).unwrap();
+        assert!(
+            concert.download_errors[0]
+                .error
+                .contains("(after 2 attempts)"),
+            "{}",
+            concert.download_errors[0].error
+        );
+        assert_eq!(retry_events(&db, id).len(), 1);
+
+        let (db, id) = seeded_db();
+        start_download(
+            db.clone(),
+            Arc::new(JobRegistry::new()),
+            retrying(config_failure(), 3),
+            id,
+        )
+        .await
+        .unwrap();
+        wait_for(&db, id, |c| !c.download_errors.is_empty()).await;
+        let concert = db::concerts::get_concert(&db.lock().unwrap(), id).unwrap();
+        assert!(!concert.download_errors[0].error.contains("attempts"));
+        assert!(retry_events(&db, id).is_empty());
+    }
+
+    #[tokio::test]
+    async fn yt_dlp_progress_lines_are_published_while_running() {
+        let (db, id) = seeded_db();
+        let registry = Arc::new(JobRegistry::new());
+        let (_, mut rx) = registry.progress().subscribe();
+        let config = JobConfig::from_commands(
+            PathBuf::from("/tmp"),
+            Arc::new(|_| {
+                let mut cmd = Command::new("sh");
+                cmd.args([
+                    "-c",
+                    "echo '[download]  50.0% of 10.00MiB at 1.00MiB/s ETA 00:05'; \
+                     echo '[Merger] Merging formats into \"x.mp4\"'",
+                ]);
+                cmd
+            }),
+            Arc::new(|_| unreachable!()),
+            Arc::new(|_| Command::new("true")),
+        );
+        start_download(db.clone(), registry.clone(), config, id)
+            .await
+            .unwrap();
+        let key = JobKey {
+            concert_id: id,
+            kind: JobKind::Download,
+        };
+        // The run's slot (and with it the progress record) is released
+        // after the success commit.
+        wait_for(&db, id, |c| c.downloaded_at.is_some()).await;
+        for _ in 0..100 {
+            if registry.progress().get(&key).is_none() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+
+        let updates: Vec<_> = std::iter::from_fn(|| rx.try_recv().ok()).collect();
+        assert!(updates.iter().any(|p| p.percent == Some(50.0)
+            && p.eta.as_deref() == Some("00:05")
+            && p.phase.as_deref() == Some("Downloading")));
+        assert!(updates
+            .iter()
+            .any(|p| p.phase.as_deref() == Some("Merging formats") && p.percent.is_none()));
+        assert_eq!(
+            updates.last().map(|p| p.state),
+            Some(crate::jobs::progress::ProgressState::Finished)
+        );
+    }
 }
@@ -1,12 +1,13 @@
 pub mod archive;
 pub mod download;
 pub mod prepare;
+pub mod progress;
 pub mod run;
 pub mod scrape_queue;
 pub mod split;
 mod split_library;
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::future::Future;
 use std::io::Write as _;
 use std::path::{Path, PathBuf};
@@ -16,15 +17,20 @@ use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
-use tokio::sync::oneshot;
+use tokio::sync::{oneshot, Notify};
 use tokio::task::JoinHandle;
 
 use concert_types::ConcertInfo;
+use live_set_splitter::concert_split::AudioFormat;
+use live_set_splitter::cut::Rendition;
 
 pub use crate::concert_media::find_downloaded_file;
+use crate::db::settings::DownloadFormat;
 use crate::model::concert_dir;
 use crate::model::sanitize_album;
 use crate::model::Concert;
+use progress::{ProgressHub, ProgressReporter};
+pub(crate) use split_library::library_options;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum JobKind {
@@ -33,6 +39,26 @@ pub enum JobKind {
     Archive,
 }
 
+impl JobKind {
+    /// Stable lowercase name, as stored in `job_queue.kind`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            JobKind::Download => "download",
+            JobKind::Split => "split",
+            JobKind::Archive => "archive",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<JobKind> {
+        match s {
+            "download" => Some(JobKind::Download),
+            "split" => Some(JobKind::Split),
+            "archive" => Some(JobKind::Archive),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct JobKey {
     pub concert_id: i64,
@@ -127,6 +153,79 @@ impl ActivationSignal {
     }
 }
 
+/// How many Job Runs of each kind may execute at once. `None` is unlimited.
+/// Accepted runs beyond the limit wait in a per-kind FIFO queue (see
+/// [`JobRegistry::run_permit`]).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JobLimits {
+    pub download: Option<usize>,
+    pub split: Option<usize>,
+    pub archive: Option<usize>,
+}
+
+impl JobLimits {
+    pub fn for_kind(&self, kind: JobKind) -> Option<usize> {
+        match kind {
+            JobKind::Download => self.download,
+            JobKind::Split => self.split,
+            JobKind::Archive => self.archive,
+        }
+    }
+}
+
+/// Execution bookkeeping behind [`JobLimits`]: runs holding a permit, and
+/// accepted runs waiting for one, oldest first.
+#[derive(Default)]
+struct RunQueue {
+    running: HashMap<JobKind, usize>,
+    waiting: HashMap<JobKind, VecDeque<i64>>,
+}
+
+impl RunQueue {
+    fn remove_waiting(&mut self, key: &JobKey) -> bool {
+        let Some(waiting) = self.waiting.get_mut(&key.kind) else {
+            return false;
+        };
+        let before = waiting.len();
+        waiting.retain(|id| *id != key.concert_id);
+        waiting.len() != before
+    }
+}
+
+/// Held by a Job Run while it executes; dropping it (run finished, or its
+/// task aborted) frees the slot for the next waiting run of the same kind.
+pub struct RunPermit {
+    registry: Arc<JobRegistry>,
+    kind: JobKind,
+}
+
+impl Drop for RunPermit {
+    fn drop(&mut self) {
+        {
+            let mut queue = self.registry.queue.lock().unwrap();
+            if let Some(running) = queue.running.get_mut(&self.kind) {
+                *running = running.saturating_sub(1);
+            }
+        }
+        self.registry.turn.notify_waiters();
+    }
+}
+
+/// Removes a still-waiting run from the queue if its task is aborted (user
+/// dequeue, shutdown) before it got a permit.
+struct WaitingEntry<'a> {
+    registry: &'a JobRegistry,
+    key: &'a JobKey,
+}
+
+impl Drop for WaitingEntry<'_> {
+    fn drop(&mut self) {
+        if self.registry.queue.lock().unwrap().remove_waiting(self.key) {
+            self.registry.turn.notify_waiters();
+        }
+    }
+}
+
 pub struct JobRegistry {
     slots: SlotsMap,
     /// dependents[upstream] = jobs to start when `upstream` completes
@@ -135,14 +234,156 @@ pub struct JobRegistry {
     /// until its upstream succeeds. On upstream failure or cancellation the
     /// queued dependents are dropped (they never run).
     dependents: Mutex<HashMap<JobKey, Vec<JobKey>>>,
+    limits: JobLimits,
+    queue: Mutex<RunQueue>,
+    /// Woken whenever a permit frees up or the waiting order changes.
+    turn: Notify,
+    progress: Arc<ProgressHub>,
 }
 
 impl JobRegistry {
     pub fn new() -> Self {
+        Self::with_limits(JobLimits::default())
+    }
+
+    pub fn with_limits(limits: JobLimits) -> Self {
         JobRegistry {
             slots: Arc::new(Mutex::new(HashMap::new())),
             dependents: Mutex::new(HashMap::new()),
+            limits,
+            queue: Mutex::new(RunQueue::default()),
+            turn: Notify::new(),
+            progress: Arc::new(ProgressHub::new()),
+        }
+    }
+
+    pub fn limits(&self) -> JobLimits {
+        self.limits
+    }
+
+    /// Live progress of queued and running Job Runs (see [`progress`]).
+    pub fn progress(&self) -> &Arc<ProgressHub> {
+        &self.progress
+    }
+
+    /// The reporter `run` hands to `JobRequest::execute` for `key`.
+    pub fn progress_reporter(&self, key: &JobKey) -> ProgressReporter {
+        self.progress.reporter(key)
+    }
+
+    /// Append an accepted Job Run to its kind's waiting queue. Called by
+    /// [`run::submit`] before the run task is spawned, so queue order is
+    /// acceptance order.
+    pub fn enqueue(&self, key: &JobKey) {
+        {
+            let mut queue = self.queue.lock().unwrap();
+            let waiting = queue.waiting.entry(key.kind).or_default();
+            if !waiting.contains(&key.concert_id) {
+                waiting.push_back(key.concert_id);
+            }
+        }
+        self.progress.queued(key);
+    }
+
+    /// Wait until `key` is at the head of its kind's queue and the kind is
+    /// under its limit, then take a permit. A key that isn't queued (never
+    /// enqueued) only waits for capacity.
+    pub async fn run_permit(self: &Arc<Self>, key: &JobKey) -> RunPermit {
+        let _entry = WaitingEntry {
+            registry: self,
+            key,
+        };
+        loop {
+            // Register for wakeups before checking, so a permit released
+            // between the check and the await isn't missed.
+            let notified = self.turn.notified();
+            tokio::pin!(notified);
+            notified.as_mut().enable();
+            if self.try_take_permit(key) {
+                self.progress.running(key);
+                return RunPermit {
+                    registry: self.clone(),
+                    kind: key.kind,
+                };
+            }
+            notified.await;
+        }
+    }
+
+    fn try_take_permit(&self, key: &JobKey) -> bool {
+        let mut queue = self.queue.lock().unwrap();
+        let running = queue.running.get(&key.kind).copied().unwrap_or(0);
+        if self
+            .limits
+            .for_kind(key.kind)
+            .is_some_and(|limit| running >= limit)
+        {
+            return false;
+        }
+        let waiting = queue.waiting.entry(key.kind).or_default();
+        match waiting.front() {
+            Some(head) if *head == key.concert_id => {
+                waiting.pop_front();
+            }
+            _ if waiting.contains(&key.concert_id) => return false,
+            _ => {}
+        }
+        *queue.running.entry(key.kind).or_default() += 1;
+        true
+    }
+
+    /// Concert ids waiting for a `kind` permit, next-to-run first.
+    pub fn waiting(&self, kind: JobKind) -> Vec<i64> {
+        self.queue
+            .lock()
+            .unwrap()
+            .waiting
+            .get(&kind)
+            .map(|waiting| waiting.iter().copied().collect())
+            .unwrap_or_default()
+    }
+
+    /// 1-based position of `key` in its kind's queue, `None` when it isn't
+    /// waiting (running, or not a Job Run at all).
+    pub fn queue_position(&self, key: &JobKey) -> Option<usize> {
+        self.queue
+            .lock()
+            .unwrap()
+            .waiting
+            .get(&key.kind)?
+            .iter()
+            .position(|id| *id == key.concert_id)
+            .map(|index| index + 1)
+    }
+
+    /// Move a waiting run one place toward the head (`up`) or the tail.
+    /// Returns `false` when it isn't waiting or is already at that end.
+    pub fn move_waiting(&self, key: &JobKey, up: bool) -> bool {
+        let moved = {
+            let mut queue = self.queue.lock().unwrap();
+            let Some(waiting) = queue.waiting.get_mut(&key.kind) else {
+                return false;
+            };
+            let Some(index) = waiting.iter().position(|id| *id == key.concert_id) else {
+                return false;
+            };
+            let other = if up {
+                index.checked_sub(1)
+            } else {
+                Some(index + 1).filter(|other| *other < waiting.len())
+            };
+            match other {
+                Some(other) => {
+                    waiting.swap(index, other);
+                    true
+                }
+                None => false,
+            }
+        };
+        if moved {
+            self.turn.notify_waiters();
         }
+        moved
     }
 
     /// True for a Reserved slot (admission in progress) or an unfinished
@@ -201,6 +442,7 @@ impl JobRegistry {
     /// handling completes.
     pub fn release(&self, key: &JobKey) {
         self.slots.lock().unwrap().remove(key);
+        self.progress.finish(key);
     }
 
     /// Abort `key`'s handle if it is still running, then remove the slot.
@@ -208,6 +450,11 @@ impl JobRegistry {
     /// `key` — the abort is a courtesy to stop wasted work, not what makes
     /// the terminal outcome exclusive (the gate does that).
     pub fn abort_and_release(&self, key: &JobKey) {
+        // The aborted task's `WaitingEntry` would also do this, but only once
+        // the task is dropped; do it now so the queue never shows it.
+        if self.queue.lock().unwrap().remove_waiting(key) {
+            self.turn.notify_waiters();
+        }
         if let Some(slot) = self.slots.lock().unwrap().remove(key) {
             if let Some(handle) = slot.handle {
                 if !handle.is_finished() {
@@ -215,6 +462,7 @@ impl JobRegistry {
                 }
             }
         }
+        self.progress.finish(key);
     }
 
     /// The terminal gate for `key`, if `key` names an *accepted* Job Run
@@ -281,13 +529,13 @@ impl JobRegistry {
         dropped_any
     }
 
-    /// Abort all running tasks and drop all queued dependents. Returns the
-    /// number of tasks aborted.
+    /// Abort all running tasks and drop all queued dependents and waiting
+    /// runs. Returns the number of tasks aborted.
     pub fn cancel_all(&self) -> usize {
         self.dependents.lock().unwrap().clear();
-        let mut slots = self.slots.lock().unwrap();
+        self.queue.lock().unwrap().waiting.clear();
         let mut count = 0;
-        for (_, slot) in slots.drain() {
+        for (_, slot) in self.slots.lock().unwrap().drain() {
             if let Some(handle) = slot.handle {
                 if !handle.is_finished() {
                     handle.abort();
@@ -295,6 +543,7 @@ impl JobRegistry {
                 }
             }
         }
+        self.progress.finish_all();
         count
     }
 }
@@ -361,6 +610,60 @@ pub fn spawn_dependents(
     }
 }
 
+/// Resubmit the Job Runs a previous process accepted but left waiting for a
+/// concurrency slot (`job_queue`), in queue order per kind. Runs once at
+/// startup, before the router serves requests, so they keep their place
+/// ahead of anything submitted afterwards. Returns how many were resumed.
+pub async fn resume_queued(
+    db: Arc<Mutex<rusqlite::Connection>>,
+    registry: Arc<JobRegistry>,
+    config: JobConfig,
+) -> anyhow::Result<usize> {
+    let queued = {
+        let conn = db.lock().unwrap();
+        let queued = crate::db::job_queue::list_queued(&conn)?;
+        // Resubmitting re-enqueues each run at the tail of its kind's queue.
+        crate::db::job_queue::clear(&conn)?;
+        queued
+    };
+    let mut resumed = 0;
+    for job in queued {
+        let concert_id = job.key.concert_id;
+        let payload = job.payload.as_deref();
+        let result = match job.key.kind {
+            JobKind::Download => {
+                download::resume_download(db.clone(), registry.clone(), config.clone(), concert_id)
+                    .await
+                    .map(|outcome| matches!(outcome, download::StartOutcome::Spawned))
+            }
+            JobKind::Split => split::resume_split(
+                db.clone(),
+                registry.clone(),
+                config.clone(),
+                concert_id,
+                payload,
+            )
+            .await
+            .map(|outcome| matches!(outcome, split::StartOutcome::Spawned)),
+            JobKind::Archive => archive::resume_archive(
+                db.clone(),
+                registry.clone(),
+                &config.working_dir,
+                concert_id,
+                payload,
+            )
+            .await
+            .map(|outcome| matches!(outcome, archive::StartOutcome::Spawned)),
+        };
+        match result {
+            Ok(true) => resumed += 1,
+            Ok(false) => tracing::warn!(key = ?job.key, "queued job was not resumed"),
+            Err(e) => tracing::warn!(key = ?job.key, "failed to resume queued job: {:#}", e),
+        }
+    }
+    Ok(resumed)
+}
+
 impl Default for JobRegistry {
     fn default() -> Self {
         Self::new()
@@ -372,6 +675,9 @@ pub struct DownloadJob {
     pub source_url: String,
     pub album: String,
     pub working_dir: PathBuf,
+    /// The configured [`DownloadFormat`]; `Audio` fetches only the best audio
+    /// stream, which the splitter cuts into `.m4a` tracks alone.
+    pub format: DownloadFormat,
 }
 
 /// How tracks should be split: automated analysis, user-supplied timestamps, or
@@ -414,6 +720,19 @@ pub struct SplitJob {
     /// `concerts/<album>/` layout this is the concert directory itself.
     pub output_dir: PathBuf,
     pub mode: SplitMode,
+    /// Directory for the splitter's diagnostic `report.html`; see
+    /// [`JobConfig::split_report_dir`].
+    pub report_dir: PathBuf,
+    /// Audio files cut per track, from the `audio_formats` setting.
+    pub audio_formats: Vec<AudioFormat>,
+    /// Downscaled video copies per track, from the `renditions` setting.
+    pub renditions: Vec<Rendition>,
+    /// Package the concert for HLS streaming too, from the `hls_packaging`
+    /// setting.
+    pub hls: bool,
+    /// The concert's scraped preview image ([`crate::scrape::preview_image_path`]),
+    /// embedded as the tracks' cover art when it exists.
+    pub cover_art: PathBuf,
     /// Kept alive so the temp file isn't deleted before the splitter reads it.
     pub _temp_file: tempfile::NamedTempFile,
     /// Timestamps temp file for user/reset modes; kept alive alongside _temp_file.
@@ -468,12 +787,14 @@ pub trait JobRunner: Send + Sync {
         &'a self,
         job: &'a DownloadJob,
         log_file: Option<&'a Path>,
+        progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome>;
 
     fn run_split<'a>(
         &'a self,
         job: &'a SplitJob,
         log_file: Option<&'a Path>,
+        progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome>;
 
     fn open_media<'a>(
@@ -524,14 +845,18 @@ impl JobRunner for ProductionJobRunner {
         &'a self,
         job: &'a DownloadJob,
         log_file: Option<&'a Path>,
+        progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome> {
         Box::pin(async move {
             let cmd = (self.download_cmd)(job);
+            progress.phase("Downloading");
+            let progress = progress.clone();
             command_job_outcome(
                 cmd,
                 "download",
                 job.concert_id,
                 log_file,
+                move |line| report_yt_dlp_line(&progress, line),
                 ". Is yt-dlp installed? See: https://github.com/yt-dlp/yt-dlp#installation",
             )
             .await
@@ -542,15 +867,17 @@ impl JobRunner for ProductionJobRunner {
         &'a self,
         job: &'a SplitJob,
         log_file: Option<&'a Path>,
+        progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome> {
         match &self.split {
             SplitBackend::Command(split_cmd) => {
                 let cmd = split_cmd(job);
                 Box::pin(async move {
+                    progress.phase("Splitting");
                     split_command_outcome(cmd, job.concert_id, log_file, &job.outcome_path).await
                 })
             }
-            SplitBackend::Library => Box::pin(split_library::run(job, log_file)),
+            SplitBackend::Library => Box::pin(split_library::run(job, log_file, progress)),
         }
     }
 
@@ -630,14 +957,30 @@ async fn split_command_outcome(
     }
 }
 
+/// Turn a line of yt-dlp's stdout into download progress: a `[download]`
+/// percent line, or the `[Merger]` step that follows separate video and audio
+/// downloads. Returns whether the line was a percent tick, which only the
+/// progress stream needs.
+fn report_yt_dlp_line(progress: &ProgressReporter, line: &str) -> bool {
+    if let Some((percent, eta)) = progress::parse_yt_dlp_progress(line) {
+        progress.download(percent, eta);
+        return true;
+    }
+    if line.starts_with("[Merger]") {
+        progress.phase("Merging formats");
+    }
+    false
+}
+
 async fn command_job_outcome(
     cmd: Command,
     kind: &'static str,
     concert_id: i64,
     log_file: Option<&Path>,
+    on_stdout: impl FnMut(&str) -> bool + Send + 'static,
     not_found_hint: &'static str,
 ) -> JobStepOutcome {
-    match run_with_logging(cmd, kind, concert_id, log_file).await {
+    match run_with_logging_observed(cmd, kind, concert_id, log_file, on_stdout).await {
         Ok((status, _)) if status.success() => JobStepOutcome::Succeeded,
         Ok((status, stderr_tail)) => JobStepOutcome::Failed(JobStepFailure::ordinary(format!(
             "exit {:?}: {}",
@@ -657,10 +1000,50 @@ async fn command_job_outcome(
     }
 }
 
+/// Automatic retry of transient download failures: at most `max_attempts`
+/// runs in total, waiting `initial_delay` before the second, doubling for
+/// each one after, capped at `max_delay`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_delay: std::time::Duration,
+    pub max_delay: std::time::Duration,
+}
+
+impl RetryPolicy {
+    /// Every failure is final.
+    pub const NONE: RetryPolicy = RetryPolicy {
+        max_attempts: 1,
+        initial_delay: std::time::Duration::ZERO,
+        max_delay: std::time::Duration::ZERO,
+    };
+
+    /// How long to wait after failed attempt `attempt` (1-based) before the
+    /// next one, or `None` when `attempt` was the last allowed.
+    pub fn delay_after(&self, attempt: u32) -> Option<std::time::Duration> {
+        if attempt >= self.max_attempts {
+            return None;
+        }
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+        Some(
+            self.initial_delay
+                .saturating_mul(factor)
+                .min(self.max_delay),
+        )
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::NONE
+    }
+}
+
 #[derive(Clone)]
 pub struct JobConfig {
     pub working_dir: PathBuf,
     runner: Arc<dyn JobRunner>,
+    download_retry: RetryPolicy,
 }
 
 /// How to invoke the CLI (subprocess) splitter adapter, resolved by
@@ -790,6 +1173,20 @@ fn build_cli_split_command(resolved: &SplitterCli, job: &SplitJob) -> Command {
             .arg("--media-duration")
             .arg(media_duration.to_string());
     }
+    // Mirrors the library adapter's `LoudnessMode::Measure` and `posters`
+    // (jobs::split_library).
+    cmd.arg("--loudness").arg("measure");
+    cmd.arg("--posters");
+    cmd.arg("--audio-format")
+        .arg(crate::db::settings::audio_formats_str(&job.audio_formats));
+    if !job.renditions.is_empty() {
+        cmd.arg("--renditions")
+            .arg(crate::db::settings::renditions_str(&job.renditions));
+    }
+    if job.hls {
+        cmd.arg("--hls").arg("alongside");
+    }
+    cmd.arg("--report").arg(&job.report_dir);
     cmd.arg("--outcome-file").arg(&job.outcome_path);
     cmd
 }
@@ -843,13 +1240,42 @@ impl JobConfig {
         self.working_dir.join("log").join("job")
     }
 
+    /// Where the splitter writes its diagnostic report for `concert_id`; each
+    /// split replaces the previous one.
+    pub fn split_report_dir(&self, concert_id: i64) -> PathBuf {
+        self.working_dir
+            .join("log")
+            .join("split-report")
+            .join(concert_id.to_string())
+    }
+
+    /// The last split's report for `concert_id`, when there is one.
+    pub fn split_report(&self, concert_id: i64) -> Option<PathBuf> {
+        Some(
+            self.split_report_dir(concert_id)
+                .join(live_set_splitter::report::REPORT_FILENAME),
+        )
+        .filter(|path| path.is_file())
+    }
+
     pub fn with_runner(working_dir: PathBuf, runner: Arc<dyn JobRunner>) -> Self {
         Self {
             working_dir,
             runner,
+            download_retry: RetryPolicy::NONE,
         }
     }
 
+    /// Retry transient download failures per `policy` (default: never).
+    pub fn with_download_retry(mut self, policy: RetryPolicy) -> Self {
+        self.download_retry = policy;
+        self
+    }
+
+    pub fn download_retry(&self) -> RetryPolicy {
+        self.download_retry
+    }
+
     /// Low-level constructor exposing the full [`SplitBackend`] choice
     /// (`Command` or `Library`). `from_commands` below is the common case
     /// (always `Command`); this is for the library backend's own tests and for
@@ -880,12 +1306,22 @@ impl JobConfig {
         )
     }
 
-    pub async fn run_download(&self, job: &DownloadJob, log_file: Option<&Path>) -> JobStepOutcome {
-        self.runner.run_download(job, log_file).await
+    pub async fn run_download(
+        &self,
+        job: &DownloadJob,
+        log_file: Option<&Path>,
+        progress: &ProgressReporter,
+    ) -> JobStepOutcome {
+        self.runner.run_download(job, log_file, progress).await
     }
 
-    pub async fn run_split(&self, job: &SplitJob, log_file: Option<&Path>) -> JobStepOutcome {
-        self.runner.run_split(job, log_file).await
+    pub async fn run_split(
+        &self,
+        job: &SplitJob,
+        log_file: Option<&Path>,
+        progress: &ProgressReporter,
+    ) -> JobStepOutcome {
+        self.runner.run_split(job, log_file, progress).await
     }
 
     pub async fn open_media(&self, concert_id: i64, path: &Path) -> OpenMediaOutcome {
@@ -924,9 +1360,7 @@ impl JobConfig {
                     .join(format!("{}.%(ext)s", sanitize_album(&job.album)))
                     .to_string_lossy()
                     .to_string();
-                let mut cmd = Command::new("yt-dlp");
-                cmd.arg("-o").arg(out).arg(&job.source_url);
-                cmd
+                build_yt_dlp_command(job, &out)
             }),
             split,
             Arc::new(move |path: &Path| {
@@ -938,6 +1372,20 @@ impl JobConfig {
     }
 }
 
+/// The yt-dlp invocation downloading `job` to the `out` template.
+fn build_yt_dlp_command(job: &DownloadJob, out: &str) -> Command {
+    let mut cmd = Command::new("yt-dlp");
+    // One progress update per line, so `report_yt_dlp_line`
+    // sees each (a pipe otherwise gets `\r`-joined updates).
+    cmd.arg("--newline");
+    if job.format == DownloadFormat::Audio {
+        // Prefer AAC in an .m4a, which the splitter stream-copies into tracks.
+        cmd.arg("-f").arg("bestaudio[ext=m4a]/bestaudio");
+    }
+    cmd.arg("-o").arg(out).arg(&job.source_url);
+    cmd
+}
+
 /// How many of the most recent stderr lines to retain for the DB error message
 /// when a child process exits non-zero. Bounded so a chatty subprocess can't
 /// blow up memory or the `*_errors` column.
@@ -984,10 +1432,24 @@ macro_rules! log_child_line {
 /// (prefixed with `[stdout]` or `[stderr]`). I/O errors on the log file
 /// are warned but do not fail the job.
 pub async fn run_with_logging(
+    cmd: Command,
+    kind: &'static str,
+    concert_id: i64,
+    log_file: Option<&Path>,
+) -> std::io::Result<(ExitStatus, String)> {
+    run_with_logging_observed(cmd, kind, concert_id, log_file, |_| false).await
+}
+
+/// [`run_with_logging`], also handing each stdout line to `on_stdout` as it
+/// arrives (live progress parsing). A line `on_stdout` returns `true` for is
+/// a progress tick: it is traced at `trace` only and kept out of the log
+/// file, since a download prints hundreds of them.
+pub async fn run_with_logging_observed(
     mut cmd: Command,
     kind: &'static str,
     concert_id: i64,
     log_file: Option<&Path>,
+    mut on_stdout: impl FnMut(&str) -> bool + Send + 'static,
 ) -> std::io::Result<(ExitStatus, String)> {
     cmd.stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -1010,6 +1472,10 @@ pub async fn run_with_logging(
     let stdout_task: JoinHandle<()> = tokio::spawn(async move {
         let mut lines = BufReader::new(stdout).lines();
         while let Ok(Some(line)) = lines.next_line().await {
+            if on_stdout(&line) {
+                tracing::trace!(kind, concert_id, stream = "stdout", "{}", line);
+                continue;
+            }
             log_child_line!(kind, concert_id, "stdout", line);
             if let Some(ref f) = log_for_stdout {
                 if let Ok(mut f) = f.lock() {
@@ -1048,6 +1514,7 @@ pub async fn run_with_logging(
 pub fn download_job_from_concert(
     concert: &Concert,
     working_dir: &Path,
+    format: DownloadFormat,
 ) -> anyhow::Result<DownloadJob> {
     let album = concert.album.as_deref().unwrap_or(&concert.title);
     Ok(DownloadJob {
@@ -1055,6 +1522,7 @@ pub fn download_job_from_concert(
         source_url: concert.source_url.clone(),
         album: album.to_string(),
         working_dir: working_dir.to_path_buf(),
+        format,
     })
 }
 
@@ -1454,6 +1922,26 @@ mod tests {
         assert!(content.contains("[stderr] err2"));
     }
 
+    #[tokio::test]
+    async fn progress_ticks_stay_out_of_the_log_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let log_path = dir.path().join("test.log");
+        let mut cmd = Command::new("sh");
+        cmd.args([
+            "-c",
+            "echo '[download] Destination: x.mp4'; echo '[download]  42.0% of 1.00MiB ETA 00:01'",
+        ]);
+        let (status, _) = run_with_logging_observed(cmd, "download", 1, Some(&log_path), |line| {
+            progress::parse_yt_dlp_progress(line).is_some()
+        })
+        .await
+        .unwrap();
+        assert!(status.success());
+        let content = std::fs::read_to_string(&log_path).unwrap();
+        assert!(content.contains("[stdout] [download] Destination: x.mp4"));
+        assert!(!content.contains("42.0%"));
+    }
+
     #[tokio::test]
     async fn run_with_logging_without_log_file_still_works() {
         let cmd = Command::new("true");
@@ -1605,6 +2093,45 @@ mod tests {
         assert!(result.unwrap_err().contains("release build"));
     }
 
+    #[test]
+    fn yt_dlp_command_selects_audio_only_formats_when_configured() {
+        let mut job = DownloadJob {
+            concert_id: 1,
+            source_url: "https://npr.org/test".to_string(),
+            album: "Album".to_string(),
+            working_dir: PathBuf::from("/tmp"),
+            format: DownloadFormat::Video,
+        };
+        let args = |job: &DownloadJob| -> Vec<String> {
+            build_yt_dlp_command(job, "/tmp/Album.%(ext)s")
+                .as_std()
+                .get_args()
+                .map(|a| a.to_string_lossy().into_owned())
+                .collect()
+        };
+        assert_eq!(
+            args(&job),
+            vec![
+                "--newline",
+                "-o",
+                "/tmp/Album.%(ext)s",
+                "https://npr.org/test"
+            ]
+        );
+        job.format = DownloadFormat::Audio;
+        assert_eq!(
+            args(&job),
+            vec![
+                "--newline",
+                "-f",
+                "bestaudio[ext=m4a]/bestaudio",
+                "-o",
+                "/tmp/Album.%(ext)s",
+                "https://npr.org/test"
+            ]
+        );
+    }
+
     #[test]
     fn build_cli_split_command_for_executable_uses_it_directly() {
         let job = test_split_job(SplitMode::Analyze);
@@ -1624,6 +2151,17 @@ mod tests {
                 job.input_file.to_string_lossy().into_owned(),
                 "--output-dir".to_string(),
                 job.output_dir.to_string_lossy().into_owned(),
+                "--loudness".to_string(),
+                "measure".to_string(),
+                "--posters".to_string(),
+                "--audio-format".to_string(),
+                "m4a,flac".to_string(),
+                "--renditions".to_string(),
+                "480p".to_string(),
+                "--hls".to_string(),
+                "alongside".to_string(),
+                "--report".to_string(),
+                job.report_dir.to_string_lossy().into_owned(),
                 "--outcome-file".to_string(),
                 job.outcome_path.to_string_lossy().into_owned(),
             ]
@@ -1710,11 +2248,17 @@ mod tests {
                 preview_image_url: None,
                 teaser: None,
                 timestamps: None,
+                interludes: None,
             },
             json_path,
             input_file: PathBuf::from("/media/input.mp4"),
             output_dir: PathBuf::from("/media/output"),
             mode,
+            report_dir: PathBuf::from("/media/split-report"),
+            audio_formats: vec![AudioFormat::M4a, AudioFormat::Flac],
+            renditions: vec![Rendition::P480],
+            hls: true,
+            cover_art: PathBuf::from("/media/output/preview.jpg"),
             _temp_file: temp_file,
             _timestamps_temp_file: None,
             timestamps_path: None,
@@ -1722,4 +2266,131 @@ mod tests {
             _outcome_file: outcome_file,
         }
     }
+
+    fn limited(download: usize) -> Arc<JobRegistry> {
+        Arc::new(JobRegistry::with_limits(JobLimits {
+            download: Some(download),
+            ..JobLimits::default()
+        }))
+    }
+
+    /// Whether `fut` completes within a few scheduler turns.
+    async fn ready_soon<F: Future>(fut: F) -> Option<F::Output> {
+        tokio::time::timeout(std::time::Duration::from_millis(50), fut)
+            .await
+            .ok()
+    }
+
+    #[tokio::test]
+    async fn run_permits_are_limited_per_kind_and_granted_in_queue_order() {
+        let registry = limited(1);
+        for id in 1..=3 {
+            registry.enqueue(&dl_key_n(id));
+        }
+        assert_eq!(registry.waiting(JobKind::Download), vec![1, 2, 3]);
+
+        // Not at the head: waits even though a slot is free.
+        let second = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.run_permit(&dl_key_n(2)).await }
+        });
+        tokio::task::yield_now().await;
+        assert!(!second.is_finished());
+
+        let first = ready_soon(registry.run_permit(&dl_key_n(1))).await.unwrap();
+        assert_eq!(registry.waiting(JobKind::Download), vec![2, 3]);
+        assert_eq!(registry.queue_position(&dl_key_n(3)), Some(2));
+
+        // Now at the head, but the only download slot is taken.
+        tokio::task::yield_now().await;
+        assert!(!second.is_finished());
+
+        // Other kinds are not limited.
+        let split = JobKey {
+            concert_id: 9,
+            kind: JobKind::Split,
+        };
+        assert!(ready_soon(registry.run_permit(&split)).await.is_some());
+
+        drop(first);
+        let _second = ready_soon(second).await.unwrap().unwrap();
+        assert_eq!(registry.waiting(JobKind::Download), vec![3]);
+    }
+
+    #[tokio::test]
+    async fn move_waiting_reorders_and_abort_leaves_the_queue() {
+        let registry = limited(1);
+        for id in 1..=3 {
+            registry.enqueue(&dl_key_n(id));
+        }
+        assert!(registry.move_waiting(&dl_key_n(3), true));
+        assert_eq!(registry.waiting(JobKind::Download), vec![1, 3, 2]);
+        assert!(!registry.move_waiting(&dl_key_n(1), true), "already first");
+        assert!(!registry.move_waiting(&dl_key_n(2), false), "already last");
+        assert!(!registry.move_waiting(&dl_key_n(7), true), "not queued");
+
+        // A waiting task that is dropped (aborted) gives up its place.
+        let waiter = tokio::spawn({
+            let registry = registry.clone();
+            async move { registry.run_permit(&dl_key_n(3)).await }
+        });
+        tokio::task::yield_now().await;
+        waiter.abort();
+        let _ = waiter.await;
+        assert_eq!(registry.waiting(JobKind::Download), vec![1, 2]);
+
+        registry.abort_and_release(&dl_key_n(1));
+        assert_eq!(registry.waiting(JobKind::Download), vec![2]);
+        assert!(ready_soon(registry.run_permit(&dl_key_n(2)))
+            .await
+            .is_some());
+    }
+
+    #[test]
+    fn retry_delay_doubles_up_to_the_cap_and_stops_at_the_limit() {
+        use std::time::Duration;
+        let policy = RetryPolicy {
+            max_attempts: 5,
+            initial_delay: Duration::from_secs(30),
+            max_delay: Duration::from_secs(90),
+        };
+        assert_eq!(policy.delay_after(1), Some(Duration::from_secs(30)));
+        assert_eq!(policy.delay_after(2), Some(Duration::from_secs(60)));
+        assert_eq!(policy.delay_after(3), Some(Duration::from_secs(90)));
+        assert_eq!(policy.delay_after(4), Some(Duration::from_secs(90)));
+        assert_eq!(policy.delay_after(5), None);
+        assert_eq!(RetryPolicy::NONE.delay_after(1), None);
+    }
+
+    #[tokio::test]
+    async fn registry_publishes_queued_running_and_finished_progress() {
+        use progress::ProgressState;
+        let registry = Arc::new(JobRegistry::new());
+        let (_, mut rx) = registry.progress().subscribe();
+        let key = dl_key_n(1);
+
+        registry.enqueue(&key);
+        let permit = registry.run_permit(&key).await;
+        registry.progress_reporter(&key).phase("Downloading");
+        assert_eq!(
+            registry.progress().get(&key).unwrap().phase.as_deref(),
+            Some("Downloading")
+        );
+        registry.release(&key);
+        drop(permit);
+        assert!(registry.progress().get(&key).is_none());
+
+        let states: Vec<ProgressState> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|p| p.state)
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ProgressState::Queued,
+                ProgressState::Running,
+                ProgressState::Running,
+                ProgressState::Finished,
+            ]
+        );
+    }
 }
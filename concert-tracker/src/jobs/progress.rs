@@ -0,0 +1,307 @@
+//! Live Job Run progress for the `/jobs/progress` Server-Sent Events stream.
+//!
+//! [`ProgressHub`] keeps the latest [`JobProgress`] of every queued or running
+//! Job Run and broadcasts each change. `JobRegistry` owns the hub and reports
+//! the lifecycle transitions itself (queued on `enqueue`, running once a
+//! permit is taken, finished on release), so those are published for every
+//! job kind and runner. Finer detail — the split phase, "k of total" tracks,
+//! yt-dlp's percent and ETA — comes from the runner through the
+//! [`ProgressReporter`] handed to `JobRequest::execute`.
+//!
+//! Progress is in-memory only; nothing here is persisted or affects a run's
+//! outcome.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use serde::Serialize;
+use tokio::sync::broadcast;
+
+use super::JobKey;
+
+/// Updates a slow subscriber may fall behind by before it skips ahead
+/// (`RecvError::Lagged`). Percent updates are frequent but superseded by the
+/// next one, so losing some is harmless.
+const CHANNEL_CAPACITY: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProgressState {
+    /// Accepted and waiting for a slot (see `JobRegistry::run_permit`).
+    Queued,
+    Running,
+    /// The run's slot was released; this is the last update for the run.
+    Finished,
+}
+
+/// The latest known progress of one Job Run, as sent to SSE clients.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct JobProgress {
+    pub concert_id: i64,
+    pub kind: &'static str,
+    pub state: ProgressState,
+    /// Human-readable current step ("Downloading", "Cutting tracks").
+    pub phase: Option<String>,
+    pub tracks_done: Option<usize>,
+    pub tracks_total: Option<usize>,
+    /// Download percent of the current file, 0–100.
+    pub percent: Option<f64>,
+    /// yt-dlp's ETA for the current file, as printed (`00:42`).
+    pub eta: Option<String>,
+}
+
+impl JobProgress {
+    fn new(key: &JobKey, state: ProgressState) -> Self {
+        JobProgress {
+            concert_id: key.concert_id,
+            kind: key.kind.as_str(),
+            state,
+            phase: None,
+            tracks_done: None,
+            tracks_total: None,
+            percent: None,
+            eta: None,
+        }
+    }
+}
+
+pub struct ProgressHub {
+    latest: Mutex<HashMap<JobKey, JobProgress>>,
+    tx: broadcast::Sender<JobProgress>,
+}
+
+impl Default for ProgressHub {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ProgressHub {
+    pub fn new() -> Self {
+        ProgressHub {
+            latest: Mutex::new(HashMap::new()),
+            tx: broadcast::channel(CHANNEL_CAPACITY).0,
+        }
+    }
+
+    /// The current progress of every queued or running Job Run, followed by
+    /// a receiver for every later change. Taken under one lock so no update
+    /// falls between the snapshot and the subscription.
+    pub fn subscribe(&self) -> (Vec<JobProgress>, broadcast::Receiver<JobProgress>) {
+        let latest = self.latest.lock().unwrap();
+        let rx = self.tx.subscribe();
+        let mut snapshot: Vec<JobProgress> = latest.values().cloned().collect();
+        snapshot.sort_by_key(|p| (p.kind, p.concert_id));
+        (snapshot, rx)
+    }
+
+    /// Progress of `key`, if it's queued or running.
+    pub fn get(&self, key: &JobKey) -> Option<JobProgress> {
+        self.latest.lock().unwrap().get(key).cloned()
+    }
+
+    /// Start `key`'s record as Queued.
+    pub(crate) fn queued(&self, key: &JobKey) {
+        let progress = JobProgress::new(key, ProgressState::Queued);
+        self.latest
+            .lock()
+            .unwrap()
+            .insert(key.clone(), progress.clone());
+        let _ = self.tx.send(progress);
+    }
+
+    /// Move `key` from Queued to Running. Only an existing record moves: a run
+    /// cancelled between taking its permit and getting here has already been
+    /// finished and must not reappear.
+    pub(crate) fn running(&self, key: &JobKey) {
+        let mut latest = self.latest.lock().unwrap();
+        let Some(progress) = latest.get_mut(key) else {
+            return;
+        };
+        *progress = JobProgress::new(key, ProgressState::Running);
+        let _ = self.tx.send(progress.clone());
+    }
+
+    /// Drop `key`'s record and tell subscribers it finished. A no-op for a
+    /// key with no record, so releasing twice sends one update.
+    pub(crate) fn finish(&self, key: &JobKey) {
+        if self.latest.lock().unwrap().remove(key).is_some() {
+            let _ = self.tx.send(JobProgress::new(key, ProgressState::Finished));
+        }
+    }
+
+    pub(crate) fn finish_all(&self) {
+        let keys: Vec<JobKey> = self.latest.lock().unwrap().keys().cloned().collect();
+        for key in keys {
+            self.finish(&key);
+        }
+    }
+
+    /// Apply `update` to `key`'s record while it's running. Updates for a run
+    /// that already finished — e.g. from a library split's blocking thread
+    /// that outlives its cancelled task — are dropped.
+    fn update(&self, key: &JobKey, update: impl FnOnce(&mut JobProgress)) {
+        let mut latest = self.latest.lock().unwrap();
+        let Some(progress) = latest.get_mut(key) else {
+            return;
+        };
+        if progress.state != ProgressState::Running {
+            return;
+        }
+        update(progress);
+        let _ = self.tx.send(progress.clone());
+    }
+
+    pub fn reporter(self: &Arc<Self>, key: &JobKey) -> ProgressReporter {
+        ProgressReporter {
+            hub: Some(self.clone()),
+            key: key.clone(),
+        }
+    }
+}
+
+/// Handle a runner uses to report one Job Run's progress. Cheap to clone and
+/// `'static`, so it can move onto a blocking thread.
+#[derive(Clone)]
+pub struct ProgressReporter {
+    hub: Option<Arc<ProgressHub>>,
+    key: JobKey,
+}
+
+impl ProgressReporter {
+    /// A reporter that publishes nothing, for runners driven outside a
+    /// registry (the Job Driver's own tests).
+    pub fn detached(key: JobKey) -> Self {
+        ProgressReporter { hub: None, key }
+    }
+
+    fn update(&self, update: impl FnOnce(&mut JobProgress)) {
+        if let Some(hub) = &self.hub {
+            hub.update(&self.key, update);
+        }
+    }
+
+    /// Start a new step. Clears any download percent from the previous one.
+    pub fn phase(&self, phase: impl Into<String>) {
+        let phase = phase.into();
+        self.update(|p| {
+            p.phase = Some(phase);
+            p.percent = None;
+            p.eta = None;
+        });
+    }
+
+    pub fn tracks(&self, done: usize, total: usize) {
+        self.update(|p| {
+            p.tracks_done = Some(done);
+            p.tracks_total = Some(total);
+        });
+    }
+
+    pub fn download(&self, percent: f64, eta: Option<String>) {
+        self.update(|p| {
+            p.percent = Some(percent);
+            p.eta = eta;
+        });
+    }
+}
+
+/// Parse one of yt-dlp's `--newline` progress lines:
+///
+/// ```text
+/// [download]  42.3% of ~ 123.45MiB at    1.23MiB/s ETA 00:12 (frag 3/40)
+/// [download] 100% of  123.45MiB in 00:01:40 at 1.23MiB/s
+/// ```
+///
+/// Returns the percent and the ETA, when one is printed.
+pub fn parse_yt_dlp_progress(line: &str) -> Option<(f64, Option<String>)> {
+    let rest = line.trim_start().strip_prefix("[download]")?;
+    let mut words = rest.split_whitespace();
+    let percent = words.next()?.strip_suffix('%')?.parse::<f64>().ok()?;
+    let eta = words
+        .skip_while(|word| *word != "ETA")
+        .nth(1)
+        .filter(|eta| *eta != "Unknown")
+        .map(str::to_string);
+    Some((percent, eta))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::jobs::JobKind;
+
+    fn key(concert_id: i64) -> JobKey {
+        JobKey {
+            concert_id,
+            kind: JobKind::Split,
+        }
+    }
+
+    #[test]
+    fn parses_yt_dlp_progress_lines() {
+        assert_eq!(
+            parse_yt_dlp_progress("[download]  42.3% of ~ 123.45MiB at 1.23MiB/s ETA 00:12"),
+            Some((42.3, Some("00:12".to_string())))
+        );
+        assert_eq!(
+            parse_yt_dlp_progress("[download] 100% of  123.45MiB in 00:01:40 at 1.23MiB/s"),
+            Some((100.0, None))
+        );
+        assert_eq!(
+            parse_yt_dlp_progress("[download]   0.0% of 10.00MiB at Unknown B/s ETA Unknown"),
+            Some((0.0, None))
+        );
+        assert_eq!(
+            parse_yt_dlp_progress("[download] Destination: /tmp/Album.f137.mp4"),
+            None
+        );
+        assert_eq!(parse_yt_dlp_progress("[Merger] Merging formats"), None);
+    }
+
+    #[test]
+    fn reporter_updates_only_running_jobs_and_finish_is_broadcast_once() {
+        let hub = Arc::new(ProgressHub::new());
+        let (snapshot, mut rx) = hub.subscribe();
+        assert!(snapshot.is_empty());
+        let reporter = hub.reporter(&key(1));
+
+        hub.running(&key(1));
+        assert!(
+            hub.get(&key(1)).is_none(),
+            "only a queued run starts running"
+        );
+        hub.queued(&key(1));
+        reporter.phase("ignored while queued");
+        hub.running(&key(1));
+        reporter.phase("Cutting tracks");
+        reporter.tracks(2, 5);
+
+        let progress = hub.get(&key(1)).unwrap();
+        assert_eq!(progress.phase.as_deref(), Some("Cutting tracks"));
+        assert_eq!(
+            (progress.tracks_done, progress.tracks_total),
+            (Some(2), Some(5))
+        );
+        assert_eq!(hub.subscribe().0, vec![progress]);
+
+        hub.finish(&key(1));
+        hub.finish(&key(1));
+        reporter.tracks(3, 5);
+        assert!(hub.get(&key(1)).is_none());
+
+        let states: Vec<ProgressState> = std::iter::from_fn(|| rx.try_recv().ok())
+            .map(|p| p.state)
+            .collect();
+        assert_eq!(
+            states,
+            vec![
+                ProgressState::Queued,
+                ProgressState::Running,
+                ProgressState::Running,
+                ProgressState::Running,
+                ProgressState::Finished,
+            ]
+        );
+    }
+}
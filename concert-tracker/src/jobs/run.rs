@@ -14,19 +14,25 @@ use std::path::{Path, PathBuf};
 use std::pin::Pin;
 use std::sync::{Arc, Mutex};
 use std::task::{Context, Poll};
+use std::time::Duration;
 
 use anyhow::{Context as _, Result};
 use rusqlite::Connection;
 
 use crate::db;
+use crate::jobs::progress::ProgressReporter;
 use crate::jobs::{
-    JobKey, JobRegistry, JobRunFuture, JobStepFailure, JobStepOutcome, TerminalGate,
+    JobKey, JobRegistry, JobRunFuture, JobStepFailure, JobStepOutcome, RunPermit, TerminalGate,
 };
 
 /// Shared with `crate::lifecycle::cancel_job` (which calls [`cancel`] below)
 /// so both record the same wording for a user-initiated cancellation.
 pub const CANCELLED_BY_USER: &str = "cancelled by user";
 
+/// Recorded instead of [`CANCELLED_BY_USER`] when the cancelled run was still
+/// waiting for a concurrency slot (the jobs dashboard's Dequeue).
+pub const DEQUEUED_BY_USER: &str = "removed from queue by user";
+
 /// The one small job-request interface every Job Run implementor provides.
 /// Each method maps to one phase of the Job Request → Job Run → terminal
 /// outcome lifecycle from issue #124:
@@ -71,10 +77,13 @@ pub trait JobRequest: JobCancellation + Send + Sync + 'static {
     /// Race-safe post-acceptance setup, run without holding the DB mutex.
     fn setup(&self, input: Self::Input) -> Result<Self::Setup>;
 
+    /// Runs the job's work. `progress` is this run's live-progress handle;
+    /// requests with nothing finer than running/finished to report ignore it.
     fn execute<'a>(
         &'a self,
         setup: &'a Self::Setup,
         log_file: Option<&'a Path>,
+        progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome>;
 
     /// FS/fact gathering for success. No `Connection` — this runs *before*
@@ -104,6 +113,35 @@ pub trait JobRequest: JobCancellation + Send + Sync + 'static {
     fn spawn_dependents(&self, db: Arc<Mutex<Connection>>, registry: Arc<JobRegistry>) {
         let _ = (db, registry);
     }
+
+    /// How long to wait before running `execute` again after `failure` on
+    /// 1-based `attempt`, or `None` to let the failure stand. Default: never
+    /// retry.
+    fn retry_delay(&self, failure: &JobStepFailure, attempt: u32) -> Option<Duration> {
+        let _ = (failure, attempt);
+        None
+    }
+
+    /// DB-only record that `attempt` failed and another follows after
+    /// `delay`. Runs outside any terminal transaction; the Job Run stays in
+    /// progress. Default: nothing to record.
+    fn record_retry(
+        &self,
+        conn: &Connection,
+        attempt: u32,
+        delay: Duration,
+        failure: &JobStepFailure,
+    ) -> Result<()> {
+        let _ = (conn, attempt, delay, failure);
+        Ok(())
+    }
+
+    /// What a restarted server needs, besides [`key`](JobCancellation::key),
+    /// to resubmit this request from `job_queue` (see
+    /// `crate::jobs::resume_queued`). Default: nothing.
+    fn queue_payload(&self) -> Option<String> {
+        None
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -135,6 +173,29 @@ pub async fn submit<R: JobRequest>(
     db: Arc<Mutex<Connection>>,
     registry: Arc<JobRegistry>,
     request: R,
+) -> Result<Admission> {
+    admit(db, registry, request, false).await
+}
+
+/// Resubmit a Job Request that a previous process accepted and left waiting
+/// in `job_queue`. Like [`submit`], except the started transition normally
+/// already happened — `fail_in_progress_jobs` leaves queued jobs' `*_started_at`
+/// alone — so `try_mark_started` only runs if something cleared it since. A
+/// request that no longer validates becomes a Failed Job rather than staying
+/// in progress.
+pub async fn resume<R: JobRequest>(
+    db: Arc<Mutex<Connection>>,
+    registry: Arc<JobRegistry>,
+    request: R,
+) -> Result<Admission> {
+    admit(db, registry, request, true).await
+}
+
+async fn admit<R: JobRequest>(
+    db: Arc<Mutex<Connection>>,
+    registry: Arc<JobRegistry>,
+    request: R,
+    resuming: bool,
 ) -> Result<Admission> {
     let request = Arc::new(request);
     let key = request.key();
@@ -151,13 +212,20 @@ pub async fn submit<R: JobRequest>(
         Ok(input) => input,
         Err(e) => {
             drop(reservation); // rollback: no lifecycle change was made
+            if resuming {
+                fail_unresumable(&db, request.as_ref(), &e);
+            }
             return Err(e);
         }
     };
 
     let started = {
         let conn = db.lock().unwrap();
-        request.try_mark_started(&conn)
+        if resuming && request.has_stale_in_progress(&conn).unwrap_or(false) {
+            Ok(true)
+        } else {
+            request.try_mark_started(&conn)
+        }
     };
     let started = match started {
         Ok(started) => started,
@@ -171,6 +239,18 @@ pub async fn submit<R: JobRequest>(
         return Ok(Admission::AlreadyRunning);
     }
 
+    // Join the kind's FIFO queue before spawning, so queue order is
+    // acceptance order. The DB mirror only matters when a limit can make the
+    // run wait; a failed write is logged rather than failing admission (the
+    // run still executes, it just wouldn't survive a restart while waiting).
+    registry.enqueue(&key);
+    if registry.limits().for_kind(key.kind).is_some() {
+        let conn = db.lock().unwrap();
+        if let Err(e) = db::job_queue::enqueue(&conn, &key, request.queue_payload().as_deref()) {
+            tracing::warn!(?key, "failed to persist queued job: {:#}", e);
+        }
+    }
+
     let terminal = reservation.terminal_gate();
     let run_request = request.clone();
     let run_db = db.clone();
@@ -184,6 +264,30 @@ pub async fn submit<R: JobRequest>(
     Ok(Admission::Accepted)
 }
 
+/// A resumed request failed validation: record it as a Failed Job so the
+/// concert isn't left in progress with no run behind it.
+pub(crate) fn fail_unresumable<R: JobCancellation>(
+    db: &Arc<Mutex<Connection>>,
+    request: &R,
+    error: &anyhow::Error,
+) {
+    let conn = db.lock().unwrap();
+    let result = request.has_stale_in_progress(&conn).and_then(|stale| {
+        if stale {
+            recover_failed(
+                &conn,
+                request,
+                &format!("could not resume queued {}: {error:#}", request.job_name()),
+            )
+        } else {
+            Ok(())
+        }
+    });
+    if let Err(e) = result {
+        tracing::error!(key = ?request.key(), "failed to record unresumable job: {:#}", e);
+    }
+}
+
 /// Cancel the Job Run (or queued/stale request) named by `request.key()`.
 ///
 /// Claims the terminal gate before writing anything: if the run has already
@@ -207,8 +311,16 @@ pub fn cancel<R: JobCancellation>(
         // find the gate already taken and do nothing — so from here on we
         // exclusively own this Job Run's terminal outcome.
         registry.drop_dependency_edges(&key);
-        let commit = commit_failure_tx(conn, request, &key, CANCELLED_BY_USER);
+        let message = if registry.queue_position(&key).is_some() {
+            DEQUEUED_BY_USER
+        } else {
+            CANCELLED_BY_USER
+        };
+        let commit = commit_failure_tx(conn, request, &key, message);
         registry.abort_and_release(&key);
+        if let Err(e) = db::job_queue::remove(conn, &key) {
+            tracing::warn!(?key, "failed to remove cancelled job from queue: {:#}", e);
+        }
         commit.context("Failed to commit cancelled terminal")?;
         return Ok(CancelOutcome::CancelledRunning);
     }
@@ -257,6 +369,16 @@ async fn run<R: JobRequest>(
     input: R::Input,
 ) {
     let key = request.key();
+    // Held until this function returns, whatever the outcome, except while a
+    // retry backs off (see `run_setup_and_execute`); the slot goes to the next
+    // waiting run of this kind.
+    let mut permit = Some(registry.run_permit(&key).await);
+    if registry.limits().for_kind(key.kind).is_some() {
+        let conn = db.lock().unwrap();
+        if let Err(e) = db::job_queue::remove(&conn, &key) {
+            tracing::warn!(?key, "failed to remove started job from queue: {:#}", e);
+        }
+    }
     let log_dir = request.log_dir();
     let temp_file = log_dir.as_ref().and_then(|dir| {
         match std::fs::create_dir_all(dir).and_then(|_| tempfile::NamedTempFile::new_in(dir)) {
@@ -269,7 +391,17 @@ async fn run<R: JobRequest>(
     });
     let temp_path = temp_file.as_ref().map(|f| f.path().to_path_buf());
 
-    let outcome = run_setup_and_execute(request.as_ref(), input, temp_path.as_deref()).await;
+    let progress = registry.progress_reporter(&key);
+    let outcome = run_setup_and_execute(
+        &db,
+        &registry,
+        &mut permit,
+        request.as_ref(),
+        input,
+        temp_path.as_deref(),
+        &progress,
+    )
+    .await;
 
     // Success facts are FS-only and gathered before the DB mutex is taken
     // (and before claiming the gate) so a slow working dir can't freeze
@@ -469,10 +601,21 @@ fn commit_step_failure_tx<R: JobRequest>(
 /// `kill_on_drop` on a subprocess — exactly as it did before this engine
 /// existed. A nested `tokio::spawn` would break that: aborting the outer
 /// task wouldn't stop an inner task's subprocess.
+///
+/// A failed `execute` is re-run while [`JobRequest::retry_delay`] asks for
+/// it, sleeping in between on the same task (so a cancel during the wait
+/// aborts it). `permit` is given back for the sleep, so a backoff of up to
+/// the retry cap doesn't hold a slot the kind's queued runs could use, and
+/// re-taken before the next attempt. Each attempt overwrites the log file; a
+/// failure that outlasts its retries names the attempt count in its message.
 async fn run_setup_and_execute<R: JobRequest>(
+    db: &Arc<Mutex<Connection>>,
+    registry: &Arc<JobRegistry>,
+    permit: &mut Option<RunPermit>,
     request: &R,
     input: R::Input,
     log_file: Option<&Path>,
+    progress: &ProgressReporter,
 ) -> std::result::Result<(R::Setup, JobStepOutcome), String> {
     let setup =
         match std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| request.setup(input))) {
@@ -485,12 +628,53 @@ async fn run_setup_and_execute<R: JobRequest>(
                 ))
             }
         };
-    match catch_unwind_future(request.execute(&setup, log_file)).await {
-        Ok(step) => Ok((setup, step)),
-        Err(payload) => Err(format!(
-            "job panicked during execution: {}",
-            panic_message(&payload)
-        )),
+    let mut attempt = 1;
+    loop {
+        let step = match catch_unwind_future(request.execute(&setup, log_file, progress)).await {
+            Ok(step) => step,
+            Err(payload) => {
+                return Err(format!(
+                    "job panicked during execution: {}",
+                    panic_message(&payload)
+                ))
+            }
+        };
+        let JobStepOutcome::Failed(failure) = step else {
+            return Ok((setup, step));
+        };
+        let Some(delay) = request.retry_delay(&failure, attempt) else {
+            let failure = match failure {
+                JobStepFailure::Ordinary { message } if attempt > 1 => {
+                    JobStepFailure::ordinary(format!("{message} (after {attempt} attempts)"))
+                }
+                failure => failure,
+            };
+            return Ok((setup, JobStepOutcome::Failed(failure)));
+        };
+        tracing::warn!(
+            key = ?request.key(),
+            attempt,
+            ?delay,
+            "{} attempt failed, retrying: {}",
+            request.job_name(),
+            failure.message()
+        );
+        {
+            let conn = db.lock().unwrap();
+            if let Err(e) = request.record_retry(&conn, attempt, delay, &failure) {
+                tracing::warn!(key = ?request.key(), "failed to record retry: {:#}", e);
+            }
+        }
+        progress.phase(format!(
+            "Attempt {attempt} failed, retrying in {}s",
+            delay.as_secs()
+        ));
+        drop(permit.take());
+        tokio::time::sleep(delay).await;
+        // Not re-queued: the run waits only for a free slot, not behind
+        // runs accepted after it.
+        *permit = Some(registry.run_permit(&request.key()).await);
+        attempt += 1;
     }
 }
 
@@ -539,6 +723,8 @@ mod tests {
         setup_panics: bool,
         commit_success_fails: bool,
         dependents_spawned: Arc<AtomicUsize>,
+        /// Retry a failed first attempt after this long; the retry succeeds.
+        retry_after: Option<Duration>,
     }
 
     enum StepResult {
@@ -557,6 +743,7 @@ mod tests {
                 setup_panics: false,
                 commit_success_fails: false,
                 dependents_spawned: Arc::new(AtomicUsize::new(0)),
+                retry_after: None,
             }
         }
 
@@ -618,13 +805,13 @@ mod tests {
             &'a self,
             _setup: &'a (),
             _log_file: Option<&'a Path>,
+            _progress: &'a ProgressReporter,
         ) -> JobRunFuture<'a, JobStepOutcome> {
-            let rx = self
-                .block
-                .lock()
-                .unwrap()
-                .take()
-                .expect("execute called twice");
+            let rx = self.block.lock().unwrap().take();
+            let Some(rx) = rx else {
+                assert!(self.retry_after.is_some(), "execute called twice");
+                return Box::pin(async { JobStepOutcome::Succeeded });
+            };
             Box::pin(async move {
                 match rx.await {
                     Ok(StepResult::Succeed) => JobStepOutcome::Succeeded,
@@ -651,6 +838,10 @@ mod tests {
         fn spawn_dependents(&self, _db: Arc<Mutex<Connection>>, _registry: Arc<JobRegistry>) {
             self.dependents_spawned.fetch_add(1, Ordering::SeqCst);
         }
+
+        fn retry_delay(&self, _failure: &JobStepFailure, attempt: u32) -> Option<Duration> {
+            self.retry_after.filter(|_| attempt == 1)
+        }
     }
 
     /// A request whose `validate` always rejects synchronously.
@@ -694,6 +885,7 @@ mod tests {
             &'a self,
             _setup: &'a (),
             _log_file: Option<&'a Path>,
+            _progress: &'a ProgressReporter,
         ) -> JobRunFuture<'a, JobStepOutcome> {
             unreachable!()
         }
@@ -1009,6 +1201,7 @@ mod tests {
             setup_panics: false,
             commit_success_fails: false,
             dependents_spawned: Arc::new(AtomicUsize::new(0)),
+            retry_after: None,
         };
         let outcome = {
             let conn = db.lock().unwrap();
@@ -1057,6 +1250,7 @@ mod tests {
             setup_panics: false,
             commit_success_fails: false,
             dependents_spawned: Arc::new(AtomicUsize::new(0)),
+            retry_after: None,
         };
         let outcome = {
             let conn = db.lock().unwrap();
@@ -1102,6 +1296,140 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn limited_kind_runs_in_queue_order_and_dequeue_is_recorded() {
+        let (db, first) = seeded_db();
+        let [second, third] = ["2", "3"].map(|n| {
+            let conn = db.lock().unwrap();
+            SeedContext::new(&conn)
+                .seed_scraped_concert(db::seeds::SeedScrapedConcert {
+                    source_url: Some(format!("https://npr.org/test/run/{n}")),
+                    title: Some(format!("Queued Concert {n}")),
+                    concert_date: None,
+                    artist: Some("Test Artist".to_string()),
+                    album: Some(format!("Queued Album {n}")),
+                    set_list: Some(vec![]),
+                })
+                .unwrap()
+                .id
+        });
+        let registry = Arc::new(JobRegistry::with_limits(crate::jobs::JobLimits {
+            download: Some(1),
+            ..Default::default()
+        }));
+        let (tx, rx) = test_oneshot::channel();
+        let (_tx2, rx2) = test_oneshot::channel();
+        let (_tx3, rx3) = test_oneshot::channel();
+        for request in [
+            TestRequest::new(first, rx),
+            TestRequest::new(second, rx2),
+            TestRequest::new(third, rx3),
+        ] {
+            assert_eq!(
+                submit(db.clone(), registry.clone(), request).await.unwrap(),
+                Admission::Accepted
+            );
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(30)).await;
+
+        let key = |concert_id| JobKey {
+            concert_id,
+            kind: crate::jobs::JobKind::Download,
+        };
+        let persisted = |db: &Arc<Mutex<Connection>>| -> Vec<i64> {
+            let conn = db.lock().unwrap();
+            db::job_queue::list_queued(&conn)
+                .unwrap()
+                .into_iter()
+                .map(|queued| queued.key.concert_id)
+                .collect()
+        };
+        assert_eq!(registry.queue_position(&key(first)), None, "running");
+        assert_eq!(registry.queue_position(&key(second)), Some(1));
+        assert_eq!(registry.queue_position(&key(third)), Some(2));
+        assert_eq!(persisted(&db), vec![second, third]);
+
+        let dequeue = TestRequest {
+            concert_id: third,
+            column: "download",
+            block: Mutex::new(None),
+            setup_result: Mutex::new(None),
+            setup_panics: false,
+            commit_success_fails: false,
+            dependents_spawned: Arc::new(AtomicUsize::new(0)),
+            retry_after: None,
+        };
+        let outcome = {
+            let conn = db.lock().unwrap();
+            cancel(&conn, &registry, &dequeue).unwrap()
+        };
+        assert_eq!(outcome, CancelOutcome::CancelledRunning);
+        {
+            let conn = db.lock().unwrap();
+            let c = db::concerts::get_concert(&conn, third).unwrap();
+            assert_eq!(c.download_errors.last().unwrap().error, DEQUEUED_BY_USER);
+        }
+        assert_eq!(persisted(&db), vec![second]);
+
+        // Finishing the running download hands its slot to the next in line.
+        tx.send(StepResult::Succeed).ok();
+        wait_for(&db, first, |c| c.downloaded_at.is_some()).await;
+        for _ in 0..100 {
+            if registry.queue_position(&key(second)).is_none() && persisted(&db).is_empty() {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+        }
+        assert_eq!(registry.queue_position(&key(second)), None);
+        assert!(registry.is_running(&key(second)));
+        assert!(persisted(&db).is_empty());
+    }
+
+    #[tokio::test]
+    async fn retry_backoff_frees_the_slot_for_queued_runs() {
+        let (db, first) = seeded_db();
+        let second = {
+            let conn = db.lock().unwrap();
+            SeedContext::new(&conn)
+                .seed_scraped_concert(db::seeds::SeedScrapedConcert {
+                    source_url: Some("https://npr.org/test/run/retry".to_string()),
+                    title: Some("Queued Concert".to_string()),
+                    concert_date: None,
+                    artist: Some("Test Artist".to_string()),
+                    album: Some("Queued Album".to_string()),
+                    set_list: Some(vec![]),
+                })
+                .unwrap()
+                .id
+        };
+        let registry = Arc::new(JobRegistry::with_limits(crate::jobs::JobLimits {
+            download: Some(1),
+            ..Default::default()
+        }));
+        let (retrying, ()) = TestRequest::instant(first, StepResult::Fail("HTTP 503".into()));
+        let retrying = TestRequest {
+            retry_after: Some(Duration::from_secs(2)),
+            ..retrying
+        };
+        let (queued, ()) = TestRequest::instant(second, StepResult::Succeed);
+        for request in [retrying, queued] {
+            assert_eq!(
+                submit(db.clone(), registry.clone(), request).await.unwrap(),
+                Admission::Accepted
+            );
+        }
+
+        // The queued download finishes while the first one is still backing
+        // off, then the retry takes the slot back and succeeds.
+        wait_for(&db, second, |c| c.downloaded_at.is_some()).await;
+        {
+            let conn = db.lock().unwrap();
+            let c = db::concerts::get_concert(&conn, first).unwrap();
+            assert!(c.downloaded_at.is_none(), "retry still backing off");
+        }
+        wait_for(&db, first, |c| c.downloaded_at.is_some()).await;
+    }
+
     async fn wait_for(
         db: &Arc<Mutex<Connection>>,
         id: i64,
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
 use concert_types::ConcertInfo;
+use live_set_splitter::concert_split::AudioFormat;
+use live_set_splitter::cut::Rendition;
 use rusqlite::Connection;
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -8,13 +10,15 @@ use tempfile::NamedTempFile;
 
 use crate::concert_media::{find_downloaded_file, tracks_present_on_disk};
 use crate::db;
+use crate::jobs::progress::ProgressReporter;
 use crate::jobs::run::{self, Admission, JobCancellation, JobRequest};
 use crate::jobs::{
     JobConfig, JobKey, JobKind, JobRegistry, JobRunFuture, JobStepFailure, JobStepOutcome,
     SplitJob, SplitMode,
 };
 use crate::model::{concert_dir, Concert, Musician};
-use crate::split_timestamps::ValidatedTimestamps;
+use crate::scrape::preview_image_path;
+use crate::split_timestamps::{song_timestamps_to_payload, ValidatedTimestamps};
 
 #[derive(Debug)]
 pub enum StartOutcome {
@@ -39,6 +43,9 @@ impl std::error::Error for SplitValidationError {}
 pub(crate) struct SplitInput {
     concert: Concert,
     mode: SplitMode,
+    audio_formats: Vec<AudioFormat>,
+    renditions: Vec<Rendition>,
+    hls: bool,
 }
 
 pub(crate) struct SplitSetup {
@@ -80,7 +87,7 @@ pub(crate) enum SplitCompletionFacts {
 /// see `build_cli_split_command` in `jobs::mod`). `ConcertInfo` is a superset
 /// of the splitter's on-disk shape (extra fields default), so one typed value
 /// serves both without a separate transport DTO.
-fn build_concert_info(concert: &Concert) -> ConcertInfo {
+pub(crate) fn build_concert_info(concert: &Concert) -> ConcertInfo {
     ConcertInfo {
         artist: concert.artist.clone().unwrap_or_default(),
         source: concert.source_url.clone(),
@@ -106,6 +113,7 @@ fn build_concert_info(concert: &Concert) -> ConcertInfo {
         preview_image_url: None,
         teaser: None,
         timestamps: None,
+        interludes: None,
     }
 }
 
@@ -135,6 +143,105 @@ pub async fn start_split(
     }
 }
 
+/// Resubmit a split left waiting in `job_queue` by a previous process. The
+/// payload's timestamps are re-validated against the concert as it is now;
+/// if they no longer fit (set list edited meanwhile) the split becomes a
+/// Failed Job.
+pub async fn resume_split(
+    db: Arc<Mutex<Connection>>,
+    registry: Arc<JobRegistry>,
+    config: JobConfig,
+    concert_id: i64,
+    payload: Option<&str>,
+) -> Result<StartOutcome> {
+    let mode = {
+        let conn = db.lock().unwrap();
+        queued_split_mode(&conn, concert_id, payload)
+    };
+    let mode = match mode {
+        Ok(mode) => mode,
+        Err(error) => {
+            run::fail_unresumable(&db, &SplitCancellation::new(concert_id), &error);
+            return Err(error);
+        }
+    };
+    let request = SplitRequest::new(concert_id, mode, config);
+    match run::resume(db, registry, request).await {
+        Ok(Admission::Accepted) => Ok(StartOutcome::Spawned),
+        Ok(Admission::AlreadyRunning) => Ok(StartOutcome::AlreadyRunning),
+        Err(error) if error.downcast_ref::<SplitValidationError>().is_some() => {
+            Ok(StartOutcome::NotDownloaded)
+        }
+        Err(error) => Err(error),
+    }
+}
+
+/// A split's `job_queue.payload`: its [`SplitMode`] with any timestamps kept
+/// unvalidated, since `ValidatedTimestamps` is only built by validation.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(tag = "mode", rename_all = "kebab-case")]
+enum QueuedSplitMode {
+    Analyze,
+    UserTimestamps {
+        timestamps: Vec<concert_types::SongTimestamp>,
+        media_duration: f64,
+    },
+    ResetToAuto {
+        timestamps: Vec<concert_types::SongTimestamp>,
+    },
+}
+
+impl From<&SplitMode> for QueuedSplitMode {
+    fn from(mode: &SplitMode) -> Self {
+        match mode {
+            SplitMode::Analyze => QueuedSplitMode::Analyze,
+            SplitMode::UserTimestamps { ts, media_duration } => QueuedSplitMode::UserTimestamps {
+                timestamps: ts.songs().to_vec(),
+                media_duration: *media_duration,
+            },
+            SplitMode::ResetToAuto(ts) => QueuedSplitMode::ResetToAuto {
+                timestamps: ts.songs().to_vec(),
+            },
+        }
+    }
+}
+
+fn queued_split_mode(
+    conn: &Connection,
+    concert_id: i64,
+    payload: Option<&str>,
+) -> Result<SplitMode> {
+    let queued: QueuedSplitMode = match payload {
+        Some(json) => serde_json::from_str(json).context("unreadable queued split mode")?,
+        None => QueuedSplitMode::Analyze,
+    };
+    let set_list =
+        || -> Result<Vec<String>> { Ok(db::concerts::get_concert(conn, concert_id)?.set_list) };
+    let invalid = |e| anyhow::anyhow!("queued timestamps are no longer valid: {e}");
+    Ok(match queued {
+        QueuedSplitMode::Analyze => SplitMode::Analyze,
+        QueuedSplitMode::UserTimestamps {
+            timestamps,
+            media_duration,
+        } => SplitMode::UserTimestamps {
+            ts: ValidatedTimestamps::validate(
+                &set_list()?,
+                Some(media_duration),
+                &song_timestamps_to_payload(&timestamps),
+            )
+            .map_err(invalid)?,
+            media_duration,
+        },
+        QueuedSplitMode::ResetToAuto { timestamps } => SplitMode::ResetToAuto(
+            ValidatedTimestamps::validate_for_reset(
+                &set_list()?,
+                &song_timestamps_to_payload(&timestamps),
+            )
+            .map_err(invalid)?,
+        ),
+    })
+}
+
 pub(crate) struct SplitRequest {
     concert_id: i64,
     mode: SplitMode,
@@ -234,9 +341,13 @@ impl JobRequest for SplitRequest {
                 self.concert_id
             );
         }
+        let settings = db::settings::get_settings(conn)?;
         Ok(SplitInput {
             concert,
             mode: self.mode.clone(),
+            audio_formats: settings.audio_formats,
+            renditions: settings.renditions,
+            hls: settings.hls_packaging,
         })
     }
 
@@ -272,6 +383,11 @@ impl JobRequest for SplitRequest {
                 input_file,
                 output_dir,
                 mode: input.mode,
+                report_dir: self.config.split_report_dir(input.concert.id),
+                audio_formats: input.audio_formats,
+                renditions: input.renditions,
+                hls: input.hls,
+                cover_art: preview_image_path(&self.config.working_dir, album),
                 _temp_file: temp_file,
                 _timestamps_temp_file: timestamps_temp_file,
                 timestamps_path,
@@ -304,9 +420,10 @@ impl JobRequest for SplitRequest {
         &'a self,
         setup: &'a SplitSetup,
         log_file: Option<&'a Path>,
+        progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome> {
         match &setup.execution {
-            SplitExecution::Run(job) => Box::pin(self.config.run_split(job, log_file)),
+            SplitExecution::Run(job) => Box::pin(self.config.run_split(job, log_file, progress)),
             SplitExecution::ExistingTracksRecovery { .. } => {
                 Box::pin(async { JobStepOutcome::Succeeded })
             }
@@ -445,6 +562,10 @@ impl JobRequest for SplitRequest {
     fn spawn_dependents(&self, db: Arc<Mutex<Connection>>, registry: Arc<JobRegistry>) {
         crate::jobs::spawn_dependents(db, registry, self.config.clone(), &self.key());
     }
+
+    fn queue_payload(&self) -> Option<String> {
+        serde_json::to_string(&QueuedSplitMode::from(&self.mode)).ok()
+    }
 }
 
 fn write_timestamps_file(ts: &ValidatedTimestamps) -> Result<NamedTempFile> {
@@ -538,6 +659,20 @@ mod tests {
         )
     }
 
+    #[test]
+    fn split_report_is_found_only_once_written() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = config_for(dir.path().to_path_buf());
+        assert_eq!(config.split_report(7), None);
+
+        let report_dir = config.split_report_dir(7);
+        fs::create_dir_all(&report_dir).unwrap();
+        let report = report_dir.join(live_set_splitter::report::REPORT_FILENAME);
+        fs::write(&report, "<html></html>").unwrap();
+        assert_eq!(config.split_report(7), Some(report));
+        assert_eq!(config.split_report(8), None);
+    }
+
     struct PartialRunner;
 
     impl JobRunner for PartialRunner {
@@ -545,6 +680,7 @@ mod tests {
             &'a self,
             _job: &'a DownloadJob,
             _log_file: Option<&'a Path>,
+            _progress: &'a ProgressReporter,
         ) -> JobRunFuture<'a, JobStepOutcome> {
             Box::pin(async { JobStepOutcome::Succeeded })
         }
@@ -553,6 +689,7 @@ mod tests {
             &'a self,
             _job: &'a SplitJob,
             _log_file: Option<&'a Path>,
+            _progress: &'a ProgressReporter,
         ) -> JobRunFuture<'a, JobStepOutcome> {
             Box::pin(async {
                 JobStepOutcome::Failed(JobStepFailure::RecoverablePartialSplit {
@@ -12,11 +12,15 @@ use std::io::Write as _;
 use std::path::{Path, PathBuf};
 
 use live_set_splitter::concert_split::{
-    self, ConcertSplitOptions, ConcertSplitOutcome, ConcertSplitProgress, ConcertSplitRequest,
-    NoOutputReason, OutputFormat, SplitPhase, TrackKind,
+    self, AlbumFormat, AlbumOutput, AudioEncoderSettings, AudioFormat, ChapterOutput,
+    ConcertSplitOptions, ConcertSplitOutcome, ConcertSplitProgress, ConcertSplitRequest,
+    DetectionSampling, HlsOutput, LoudnessMode, NoOutputReason, OutputFormat, SplitPhase,
+    TrackKind,
 };
-use live_set_splitter::cut::VideoCutMode;
+use live_set_splitter::cut::{Rendition, VideoCutMode};
+use live_set_splitter::tags::TagTemplate;
 
+use super::progress::ProgressReporter;
 use super::{JobStepFailure, JobStepOutcome, SplitJob, SplitMode};
 
 /// The subset of [`SplitJob`]'s fields the library adapter needs, owned so it
@@ -33,6 +37,11 @@ struct Job {
     input_file: PathBuf,
     output_dir: PathBuf,
     mode: SplitMode,
+    report_dir: PathBuf,
+    audio_formats: Vec<AudioFormat>,
+    renditions: Vec<Rendition>,
+    hls: bool,
+    cover_art: PathBuf,
 }
 
 impl From<&SplitJob> for Job {
@@ -44,6 +53,11 @@ impl From<&SplitJob> for Job {
             input_file: job.input_file.clone(),
             output_dir: job.output_dir.clone(),
             mode: job.mode.clone(),
+            report_dir: job.report_dir.clone(),
+            audio_formats: job.audio_formats.clone(),
+            renditions: job.renditions.clone(),
+            hls: job.hls,
+            cover_art: job.cover_art.clone(),
         }
     }
 }
@@ -61,22 +75,61 @@ fn options_for(job: &Job) -> ConcertSplitOptions {
         SplitMode::UserTimestamps { media_duration, .. } => (true, Some(*media_duration)),
         SplitMode::Analyze | SplitMode::ResetToAuto(_) => (false, None),
     };
+    ConcertSplitOptions {
+        audio_formats: job.audio_formats.clone(),
+        renditions: job.renditions.clone(),
+        emit_interludes,
+        media_duration,
+        hls: if job.hls {
+            HlsOutput::Alongside
+        } else {
+            HlsOutput::None
+        },
+        cover_art: Some(job.cover_art.clone()).filter(|path| path.is_file()),
+        report_dir: Some(job.report_dir.clone()),
+        ..library_options()
+    }
+}
+
+/// The job-independent part of [`options_for`], shared with `split_eval` so
+/// an evaluation detects boundaries exactly as a split job would.
+pub(crate) fn library_options() -> ConcertSplitOptions {
     ConcertSplitOptions {
         no_save_songs: false,
         // Deliberately false for every mode: user/reset timestamps are already
         // correct and must not be silently rewritten (mirrors the CLI
         // subprocess command builder never passing --refine-timestamps); Analyze
-        // mode's `timestamps: None` below makes `run` refine unconditionally
+        // mode's `timestamps: None` makes `run` refine unconditionally
         // regardless of this flag (see docs/concert-split.md).
         refine_timestamps: false,
         output_format: OutputFormat::Both,
+        audio_formats: vec![AudioFormat::M4a],
+        audio_encoder: AudioEncoderSettings::default(),
         video_cut_mode: VideoCutMode::Smart,
+        renditions: Vec::new(),
         analyze_images: false,
         reuse_frames: false,
         keep_frames: false,
         ocr_engine: None,
-        emit_interludes,
-        media_duration,
+        // Concurrent jobs already share the cores (see the per-kind job limits).
+        ocr_threads: 1,
+        detection_sampling: DetectionSampling::Dense,
+        overlay_crop: None,
+        set_list_free: None,
+        emit_interludes: false,
+        media_duration: None,
+        chapters: ChapterOutput::None,
+        album: AlbumOutput::None,
+        album_format: AlbumFormat::Flac,
+        hls: HlsOutput::None,
+        // The track list, player bar and playlists show the posters.
+        posters: true,
+        tag_template: TagTemplate::default(),
+        cover_art: None,
+        // The player reads `loudness.json` for its ReplayGain modes; tags stay
+        // off so the tracks keep iTunes-style metadata (see `LoudnessMode::Tag`).
+        loudness: LoudnessMode::Measure,
+        report_dir: None,
     }
 }
 
@@ -119,6 +172,11 @@ fn render_progress_line(event: &ConcertSplitProgress) -> (&'static str, String)
                 SplitPhase::RefineAudio => "Refining song boundaries using audio analysis...",
                 SplitPhase::WriteMetadata => "Writing timestamps metadata",
                 SplitPhase::Cut => "Cutting tracks",
+                SplitPhase::MeasureLoudness => "Measuring loudness",
+                SplitPhase::WriteChapters => "Writing chaptered copy",
+                SplitPhase::WriteAlbum => "Writing album and CUE sheet",
+                SplitPhase::WriteHls => "Packaging for HLS",
+                SplitPhase::WritePosters => "Writing poster frames",
                 SplitPhase::ValidateOutput => "Validating split output",
                 SplitPhase::Publish => "Publishing split output",
                 SplitPhase::Cleanup => "Cleaning up",
@@ -141,6 +199,49 @@ fn render_progress_line(event: &ConcertSplitProgress) -> (&'static str, String)
     }
 }
 
+/// Short label for the Jobs page and concert card's live progress.
+fn phase_label(phase: &SplitPhase) -> &'static str {
+    match phase {
+        SplitPhase::Validate => "Validating input",
+        SplitPhase::Inspect => "Inspecting source",
+        SplitPhase::Detect => "Detecting songs",
+        SplitPhase::RecoverSilence => "Recovering songs from silence",
+        SplitPhase::RefineAudio => "Refining boundaries",
+        SplitPhase::WriteMetadata => "Writing metadata",
+        SplitPhase::Cut => "Cutting tracks",
+        SplitPhase::MeasureLoudness => "Measuring loudness",
+        SplitPhase::WriteChapters => "Writing chapters",
+        SplitPhase::WriteAlbum => "Writing album",
+        SplitPhase::WriteHls => "Packaging for HLS",
+        SplitPhase::WritePosters => "Writing poster frames",
+        SplitPhase::ValidateOutput => "Validating output",
+        SplitPhase::Publish => "Publishing",
+        SplitPhase::Cleanup => "Cleaning up",
+    }
+}
+
+/// Forward the events a person watching the split cares about — the phase and
+/// "k of total" tracks — to `progress`. `tracks` counts completions against
+/// the `CutPlanned` total.
+fn report_progress(
+    progress: &ProgressReporter,
+    tracks: &mut (usize, usize),
+    event: &ConcertSplitProgress,
+) {
+    match event {
+        ConcertSplitProgress::PhaseStarted(phase) => progress.phase(phase_label(phase)),
+        ConcertSplitProgress::CutPlanned { total } => {
+            *tracks = (0, *total);
+            progress.tracks(0, *total);
+        }
+        ConcertSplitProgress::TrackCompleted { .. } => {
+            tracks.0 += 1;
+            progress.tracks(tracks.0, tracks.1);
+        }
+        ConcertSplitProgress::Warning(_) | ConcertSplitProgress::Diagnostic(_) => {}
+    }
+}
+
 /// `concert.json` is a byte-for-byte copy of `job.json_path` (only written if
 /// not already present) — a transport artifact. The CLI adapter's subprocess
 /// gets this for free: the spawned `live-set-splitter` binary's own `main()`
@@ -212,7 +313,7 @@ fn outcome_to_step(job: &Job, result: anyhow::Result<ConcertSplitOutcome>) -> Jo
 /// job here cannot stop this blocking thread — see docs/concert-split.md's
 /// adapter-selection section for the accepted cancellation-semantics
 /// divergence this implies.
-fn run_blocking(job: &Job, log_file: Option<&Path>) -> JobStepOutcome {
+fn run_blocking(job: &Job, log_file: Option<&Path>, progress: &ProgressReporter) -> JobStepOutcome {
     let mut log = log_file.and_then(|path| match std::fs::File::create(path) {
         Ok(f) => Some(f),
         Err(e) => {
@@ -222,7 +323,9 @@ fn run_blocking(job: &Job, log_file: Option<&Path>) -> JobStepOutcome {
     });
 
     let concert_id = job.concert_id;
+    let mut tracks = (0, 0);
     let mut sink = |event: ConcertSplitProgress| {
+        report_progress(progress, &mut tracks, &event);
         let (stream, line) = render_progress_line(&event);
         tracing::info!(
             target: "concert_tracker::jobs::split",
@@ -247,12 +350,19 @@ fn run_blocking(job: &Job, log_file: Option<&Path>) -> JobStepOutcome {
 /// future's whole lifetime, so `run_blocking` (moved into `spawn_blocking` as
 /// an owned [`Job`] snapshot of the paths it needs) can safely read
 /// `job.json_path`'s file without racing its deletion.
-pub(super) async fn run(job: &SplitJob, log_file: Option<&Path>) -> JobStepOutcome {
+pub(super) async fn run(
+    job: &SplitJob,
+    log_file: Option<&Path>,
+    progress: &ProgressReporter,
+) -> JobStepOutcome {
     let concert_id = job.concert_id;
     let owned_job = Job::from(job);
     let log_file = log_file.map(|p| p.to_path_buf());
-    let outcome =
-        tokio::task::spawn_blocking(move || run_blocking(&owned_job, log_file.as_deref())).await;
+    let progress = progress.clone();
+    let outcome = tokio::task::spawn_blocking(move || {
+        run_blocking(&owned_job, log_file.as_deref(), &progress)
+    })
+    .await;
     match outcome {
         Ok(step) => step,
         Err(join_error) => JobStepOutcome::Failed(JobStepFailure::ordinary(format!(
@@ -291,11 +401,17 @@ mod tests {
                 preview_image_url: None,
                 teaser: None,
                 timestamps: None,
+                interludes: None,
             },
             json_path: PathBuf::from("/does/not/matter/for/pure/translation.json"),
             input_file: PathBuf::from("/media/input.mp4"),
             output_dir: PathBuf::from("/media/output"),
             mode,
+            report_dir: PathBuf::from("/media/split-report"),
+            audio_formats: vec![AudioFormat::M4a],
+            renditions: Vec::new(),
+            hls: false,
+            cover_art: PathBuf::from("/media/output/preview.jpg"),
         }
     }
 
@@ -308,6 +424,10 @@ mod tests {
         assert!(!options.no_save_songs);
         assert!(!options.emit_interludes);
         assert_eq!(options.media_duration, None);
+        assert_eq!(
+            options.report_dir,
+            Some(PathBuf::from("/media/split-report"))
+        );
     }
 
     #[test]
@@ -372,6 +492,11 @@ mod tests {
                 timestamps: vec![],
                 tracks: vec![],
                 output_dir: output_dir.clone(),
+                chapters_file: None,
+                album_file: None,
+                cue_file: None,
+                hls_playlist: None,
+                contact_sheet: None,
             },
         ));
         let step = outcome_to_step(&job, outcome);
@@ -397,6 +522,11 @@ mod tests {
                 timestamps: vec![],
                 tracks: vec![],
                 output_dir: output_dir.clone(),
+                chapters_file: None,
+                album_file: None,
+                cue_file: None,
+                hls_playlist: None,
+                contact_sheet: None,
             },
         ));
         let step = outcome_to_step(&job, outcome);
@@ -452,15 +582,22 @@ mod tests {
                         kind: live_set_splitter::concert_split::TrackKind::Song,
                         start_time: 0.0,
                         end_time: 10.0,
+                        label: None,
                     },
                     live_set_splitter::concert_split::ProducedTrack {
                         title: "Third".to_string(),
                         kind: live_set_splitter::concert_split::TrackKind::Song,
                         start_time: 20.0,
                         end_time: 30.0,
+                        label: None,
                     },
                 ],
                 output_dir: job.output_dir.clone(),
+                chapters_file: None,
+                album_file: None,
+                cue_file: None,
+                hls_playlist: None,
+                contact_sheet: None,
             },
         ));
         let step = outcome_to_step(&job, outcome);
@@ -470,4 +607,37 @@ mod tests {
                 if tracks == ["First", "Third"]
         ));
     }
+
+    #[test]
+    fn report_progress_tracks_phases_and_completed_tracks() {
+        use crate::jobs::progress::ProgressHub;
+        use crate::jobs::{JobKey, JobKind};
+        let hub = std::sync::Arc::new(ProgressHub::new());
+        let key = JobKey {
+            concert_id: 1,
+            kind: JobKind::Split,
+        };
+        hub.queued(&key);
+        hub.running(&key);
+        let reporter = hub.reporter(&key);
+        let mut tracks = (0, 0);
+        for event in [
+            ConcertSplitProgress::PhaseStarted(SplitPhase::Cut),
+            ConcertSplitProgress::CutPlanned { total: 3 },
+            ConcertSplitProgress::TrackCompleted {
+                index: 1,
+                title: "First".to_string(),
+                kind: TrackKind::Song,
+            },
+            ConcertSplitProgress::Diagnostic("ignored".to_string()),
+        ] {
+            report_progress(&reporter, &mut tracks, &event);
+        }
+        let progress = hub.get(&key).unwrap();
+        assert_eq!(progress.phase.as_deref(), Some("Cutting tracks"));
+        assert_eq!(
+            (progress.tracks_done, progress.tracks_total),
+            (Some(1), Some(3))
+        );
+    }
 }
@@ -1,17 +1,22 @@
 pub mod archive_import;
+pub mod auto_sync;
+pub mod chapters;
 pub mod concert_media;
 pub mod db;
 pub mod events;
 pub mod import;
 pub mod jobs;
 pub mod lifecycle;
+pub mod loudness;
 pub mod model;
 pub mod month_walk;
 pub mod normalize;
 pub mod playback;
 pub mod playlist;
+pub mod playlist_file;
 pub mod scan;
 pub mod scrape;
+pub mod split_eval;
 pub mod split_timestamps;
 pub mod sync;
 #[cfg(feature = "test-control")]
@@ -150,8 +150,13 @@ pub fn delete_track(
     let dir = crate::model::concert_dir(working_dir, album);
 
     let mut removed_files = Vec::new();
-    for ext in &["mp4", "m4a"] {
-        let path = dir.join(format!("{stem}.{ext}"));
+    // The poster frame goes with the track's media.
+    let names = crate::concert_media::SPLIT_TRACK_EXTENSIONS
+        .iter()
+        .map(|ext| format!("{stem}.{ext}"))
+        .chain([live_set_splitter::poster::poster_file_name(&track_title)]);
+    for name in names {
+        let path = dir.join(name);
         if !path.exists() {
             continue;
         }
@@ -236,8 +241,10 @@ pub fn cancel_job(
 /// server startup (before the registry exists) and after graceful shutdown's
 /// `JobRegistry::cancel_all` (once every slot/gate is already gone) — see
 /// `recover_failed`'s doc comment for why no gate/reservation is needed here.
+/// Runs still waiting in `job_queue` are left in progress for
+/// `jobs::resume_queued` to pick up.
 pub fn fail_in_progress_jobs(conn: &Connection, error: &str) -> Result<InProgressFailureCount> {
-    let download_ids = ids_with_column(conn, "download_started_at")?;
+    let download_ids = ids_with_column(conn, "download_started_at", "download")?;
     for id in &download_ids {
         crate::jobs::run::recover_failed(
             conn,
@@ -246,7 +253,7 @@ pub fn fail_in_progress_jobs(conn: &Connection, error: &str) -> Result<InProgres
         )?;
     }
 
-    let split_ids = ids_with_column(conn, "split_started_at")?;
+    let split_ids = ids_with_column(conn, "split_started_at", "split")?;
     for id in &split_ids {
         crate::jobs::run::recover_failed(
             conn,
@@ -255,7 +262,7 @@ pub fn fail_in_progress_jobs(conn: &Connection, error: &str) -> Result<InProgres
         )?;
     }
 
-    let archive_ids = ids_with_column(conn, "archive_started_at")?;
+    let archive_ids = ids_with_column(conn, "archive_started_at", "archive")?;
     for id in &archive_ids {
         crate::jobs::run::recover_failed(
             conn,
@@ -282,11 +289,15 @@ pub fn reset_in_progress(conn: &Connection) -> Result<usize> {
     Ok(rows)
 }
 
-fn ids_with_column(conn: &Connection, column: &str) -> Result<Vec<i64>> {
+/// Concerts with `column` set, minus those whose `kind` job is waiting in
+/// `job_queue`: those are resumed at startup rather than failed.
+fn ids_with_column(conn: &Connection, column: &str, kind: &str) -> Result<Vec<i64>> {
     conn.prepare(&format!(
-        "SELECT id FROM concerts WHERE {column} IS NOT NULL ORDER BY id"
+        "SELECT id FROM concerts WHERE {column} IS NOT NULL
+         AND NOT EXISTS (SELECT 1 FROM job_queue q WHERE q.concert_id = concerts.id AND q.kind = ?1)
+         ORDER BY id"
     ))?
-    .query_map([], |row| row.get::<_, i64>(0))?
+    .query_map([kind], |row| row.get::<_, i64>(0))?
     .collect::<rusqlite::Result<_>>()
     .with_context(|| format!("Failed to read concerts with {column}"))
 }
@@ -397,6 +408,7 @@ mod tests {
             start_time: 0.0,
             end_time: 10.0,
             duration: 10.0,
+            boundary: None,
         }])
         .unwrap();
         conn.execute(
@@ -431,6 +443,7 @@ mod tests {
             start_time: 0.0,
             end_time: 10.0,
             duration: 10.0,
+            boundary: None,
         }])
         .unwrap();
         conn.execute(
@@ -483,8 +496,13 @@ mod tests {
         db::lifecycle::mark_split_succeeded(&conn, id).unwrap();
         db::split_timestamps::set_tracks_present(&conn, id, &[true, true]).unwrap();
 
+        let poster = concert_dir(dir.path(), "Album").join("One.poster.jpg");
+        fs::write(&poster, b"jpg").unwrap();
+
         let first = delete_track(&conn, dir.path(), id, 0).unwrap();
         assert!(!first.split_cleared);
+        assert!(!poster.exists());
+        assert_eq!(first.removed_files.len(), 2);
         let concert = db::concerts::get_concert(&conn, id).unwrap();
         assert!(concert.split_at.is_some());
         assert_eq!(concert.tracks_present, vec![false, true]);
@@ -882,4 +900,33 @@ mod tests {
             .unwrap()
             .is_empty());
     }
+
+    #[test]
+    fn restart_recovery_leaves_queued_jobs_in_progress() {
+        let conn = db::connection::open_in_memory().unwrap();
+        let queued_id = insert_concert(&conn, "Queued", &["One"]);
+        let running_id = insert_concert(&conn, "Running", &["One"]);
+        db::lifecycle::try_mark_download_started(&conn, queued_id).unwrap();
+        db::lifecycle::try_mark_download_started(&conn, running_id).unwrap();
+        db::job_queue::enqueue(
+            &conn,
+            &JobKey {
+                concert_id: queued_id,
+                kind: JobKind::Download,
+            },
+            None,
+        )
+        .unwrap();
+
+        let counts = fail_in_progress_jobs(&conn, "server restarted").unwrap();
+
+        assert_eq!(counts.downloads, 1);
+        let queued = db::concerts::get_concert(&conn, queued_id).unwrap();
+        assert!(queued.download_started_at.is_some());
+        assert!(queued.download_errors.is_empty());
+        assert!(db::concerts::get_concert(&conn, running_id)
+            .unwrap()
+            .download_started_at
+            .is_none());
+    }
 }
@@ -0,0 +1,136 @@
+//! Player gain from the splitter's `loudness.json` sidecar (written by
+//! `live-set-splitter --loudness`). The configured [`ReplayGainMode`] picks
+//! each file's own track gain or the concert-wide album gain; the browser
+//! applies the resulting dB value to its media element.
+
+use std::path::Path;
+
+use concert_types::{GainMeasurement, LoudnessFile, LOUDNESS_FILE};
+
+use crate::db::settings::ReplayGainMode;
+use crate::model::concert_dir;
+
+/// Read a concert's `loudness.json`. `None` when the concert was split without
+/// loudness analysis (or before it existed), or the file is unreadable.
+pub fn read_loudness(working_dir: &Path, album: &str) -> Option<LoudnessFile> {
+    let path = concert_dir(working_dir, album).join(LOUDNESS_FILE);
+    let json = std::fs::read_to_string(&path).ok()?;
+    match serde_json::from_str(&json) {
+        Ok(file) => Some(file),
+        Err(e) => {
+            tracing::warn!(path = %path.display(), "ignoring unreadable loudness file: {e}");
+            None
+        }
+    }
+}
+
+/// Gains for one concert's files under one mode, loaded once per request.
+pub struct PlaybackGain {
+    mode: ReplayGainMode,
+    loudness: Option<LoudnessFile>,
+}
+
+impl PlaybackGain {
+    pub fn load(working_dir: &Path, album: &str, mode: ReplayGainMode) -> Self {
+        let loudness = match mode {
+            ReplayGainMode::Off => None,
+            ReplayGainMode::Track | ReplayGainMode::Album => read_loudness(working_dir, album),
+        };
+        PlaybackGain { mode, loudness }
+    }
+
+    /// dB to apply to `filename` (a track or the whole source), or `None` for
+    /// no adjustment. Track mode falls back to the album gain for files
+    /// without their own measurement, such as the unsplit source. The gain is
+    /// capped so the measured true peak doesn't clip.
+    pub fn for_file(&self, filename: &str) -> Option<f64> {
+        let loudness = self.loudness.as_ref()?;
+        let measurement = match self.mode {
+            ReplayGainMode::Off => return None,
+            ReplayGainMode::Track => Path::new(filename)
+                .file_stem()
+                .and_then(|stem| stem.to_str())
+                .and_then(|stem| loudness.track(stem))
+                .unwrap_or(&loudness.album),
+            ReplayGainMode::Album => &loudness.album,
+        };
+        Some(clip_safe_gain(measurement))
+    }
+}
+
+fn clip_safe_gain(measurement: &GainMeasurement) -> f64 {
+    let headroom = if measurement.peak > 0.0 {
+        -20.0 * measurement.peak.log10()
+    } else {
+        f64::INFINITY
+    };
+    let gain = measurement.gain_db.min(headroom);
+    (gain * 100.0).round() / 100.0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use concert_types::TrackLoudness;
+
+    fn measurement(gain_db: f64, peak: f64) -> GainMeasurement {
+        GainMeasurement {
+            integrated_lufs: -18.0 - gain_db,
+            gain_db,
+            peak,
+        }
+    }
+
+    fn write_loudness(working_dir: &Path) {
+        let dir = concert_dir(working_dir, "Test: Album");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = LoudnessFile {
+            reference_lufs: -18.0,
+            album: measurement(-2.0, 0.9),
+            tracks: vec![TrackLoudness {
+                stem: "Song A".to_string(),
+                measurement: measurement(6.0, 0.25),
+            }],
+        };
+        std::fs::write(
+            dir.join(LOUDNESS_FILE),
+            serde_json::to_string(&file).unwrap(),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn track_mode_uses_the_track_gain_and_falls_back_to_album() {
+        let wd = tempfile::tempdir().unwrap();
+        write_loudness(wd.path());
+        let gain = PlaybackGain::load(wd.path(), "Test: Album", ReplayGainMode::Track);
+        // 6 dB requested but a 0.25 peak leaves only ~12 dB headroom: not capped.
+        assert_eq!(gain.for_file("Song A.m4a"), Some(6.0));
+        assert_eq!(gain.for_file("Test Album.mp4"), Some(-2.0));
+    }
+
+    #[test]
+    fn album_mode_and_off() {
+        let wd = tempfile::tempdir().unwrap();
+        write_loudness(wd.path());
+        let album = PlaybackGain::load(wd.path(), "Test: Album", ReplayGainMode::Album);
+        assert_eq!(album.for_file("Song A.m4a"), Some(-2.0));
+        let off = PlaybackGain::load(wd.path(), "Test: Album", ReplayGainMode::Off);
+        assert_eq!(off.for_file("Song A.m4a"), None);
+    }
+
+    #[test]
+    fn missing_sidecar_means_no_gain() {
+        let wd = tempfile::tempdir().unwrap();
+        let gain = PlaybackGain::load(wd.path(), "Test: Album", ReplayGainMode::Track);
+        assert_eq!(gain.for_file("Song A.m4a"), None);
+    }
+
+    #[test]
+    fn gain_is_capped_by_peak_headroom() {
+        // Peak at 0.5 (-6.02 dBTP) leaves 6.02 dB of headroom.
+        assert_eq!(clip_safe_gain(&measurement(10.0, 0.5)), 6.02);
+        assert_eq!(clip_safe_gain(&measurement(-3.0, 1.0)), -3.0);
+        assert_eq!(clip_safe_gain(&measurement(4.0, 0.0)), 4.0);
+    }
+}
@@ -44,7 +44,7 @@ pub fn is_browser_playable(ext: &str) -> bool {
 // `concert_media` directly. See docs/change/2026-07-09-concert-media-inventory.md.
 pub use crate::concert_media::{
     find_downloaded_file, find_interlude_file, find_interlude_track_file, find_track_file,
-    is_video_extension, source_redundant, INTERLUDE_EXTENSIONS,
+    is_video_extension, source_redundant, SPLIT_TRACK_EXTENSIONS,
 };
 
 // ── media_duration backfill ───────────────────────────────────────────────────
@@ -135,6 +135,26 @@ pub enum PlaybackItemKind {
     Interlude { index: usize },
 }
 
+/// The part of a file that plays as one item, in seconds. Set when a song or
+/// interlude has no file of its own and plays from the concert's cue-indexed
+/// album instead; `end_time` is `None` when it runs to the end of the file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Clip {
+    pub start_time: f64,
+    pub end_time: Option<f64>,
+}
+
+impl Clip {
+    /// The media fragment (`#t=start,end`) that makes the browser play only
+    /// this span of the file.
+    pub fn url_fragment(&self) -> String {
+        match self.end_time {
+            Some(end) => format!("#t={:.3},{:.3}", self.start_time, end),
+            None => format!("#t={:.3}", self.start_time),
+        }
+    }
+}
+
 /// One item in the ordered reconstruction-playback sequence built by
 /// [`build_reconstruction`].
 #[derive(Debug, Clone)]
@@ -142,9 +162,11 @@ pub struct PlaybackItem {
     pub kind: PlaybackItemKind,
     /// Display title (song title, or `"interlude"` for interludes).
     pub title: String,
-    /// Filename within the concert directory (`{stem}.{ext}`).
+    /// Filename within the concert directory (`{stem}.{ext}`), or the album
+    /// file when `clip` is set.
     pub filename: String,
     pub is_video: bool,
+    pub clip: Option<Clip>,
 }
 
 impl PlaybackItem {
@@ -196,6 +218,9 @@ pub struct TrackInfo {
     pub available: bool,
     pub is_video: bool,
     pub liked: bool,
+    /// The song's poster frame; filled from disk by
+    /// [`crate::concert_media::attach_posters`], `None` until then.
+    pub poster_url: Option<String>,
 }
 
 /// One row in the `GET /concerts/:id/track-details` JSON response.  Combines
@@ -229,6 +254,7 @@ pub fn list_tracks_from_events(
             available: false,
             is_video: false,
             liked: false,
+            poster_url: None,
         })
         .collect()
 }
@@ -547,6 +573,7 @@ pub fn list_all_tracks_from_db(
                 available,
                 is_video: false,
                 liked,
+                poster_url: None,
             }
         })
         .collect()
@@ -1285,6 +1312,7 @@ mod tests {
             start_time: start,
             end_time: end,
             duration: end - start,
+            boundary: None,
         }
     }
 
@@ -3,7 +3,7 @@ use std::path::Path;
 use crate::concert_media::{
     find_downloaded_file, is_video_extension, list_all_track_details, ConcertMediaInventory,
 };
-use crate::model::{self, Concert, PlaybackItem, TrackDetailItem};
+use crate::model::{self, Clip, Concert, PlaybackItem, TrackDetailItem};
 
 #[derive(Debug, Clone)]
 pub enum PlaybackPlan {
@@ -20,8 +20,9 @@ pub struct SourceMedia {
     pub playable: bool,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, PartialEq)]
 pub struct TrackMedia {
+    /// The track file, or the cue-indexed album when `clip` is set.
     pub filename: String,
     pub title: String,
     pub artist: String,
@@ -31,6 +32,7 @@ pub struct TrackMedia {
     pub has_next: bool,
     pub has_prev: bool,
     pub liked: bool,
+    pub clip: Option<Clip>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -212,8 +214,8 @@ fn track_media_inner(
         .get(track_index)
         .ok_or(PlaybackLookupError::NotPlayable)?
         .clone();
-    let filename = ConcertMediaInventory::for_concert(working_dir, concert, None)
-        .find_track_file(&title)
+    let (filename, clip) = ConcertMediaInventory::for_concert(working_dir, concert, None)
+        .find_track_source(track_index)
         .ok_or(PlaybackLookupError::NotPlayable)?;
     let ext = filename.rsplit('.').next().unwrap_or("");
     let playable = model::is_browser_playable(ext);
@@ -246,6 +248,7 @@ fn track_media_inner(
             .get(track_index)
             .copied()
             .unwrap_or(false),
+        clip,
     })
 }
 
@@ -258,13 +261,9 @@ where
     I: IntoIterator<Item = usize>,
 {
     concert.album.as_deref()?;
+    let inventory = ConcertMediaInventory::for_concert(working_dir, concert, None);
     for index in indices {
-        let Some(title) = concert.set_list.get(index) else {
-            continue;
-        };
-        let Some(filename) =
-            ConcertMediaInventory::for_concert(working_dir, concert, None).find_track_file(title)
-        else {
+        let Some((filename, _clip)) = inventory.find_track_source(index) else {
             continue;
         };
         let ext = filename.rsplit('.').next().unwrap_or("");
@@ -173,6 +173,7 @@ mod tests {
             start_time: start,
             end_time: end,
             duration: end - start,
+            boundary: None,
         }
     }
 
@@ -0,0 +1,630 @@
+//! Playlist files: export a playlist as M3U8 or XSPF for an external music
+//! player, and import an M3U back into a playlist.
+//!
+//! Export works on [`crate::playlist::expand_playlist`]'s flattened tracks, so
+//! nested playlists and whole-concert items come out as their concrete tracks
+//! in play order. Tracks with no split file on disk (never split, deleted, or
+//! archived off the working dir) are skipped — a player can't do anything
+//! with a path that doesn't exist — and counted in [`ExportedPlaylist::skipped`].
+//!
+//! Import matches each entry by its last two path components (album dir, file
+//! name) against concert directories, so a playlist written with relative
+//! paths, absolute paths, or `file://` URIs from another machine all resolve
+//! the same way. Only the file name → set-list index step touches the disk,
+//! through `ConcertMediaInventory::find_track_file`, which is the one place
+//! track file names are derived.
+
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::fmt::Write as _;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+use rusqlite::Connection;
+
+use crate::concert_media::ConcertMediaInventory;
+use crate::db;
+use crate::db::playlists::PlaylistError;
+use crate::model::{concert_dir, sanitize_album, Concert, PlaylistItemKind};
+
+/// Characters left unescaped in an XSPF `<location>` path segment: RFC 3986
+/// unreserved. Everything else (spaces, `#`, `?`, non-ASCII) is encoded so the
+/// location is a valid URI reference.
+const URI_SEGMENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+/// `#EXTINF` duration for a track whose length isn't known (the M3U
+/// convention; players fall back to probing the file).
+const EXTINF_UNKNOWN_DURATION: i64 = -1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlaylistFormat {
+    M3u8,
+    Xspf,
+}
+
+impl PlaylistFormat {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "m3u8" | "m3u" => Ok(PlaylistFormat::M3u8),
+            "xspf" => Ok(PlaylistFormat::Xspf),
+            other => Err(anyhow!(
+                "unknown playlist format: {other} (expected m3u8 or xspf)"
+            )),
+        }
+    }
+
+    pub fn extension(self) -> &'static str {
+        match self {
+            PlaylistFormat::M3u8 => "m3u8",
+            PlaylistFormat::Xspf => "xspf",
+        }
+    }
+
+    pub fn content_type(self) -> &'static str {
+        match self {
+            PlaylistFormat::M3u8 => "audio/x-mpegurl; charset=utf-8",
+            PlaylistFormat::Xspf => "application/xspf+xml; charset=utf-8",
+        }
+    }
+}
+
+/// How exported entries point at track files.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PathStyle {
+    /// `<album dir>/<track file>`, relative to `<working dir>/concerts`. Save
+    /// the playlist file in that directory and it keeps working wherever the
+    /// tree is mounted.
+    #[default]
+    Relative,
+    /// Absolute filesystem paths (`file://` URIs in XSPF).
+    Absolute,
+}
+
+impl PathStyle {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "relative" => Ok(PathStyle::Relative),
+            "absolute" => Ok(PathStyle::Absolute),
+            other => Err(anyhow!(
+                "unknown path style: {other} (expected relative or absolute)"
+            )),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ExportedPlaylist {
+    /// Playlist name, for the download's file name.
+    pub name: String,
+    pub body: String,
+    /// Resolved tracks left out because no split file exists for them.
+    pub skipped: usize,
+}
+
+/// One playable entry, with the path already in its exported style.
+struct ExportEntry {
+    path: PathBuf,
+    title: String,
+    artist: Option<String>,
+    album: Option<String>,
+    duration: Option<f64>,
+}
+
+impl ExportEntry {
+    /// `Artist - Title`, the display string players show for an `#EXTINF`.
+    fn display_title(&self) -> String {
+        match &self.artist {
+            Some(artist) => format!("{artist} - {}", self.title),
+            None => self.title.clone(),
+        }
+    }
+}
+
+pub fn export_playlist(
+    conn: &Connection,
+    working_dir: &Path,
+    playlist_id: i64,
+    format: PlaylistFormat,
+    paths: PathStyle,
+) -> std::result::Result<ExportedPlaylist, PlaylistError> {
+    let playlist =
+        db::playlists::get_playlist(conn, playlist_id)?.ok_or(PlaylistError::NotFound)?;
+    let tracks = crate::playlist::expand_playlist(conn, playlist_id)?;
+    let total = tracks.len();
+
+    let mut concerts: HashMap<i64, Concert> = HashMap::new();
+    let mut entries = Vec::with_capacity(total);
+    for track in tracks {
+        let concert = match concerts.entry(track.concert_id) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => e.insert(db::concerts::get_concert(conn, track.concert_id)?),
+        };
+        let Some(album) = concert.album.as_deref() else {
+            continue;
+        };
+        let inventory = ConcertMediaInventory::for_concert(working_dir, concert, None);
+        let Some(file) = inventory.find_track_file(&track.title) else {
+            continue;
+        };
+        let path = match paths {
+            PathStyle::Relative => PathBuf::from(sanitize_album(album)).join(file),
+            PathStyle::Absolute => std::path::absolute(concert_dir(working_dir, album).join(file))
+                .context("Failed to resolve absolute track path")?,
+        };
+        entries.push(ExportEntry {
+            path,
+            title: track.title,
+            artist: concert.artist.clone(),
+            album: concert.album.clone(),
+            duration: track.duration,
+        });
+    }
+
+    let skipped = total - entries.len();
+    if skipped > 0 {
+        tracing::warn!(
+            playlist_id,
+            skipped,
+            total,
+            "playlist export skipped tracks with no split file"
+        );
+    }
+    let body = match format {
+        PlaylistFormat::M3u8 => render_m3u8(&playlist.name, &entries),
+        PlaylistFormat::Xspf => render_xspf(&playlist.name, &entries, paths),
+    };
+    Ok(ExportedPlaylist {
+        name: playlist.name,
+        body,
+        skipped,
+    })
+}
+
+fn render_m3u8(name: &str, entries: &[ExportEntry]) -> String {
+    let mut out = String::from("#EXTM3U\n");
+    // Playlist-name directive; also what `import_m3u` reads the name back from.
+    let _ = writeln!(out, "#PLAYLIST:{}", single_line(name));
+    for entry in entries {
+        let secs = entry
+            .duration
+            .map(|d| d.round() as i64)
+            .unwrap_or(EXTINF_UNKNOWN_DURATION);
+        let _ = writeln!(
+            out,
+            "#EXTINF:{secs},{}",
+            single_line(&entry.display_title())
+        );
+        let _ = writeln!(out, "{}", entry.path.to_string_lossy());
+    }
+    out
+}
+
+fn render_xspf(name: &str, entries: &[ExportEntry], paths: PathStyle) -> String {
+    let mut out = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<playlist version=\"1\" xmlns=\"http://xspf.org/ns/0/\">\n",
+    );
+    let _ = writeln!(out, "  <title>{}</title>", xml_escape(name));
+    out.push_str("  <trackList>\n");
+    for entry in entries {
+        out.push_str("    <track>\n");
+        let _ = writeln!(
+            out,
+            "      <location>{}</location>",
+            xml_escape(&location_uri(&entry.path, paths))
+        );
+        let _ = writeln!(out, "      <title>{}</title>", xml_escape(&entry.title));
+        if let Some(artist) = &entry.artist {
+            let _ = writeln!(out, "      <creator>{}</creator>", xml_escape(artist));
+        }
+        if let Some(album) = &entry.album {
+            let _ = writeln!(out, "      <album>{}</album>", xml_escape(album));
+        }
+        if let Some(duration) = entry.duration {
+            // XSPF durations are integer milliseconds.
+            let _ = writeln!(
+                out,
+                "      <duration>{}</duration>",
+                (duration * 1000.0).round() as i64
+            );
+        }
+        out.push_str("    </track>\n");
+    }
+    out.push_str("  </trackList>\n</playlist>\n");
+    out
+}
+
+/// XSPF `<location>` is a URI: relative paths become a relative URI reference,
+/// absolute ones a `file://` URI, with every segment percent-encoded.
+fn location_uri(path: &Path, paths: PathStyle) -> String {
+    let encoded: Vec<String> = path
+        .components()
+        .filter_map(|c| match c {
+            std::path::Component::Normal(s) => {
+                Some(utf8_percent_encode(&s.to_string_lossy(), URI_SEGMENT).to_string())
+            }
+            _ => None,
+        })
+        .collect();
+    match paths {
+        PathStyle::Relative => encoded.join("/"),
+        PathStyle::Absolute => format!("file:///{}", encoded.join("/")),
+    }
+}
+
+fn xml_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// M3U is line-oriented: a newline in a title would start a bogus entry.
+fn single_line(s: &str) -> String {
+    s.replace(['\r', '\n'], " ")
+}
+
+/// Outcome of [`import_m3u`].
+#[derive(Debug)]
+pub struct ImportReport {
+    pub playlist_id: i64,
+    pub added: usize,
+    /// Entries (as written in the file) that matched no split track.
+    pub unresolved: Vec<String>,
+}
+
+/// Parsed M3U: the `#PLAYLIST:` name, if any, and the entry lines in order.
+#[derive(Debug, Default, PartialEq)]
+struct M3u {
+    name: Option<String>,
+    entries: Vec<String>,
+}
+
+fn parse_m3u(contents: &str) -> M3u {
+    let mut m3u = M3u::default();
+    for line in contents.trim_start_matches('\u{feff}').lines() {
+        let line = line.trim();
+        if let Some(name) = line.strip_prefix("#PLAYLIST:") {
+            let name = name.trim();
+            if !name.is_empty() {
+                m3u.name = Some(name.to_string());
+            }
+        } else if !line.is_empty() && !line.starts_with('#') {
+            m3u.entries.push(line.to_string());
+        }
+    }
+    m3u
+}
+
+/// The (album dir, file name) an M3U entry points at. Accepts plain paths,
+/// either separator, and percent-encoded `file://` URIs.
+fn entry_location(entry: &str) -> Option<(String, String)> {
+    let path = match entry.strip_prefix("file://") {
+        Some(uri) => percent_decode_str(uri).decode_utf8().ok()?.into_owned(),
+        None => entry.to_string(),
+    };
+    let mut parts = path.rsplit(['/', '\\']).filter(|p| !p.is_empty());
+    let file = parts.next()?.to_string();
+    let album_dir = parts.next()?.to_string();
+    Some((album_dir, file))
+}
+
+/// Create a playlist from an M3U/M3U8 file's contents. The name is `name` if
+/// given, else the file's `#PLAYLIST:` directive. Each entry becomes a track
+/// item; entries that match no split track are reported, not fatal — but a
+/// file where nothing resolves is rejected rather than creating an empty
+/// playlist.
+pub fn import_m3u(
+    conn: &Connection,
+    working_dir: &Path,
+    name: Option<&str>,
+    contents: &str,
+) -> std::result::Result<ImportReport, PlaylistError> {
+    let m3u = parse_m3u(contents);
+    let name = name.map(str::to_string).or(m3u.name).ok_or_else(|| {
+        PlaylistError::Invalid("playlist name required (no #PLAYLIST: line in file)".into())
+    })?;
+
+    let concerts_by_dir: HashMap<String, Concert> = db::concerts::list_concerts(conn)?
+        .into_iter()
+        .filter_map(|c| Some((sanitize_album(c.album.as_deref()?), c)))
+        .collect();
+
+    let mut items = Vec::new();
+    let mut unresolved = Vec::new();
+    for entry in &m3u.entries {
+        match resolve_entry(working_dir, &concerts_by_dir, entry) {
+            Some(kind) => items.push(kind),
+            None => unresolved.push(entry.clone()),
+        }
+    }
+    if items.is_empty() {
+        return Err(PlaylistError::Invalid(format!(
+            "none of the {} playlist entries matched a split track",
+            m3u.entries.len()
+        )));
+    }
+
+    // One transaction for the playlist and all its items, so a failed insert
+    // can't leave a half-imported playlist behind.
+    let tx = conn
+        .unchecked_transaction()
+        .context("Failed to begin import transaction")?;
+    let playlist_id = db::playlists::create_playlist(&tx, &name, None)?;
+    for kind in &items {
+        db::playlists::insert_playlist_item(&tx, playlist_id, kind)?;
+    }
+    tx.commit().context("Failed to commit import")?;
+    tracing::info!(
+        playlist_id,
+        added = items.len(),
+        unresolved = unresolved.len(),
+        "imported M3U playlist"
+    );
+    Ok(ImportReport {
+        playlist_id,
+        added: items.len(),
+        unresolved,
+    })
+}
+
+fn resolve_entry(
+    working_dir: &Path,
+    concerts_by_dir: &HashMap<String, Concert>,
+    entry: &str,
+) -> Option<PlaylistItemKind> {
+    let (album_dir, file) = entry_location(entry)?;
+    let concert = concerts_by_dir.get(&album_dir)?;
+    let inventory = ConcertMediaInventory::for_concert(working_dir, concert, None);
+    let track_index = concert
+        .set_list
+        .iter()
+        .position(|title| inventory.find_track_file(title).as_deref() == Some(file.as_str()))?;
+    Some(PlaylistItemKind::Track {
+        concert_id: concert.id,
+        track_index,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::connection::open_in_memory;
+    use crate::db::tests::seed_with_album;
+    use std::fs;
+
+    /// `seed_with_album` concert ("Test Album": Song A, Song B) with both
+    /// tracks split to disk and durations stored, in a playlist of its own.
+    fn fixture(conn: &Connection, working_dir: &Path) -> (i64, i64) {
+        let concert_id = seed_with_album(conn);
+        let dir = concert_dir(working_dir, "Test Album");
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("Song A.m4a"), b"").unwrap();
+        fs::write(dir.join("Song B.mp4"), b"").unwrap();
+        db::split_timestamps::set_auto_split_timestamps(
+            conn,
+            concert_id,
+            &[
+                concert_types::SongTimestamp {
+                    title: "Song A".into(),
+                    start_time: 0.0,
+                    end_time: 61.4,
+                    duration: 61.4,
+                    boundary: None,
+                },
+                concert_types::SongTimestamp {
+                    title: "Song B".into(),
+                    start_time: 61.4,
+                    end_time: 200.0,
+                    duration: 138.6,
+                    boundary: None,
+                },
+            ],
+        )
+        .unwrap();
+        let playlist_id = db::playlists::create_playlist(conn, "Road Trip", None).unwrap();
+        db::playlists::add_playlist_item(
+            conn,
+            playlist_id,
+            &PlaylistItemKind::Concert { concert_id },
+        )
+        .unwrap();
+        (concert_id, playlist_id)
+    }
+
+    #[test]
+    fn m3u8_export_has_extinf_durations_and_relative_paths() {
+        let conn = open_in_memory().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let (_, playlist_id) = fixture(&conn, tmp.path());
+
+        let out = export_playlist(
+            &conn,
+            tmp.path(),
+            playlist_id,
+            PlaylistFormat::M3u8,
+            PathStyle::Relative,
+        )
+        .unwrap();
+        assert_eq!(out.skipped, 0);
+        assert_eq!(
+            out.body,
+            "#EXTM3U\n#PLAYLIST:Road Trip\n\
+             #EXTINF:61,Test Artist - Song A\nTest Album/Song A.m4a\n\
+             #EXTINF:139,Test Artist - Song B\nTest Album/Song B.mp4\n"
+        );
+    }
+
+    #[test]
+    fn export_skips_tracks_without_files() {
+        let conn = open_in_memory().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let (_, playlist_id) = fixture(&conn, tmp.path());
+        fs::remove_file(concert_dir(tmp.path(), "Test Album").join("Song B.mp4")).unwrap();
+
+        let out = export_playlist(
+            &conn,
+            tmp.path(),
+            playlist_id,
+            PlaylistFormat::M3u8,
+            PathStyle::Relative,
+        )
+        .unwrap();
+        assert_eq!(out.skipped, 1);
+        assert!(!out.body.contains("Song B"), "{}", out.body);
+    }
+
+    #[test]
+    fn xspf_export_encodes_locations_and_escapes_text() {
+        let conn = open_in_memory().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let (_, playlist_id) = fixture(&conn, tmp.path());
+        db::playlists::update_playlist(&conn, playlist_id, "Rock & Roll", None).unwrap();
+
+        let out = export_playlist(
+            &conn,
+            tmp.path(),
+            playlist_id,
+            PlaylistFormat::Xspf,
+            PathStyle::Absolute,
+        )
+        .unwrap();
+        assert!(
+            out.body.contains("<title>Rock &amp; Roll</title>"),
+            "{}",
+            out.body
+        );
+        assert!(
+            out.body.contains("/Test%20Album/Song%20A.m4a</location>"),
+            "{}",
+            out.body
+        );
+        assert!(out.body.contains("<location>file:///"), "{}", out.body);
+        assert!(
+            out.body.contains("<duration>61400</duration>"),
+            "{}",
+            out.body
+        );
+        assert!(
+            out.body.contains("<creator>Test Artist</creator>"),
+            "{}",
+            out.body
+        );
+    }
+
+    #[test]
+    fn export_of_missing_playlist_is_not_found() {
+        let conn = open_in_memory().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let err = export_playlist(
+            &conn,
+            tmp.path(),
+            999,
+            PlaylistFormat::M3u8,
+            PathStyle::Relative,
+        )
+        .unwrap_err();
+        assert!(matches!(err, PlaylistError::NotFound));
+    }
+
+    #[test]
+    fn m3u_round_trips_through_import() {
+        let conn = open_in_memory().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        let (concert_id, playlist_id) = fixture(&conn, tmp.path());
+        let exported = export_playlist(
+            &conn,
+            tmp.path(),
+            playlist_id,
+            PlaylistFormat::M3u8,
+            PathStyle::Absolute,
+        )
+        .unwrap();
+
+        let report = import_m3u(&conn, tmp.path(), None, &exported.body).unwrap();
+        assert_eq!(report.added, 2);
+        assert!(report.unresolved.is_empty());
+        let imported = db::playlists::get_playlist(&conn, report.playlist_id)
+            .unwrap()
+            .unwrap();
+        assert_eq!(imported.name, "Road Trip");
+        let kinds: Vec<PlaylistItemKind> =
+            db::playlists::list_playlist_items(&conn, report.playlist_id)
+                .unwrap()
+                .into_iter()
+                .map(|i| i.kind)
+                .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                PlaylistItemKind::Track {
+                    concert_id,
+                    track_index: 0
+                },
+                PlaylistItemKind::Track {
+                    concert_id,
+                    track_index: 1
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn import_reports_unresolved_entries_and_accepts_file_uris() {
+        let conn = open_in_memory().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        fixture(&conn, tmp.path());
+        let m3u = "#EXTM3U\n\
+                   file:///mnt/music/Test%20Album/Song%20B.mp4\n\
+                   C:\\Music\\Test Album\\Song A.m4a\n\
+                   Other Album/Song A.m4a\n\
+                   Test Album/Missing.m4a\n";
+
+        let report = import_m3u(&conn, tmp.path(), Some("Mixed"), m3u).unwrap();
+        assert_eq!(report.added, 2);
+        assert_eq!(
+            report.unresolved,
+            vec!["Other Album/Song A.m4a", "Test Album/Missing.m4a"]
+        );
+    }
+
+    #[test]
+    fn import_rejects_files_with_no_matches_or_no_name() {
+        let conn = open_in_memory().unwrap();
+        let tmp = tempfile::tempdir().unwrap();
+        fixture(&conn, tmp.path());
+
+        let err = import_m3u(&conn, tmp.path(), Some("X"), "nowhere/nothing.mp3\n").unwrap_err();
+        assert!(matches!(err, PlaylistError::Invalid(_)), "{err}");
+        let err = import_m3u(&conn, tmp.path(), None, "Test Album/Song A.m4a\n").unwrap_err();
+        assert!(matches!(err, PlaylistError::Invalid(_)), "{err}");
+        // Neither rejected import left a playlist behind.
+        assert_eq!(db::playlists::list_playlists(&conn).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn parse_m3u_reads_name_and_skips_directives() {
+        let m3u = parse_m3u("\u{feff}#EXTM3U\r\n#PLAYLIST: Mix \r\n#EXTINF:3,x\r\na/b.mp3\r\n\r\n");
+        assert_eq!(
+            m3u,
+            M3u {
+                name: Some("Mix".to_string()),
+                entries: vec!["a/b.mp3".to_string()],
+            }
+        );
+    }
+}
@@ -558,12 +558,14 @@ mod tests {
                 start_time: 0.0,
                 end_time: 50.0,
                 duration: 50.0,
+                boundary: None,
             },
             concert_types::SongTimestamp {
                 title: "Song B".to_string(),
                 start_time: 55.0,
                 end_time: 120.0,
                 duration: 65.0,
+                boundary: None,
             },
         ];
         db::split_timestamps::set_auto_split_timestamps(&conn, id, &ts).unwrap();
@@ -647,6 +649,7 @@ mod tests {
             start_time: 0.0,
             end_time: 42.0,
             duration: 42.0,
+            boundary: None,
         }];
         db::split_timestamps::set_auto_split_timestamps(&conn, id, &ts).unwrap();
 
@@ -672,6 +675,7 @@ mod tests {
             start_time: 0.0,
             end_time: 33.0,
             duration: 33.0,
+            boundary: None,
         }];
         db::split_timestamps::set_auto_split_timestamps(&conn, id, &ts).unwrap();
 
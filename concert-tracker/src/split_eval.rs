@@ -0,0 +1,712 @@
+//! Boundary accuracy evaluation for the splitter (the `split-eval` binary).
+//!
+//! User-corrected timestamps (`user_split_timestamps_json`) are the ground
+//! truth. [`export_corpus`] collects them, with the concert metadata a re-run
+//! needs, into a JSON corpus; [`evaluate`] re-runs detection on every case
+//! whose source is still downloaded (or scores the stored automated
+//! timestamps) and measures each song start against the corrected one.
+//! [`regressions`] compares a run with a saved baseline report, so a change to
+//! OCR matching or refinement constants can be judged on the whole library
+//! rather than one concert. See docs/change/2026-08-11-split-eval.md.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use concert_types::{ConcertInfo, SongTimestamp};
+use live_set_splitter::concert_split::{
+    self, ConcertSplitOptions, ConcertSplitOutcome, ConcertSplitProgress, ConcertSplitRequest,
+    DetectionSampling, LoudnessMode, NoOutputReason,
+};
+use rusqlite::Connection;
+use serde::{Deserialize, Serialize};
+
+use crate::concert_media::find_downloaded_file;
+use crate::db;
+use crate::jobs::library_options;
+use crate::jobs::split::build_concert_info;
+
+/// A start within this many seconds of the corrected one counts as a match.
+pub const DEFAULT_TOLERANCE_SECONDS: f64 = 2.0;
+
+/// Upper bounds (seconds) of the error histogram's buckets; the last bucket
+/// is everything above the final bound.
+const HISTOGRAM_BOUNDS: [f64; 5] = [0.5, 1.0, 2.0, 5.0, 10.0];
+
+/// The ground-truth corpus written by `split-eval export`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Corpus {
+    pub cases: Vec<EvalCase>,
+}
+
+/// One concert with user-corrected timestamps.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvalCase {
+    pub concert_id: i64,
+    /// What the splitter is given, as a split job would build it.
+    pub concert: ConcertInfo,
+    /// The user-corrected timestamps.
+    pub truth: Vec<SongTimestamp>,
+    /// The automated timestamps stored when the concert was last split.
+    #[serde(default)]
+    pub auto: Option<Vec<SongTimestamp>>,
+}
+
+/// Where [`evaluate`] gets the timestamps it scores.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EvalSource {
+    /// Re-run detection on the downloaded source.
+    Detect,
+    /// The automated timestamps stored in the corpus.
+    Stored,
+}
+
+/// How one case went.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum CaseStatus {
+    Evaluated,
+    /// Nothing to score: no downloaded source, or no stored timestamps.
+    Skipped {
+        reason: String,
+    },
+    /// The split ran but produced no timestamps. Its songs count as undetected.
+    Failed {
+        reason: String,
+    },
+}
+
+/// One song start, detected against corrected.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BoundaryError {
+    pub title: String,
+    pub truth_start: f64,
+    /// `None` when the split has no song of this title.
+    pub detected_start: Option<f64>,
+}
+
+impl BoundaryError {
+    /// Absolute error in seconds, when the song was detected.
+    pub fn error(&self) -> Option<f64> {
+        self.detected_start
+            .map(|start| (start - self.truth_start).abs())
+    }
+
+    fn matched(&self, tolerance: f64) -> bool {
+        self.error().is_some_and(|error| error <= tolerance)
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CaseResult {
+    pub concert_id: i64,
+    pub album: String,
+    #[serde(flatten)]
+    pub status: CaseStatus,
+    pub boundaries: Vec<BoundaryError>,
+}
+
+/// Error distribution and match rate over every scored boundary.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+    pub cases_evaluated: usize,
+    pub cases_skipped: usize,
+    pub cases_failed: usize,
+    pub boundaries: usize,
+    pub detected: usize,
+    /// Detected within the tolerance.
+    pub matched: usize,
+    /// `matched / boundaries`; 0 when there are none.
+    pub match_rate: f64,
+    pub median_error: Option<f64>,
+    pub p90_error: Option<f64>,
+    pub max_error: Option<f64>,
+    /// Counts of detected boundaries per error bucket: `<= 0.5s`, `<= 1s`,
+    /// `<= 2s`, `<= 5s`, `<= 10s`, `> 10s`.
+    pub histogram: Vec<usize>,
+}
+
+/// The result of one evaluation run, saved as JSON to serve as a baseline.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct EvalReport {
+    pub source: EvalSource,
+    pub tolerance: f64,
+    pub summary: Summary,
+    pub cases: Vec<CaseResult>,
+}
+
+/// A boundary that matched in the baseline but no longer does.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Regression {
+    pub concert_id: i64,
+    pub album: String,
+    pub title: String,
+    pub baseline_error: f64,
+    /// `None` when the song is no longer detected.
+    pub error: Option<f64>,
+}
+
+/// Every concert with user-corrected timestamps, as an evaluation corpus.
+pub fn export_corpus(conn: &Connection) -> Result<Corpus> {
+    let mut cases = Vec::new();
+    for concert in db::split_timestamps::list_concerts_with_user_split_timestamps(conn)? {
+        let stored = db::split_timestamps::get_split_timestamps(conn, concert.id)?;
+        // Unparseable JSON reads as `None`; such a row has no usable truth.
+        let Some(truth) = stored.user else {
+            continue;
+        };
+        cases.push(EvalCase {
+            concert_id: concert.id,
+            concert: build_concert_info(&concert),
+            truth,
+            auto: stored.auto,
+        });
+    }
+    Ok(Corpus { cases })
+}
+
+pub fn read_corpus(path: &Path) -> Result<Corpus> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read corpus {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("Invalid corpus {}", path.display()))
+}
+
+pub fn read_report(path: &Path) -> Result<EvalReport> {
+    let json = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read report {}", path.display()))?;
+    serde_json::from_str(&json).with_context(|| format!("Invalid report {}", path.display()))
+}
+
+/// Score every case of `corpus`. `Detect` re-runs the splitter on the source
+/// found under `working_dir`; `progress` gets one line per case.
+pub fn evaluate(
+    corpus: &Corpus,
+    working_dir: &Path,
+    source: EvalSource,
+    tolerance: f64,
+    progress: &mut dyn FnMut(String),
+) -> EvalReport {
+    let cases: Vec<CaseResult> = corpus
+        .cases
+        .iter()
+        .map(|case| {
+            progress(format!(
+                "[{}] {}",
+                case.concert_id,
+                display_album(&case.concert)
+            ));
+            let detected = match source {
+                EvalSource::Detect => detect(case, working_dir),
+                EvalSource::Stored => stored(case),
+            };
+            score_case(case, detected)
+        })
+        .collect();
+    EvalReport {
+        source,
+        tolerance,
+        summary: summarize(&cases, tolerance),
+        cases,
+    }
+}
+
+/// What [`detect`] and [`stored`] found for a case.
+enum Detected {
+    Timestamps(Vec<SongTimestamp>),
+    Skipped(String),
+    Failed(String),
+}
+
+fn stored(case: &EvalCase) -> Detected {
+    match &case.auto {
+        Some(auto) => Detected::Timestamps(auto.clone()),
+        None => Detected::Skipped("no stored automated timestamps".to_string()),
+    }
+}
+
+/// Re-run the split on the downloaded source without cutting tracks, reading
+/// the boundaries back from the `timestamps.json` it publishes into a scratch
+/// directory. Frames are kept and reused, so only the first run of a concert
+/// extracts them.
+fn detect(case: &EvalCase, working_dir: &Path) -> Detected {
+    let Some(input_file) = find_downloaded_file(working_dir, &case.concert.album) else {
+        return Detected::Skipped("source not downloaded".to_string());
+    };
+    let result = (|| -> Result<Detected> {
+        let scratch = tempfile::tempdir().context("Failed to create scratch directory")?;
+        let output_dir = scratch.path().join("split");
+        let request = ConcertSplitRequest {
+            concert: case.concert.clone(),
+            input_file,
+            output_dir: output_dir.clone(),
+            timestamps: None,
+            options: detect_options(),
+        };
+        let outcome = concert_split::run(request, &mut |event| {
+            if let ConcertSplitProgress::Warning(message) = event {
+                tracing::warn!("{}: {}", case.concert.album, message);
+            }
+        })?;
+        match outcome {
+            ConcertSplitOutcome::NoOutput {
+                reason: NoOutputReason::AnalysisOnly,
+            } => {
+                let path = output_dir.join("timestamps.json");
+                let json = fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read {}", path.display()))?;
+                let info: ConcertInfo = serde_json::from_str(&json)
+                    .with_context(|| format!("Invalid {}", path.display()))?;
+                Ok(Detected::Timestamps(info.timestamps.unwrap_or_default()))
+            }
+            ConcertSplitOutcome::NoOutput { reason } => Ok(Detected::Failed(reason.to_string())),
+            ConcertSplitOutcome::Complete(output) | ConcertSplitOutcome::Partial(output) => {
+                Ok(Detected::Timestamps(output.timestamps))
+            }
+        }
+    })();
+    result.unwrap_or_else(|error| Detected::Failed(format!("{:#}", error)))
+}
+
+/// A split job's options (see `jobs::split_library`), without cutting and
+/// with the detection frames kept for the next run. The fields detection
+/// reads are spelled out; a test keeps them equal to the split job's.
+fn detect_options() -> ConcertSplitOptions {
+    ConcertSplitOptions {
+        no_save_songs: true,
+        refine_timestamps: false,
+        analyze_images: false,
+        reuse_frames: true,
+        keep_frames: true,
+        ocr_engine: None,
+        ocr_threads: 1,
+        detection_sampling: DetectionSampling::Dense,
+        overlay_crop: None,
+        set_list_free: None,
+        media_duration: None,
+        posters: false,
+        loudness: LoudnessMode::Off,
+        ..library_options()
+    }
+}
+
+fn score_case(case: &EvalCase, detected: Detected) -> CaseResult {
+    let (status, detected) = match detected {
+        Detected::Timestamps(timestamps) => (CaseStatus::Evaluated, timestamps),
+        Detected::Skipped(reason) => (CaseStatus::Skipped { reason }, Vec::new()),
+        Detected::Failed(reason) => (CaseStatus::Failed { reason }, Vec::new()),
+    };
+    let boundaries = match status {
+        CaseStatus::Skipped { .. } => Vec::new(),
+        _ => boundary_errors(&case.truth, &detected),
+    };
+    CaseResult {
+        concert_id: case.concert_id,
+        album: display_album(&case.concert),
+        status,
+        boundaries,
+    }
+}
+
+/// Pair each corrected start with the detected song of the same title.
+fn boundary_errors(truth: &[SongTimestamp], detected: &[SongTimestamp]) -> Vec<BoundaryError> {
+    truth
+        .iter()
+        .map(|song| BoundaryError {
+            title: song.title.clone(),
+            truth_start: song.start_time,
+            detected_start: detected
+                .iter()
+                .find(|d| d.title == song.title)
+                .map(|d| d.start_time),
+        })
+        .collect()
+}
+
+fn summarize(cases: &[CaseResult], tolerance: f64) -> Summary {
+    let count = |f: fn(&CaseStatus) -> bool| cases.iter().filter(|c| f(&c.status)).count();
+    let boundaries: Vec<&BoundaryError> = cases.iter().flat_map(|c| &c.boundaries).collect();
+    let mut errors: Vec<f64> = boundaries.iter().filter_map(|b| b.error()).collect();
+    errors.sort_by(f64::total_cmp);
+    let matched = boundaries.iter().filter(|b| b.matched(tolerance)).count();
+    let mut histogram = vec![0; HISTOGRAM_BOUNDS.len() + 1];
+    for &error in &errors {
+        let bucket = HISTOGRAM_BOUNDS
+            .iter()
+            .position(|&bound| error <= bound)
+            .unwrap_or(HISTOGRAM_BOUNDS.len());
+        histogram[bucket] += 1;
+    }
+    Summary {
+        cases_evaluated: count(|s| *s == CaseStatus::Evaluated),
+        cases_skipped: count(|s| matches!(s, CaseStatus::Skipped { .. })),
+        cases_failed: count(|s| matches!(s, CaseStatus::Failed { .. })),
+        boundaries: boundaries.len(),
+        detected: errors.len(),
+        matched,
+        match_rate: if boundaries.is_empty() {
+            0.0
+        } else {
+            matched as f64 / boundaries.len() as f64
+        },
+        median_error: percentile(&errors, 0.5),
+        p90_error: percentile(&errors, 0.9),
+        max_error: errors.last().copied(),
+        histogram,
+    }
+}
+
+/// Nearest-rank percentile of the sorted `values`.
+fn percentile(values: &[f64], p: f64) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    let rank = (p * values.len() as f64).ceil() as usize;
+    Some(values[rank.clamp(1, values.len()) - 1])
+}
+
+/// Boundaries within `current`'s tolerance in `baseline` that are not in
+/// `current`. Cases skipped in either run are left out.
+pub fn regressions(baseline: &EvalReport, current: &EvalReport) -> Vec<Regression> {
+    let tolerance = current.tolerance;
+    let mut found = Vec::new();
+    for case in &current.cases {
+        if matches!(case.status, CaseStatus::Skipped { .. }) {
+            continue;
+        }
+        let Some(before) = baseline
+            .cases
+            .iter()
+            .find(|b| b.concert_id == case.concert_id)
+        else {
+            continue;
+        };
+        for boundary in &case.boundaries {
+            let Some(baseline_error) = before
+                .boundaries
+                .iter()
+                .find(|b| b.title == boundary.title)
+                .and_then(BoundaryError::error)
+            else {
+                continue;
+            };
+            if baseline_error <= tolerance && !boundary.matched(tolerance) {
+                found.push(Regression {
+                    concert_id: case.concert_id,
+                    album: case.album.clone(),
+                    title: boundary.title.clone(),
+                    baseline_error,
+                    error: boundary.error(),
+                });
+            }
+        }
+    }
+    found
+}
+
+/// The report as a readable table: one row per case, the summary, and the
+/// regressions against a baseline when there is one.
+pub fn render_table(report: &EvalReport, regressions: Option<&[Regression]>) -> String {
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "{:>6}  {:<40}  {:>7}  {:>8}  {:>8}  status",
+        "id", "album", "matched", "median", "max"
+    );
+    for case in &report.cases {
+        let mut errors: Vec<f64> = case.boundaries.iter().filter_map(|b| b.error()).collect();
+        errors.sort_by(f64::total_cmp);
+        let matched = case
+            .boundaries
+            .iter()
+            .filter(|b| b.matched(report.tolerance))
+            .count();
+        let status = match &case.status {
+            CaseStatus::Evaluated => String::new(),
+            CaseStatus::Skipped { reason } => format!("skipped: {}", reason),
+            CaseStatus::Failed { reason } => format!("failed: {}", reason),
+        };
+        let _ = writeln!(
+            out,
+            "{:>6}  {:<40}  {:>7}  {:>8}  {:>8}  {}",
+            case.concert_id,
+            truncate(&case.album, 40),
+            format!("{}/{}", matched, case.boundaries.len()),
+            seconds(percentile(&errors, 0.5)),
+            seconds(errors.last().copied()),
+            status
+        );
+    }
+
+    let s = &report.summary;
+    let _ = writeln!(
+        out,
+        "\n{} evaluated, {} failed, {} skipped ({} timestamps)",
+        s.cases_evaluated,
+        s.cases_failed,
+        s.cases_skipped,
+        match report.source {
+            EvalSource::Detect => "re-detected",
+            EvalSource::Stored => "stored",
+        }
+    );
+    let _ = writeln!(
+        out,
+        "Matched within {}s: {}/{} ({:.1}%), {} not detected",
+        report.tolerance,
+        s.matched,
+        s.boundaries,
+        s.match_rate * 100.0,
+        s.boundaries - s.detected
+    );
+    let _ = writeln!(
+        out,
+        "Error: median {}, p90 {}, max {}",
+        seconds(s.median_error),
+        seconds(s.p90_error),
+        seconds(s.max_error)
+    );
+    let labels = ["<=0.5s", "<=1s", "<=2s", "<=5s", "<=10s", ">10s"];
+    let buckets: Vec<String> = labels
+        .iter()
+        .zip(&s.histogram)
+        .map(|(label, count)| format!("{} {}", label, count))
+        .collect();
+    let _ = writeln!(out, "Histogram: {}", buckets.join(", "));
+
+    if let Some(regressions) = regressions {
+        let _ = writeln!(out, "\nRegressions against baseline: {}", regressions.len());
+        for r in regressions {
+            let _ = writeln!(
+                out,
+                "  [{}] {}: {} (was {}, now {})",
+                r.concert_id,
+                r.album,
+                r.title,
+                seconds(Some(r.baseline_error)),
+                r.error
+                    .map(|e| seconds(Some(e)))
+                    .unwrap_or_else(|| "not detected".to_string())
+            );
+        }
+    }
+    out
+}
+
+fn display_album(concert: &ConcertInfo) -> String {
+    if concert.album.is_empty() {
+        concert.artist.clone()
+    } else {
+        concert.album.clone()
+    }
+}
+
+fn seconds(value: Option<f64>) -> String {
+    value
+        .map(|v| format!("{:.1}s", v))
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn truncate(text: &str, width: usize) -> String {
+    if text.chars().count() <= width {
+        text.to_string()
+    } else {
+        let mut short: String = text.chars().take(width - 1).collect();
+        short.push('…');
+        short
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(title: &str, start_time: f64) -> SongTimestamp {
+        SongTimestamp {
+            title: title.to_string(),
+            start_time,
+            end_time: start_time + 100.0,
+            duration: 100.0,
+            boundary: None,
+        }
+    }
+
+    fn case(
+        concert_id: i64,
+        truth: Vec<SongTimestamp>,
+        auto: Option<Vec<SongTimestamp>>,
+    ) -> EvalCase {
+        EvalCase {
+            concert_id,
+            concert: ConcertInfo {
+                artist: "Artist".to_string(),
+                source: String::new(),
+                show: String::new(),
+                date: None,
+                album: format!("Album {}", concert_id),
+                description: None,
+                set_list: vec![],
+                musicians: vec![],
+                preview_image_url: None,
+                teaser: None,
+                timestamps: None,
+                interludes: None,
+            },
+            truth,
+            auto,
+        }
+    }
+
+    fn corpus() -> Corpus {
+        Corpus {
+            cases: vec![
+                case(
+                    1,
+                    vec![ts("A", 0.0), ts("B", 100.0), ts("C", 200.0)],
+                    Some(vec![ts("A", 0.0), ts("B", 101.5), ts("C", 230.0)]),
+                ),
+                case(
+                    2,
+                    vec![ts("D", 0.0), ts("E", 90.0)],
+                    Some(vec![ts("D", 0.4)]),
+                ),
+                case(3, vec![ts("F", 0.0)], None),
+            ],
+        }
+    }
+
+    #[test]
+    fn stored_timestamps_are_scored_by_title() {
+        let report = evaluate(
+            &corpus(),
+            Path::new("/nonexistent"),
+            EvalSource::Stored,
+            DEFAULT_TOLERANCE_SECONDS,
+            &mut |_| {},
+        );
+        let s = &report.summary;
+        assert_eq!(
+            (s.cases_evaluated, s.cases_skipped, s.cases_failed),
+            (2, 1, 0)
+        );
+        assert_eq!(s.boundaries, 5);
+        assert_eq!(s.detected, 4);
+        assert_eq!(s.matched, 3);
+        assert_eq!(s.match_rate, 0.6);
+        assert_eq!(s.median_error, Some(0.4));
+        assert_eq!(s.max_error, Some(30.0));
+        assert_eq!(s.histogram, vec![2, 0, 1, 0, 0, 1]);
+        assert_eq!(report.cases[1].boundaries[1].detected_start, None);
+
+        let table = render_table(&report, None);
+        assert!(table.contains("Matched within 2s: 3/5 (60.0%), 1 not detected"));
+        assert!(table.contains("skipped: no stored automated timestamps"));
+    }
+
+    #[test]
+    fn detect_skips_cases_without_a_downloaded_source() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = evaluate(
+            &corpus(),
+            dir.path(),
+            EvalSource::Detect,
+            DEFAULT_TOLERANCE_SECONDS,
+            &mut |_| {},
+        );
+        assert_eq!(report.summary.cases_skipped, 3);
+        assert!(report.cases.iter().all(|c| c.status
+            == CaseStatus::Skipped {
+                reason: "source not downloaded".to_string()
+            }));
+    }
+
+    #[test]
+    fn detect_options_detect_like_the_split_job() {
+        let eval = detect_options();
+        let job = library_options();
+        assert_eq!(eval.refine_timestamps, job.refine_timestamps);
+        assert_eq!(eval.analyze_images, job.analyze_images);
+        assert_eq!(eval.ocr_engine, job.ocr_engine);
+        assert_eq!(eval.ocr_threads, job.ocr_threads);
+        assert_eq!(eval.detection_sampling, job.detection_sampling);
+        assert_eq!(eval.overlay_crop, job.overlay_crop);
+        assert_eq!(eval.set_list_free, job.set_list_free);
+        assert_eq!(eval.media_duration, job.media_duration);
+        assert!(eval.no_save_songs);
+    }
+
+    #[test]
+    fn regressions_are_boundaries_that_stopped_matching() {
+        let baseline = evaluate(
+            &corpus(),
+            Path::new("/nonexistent"),
+            EvalSource::Stored,
+            DEFAULT_TOLERANCE_SECONDS,
+            &mut |_| {},
+        );
+        let mut worse = corpus();
+        worse.cases[0].auto = Some(vec![ts("A", 0.0), ts("B", 104.0), ts("C", 201.0)]);
+        worse.cases[1].auto = Some(vec![]);
+        let current = evaluate(
+            &worse,
+            Path::new("/nonexistent"),
+            EvalSource::Stored,
+            DEFAULT_TOLERANCE_SECONDS,
+            &mut |_| {},
+        );
+
+        let found = regressions(&baseline, &current);
+        let titles: Vec<(&str, Option<f64>)> =
+            found.iter().map(|r| (r.title.as_str(), r.error)).collect();
+        assert_eq!(titles, vec![("B", Some(4.0)), ("D", None)]);
+        // C got better, which is not a regression.
+        assert!(render_table(&current, Some(&found)).contains("Regressions against baseline: 2"));
+
+        let json = serde_json::to_string(&current).unwrap();
+        let reread: EvalReport = serde_json::from_str(&json).unwrap();
+        assert_eq!(reread.summary, current.summary);
+    }
+
+    #[test]
+    fn export_collects_concerts_with_user_timestamps() {
+        let conn = db::connection::open_in_memory().unwrap();
+        let seeds = db::seeds::SeedContext::new(&conn);
+        let mut ids = Vec::new();
+        for album in ["Corrected", "Automatic"] {
+            let id = seeds
+                .seed_scraped_concert(db::seeds::SeedScrapedConcert {
+                    source_url: Some(format!("https://npr.org/c/{}", album)),
+                    title: Some(album.to_string()),
+                    concert_date: None,
+                    artist: Some("Test Artist".to_string()),
+                    album: Some(album.to_string()),
+                    set_list: Some(vec!["A".to_string(), "B".to_string()]),
+                })
+                .unwrap()
+                .id;
+            db::split_timestamps::set_auto_split_timestamps(
+                &conn,
+                id,
+                &[ts("A", 0.0), ts("B", 95.0)],
+            )
+            .unwrap();
+            ids.push(id);
+        }
+        db::split_timestamps::set_user_split_timestamps(
+            &conn,
+            ids[0],
+            &[ts("A", 0.0), ts("B", 100.0)],
+        )
+        .unwrap();
+
+        let corpus = export_corpus(&conn).unwrap();
+        assert_eq!(corpus.cases.len(), 1);
+        let case = &corpus.cases[0];
+        assert_eq!(case.concert_id, ids[0]);
+        assert_eq!(case.concert.album, "Corrected");
+        assert_eq!(case.concert.set_list.len(), 2);
+        assert_eq!(case.truth[1].start_time, 100.0);
+        assert_eq!(case.auto.as_ref().unwrap()[1].start_time, 95.0);
+    }
+}
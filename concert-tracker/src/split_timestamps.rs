@@ -473,6 +473,7 @@ impl ValidatedTimestamps {
                 start_time: song.start_time,
                 end_time: song.end_time,
                 duration,
+                boundary: None,
             });
         }
 
@@ -610,6 +611,7 @@ mod tests {
                 start_time: (i * 60) as f64,
                 end_time: (i * 60 + 55) as f64,
                 duration: 55.0,
+                boundary: None,
             })
             .collect()
     }
@@ -788,6 +790,7 @@ mod tests {
                 start_time: 0.0,
                 end_time: 180.0,
                 duration: 180.0,
+                boundary: None,
             }]),
             user: None,
             media_duration: Some(212.5),
@@ -820,12 +823,14 @@ mod tests {
                 start_time: 1.0,
                 end_time: 50.0,
                 duration: 49.0,
+                boundary: None,
             },
             SongTimestamp {
                 title: "Beta".to_string(),
                 start_time: 55.0,
                 end_time: 100.0,
                 duration: 45.0,
+                boundary: None,
             },
         ];
         db::split_timestamps::set_user_split_timestamps(&conn, id, &user).unwrap();
@@ -861,6 +866,7 @@ mod tests {
             preview_image_url: None,
             teaser: None,
             timestamps: Some(sample_timestamps(&songs)),
+            interludes: None,
         })
         .unwrap();
         std::fs::write(concert_dir.join("timestamps.json"), timestamps_json).unwrap();
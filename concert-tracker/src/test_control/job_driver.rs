@@ -9,12 +9,14 @@ use std::future::Future;
 use std::path::Path;
 use std::sync::Mutex;
 
+use live_set_splitter::concert_split::AudioFormat;
 use serde::Deserialize;
 
 use crate::db::seeds::{
     fake_analysis_timestamps, write_interlude_sentinels, write_legacy_timestamps_json,
     write_track_sentinels, SENTINEL_BYTES,
 };
+use crate::jobs::progress::ProgressReporter;
 use crate::jobs::{
     DownloadJob, JobRunFuture, JobRunner, JobStepFailure, JobStepOutcome, OpenMediaOutcome,
     SplitJob, SplitMode,
@@ -325,6 +327,7 @@ impl JobRunner for TestControlJobRunner {
         &'a self,
         job: &'a DownloadJob,
         _log_file: Option<&'a Path>,
+        _progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome> {
         Box::pin(async move {
             self.driver
@@ -339,6 +342,7 @@ impl JobRunner for TestControlJobRunner {
         &'a self,
         job: &'a SplitJob,
         _log_file: Option<&'a Path>,
+        _progress: &'a ProgressReporter,
     ) -> JobRunFuture<'a, JobStepOutcome> {
         Box::pin(async move {
             self.driver
@@ -437,16 +441,27 @@ fn write_split_output(job: &SplitJob) -> anyhow::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::db::settings::DownloadFormat;
+    use crate::jobs::{JobKey, JobKind};
     use crate::split_timestamps::{TimestampPayloadSong, ValidatedTimestamps};
     use std::path::PathBuf;
     use std::sync::Arc;
 
+    /// The runner ignores progress; these tests drive it without a registry.
+    fn no_progress() -> ProgressReporter {
+        ProgressReporter::detached(JobKey {
+            concert_id: 1,
+            kind: JobKind::Download,
+        })
+    }
+
     fn download_job(concert_id: i64, working_dir: &Path, album: &str) -> DownloadJob {
         DownloadJob {
             concert_id,
             source_url: "https://npr.org/test".to_string(),
             album: album.to_string(),
             working_dir: working_dir.to_path_buf(),
+            format: DownloadFormat::Video,
         }
     }
 
@@ -482,6 +497,11 @@ mod tests {
             input_file: PathBuf::from("/nonexistent/source.mp4"),
             output_dir: output_dir.to_path_buf(),
             mode: SplitMode::Analyze,
+            report_dir: PathBuf::from("/nonexistent/split-report"),
+            audio_formats: vec![AudioFormat::M4a],
+            renditions: Vec::new(),
+            hls: false,
+            cover_art: output_dir.join("preview.jpg"),
             _temp_file: json_file,
             _timestamps_temp_file: None,
             timestamps_path: None,
@@ -507,6 +527,11 @@ mod tests {
             input_file: PathBuf::from("/nonexistent/source.mp4"),
             output_dir: output_dir.to_path_buf(),
             mode: SplitMode::UserTimestamps { ts, media_duration },
+            report_dir: PathBuf::from("/nonexistent/split-report"),
+            audio_formats: vec![AudioFormat::M4a],
+            renditions: Vec::new(),
+            hls: false,
+            cover_art: output_dir.join("preview.jpg"),
             _temp_file: tempfile::NamedTempFile::new().unwrap(),
             _timestamps_temp_file: None,
             timestamps_path: None,
@@ -785,7 +810,7 @@ mod tests {
         let runner = TestControlJobRunner::new(driver);
         let job = download_job(1, tmp.path(), "Sentinel Album");
 
-        let outcome = runner.run_download(&job, None).await;
+        let outcome = runner.run_download(&job, None, &no_progress()).await;
 
         assert!(matches!(outcome, JobStepOutcome::Succeeded));
         assert!(crate::concert_media::find_downloaded_file(tmp.path(), "Sentinel Album").is_some());
@@ -801,7 +826,7 @@ mod tests {
         let runner = TestControlJobRunner::new(driver);
         let job = download_job(1, tmp.path(), "No File Album");
 
-        let outcome = runner.run_download(&job, None).await;
+        let outcome = runner.run_download(&job, None, &no_progress()).await;
 
         assert!(matches!(outcome, JobStepOutcome::Failed(_)));
         assert!(crate::concert_media::find_downloaded_file(tmp.path(), "No File Album").is_none());
@@ -815,7 +840,7 @@ mod tests {
         let runner = TestControlJobRunner::new(driver);
         let job = analyze_split_job(1, &output_dir, &["Song A", "Song B"]);
 
-        let outcome = runner.run_split(&job, None).await;
+        let outcome = runner.run_split(&job, None, &no_progress()).await;
 
         assert!(matches!(outcome, JobStepOutcome::Succeeded));
         assert!(output_dir.join("Song A.m4a").exists());
@@ -841,7 +866,7 @@ mod tests {
         // — both well over MIN_INTERLUDE_SECONDS.
         let job = user_timestamps_split_job(1, &output_dir, &set_list, payload, 200.0);
 
-        let outcome = runner.run_split(&job, None).await;
+        let outcome = runner.run_split(&job, None, &no_progress()).await;
 
         assert!(matches!(outcome, JobStepOutcome::Succeeded));
         assert!(output_dir.join("Alpha.m4a").exists());
@@ -879,6 +904,11 @@ mod tests {
             input_file: PathBuf::from("/nonexistent/source.mp4"),
             output_dir: output_dir.clone(),
             mode: SplitMode::ResetToAuto(ts),
+            report_dir: PathBuf::from("/nonexistent/split-report"),
+            audio_formats: vec![AudioFormat::M4a],
+            renditions: Vec::new(),
+            hls: false,
+            cover_art: output_dir.join("preview.jpg"),
             _temp_file: tempfile::NamedTempFile::new().unwrap(),
             _timestamps_temp_file: None,
             timestamps_path: None,
@@ -886,7 +916,7 @@ mod tests {
             _outcome_file: outcome_file,
         };
 
-        let outcome = runner.run_split(&job, None).await;
+        let outcome = runner.run_split(&job, None, &no_progress()).await;
 
         assert!(matches!(outcome, JobStepOutcome::Succeeded));
         assert!(output_dir.join("Alpha.m4a").exists());
@@ -5,21 +5,29 @@ use askama_axum::IntoResponse;
 use axum::{
     extract::{Form, Path, Query, State},
     http::{HeaderMap, HeaderValue, StatusCode},
+    response::sse::{self, KeepAlive, Sse},
     response::Response,
     Json,
 };
+use futures_util::Stream;
+use live_set_splitter::concert_split::AudioFormat;
+use live_set_splitter::cut::Rendition;
+use live_set_splitter::hls;
 use rusqlite::Connection;
 use utoipa::ToSchema;
 
+use crate::chapters::WriteChaptersOutcome;
+use crate::concert_media::VideoRenditions;
 use crate::concert_media::{find_downloaded_file, ConcertMediaInventory};
 use crate::db;
 use crate::jobs::download::start_download;
 use crate::jobs::split::start_split;
 use crate::jobs::OpenMediaOutcome;
-use crate::jobs::{JobKey, JobKind, SplitMode};
+use crate::jobs::{JobKey, JobKind, JobRegistry, SplitMode};
 use crate::lifecycle::{
     CancelJobOutcome, DeleteDownloadOutcome, DeleteRedundantSourceOutcome, DeleteSplitOutcome,
 };
+use crate::loudness::PlaybackGain;
 use crate::model::{
     concert_dir, ArchiveStatus, Concert, DownloadStatus, PlaybackItemKind, SplitStatus, TrackInfo,
 };
@@ -129,6 +137,46 @@ struct DetailTemplate {
     card_html: String,
     notes_value: String,
     events: Vec<crate::events::EventRow>,
+    chapters_html: String,
+    /// The concert's artist is on the follow list.
+    artist_followed: bool,
+    /// The last split left a diagnostic report.
+    has_split_report: bool,
+    /// The last split's contact sheet, when it wrote one.
+    contact_sheet_url: Option<String>,
+}
+
+/// Concert-detail chaptered-copy control: a download link when the file
+/// exists, the (re)write button, and the last attempt's error if any.
+#[derive(Template)]
+#[template(path = "chapters_action.html")]
+struct ChaptersActionTemplate {
+    id: i64,
+    url: Option<String>,
+    error: Option<String>,
+}
+
+fn render_chapters_action(
+    working_dir: &std::path::Path,
+    concert: &Concert,
+    error: Option<String>,
+) -> Result<String, AppError> {
+    let url = concert.album.as_deref().and_then(|album| {
+        crate::chapters::find_chapters_file(working_dir, concert).map(|file| {
+            format!(
+                "/concert-files/{}/{}",
+                crate::model::sanitize_album(album),
+                file
+            )
+        })
+    });
+    ChaptersActionTemplate {
+        id: concert.id,
+        url,
+        error,
+    }
+    .render()
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("{}", e)))
 }
 
 #[derive(Template)]
@@ -185,9 +233,38 @@ struct TrackListenButtonTemplate {
 struct SettingsTemplate {
     chrome: Chrome,
     archive_location: String,
+    replay_gain: db::settings::ReplayGainMode,
+    download_format: db::settings::DownloadFormat,
+    audio_format_choices: Vec<AudioFormatChoice>,
+    rendition_choices: Vec<RenditionChoice>,
+    video_quality: db::settings::VideoQuality,
+    hls_packaging: bool,
+    auto_sync: db::settings::AutoSync,
+    auto_sync_intervals: &'static [u32],
     saved: bool,
 }
 
+/// One checkbox of the settings page's audio formats.
+struct AudioFormatChoice {
+    ext: &'static str,
+    label: &'static str,
+    checked: bool,
+}
+
+/// One checkbox of the settings page's video renditions; its name is also a
+/// video quality option.
+struct RenditionChoice {
+    name: &'static str,
+    checked: bool,
+}
+
+#[derive(Template)]
+#[template(path = "following.html")]
+struct FollowingTemplate {
+    chrome: Chrome,
+    artists: Vec<db::followed_artists::FollowedArtist>,
+}
+
 #[derive(Template)]
 #[template(path = "delete_confirm.html")]
 struct DeleteConfirmTemplate {
@@ -201,6 +278,10 @@ struct JobRow {
     kind_slug: &'static str,
     kind_label: &'static str,
     started_at: String,
+    /// 1-based place in its kind's queue while waiting for a slot.
+    queue_position: Option<usize>,
+    /// "Attempt n of max" once a download has been retried.
+    attempt: Option<String>,
 }
 
 struct FailedJobRow {
@@ -394,11 +475,14 @@ fn render_card(state: &AppState, id: i64) -> Result<String, AppError> {
             .unwrap_or(None);
         (concert, stored_ts)
     };
-    let tracks = crate::model::list_all_tracks_from_db(
+    let mut tracks = crate::model::list_all_tracks_from_db(
         &concert.set_list,
         &concert.tracks_present,
         &concert.tracks_liked,
     );
+    if let Some(album) = concert.album.as_deref() {
+        crate::concert_media::attach_posters(&state.jobs.working_dir, album, &mut tracks);
+    }
     let inventory =
         ConcertMediaInventory::for_concert(&state.jobs.working_dir, &concert, stored_ts.as_deref());
     let source_redundant = inventory.source_redundant();
@@ -460,8 +544,11 @@ fn render_detail_card(
     stored_user_ts: Option<&[concert_types::SongTimestamp]>,
     working_dir: &std::path::Path,
 ) -> Result<String, askama::Error> {
-    let tracks =
+    let mut tracks =
         crate::model::list_all_tracks_from_db(&c.set_list, &c.tracks_present, &c.tracks_liked);
+    if let Some(album) = c.album.as_deref() {
+        crate::concert_media::attach_posters(working_dir, album, &mut tracks);
+    }
     let inventory = ConcertMediaInventory::for_concert(working_dir, c, stored_user_ts);
     let source_redundant = inventory.source_redundant();
     let can_play_concert = inventory.can_play_concert();
@@ -707,20 +794,47 @@ pub async fn detail(
     )
     .map_err(|e| AppError::Internal(anyhow::anyhow!("{}", e)))?;
     let notes_value = concert.notes.clone().unwrap_or_default();
-    let events = {
+    let (events, artist_followed) = {
         let conn = state.db.lock().unwrap();
-        crate::events::list_for_concert(&conn, id)
+        let followed = match concert.artist.as_deref() {
+            Some(artist) => db::followed_artists::find_followed(&conn, artist)?.is_some(),
+            None => false,
+        };
+        (crate::events::list_for_concert(&conn, id), followed)
     };
+    let chapters_html = render_chapters_action(&state.jobs.working_dir, &concert, None)?;
 
     Ok(DetailTemplate {
         chrome: Chrome::from_state(&state),
         card_html,
         notes_value,
         events,
+        chapters_html,
+        artist_followed,
+        has_split_report: state.jobs.split_report(id).is_some(),
+        contact_sheet_url: concert.album.as_deref().and_then(|album| {
+            crate::concert_media::contact_sheet_url(&state.jobs.working_dir, album)
+        }),
         concert,
     })
 }
 
+/// `GET /concerts/:id/split-report`: the splitter's diagnostic report of the
+/// concert's last split.
+pub async fn split_report(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let path = state.jobs.split_report(id).ok_or(AppError::NotFound)?;
+    let html = tokio::fs::read_to_string(&path)
+        .await
+        .map_err(|_| AppError::NotFound)?;
+    Ok((
+        [(axum::http::header::CONTENT_TYPE, "text/html; charset=utf-8")],
+        html,
+    ))
+}
+
 pub async fn ignore(
     State(state): State<AppState>,
     Path(id): Path<i64>,
@@ -1194,6 +1308,58 @@ pub async fn delete_split(
     Ok(([("content-type", "text/html; charset=utf-8")], body).into_response())
 }
 
+/// Write a chaptered copy of the downloaded source from the stored split
+/// timestamps (user, else auto). Returns the refreshed chapters control; a
+/// missing source/timestamps or an ffmpeg failure is shown inline there rather
+/// than as an error status, so htmx still swaps it in.
+pub async fn write_chapters(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<impl IntoResponse, AppError> {
+    let (concert, timestamps) = {
+        let conn = state.db.lock().unwrap();
+        let concert = db::concerts::get_concert(&conn, id).map_err(|_| AppError::NotFound)?;
+        let stored = db::split_timestamps::get_split_timestamps(&conn, id)?;
+        (concert, stored.user.or(stored.auto))
+    };
+
+    tracing::info!(
+        "write-chapters started for concert {} ({})",
+        id,
+        concert.title
+    );
+    let working_dir = state.jobs.working_dir.clone();
+    let task_concert = concert.clone();
+    let result = tokio::task::spawn_blocking(move || {
+        crate::chapters::write_chapters(&working_dir, &task_concert, timestamps.as_deref())
+    })
+    .await
+    .map_err(|e| AppError::Internal(anyhow::anyhow!("chapters task failed: {}", e)))?;
+
+    let error = match result {
+        Ok(WriteChaptersOutcome::Written { filename }) => {
+            let json = serde_json::json!({ "file": filename }).to_string();
+            let conn = state.db.lock().unwrap();
+            crate::events::record_now(
+                &conn,
+                id,
+                crate::events::Event::ChaptersWritten,
+                Some(&json),
+            );
+            None
+        }
+        Ok(WriteChaptersOutcome::NoSource) => Some("Source file not found".to_string()),
+        Ok(WriteChaptersOutcome::NoTimestamps) => {
+            Some("No split timestamps to build chapters from".to_string())
+        }
+        Err(e) => {
+            tracing::error!("write-chapters failed for concert {}: {:#}", id, e);
+            Some(format!("Writing chapters failed: {e:#}"))
+        }
+    };
+    render_chapters_action(&state.jobs.working_dir, &concert, error)
+}
+
 pub async fn listen(
     State(state): State<AppState>,
     Path(id): Path<i64>,
@@ -1240,6 +1406,15 @@ pub struct PlaybackItemJson {
     pub track_index: Option<usize>,
     pub interlude_index: Option<usize>,
     pub liked: bool,
+    /// dB the player applies under the configured ReplayGain mode; `None`
+    /// when the mode is off or the concert has no loudness measurements.
+    pub gain_db: Option<f64>,
+    /// HLS playlist of this item's span of the concert's HLS package, for
+    /// players that stream HLS; `None` without a package.
+    pub hls_url: Option<String>,
+    /// The song's poster frame for the player bar; `None` for interludes and
+    /// songs split without posters.
+    pub poster_url: Option<String>,
 }
 
 /// Tagged-union response for `GET /concerts/:id/concert-playback`.
@@ -1256,7 +1431,10 @@ pub enum ConcertPlaybackResponse {
     get,
     path = "/concerts/{id}/concert-playback",
     tag = "playback",
-    params(("id" = i64, Path, description = "Concert ID")),
+    params(
+        ("id" = i64, Path, description = "Concert ID"),
+        ("quality" = Option<String>, Query, description = "Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed"),
+    ),
     responses(
         (status = 200, description = "Playback plan: whole-source or reconstructed from tracks", body = ConcertPlaybackResponse),
         (status = 404, description = "Concert not found"),
@@ -1266,28 +1444,47 @@ pub enum ConcertPlaybackResponse {
 pub async fn concert_playback(
     State(state): State<AppState>,
     Path(id): Path<i64>,
+    Query(params): Query<QualityParams>,
 ) -> Result<Json<ConcertPlaybackResponse>, AppError> {
-    let (concert, stored_ts, working_dir) = {
+    let (concert, stored_ts, working_dir, gain_mode, quality) = {
         let conn = state.db.lock().unwrap();
         let concert = db::concerts::get_concert(&conn, id).map_err(|_| AppError::NotFound)?;
         let stored_ts = db::split_timestamps::get_split_timestamps(&conn, id)
             .map(|s| s.user)
             .unwrap_or(None);
         let working_dir = state.jobs.working_dir.clone();
-        (concert, stored_ts, working_dir)
+        (
+            concert,
+            stored_ts,
+            working_dir,
+            replay_gain_mode(&conn)?,
+            video_quality(&conn, &params)?,
+        )
     };
 
     let album = concert.album.as_deref().ok_or(AppError::NotFound)?;
+    let gain = PlaybackGain::load(&working_dir, album, gain_mode);
+    let renditions = VideoRenditions::load(&working_dir, album, quality);
+    let hls = HlsUrls::find(&working_dir, id, album);
     match crate::playback::concert_playback_plan(&working_dir, &concert, stored_ts.as_deref()) {
         Ok(PlaybackPlan::Source(source)) => Ok(Json(ConcertPlaybackResponse::Source {
-            source: media_info_from_source(album, source),
+            source: media_info_from_source(album, source, &gain, hls.as_ref()),
         })),
         Ok(PlaybackPlan::Reconstruction(items)) => {
             let artist = concert.artist.unwrap_or_default();
-            let sanitized_album = crate::model::sanitize_album(album);
             let json_items: Vec<PlaybackItemJson> = items
                 .into_iter()
-                .map(|item| playback_item_json(&sanitized_album, &artist, item))
+                .map(|item| {
+                    playback_item_json(
+                        &working_dir,
+                        album,
+                        &artist,
+                        item,
+                        &gain,
+                        &renditions,
+                        hls.as_ref(),
+                    )
+                })
                 .collect();
             Ok(Json(ConcertPlaybackResponse::Reconstruction {
                 items: json_items,
@@ -1310,7 +1507,7 @@ pub async fn delete_interlude(
     let stem = concert_types::interlude_filename_stem(idx);
 
     let mut removed = false;
-    for ext in crate::model::INTERLUDE_EXTENSIONS {
+    for ext in crate::model::SPLIT_TRACK_EXTENSIONS.iter() {
         let path = dir.join(format!("{stem}.{ext}"));
         match std::fs::remove_file(&path) {
             Ok(()) => {
@@ -1402,9 +1599,55 @@ pub struct MediaInfo {
     /// Whether this track is liked, so the player bar can show its like star.
     /// Always false for whole-album playback (no per-track like).
     pub liked: bool,
+    /// dB the player applies under the configured ReplayGain mode; `None`
+    /// when the mode is off or the concert has no loudness measurements.
+    pub gain_db: Option<f64>,
+    /// HLS playlist of the same media (the whole package for whole-album
+    /// playback, the track's span otherwise), for players that stream HLS;
+    /// `None` when the concert has no HLS package or the track is a clip.
+    pub hls_url: Option<String>,
+    /// The track's poster frame for the player bar. Always `None` for
+    /// whole-album playback.
+    pub poster_url: Option<String>,
+}
+
+/// URLs into one concert's HLS package (see
+/// [`crate::concert_media::find_hls_playlist`]).
+struct HlsUrls {
+    concert_id: i64,
+    sanitized_album: String,
+    master: String,
+}
+
+impl HlsUrls {
+    fn find(working_dir: &std::path::Path, concert_id: i64, album: &str) -> Option<HlsUrls> {
+        crate::concert_media::find_hls_playlist(working_dir, album).map(|master| HlsUrls {
+            concert_id,
+            sanitized_album: crate::model::sanitize_album(album),
+            master,
+        })
+    }
+
+    /// The whole package, served as a static file.
+    fn concert(&self) -> String {
+        format!("/concert-files/{}/{}", self.sanitized_album, self.master)
+    }
+
+    /// The package narrowed to one song or interlude by [`hls_playlist`].
+    fn range(&self, range_id: &str) -> String {
+        format!(
+            "/concerts/{}/hls/{}?range={}",
+            self.concert_id, self.master, range_id
+        )
+    }
 }
 
-fn media_info_from_source(album: &str, source: SourceMedia) -> MediaInfo {
+fn media_info_from_source(
+    album: &str,
+    source: SourceMedia,
+    gain: &PlaybackGain,
+    hls: Option<&HlsUrls>,
+) -> MediaInfo {
     let sanitized_album = crate::model::sanitize_album(album);
     MediaInfo {
         url: format!("/concert-files/{}/{}", sanitized_album, source.filename),
@@ -1416,13 +1659,37 @@ fn media_info_from_source(album: &str, source: SourceMedia) -> MediaInfo {
         has_next: false,
         has_prev: false,
         liked: false,
+        gain_db: gain.for_file(&source.filename),
+        hls_url: hls.map(HlsUrls::concert),
+        poster_url: None,
     }
 }
 
-fn media_info_from_track(album: &str, track: TrackMedia) -> MediaInfo {
+fn media_info_from_track(
+    working_dir: &std::path::Path,
+    album: &str,
+    track: TrackMedia,
+    gain: &PlaybackGain,
+    renditions: &VideoRenditions,
+    hls: Option<&HlsUrls>,
+) -> MediaInfo {
     let sanitized_album = crate::model::sanitize_album(album);
+    let hls_url = match (&track.clip, hls) {
+        (None, Some(hls)) => Some(hls.range(&hls::song_range_id(track.track_index))),
+        _ => None,
+    };
+    let poster_url = crate::concert_media::poster_url(working_dir, album, &track.title);
+    let fragment = track
+        .clip
+        .map(|clip| clip.url_fragment())
+        .unwrap_or_default();
     MediaInfo {
-        url: format!("/concert-files/{}/{}", sanitized_album, track.filename),
+        url: format!(
+            "/concert-files/{}/{}{}",
+            sanitized_album,
+            renditions.for_file(&track.filename),
+            fragment
+        ),
         title: track.title,
         artist: track.artist,
         is_video: track.is_video,
@@ -1431,25 +1698,47 @@ fn media_info_from_track(album: &str, track: TrackMedia) -> MediaInfo {
         has_next: track.has_next,
         has_prev: track.has_prev,
         liked: track.liked,
+        gain_db: gain.for_file(&track.filename),
+        hls_url,
+        poster_url,
     }
 }
 
 fn playback_item_json(
-    sanitized_album: &str,
+    working_dir: &std::path::Path,
+    album: &str,
     artist: &str,
     item: crate::model::PlaybackItem,
+    gain: &PlaybackGain,
+    renditions: &VideoRenditions,
+    hls: Option<&HlsUrls>,
 ) -> PlaybackItemJson {
-    let url = format!("/concert-files/{}/{}", sanitized_album, item.filename);
+    let hls = hls.filter(|_| item.clip.is_none());
+    let sanitized_album = crate::model::sanitize_album(album);
+    let fragment = item
+        .clip
+        .map(|clip| clip.url_fragment())
+        .unwrap_or_default();
+    let url = format!(
+        "/concert-files/{}/{}{}",
+        sanitized_album,
+        renditions.for_file(&item.filename),
+        fragment
+    );
+    let gain_db = gain.for_file(&item.filename);
     match item.kind {
         PlaybackItemKind::Song { track_index, liked } => PlaybackItemJson {
             kind: "song",
-            title: item.title,
             url,
             is_video: item.is_video,
             artist: artist.to_string(),
             track_index: Some(track_index),
             interlude_index: None,
             liked,
+            gain_db,
+            hls_url: hls.map(|hls| hls.range(&hls::song_range_id(track_index))),
+            poster_url: crate::concert_media::poster_url(working_dir, album, &item.title),
+            title: item.title,
         },
         PlaybackItemKind::Interlude { index } => PlaybackItemJson {
             kind: "interlude",
@@ -1460,10 +1749,38 @@ fn playback_item_json(
             track_index: None,
             interlude_index: Some(index),
             liked: false,
+            gain_db,
+            hls_url: hls.map(|hls| hls.range(&hls::interlude_range_id(index))),
+            poster_url: None,
         },
     }
 }
 
+fn replay_gain_mode(conn: &rusqlite::Connection) -> anyhow::Result<db::settings::ReplayGainMode> {
+    Ok(db::settings::get_settings(conn)?.replay_gain)
+}
+
+#[derive(serde::Deserialize)]
+pub struct QualityParams {
+    #[serde(default)]
+    quality: Option<String>,
+}
+
+/// The video quality to stream: the configured one, lowered to the player's
+/// `?quality=` (picked from its screen size) when that is smaller.
+fn video_quality(
+    conn: &rusqlite::Connection,
+    params: &QualityParams,
+) -> Result<db::settings::VideoQuality, AppError> {
+    let configured = db::settings::get_settings(conn)?.video_quality;
+    let Some(requested) = params.quality.as_deref() else {
+        return Ok(configured);
+    };
+    let requested = db::settings::VideoQuality::parse(requested)
+        .map_err(|e| AppError::BadRequest(e.to_string()))?;
+    Ok(configured.min(requested))
+}
+
 fn playback_error_to_app_error(e: PlaybackLookupError) -> AppError {
     match e {
         PlaybackLookupError::NotPlayable => AppError::NotFound,
@@ -1494,17 +1811,28 @@ pub async fn media_info(
     State(state): State<AppState>,
     Path(id): Path<i64>,
 ) -> Result<Json<MediaInfo>, AppError> {
-    let (concert, working_dir) = {
+    let (concert, working_dir, gain_mode) = {
         let conn = state.db.lock().unwrap();
         let concert = db::concerts::get_concert(&conn, id).map_err(|_| AppError::NotFound)?;
-        (concert, state.jobs.working_dir.clone())
+        (
+            concert,
+            state.jobs.working_dir.clone(),
+            replay_gain_mode(&conn)?,
+        )
     };
 
     let album = concert.album.as_deref().ok_or(AppError::NotFound)?;
+    let gain = PlaybackGain::load(&working_dir, album, gain_mode);
     let source = crate::playback::source_media(&working_dir, &concert)
         .map_err(playback_error_to_app_error)?;
 
-    Ok(Json(media_info_from_source(album, source)))
+    let hls = HlsUrls::find(&working_dir, id, album);
+    Ok(Json(media_info_from_source(
+        album,
+        source,
+        &gain,
+        hls.as_ref(),
+    )))
 }
 
 #[utoipa::path(
@@ -1514,6 +1842,7 @@ pub async fn media_info(
     params(
         ("id" = i64, Path, description = "Concert ID"),
         ("idx" = usize, Path, description = "0-based set-list track index"),
+        ("quality" = Option<String>, Query, description = "Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed"),
     ),
     responses(
         (status = 200, description = "Track playback info", body = MediaInfo),
@@ -1523,18 +1852,34 @@ pub async fn media_info(
 pub async fn track_media_info(
     State(state): State<AppState>,
     Path((id, idx)): Path<(i64, usize)>,
+    Query(params): Query<QualityParams>,
 ) -> Result<Json<MediaInfo>, AppError> {
-    let (concert, working_dir) = {
+    let (concert, working_dir, gain_mode, quality) = {
         let conn = state.db.lock().unwrap();
         let concert = db::concerts::get_concert(&conn, id).map_err(|_| AppError::NotFound)?;
-        (concert, state.jobs.working_dir.clone())
+        (
+            concert,
+            state.jobs.working_dir.clone(),
+            replay_gain_mode(&conn)?,
+            video_quality(&conn, &params)?,
+        )
     };
 
     let album = concert.album.as_deref().ok_or(AppError::NotFound)?;
+    let gain = PlaybackGain::load(&working_dir, album, gain_mode);
+    let renditions = VideoRenditions::load(&working_dir, album, quality);
     let media = crate::playback::track_media(&working_dir, &concert, idx)
         .map_err(playback_error_to_app_error)?;
 
-    Ok(Json(media_info_from_track(album, media)))
+    let hls = HlsUrls::find(&working_dir, id, album);
+    Ok(Json(media_info_from_track(
+        &working_dir,
+        album,
+        media,
+        &gain,
+        &renditions,
+        hls.as_ref(),
+    )))
 }
 
 #[utoipa::path(
@@ -1544,6 +1889,7 @@ pub async fn track_media_info(
     params(
         ("id" = i64, Path, description = "Concert ID"),
         ("idx" = usize, Path, description = "0-based set-list index to advance from"),
+        ("quality" = Option<String>, Query, description = "Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed"),
     ),
     responses(
         (status = 200, description = "Next playable track's playback info", body = MediaInfo),
@@ -1553,17 +1899,33 @@ pub async fn track_media_info(
 pub async fn next_track_media_info(
     State(state): State<AppState>,
     Path((id, idx)): Path<(i64, usize)>,
+    Query(params): Query<QualityParams>,
 ) -> Result<Json<MediaInfo>, AppError> {
-    let (concert, working_dir) = {
+    let (concert, working_dir, gain_mode, quality) = {
         let conn = state.db.lock().unwrap();
         let concert = db::concerts::get_concert(&conn, id).map_err(|_| AppError::NotFound)?;
-        (concert, state.jobs.working_dir.clone())
+        (
+            concert,
+            state.jobs.working_dir.clone(),
+            replay_gain_mode(&conn)?,
+            video_quality(&conn, &params)?,
+        )
     };
 
     let album = concert.album.as_deref().ok_or(AppError::NotFound)?;
+    let gain = PlaybackGain::load(&working_dir, album, gain_mode);
+    let renditions = VideoRenditions::load(&working_dir, album, quality);
     let media = crate::playback::next_track_media(&working_dir, &concert, idx)
         .map_err(playback_error_to_app_error)?;
-    Ok(Json(media_info_from_track(album, media)))
+    let hls = HlsUrls::find(&working_dir, id, album);
+    Ok(Json(media_info_from_track(
+        &working_dir,
+        album,
+        media,
+        &gain,
+        &renditions,
+        hls.as_ref(),
+    )))
 }
 
 /// Media info for the nearest playable track *before* `idx` (the Back button).
@@ -1575,6 +1937,7 @@ pub async fn next_track_media_info(
     params(
         ("id" = i64, Path, description = "Concert ID"),
         ("idx" = usize, Path, description = "0-based set-list index to go back from"),
+        ("quality" = Option<String>, Query, description = "Video quality for this player's screen (`original`, `1080p`, `720p`, `480p` or `360p`); the lower of it and the configured quality is streamed"),
     ),
     responses(
         (status = 200, description = "Previous playable track's playback info", body = MediaInfo),
@@ -1584,17 +1947,71 @@ pub async fn next_track_media_info(
 pub async fn prev_track_media_info(
     State(state): State<AppState>,
     Path((id, idx)): Path<(i64, usize)>,
+    Query(params): Query<QualityParams>,
 ) -> Result<Json<MediaInfo>, AppError> {
-    let (concert, working_dir) = {
+    let (concert, working_dir, gain_mode, quality) = {
         let conn = state.db.lock().unwrap();
         let concert = db::concerts::get_concert(&conn, id).map_err(|_| AppError::NotFound)?;
-        (concert, state.jobs.working_dir.clone())
+        (
+            concert,
+            state.jobs.working_dir.clone(),
+            replay_gain_mode(&conn)?,
+            video_quality(&conn, &params)?,
+        )
     };
 
     let album = concert.album.as_deref().ok_or(AppError::NotFound)?;
+    let gain = PlaybackGain::load(&working_dir, album, gain_mode);
+    let renditions = VideoRenditions::load(&working_dir, album, quality);
     let media = crate::playback::prev_track_media(&working_dir, &concert, idx)
         .map_err(playback_error_to_app_error)?;
-    Ok(Json(media_info_from_track(album, media)))
+    let hls = HlsUrls::find(&working_dir, id, album);
+    Ok(Json(media_info_from_track(
+        &working_dir,
+        album,
+        media,
+        &gain,
+        &renditions,
+        hls.as_ref(),
+    )))
+}
+
+/// One playlist of the concert's HLS package narrowed to the song or
+/// interlude `?range=` names (see [`crate::concert_media::hls_range_playlist`]).
+/// The whole package needs no narrowing and is served from `/concert-files/`.
+pub async fn hls_playlist(
+    State(state): State<AppState>,
+    Path((id, playlist)): Path<(i64, String)>,
+    Query(params): Query<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    let concert = {
+        let conn = state.db.lock().unwrap();
+        db::concerts::get_concert(&conn, id).map_err(|_| AppError::NotFound)?
+    };
+    let album = concert.album.as_deref().ok_or(AppError::NotFound)?;
+    let range = params.get("range").ok_or(AppError::NotFound)?;
+    let working_dir = &state.jobs.working_dir;
+    let body = live_set_splitter::publication::with_shared_lock(
+        &crate::model::concert_dir(working_dir, album),
+        || {
+            Ok(crate::concert_media::hls_range_playlist(
+                working_dir,
+                album,
+                &playlist,
+                range,
+            ))
+        },
+    )
+    .map_err(AppError::Internal)?
+    .ok_or(AppError::NotFound)?;
+    Ok((
+        [(
+            axum::http::header::CONTENT_TYPE,
+            "application/vnd.apple.mpegurl",
+        )],
+        body,
+    )
+        .into_response())
 }
 
 pub async fn watch(
@@ -1701,6 +2118,9 @@ pub async fn tracks(
         };
         tracks = tracks_from_events(&concert.set_list, &events);
     }
+    if let Some(album) = concert.album.as_deref() {
+        crate::concert_media::attach_posters(&state.jobs.working_dir, album, &mut tracks);
+    }
 
     TracksTemplate {
         id,
@@ -1758,7 +2178,7 @@ pub async fn listen_track(
 
     let title = concert.set_list.get(idx).ok_or(AppError::NotFound)?.clone();
     let file_exists = ConcertMediaInventory::for_concert(&state.jobs.working_dir, &concert, None)
-        .find_track_file(&title)
+        .find_track_source(idx)
         .is_some();
 
     let render_state = if file_exists {
@@ -1862,14 +2282,29 @@ pub async fn jobs_list(
 ) -> Result<impl IntoResponse, AppError> {
     let failed_filter = params.get("failed_filter").cloned().unwrap_or_default();
 
-    let (concerts, failed) = {
+    let (concerts, failed, download_attempts) = {
         let conn = state.db.lock().unwrap();
         let concerts = db::lifecycle::list_in_progress(&conn)?;
         let failed = db::failed_jobs::list_failed_jobs(&conn, 100)?;
-        (concerts, failed)
+        let mut download_attempts = HashMap::new();
+        for c in &concerts {
+            if c.download_started_at.is_some() && c.downloaded_at.is_none() {
+                download_attempts.insert(c.id, db::lifecycle::download_attempt(&conn, c.id)?);
+            }
+        }
+        (concerts, failed, download_attempts)
     };
 
-    let jobs: Vec<JobRow> = concerts
+    let queue_position =
+        |concert_id, kind| state.registry.queue_position(&JobKey { concert_id, kind });
+    let max_attempts = state.jobs.download_retry().max_attempts;
+    let download_attempt = |concert_id: i64| {
+        download_attempts
+            .get(&concert_id)
+            .filter(|&&attempt| attempt > 1)
+            .map(|attempt| format!("Attempt {attempt} of {max_attempts}"))
+    };
+    let mut jobs: Vec<JobRow> = concerts
         .iter()
         .flat_map(|c| {
             let mut rows = Vec::new();
@@ -1881,6 +2316,8 @@ pub async fn jobs_list(
                     kind_slug: "downloading",
                     kind_label: "Download",
                     started_at: c.download_started_at.clone().unwrap_or_default(),
+                    queue_position: queue_position(c.id, JobKind::Download),
+                    attempt: download_attempt(c.id),
                 });
             }
             if c.split_started_at.is_some() && c.split_at.is_none() {
@@ -1891,6 +2328,8 @@ pub async fn jobs_list(
                     kind_slug: "splitting",
                     kind_label: "Split",
                     started_at: c.split_started_at.clone().unwrap_or_default(),
+                    queue_position: queue_position(c.id, JobKind::Split),
+                    attempt: None,
                 });
             }
             if c.archive_started_at.is_some() && c.archived_at.is_none() {
@@ -1901,11 +2340,18 @@ pub async fn jobs_list(
                     kind_slug: "archiving",
                     kind_label: "Archive",
                     started_at: c.archive_started_at.clone().unwrap_or_default(),
+                    queue_position: queue_position(c.id, JobKind::Archive),
+                    attempt: None,
                 });
             }
             rows
         })
         .collect();
+    // Running jobs first, then each kind's queue in order.
+    jobs.sort_by_key(|job| {
+        job.queue_position
+            .map(|position| (job.kind_label, position))
+    });
 
     let failed_jobs: Vec<FailedJobRow> = failed
         .into_iter()
@@ -1980,21 +2426,70 @@ pub async fn jobs_count(State(state): State<AppState>) -> Result<String, AppErro
     }
 }
 
+/// `GET /jobs/progress`: Server-Sent Events carrying live Job Run progress
+/// (see `jobs::progress`). The first event is a `reset` with the progress of
+/// every queued and running job as a JSON array; each later change is a
+/// `progress` event with one job's progress. A subscriber that falls too far
+/// behind gets a fresh `reset` instead of the updates it missed.
+pub async fn jobs_progress(
+    State(state): State<AppState>,
+) -> Sse<impl Stream<Item = Result<sse::Event, axum::Error>>> {
+    Sse::new(progress_events(state.registry)).keep_alive(KeepAlive::default())
+}
+
+fn progress_events(
+    registry: std::sync::Arc<JobRegistry>,
+) -> impl Stream<Item = Result<sse::Event, axum::Error>> {
+    use crate::jobs::progress::JobProgress;
+    use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+    futures_util::stream::unfold(None::<Receiver<JobProgress>>, move |rx| {
+        let registry = registry.clone();
+        async move {
+            let mut rx = match rx {
+                Some(rx) => rx,
+                None => {
+                    let (snapshot, rx) = registry.progress().subscribe();
+                    let reset = sse::Event::default().event("reset").json_data(snapshot);
+                    return Some((reset, Some(rx)));
+                }
+            };
+            match rx.recv().await {
+                Ok(progress) => {
+                    let event = sse::Event::default().event("progress").json_data(progress);
+                    Some((event, Some(rx)))
+                }
+                Err(RecvError::Lagged(skipped)) => {
+                    tracing::debug!(skipped, "job progress subscriber lagged, resetting");
+                    let (snapshot, rx) = registry.progress().subscribe();
+                    let reset = sse::Event::default().event("reset").json_data(snapshot);
+                    Some((reset, Some(rx)))
+                }
+                Err(RecvError::Closed) => None,
+            }
+        }
+    })
+}
+
+/// The `:kind` path segment of the job actions: a dashboard slug
+/// ("downloading") or the plain kind name ("download").
+fn parse_job_kind(kind: &str) -> Result<JobKind, AppError> {
+    match kind {
+        "downloading" | "download" => Ok(JobKind::Download),
+        "splitting" | "split" => Ok(JobKind::Split),
+        "archiving" | "archive" => Ok(JobKind::Archive),
+        _ => Err(AppError::Internal(anyhow::anyhow!(
+            "unknown job kind: {}",
+            kind
+        ))),
+    }
+}
+
 pub async fn cancel_job(
     State(state): State<AppState>,
     Path((id, kind)): Path<(i64, String)>,
 ) -> Result<Response, AppError> {
-    let job_kind = match kind.as_str() {
-        "downloading" | "download" => JobKind::Download,
-        "splitting" | "split" => JobKind::Split,
-        "archiving" | "archive" => JobKind::Archive,
-        _ => {
-            return Err(AppError::Internal(anyhow::anyhow!(
-                "unknown job kind: {}",
-                kind
-            )))
-        }
-    };
+    let job_kind = parse_job_kind(&kind)?;
 
     let outcome = {
         let conn = state.db.lock().unwrap();
@@ -2015,6 +2510,35 @@ pub async fn cancel_job(
     Ok((headers, "").into_response())
 }
 
+/// Move a queued job one place up or down its kind's queue.
+pub async fn move_queued_job(
+    State(state): State<AppState>,
+    Path((id, kind, direction)): Path<(i64, String, String)>,
+) -> Result<Response, AppError> {
+    let job_kind = parse_job_kind(&kind)?;
+    let up = match direction.as_str() {
+        "up" => true,
+        "down" => false,
+        other => {
+            return Err(AppError::BadRequest(format!(
+                "unknown direction {other:?}; expected up or down"
+            )))
+        }
+    };
+    let key = JobKey {
+        concert_id: id,
+        kind: job_kind,
+    };
+    if state.registry.move_waiting(&key, up) {
+        let conn = state.db.lock().unwrap();
+        db::job_queue::reorder(&conn, job_kind, &state.registry.waiting(job_kind))?;
+    }
+
+    let mut headers = HeaderMap::new();
+    headers.insert("HX-Redirect", "/jobs".parse().unwrap());
+    Ok((headers, "").into_response())
+}
+
 pub async fn settings_page(
     State(state): State<AppState>,
     Query(params): Query<HashMap<String, String>>,
@@ -2029,6 +2553,32 @@ pub async fn settings_page(
             theme: settings.theme,
         },
         archive_location: settings.archive_location.unwrap_or_default(),
+        replay_gain: settings.replay_gain,
+        download_format: settings.download_format,
+        audio_format_choices: AudioFormat::ALL
+            .into_iter()
+            .map(|format| AudioFormatChoice {
+                ext: format.extension(),
+                label: match format {
+                    AudioFormat::M4a => "M4A (source AAC)",
+                    AudioFormat::Flac => "FLAC",
+                    AudioFormat::Opus => "Opus",
+                    AudioFormat::Mp3 => "MP3",
+                },
+                checked: settings.audio_formats.contains(&format),
+            })
+            .collect(),
+        rendition_choices: Rendition::ALL
+            .into_iter()
+            .map(|rendition| RenditionChoice {
+                name: rendition.name(),
+                checked: settings.renditions.contains(&rendition),
+            })
+            .collect(),
+        video_quality: settings.video_quality,
+        hls_packaging: settings.hls_packaging,
+        auto_sync: settings.auto_sync,
+        auto_sync_intervals: db::settings::AUTO_SYNC_INTERVALS,
         saved,
     })
 }
@@ -2046,22 +2596,155 @@ pub async fn settings_save(
         .map(|s| db::settings::Theme::parse(s))
         .transpose()
         .map_err(|_| AppError::Internal(anyhow::anyhow!("invalid theme value")))?;
+    let replay_gain = form
+        .get("replay_gain")
+        .map(|s| db::settings::ReplayGainMode::parse(s))
+        .transpose()
+        .map_err(|_| AppError::Internal(anyhow::anyhow!("invalid replay gain value")))?;
+    let download_format = form
+        .get("download_format")
+        .map(|s| db::settings::DownloadFormat::parse(s))
+        .transpose()
+        .map_err(|_| AppError::Internal(anyhow::anyhow!("invalid download format value")))?;
+    // A checkbox is only posted when ticked, so the checkbox settings are
+    // saved only when the form's `checkboxes` marker says they were on it.
+    let ticked = |name: &str| form.get(name).is_some_and(|v| v == "on");
+    let checkboxes = ticked("checkboxes");
+    // Nothing ticked keeps splits on m4a.
+    let audio_formats = checkboxes.then(|| {
+        let formats: Vec<AudioFormat> = AudioFormat::ALL
+            .into_iter()
+            .filter(|format| ticked(&format!("audio_format_{}", format.extension())))
+            .collect();
+        if formats.is_empty() {
+            vec![AudioFormat::M4a]
+        } else {
+            formats
+        }
+    });
+    // Nothing ticked writes no renditions.
+    let renditions = checkboxes.then(|| {
+        Rendition::ALL
+            .into_iter()
+            .filter(|rendition| ticked(&format!("rendition_{}", rendition.name())))
+            .collect::<Vec<_>>()
+    });
+    let hls_packaging = checkboxes.then(|| ticked("hls_packaging"));
+    let auto_download = checkboxes.then(|| ticked("auto_download"));
+    let video_quality = form
+        .get("video_quality")
+        .map(|s| db::settings::VideoQuality::parse(s))
+        .transpose()
+        .map_err(|_| AppError::Internal(anyhow::anyhow!("invalid video quality value")))?;
+    let auto_sync_hours = form
+        .get("auto_sync_hours")
+        .map(|s| {
+            s.parse::<u32>()
+                .ok()
+                .filter(|h| db::settings::AUTO_SYNC_INTERVALS.contains(h))
+                .ok_or_else(|| AppError::Internal(anyhow::anyhow!("invalid auto sync interval")))
+        })
+        .transpose()?;
     {
         let conn = state.db.lock().unwrap();
         db::settings::update_archive_location(&conn, location)?;
         if let Some(t) = theme {
             db::settings::update_theme(&conn, t)?;
         }
+        if let Some(mode) = replay_gain {
+            db::settings::update_replay_gain(&conn, mode)?;
+        }
+        if let Some(format) = download_format {
+            db::settings::update_download_format(&conn, format)?;
+        }
+        if let Some(formats) = &audio_formats {
+            db::settings::update_audio_formats(&conn, formats)?;
+        }
+        if let Some(renditions) = &renditions {
+            db::settings::update_renditions(&conn, renditions)?;
+        }
+        if let Some(quality) = video_quality {
+            db::settings::update_video_quality(&conn, quality)?;
+        }
+        if let Some(enabled) = hls_packaging {
+            db::settings::update_hls_packaging(&conn, enabled)?;
+        }
+        if let Some(hours) = auto_sync_hours {
+            db::settings::update_auto_sync_hours(&conn, hours)?;
+        }
+        if let Some(download) = auto_download {
+            db::settings::update_auto_download(&conn, download)?;
+        }
     }
     tracing::info!(
-        "settings updated: archive_location={:?} theme={:?}",
+        "settings updated: archive_location={:?} theme={:?} replay_gain={:?} download_format={:?} audio_formats={:?} renditions={:?} video_quality={:?} hls_packaging={:?} auto_sync_hours={:?} auto_download={:?}",
         location,
-        theme.map(|t| t.as_str())
+        theme.map(|t| t.as_str()),
+        replay_gain.map(|m| m.as_str()),
+        download_format.map(|f| f.as_str()),
+        audio_formats
+            .as_deref()
+            .map(db::settings::audio_formats_str),
+        renditions.as_deref().map(db::settings::renditions_str),
+        video_quality.map(|q| q.as_str()),
+        hls_packaging,
+        auto_sync_hours,
+        auto_download
     );
 
     Ok(axum::response::Redirect::to("/settings?saved=1").into_response())
 }
 
+pub async fn following_page(State(state): State<AppState>) -> Result<impl IntoResponse, AppError> {
+    let artists = {
+        let conn = state.db.lock().unwrap();
+        db::followed_artists::list_followed(&conn)?
+    };
+    Ok(FollowingTemplate {
+        chrome: Chrome::from_state(&state),
+        artists,
+    })
+}
+
+/// Follow the form's `name`, then return to `next` (the concert-detail
+/// Follow button) or the following page.
+pub async fn follow_artist(
+    State(state): State<AppState>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<Response, AppError> {
+    let name = form.get("name").map(|s| s.trim()).unwrap_or("");
+    if db::followed_artists::normalize_artist(name).is_empty() {
+        return Err(AppError::BadRequest(
+            "artist name must contain letters or digits".to_string(),
+        ));
+    }
+    let added = {
+        let conn = state.db.lock().unwrap();
+        db::followed_artists::follow(&conn, name)?
+    };
+    tracing::info!("follow artist {:?}: added={}", name, added);
+    let next = form
+        .get("next")
+        .map(|s| s.as_str())
+        .filter(|next| next.starts_with('/') && !next.starts_with("//"))
+        .unwrap_or("/following");
+    Ok(axum::response::Redirect::to(next).into_response())
+}
+
+pub async fn unfollow_artist(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+) -> Result<Response, AppError> {
+    let removed = {
+        let conn = state.db.lock().unwrap();
+        db::followed_artists::unfollow(&conn, id)?
+    };
+    if !removed {
+        return Err(AppError::NotFound);
+    }
+    Ok(axum::response::Redirect::to("/following").into_response())
+}
+
 pub async fn archive(
     State(state): State<AppState>,
     Path(id): Path<i64>,
@@ -2392,6 +3075,108 @@ pub async fn htmx_js() -> impl IntoResponse {
     )
 }
 
+// ── Search ───────────────────────────────────────────────────────────────────
+
+#[derive(serde::Deserialize)]
+pub struct SearchParams {
+    #[serde(default)]
+    q: String,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+impl SearchParams {
+    fn limit(&self) -> usize {
+        self.limit.unwrap_or(db::search::DEFAULT_SEARCH_LIMIT)
+    }
+}
+
+/// One ranked search match. `snippet` is plain text; `highlights` lists the
+/// matched substrings in it, in order, so a client can mark them up without
+/// the API shipping HTML.
+#[derive(serde::Serialize, ToSchema)]
+pub struct SearchHitJson {
+    id: i64,
+    title: String,
+    artist: Option<String>,
+    concert_date: Option<String>,
+    snippet: String,
+    highlights: Vec<String>,
+}
+
+impl From<db::search::SearchHit> for SearchHitJson {
+    fn from(hit: db::search::SearchHit) -> Self {
+        let snippet = hit.snippet_text();
+        let highlights = hit
+            .snippet
+            .into_iter()
+            .filter(|s| s.highlighted)
+            .map(|s| s.text)
+            .collect();
+        SearchHitJson {
+            id: hit.concert.id,
+            title: hit.concert.title,
+            artist: hit.concert.artist,
+            concert_date: hit.concert.concert_date,
+            snippet,
+            highlights,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "search_results.html")]
+struct SearchResultsTemplate {
+    query: String,
+    hits: Vec<db::search::SearchHit>,
+}
+
+#[utoipa::path(
+    get,
+    path = "/search",
+    tag = "search",
+    params(
+        ("q" = String, Query, description = "Words to search for; each matches as a prefix and all must match"),
+        ("limit" = Option<usize>, Query, description = "Maximum hits (default 50, capped at 200)"),
+    ),
+    responses(
+        (status = 200, description = "Concerts ranked by relevance", body = Vec<SearchHitJson>),
+        (status = 422, description = "Query has no searchable words"),
+        (status = 500, description = "Internal error", content_type = "text/plain"),
+    )
+)]
+pub async fn search(
+    State(state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Json<Vec<SearchHitJson>>, AppError> {
+    let query = db::search::SearchQuery::parse(&params.q).ok_or_else(|| {
+        AppError::BadRequest("search query must contain at least one word".into())
+    })?;
+    let conn = state.db.lock().unwrap();
+    let hits = db::search::search_concerts(&conn, &query, params.limit())?;
+    Ok(Json(hits.into_iter().map(Into::into).collect()))
+}
+
+/// htmx fragment for the list page's search box. An empty box renders nothing
+/// so clearing it collapses the results back to the plain listing.
+pub async fn search_results(
+    State(state): State<AppState>,
+    Query(params): Query<SearchParams>,
+) -> Result<Response, AppError> {
+    let Some(query) = db::search::SearchQuery::parse(&params.q) else {
+        return Ok(String::new().into_response());
+    };
+    let hits = {
+        let conn = state.db.lock().unwrap();
+        db::search::search_concerts(&conn, &query, params.limit())?
+    };
+    Ok(SearchResultsTemplate {
+        query: params.q.trim().to_string(),
+        hits,
+    }
+    .into_response())
+}
+
 // ── Playlists (JSON API) ─────────────────────────────────────────────────────
 //
 // All playlist endpoints live under `/api/...` and speak JSON, distinct from the
@@ -2823,6 +3608,123 @@ pub async fn track_playlists(
     Ok(Json(out.into_iter().map(Into::into).collect()))
 }
 
+#[derive(serde::Deserialize)]
+pub struct PlaylistExportParams {
+    #[serde(default = "default_export_format")]
+    format: crate::playlist_file::PlaylistFormat,
+    #[serde(default)]
+    paths: crate::playlist_file::PathStyle,
+}
+
+fn default_export_format() -> crate::playlist_file::PlaylistFormat {
+    crate::playlist_file::PlaylistFormat::M3u8
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/playlists/{id}/export",
+    tag = "playlists",
+    params(
+        ("id" = i64, Path, description = "Playlist ID"),
+        ("format" = Option<String>, Query, description = "`m3u8` (default) or `xspf`"),
+        ("paths" = Option<String>, Query, description = "`relative` (default; relative to the concerts directory) or `absolute`"),
+    ),
+    responses(
+        (status = 200, description = "Playlist file download; tracks with no split file are omitted and counted in the X-Skipped-Tracks header", body = String, content_type = "audio/x-mpegurl"),
+        (status = 404, description = "Playlist not found"),
+        (status = 500, description = "Internal error", content_type = "text/plain"),
+    )
+)]
+pub async fn export_playlist(
+    State(state): State<AppState>,
+    Path(id): Path<i64>,
+    Query(params): Query<PlaylistExportParams>,
+) -> Result<Response, AppError> {
+    let exported = {
+        let conn = state.db.lock().unwrap();
+        crate::playlist_file::export_playlist(
+            &conn,
+            &state.jobs.working_dir,
+            id,
+            params.format,
+            params.paths,
+        )
+        .map_err(AppError::from_playlist)?
+    };
+    // Header values must be visible ASCII; the name is only a download hint.
+    let stem: String = crate::model::sanitize_filename(&exported.name)
+        .chars()
+        .map(|c| {
+            if c.is_ascii_graphic() || c == ' ' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    let disposition = format!(
+        "attachment; filename=\"{stem}.{}\"",
+        params.format.extension()
+    );
+    let mut headers = HeaderMap::new();
+    headers.insert(
+        axum::http::header::CONTENT_TYPE,
+        HeaderValue::from_static(params.format.content_type()),
+    );
+    headers.insert(
+        axum::http::header::CONTENT_DISPOSITION,
+        HeaderValue::from_str(&disposition).map_err(anyhow::Error::from)?,
+    );
+    headers.insert("x-skipped-tracks", HeaderValue::from(exported.skipped));
+    Ok((headers, exported.body).into_response())
+}
+
+#[derive(serde::Deserialize)]
+pub struct PlaylistImportParams {
+    #[serde(default)]
+    name: Option<String>,
+}
+
+#[derive(serde::Serialize, ToSchema)]
+pub struct PlaylistImportJson {
+    id: i64,
+    added: usize,
+    /// Entries, as written in the file, that matched no split track.
+    unresolved: Vec<String>,
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/playlists/import",
+    tag = "playlists",
+    params(("name" = Option<String>, Query, description = "Playlist name; defaults to the file's #PLAYLIST: line")),
+    request_body(content = String, description = "M3U/M3U8 file contents", content_type = "audio/x-mpegurl"),
+    responses(
+        (status = 200, description = "Playlist created from the resolvable entries", body = PlaylistImportJson),
+        (status = 422, description = "No name, or no entry matched a split track"),
+        (status = 500, description = "Internal error", content_type = "text/plain"),
+    )
+)]
+pub async fn import_playlist(
+    State(state): State<AppState>,
+    Query(params): Query<PlaylistImportParams>,
+    body: String,
+) -> Result<Json<PlaylistImportJson>, AppError> {
+    let conn = state.db.lock().unwrap();
+    let report = crate::playlist_file::import_m3u(
+        &conn,
+        &state.jobs.working_dir,
+        params.name.as_deref(),
+        &body,
+    )
+    .map_err(AppError::from_playlist)?;
+    Ok(Json(PlaylistImportJson {
+        id: report.playlist_id,
+        added: report.added,
+        unresolved: report.unresolved,
+    }))
+}
+
 #[utoipa::path(
     get,
     path = "/api/concerts/{id}/playlists",
@@ -2892,14 +3794,17 @@ struct PlaylistDetailTemplate {
     items: Vec<PlaylistItemRow>,
 }
 
-/// One raw playlist item rendered for the detail page. `href`/`sublabel` are `""`
-/// when absent (a track item has no link; a nested playlist has no sublabel).
+/// One raw playlist item rendered for the detail page. `href`/`sublabel`/`image`
+/// are `""` when absent (a track item has no link; a nested playlist has no
+/// sublabel or image). `image` is a track's poster frame or a concert's
+/// listing thumbnail.
 struct PlaylistItemRow {
     item_id: i64,
     kind: &'static str,
     label: String,
     sublabel: String,
     href: String,
+    image: String,
     available: bool,
 }
 
@@ -2942,7 +3847,7 @@ pub async fn playlist_detail_page(
         let summary = crate::playlist::summarize_playlist(&conn, id)?;
         let mut items = Vec::with_capacity(raw_items.len());
         for it in raw_items {
-            items.push(build_item_row(&conn, it)?);
+            items.push(build_item_row(&conn, &state.jobs.working_dir, it)?);
         }
         (
             playlist.name,
@@ -2971,6 +3876,7 @@ pub async fn playlist_detail_page(
 /// labelled placeholder rather than erroring the whole page.
 fn build_item_row(
     conn: &Connection,
+    working_dir: &std::path::Path,
     item: crate::model::PlaylistItem,
 ) -> Result<PlaylistItemRow, AppError> {
     use crate::model::PlaylistItemKind::*;
@@ -2979,24 +3885,33 @@ fn build_item_row(
             concert_id,
             track_index,
         } => match db::concerts::get_concert_opt(conn, concert_id)? {
-            Some(c) => PlaylistItemRow {
-                item_id: item.id,
-                kind: "track",
-                label: c
-                    .set_list
-                    .get(track_index)
-                    .cloned()
-                    .unwrap_or_else(|| format!("Track {}", track_index + 1)),
-                sublabel: c.title,
-                href: String::new(),
-                available: c.tracks_present.get(track_index).copied().unwrap_or(false),
-            },
+            Some(c) => {
+                let title = c.set_list.get(track_index).cloned();
+                let image = c
+                    .album
+                    .as_deref()
+                    .zip(title.as_deref())
+                    .and_then(|(album, title)| {
+                        crate::concert_media::poster_url(working_dir, album, title)
+                    })
+                    .unwrap_or_default();
+                PlaylistItemRow {
+                    item_id: item.id,
+                    kind: "track",
+                    label: title.unwrap_or_else(|| format!("Track {}", track_index + 1)),
+                    sublabel: c.title,
+                    href: String::new(),
+                    image,
+                    available: c.tracks_present.get(track_index).copied().unwrap_or(false),
+                }
+            }
             None => PlaylistItemRow {
                 item_id: item.id,
                 kind: "track",
                 label: format!("Track {}", track_index + 1),
                 sublabel: "(missing concert)".to_string(),
                 href: String::new(),
+                image: String::new(),
                 available: false,
             },
         },
@@ -3014,6 +3929,10 @@ fn build_item_row(
                     .and_then(|c| c.artist.clone())
                     .unwrap_or_default(),
                 href: format!("/concerts/{concert_id}"),
+                image: concert
+                    .as_ref()
+                    .and_then(|c| c.thumbnail_url_from_db())
+                    .unwrap_or_default(),
                 available: true,
             }
         }
@@ -3025,6 +3944,7 @@ fn build_item_row(
                 .unwrap_or_else(|| "(missing playlist)".to_string()),
             sublabel: String::new(),
             href: format!("/playlists/{child_playlist_id}"),
+            image: String::new(),
             available: true,
         },
     };
@@ -3225,6 +4145,7 @@ mod tests {
             available,
             is_video: false,
             liked: false,
+            poster_url: None,
         }]
     }
 
@@ -3295,6 +4216,31 @@ mod tests {
         assert!(sidebar.contains("/tracks/0/like"), "{sidebar}");
     }
 
+    #[test]
+    fn tracks_template_shows_poster_frames_when_present() {
+        let mut tracks = one_track(true);
+        tracks.push(TrackInfo {
+            index: 1,
+            title: "Song B".to_string(),
+            poster_url: Some("/concert-files/A/Song B.poster.jpg".to_string()),
+            ..tracks[0].clone()
+        });
+        let html = TracksTemplate {
+            id: 1,
+            tracks,
+            show_delete: false,
+            tracks_busy: false,
+            sidebar: false,
+        }
+        .render()
+        .unwrap();
+        assert_eq!(html.matches("class=\"track-poster\"").count(), 1, "{html}");
+        assert!(
+            html.contains("src=\"/concert-files/A/Song B.poster.jpg\""),
+            "{html}"
+        );
+    }
+
     #[test]
     fn like_button_renders_star_state_and_self_swap() {
         let liked = LikeButtonTemplate {
@@ -3490,4 +4436,23 @@ mod tests {
             "/"
         );
     }
+
+    #[test]
+    fn search_results_highlight_matches_and_escape_stored_markup() {
+        let conn = db::connection::open_in_memory().unwrap();
+        let id = seed_listing(&conn, "https://example.org/search");
+        db::concerts::set_notes(&conn, id, "<b>pedal</b> steel solo").unwrap();
+        let query = db::search::SearchQuery::parse("pedal").unwrap();
+        let hits = db::search::search_concerts(&conn, &query, 10).unwrap();
+
+        let html = SearchResultsTemplate {
+            query: "pedal".to_string(),
+            hits,
+        }
+        .render()
+        .unwrap();
+        assert!(html.contains("<mark>pedal</mark>"), "html: {html}");
+        assert!(html.contains("&lt;b&gt;"), "html: {html}");
+        assert!(html.contains(&format!("/concerts/{id}")), "html: {html}");
+    }
 }
@@ -147,6 +147,8 @@ pub(crate) fn api_router() -> (Router<AppState>, utoipa::openapi::OpenApi) {
         .routes(routes!(handlers::prepare_concert))
         .routes(routes!(handlers::prepare_status))
         .route("/concerts/:id/delete-split", post(handlers::delete_split))
+        .route("/concerts/:id/chapters", post(handlers::write_chapters))
+        .route("/concerts/:id/split-report", get(handlers::split_report))
         .routes(routes!(
             handlers::get_split_timestamps,
             handlers::set_split_timestamps
@@ -164,6 +166,7 @@ pub(crate) fn api_router() -> (Router<AppState>, utoipa::openapi::OpenApi) {
         .route("/concerts/:id/listen", post(handlers::listen))
         .route("/concerts/:id/watch", post(handlers::watch))
         .routes(routes!(handlers::media_info))
+        .route("/concerts/:id/hls/:playlist", get(handlers::hls_playlist))
         .route("/concerts/:id/tracks", get(handlers::tracks))
         .routes(routes!(handlers::track_details))
         .route(
@@ -187,12 +190,22 @@ pub(crate) fn api_router() -> (Router<AppState>, utoipa::openapi::OpenApi) {
         .route("/concerts/:id/status", get(handlers::status_row))
         .route("/jobs", get(handlers::jobs_list))
         .route("/jobs/count", get(handlers::jobs_count))
+        .route("/jobs/progress", get(handlers::jobs_progress))
         .route("/jobs/:id/log", get(handlers::job_log))
         .route("/jobs/:id/cancel/:kind", post(handlers::cancel_job))
+        .route(
+            "/jobs/:id/move/:kind/:direction",
+            post(handlers::move_queued_job),
+        )
         .route(
             "/settings",
             get(handlers::settings_page).post(handlers::settings_save),
         )
+        .route(
+            "/following",
+            get(handlers::following_page).post(handlers::follow_artist),
+        )
+        .route("/following/:id/delete", post(handlers::unfollow_artist))
         // Playlists HTML pages (Phase 2a). Distinct from the /api/playlists JSON
         // surface below, which these pages hydrate from.
         .route("/playlists", get(handlers::playlists_page))
@@ -210,8 +223,12 @@ pub(crate) fn api_router() -> (Router<AppState>, utoipa::openapi::OpenApi) {
         .routes(routes!(handlers::reorder_playlist_items))
         .routes(routes!(handlers::remove_playlist_item))
         .routes(routes!(handlers::playlist_nested_in))
+        .routes(routes!(handlers::export_playlist))
+        .routes(routes!(handlers::import_playlist))
         .routes(routes!(handlers::concert_playlists))
         .routes(routes!(handlers::track_playlists))
+        .routes(routes!(handlers::search))
+        .route("/search/results", get(handlers::search_results))
         .route("/sync/:year/:month", post(handlers::sync_month_handler))
         .split_for_parts()
 }
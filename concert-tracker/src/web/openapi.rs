@@ -1,6 +1,6 @@
 //! OpenAPI 3.1 document for the JSON API surface (the `/api/playlists*` family
 //! plus the JSON helpers that sit alongside the htmx HTML pages: playback,
-//! media-info, prepare-status, and split-timestamps). HTML/htmx routes are
+//! media-info, prepare-status, split-timestamps, and search). HTML/htmx routes are
 //! intentionally not represented here — this documents the machine-readable
 //! contract, not the whole app.
 //!
@@ -26,9 +26,9 @@ use crate::web::handlers;
     info(
         title = "concert-tracker JSON API",
         version = "0.1.0",
-        description = "JSON endpoints for playlists, playback, and split-timestamp \
-                        editing. The rest of the app is htmx-rendered HTML and is \
-                        not represented here."
+        description = "JSON endpoints for playlists, playback, split-timestamp \
+                        editing, and search. The rest of the app is htmx-rendered \
+                        HTML and is not represented here."
     ),
     components(schemas(
         handlers::PrepareStatus,
@@ -53,14 +53,20 @@ use crate::web::handlers;
         handlers::ReorderReq,
         handlers::CreatedPlaylistJson,
         handlers::CreatedItemJson,
+        handlers::PlaylistImportJson,
         TimestampPayload,
         TimestampPayloadSong,
         concert_types::SongTimestamp,
+        concert_types::BoundaryProvenance,
+        concert_types::BoundarySource,
+        concert_types::BoundaryRefinement,
+        handlers::SearchHitJson,
     )),
     tags(
         (name = "playlists", description = "Playlist CRUD and membership"),
         (name = "playback", description = "Concert/track media and playback info"),
         (name = "splitting", description = "Split timestamps and split-job status"),
+        (name = "search", description = "Full-text concert search"),
     ),
 )]
 pub struct ApiDoc;
@@ -92,8 +98,11 @@ mod tests {
         "/api/playlists/{id}/items/{item_id}",
         "/api/playlists/{id}/items/reorder",
         "/api/playlists/{id}/nested-in",
+        "/api/playlists/{id}/export",
+        "/api/playlists/import",
         "/api/concerts/{id}/playlists",
         "/api/concerts/{id}/tracks/{idx}/playlists",
+        "/search",
     ];
 
     #[test]
@@ -78,5 +78,5 @@ Received (typeof ${typeof s}): ${JSON.stringify(s)}`}))))}),RequestGetMessageSch
 
   export default defineConfig({ plugins: [foldkit()] })
 
-Starting without HMR support.`),m.succeed(void 0)))):m.succeed(void 0)},oH=e=>{FS.runMain(nH(m.flatMap(rH(e.runtimeId),e.start)))},GCe=(e,t)=>{let n={};return Z.isNotUndefined(e)&&(Object.entries(e.inbound??{}).forEach(([r,o])=>{n[r]={send:s=>t.sendInbound(r,o,s)}}),Object.entries(e.outbound??{}).forEach(([r,o])=>{n[r]={subscribe:s=>t.addListener(o,s)}})),n},sH=e=>{let t=Xz.get(e);if(Z.isUndefined(t))throw new Error("[foldkit] embed expects a program created by makeApplication or makeElement.");let n=t;if(n.isEmbedActive)throw new Error("[foldkit] This program is already embedded. Dispose the existing handle first, or create a separate program: each program owns one container.");n.isEmbedActive=!0;let r=FCe(),o=ke(h.match(n.maybeActiveFiber,{onNone:()=>m.void,onSome:u=>m.asVoid(Xr.await(u))}),m.andThen(rH(e.runtimeId)),m.flatMap(u=>n.startWith(h.some(r),u))),s=m.runFork(nH(o));n.maybeActiveFiber=h.some(s);let i=!1,a=()=>{i||(i=!0,r.dispose(),n.isEmbedActive=!1,m.runFork(Xr.interrupt(s)))};return{ports:GCe(e.ports,r),dispose:a}};var VS={};We(VS,{aggregate:()=>tz,animationFrame:()=>iH,lift:()=>nz,make:()=>ez,persistent:()=>PS});var ZCe=e=>B.callback(t=>m.acquireRelease(m.sync(()=>{let n={frameId:0,lastTime:performance.now()},r=o=>{let s=o-n.lastTime;n.lastTime=o,on.offerUnsafe(t,e(s)),n.frameId=requestAnimationFrame(r)};return n.frameId=requestAnimationFrame(r),n}),n=>m.sync(()=>cancelAnimationFrame(n.frameId))).pipe(m.flatMap(()=>m.never))),iH=e=>({dependenciesSchema:p.Struct({isActive:p.Boolean}),modelToDependencies:t=>({isActive:e.isActive(t)}),dependenciesToStream:({isActive:t})=>B.when(ZCe(e.toMessage),m.sync(()=>t))});var cO="sidebarWidth";var aH='a, button, input, select, textarea, label, [role="button"], [onclick]';function GS(e){return Math.max(240,Math.min(600,Math.round(e)))}function ch(e,t,n,r,o=null,s=null){return{concertId:e,trackIdx:t,title:n,liked:!!r,playlistName:o||null,groupId:s||null}}function KS(e,t){return e.some(n=>n.concertId===t.concertId&&n.trackIdx===t.trackIdx)?{queue:[...e],added:!1}:{queue:[...e,t],added:!0}}function uO(e,t){let n=[...e];return n.splice(t,1),n}function cH(e,t){return e.filter(n=>n.groupId!==t)}function uH(e){let t=[],n=[],r,o=new Set;for(let s=e.length-1;s>=0;s--){let i=e[s];i.groupId!==null&&i.groupId!==r?(o.has(i.groupId)&&n.push(i.groupId),o.add(i.groupId),r=i.groupId,t.push({kind:"group-header",groupId:i.groupId,name:i.playlistName||"Playlist"})):i.groupId===null&&(r=null),t.push({kind:"song",pos:s,entry:i,nested:i.groupId!==null})}return{rows:t,nonContiguousGroups:n}}function JS(e,t){return e.concert?e.concert.pos+1<e.concert.items.length:t>0||e.hasNext}function ZS(e){return e.concert?e.concert.pos>0:e.hasPrev}function lH(e,t){return{hasPrev:t>0,hasNext:t+1<e.length,item:e[t]??null}}function fH(e,t){let n=e+1;return n>=t?null:n}function pH(e,t,n){if(!t)return n;let r=e.findIndex(o=>o.url===t);return r>=0?r:n}function lO(e){let t=Math.floor(e/60),n=Math.floor(e%60);return t+":"+(n<10?"0":"")+n}function dH(e,t){return!(!t||!e||!(e instanceof Node)||t.contains(e)||e instanceof Element&&e.closest&&e.closest(aH))}function mH(e){return(e.code==="Space"||e.key===" "||e.key==="Spacebar")&&!e.ctrlKey&&!e.metaKey&&!e.altKey&&!e.shiftKey}function hH(e){return(e.code==="Escape"||e.key==="Escape"||e.key==="Esc")&&!e.ctrlKey&&!e.metaKey&&!e.altKey&&!e.shiftKey}function gH(e,t){return!!(e.button!==0||e.metaKey||e.ctrlKey||e.shiftKey||e.altKey||e.defaultPrevented||t.target!==""&&t.target!=="_self"||t.hasAttribute("download"))}function QS(e){if(!(e instanceof HTMLElement))return!1;if(e.isContentEditable||e.matches("input, textarea, select"))return!0;let t=e.closest("[contenteditable]");return!!(t&&t.isContentEditable)}function QCe(e){return!(e instanceof Element)||QS(e)?!1:!!e.closest("#player-bar, #player-video-panel")}function xH(e){return!(e instanceof Element)||QCe(e)?!1:QS(e)?!0:!!e.closest(aH)}var yH={concertId:null,trackIdx:null};function bH(e){yH=e}function SH(){return yH}var EH=$("PlayAlbum",{concertId:p.Number}),kH=$("PlayTrack",{concertId:p.Number,trackIdx:p.Number}),wH=$("PlayTracks",{concertId:p.Number}),TH=$("StartAlbum",{concertId:p.Number,recordListen:p.Boolean}),IH=$("StartTrack",{concertId:p.Number,trackIdx:p.Number}),vH=$("TogglePause"),AH=$("Seek",{seconds:p.Number}),CH=$("SkipToNext"),OH=$("SkipToPrev"),MH=$("Watch"),PH=$("OpenExternal"),_H=$("WatchTrackDirect",{concertId:p.Number,trackIdx:p.Number}),RH=$("ToggleLike"),NH=$("DeleteTrack"),FH=$("OpenSidebar"),DH=$("CloseSidebar"),LH=$("ToggleSidebar"),UH=$("SidebarDeleteTrack",{concertId:p.Number,trackIdx:p.Number}),$H=$("PlayQueueEntryNow",{pos:p.Number}),BH=$("Dequeue",{pos:p.Number}),jH=$("RemoveGroup",{groupId:p.Number}),qH=$("Enqueue",{concertId:p.Number,trackIdx:p.Number,title:p.String,liked:p.Boolean}),zH=$("PlayAlbumAt",{concertId:p.Number,seconds:p.Number}),HH=$("PlayPlaylist",{playlistId:p.Number}),WH=$("AddToPlaylist"),VH=$("StopPlayback"),GH=$("PlayConcert",{concertId:p.Number}),KH=$("PlayConcertFrom",{concertId:p.Number,pos:p.Number}),JH=$("SidebarDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number}),ZH=$("SidebarLikeTrack",{concertId:p.Number,trackIdx:p.Number}),QH=$("SidebarAddToPlaylist",{concertId:p.Number,trackIdx:p.Number,label:p.String}),fO=p.Union([EH,kH,wH,TH,IH,vH,AH,CH,OH,MH,PH,_H,RH,NH,FH,DH,LH,UH,$H,BH,jH,qH,zH,HH,WH,VH,GH,KH,JH,ZH,QH]),se={PlayAlbum:EH,PlayTrack:kH,PlayTracks:wH,StartAlbum:TH,StartTrack:IH,TogglePause:vH,Seek:AH,SkipToNext:CH,SkipToPrev:OH,Watch:MH,OpenExternal:PH,WatchTrackDirect:_H,ToggleLike:RH,DeleteTrack:NH,OpenSidebar:FH,CloseSidebar:DH,ToggleSidebar:LH,SidebarDeleteTrack:UH,PlayQueueEntryNow:$H,Dequeue:BH,RemoveGroup:jH,Enqueue:qH,PlayAlbumAt:zH,PlayPlaylist:HH,AddToPlaylist:WH,StopPlayback:VH,PlayConcert:GH,PlayConcertFrom:KH,SidebarDeleteInterlude:JH,SidebarLikeTrack:ZH,SidebarAddToPlaylist:QH},YS={inbound:{command:Bf.inbound(fO)}};function pO(e){return e.mode==="source"}var XS=class extends Error{constructor(t,n){super(n??`HTTP ${t}`),this.name="ApiError",this.status=t}};async function dO(e){return await e.json()}async function Pu(e,t){let n=await fetch(e,t);if(!n.ok)throw new XS(n.status);return dO(n)}async function YCe(e,t){let n=await fetch(e,t);return n.ok?dO(n):null}async function XCe(e,t){let n=await fetch(e,t);if(n.status===404)return null;if(!n.ok)throw new XS(n.status);return dO(n)}async function uh(e,t,n="POST"){let r={method:n};return t!==void 0&&(r.headers={"Content-Type":"application/json"},r.body=JSON.stringify(t)),fetch(e,r)}async function YH(e){return Pu(`/api/playlists/${e}`)}async function XH(e){return Pu(`/concerts/${e}/media-info`)}async function lh(e,t){return YCe(`/concerts/${e}/tracks/${t}/media-info`)}async function eW(e,t,n){return Pu(`/concerts/${e}/tracks/${t}/next-media-info`,{signal:n??null})}async function tW(e,t,n){return XCe(`/concerts/${e}/tracks/${t}/next-media-info`,{signal:n??null})}async function nW(e,t,n){return Pu(`/concerts/${e}/tracks/${t}/prev-media-info`,{signal:n??null})}async function mO(e){return Pu(`/concerts/${e}/concert-playback`)}async function rW(e){return uh(`/concerts/${e}/prepare`,void 0,"POST")}async function oW(e){return Pu(`/concerts/${e}/prepare-status`)}async function sW(e,t){return uh(`/concerts/${e}/tracks/${t}/like`,void 0,"POST")}async function iW(e,t){return uh(`/concerts/${e}/tracks/${t}/delete`,void 0,"POST")}async function aW(e,t){return uh(`/concerts/${e}/interludes/${t}/delete`,void 0,"POST")}async function cW(e){return Pu(`/concerts/${e}/track-details`)}async function hO(e){return uh(e,void 0,"POST")}function ao(e){return document.getElementById(e)}function ei(e,t){let n=ao(e);if(n===null)return null;if(!(n instanceof t))throw new Error(`expected #${e} to be a ${t.name}`);return n}var gO=p.Struct({index:p.Number,title:p.String,available:p.Boolean,is_video:p.Boolean,liked:p.Boolean}),eOe=p.Struct({tracksBusy:p.Boolean,tracks:p.mutable(p.Array(gO))}),xO=p.Struct({artist:p.String,has_next:p.Boolean,has_prev:p.Boolean,is_video:p.Boolean,liked:p.Boolean,playable:p.Boolean,title:p.String,track_index:p.optionalKey(p.NullOr(p.Number)),url:p.String}),zf=p.Struct({download:p.String,split:p.String,split_queued:p.Boolean,tracks_present:p.mutable(p.Array(p.Boolean))}),eE=p.Struct({artist:p.String,interlude_index:p.optionalKey(p.NullOr(p.Number)),is_video:p.Boolean,kind:p.String,liked:p.Boolean,title:p.String,track_index:p.optionalKey(p.NullOr(p.Number)),url:p.String}),fh=p.Struct({concertId:p.Number,trackIdx:p.Number,title:p.String,liked:p.Boolean,playlistName:p.NullOr(p.String),groupId:p.NullOr(p.Number)}),tOe=p.Struct({id:p.Number,items:p.mutable(p.Array(eE)),pos:p.Number}),nOe=p.Struct({concertId:p.NullOr(p.Number),trackIdx:p.NullOr(p.Number),title:p.String,artist:p.String,isVideo:p.Boolean,watchUrl:p.NullOr(p.String),hasNext:p.Boolean,hasPrev:p.Boolean,liked:p.Boolean,ended:p.Boolean,concert:p.OptionFromNullOr(tOe),playlistLabel:p.NullOr(p.String)}),yO={concertId:null,trackIdx:null,title:"",artist:"",isVideo:!1,watchUrl:null,hasNext:!1,hasPrev:!1,liked:!1,ended:!1,concert:h.none(),playlistLabel:null},uW=$("Track",{concertId:p.Number,trackIdx:p.Number}),Hi=uW,tE={Track:uW},bO=(e,t)=>e.concertId===t.concertId&&e.trackIdx===t.trackIdx,lW=$("Track",{concertId:p.Number,trackIdx:p.Number}),fW=$("Album",{concertId:p.Number}),pW=$("ConcertItem",{concertId:p.Number,trackIdx:p.NullOr(p.Number),isInterlude:p.Boolean}),ph=p.Union([lW,fW,pW]),co={Track:lW,Album:fW,ConcertItem:pW},dh=p.Struct({recordListen:p.Boolean,playlistName:p.NullOr(p.String),openVideoPanel:p.Boolean}),Wi={recordListen:!0,playlistName:null,openVideoPanel:!1},_u=p.Literals(["queue-only","next-or-none","next-or-stop","next-or-collapse"]),dW=$("Idle"),mW=$("Busy",{message:p.String}),hW=$("Error",{message:p.String}),rOe=p.Union([dW,mW,hW]),Ga={Idle:dW,Busy:mW,Error:hW},gW=p.Struct({playback:nOe,queue:p.Array(fh),nextGroupId:p.Number,pending:p.Option(Hi),sidebar:p.Struct({open:p.Boolean,tracks:p.Option(eOe),loadGen:p.Number}),video:p.Struct({open:p.Boolean}),isPlaying:p.Boolean,pendingSeek:p.Option(p.Number),status:rOe,audioTime:p.Struct({currentTime:p.Number,duration:p.Number}),audioLoadGen:p.Number}),nE={currentTime:0,duration:0},xW={playback:yO,queue:[],nextGroupId:1,pending:h.none(),sidebar:{open:!1,tracks:h.none(),loadGen:0},video:{open:!1},isPlaying:!1,pendingSeek:h.none(),status:Ga.Idle(),audioTime:nE,audioLoadGen:0},yW=p.Struct({});var it=Ie("CommandReceived",{command:fO}),No=Ie("SucceededMediaInfo",{source:ph,info:xO,opts:dh}),Ka=Ie("NotPlayable",{source:ph,url:p.String}),rE=Ie("TrackMissing",{source:ph}),Hf=Ie("FailedFetchInfo",{source:ph,errorMessage:p.String}),mh=Ie("SucceededTrackInfoForEnqueue",{concertId:p.Number,trackIdx:p.Number,info:p.Option(p.Struct({title:p.String,liked:p.Boolean}))}),hh=Ie("ResolvedFirstAvailableTrack",{concertId:p.Number,trackIdx:p.Option(p.Number)}),gh=Ie("DrainedQueue",{played:p.Option(p.Struct({entry:fh,info:xO})),skippedCount:p.Number,plan:_u}),oE=Ie("NoNextTrack",{plan:_u}),sE=Ie("FailedNextTrackInfo",{plan:_u}),iE=Ie("FailedPrevTrackInfo"),aE=Ie("SucceededPrepareStart",{target:Hi,seedStatus:p.Option(zf)}),xh=Ie("FailedPrepareStart",{target:Hi}),yh=Ie("SucceededPrepareStatus",{target:Hi,status:zf,elapsedMs:p.Number}),cE=Ie("FailedPollPrepareStatus",{target:Hi,elapsedMs:p.Number}),uE=Ie("CompletedLikeToggle",{concertId:p.Number,trackIdx:p.Number,liked:p.Boolean}),bh=Ie("FailedLikeToggle",{concertId:p.Number,trackIdx:p.Number,attempted:p.Boolean}),Wf=Ie("CompletedDeleteTrack",{concertId:p.Number,trackIdx:p.Number,ok:p.Boolean,source:p.Literals(["bar","sidebar"])}),lE=Ie("SucceededConcertItems",{concertId:p.Number,items:p.mutable(p.Array(eE)),advanceAfter:p.Boolean}),Sh=Ie("FailedConcertItems",{concertId:p.Number}),fE=Ie("SucceededConcertPlaybackItems",{concertId:p.Number,items:p.mutable(p.Array(eE)),atPos:p.Number}),Eh=Ie("FailedConcertPlayback",{concertId:p.Number,errorMessage:p.String}),pE=Ie("CompletedDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number,wasPlayingThis:p.Boolean}),kh=Ie("FailedDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number}),oOe=p.Struct({concertId:p.Number,trackIdx:p.Number,title:p.String}),dE=Ie("SucceededPlaylistTracks",{playlistId:p.Number,name:p.String,tracks:p.Array(oOe)}),mE=Ie("FailedPlaylistLoad",{playlistId:p.Number}),hE=Ie("SucceededTrackDetails",{concertId:p.Number,loadGen:p.Number,tracksBusy:p.Boolean,tracks:p.mutable(p.Array(gO))}),gE=Ie("FailedTrackDetails",{concertId:p.Number,loadGen:p.Number}),xE=Ie("FailedOpenExternal"),SO=Ie("StartedAudio"),EO=Ie("PausedAudio"),kO=Ie("UpdatedAudioTime",{currentTime:p.Number,duration:p.Number,loadGen:p.Number}),wO=Ie("EndedAudio"),TO=Ie("ErroredAudio"),Vi=Ie("RejectedAudioPlay"),IO=Ie("SettledHtmxContent"),vO=Ie("SwappedLikeButton",{concertId:p.Number,trackIdx:p.Number,liked:p.Boolean}),AO=Ie("PressedSpace",{audioPaused:p.Boolean}),CO=Ie("PressedEscape"),OO=Ie("ClickedOutsideVideo"),MO=Ie("MovedSidebarDrag",{clientX:p.Number}),PO=Ie("ReleasedSidebarDrag",{clientX:p.Number,moved:p.Boolean}),de=Ie("Acked"),xLe=p.Union([it,No,Ka,rE,Hf,mh,hh,gh,oE,sE,iE,aE,xh,yh,cE,uE,bh,Wf,lE,Sh,fE,Eh,pE,kh,dE,mE,hE,gE,xE,SO,EO,kO,wO,TO,Vi,IO,vO,AO,CO,OO,MO,PO,de]);var yE=Oe.define("FetchAlbumInfo",{concertId:p.Number,opts:dh},No,Ka,Hf)(({concertId:e,opts:t})=>m.tryPromise(()=>XH(e)).pipe(m.map(n=>n.playable?No({source:co.Album({concertId:e}),info:n,opts:t}):Ka({source:co.Album({concertId:e}),url:n.url})),m.catch(()=>m.succeed(Hf({source:co.Album({concertId:e}),errorMessage:"Couldn't load album"}))))),Vf=Oe.define("FetchTrackInfo",{concertId:p.Number,trackIdx:p.Number,opts:dh},No,Ka,rE,Hf)(({concertId:e,trackIdx:t,opts:n})=>{let r=co.Track({concertId:e,trackIdx:t});return m.tryPromise(()=>lh(e,t)).pipe(m.map(o=>o===null?rE({source:r}):o.playable?No({source:r,info:o,opts:n}):Ka({source:r,url:o.url})),m.catch(()=>m.succeed(Hf({source:r,errorMessage:"Couldn't load track"}))))}),bW=Oe.define("FetchTrackInfoForEnqueue",{concertId:p.Number,trackIdx:p.Number},mh)(({concertId:e,trackIdx:t})=>m.tryPromise(()=>lh(e,t)).pipe(m.map(n=>mh({concertId:e,trackIdx:t,info:n===null?h.none():h.some({title:n.title,liked:!!n.liked})})),m.catch(()=>m.succeed(mh({concertId:e,trackIdx:t,info:h.none()}))))),SW=Oe.define("ResolveFirstAvailableTrack",{concertId:p.Number},hh)(({concertId:e})=>m.gen(function*(){if(yield*m.tryPromise(()=>lh(e,0)).pipe(m.catch(()=>m.succeed(null))))return hh({concertId:e,trackIdx:h.some(0)});let r=(yield*m.tryPromise(()=>eW(e,0)).pipe(m.catch(()=>m.succeed(null))))?.track_index??null;return hh({concertId:e,trackIdx:r===null?h.none():h.some(r)})})),Ru=Oe.define("DrainQueue",{queue:p.Array(fh),plan:_u},gh)(({queue:e,plan:t})=>m.gen(function*(){for(let n=0;n<e.length;n++){let r=e[n],o=yield*m.tryPromise(()=>lh(r.concertId,r.trackIdx)).pipe(m.catch(()=>m.succeed(null)));if(o&&o.playable)return gh({played:h.some({entry:r,info:o}),skippedCount:n,plan:t})}return gh({played:h.none(),skippedCount:e.length,plan:t})})),EW=Oe.define("FetchNextTrackInfo",{concertId:p.Number,trackIdx:p.Number,plan:_u},No,oE,sE)(({concertId:e,trackIdx:t,plan:n})=>m.tryPromise(()=>tW(e,t)).pipe(m.map(r=>r===null?oE({plan:n}):No({source:co.Track({concertId:e,trackIdx:r.track_index??t}),info:r,opts:{recordListen:!0,playlistName:null,openVideoPanel:!1}})),m.catch(()=>m.succeed(sE({plan:n}))))),kW=Oe.define("FetchPrevTrackInfo",{concertId:p.Number,trackIdx:p.Number},No,iE)(({concertId:e,trackIdx:t})=>m.tryPromise(()=>nW(e,t)).pipe(m.map(n=>No({source:co.Track({concertId:e,trackIdx:n.track_index??t}),info:n,opts:{recordListen:!0,playlistName:null,openVideoPanel:!1}})),m.catch(()=>m.succeed(iE())))),bE=Oe.define("PostPrepare",{target:Hi},aE,xh)(({target:e})=>m.gen(function*(){let t=yield*m.tryPromise(()=>rW(e.concertId));if(!t.ok)return xh({target:e});let n=yield*m.tryPromise(()=>t.json()).pipe(m.map(r=>{let o=p.decodeUnknownExit(zf)(r);return o._tag==="Success"?h.some(o.value):h.none()}),m.catch(()=>m.succeed(h.none())));return aE({target:e,seedStatus:n})}).pipe(m.catch(()=>m.succeed(xh({target:e}))))),SE=Oe.define("PollPrepareStatus",{target:Hi,elapsedMs:p.Number,seedStatus:p.Option(zf)},yh,cE)(({target:e,elapsedMs:t,seedStatus:n})=>h.match(n,{onSome:r=>m.succeed(yh({target:e,status:r,elapsedMs:t})),onNone:()=>{let r=e.concertId;return m.sleep(2e3).pipe(m.flatMap(()=>m.tryPromise(()=>oW(r))),m.map(o=>yh({target:e,status:o,elapsedMs:t+2e3})),m.catch(()=>m.succeed(cE({target:e,elapsedMs:t+2e3}))))}})),RO=Oe.define("ToggleLikeRequest",{concertId:p.Number,trackIdx:p.Number,next:p.Boolean},uE,bh)(({concertId:e,trackIdx:t,next:n})=>m.tryPromise(()=>sW(e,t)).pipe(m.map(r=>r.ok?uE({concertId:e,trackIdx:t,liked:n}):bh({concertId:e,trackIdx:t,attempted:n})),m.catch(()=>m.succeed(bh({concertId:e,trackIdx:t,attempted:n}))))),NO=Oe.define("DeleteTrackRequest",{concertId:p.Number,trackIdx:p.Number,source:p.Literals(["bar","sidebar"])},Wf)(({concertId:e,trackIdx:t,source:n})=>m.tryPromise(()=>iW(e,t)).pipe(m.flatMap(r=>r.ok?m.tryPromise(()=>r.text()).pipe(m.tap(o=>m.sync(()=>{let s=ao(`concert-${e}`);if(s){s.outerHTML=o;let i=ao(`concert-${e}`);i&&window.htmx&&window.htmx.process(i)}})),m.as(Wf({concertId:e,trackIdx:t,ok:!0,source:n}))):m.succeed(Wf({concertId:e,trackIdx:t,ok:!1,source:n}))),m.catch(()=>m.succeed(Wf({concertId:e,trackIdx:t,ok:!1,source:n}))))),FO=Oe.define("RefreshConcertItems",{concertId:p.Number,advanceAfter:p.Boolean},lE,Sh)(({concertId:e,advanceAfter:t})=>m.tryPromise(()=>mO(e)).pipe(m.map(n=>pO(n)?Sh({concertId:e}):lE({concertId:e,items:n.items,advanceAfter:t})),m.catch(()=>m.succeed(Sh({concertId:e}))))),EE=Oe.define("FetchConcertPlayback",{concertId:p.Number,atPos:p.Option(p.Number),errorMessage:p.String},No,Ka,fE,Eh)(({concertId:e,atPos:t,errorMessage:n})=>m.tryPromise(()=>mO(e)).pipe(m.map(r=>{if(pO(r)){let o=r.source;return o.playable?No({source:co.Album({concertId:e}),info:o,opts:{recordListen:!0,playlistName:null,openVideoPanel:!1}}):Ka({source:co.Album({concertId:e}),url:o.url})}return r.items.length>0?fE({concertId:e,items:r.items,atPos:h.getOrElse(t,()=>0)}):Eh({concertId:e,errorMessage:"Nothing to play"})}),m.catch(()=>m.succeed(Eh({concertId:e,errorMessage:n}))))),wW=Oe.define("PostDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number,wasPlayingThis:p.Boolean},pE,kh)(({concertId:e,interludeIdx:t,wasPlayingThis:n})=>m.tryPromise(()=>aW(e,t)).pipe(m.map(r=>r.ok?pE({concertId:e,interludeIdx:t,wasPlayingThis:n}):kh({concertId:e,interludeIdx:t})),m.catch(()=>m.succeed(kh({concertId:e,interludeIdx:t}))))),TW=Oe.define("FetchPlaylistForPlay",{playlistId:p.Number},dE,mE)(({playlistId:e})=>m.tryPromise(()=>YH(e)).pipe(m.map(t=>{let n=(t.resolved_tracks||[]).filter(r=>r.available).map(r=>({concertId:r.concert_id,trackIdx:r.track_index,title:r.title}));return dE({playlistId:e,name:t.playlist.name,tracks:n})}),m.catch(()=>m.succeed(mE({playlistId:e}))))),IW=Oe.define("RecordListenEvent",{url:p.String},de)(({url:e})=>m.tryPromise(()=>hO(e)).pipe(m.catch(()=>m.succeed(void 0)),m.as(de()))),vW=Oe.define("OpenExternalRequest",{url:p.String},de,xE)(({url:e})=>m.tryPromise(()=>hO(e)).pipe(m.as(de()),m.catch(()=>m.succeed(xE())))),AW=Oe.define("PlayAudio",{url:p.String,loadGen:p.Number},de,Vi)(({url:e,loadGen:t})=>m.sync(()=>ei("player-audio",HTMLMediaElement)).pipe(m.flatMap(n=>n?(n.src=e,n.dataset.audioLoadGen=String(t),m.tryPromise(()=>n.play()).pipe(m.as(de()),m.catch(()=>m.succeed(Vi())))):m.succeed(Vi())))),Es=Oe.define("PauseAudio",de)(m.sync(()=>ei("player-audio",HTMLMediaElement)?.pause()).pipe(m.as(de()))),Gf=Oe.define("ResumeAudio",de,Vi)(m.sync(()=>ei("player-audio",HTMLMediaElement)).pipe(m.flatMap(e=>e?m.tryPromise(()=>e.play()):m.void),m.as(de()),m.catch(()=>m.succeed(Vi())))),CW=Oe.define("ToggleAudio",de,Vi)(m.sync(()=>ei("player-audio",HTMLMediaElement)).pipe(m.flatMap(e=>e?e.paused?m.tryPromise(()=>e.play()).pipe(m.as(de()),m.catch(()=>m.succeed(Vi()))):(e.pause(),m.succeed(de())):m.succeed(de())))),wh=Oe.define("SeekAudio",{seconds:p.Number},de)(({seconds:e})=>m.sync(()=>{let t=ei("player-audio",HTMLMediaElement);t&&Number.isFinite(t.duration)&&t.duration>0&&(t.currentTime=e)}).pipe(m.as(de()))),DO=Oe.define("ClearAudioSrc",de)(m.sync(()=>{let e=ei("player-audio",HTMLMediaElement);e&&(e.pause(),e.removeAttribute("src"),e.load())}).pipe(m.as(de())));function OW(e,t){return t!=null?document.querySelectorAll(`[data-concert-id="${e}"][data-track-idx="${t}"]`):document.querySelectorAll(`[data-concert-id="${e}"][data-role="listen-album"]`)}var kE=Oe.define("MarkPlayingExternal",{concertId:p.Number,trackIdx:p.Option(p.Number)},de)(({concertId:e,trackIdx:t})=>m.sync(()=>{document.querySelectorAll(".btn-track-listen.playing, .btn-listen.playing").forEach(n=>n.classList.remove("playing")),OW(e,h.getOrNull(t)).forEach(n=>n.classList.add("playing"))}).pipe(m.as(de()))),MW=Oe.define("MarkPlayingInterludeExternal",{concertId:p.Number,interludeIdx:p.Number},de)(({concertId:e,interludeIdx:t})=>m.sync(()=>{document.querySelectorAll(".btn-track-listen.playing, .btn-listen.playing").forEach(n=>n.classList.remove("playing")),document.querySelectorAll(`[data-concert-id="${e}"][data-interlude-idx="${t}"]`).forEach(n=>n.classList.add("playing"))}).pipe(m.as(de()))),LO=Oe.define("MarkPreparingExternal",{concertId:p.Number,trackIdx:p.Number},de)(({concertId:e,trackIdx:t})=>m.sync(()=>{OW(e,t).forEach(n=>n.classList.add("preparing"))}).pipe(m.as(de()))),Nu=Oe.define("ClearPreparingExternal",de)(m.sync(()=>{document.querySelectorAll(".btn-track-listen.preparing").forEach(e=>e.classList.remove("preparing"))}).pipe(m.as(de()))),UO=Oe.define("DisableCardTracksExternal",{concertId:p.Number},de)(({concertId:e})=>m.sync(()=>{ao(`concert-${e}`)?.querySelectorAll(".btn-tracks, .btn-track-listen").forEach(n=>{n.disabled=!0})}).pipe(m.as(de()))),Th=Oe.define("SyncLikeButtonsExternal",{concertId:p.Number,trackIdx:p.Option(p.Number),liked:p.Boolean},de)(({concertId:e,trackIdx:t,liked:n})=>m.sync(()=>{document.querySelectorAll(`.btn-like[hx-post="/concerts/${e}/tracks/${h.getOrNull(t)}/like"]`).forEach(r=>{r.classList.toggle("liked",n),r.textContent=n?"\u2605":"\u2606"})}).pipe(m.as(de()))),PW=Oe.define("OpenInNewTab",{url:p.String},de)(({url:e})=>m.sync(()=>window.open(e,"_blank","noopener")).pipe(m.as(de()))),$O=Oe.define("RefreshCardStatus",{concertId:p.Number},de)(({concertId:e})=>m.sync(()=>{ao(`concert-${e}`)&&window.htmx&&window.htmx.ajax("GET",`/concerts/${e}/status`,{target:`#concert-${e}`,swap:"outerHTML"})}).pipe(m.as(de()))),_W=Oe.define("SyncNowPlayingMirror",{concertId:p.NullOr(p.Number),trackIdx:p.NullOr(p.Number)},de)(({concertId:e,trackIdx:t})=>m.sync(()=>{bH({concertId:e,trackIdx:t}),document.body.classList.toggle("player-active",e!==null)}).pipe(m.as(de()))),BO=Oe.define("OpenAddToPlaylist",{concertId:p.Number,trackIdx:p.Number,label:p.String},de)(({concertId:e,trackIdx:t,label:n})=>m.sync(()=>{window.Playlists?.openAdd({type:"track",concertId:e,trackIndex:t,label:n})}).pipe(m.as(de()))),Kf=Oe.define("ScrollQueueToBottom",de)(m.sync(()=>{let e=ao("sidebar-queue-section");e&&(e.scrollTop=e.scrollHeight)}).pipe(m.as(de()))),Jf=Oe.define("MutateBodyClass",{className:p.String,add:p.Boolean},de)(({className:e,add:t})=>m.sync(()=>{document.body.classList[t?"add":"remove"](e)}).pipe(m.as(de()))),wE=Oe.define("ShowVideoPanel",de)(m.sync(()=>{ao("player-video-panel")?.classList.add("open")}).pipe(m.as(de()))),ks=Oe.define("HideVideoPanel",de)(m.sync(()=>{ao("player-video-panel")?.classList.remove("open","controls-visible")}).pipe(m.as(de()))),RW=Oe.define("LoadSidebarWidth",de)(m.sync(()=>{let e=parseInt(localStorage.getItem(cO)||"",10);isNaN(e)||document.documentElement.style.setProperty("--sidebar-width",`${GS(e)}px`)}).pipe(m.as(de()))),jO=Oe.define("SetSidebarWidthVar",{px:p.Number},de)(({px:e})=>m.sync(()=>{document.documentElement.style.setProperty("--sidebar-width",`${GS(e)}px`)}).pipe(m.as(de()))),NW=Oe.define("PersistSidebarWidth",{px:p.Number},de)(({px:e})=>m.sync(()=>{try{localStorage.setItem(cO,String(GS(e)))}catch{}}).pipe(m.as(de()))),Ih=Oe.define("FetchTrackDetails",{concertId:p.Number,loadGen:p.Number},hE,gE)(({concertId:e,loadGen:t})=>m.tryPromise(()=>cW(e)).pipe(m.map(n=>hE({concertId:e,loadGen:t,tracksBusy:n.tracks_busy,tracks:n.tracks})),m.catch(()=>m.succeed(gE({concertId:e,loadGen:t})))));function aOe(){let e=ei("player-audio",HTMLMediaElement);return e&&(e.currentSrc||e.getAttribute("src"))?e:null}function cOe(e){if(!e)return()=>{};let t=null,n=()=>{e.classList.add("controls-visible"),t&&clearTimeout(t),t=setTimeout(()=>e.classList.remove("controls-visible"),2500)};return e.addEventListener("mousemove",n),e.addEventListener("touchstart",n,{passive:!0}),()=>{e.removeEventListener("mousemove",n),e.removeEventListener("touchstart",n),t&&clearTimeout(t),e.classList.remove("controls-visible")}}function uOe(e){if(!Number.isFinite(e.duration)||e.duration<=0)return h.none();let t=Number(e.dataset.audioLoadGen);return h.some(kO({currentTime:e.currentTime,duration:e.duration,loadGen:Number.isFinite(t)?t:-1}))}function lOe(e){let r=(e instanceof CustomEvent?e.detail:void 0)?.elt?.getAttribute("hx-post")?.match(/\/concerts\/(\d+)\/tracks\/(\d+)\/like/);if(!r)return h.none();let o=parseInt(r[1],10),s=parseInt(r[2],10),i=document.querySelector(`[hx-post="/concerts/${o}/tracks/${s}/like"]`);return i?h.some({concertId:o,trackIdx:s,liked:i.classList.contains("liked")}):h.none()}var FW=VS.make()(e=>({audioEvents:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>{let t=ei("player-audio",HTMLMediaElement);return t?B.mergeAll([B.merge(B.fromEventListener(t,"play").pipe(B.map(()=>SO())),B.fromEventListener(t,"pause").pipe(B.map(()=>EO()))),B.merge(B.fromEventListener(t,"ended").pipe(B.map(()=>wO())),B.fromEventListener(t,"error").pipe(B.map(()=>TO()))),B.merge(B.fromEventListener(t,"timeupdate"),B.fromEventListener(t,"loadedmetadata")).pipe(B.map(()=>uOe(t)),B.filter(h.isSome),B.map(n=>n.value))],{concurrency:"unbounded"}):B.empty}}),keyboard:e({videoOpen:p.Boolean},{modelToDependencies:t=>({videoOpen:t.video.open}),dependenciesToStream:({videoOpen:t})=>B.fromEventListener(document,"keydown").pipe(B.mapEffect(n=>m.sync(()=>{if(n.defaultPrevented)return h.none();if(hH(n)&&!QS(n.target)&&t)return n.preventDefault(),h.some(CO());if(mH(n)&&!xH(n.target)){let r=aOe();return r?(n.preventDefault(),n.repeat?h.none():h.some(AO({audioPaused:r.paused}))):h.none()}return h.none()})),B.filter(h.isSome),B.map(n=>n.value))}),outsideVideo:e({videoOpen:p.Boolean},{modelToDependencies:t=>({videoOpen:t.video.open}),dependenciesToStream:({videoOpen:t})=>B.when(B.fromEventListener(document,"click").pipe(B.mapEffect(n=>m.sync(()=>{let r=ao("player-video-panel");return dH(n.target,r)?h.some(OO()):h.none()})),B.filter(h.isSome),B.map(n=>n.value)),m.sync(()=>t))}),htmxSettle:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>B.merge(B.fromEventListener(document.body,"htmx:afterSettle"),B.fromEventListener(document.body,"htmx:historyRestore")).pipe(B.map(()=>IO()))}),htmxSwap:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>B.callback(t=>m.acquireRelease(m.sync(()=>{let n=r=>h.map(lOe(r),o=>on.offerUnsafe(t,vO(o)));return document.body.addEventListener("htmx:afterSwap",n),n}),n=>m.sync(()=>document.body.removeEventListener("htmx:afterSwap",n))).pipe(m.flatMap(()=>m.never)))}),sidebarResize:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>B.callback(t=>m.acquireRelease(m.sync(()=>{let n=ao("sidebar-resize");if(!n)return()=>{};let r=!1,o=!1,s=c=>{r=!0,o=!1,n.setPointerCapture(c.pointerId),c.preventDefault(),document.body.classList.add("sidebar-resizing")},i=c=>{r&&(o=!0,on.offerUnsafe(t,MO({clientX:Math.round(c.clientX)})))},a=c=>{if(!r)return;r=!1,document.body.classList.remove("sidebar-resizing");let u=Math.round(c.clientX||parseInt(getComputedStyle(document.documentElement).getPropertyValue("--sidebar-width"),10)||240);on.offerUnsafe(t,PO({clientX:u,moved:o})),o=!1};return n.addEventListener("pointerdown",s),document.addEventListener("pointermove",i),document.addEventListener("pointerup",a),document.addEventListener("pointercancel",a),()=>{n.removeEventListener("pointerdown",s),document.removeEventListener("pointermove",i),document.removeEventListener("pointerup",a),document.removeEventListener("pointercancel",a)}}),n=>m.sync(n)).pipe(m.flatMap(()=>m.never)))}),videoControlsIdle:e({videoOpen:p.Boolean},{modelToDependencies:t=>({videoOpen:t.video.open}),dependenciesToStream:({videoOpen:t})=>t?B.callback(()=>m.acquireRelease(m.sync(()=>cOe(ao("player-video-panel"))),n=>m.sync(n)).pipe(m.flatMap(()=>m.never))):B.empty}),commandPort:Bf.subscription(YS.inbound.command,t=>it({command:t}))}));var vh=q.withReturnType(),qO=e=>({concertId:e.concertId,trackIdx:e.trackIdx,isVideo:e.isVideo,watchUrl:e.watchUrl,hasNext:e.hasNext,hasPrev:e.hasPrev,liked:e.liked,concert:h.getOrNull(e.concert)}),DW=e=>e.playback.concertId!==null,fOe=e=>DW(e)&&e.isPlaying,LW=e=>!DW(e)||e.playback.ended,pr=(e,t)=>L(e,{status:()=>Ga.Error({message:t})}),UW=(e,t)=>L(e,{status:()=>Ga.Busy({message:t})}),Ah=(e,t)=>[e,[...t,_W({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx})]],pOe=e=>q.value(e).pipe(q.withReturnType(),q.tagsExhaustive({Track:({concertId:t,trackIdx:n})=>({concertId:t,trackIdx:n}),Album:({concertId:t})=>({concertId:t,trackIdx:null}),ConcertItem:({concertId:t,trackIdx:n})=>({concertId:t,trackIdx:n})})),dOe=e=>q.value(e).pipe(q.withReturnType(),q.tagsExhaustive({Track:({concertId:t,trackIdx:n})=>`/concerts/${t}/tracks/${n}/listen`,Album:({concertId:t})=>`/concerts/${t}/listen`,ConcertItem:({concertId:t,trackIdx:n,isInterlude:r})=>r||n===null?null:`/concerts/${t}/tracks/${n}/listen`})),mOe=(e,t)=>t.is_video?q.value(e).pipe(q.withReturnType(),q.tagsExhaustive({Track:({concertId:n,trackIdx:r})=>`/concerts/${n}/tracks/${r}/watch`,Album:({concertId:n})=>`/concerts/${n}/watch`,ConcertItem:()=>null})):null,TE=(e,t,n,r)=>{let{concertId:o,trackIdx:s}=pOe(t),i=dOe(t),a=mOe(t,n),c=n.is_video?r.openVideoPanel?!0:e.video.open:!1,u=e.audioLoadGen+1,f=L(e,{playback:()=>({concertId:o,trackIdx:s,title:n.title,artist:n.artist,isVideo:n.is_video,watchUrl:a,hasNext:n.has_next,hasPrev:n.has_prev,liked:n.liked,ended:!1,concert:h.none(),playlistLabel:r.playlistName}),video:()=>({open:c}),pending:()=>h.none(),status:()=>Ga.Idle(),audioTime:()=>nE,audioLoadGen:()=>u}),d=c!==e.video.open?[c?wE():ks()]:[],g=i&&r.recordListen?[IW({url:i})]:[],y=h.match(e.pendingSeek,{onNone:()=>[],onSome:b=>[wh({seconds:b})]}),x=[AW({url:n.url,loadGen:u}),kE({concertId:o,trackIdx:h.fromNullishOr(s)}),Nu(),...d,...g,...y];return[L(f,{pendingSeek:()=>h.none()}),x]},zO=(e,[t,n])=>{let r=t.playback.concertId;if(!e.sidebar.open||r===null||r===e.playback.concertId||h.isSome(t.playback.concert))return[t,n];let o=e.sidebar.loadGen+1;return[L(t,{sidebar:()=>L(t.sidebar,{loadGen:()=>o})}),[...n,Ih({concertId:r,loadGen:o})]]},Ch=(e,t,n)=>e.playback.concertId===t&&e.playback.trackIdx===n?e.isPlaying?[e,[Es()]]:[e,[Gf()]]:fOe(e)?[e,[bW({concertId:t,trackIdx:n})]]:[e,[Vf({concertId:t,trackIdx:n,opts:Wi})]],HO=e=>Ah(L(e,{playback:()=>yO,queue:()=>[],sidebar:()=>({open:!1,tracks:h.none(),loadGen:0}),video:()=>({open:!1}),isPlaying:()=>!1,pendingSeek:()=>h.none(),status:()=>Ga.Idle(),audioTime:()=>nE,audioLoadGen:()=>e.audioLoadGen+1}),e.video.open?[DO(),ks()]:[DO()]),IE=(e,t)=>q.value(t).pipe(vh,q.whenOr("queue-only","next-or-none",()=>[L(e,{isPlaying:()=>!1}),[]]),q.when("next-or-stop",()=>HO(e)),q.when("next-or-collapse",()=>[L(e,{isPlaying:()=>!1,video:()=>({open:!1})}),e.video.open?[ks()]:[]]),q.exhaustive),$W=(e,t)=>{let{concertId:n,trackIdx:r}=e.playback;return n===null||r===null?IE(e,t):[e,[EW({concertId:n,trackIdx:r,plan:t})]]},WO=e=>[e,[Es(),Ru({queue:e.queue,plan:"next-or-stop"})]],VO=e=>h.isSome(e.playback.concert)?GO(e):[e,[Ru({queue:e.queue,plan:"next-or-collapse"})]];function Oh(e,t){return h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:n=>{let r=n.items[t];if(!r)return[e,[]];let o=r.kind==="interlude",s=o?null:r.track_index??null,{hasPrev:i,hasNext:a}=lH(n.items,t),c={artist:r.artist,has_next:a,has_prev:i,is_video:r.is_video,liked:r.liked,playable:!0,title:r.title,track_index:s,url:r.url},u=co.ConcertItem({concertId:n.id,trackIdx:s,isInterlude:o}),[f,d]=TE(e,u,c,Wi),g=L(f,{playback:()=>L(f.playback,{concert:()=>h.some(L(n,{pos:()=>t}))})}),y=o&&r.interlude_index!=null?[MW({concertId:n.id,interludeIdx:r.interlude_index})]:[];return Ah(g,[...d,...y])}})}function GO(e){return h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:t=>{let n=fH(t.pos,t.items.length);return n===null?[L(e,{playback:()=>L(e.playback,{concert:()=>h.none()}),video:()=>({open:!1})}),e.video.open?[ks()]:[]]:Oh(e,n)}})}function KO(e){return h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:t=>t.pos<t.items.length?Oh(e,t.pos):[L(e,{playback:()=>L(e.playback,{concert:()=>h.none()}),video:()=>({open:!1})}),e.video.open?[ks()]:[]]})}var vE={start:"Couldn't start concert",load:"Couldn't load concert"};function hOe(e,t,n,r){return h.match(e.sidebar.tracks,{onNone:()=>e,onSome:o=>{if(e.playback.concertId!==t)return e;let s=o.tracks.map(i=>i.index===n?{...i,liked:r}:i);return L(e,{sidebar:()=>L(e.sidebar,{tracks:()=>h.some(L(o,{tracks:()=>s}))})})}})}function BW(e,t,n,r){return h.match(e.sidebar.tracks,{onNone:()=>e,onSome:o=>{if(e.playback.concertId!==t)return e;let s=o.tracks.map(i=>i.index===n?{...i,available:r}:i);return L(e,{sidebar:()=>L(e.sidebar,{tracks:()=>h.some(L(o,{tracks:()=>s}))})})}})}function gOe(e,t,n,r){return h.match(e.playback.concert,{onNone:()=>e,onSome:o=>{if(o.id!==t)return e;let s=o.items.map(i=>i.track_index===n&&i.kind!=="interlude"?{...i,liked:r}:i);return L(e,{playback:()=>L(e.playback,{concert:()=>h.some(L(o,{items:()=>s}))})})}})}function Fu(e,t,n,r){let s=e.playback.concertId===t&&e.playback.trackIdx===n?L(e,{playback:()=>L(e.playback,{liked:()=>r})}):e,i=hOe(s,t,n,r);return gOe(i,t,n,r)}function jW(e,t,n){let r=e.playback.concertId===t?h.flatMap(e.sidebar.tracks,o=>h.fromNullishOr(o.tracks.find(s=>s.index===n)?.liked)):h.none();return h.orElse(r,()=>h.flatMap(e.playback.concert,o=>o.id===t?h.fromNullishOr(o.items.find(s=>s.track_index===n&&s.kind!=="interlude")?.liked):h.none()))}var qW=e=>t=>q.value(t).pipe(vh,q.tagsExhaustive({PlayAlbum:({concertId:n})=>[e,[yE({concertId:n,opts:Wi})]],PlayTrack:({concertId:n,trackIdx:r})=>Ch(e,n,r),PlayTracks:({concertId:n})=>[e,[SW({concertId:n})]],StartAlbum:({concertId:n,recordListen:r})=>[e,[yE({concertId:n,opts:{recordListen:r,playlistName:null,openVideoPanel:!1}})]],StartTrack:({concertId:n,trackIdx:r})=>[e,[Vf({concertId:n,trackIdx:r,opts:Wi})]],TogglePause:()=>[e,[CW()]],Seek:({seconds:n})=>[e,[wh({seconds:n})]],SkipToNext:()=>{if(h.isSome(e.playback.concert)){let[n,r]=GO(e);return[n,[Es(),...r]]}return JS(qO(e.playback),e.queue.length)?[e,[Es(),Ru({queue:e.queue,plan:"next-or-none"})]]:[e,[]]},SkipToPrev:()=>{if(h.isSome(e.playback.concert)){let n=e.playback.concert.value;if(n.pos<=0)return[e,[]];let[r,o]=Oh(e,n.pos-1);return[r,[Es(),...o]]}return ZS(qO(e.playback))?e.playback.concertId===null||e.playback.trackIdx===null?[e,[]]:[e,[Es(),kW({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx})]]:[e,[]]},Watch:()=>{let n=!e.video.open;return[L(e,{video:()=>({open:n})}),[n?wE():ks()]]},OpenExternal:()=>e.playback.watchUrl===null?[e,[]]:[e,[Es(),vW({url:e.playback.watchUrl})]],WatchTrackDirect:({concertId:n,trackIdx:r})=>[e,[Vf({concertId:n,trackIdx:r,opts:{recordListen:!0,playlistName:null,openVideoPanel:!0}})]],ToggleLike:()=>{if(e.playback.trackIdx===null||e.playback.concertId===null)return[e,[]];let{concertId:n,trackIdx:r}=e.playback,o=!e.playback.liked;return[Fu(e,n,r,o),[RO({concertId:n,trackIdx:r,next:o}),Th({concertId:n,trackIdx:h.some(r),liked:o})]]},DeleteTrack:()=>e.playback.trackIdx===null||e.playback.concertId===null?[e,[]]:[e,[NO({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx,source:"bar"})]],OpenSidebar:()=>{let n=L(e,{sidebar:()=>L(e.sidebar,{open:()=>!0})}),r=e.playback.concertId;if(r!==null&&h.isNone(e.playback.concert)){let o=e.sidebar.loadGen+1;return[L(n,{sidebar:()=>L(n.sidebar,{loadGen:()=>o})}),[Jf({className:"sidebar-open",add:!0}),Ih({concertId:r,loadGen:o})]]}return[n,[Jf({className:"sidebar-open",add:!0})]]},CloseSidebar:()=>[L(e,{sidebar:()=>L(e.sidebar,{open:()=>!1})}),[Jf({className:"sidebar-open",add:!1})]],ToggleSidebar:()=>{let n=!e.sidebar.open,r=L(e,{sidebar:()=>L(e.sidebar,{open:()=>n})}),o=e.playback.concertId;if(n&&o!==null&&h.isNone(e.playback.concert)){let s=e.sidebar.loadGen+1;return[L(r,{sidebar:()=>L(r.sidebar,{loadGen:()=>s})}),[Jf({className:"sidebar-open",add:n}),Ih({concertId:o,loadGen:s})]]}return[r,[Jf({className:"sidebar-open",add:n})]]},SidebarDeleteTrack:({concertId:n,trackIdx:r})=>[e,[NO({concertId:n,trackIdx:r,source:"sidebar"})]],PlayQueueEntryNow:({pos:n})=>{let r=e.queue[n];return r?[L(e,{queue:s=>uO(s,n)}),[Vf({concertId:r.concertId,trackIdx:r.trackIdx,opts:Wi})]]:[e,[]]},Dequeue:({pos:n})=>[L(e,{queue:r=>uO(r,n)}),[]],RemoveGroup:({groupId:n})=>[L(e,{queue:r=>cH(r,n)}),[]],Enqueue:({concertId:n,trackIdx:r,title:o,liked:s})=>{let i=KS(e.queue,ch(n,r,o,s));return[L(e,{queue:()=>i.queue}),i.added?[Kf()]:[]]},PlayAlbumAt:({concertId:n,seconds:r})=>e.playback.concertId===n&&e.playback.trackIdx===null?[e,[wh({seconds:r}),...e.isPlaying?[]:[Gf()]]]:[L(e,{pendingSeek:()=>h.some(r)}),[yE({concertId:n,opts:{recordListen:!1,playlistName:null,openVideoPanel:!1}})]],PlayPlaylist:({playlistId:n})=>[e,[TW({playlistId:n})]],AddToPlaylist:()=>e.playback.trackIdx===null||e.playback.concertId===null?[e,[]]:[e,[BO({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx,label:e.playback.title})]],StopPlayback:()=>HO(e),PlayConcert:({concertId:n})=>[e,[EE({concertId:n,atPos:h.none(),errorMessage:vE.start})]],PlayConcertFrom:({concertId:n,pos:r})=>h.match(e.playback.concert,{onSome:o=>o.id===n?Oh(L(e,{playback:()=>L(e.playback,{concert:()=>h.some(L(o,{pos:()=>r}))})}),r):[e,[EE({concertId:n,atPos:h.some(r),errorMessage:vE.load})]],onNone:()=>[e,[EE({concertId:n,atPos:h.some(r),errorMessage:vE.load})]]}),SidebarDeleteInterlude:({concertId:n,interludeIdx:r})=>{let o=h.match(e.playback.concert,{onNone:()=>!1,onSome:s=>{let i=s.items[s.pos];return!!(i&&i.kind==="interlude"&&i.interlude_index===r)}});return[e,[wW({concertId:n,interludeIdx:r,wasPlayingThis:o})]]},SidebarLikeTrack:({concertId:n,trackIdx:r})=>{let o=jW(e,n,r);if(h.isNone(o))return[e,[]];let s=!o.value;return[Fu(e,n,r,s),[RO({concertId:n,trackIdx:r,next:s}),Th({concertId:n,trackIdx:h.some(r),liked:s})]]},SidebarAddToPlaylist:({concertId:n,trackIdx:r,label:o})=>[e,[BO({concertId:n,trackIdx:r,label:o})]]}));var HW=(e,t)=>q.value(t).pipe(vh,q.tagsExhaustive({CommandReceived:({command:n})=>qW(e)(n),SucceededMediaInfo:({source:n,info:r,opts:o})=>Ah(...zO(e,TE(e,n,r,o))),NotPlayable:({url:n})=>[e,[PW({url:n})]],TrackMissing:({source:n})=>n._tag!=="Track"?[e,[]]:[e,[bE({target:tE.Track({concertId:n.concertId,trackIdx:n.trackIdx})})]],FailedFetchInfo:({errorMessage:n})=>[pr(e,n),[]],SucceededTrackInfoForEnqueue:({concertId:n,trackIdx:r,info:o})=>h.match(o,{onNone:()=>[e,[bE({target:tE.Track({concertId:n,trackIdx:r})})]],onSome:({title:s,liked:i})=>{let a=KS(e.queue,ch(n,r,s,i));return[L(e,{queue:()=>a.queue}),a.added?[Kf()]:[]]}}),ResolvedFirstAvailableTrack:({concertId:n,trackIdx:r})=>h.match(r,{onNone:()=>[e,[bE({target:tE.Track({concertId:n,trackIdx:0})})]],onSome:o=>Ch(e,n,o)}),DrainedQueue:({played:n,skippedCount:r,plan:o})=>{let s=h.isSome(n)?r+1:r,i=L(e,{queue:a=>a.slice(s)});return h.match(n,{onSome:({entry:a,info:c})=>Ah(...zO(i,TE(i,co.Track({concertId:a.concertId,trackIdx:a.trackIdx}),c,{...Wi,playlistName:a.playlistName}))),onNone:()=>o==="queue-only"?[i,[]]:$W(i,o)})},NoNextTrack:({plan:n})=>IE(e,n),FailedNextTrackInfo:({plan:n})=>{let r=n==="next-or-stop"?L(e,{isPlaying:()=>!1}):pr(L(e,{isPlaying:()=>!1}),"Couldn't load next track");return IE(r,n)},FailedPrevTrackInfo:()=>[L(e,{isPlaying:()=>!1}),[]],SucceededPrepareStart:({target:n,seedStatus:r})=>{let o=L(e,{pending:()=>h.some(n),status:()=>Ga.Busy({message:"Preparing\u2026"})}),s=n._tag==="Track"?[LO({concertId:n.concertId,trackIdx:n.trackIdx}),UO({concertId:n.concertId}),$O({concertId:n.concertId})]:[UO({concertId:n.concertId}),$O({concertId:n.concertId})];return[o,[...s,SE({target:n,elapsedMs:0,seedStatus:r})]]},FailedPrepareStart:()=>[pr(e,"Prepare failed"),[]],SucceededPrepareStatus:({target:n,status:r,elapsedMs:o})=>h.match(e.pending,{onNone:()=>[e,[]],onSome:s=>{if(!bO(s,n))return[e,[]];if(n._tag==="Track"&&r.tracks_present[n.trackIdx]===!0&&n._tag==="Track"){let c=L(e,{pending:()=>h.none()}),[u,f]=Ch(c,n.concertId,n.trackIdx);return[u,[Nu(),...f]]}if(r.download==="download-error"||r.split==="split-error")return[L(pr(e,"Preparing tracks failed"),{pending:()=>h.none()}),[Nu()]];if(o>18e5)return[L(pr(e,"Preparing tracks timed out"),{pending:()=>h.none()}),[Nu()]];let a=r.split==="splitting"?"Preparing\u2026 (splitting)":"Preparing\u2026 (downloading)";return[UW(e,a),[SE({target:n,elapsedMs:o,seedStatus:h.none()})]]}}),FailedPollPrepareStatus:({target:n,elapsedMs:r})=>h.match(e.pending,{onNone:()=>[e,[]],onSome:o=>bO(o,n)?r>18e5?[L(pr(e,"Preparing tracks timed out"),{pending:()=>h.none()}),[Nu()]]:[e,[SE({target:n,elapsedMs:r,seedStatus:h.none()})]]:[e,[]]}),CompletedLikeToggle:({concertId:n,trackIdx:r,liked:o})=>[Fu(e,n,r,o),[]],FailedLikeToggle:({concertId:n,trackIdx:r,attempted:o})=>{let s=!o,i=e.playback.concertId===n&&e.playback.trackIdx===r,a=Fu(e,n,r,s);return[i?pr(a,"Like failed"):a,[Th({concertId:n,trackIdx:h.some(r),liked:s})]]},CompletedDeleteTrack:({concertId:n,trackIdx:r,ok:o,source:s})=>{if(!o)return[pr(e,"Delete failed"),[]];if(s==="bar")return e.playback.concertId!==n||e.playback.trackIdx!==r?[e,[]]:WO(e);if(!(h.isSome(e.playback.concert)&&e.playback.concert.value.id===n)){let c=BW(e,n,r,!1);return e.playback.concertId===n&&e.playback.trackIdx===r?WO(c):[c,[]]}let a=e.playback.concertId===n&&e.playback.trackIdx===r;return[e,[FO({concertId:n,advanceAfter:a})]]},SucceededConcertItems:({concertId:n,items:r,advanceAfter:o})=>h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:s=>{if(s.id!==n)return[e,[]];let i=s.items[s.pos]??null,a=pH(r,i?i.url:null,s.pos),c=L(e,{playback:()=>L(e.playback,{concert:()=>h.some(L(s,{items:()=>r,pos:()=>a}))})});return o?KO(c):[c,[]]}}),FailedConcertItems:()=>[e,[]],SucceededConcertPlaybackItems:({concertId:n,items:r,atPos:o})=>KO(L(e,{playback:()=>L(e.playback,{concert:()=>h.some({id:n,items:r,pos:o})})})),FailedConcertPlayback:({errorMessage:n})=>[pr(e,n),[]],CompletedDeleteInterlude:({concertId:n,wasPlayingThis:r})=>h.isSome(e.playback.concert)&&e.playback.concert.value.id===n?[e,[FO({concertId:n,advanceAfter:r})]]:[e,[]],FailedDeleteInterlude:()=>[pr(e,"Delete failed"),[]],SucceededPlaylistTracks:({tracks:n,name:r})=>{if(A.isReadonlyArrayEmpty(n))return[pr(e,"Nothing to play in this playlist"),[]];let o=e.nextGroupId,s=n.map(a=>ch(a.concertId,a.trackIdx,a.title,!1,r,o)),i=L(e,{queue:a=>[...a,...s],nextGroupId:()=>o+1});return LW(i)?[i,[Ru({queue:i.queue,plan:"queue-only"}),Kf()]]:[i,[Kf()]]},FailedPlaylistLoad:()=>[pr(e,"Couldn't load playlist"),[]],SucceededTrackDetails:({concertId:n,loadGen:r,tracksBusy:o,tracks:s})=>e.sidebar.loadGen!==r?[e,[]]:e.playback.concertId!==n?[e,[]]:[L(e,{sidebar:()=>L(e.sidebar,{tracks:()=>h.some({tracksBusy:o,tracks:s})})}),[]],FailedTrackDetails:()=>[e,[]],FailedOpenExternal:()=>[pr(e,"Couldn't open externally"),[]],StartedAudio:()=>[L(e,{isPlaying:()=>!0}),[]],PausedAudio:()=>[L(e,{isPlaying:()=>!1}),[]],UpdatedAudioTime:({currentTime:n,duration:r,loadGen:o})=>o===e.audioLoadGen?[L(e,{audioTime:()=>({currentTime:n,duration:r})}),[]]:[e,[]],EndedAudio:()=>VO(L(e,{playback:()=>L(e.playback,{ended:()=>!0})})),ErroredAudio:()=>VO(pr(L(e,{playback:()=>L(e.playback,{ended:()=>!0})}),"Failed to load media")),RejectedAudioPlay:()=>[pr(L(e,{isPlaying:()=>!1}),"Playback blocked"),[]],SettledHtmxContent:()=>{let{concertId:n,trackIdx:r}=e.playback,o=n!==null?[kE({concertId:n,trackIdx:h.fromNullishOr(r)})]:[],s=h.match(e.pending,{onNone:()=>[],onSome:i=>i._tag==="Track"?[LO({concertId:i.concertId,trackIdx:i.trackIdx})]:[]});return[e,[...o,...s]]},SwappedLikeButton:({concertId:n,trackIdx:r,liked:o})=>[Fu(e,n,r,o),[]],PressedSpace:({audioPaused:n})=>n?[e,[Gf()]]:[e,[Es()]],PressedEscape:()=>e.video.open?[L(e,{video:()=>({open:!1})}),[ks()]]:[e,[]],ClickedOutsideVideo:()=>e.video.open?[L(e,{video:()=>({open:!1})}),[ks()]]:[e,[]],MovedSidebarDrag:({clientX:n})=>[e,[jO({px:n})]],ReleasedSidebarDrag:({clientX:n,moved:r})=>r?[e,[jO({px:n}),NW({px:n})]]:[e,[]],Acked:()=>[e,[]]}));var WW=e=>t=>t==="Enter"?h.some(e):h.none(),xOe=e=>({concertId:e.concertId,trackIdx:e.trackIdx,isVideo:e.isVideo,watchUrl:e.watchUrl,hasNext:e.hasNext,hasPrev:e.hasPrev,liked:e.liked,concert:h.getOrNull(e.concert)}),VW=e=>e?"btn-like liked":"btn-like",GW=(e,t,n)=>{let r=_n();return r.button([r.Class(VW(n)),r.Title("Like"),r.AriaLabel("Like"),r.AriaPressed(n?"true":"false"),r.OnClick(it({command:se.SidebarLikeTrack({concertId:e,trackIdx:t})}))],[n?"\u2605":"\u2606"])},KW=(e,t,n)=>{let r=_n();return r.button([r.Class("btn-delete"),r.Title("Delete track files"),r.AriaLabel(`Delete files for ${n}`),r.OnClick(it({command:se.SidebarDeleteTrack({concertId:e,trackIdx:t})}))],[r.span([r.Class("icon-trash")],[])])},JW=(e,t,n)=>{let r=_n();return r.button([r.Class("btn-add-pl"),r.Title("Add to playlist"),r.AriaLabel(`Add ${n} to playlist`),r.OnClick(it({command:se.SidebarAddToPlaylist({concertId:e,trackIdx:t,label:n})}))],["+"])},yOe=(e,t,n,r,o)=>{let s=_n();return s.keyed("li")(`track-${n}`,[s.Class(o?"concert-item concert-item-playing":"concert-item")],[GW(r,n,e.liked),s.button([s.Class(o?"btn-track-listen playing":"btn-track-listen"),s.Attribute("data-concert-id",String(r)),s.Attribute("data-track-idx",String(n)),s.OnClick(it({command:se.PlayConcertFrom({concertId:r,pos:t})}))],[e.title]),KW(r,n,e.title),JW(r,n,e.title)])},bOe=(e,t,n,r)=>{let o=_n(),s=e.interlude_index??0;return o.keyed("li")(`interlude-${s}`,[o.Class(r?"concert-item concert-item-interlude concert-item-playing":"concert-item concert-item-interlude")],[o.button([o.Class(r?"btn-track-listen btn-interlude playing":"btn-track-listen btn-interlude"),o.Attribute("data-concert-id",String(n)),o.Attribute("data-interlude-idx",String(s)),o.OnClick(it({command:se.PlayConcertFrom({concertId:n,pos:t})}))],[e.title]),o.button([o.Class("btn-delete"),o.Title("Delete interlude file"),o.AriaLabel(`Delete interlude file for ${e.title}`),o.OnClick(it({command:se.SidebarDeleteInterlude({concertId:n,interludeIdx:s})}))],[o.span([o.Class("icon-trash")],[])])])};function SOe(e,t){let n=_n();return n.ol([n.Class("track-list track-list-concert-playback")],e.items.map((r,o)=>{let s=o===e.pos,i=r.kind==="interlude",a=r.track_index??null;return!i&&a!==null?yOe(r,o,a,t,s):bOe(r,o,t,s)}))}var EOe=(e,t,n,r)=>{let o=_n();return o.keyed("li")(`avail-${e.index}`,[o.Class(n?"concert-item concert-item-playing":"concert-item")],[GW(t,e.index,e.liked),o.button([o.Class(n?"btn-track-listen playing":"btn-track-listen"),o.Attribute("data-concert-id",String(t)),o.Attribute("data-track-idx",String(e.index)),o.Disabled(r),o.OnClick(it({command:se.PlayTrack({concertId:t,trackIdx:e.index})}))],[e.title]),...e.is_video?[o.button([o.Class("btn-watch"),o.OnClick(it({command:se.WatchTrackDirect({concertId:t,trackIdx:e.index})}))],["Watch"])]:[],KW(t,e.index,e.title),JW(t,e.index,e.title)])},kOe=(e,t,n)=>{let r=_n();return r.keyed("li")(`unavail-${e.index}`,[r.Class("concert-item track-unavailable")],[r.button([r.Class("btn-track-listen track-title-unavailable"),r.Attribute("data-concert-id",String(t)),r.Attribute("data-track-idx",String(e.index)),r.Disabled(n),r.OnClick(it({command:se.PlayTrack({concertId:t,trackIdx:e.index})}))],[e.title])])};function wOe(e,t,n){let r=_n(),{tracksBusy:o,tracks:s}=e;return r.ol([r.Class("track-list")],s.map(i=>{let a=i.index===n;return i.available?EOe(i,t,a,o):kOe(i,t,o)}))}function TOe(e){let t=_n(),n=t.keyed("li"),{rows:r}=uH(e.queue);return t.section([t.Id("sidebar-queue-section")],[t.h2([],["Queue"]),t.ol([t.Id("sidebar-queue-list"),t.Class("track-list")],r.map(o=>o.kind==="group-header"?n(`group-${o.groupId}`,[t.Class("queue-group-header")],[t.span([t.Class("queue-group-name")],[o.name]),t.button([t.Class("btn-remove-group"),t.AriaLabel(`Remove ${o.name} from queue`),t.OnClick(it({command:se.RemoveGroup({groupId:o.groupId})}))],["\xD7"])]):n(`song-${o.entry.groupId??"solo"}-${o.entry.concertId}-${o.entry.trackIdx}`,[t.Class(o.nested?"queue-song queue-song-nested":"queue-song")],[t.button([t.Class("btn-remove-queue"),t.AriaLabel(`Remove ${o.entry.title} from queue`),t.OnClick(it({command:se.Dequeue({pos:o.pos})}))],["\xD7"]),t.button([t.Class("btn-play-queue"),t.OnClick(it({command:se.PlayQueueEntryNow({pos:o.pos})}))],[o.entry.title])]))),t.p([t.Id("sidebar-queue-empty"),t.Style({display:r.length===0?"":"none"})],["Nothing queued"])])}function IOe(e){let t=_n(),n=e.playback.concertId;if(n===null)return t.section([t.Id("sidebar-concert-section")],[]);let r=h.match(e.playback.concert,{onSome:o=>SOe(o,n),onNone:()=>h.match(e.sidebar.tracks,{onSome:o=>wOe(o,n,e.playback.trackIdx),onNone:()=>t.p([t.Class("sidebar-loading")],["Loading\u2026"])})});return t.section([t.Id("sidebar-concert-section")],[t.h2([t.Id("sidebar-concert-heading")],["Now playing"]),r])}function vOe(e){let t=_n(),n=e.playback,r=n.concertId!==null,o=r&&n.trackIdx!==null,s=xOe(n),i=e.queue.length,a=r&&JS(s,i),c=r&&ZS(s),u=e.status._tag==="Error"?e.status.message:"",f=e.status._tag==="Busy"?e.status.message:"";return t.div([t.Id("player-bar"),...r?[t.Class("active")]:[]],[t.button([t.Id("player-queue-toggle"),t.AriaLabel("Toggle queue and tracks sidebar"),t.AriaExpanded(e.sidebar.open),t.Title("Show queue and tracks"),t.OnClick(it({command:se.ToggleSidebar()}))],["\u2630",t.span([t.Id("player-queue-badge"),t.Style({visibility:i>0?"visible":"hidden"}),t.Title(i>0?e.queue.map(d=>d.title).join(`
-`):"")],[i>0?String(i):""])]),t.div([t.Id("player-info")],[t.span([t.Class("player-title-line")],[t.button([t.Id("player-like"),t.Class(VW(n.liked)),t.Title("Like"),t.AriaLabel("Like"),t.AriaPressed(n.liked?"true":"false"),t.Style({display:o?"":"none"}),t.OnClick(it({command:se.ToggleLike()}))],[n.liked?"\u2605":"\u2606"]),t.button([t.Id("player-add-pl"),t.Title("Add to playlist"),t.AriaLabel("Add to playlist"),t.Style({display:o?"":"none"}),t.OnClick(it({command:se.AddToPlaylist()}))],["+"]),t.span([t.Id("player-track"),t.Role("button"),t.Tabindex(0),t.AriaLabel("Toggle queue and tracks sidebar"),t.Style({display:o&&n.trackIdx!==null?"inline-block":"none"}),t.OnClick(it({command:se.ToggleSidebar()})),t.OnKeyDownPreventDefault(WW(it({command:se.ToggleSidebar()})))],[o&&n.trackIdx!==null?`#${n.trackIdx+1}`:""]),t.span([t.Id("player-title"),t.Role("button"),t.Tabindex(0),t.AriaLabel("Toggle queue and tracks sidebar"),t.OnClick(it({command:se.ToggleSidebar()})),t.OnKeyDownPreventDefault(WW(it({command:se.ToggleSidebar()})))],[n.title])]),t.a([t.Id("player-artist"),t.Attribute("hx-boost","false"),t.Attribute("onclick","Player.openConcert(event)"),t.Href(r?`/concerts/${n.concertId}`:"#"),t.Attribute("hx-target","#content"),t.Attribute("hx-select","#content"),t.Attribute("hx-swap","outerHTML show:window:top"),t.Attribute("hx-push-url","true"),t.Title("View concert")],[n.artist]),t.span([t.Id("player-playlist"),t.Style({display:n.playlistLabel!==null?"":"none"})],[n.playlistLabel??""])]),t.span([t.Id("player-error"),t.Role("alert"),t.Style({display:u?"inline":"none"})],[u]),t.span([t.Id("player-status"),t.AriaLive("polite"),t.Style({display:f?"inline":"none"})],[f]),t.button([t.Id("player-watch"),t.Title("Watch video in player"),t.Style({display:n.isVideo?"inline-block":"none"}),t.OnClick(it({command:se.Watch()}))],["Watch"]),t.button([t.Id("player-open"),t.Title("Open in system player"),t.AriaLabel("Open in system player"),t.Style({display:n.watchUrl!==null?"inline-block":"none"}),t.OnClick(it({command:se.OpenExternal()}))],["\u229E"]),t.button([t.Id("player-delete"),t.Title("Delete this track"),t.AriaLabel("Delete this track"),t.Style({display:o&&!n.liked?"inline-block":"none"}),t.OnClick(it({command:se.DeleteTrack()}))],[t.span([t.Class("icon-trash")],[])]),t.button([t.Id("player-prev"),t.Title("Previous track"),t.AriaLabel("Previous track"),t.Disabled(!c),t.OnClick(it({command:se.SkipToPrev()}))],["\u23EE"]),t.button([t.Id("player-play-pause"),t.AriaLabel(e.isPlaying?"Pause":"Play"),t.OnClick(it({command:se.TogglePause()}))],[e.isPlaying?"\u23F8":"\u25B6"]),t.button([t.Id("player-next"),t.Title("Next track"),t.AriaLabel("Next track"),t.Disabled(!a),t.OnClick(it({command:se.SkipToNext()}))],["\u23ED"]),t.input([t.Id("player-seek"),t.Type("range"),t.AriaLabel("Seek"),t.Min("0"),t.Max(String(Math.ceil(e.audioTime.duration))),t.Value(String(e.audioTime.currentTime)),t.Step("1"),t.Disabled(e.audioTime.duration<=0),t.OnInput(d=>{let g=Number(d),y=Number.isFinite(g)?g:e.audioTime.currentTime;return it({command:se.Seek({seconds:y})})})]),t.span([t.Id("player-time")],[`${lO(e.audioTime.currentTime)} / ${lO(e.audioTime.duration)}`])])}function AOe(e){let t=_n();return t.aside([t.Id("player-sidebar")],[t.button([t.Id("sidebar-close"),t.Type("button"),t.Title("Close"),t.AriaLabel("Close sidebar"),t.OnClick(it({command:se.CloseSidebar()}))],["\xD7"]),t.div([t.Id("sidebar-resize"),t.AriaHidden(!0)],[]),t.div([t.Class("sidebar-top-spacer")],[]),TOe(e),IOe(e)])}var ZW=e=>_n().div([],[vOe(e),AOe(e)]);var COe=()=>[xW,[RW()]],QW=e=>qf.makeElement({Model:gW,Flags:yW,flags:m.succeed({}),init:COe,update:HW,view:ZW,subscriptions:FW,ports:YS,container:e});var YW=document.getElementById("player-root"),OOe=YW?qf.embed(QW(YW)):null,bt=e=>{OOe?.ports.command.send(e)};window.Player={playAlbum(e,t){return bt(se.PlayAlbum({concertId:t})),Promise.resolve()},playTrack(e,t,n){return bt(se.PlayTrack({concertId:t,trackIdx:n})),Promise.resolve()},playTracks(e,t){return bt(se.PlayTracks({concertId:t})),Promise.resolve()},startAlbum(e,t,n=!0){return bt(se.StartAlbum({concertId:t,recordListen:n})),Promise.resolve(!0)},startTrack(e,t,n){return bt(se.StartTrack({concertId:t,trackIdx:n})),Promise.resolve(!0)},togglePause(){bt(se.TogglePause())},seek(e){let t=typeof e=="string"?parseFloat(e):e;isNaN(t)||bt(se.Seek({seconds:t}))},skipToNext(){return bt(se.SkipToNext()),Promise.resolve()},skipToPrev(){return bt(se.SkipToPrev()),Promise.resolve()},watch(){bt(se.Watch())},openExternal(){return bt(se.OpenExternal()),Promise.resolve()},watchTrackDirect(e,t,n){return bt(se.WatchTrackDirect({concertId:t,trackIdx:n})),Promise.resolve()},toggleLike(){return bt(se.ToggleLike()),Promise.resolve()},deleteTrack(){return bt(se.DeleteTrack()),Promise.resolve()},openConcert(e){if(!(e instanceof MouseEvent)||!(e.currentTarget instanceof HTMLAnchorElement))return;let t=e.currentTarget;if(gH(e,t))return;let n=t.getAttribute("href");!n||n==="#"||!window.htmx||(e.preventDefault(),window.htmx.ajax("GET",n,{source:t}))},openSidebar(){document.body.classList.add("sidebar-open"),bt(se.OpenSidebar())},closeSidebar(){document.body.classList.remove("sidebar-open"),bt(se.CloseSidebar())},toggleSidebar(){bt(se.ToggleSidebar())},sidebarDeleteTrack(e,t){return bt(se.SidebarDeleteTrack({concertId:e,trackIdx:t})),Promise.resolve()},playQueueEntryNow(e){bt(se.PlayQueueEntryNow({pos:e}))},dequeue(e){bt(se.Dequeue({pos:e}))},enqueue(e,t,n,r){bt(se.Enqueue({concertId:e,trackIdx:t,title:n,liked:r}))},playAlbumAt(e,t){return bt(se.PlayAlbumAt({concertId:e,seconds:t})),Promise.resolve()},nowPlaying(){return SH()},playPlaylist(e){return bt(se.PlayPlaylist({playlistId:e})),Promise.resolve()},addToPlaylist(){bt(se.AddToPlaylist())},stopPlayback(){bt(se.StopPlayback())},playConcert(e){return bt(se.PlayConcert({concertId:e})),Promise.resolve()},playConcertFrom(e,t){return bt(se.PlayConcertFrom({concertId:e,pos:t})),Promise.resolve()},sidebarDeleteInterlude(e,t){return bt(se.SidebarDeleteInterlude({concertId:e,interludeIdx:t})),Promise.resolve()}};})();
+Starting without HMR support.`),m.succeed(void 0)))):m.succeed(void 0)},oH=e=>{FS.runMain(nH(m.flatMap(rH(e.runtimeId),e.start)))},GCe=(e,t)=>{let n={};return Z.isNotUndefined(e)&&(Object.entries(e.inbound??{}).forEach(([r,o])=>{n[r]={send:s=>t.sendInbound(r,o,s)}}),Object.entries(e.outbound??{}).forEach(([r,o])=>{n[r]={subscribe:s=>t.addListener(o,s)}})),n},sH=e=>{let t=Xz.get(e);if(Z.isUndefined(t))throw new Error("[foldkit] embed expects a program created by makeApplication or makeElement.");let n=t;if(n.isEmbedActive)throw new Error("[foldkit] This program is already embedded. Dispose the existing handle first, or create a separate program: each program owns one container.");n.isEmbedActive=!0;let r=FCe(),o=ke(h.match(n.maybeActiveFiber,{onNone:()=>m.void,onSome:u=>m.asVoid(Xr.await(u))}),m.andThen(rH(e.runtimeId)),m.flatMap(u=>n.startWith(h.some(r),u))),s=m.runFork(nH(o));n.maybeActiveFiber=h.some(s);let i=!1,a=()=>{i||(i=!0,r.dispose(),n.isEmbedActive=!1,m.runFork(Xr.interrupt(s)))};return{ports:GCe(e.ports,r),dispose:a}};var VS={};We(VS,{aggregate:()=>tz,animationFrame:()=>iH,lift:()=>nz,make:()=>ez,persistent:()=>PS});var ZCe=e=>B.callback(t=>m.acquireRelease(m.sync(()=>{let n={frameId:0,lastTime:performance.now()},r=o=>{let s=o-n.lastTime;n.lastTime=o,on.offerUnsafe(t,e(s)),n.frameId=requestAnimationFrame(r)};return n.frameId=requestAnimationFrame(r),n}),n=>m.sync(()=>cancelAnimationFrame(n.frameId))).pipe(m.flatMap(()=>m.never))),iH=e=>({dependenciesSchema:p.Struct({isActive:p.Boolean}),modelToDependencies:t=>({isActive:e.isActive(t)}),dependenciesToStream:({isActive:t})=>B.when(ZCe(e.toMessage),m.sync(()=>t))});var cO="sidebarWidth";var aH='a, button, input, select, textarea, label, [role="button"], [onclick]';function GS(e){return Math.max(240,Math.min(600,Math.round(e)))}function ch(e,t,n,r,o=null,s=null){return{concertId:e,trackIdx:t,title:n,liked:!!r,playlistName:o||null,groupId:s||null}}function KS(e,t){return e.some(n=>n.concertId===t.concertId&&n.trackIdx===t.trackIdx)?{queue:[...e],added:!1}:{queue:[...e,t],added:!0}}function uO(e,t){let n=[...e];return n.splice(t,1),n}function cH(e,t){return e.filter(n=>n.groupId!==t)}function uH(e){let t=[],n=[],r,o=new Set;for(let s=e.length-1;s>=0;s--){let i=e[s];i.groupId!==null&&i.groupId!==r?(o.has(i.groupId)&&n.push(i.groupId),o.add(i.groupId),r=i.groupId,t.push({kind:"group-header",groupId:i.groupId,name:i.playlistName||"Playlist"})):i.groupId===null&&(r=null),t.push({kind:"song",pos:s,entry:i,nested:i.groupId!==null})}return{rows:t,nonContiguousGroups:n}}function JS(e,t){return e.concert?e.concert.pos+1<e.concert.items.length:t>0||e.hasNext}function ZS(e){return e.concert?e.concert.pos>0:e.hasPrev}function lH(e,t){return{hasPrev:t>0,hasNext:t+1<e.length,item:e[t]??null}}function fH(e,t){let n=e+1;return n>=t?null:n}function pH(e,t,n){if(!t)return n;let r=e.findIndex(o=>o.url===t);return r>=0?r:n}function lO(e){let t=Math.floor(e/60),n=Math.floor(e%60);return t+":"+(n<10?"0":"")+n}function dH(e,t){return!(!t||!e||!(e instanceof Node)||t.contains(e)||e instanceof Element&&e.closest&&e.closest(aH))}function mH(e){return(e.code==="Space"||e.key===" "||e.key==="Spacebar")&&!e.ctrlKey&&!e.metaKey&&!e.altKey&&!e.shiftKey}function hH(e){return(e.code==="Escape"||e.key==="Escape"||e.key==="Esc")&&!e.ctrlKey&&!e.metaKey&&!e.altKey&&!e.shiftKey}function gH(e,t){return!!(e.button!==0||e.metaKey||e.ctrlKey||e.shiftKey||e.altKey||e.defaultPrevented||t.target!==""&&t.target!=="_self"||t.hasAttribute("download"))}function QS(e){if(!(e instanceof HTMLElement))return!1;if(e.isContentEditable||e.matches("input, textarea, select"))return!0;let t=e.closest("[contenteditable]");return!!(t&&t.isContentEditable)}function QCe(e){return!(e instanceof Element)||QS(e)?!1:!!e.closest("#player-bar, #player-video-panel")}function xH(e){return!(e instanceof Element)||QCe(e)?!1:QS(e)?!0:!!e.closest(aH)}var yH={concertId:null,trackIdx:null};function bH(e){yH=e}function SH(){return yH}var EH=$("PlayAlbum",{concertId:p.Number}),kH=$("PlayTrack",{concertId:p.Number,trackIdx:p.Number}),wH=$("PlayTracks",{concertId:p.Number}),TH=$("StartAlbum",{concertId:p.Number,recordListen:p.Boolean}),IH=$("StartTrack",{concertId:p.Number,trackIdx:p.Number}),vH=$("TogglePause"),AH=$("Seek",{seconds:p.Number}),CH=$("SkipToNext"),OH=$("SkipToPrev"),MH=$("Watch"),PH=$("OpenExternal"),_H=$("WatchTrackDirect",{concertId:p.Number,trackIdx:p.Number}),RH=$("ToggleLike"),NH=$("DeleteTrack"),FH=$("OpenSidebar"),DH=$("CloseSidebar"),LH=$("ToggleSidebar"),UH=$("SidebarDeleteTrack",{concertId:p.Number,trackIdx:p.Number}),$H=$("PlayQueueEntryNow",{pos:p.Number}),BH=$("Dequeue",{pos:p.Number}),jH=$("RemoveGroup",{groupId:p.Number}),qH=$("Enqueue",{concertId:p.Number,trackIdx:p.Number,title:p.String,liked:p.Boolean}),zH=$("PlayAlbumAt",{concertId:p.Number,seconds:p.Number}),HH=$("PlayPlaylist",{playlistId:p.Number}),WH=$("AddToPlaylist"),VH=$("StopPlayback"),GH=$("PlayConcert",{concertId:p.Number}),KH=$("PlayConcertFrom",{concertId:p.Number,pos:p.Number}),JH=$("SidebarDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number}),ZH=$("SidebarLikeTrack",{concertId:p.Number,trackIdx:p.Number}),QH=$("SidebarAddToPlaylist",{concertId:p.Number,trackIdx:p.Number,label:p.String}),fO=p.Union([EH,kH,wH,TH,IH,vH,AH,CH,OH,MH,PH,_H,RH,NH,FH,DH,LH,UH,$H,BH,jH,qH,zH,HH,WH,VH,GH,KH,JH,ZH,QH]),se={PlayAlbum:EH,PlayTrack:kH,PlayTracks:wH,StartAlbum:TH,StartTrack:IH,TogglePause:vH,Seek:AH,SkipToNext:CH,SkipToPrev:OH,Watch:MH,OpenExternal:PH,WatchTrackDirect:_H,ToggleLike:RH,DeleteTrack:NH,OpenSidebar:FH,CloseSidebar:DH,ToggleSidebar:LH,SidebarDeleteTrack:UH,PlayQueueEntryNow:$H,Dequeue:BH,RemoveGroup:jH,Enqueue:qH,PlayAlbumAt:zH,PlayPlaylist:HH,AddToPlaylist:WH,StopPlayback:VH,PlayConcert:GH,PlayConcertFrom:KH,SidebarDeleteInterlude:JH,SidebarLikeTrack:ZH,SidebarAddToPlaylist:QH},YS={inbound:{command:Bf.inbound(fO)}};function pO(e){return e.mode==="source"}var XS=class extends Error{constructor(t,n){super(n??`HTTP ${t}`),this.name="ApiError",this.status=t}};async function dO(e){return await e.json()}async function Pu(e,t){let n=await fetch(e,t);if(!n.ok)throw new XS(n.status);return dO(n)}async function YCe(e,t){let n=await fetch(e,t);return n.ok?dO(n):null}async function XCe(e,t){let n=await fetch(e,t);if(n.status===404)return null;if(!n.ok)throw new XS(n.status);return dO(n)}async function uh(e,t,n="POST"){let r={method:n};return t!==void 0&&(r.headers={"Content-Type":"application/json"},r.body=JSON.stringify(t)),fetch(e,r)}async function YH(e){return Pu(`/api/playlists/${e}`)}async function XH(e){return Pu(`/concerts/${e}/media-info`)}async function lh(e,t){return YCe(`/concerts/${e}/tracks/${t}/media-info`)}async function eW(e,t,n){return Pu(`/concerts/${e}/tracks/${t}/next-media-info`,{signal:n??null})}async function tW(e,t,n){return XCe(`/concerts/${e}/tracks/${t}/next-media-info`,{signal:n??null})}async function nW(e,t,n){return Pu(`/concerts/${e}/tracks/${t}/prev-media-info`,{signal:n??null})}async function mO(e){return Pu(`/concerts/${e}/concert-playback`)}async function rW(e){return uh(`/concerts/${e}/prepare`,void 0,"POST")}async function oW(e){return Pu(`/concerts/${e}/prepare-status`)}async function sW(e,t){return uh(`/concerts/${e}/tracks/${t}/like`,void 0,"POST")}async function iW(e,t){return uh(`/concerts/${e}/tracks/${t}/delete`,void 0,"POST")}async function aW(e,t){return uh(`/concerts/${e}/interludes/${t}/delete`,void 0,"POST")}async function cW(e){return Pu(`/concerts/${e}/track-details`)}async function hO(e){return uh(e,void 0,"POST")}function ao(e){return document.getElementById(e)}function ei(e,t){let n=ao(e);if(n===null)return null;if(!(n instanceof t))throw new Error(`expected #${e} to be a ${t.name}`);return n}var gO=p.Struct({index:p.Number,title:p.String,available:p.Boolean,is_video:p.Boolean,liked:p.Boolean}),eOe=p.Struct({tracksBusy:p.Boolean,tracks:p.mutable(p.Array(gO))}),xO=p.Struct({artist:p.String,has_next:p.Boolean,has_prev:p.Boolean,hls_url:p.optionalKey(p.NullOr(p.String)),is_video:p.Boolean,liked:p.Boolean,playable:p.Boolean,poster_url:p.optionalKey(p.NullOr(p.String)),title:p.String,track_index:p.optionalKey(p.NullOr(p.Number)),url:p.String}),zf=p.Struct({download:p.String,split:p.String,split_queued:p.Boolean,tracks_present:p.mutable(p.Array(p.Boolean))}),eE=p.Struct({artist:p.String,hls_url:p.optionalKey(p.NullOr(p.String)),interlude_index:p.optionalKey(p.NullOr(p.Number)),is_video:p.Boolean,kind:p.String,liked:p.Boolean,poster_url:p.optionalKey(p.NullOr(p.String)),title:p.String,track_index:p.optionalKey(p.NullOr(p.Number)),url:p.String}),fh=p.Struct({concertId:p.Number,trackIdx:p.Number,title:p.String,liked:p.Boolean,playlistName:p.NullOr(p.String),groupId:p.NullOr(p.Number)}),tOe=p.Struct({id:p.Number,items:p.mutable(p.Array(eE)),pos:p.Number}),nOe=p.Struct({concertId:p.NullOr(p.Number),trackIdx:p.NullOr(p.Number),title:p.String,artist:p.String,isVideo:p.Boolean,watchUrl:p.NullOr(p.String),hasNext:p.Boolean,hasPrev:p.Boolean,liked:p.Boolean,posterUrl:p.NullOr(p.String),ended:p.Boolean,concert:p.OptionFromNullOr(tOe),playlistLabel:p.NullOr(p.String)}),yO={concertId:null,trackIdx:null,title:"",artist:"",isVideo:!1,watchUrl:null,hasNext:!1,hasPrev:!1,liked:!1,posterUrl:null,ended:!1,concert:h.none(),playlistLabel:null},uW=$("Track",{concertId:p.Number,trackIdx:p.Number}),Hi=uW,tE={Track:uW},bO=(e,t)=>e.concertId===t.concertId&&e.trackIdx===t.trackIdx,lW=$("Track",{concertId:p.Number,trackIdx:p.Number}),fW=$("Album",{concertId:p.Number}),pW=$("ConcertItem",{concertId:p.Number,trackIdx:p.NullOr(p.Number),isInterlude:p.Boolean}),ph=p.Union([lW,fW,pW]),co={Track:lW,Album:fW,ConcertItem:pW},dh=p.Struct({recordListen:p.Boolean,playlistName:p.NullOr(p.String),openVideoPanel:p.Boolean}),Wi={recordListen:!0,playlistName:null,openVideoPanel:!1},_u=p.Literals(["queue-only","next-or-none","next-or-stop","next-or-collapse"]),dW=$("Idle"),mW=$("Busy",{message:p.String}),hW=$("Error",{message:p.String}),rOe=p.Union([dW,mW,hW]),Ga={Idle:dW,Busy:mW,Error:hW},gW=p.Struct({playback:nOe,queue:p.Array(fh),nextGroupId:p.Number,pending:p.Option(Hi),sidebar:p.Struct({open:p.Boolean,tracks:p.Option(eOe),loadGen:p.Number}),video:p.Struct({open:p.Boolean}),isPlaying:p.Boolean,pendingSeek:p.Option(p.Number),status:rOe,audioTime:p.Struct({currentTime:p.Number,duration:p.Number}),audioLoadGen:p.Number}),nE={currentTime:0,duration:0},xW={playback:yO,queue:[],nextGroupId:1,pending:h.none(),sidebar:{open:!1,tracks:h.none(),loadGen:0},video:{open:!1},isPlaying:!1,pendingSeek:h.none(),status:Ga.Idle(),audioTime:nE,audioLoadGen:0},yW=p.Struct({});var it=Ie("CommandReceived",{command:fO}),No=Ie("SucceededMediaInfo",{source:ph,info:xO,opts:dh}),Ka=Ie("NotPlayable",{source:ph,url:p.String}),rE=Ie("TrackMissing",{source:ph}),Hf=Ie("FailedFetchInfo",{source:ph,errorMessage:p.String}),mh=Ie("SucceededTrackInfoForEnqueue",{concertId:p.Number,trackIdx:p.Number,info:p.Option(p.Struct({title:p.String,liked:p.Boolean}))}),hh=Ie("ResolvedFirstAvailableTrack",{concertId:p.Number,trackIdx:p.Option(p.Number)}),gh=Ie("DrainedQueue",{played:p.Option(p.Struct({entry:fh,info:xO})),skippedCount:p.Number,plan:_u}),oE=Ie("NoNextTrack",{plan:_u}),sE=Ie("FailedNextTrackInfo",{plan:_u}),iE=Ie("FailedPrevTrackInfo"),aE=Ie("SucceededPrepareStart",{target:Hi,seedStatus:p.Option(zf)}),xh=Ie("FailedPrepareStart",{target:Hi}),yh=Ie("SucceededPrepareStatus",{target:Hi,status:zf,elapsedMs:p.Number}),cE=Ie("FailedPollPrepareStatus",{target:Hi,elapsedMs:p.Number}),uE=Ie("CompletedLikeToggle",{concertId:p.Number,trackIdx:p.Number,liked:p.Boolean}),bh=Ie("FailedLikeToggle",{concertId:p.Number,trackIdx:p.Number,attempted:p.Boolean}),Wf=Ie("CompletedDeleteTrack",{concertId:p.Number,trackIdx:p.Number,ok:p.Boolean,source:p.Literals(["bar","sidebar"])}),lE=Ie("SucceededConcertItems",{concertId:p.Number,items:p.mutable(p.Array(eE)),advanceAfter:p.Boolean}),Sh=Ie("FailedConcertItems",{concertId:p.Number}),fE=Ie("SucceededConcertPlaybackItems",{concertId:p.Number,items:p.mutable(p.Array(eE)),atPos:p.Number}),Eh=Ie("FailedConcertPlayback",{concertId:p.Number,errorMessage:p.String}),pE=Ie("CompletedDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number,wasPlayingThis:p.Boolean}),kh=Ie("FailedDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number}),oOe=p.Struct({concertId:p.Number,trackIdx:p.Number,title:p.String}),dE=Ie("SucceededPlaylistTracks",{playlistId:p.Number,name:p.String,tracks:p.Array(oOe)}),mE=Ie("FailedPlaylistLoad",{playlistId:p.Number}),hE=Ie("SucceededTrackDetails",{concertId:p.Number,loadGen:p.Number,tracksBusy:p.Boolean,tracks:p.mutable(p.Array(gO))}),gE=Ie("FailedTrackDetails",{concertId:p.Number,loadGen:p.Number}),xE=Ie("FailedOpenExternal"),SO=Ie("StartedAudio"),EO=Ie("PausedAudio"),kO=Ie("UpdatedAudioTime",{currentTime:p.Number,duration:p.Number,loadGen:p.Number}),wO=Ie("EndedAudio"),TO=Ie("ErroredAudio"),Vi=Ie("RejectedAudioPlay"),IO=Ie("SettledHtmxContent"),vO=Ie("SwappedLikeButton",{concertId:p.Number,trackIdx:p.Number,liked:p.Boolean}),AO=Ie("PressedSpace",{audioPaused:p.Boolean}),CO=Ie("PressedEscape"),OO=Ie("ClickedOutsideVideo"),MO=Ie("MovedSidebarDrag",{clientX:p.Number}),PO=Ie("ReleasedSidebarDrag",{clientX:p.Number,moved:p.Boolean}),de=Ie("Acked"),xLe=p.Union([it,No,Ka,rE,Hf,mh,hh,gh,oE,sE,iE,aE,xh,yh,cE,uE,bh,Wf,lE,Sh,fE,Eh,pE,kh,dE,mE,hE,gE,xE,SO,EO,kO,wO,TO,Vi,IO,vO,AO,CO,OO,MO,PO,de]);var yE=Oe.define("FetchAlbumInfo",{concertId:p.Number,opts:dh},No,Ka,Hf)(({concertId:e,opts:t})=>m.tryPromise(()=>XH(e)).pipe(m.map(n=>n.playable?No({source:co.Album({concertId:e}),info:n,opts:t}):Ka({source:co.Album({concertId:e}),url:n.url})),m.catch(()=>m.succeed(Hf({source:co.Album({concertId:e}),errorMessage:"Couldn't load album"}))))),Vf=Oe.define("FetchTrackInfo",{concertId:p.Number,trackIdx:p.Number,opts:dh},No,Ka,rE,Hf)(({concertId:e,trackIdx:t,opts:n})=>{let r=co.Track({concertId:e,trackIdx:t});return m.tryPromise(()=>lh(e,t)).pipe(m.map(o=>o===null?rE({source:r}):o.playable?No({source:r,info:o,opts:n}):Ka({source:r,url:o.url})),m.catch(()=>m.succeed(Hf({source:r,errorMessage:"Couldn't load track"}))))}),bW=Oe.define("FetchTrackInfoForEnqueue",{concertId:p.Number,trackIdx:p.Number},mh)(({concertId:e,trackIdx:t})=>m.tryPromise(()=>lh(e,t)).pipe(m.map(n=>mh({concertId:e,trackIdx:t,info:n===null?h.none():h.some({title:n.title,liked:!!n.liked})})),m.catch(()=>m.succeed(mh({concertId:e,trackIdx:t,info:h.none()}))))),SW=Oe.define("ResolveFirstAvailableTrack",{concertId:p.Number},hh)(({concertId:e})=>m.gen(function*(){if(yield*m.tryPromise(()=>lh(e,0)).pipe(m.catch(()=>m.succeed(null))))return hh({concertId:e,trackIdx:h.some(0)});let r=(yield*m.tryPromise(()=>eW(e,0)).pipe(m.catch(()=>m.succeed(null))))?.track_index??null;return hh({concertId:e,trackIdx:r===null?h.none():h.some(r)})})),Ru=Oe.define("DrainQueue",{queue:p.Array(fh),plan:_u},gh)(({queue:e,plan:t})=>m.gen(function*(){for(let n=0;n<e.length;n++){let r=e[n],o=yield*m.tryPromise(()=>lh(r.concertId,r.trackIdx)).pipe(m.catch(()=>m.succeed(null)));if(o&&o.playable)return gh({played:h.some({entry:r,info:o}),skippedCount:n,plan:t})}return gh({played:h.none(),skippedCount:e.length,plan:t})})),EW=Oe.define("FetchNextTrackInfo",{concertId:p.Number,trackIdx:p.Number,plan:_u},No,oE,sE)(({concertId:e,trackIdx:t,plan:n})=>m.tryPromise(()=>tW(e,t)).pipe(m.map(r=>r===null?oE({plan:n}):No({source:co.Track({concertId:e,trackIdx:r.track_index??t}),info:r,opts:{recordListen:!0,playlistName:null,openVideoPanel:!1}})),m.catch(()=>m.succeed(sE({plan:n}))))),kW=Oe.define("FetchPrevTrackInfo",{concertId:p.Number,trackIdx:p.Number},No,iE)(({concertId:e,trackIdx:t})=>m.tryPromise(()=>nW(e,t)).pipe(m.map(n=>No({source:co.Track({concertId:e,trackIdx:n.track_index??t}),info:n,opts:{recordListen:!0,playlistName:null,openVideoPanel:!1}})),m.catch(()=>m.succeed(iE())))),bE=Oe.define("PostPrepare",{target:Hi},aE,xh)(({target:e})=>m.gen(function*(){let t=yield*m.tryPromise(()=>rW(e.concertId));if(!t.ok)return xh({target:e});let n=yield*m.tryPromise(()=>t.json()).pipe(m.map(r=>{let o=p.decodeUnknownExit(zf)(r);return o._tag==="Success"?h.some(o.value):h.none()}),m.catch(()=>m.succeed(h.none())));return aE({target:e,seedStatus:n})}).pipe(m.catch(()=>m.succeed(xh({target:e}))))),SE=Oe.define("PollPrepareStatus",{target:Hi,elapsedMs:p.Number,seedStatus:p.Option(zf)},yh,cE)(({target:e,elapsedMs:t,seedStatus:n})=>h.match(n,{onSome:r=>m.succeed(yh({target:e,status:r,elapsedMs:t})),onNone:()=>{let r=e.concertId;return m.sleep(2e3).pipe(m.flatMap(()=>m.tryPromise(()=>oW(r))),m.map(o=>yh({target:e,status:o,elapsedMs:t+2e3})),m.catch(()=>m.succeed(cE({target:e,elapsedMs:t+2e3}))))}})),RO=Oe.define("ToggleLikeRequest",{concertId:p.Number,trackIdx:p.Number,next:p.Boolean},uE,bh)(({concertId:e,trackIdx:t,next:n})=>m.tryPromise(()=>sW(e,t)).pipe(m.map(r=>r.ok?uE({concertId:e,trackIdx:t,liked:n}):bh({concertId:e,trackIdx:t,attempted:n})),m.catch(()=>m.succeed(bh({concertId:e,trackIdx:t,attempted:n}))))),NO=Oe.define("DeleteTrackRequest",{concertId:p.Number,trackIdx:p.Number,source:p.Literals(["bar","sidebar"])},Wf)(({concertId:e,trackIdx:t,source:n})=>m.tryPromise(()=>iW(e,t)).pipe(m.flatMap(r=>r.ok?m.tryPromise(()=>r.text()).pipe(m.tap(o=>m.sync(()=>{let s=ao(`concert-${e}`);if(s){s.outerHTML=o;let i=ao(`concert-${e}`);i&&window.htmx&&window.htmx.process(i)}})),m.as(Wf({concertId:e,trackIdx:t,ok:!0,source:n}))):m.succeed(Wf({concertId:e,trackIdx:t,ok:!1,source:n}))),m.catch(()=>m.succeed(Wf({concertId:e,trackIdx:t,ok:!1,source:n}))))),FO=Oe.define("RefreshConcertItems",{concertId:p.Number,advanceAfter:p.Boolean},lE,Sh)(({concertId:e,advanceAfter:t})=>m.tryPromise(()=>mO(e)).pipe(m.map(n=>pO(n)?Sh({concertId:e}):lE({concertId:e,items:n.items,advanceAfter:t})),m.catch(()=>m.succeed(Sh({concertId:e}))))),EE=Oe.define("FetchConcertPlayback",{concertId:p.Number,atPos:p.Option(p.Number),errorMessage:p.String},No,Ka,fE,Eh)(({concertId:e,atPos:t,errorMessage:n})=>m.tryPromise(()=>mO(e)).pipe(m.map(r=>{if(pO(r)){let o=r.source;return o.playable?No({source:co.Album({concertId:e}),info:o,opts:{recordListen:!0,playlistName:null,openVideoPanel:!1}}):Ka({source:co.Album({concertId:e}),url:o.url})}return r.items.length>0?fE({concertId:e,items:r.items,atPos:h.getOrElse(t,()=>0)}):Eh({concertId:e,errorMessage:"Nothing to play"})}),m.catch(()=>m.succeed(Eh({concertId:e,errorMessage:n}))))),wW=Oe.define("PostDeleteInterlude",{concertId:p.Number,interludeIdx:p.Number,wasPlayingThis:p.Boolean},pE,kh)(({concertId:e,interludeIdx:t,wasPlayingThis:n})=>m.tryPromise(()=>aW(e,t)).pipe(m.map(r=>r.ok?pE({concertId:e,interludeIdx:t,wasPlayingThis:n}):kh({concertId:e,interludeIdx:t})),m.catch(()=>m.succeed(kh({concertId:e,interludeIdx:t}))))),TW=Oe.define("FetchPlaylistForPlay",{playlistId:p.Number},dE,mE)(({playlistId:e})=>m.tryPromise(()=>YH(e)).pipe(m.map(t=>{let n=(t.resolved_tracks||[]).filter(r=>r.available).map(r=>({concertId:r.concert_id,trackIdx:r.track_index,title:r.title}));return dE({playlistId:e,name:t.playlist.name,tracks:n})}),m.catch(()=>m.succeed(mE({playlistId:e}))))),IW=Oe.define("RecordListenEvent",{url:p.String},de)(({url:e})=>m.tryPromise(()=>hO(e)).pipe(m.catch(()=>m.succeed(void 0)),m.as(de()))),vW=Oe.define("OpenExternalRequest",{url:p.String},de,xE)(({url:e})=>m.tryPromise(()=>hO(e)).pipe(m.as(de()),m.catch(()=>m.succeed(xE())))),AW=Oe.define("PlayAudio",{url:p.String,hlsUrl:p.NullOr(p.String),loadGen:p.Number},de,Vi)(({url:e,hlsUrl:h,loadGen:t})=>m.sync(()=>ei("player-audio",HTMLMediaElement)).pipe(m.flatMap(n=>n?(n.src=h!==null&&n.canPlayType("application/vnd.apple.mpegurl")!==""?h:e,n.dataset.audioLoadGen=String(t),m.tryPromise(()=>n.play()).pipe(m.as(de()),m.catch(()=>m.succeed(Vi())))):m.succeed(Vi())))),Es=Oe.define("PauseAudio",de)(m.sync(()=>ei("player-audio",HTMLMediaElement)?.pause()).pipe(m.as(de()))),Gf=Oe.define("ResumeAudio",de,Vi)(m.sync(()=>ei("player-audio",HTMLMediaElement)).pipe(m.flatMap(e=>e?m.tryPromise(()=>e.play()):m.void),m.as(de()),m.catch(()=>m.succeed(Vi())))),CW=Oe.define("ToggleAudio",de,Vi)(m.sync(()=>ei("player-audio",HTMLMediaElement)).pipe(m.flatMap(e=>e?e.paused?m.tryPromise(()=>e.play()).pipe(m.as(de()),m.catch(()=>m.succeed(Vi()))):(e.pause(),m.succeed(de())):m.succeed(de())))),wh=Oe.define("SeekAudio",{seconds:p.Number},de)(({seconds:e})=>m.sync(()=>{let t=ei("player-audio",HTMLMediaElement);t&&Number.isFinite(t.duration)&&t.duration>0&&(t.currentTime=e)}).pipe(m.as(de()))),DO=Oe.define("ClearAudioSrc",de)(m.sync(()=>{let e=ei("player-audio",HTMLMediaElement);e&&(e.pause(),e.removeAttribute("src"),e.load())}).pipe(m.as(de())));function OW(e,t){return t!=null?document.querySelectorAll(`[data-concert-id="${e}"][data-track-idx="${t}"]`):document.querySelectorAll(`[data-concert-id="${e}"][data-role="listen-album"]`)}var kE=Oe.define("MarkPlayingExternal",{concertId:p.Number,trackIdx:p.Option(p.Number)},de)(({concertId:e,trackIdx:t})=>m.sync(()=>{document.querySelectorAll(".btn-track-listen.playing, .btn-listen.playing").forEach(n=>n.classList.remove("playing")),OW(e,h.getOrNull(t)).forEach(n=>n.classList.add("playing"))}).pipe(m.as(de()))),MW=Oe.define("MarkPlayingInterludeExternal",{concertId:p.Number,interludeIdx:p.Number},de)(({concertId:e,interludeIdx:t})=>m.sync(()=>{document.querySelectorAll(".btn-track-listen.playing, .btn-listen.playing").forEach(n=>n.classList.remove("playing")),document.querySelectorAll(`[data-concert-id="${e}"][data-interlude-idx="${t}"]`).forEach(n=>n.classList.add("playing"))}).pipe(m.as(de()))),LO=Oe.define("MarkPreparingExternal",{concertId:p.Number,trackIdx:p.Number},de)(({concertId:e,trackIdx:t})=>m.sync(()=>{OW(e,t).forEach(n=>n.classList.add("preparing"))}).pipe(m.as(de()))),Nu=Oe.define("ClearPreparingExternal",de)(m.sync(()=>{document.querySelectorAll(".btn-track-listen.preparing").forEach(e=>e.classList.remove("preparing"))}).pipe(m.as(de()))),UO=Oe.define("DisableCardTracksExternal",{concertId:p.Number},de)(({concertId:e})=>m.sync(()=>{ao(`concert-${e}`)?.querySelectorAll(".btn-tracks, .btn-track-listen").forEach(n=>{n.disabled=!0})}).pipe(m.as(de()))),Th=Oe.define("SyncLikeButtonsExternal",{concertId:p.Number,trackIdx:p.Option(p.Number),liked:p.Boolean},de)(({concertId:e,trackIdx:t,liked:n})=>m.sync(()=>{document.querySelectorAll(`.btn-like[hx-post="/concerts/${e}/tracks/${h.getOrNull(t)}/like"]`).forEach(r=>{r.classList.toggle("liked",n),r.textContent=n?"\u2605":"\u2606"})}).pipe(m.as(de()))),PW=Oe.define("OpenInNewTab",{url:p.String},de)(({url:e})=>m.sync(()=>window.open(e,"_blank","noopener")).pipe(m.as(de()))),$O=Oe.define("RefreshCardStatus",{concertId:p.Number},de)(({concertId:e})=>m.sync(()=>{ao(`concert-${e}`)&&window.htmx&&window.htmx.ajax("GET",`/concerts/${e}/status`,{target:`#concert-${e}`,swap:"outerHTML"})}).pipe(m.as(de()))),_W=Oe.define("SyncNowPlayingMirror",{concertId:p.NullOr(p.Number),trackIdx:p.NullOr(p.Number)},de)(({concertId:e,trackIdx:t})=>m.sync(()=>{bH({concertId:e,trackIdx:t}),document.body.classList.toggle("player-active",e!==null)}).pipe(m.as(de()))),BO=Oe.define("OpenAddToPlaylist",{concertId:p.Number,trackIdx:p.Number,label:p.String},de)(({concertId:e,trackIdx:t,label:n})=>m.sync(()=>{window.Playlists?.openAdd({type:"track",concertId:e,trackIndex:t,label:n})}).pipe(m.as(de()))),Kf=Oe.define("ScrollQueueToBottom",de)(m.sync(()=>{let e=ao("sidebar-queue-section");e&&(e.scrollTop=e.scrollHeight)}).pipe(m.as(de()))),Jf=Oe.define("MutateBodyClass",{className:p.String,add:p.Boolean},de)(({className:e,add:t})=>m.sync(()=>{document.body.classList[t?"add":"remove"](e)}).pipe(m.as(de()))),wE=Oe.define("ShowVideoPanel",de)(m.sync(()=>{ao("player-video-panel")?.classList.add("open")}).pipe(m.as(de()))),ks=Oe.define("HideVideoPanel",de)(m.sync(()=>{ao("player-video-panel")?.classList.remove("open","controls-visible")}).pipe(m.as(de()))),RW=Oe.define("LoadSidebarWidth",de)(m.sync(()=>{let e=parseInt(localStorage.getItem(cO)||"",10);isNaN(e)||document.documentElement.style.setProperty("--sidebar-width",`${GS(e)}px`)}).pipe(m.as(de()))),jO=Oe.define("SetSidebarWidthVar",{px:p.Number},de)(({px:e})=>m.sync(()=>{document.documentElement.style.setProperty("--sidebar-width",`${GS(e)}px`)}).pipe(m.as(de()))),NW=Oe.define("PersistSidebarWidth",{px:p.Number},de)(({px:e})=>m.sync(()=>{try{localStorage.setItem(cO,String(GS(e)))}catch{}}).pipe(m.as(de()))),Ih=Oe.define("FetchTrackDetails",{concertId:p.Number,loadGen:p.Number},hE,gE)(({concertId:e,loadGen:t})=>m.tryPromise(()=>cW(e)).pipe(m.map(n=>hE({concertId:e,loadGen:t,tracksBusy:n.tracks_busy,tracks:n.tracks})),m.catch(()=>m.succeed(gE({concertId:e,loadGen:t})))));function aOe(){let e=ei("player-audio",HTMLMediaElement);return e&&(e.currentSrc||e.getAttribute("src"))?e:null}function cOe(e){if(!e)return()=>{};let t=null,n=()=>{e.classList.add("controls-visible"),t&&clearTimeout(t),t=setTimeout(()=>e.classList.remove("controls-visible"),2500)};return e.addEventListener("mousemove",n),e.addEventListener("touchstart",n,{passive:!0}),()=>{e.removeEventListener("mousemove",n),e.removeEventListener("touchstart",n),t&&clearTimeout(t),e.classList.remove("controls-visible")}}function uOe(e){if(!Number.isFinite(e.duration)||e.duration<=0)return h.none();let t=Number(e.dataset.audioLoadGen);return h.some(kO({currentTime:e.currentTime,duration:e.duration,loadGen:Number.isFinite(t)?t:-1}))}function lOe(e){let r=(e instanceof CustomEvent?e.detail:void 0)?.elt?.getAttribute("hx-post")?.match(/\/concerts\/(\d+)\/tracks\/(\d+)\/like/);if(!r)return h.none();let o=parseInt(r[1],10),s=parseInt(r[2],10),i=document.querySelector(`[hx-post="/concerts/${o}/tracks/${s}/like"]`);return i?h.some({concertId:o,trackIdx:s,liked:i.classList.contains("liked")}):h.none()}function Xq9(e){let t=/#t=[\d.]*,([\d.]+)$/.exec(e);return t?Number(t[1]):null}function jZ9(e,t){let r=Xq9(e);return r!==null&&t>=r-.25}var FW=VS.make()(e=>({audioEvents:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>{let t=ei("player-audio",HTMLMediaElement);return t?B.mergeAll([B.merge(B.fromEventListener(t,"play").pipe(B.map(()=>SO())),B.fromEventListener(t,"pause").pipe(B.flatMap(()=>B.fromIterable(jZ9(t.src,t.currentTime)?[EO(),wO()]:[EO()])))),B.merge(B.fromEventListener(t,"ended").pipe(B.map(()=>wO())),B.fromEventListener(t,"error").pipe(B.map(()=>TO()))),B.merge(B.fromEventListener(t,"timeupdate"),B.fromEventListener(t,"loadedmetadata")).pipe(B.map(()=>uOe(t)),B.filter(h.isSome),B.map(n=>n.value))],{concurrency:"unbounded"}):B.empty}}),keyboard:e({videoOpen:p.Boolean},{modelToDependencies:t=>({videoOpen:t.video.open}),dependenciesToStream:({videoOpen:t})=>B.fromEventListener(document,"keydown").pipe(B.mapEffect(n=>m.sync(()=>{if(n.defaultPrevented)return h.none();if(hH(n)&&!QS(n.target)&&t)return n.preventDefault(),h.some(CO());if(mH(n)&&!xH(n.target)){let r=aOe();return r?(n.preventDefault(),n.repeat?h.none():h.some(AO({audioPaused:r.paused}))):h.none()}return h.none()})),B.filter(h.isSome),B.map(n=>n.value))}),outsideVideo:e({videoOpen:p.Boolean},{modelToDependencies:t=>({videoOpen:t.video.open}),dependenciesToStream:({videoOpen:t})=>B.when(B.fromEventListener(document,"click").pipe(B.mapEffect(n=>m.sync(()=>{let r=ao("player-video-panel");return dH(n.target,r)?h.some(OO()):h.none()})),B.filter(h.isSome),B.map(n=>n.value)),m.sync(()=>t))}),htmxSettle:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>B.merge(B.fromEventListener(document.body,"htmx:afterSettle"),B.fromEventListener(document.body,"htmx:historyRestore")).pipe(B.map(()=>IO()))}),htmxSwap:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>B.callback(t=>m.acquireRelease(m.sync(()=>{let n=r=>h.map(lOe(r),o=>on.offerUnsafe(t,vO(o)));return document.body.addEventListener("htmx:afterSwap",n),n}),n=>m.sync(()=>document.body.removeEventListener("htmx:afterSwap",n))).pipe(m.flatMap(()=>m.never)))}),sidebarResize:e({},{modelToDependencies:()=>({}),dependenciesToStream:()=>B.callback(t=>m.acquireRelease(m.sync(()=>{let n=ao("sidebar-resize");if(!n)return()=>{};let r=!1,o=!1,s=c=>{r=!0,o=!1,n.setPointerCapture(c.pointerId),c.preventDefault(),document.body.classList.add("sidebar-resizing")},i=c=>{r&&(o=!0,on.offerUnsafe(t,MO({clientX:Math.round(c.clientX)})))},a=c=>{if(!r)return;r=!1,document.body.classList.remove("sidebar-resizing");let u=Math.round(c.clientX||parseInt(getComputedStyle(document.documentElement).getPropertyValue("--sidebar-width"),10)||240);on.offerUnsafe(t,PO({clientX:u,moved:o})),o=!1};return n.addEventListener("pointerdown",s),document.addEventListener("pointermove",i),document.addEventListener("pointerup",a),document.addEventListener("pointercancel",a),()=>{n.removeEventListener("pointerdown",s),document.removeEventListener("pointermove",i),document.removeEventListener("pointerup",a),document.removeEventListener("pointercancel",a)}}),n=>m.sync(n)).pipe(m.flatMap(()=>m.never)))}),videoControlsIdle:e({videoOpen:p.Boolean},{modelToDependencies:t=>({videoOpen:t.video.open}),dependenciesToStream:({videoOpen:t})=>t?B.callback(()=>m.acquireRelease(m.sync(()=>cOe(ao("player-video-panel"))),n=>m.sync(n)).pipe(m.flatMap(()=>m.never))):B.empty}),commandPort:Bf.subscription(YS.inbound.command,t=>it({command:t}))}));var vh=q.withReturnType(),qO=e=>({concertId:e.concertId,trackIdx:e.trackIdx,isVideo:e.isVideo,watchUrl:e.watchUrl,hasNext:e.hasNext,hasPrev:e.hasPrev,liked:e.liked,concert:h.getOrNull(e.concert)}),DW=e=>e.playback.concertId!==null,fOe=e=>DW(e)&&e.isPlaying,LW=e=>!DW(e)||e.playback.ended,pr=(e,t)=>L(e,{status:()=>Ga.Error({message:t})}),UW=(e,t)=>L(e,{status:()=>Ga.Busy({message:t})}),Ah=(e,t)=>[e,[...t,_W({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx})]],pOe=e=>q.value(e).pipe(q.withReturnType(),q.tagsExhaustive({Track:({concertId:t,trackIdx:n})=>({concertId:t,trackIdx:n}),Album:({concertId:t})=>({concertId:t,trackIdx:null}),ConcertItem:({concertId:t,trackIdx:n})=>({concertId:t,trackIdx:n})})),dOe=e=>q.value(e).pipe(q.withReturnType(),q.tagsExhaustive({Track:({concertId:t,trackIdx:n})=>`/concerts/${t}/tracks/${n}/listen`,Album:({concertId:t})=>`/concerts/${t}/listen`,ConcertItem:({concertId:t,trackIdx:n,isInterlude:r})=>r||n===null?null:`/concerts/${t}/tracks/${n}/listen`})),mOe=(e,t)=>t.is_video?q.value(e).pipe(q.withReturnType(),q.tagsExhaustive({Track:({concertId:n,trackIdx:r})=>`/concerts/${n}/tracks/${r}/watch`,Album:({concertId:n})=>`/concerts/${n}/watch`,ConcertItem:()=>null})):null,TE=(e,t,n,r)=>{let{concertId:o,trackIdx:s}=pOe(t),i=dOe(t),a=mOe(t,n),c=n.is_video?r.openVideoPanel?!0:e.video.open:!1,u=e.audioLoadGen+1,f=L(e,{playback:()=>({concertId:o,trackIdx:s,title:n.title,artist:n.artist,isVideo:n.is_video,watchUrl:a,hasNext:n.has_next,hasPrev:n.has_prev,liked:n.liked,posterUrl:n.poster_url??null,ended:!1,concert:h.none(),playlistLabel:r.playlistName}),video:()=>({open:c}),pending:()=>h.none(),status:()=>Ga.Idle(),audioTime:()=>nE,audioLoadGen:()=>u}),d=c!==e.video.open?[c?wE():ks()]:[],g=i&&r.recordListen?[IW({url:i})]:[],y=h.match(e.pendingSeek,{onNone:()=>[],onSome:b=>[wh({seconds:b})]}),x=[AW({url:n.url,hlsUrl:n.hls_url??null,loadGen:u}),kE({concertId:o,trackIdx:h.fromNullishOr(s)}),Nu(),...d,...g,...y];return[L(f,{pendingSeek:()=>h.none()}),x]},zO=(e,[t,n])=>{let r=t.playback.concertId;if(!e.sidebar.open||r===null||r===e.playback.concertId||h.isSome(t.playback.concert))return[t,n];let o=e.sidebar.loadGen+1;return[L(t,{sidebar:()=>L(t.sidebar,{loadGen:()=>o})}),[...n,Ih({concertId:r,loadGen:o})]]},Ch=(e,t,n)=>e.playback.concertId===t&&e.playback.trackIdx===n?e.isPlaying?[e,[Es()]]:[e,[Gf()]]:fOe(e)?[e,[bW({concertId:t,trackIdx:n})]]:[e,[Vf({concertId:t,trackIdx:n,opts:Wi})]],HO=e=>Ah(L(e,{playback:()=>yO,queue:()=>[],sidebar:()=>({open:!1,tracks:h.none(),loadGen:0}),video:()=>({open:!1}),isPlaying:()=>!1,pendingSeek:()=>h.none(),status:()=>Ga.Idle(),audioTime:()=>nE,audioLoadGen:()=>e.audioLoadGen+1}),e.video.open?[DO(),ks()]:[DO()]),IE=(e,t)=>q.value(t).pipe(vh,q.whenOr("queue-only","next-or-none",()=>[L(e,{isPlaying:()=>!1}),[]]),q.when("next-or-stop",()=>HO(e)),q.when("next-or-collapse",()=>[L(e,{isPlaying:()=>!1,video:()=>({open:!1})}),e.video.open?[ks()]:[]]),q.exhaustive),$W=(e,t)=>{let{concertId:n,trackIdx:r}=e.playback;return n===null||r===null?IE(e,t):[e,[EW({concertId:n,trackIdx:r,plan:t})]]},WO=e=>[e,[Es(),Ru({queue:e.queue,plan:"next-or-stop"})]],VO=e=>h.isSome(e.playback.concert)?GO(e):[e,[Ru({queue:e.queue,plan:"next-or-collapse"})]];function Oh(e,t){return h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:n=>{let r=n.items[t];if(!r)return[e,[]];let o=r.kind==="interlude",s=o?null:r.track_index??null,{hasPrev:i,hasNext:a}=lH(n.items,t),c={artist:r.artist,has_next:a,has_prev:i,hls_url:r.hls_url??null,is_video:r.is_video,liked:r.liked,playable:!0,poster_url:r.poster_url??null,title:r.title,track_index:s,url:r.url},u=co.ConcertItem({concertId:n.id,trackIdx:s,isInterlude:o}),[f,d]=TE(e,u,c,Wi),g=L(f,{playback:()=>L(f.playback,{concert:()=>h.some(L(n,{pos:()=>t}))})}),y=o&&r.interlude_index!=null?[MW({concertId:n.id,interludeIdx:r.interlude_index})]:[];return Ah(g,[...d,...y])}})}function GO(e){return h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:t=>{let n=fH(t.pos,t.items.length);return n===null?[L(e,{playback:()=>L(e.playback,{concert:()=>h.none()}),video:()=>({open:!1})}),e.video.open?[ks()]:[]]:Oh(e,n)}})}function KO(e){return h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:t=>t.pos<t.items.length?Oh(e,t.pos):[L(e,{playback:()=>L(e.playback,{concert:()=>h.none()}),video:()=>({open:!1})}),e.video.open?[ks()]:[]]})}var vE={start:"Couldn't start concert",load:"Couldn't load concert"};function hOe(e,t,n,r){return h.match(e.sidebar.tracks,{onNone:()=>e,onSome:o=>{if(e.playback.concertId!==t)return e;let s=o.tracks.map(i=>i.index===n?{...i,liked:r}:i);return L(e,{sidebar:()=>L(e.sidebar,{tracks:()=>h.some(L(o,{tracks:()=>s}))})})}})}function BW(e,t,n,r){return h.match(e.sidebar.tracks,{onNone:()=>e,onSome:o=>{if(e.playback.concertId!==t)return e;let s=o.tracks.map(i=>i.index===n?{...i,available:r}:i);return L(e,{sidebar:()=>L(e.sidebar,{tracks:()=>h.some(L(o,{tracks:()=>s}))})})}})}function gOe(e,t,n,r){return h.match(e.playback.concert,{onNone:()=>e,onSome:o=>{if(o.id!==t)return e;let s=o.items.map(i=>i.track_index===n&&i.kind!=="interlude"?{...i,liked:r}:i);return L(e,{playback:()=>L(e.playback,{concert:()=>h.some(L(o,{items:()=>s}))})})}})}function Fu(e,t,n,r){let s=e.playback.concertId===t&&e.playback.trackIdx===n?L(e,{playback:()=>L(e.playback,{liked:()=>r})}):e,i=hOe(s,t,n,r);return gOe(i,t,n,r)}function jW(e,t,n){let r=e.playback.concertId===t?h.flatMap(e.sidebar.tracks,o=>h.fromNullishOr(o.tracks.find(s=>s.index===n)?.liked)):h.none();return h.orElse(r,()=>h.flatMap(e.playback.concert,o=>o.id===t?h.fromNullishOr(o.items.find(s=>s.track_index===n&&s.kind!=="interlude")?.liked):h.none()))}var qW=e=>t=>q.value(t).pipe(vh,q.tagsExhaustive({PlayAlbum:({concertId:n})=>[e,[yE({concertId:n,opts:Wi})]],PlayTrack:({concertId:n,trackIdx:r})=>Ch(e,n,r),PlayTracks:({concertId:n})=>[e,[SW({concertId:n})]],StartAlbum:({concertId:n,recordListen:r})=>[e,[yE({concertId:n,opts:{recordListen:r,playlistName:null,openVideoPanel:!1}})]],StartTrack:({concertId:n,trackIdx:r})=>[e,[Vf({concertId:n,trackIdx:r,opts:Wi})]],TogglePause:()=>[e,[CW()]],Seek:({seconds:n})=>[e,[wh({seconds:n})]],SkipToNext:()=>{if(h.isSome(e.playback.concert)){let[n,r]=GO(e);return[n,[Es(),...r]]}return JS(qO(e.playback),e.queue.length)?[e,[Es(),Ru({queue:e.queue,plan:"next-or-none"})]]:[e,[]]},SkipToPrev:()=>{if(h.isSome(e.playback.concert)){let n=e.playback.concert.value;if(n.pos<=0)return[e,[]];let[r,o]=Oh(e,n.pos-1);return[r,[Es(),...o]]}return ZS(qO(e.playback))?e.playback.concertId===null||e.playback.trackIdx===null?[e,[]]:[e,[Es(),kW({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx})]]:[e,[]]},Watch:()=>{let n=!e.video.open;return[L(e,{video:()=>({open:n})}),[n?wE():ks()]]},OpenExternal:()=>e.playback.watchUrl===null?[e,[]]:[e,[Es(),vW({url:e.playback.watchUrl})]],WatchTrackDirect:({concertId:n,trackIdx:r})=>[e,[Vf({concertId:n,trackIdx:r,opts:{recordListen:!0,playlistName:null,openVideoPanel:!0}})]],ToggleLike:()=>{if(e.playback.trackIdx===null||e.playback.concertId===null)return[e,[]];let{concertId:n,trackIdx:r}=e.playback,o=!e.playback.liked;return[Fu(e,n,r,o),[RO({concertId:n,trackIdx:r,next:o}),Th({concertId:n,trackIdx:h.some(r),liked:o})]]},DeleteTrack:()=>e.playback.trackIdx===null||e.playback.concertId===null?[e,[]]:[e,[NO({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx,source:"bar"})]],OpenSidebar:()=>{let n=L(e,{sidebar:()=>L(e.sidebar,{open:()=>!0})}),r=e.playback.concertId;if(r!==null&&h.isNone(e.playback.concert)){let o=e.sidebar.loadGen+1;return[L(n,{sidebar:()=>L(n.sidebar,{loadGen:()=>o})}),[Jf({className:"sidebar-open",add:!0}),Ih({concertId:r,loadGen:o})]]}return[n,[Jf({className:"sidebar-open",add:!0})]]},CloseSidebar:()=>[L(e,{sidebar:()=>L(e.sidebar,{open:()=>!1})}),[Jf({className:"sidebar-open",add:!1})]],ToggleSidebar:()=>{let n=!e.sidebar.open,r=L(e,{sidebar:()=>L(e.sidebar,{open:()=>n})}),o=e.playback.concertId;if(n&&o!==null&&h.isNone(e.playback.concert)){let s=e.sidebar.loadGen+1;return[L(r,{sidebar:()=>L(r.sidebar,{loadGen:()=>s})}),[Jf({className:"sidebar-open",add:n}),Ih({concertId:o,loadGen:s})]]}return[r,[Jf({className:"sidebar-open",add:n})]]},SidebarDeleteTrack:({concertId:n,trackIdx:r})=>[e,[NO({concertId:n,trackIdx:r,source:"sidebar"})]],PlayQueueEntryNow:({pos:n})=>{let r=e.queue[n];return r?[L(e,{queue:s=>uO(s,n)}),[Vf({concertId:r.concertId,trackIdx:r.trackIdx,opts:Wi})]]:[e,[]]},Dequeue:({pos:n})=>[L(e,{queue:r=>uO(r,n)}),[]],RemoveGroup:({groupId:n})=>[L(e,{queue:r=>cH(r,n)}),[]],Enqueue:({concertId:n,trackIdx:r,title:o,liked:s})=>{let i=KS(e.queue,ch(n,r,o,s));return[L(e,{queue:()=>i.queue}),i.added?[Kf()]:[]]},PlayAlbumAt:({concertId:n,seconds:r})=>e.playback.concertId===n&&e.playback.trackIdx===null?[e,[wh({seconds:r}),...e.isPlaying?[]:[Gf()]]]:[L(e,{pendingSeek:()=>h.some(r)}),[yE({concertId:n,opts:{recordListen:!1,playlistName:null,openVideoPanel:!1}})]],PlayPlaylist:({playlistId:n})=>[e,[TW({playlistId:n})]],AddToPlaylist:()=>e.playback.trackIdx===null||e.playback.concertId===null?[e,[]]:[e,[BO({concertId:e.playback.concertId,trackIdx:e.playback.trackIdx,label:e.playback.title})]],StopPlayback:()=>HO(e),PlayConcert:({concertId:n})=>[e,[EE({concertId:n,atPos:h.none(),errorMessage:vE.start})]],PlayConcertFrom:({concertId:n,pos:r})=>h.match(e.playback.concert,{onSome:o=>o.id===n?Oh(L(e,{playback:()=>L(e.playback,{concert:()=>h.some(L(o,{pos:()=>r}))})}),r):[e,[EE({concertId:n,atPos:h.some(r),errorMessage:vE.load})]],onNone:()=>[e,[EE({concertId:n,atPos:h.some(r),errorMessage:vE.load})]]}),SidebarDeleteInterlude:({concertId:n,interludeIdx:r})=>{let o=h.match(e.playback.concert,{onNone:()=>!1,onSome:s=>{let i=s.items[s.pos];return!!(i&&i.kind==="interlude"&&i.interlude_index===r)}});return[e,[wW({concertId:n,interludeIdx:r,wasPlayingThis:o})]]},SidebarLikeTrack:({concertId:n,trackIdx:r})=>{let o=jW(e,n,r);if(h.isNone(o))return[e,[]];let s=!o.value;return[Fu(e,n,r,s),[RO({concertId:n,trackIdx:r,next:s}),Th({concertId:n,trackIdx:h.some(r),liked:s})]]},SidebarAddToPlaylist:({concertId:n,trackIdx:r,label:o})=>[e,[BO({concertId:n,trackIdx:r,label:o})]]}));var HW=(e,t)=>q.value(t).pipe(vh,q.tagsExhaustive({CommandReceived:({command:n})=>qW(e)(n),SucceededMediaInfo:({source:n,info:r,opts:o})=>Ah(...zO(e,TE(e,n,r,o))),NotPlayable:({url:n})=>[e,[PW({url:n})]],TrackMissing:({source:n})=>n._tag!=="Track"?[e,[]]:[e,[bE({target:tE.Track({concertId:n.concertId,trackIdx:n.trackIdx})})]],FailedFetchInfo:({errorMessage:n})=>[pr(e,n),[]],SucceededTrackInfoForEnqueue:({concertId:n,trackIdx:r,info:o})=>h.match(o,{onNone:()=>[e,[bE({target:tE.Track({concertId:n,trackIdx:r})})]],onSome:({title:s,liked:i})=>{let a=KS(e.queue,ch(n,r,s,i));return[L(e,{queue:()=>a.queue}),a.added?[Kf()]:[]]}}),ResolvedFirstAvailableTrack:({concertId:n,trackIdx:r})=>h.match(r,{onNone:()=>[e,[bE({target:tE.Track({concertId:n,trackIdx:0})})]],onSome:o=>Ch(e,n,o)}),DrainedQueue:({played:n,skippedCount:r,plan:o})=>{let s=h.isSome(n)?r+1:r,i=L(e,{queue:a=>a.slice(s)});return h.match(n,{onSome:({entry:a,info:c})=>Ah(...zO(i,TE(i,co.Track({concertId:a.concertId,trackIdx:a.trackIdx}),c,{...Wi,playlistName:a.playlistName}))),onNone:()=>o==="queue-only"?[i,[]]:$W(i,o)})},NoNextTrack:({plan:n})=>IE(e,n),FailedNextTrackInfo:({plan:n})=>{let r=n==="next-or-stop"?L(e,{isPlaying:()=>!1}):pr(L(e,{isPlaying:()=>!1}),"Couldn't load next track");return IE(r,n)},FailedPrevTrackInfo:()=>[L(e,{isPlaying:()=>!1}),[]],SucceededPrepareStart:({target:n,seedStatus:r})=>{let o=L(e,{pending:()=>h.some(n),status:()=>Ga.Busy({message:"Preparing\u2026"})}),s=n._tag==="Track"?[LO({concertId:n.concertId,trackIdx:n.trackIdx}),UO({concertId:n.concertId}),$O({concertId:n.concertId})]:[UO({concertId:n.concertId}),$O({concertId:n.concertId})];return[o,[...s,SE({target:n,elapsedMs:0,seedStatus:r})]]},FailedPrepareStart:()=>[pr(e,"Prepare failed"),[]],SucceededPrepareStatus:({target:n,status:r,elapsedMs:o})=>h.match(e.pending,{onNone:()=>[e,[]],onSome:s=>{if(!bO(s,n))return[e,[]];if(n._tag==="Track"&&r.tracks_present[n.trackIdx]===!0&&n._tag==="Track"){let c=L(e,{pending:()=>h.none()}),[u,f]=Ch(c,n.concertId,n.trackIdx);return[u,[Nu(),...f]]}if(r.download==="download-error"||r.split==="split-error")return[L(pr(e,"Preparing tracks failed"),{pending:()=>h.none()}),[Nu()]];if(o>18e5)return[L(pr(e,"Preparing tracks timed out"),{pending:()=>h.none()}),[Nu()]];let a=r.split==="splitting"?"Preparing\u2026 (splitting)":"Preparing\u2026 (downloading)";return[UW(e,a),[SE({target:n,elapsedMs:o,seedStatus:h.none()})]]}}),FailedPollPrepareStatus:({target:n,elapsedMs:r})=>h.match(e.pending,{onNone:()=>[e,[]],onSome:o=>bO(o,n)?r>18e5?[L(pr(e,"Preparing tracks timed out"),{pending:()=>h.none()}),[Nu()]]:[e,[SE({target:n,elapsedMs:r,seedStatus:h.none()})]]:[e,[]]}),CompletedLikeToggle:({concertId:n,trackIdx:r,liked:o})=>[Fu(e,n,r,o),[]],FailedLikeToggle:({concertId:n,trackIdx:r,attempted:o})=>{let s=!o,i=e.playback.concertId===n&&e.playback.trackIdx===r,a=Fu(e,n,r,s);return[i?pr(a,"Like failed"):a,[Th({concertId:n,trackIdx:h.some(r),liked:s})]]},CompletedDeleteTrack:({concertId:n,trackIdx:r,ok:o,source:s})=>{if(!o)return[pr(e,"Delete failed"),[]];if(s==="bar")return e.playback.concertId!==n||e.playback.trackIdx!==r?[e,[]]:WO(e);if(!(h.isSome(e.playback.concert)&&e.playback.concert.value.id===n)){let c=BW(e,n,r,!1);return e.playback.concertId===n&&e.playback.trackIdx===r?WO(c):[c,[]]}let a=e.playback.concertId===n&&e.playback.trackIdx===r;return[e,[FO({concertId:n,advanceAfter:a})]]},SucceededConcertItems:({concertId:n,items:r,advanceAfter:o})=>h.match(e.playback.concert,{onNone:()=>[e,[]],onSome:s=>{if(s.id!==n)return[e,[]];let i=s.items[s.pos]??null,a=pH(r,i?i.url:null,s.pos),c=L(e,{playback:()=>L(e.playback,{concert:()=>h.some(L(s,{items:()=>r,pos:()=>a}))})});return o?KO(c):[c,[]]}}),FailedConcertItems:()=>[e,[]],SucceededConcertPlaybackItems:({concertId:n,items:r,atPos:o})=>KO(L(e,{playback:()=>L(e.playback,{concert:()=>h.some({id:n,items:r,pos:o})})})),FailedConcertPlayback:({errorMessage:n})=>[pr(e,n),[]],CompletedDeleteInterlude:({concertId:n,wasPlayingThis:r})=>h.isSome(e.playback.concert)&&e.playback.concert.value.id===n?[e,[FO({concertId:n,advanceAfter:r})]]:[e,[]],FailedDeleteInterlude:()=>[pr(e,"Delete failed"),[]],SucceededPlaylistTracks:({tracks:n,name:r})=>{if(A.isReadonlyArrayEmpty(n))return[pr(e,"Nothing to play in this playlist"),[]];let o=e.nextGroupId,s=n.map(a=>ch(a.concertId,a.trackIdx,a.title,!1,r,o)),i=L(e,{queue:a=>[...a,...s],nextGroupId:()=>o+1});return LW(i)?[i,[Ru({queue:i.queue,plan:"queue-only"}),Kf()]]:[i,[Kf()]]},FailedPlaylistLoad:()=>[pr(e,"Couldn't load playlist"),[]],SucceededTrackDetails:({concertId:n,loadGen:r,tracksBusy:o,tracks:s})=>e.sidebar.loadGen!==r?[e,[]]:e.playback.concertId!==n?[e,[]]:[L(e,{sidebar:()=>L(e.sidebar,{tracks:()=>h.some({tracksBusy:o,tracks:s})})}),[]],FailedTrackDetails:()=>[e,[]],FailedOpenExternal:()=>[pr(e,"Couldn't open externally"),[]],StartedAudio:()=>[L(e,{isPlaying:()=>!0}),[]],PausedAudio:()=>[L(e,{isPlaying:()=>!1}),[]],UpdatedAudioTime:({currentTime:n,duration:r,loadGen:o})=>o===e.audioLoadGen?[L(e,{audioTime:()=>({currentTime:n,duration:r})}),[]]:[e,[]],EndedAudio:()=>VO(L(e,{playback:()=>L(e.playback,{ended:()=>!0})})),ErroredAudio:()=>VO(pr(L(e,{playback:()=>L(e.playback,{ended:()=>!0})}),"Failed to load media")),RejectedAudioPlay:()=>[pr(L(e,{isPlaying:()=>!1}),"Playback blocked"),[]],SettledHtmxContent:()=>{let{concertId:n,trackIdx:r}=e.playback,o=n!==null?[kE({concertId:n,trackIdx:h.fromNullishOr(r)})]:[],s=h.match(e.pending,{onNone:()=>[],onSome:i=>i._tag==="Track"?[LO({concertId:i.concertId,trackIdx:i.trackIdx})]:[]});return[e,[...o,...s]]},SwappedLikeButton:({concertId:n,trackIdx:r,liked:o})=>[Fu(e,n,r,o),[]],PressedSpace:({audioPaused:n})=>n?[e,[Gf()]]:[e,[Es()]],PressedEscape:()=>e.video.open?[L(e,{video:()=>({open:!1})}),[ks()]]:[e,[]],ClickedOutsideVideo:()=>e.video.open?[L(e,{video:()=>({open:!1})}),[ks()]]:[e,[]],MovedSidebarDrag:({clientX:n})=>[e,[jO({px:n})]],ReleasedSidebarDrag:({clientX:n,moved:r})=>r?[e,[jO({px:n}),NW({px:n})]]:[e,[]],Acked:()=>[e,[]]}));var WW=e=>t=>t==="Enter"?h.some(e):h.none(),xOe=e=>({concertId:e.concertId,trackIdx:e.trackIdx,isVideo:e.isVideo,watchUrl:e.watchUrl,hasNext:e.hasNext,hasPrev:e.hasPrev,liked:e.liked,concert:h.getOrNull(e.concert)}),VW=e=>e?"btn-like liked":"btn-like",GW=(e,t,n)=>{let r=_n();return r.button([r.Class(VW(n)),r.Title("Like"),r.AriaLabel("Like"),r.AriaPressed(n?"true":"false"),r.OnClick(it({command:se.SidebarLikeTrack({concertId:e,trackIdx:t})}))],[n?"\u2605":"\u2606"])},KW=(e,t,n)=>{let r=_n();return r.button([r.Class("btn-delete"),r.Title("Delete track files"),r.AriaLabel(`Delete files for ${n}`),r.OnClick(it({command:se.SidebarDeleteTrack({concertId:e,trackIdx:t})}))],[r.span([r.Class("icon-trash")],[])])},JW=(e,t,n)=>{let r=_n();return r.button([r.Class("btn-add-pl"),r.Title("Add to playlist"),r.AriaLabel(`Add ${n} to playlist`),r.OnClick(it({command:se.SidebarAddToPlaylist({concertId:e,trackIdx:t,label:n})}))],["+"])},yOe=(e,t,n,r,o)=>{let s=_n();return s.keyed("li")(`track-${n}`,[s.Class(o?"concert-item concert-item-playing":"concert-item")],[GW(r,n,e.liked),s.button([s.Class(o?"btn-track-listen playing":"btn-track-listen"),s.Attribute("data-concert-id",String(r)),s.Attribute("data-track-idx",String(n)),s.OnClick(it({command:se.PlayConcertFrom({concertId:r,pos:t})}))],[e.title]),KW(r,n,e.title),JW(r,n,e.title)])},bOe=(e,t,n,r)=>{let o=_n(),s=e.interlude_index??0;return o.keyed("li")(`interlude-${s}`,[o.Class(r?"concert-item concert-item-interlude concert-item-playing":"concert-item concert-item-interlude")],[o.button([o.Class(r?"btn-track-listen btn-interlude playing":"btn-track-listen btn-interlude"),o.Attribute("data-concert-id",String(n)),o.Attribute("data-interlude-idx",String(s)),o.OnClick(it({command:se.PlayConcertFrom({concertId:n,pos:t})}))],[e.title]),o.button([o.Class("btn-delete"),o.Title("Delete interlude file"),o.AriaLabel(`Delete interlude file for ${e.title}`),o.OnClick(it({command:se.SidebarDeleteInterlude({concertId:n,interludeIdx:s})}))],[o.span([o.Class("icon-trash")],[])])])};function SOe(e,t){let n=_n();return n.ol([n.Class("track-list track-list-concert-playback")],e.items.map((r,o)=>{let s=o===e.pos,i=r.kind==="interlude",a=r.track_index??null;return!i&&a!==null?yOe(r,o,a,t,s):bOe(r,o,t,s)}))}var EOe=(e,t,n,r)=>{let o=_n();return o.keyed("li")(`avail-${e.index}`,[o.Class(n?"concert-item concert-item-playing":"concert-item")],[GW(t,e.index,e.liked),o.button([o.Class(n?"btn-track-listen playing":"btn-track-listen"),o.Attribute("data-concert-id",String(t)),o.Attribute("data-track-idx",String(e.index)),o.Disabled(r),o.OnClick(it({command:se.PlayTrack({concertId:t,trackIdx:e.index})}))],[e.title]),...e.is_video?[o.button([o.Class("btn-watch"),o.OnClick(it({command:se.WatchTrackDirect({concertId:t,trackIdx:e.index})}))],["Watch"])]:[],KW(t,e.index,e.title),JW(t,e.index,e.title)])},kOe=(e,t,n)=>{let r=_n();return r.keyed("li")(`unavail-${e.index}`,[r.Class("concert-item track-unavailable")],[r.button([r.Class("btn-track-listen track-title-unavailable"),r.Attribute("data-concert-id",String(t)),r.Attribute("data-track-idx",String(e.index)),r.Disabled(n),r.OnClick(it({command:se.PlayTrack({concertId:t,trackIdx:e.index})}))],[e.title])])};function wOe(e,t,n){let r=_n(),{tracksBusy:o,tracks:s}=e;return r.ol([r.Class("track-list")],s.map(i=>{let a=i.index===n;return i.available?EOe(i,t,a,o):kOe(i,t,o)}))}function TOe(e){let t=_n(),n=t.keyed("li"),{rows:r}=uH(e.queue);return t.section([t.Id("sidebar-queue-section")],[t.h2([],["Queue"]),t.ol([t.Id("sidebar-queue-list"),t.Class("track-list")],r.map(o=>o.kind==="group-header"?n(`group-${o.groupId}`,[t.Class("queue-group-header")],[t.span([t.Class("queue-group-name")],[o.name]),t.button([t.Class("btn-remove-group"),t.AriaLabel(`Remove ${o.name} from queue`),t.OnClick(it({command:se.RemoveGroup({groupId:o.groupId})}))],["\xD7"])]):n(`song-${o.entry.groupId??"solo"}-${o.entry.concertId}-${o.entry.trackIdx}`,[t.Class(o.nested?"queue-song queue-song-nested":"queue-song")],[t.button([t.Class("btn-remove-queue"),t.AriaLabel(`Remove ${o.entry.title} from queue`),t.OnClick(it({command:se.Dequeue({pos:o.pos})}))],["\xD7"]),t.button([t.Class("btn-play-queue"),t.OnClick(it({command:se.PlayQueueEntryNow({pos:o.pos})}))],[o.entry.title])]))),t.p([t.Id("sidebar-queue-empty"),t.Style({display:r.length===0?"":"none"})],["Nothing queued"])])}function IOe(e){let t=_n(),n=e.playback.concertId;if(n===null)return t.section([t.Id("sidebar-concert-section")],[]);let r=h.match(e.playback.concert,{onSome:o=>SOe(o,n),onNone:()=>h.match(e.sidebar.tracks,{onSome:o=>wOe(o,n,e.playback.trackIdx),onNone:()=>t.p([t.Class("sidebar-loading")],["Loading\u2026"])})});return t.section([t.Id("sidebar-concert-section")],[t.h2([t.Id("sidebar-concert-heading")],["Now playing"]),r])}function vOe(e){let t=_n(),n=e.playback,r=n.concertId!==null,o=r&&n.trackIdx!==null,s=xOe(n),i=e.queue.length,a=r&&JS(s,i),c=r&&ZS(s),u=e.status._tag==="Error"?e.status.message:"",f=e.status._tag==="Busy"?e.status.message:"";return t.div([t.Id("player-bar"),...r?[t.Class("active")]:[]],[t.button([t.Id("player-queue-toggle"),t.AriaLabel("Toggle queue and tracks sidebar"),t.AriaExpanded(e.sidebar.open),t.Title("Show queue and tracks"),t.OnClick(it({command:se.ToggleSidebar()}))],["\u2630",t.span([t.Id("player-queue-badge"),t.Style({visibility:i>0?"visible":"hidden"}),t.Title(i>0?e.queue.map(d=>d.title).join(`
+`):"")],[i>0?String(i):""])]),t.img([t.Id("player-poster"),t.Src(n.posterUrl??""),t.Alt(""),t.Style({display:r&&n.posterUrl!==null?"":"none"})]),t.div([t.Id("player-info")],[t.span([t.Class("player-title-line")],[t.button([t.Id("player-like"),t.Class(VW(n.liked)),t.Title("Like"),t.AriaLabel("Like"),t.AriaPressed(n.liked?"true":"false"),t.Style({display:o?"":"none"}),t.OnClick(it({command:se.ToggleLike()}))],[n.liked?"\u2605":"\u2606"]),t.button([t.Id("player-add-pl"),t.Title("Add to playlist"),t.AriaLabel("Add to playlist"),t.Style({display:o?"":"none"}),t.OnClick(it({command:se.AddToPlaylist()}))],["+"]),t.span([t.Id("player-track"),t.Role("button"),t.Tabindex(0),t.AriaLabel("Toggle queue and tracks sidebar"),t.Style({display:o&&n.trackIdx!==null?"inline-block":"none"}),t.OnClick(it({command:se.ToggleSidebar()})),t.OnKeyDownPreventDefault(WW(it({command:se.ToggleSidebar()})))],[o&&n.trackIdx!==null?`#${n.trackIdx+1}`:""]),t.span([t.Id("player-title"),t.Role("button"),t.Tabindex(0),t.AriaLabel("Toggle queue and tracks sidebar"),t.OnClick(it({command:se.ToggleSidebar()})),t.OnKeyDownPreventDefault(WW(it({command:se.ToggleSidebar()})))],[n.title])]),t.a([t.Id("player-artist"),t.Attribute("hx-boost","false"),t.Attribute("onclick","Player.openConcert(event)"),t.Href(r?`/concerts/${n.concertId}`:"#"),t.Attribute("hx-target","#content"),t.Attribute("hx-select","#content"),t.Attribute("hx-swap","outerHTML show:window:top"),t.Attribute("hx-push-url","true"),t.Title("View concert")],[n.artist]),t.span([t.Id("player-playlist"),t.Style({display:n.playlistLabel!==null?"":"none"})],[n.playlistLabel??""])]),t.span([t.Id("player-error"),t.Role("alert"),t.Style({display:u?"inline":"none"})],[u]),t.span([t.Id("player-status"),t.AriaLive("polite"),t.Style({display:f?"inline":"none"})],[f]),t.button([t.Id("player-watch"),t.Title("Watch video in player"),t.Style({display:n.isVideo?"inline-block":"none"}),t.OnClick(it({command:se.Watch()}))],["Watch"]),t.button([t.Id("player-open"),t.Title("Open in system player"),t.AriaLabel("Open in system player"),t.Style({display:n.watchUrl!==null?"inline-block":"none"}),t.OnClick(it({command:se.OpenExternal()}))],["\u229E"]),t.button([t.Id("player-delete"),t.Title("Delete this track"),t.AriaLabel("Delete this track"),t.Style({display:o&&!n.liked?"inline-block":"none"}),t.OnClick(it({command:se.DeleteTrack()}))],[t.span([t.Class("icon-trash")],[])]),t.button([t.Id("player-prev"),t.Title("Previous track"),t.AriaLabel("Previous track"),t.Disabled(!c),t.OnClick(it({command:se.SkipToPrev()}))],["\u23EE"]),t.button([t.Id("player-play-pause"),t.AriaLabel(e.isPlaying?"Pause":"Play"),t.OnClick(it({command:se.TogglePause()}))],[e.isPlaying?"\u23F8":"\u25B6"]),t.button([t.Id("player-next"),t.Title("Next track"),t.AriaLabel("Next track"),t.Disabled(!a),t.OnClick(it({command:se.SkipToNext()}))],["\u23ED"]),t.input([t.Id("player-seek"),t.Type("range"),t.AriaLabel("Seek"),t.Min("0"),t.Max(String(Math.ceil(e.audioTime.duration))),t.Value(String(e.audioTime.currentTime)),t.Step("1"),t.Disabled(e.audioTime.duration<=0),t.OnInput(d=>{let g=Number(d),y=Number.isFinite(g)?g:e.audioTime.currentTime;return it({command:se.Seek({seconds:y})})})]),t.span([t.Id("player-time")],[`${lO(e.audioTime.currentTime)} / ${lO(e.audioTime.duration)}`])])}function AOe(e){let t=_n();return t.aside([t.Id("player-sidebar")],[t.button([t.Id("sidebar-close"),t.Type("button"),t.Title("Close"),t.AriaLabel("Close sidebar"),t.OnClick(it({command:se.CloseSidebar()}))],["\xD7"]),t.div([t.Id("sidebar-resize"),t.AriaHidden(!0)],[]),t.div([t.Class("sidebar-top-spacer")],[]),TOe(e),IOe(e)])}var ZW=e=>_n().div([],[vOe(e),AOe(e)]);var COe=()=>[xW,[RW()]],QW=e=>qf.makeElement({Model:gW,Flags:yW,flags:m.succeed({}),init:COe,update:HW,view:ZW,subscriptions:FW,ports:YS,container:e});var YW=document.getElementById("player-root"),OOe=YW?qf.embed(QW(YW)):null,bt=e=>{OOe?.ports.command.send(e)};window.Player={playAlbum(e,t){return bt(se.PlayAlbum({concertId:t})),Promise.resolve()},playTrack(e,t,n){return bt(se.PlayTrack({concertId:t,trackIdx:n})),Promise.resolve()},playTracks(e,t){return bt(se.PlayTracks({concertId:t})),Promise.resolve()},startAlbum(e,t,n=!0){return bt(se.StartAlbum({concertId:t,recordListen:n})),Promise.resolve(!0)},startTrack(e,t,n){return bt(se.StartTrack({concertId:t,trackIdx:n})),Promise.resolve(!0)},togglePause(){bt(se.TogglePause())},seek(e){let t=typeof e=="string"?parseFloat(e):e;isNaN(t)||bt(se.Seek({seconds:t}))},skipToNext(){return bt(se.SkipToNext()),Promise.resolve()},skipToPrev(){return bt(se.SkipToPrev()),Promise.resolve()},watch(){bt(se.Watch())},openExternal(){return bt(se.OpenExternal()),Promise.resolve()},watchTrackDirect(e,t,n){return bt(se.WatchTrackDirect({concertId:t,trackIdx:n})),Promise.resolve()},toggleLike(){return bt(se.ToggleLike()),Promise.resolve()},deleteTrack(){return bt(se.DeleteTrack()),Promise.resolve()},openConcert(e){if(!(e instanceof MouseEvent)||!(e.currentTarget instanceof HTMLAnchorElement))return;let t=e.currentTarget;if(gH(e,t))return;let n=t.getAttribute("href");!n||n==="#"||!window.htmx||(e.preventDefault(),window.htmx.ajax("GET",n,{source:t}))},openSidebar(){document.body.classList.add("sidebar-open"),bt(se.OpenSidebar())},closeSidebar(){document.body.classList.remove("sidebar-open"),bt(se.CloseSidebar())},toggleSidebar(){bt(se.ToggleSidebar())},sidebarDeleteTrack(e,t){return bt(se.SidebarDeleteTrack({concertId:e,trackIdx:t})),Promise.resolve()},playQueueEntryNow(e){bt(se.PlayQueueEntryNow({pos:e}))},dequeue(e){bt(se.Dequeue({pos:e}))},enqueue(e,t,n,r){bt(se.Enqueue({concertId:e,trackIdx:t,title:n,liked:r}))},playAlbumAt(e,t){return bt(se.PlayAlbumAt({concertId:e,seconds:t})),Promise.resolve()},nowPlaying(){return SH()},playPlaylist(e){return bt(se.PlayPlaylist({playlistId:e})),Promise.resolve()},addToPlaylist(){bt(se.AddToPlaylist())},stopPlayback(){bt(se.StopPlayback())},playConcert(e){return bt(se.PlayConcert({concertId:e})),Promise.resolve()},playConcertFrom(e,t){return bt(se.PlayConcertFrom({concertId:e,pos:t})),Promise.resolve()},sidebarDeleteInterlude(e,t){return bt(se.SidebarDeleteInterlude({concertId:e,interludeIdx:t})),Promise.resolve()}};})();
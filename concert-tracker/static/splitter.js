@@ -78,4 +78,4 @@ Received (typeof ${typeof s}): ${JSON.stringify(s)}`}))))}),RequestGetMessageSch
 
   export default defineConfig({ plugins: [foldkit()] })
 
-Starting without HMR support.`),y.succeed(void 0)))):y.succeed(void 0)},vq=e=>{Jb.runMain(Iq(y.flatMap(Aq(e.runtimeId),e.start)))},fAe=(e,t)=>{let n={};return G.isNotUndefined(e)&&(Object.entries(e.inbound??{}).forEach(([r,o])=>{n[r]={send:s=>t.sendInbound(r,o,s)}}),Object.entries(e.outbound??{}).forEach(([r,o])=>{n[r]={subscribe:s=>t.addListener(o,s)}})),n},Cq=e=>{let t=wq.get(e);if(G.isUndefined(t))throw new Error("[foldkit] embed expects a program created by makeApplication or makeElement.");let n=t;if(n.isEmbedActive)throw new Error("[foldkit] This program is already embedded. Dispose the existing handle first, or create a separate program: each program owns one container.");n.isEmbedActive=!0;let r=XIe(),o=be(g.match(n.maybeActiveFiber,{onNone:()=>y.void,onSome:u=>y.asVoid(Kr.await(u))}),y.andThen(Aq(e.runtimeId)),y.flatMap(u=>n.startWith(g.some(r),u))),s=y.runFork(Iq(o));n.maybeActiveFiber=g.some(s);let i=!1,a=()=>{i||(i=!0,r.dispose(),n.isEmbedActive=!1,y.runFork(Kr.interrupt(s)))};return{ports:fAe(e.ports,r),dispose:a}};var iS={};Be(iS,{aggregate:()=>kj,animationFrame:()=>Oq,lift:()=>Ij,make:()=>Tj,persistent:()=>Wb});var mAe=e=>te.callback(t=>y.acquireRelease(y.sync(()=>{let n={frameId:0,lastTime:performance.now()},r=o=>{let s=o-n.lastTime;n.lastTime=o,jn.offerUnsafe(t,e(s)),n.frameId=requestAnimationFrame(r)};return n.frameId=requestAnimationFrame(r),n}),n=>y.sync(()=>cancelAnimationFrame(n.frameId))).pipe(y.flatMap(()=>y.never))),Oq=e=>({dependenciesSchema:x.Struct({isActive:x.Boolean}),modelToDependencies:t=>({isActive:e.isActive(t)}),dependenciesToStream:({isActive:t})=>te.when(mAe(e.toMessage),y.sync(()=>t))});function hAe(e){return document.getElementById(e)}function Mq(e,t){let n=hAe(e);if(n===null)return null;if(!(n instanceof t))throw new Error(`expected #${e} to be a ${t.name}`);return n}var rC=class extends Error{constructor(t,n){super(n??`HTTP ${t}`),this.name="ApiError",this.status=t}};async function _q(e){return await e.json()}async function aS(e,t){let n=await fetch(e,t);if(!n.ok)throw new rC(n.status);return _q(n)}async function Rq(e,t){let n=await fetch(e,t);return n.ok?_q(n):null}async function Pq(e,t,n="POST"){let r={method:n};return t!==void 0&&(r.headers={"Content-Type":"application/json"},r.body=JSON.stringify(t)),fetch(e,r)}function cS(e,t,n){return n<t||e<t?t:e>n?n:e}function Nq(e){return Math.round(e*1e3)/1e3}function Fq(e){if(typeof e!="string")return NaN;let t=e.trim();if(t==="")return NaN;if(t.indexOf(":")===-1){let o=Number(t);return Number.isFinite(o)?o:NaN}let n=t.split(":");if(n.length>3)return NaN;let r=0;for(let o of n){if(o.trim()==="")return NaN;let s=Number(o);if(!Number.isFinite(s)||s<0)return NaN;r=r*60+s}return r}function Dq(e){(!Number.isFinite(e)||e<0)&&(e=0);let t=Math.floor(e/60),r=(e-t*60).toFixed(1).padStart(4,"0");return t+":"+r}function uS(e){let t=e.user??e.auto;if(!t||t.length===0)return null;let n=t.map(a=>({title:a.title,start:a.start_time,end:a.end_time})),r=n[n.length-1];if(!r)return null;let o=r.end,s=e.media_duration;(s==null||!Number.isFinite(s)||s<o)&&(s=o);let i=[];for(let a=0;a<n.length-1;a++)i.push(Math.abs(n[a].end-n[a+1].start)<=.05);return{duration:s,tracks:n,linked:i}}function oC(e,t,n){let r=e.tracks,o=t===0?0:e.linked[t-1]?r[t-1].start+1:r[t-1].end,s=r[t].end-1,i=cS(n,o,s);return r[t].start=i,t>0&&e.linked[t-1]&&(r[t-1].end=i),e}function sC(e,t,n){let r=e.tracks,o=r.length-1,s=t===o?e.duration:e.linked[t]?r[t+1].end-1:r[t+1].start,i=r[t].start+1,a=cS(n,i,s);return r[t].end=a,t<o&&e.linked[t]&&(r[t+1].start=a),e}function Uq(e,t){return e.linked[t]=!1,e}function Lq(e,t){return e.linked[t]=!0,e.tracks[t+1].start=e.tracks[t].end,e}function lS(e){let n=e.tracks.length-1,r=[{kind:"start",track:0}];for(let o=0;o<n;o++)e.linked[o]?r.push({kind:"end",track:o,boundary:o,linked:!0}):(r.push({kind:"end",track:o,boundary:o,linked:!1}),r.push({kind:"start",track:o+1,boundary:o,linked:!1}));return r.push({kind:"end",track:n}),r}function $q(e,t){return t.kind==="start"?e.tracks[t.track].start:e.tracks[t.track].end}function Bq(e,t,n){return t.kind==="start"?oC(e,t.track,n):sC(e,t.track,n)}function fS(e){let t=[],n=e.tracks;for(let r=0;r<n.length;r++){let o=n[r];o.start<-1e-6&&t.push(`${o.title}: starts before 0`),o.end-o.start<1-1e-6&&t.push(`${o.title}: shorter than 1s`),o.end>e.duration+1e-6&&t.push(`${o.title}: ends past media duration`),r<n.length-1&&o.end>n[r+1].start+1e-6&&t.push(`${o.title} overlaps ${n[r+1].title}`)}return t}function jq(e){return{songs:e.tracks.map(t=>({title:t.title,start_time:Nq(t.start),end_time:Nq(t.end)}))}}var gAe=x.Struct({title:x.String,start:x.Number,end:x.Number}),Um=x.Struct({duration:x.Number,tracks:x.mutable(x.Array(gAe)),linked:x.mutable(x.Array(x.Boolean))}),qq=oe("NoStatus"),zq=oe("StatusOk",{message:x.String}),Hq=oe("StatusError",{message:x.String}),xAe=x.Union([qq,zq,Hq]),Sr={NoStatus:qq,StatusOk:zq,StatusError:Hq},Wq=oe("NotDragging"),Vq=oe("Dragging",{handleIndex:x.Number}),yAe=x.Union([Wq,Vq]),Lm={NotDragging:Wq,Dragging:Vq},Gq=oe("Ready",{editor:Um,mediaUrl:x.Option(x.String),playable:x.Boolean}),Kq=oe("Loading"),Jq=oe("Empty"),Zq=oe("LoadFailed"),bAe=x.Union([Kq,Jq,Zq,Gq]),xu={Loading:Kq,Empty:Jq,LoadFailed:Zq,Ready:Gq},Yq=x.Struct({concertId:x.Number,phase:bAe,busy:x.Boolean,status:xAe,dragState:yAe,playheadFraction:x.Option(x.Number)}),Qq=x.Struct({concertId:x.Number});var pS=an("SucceededFetchSplitterData",{maybeEditor:x.Option(Um),maybeMediaUrl:x.Option(x.String),playable:x.Boolean}),dS=an("FailedFetchSplitterData"),iC=an("PressedHandle",{handleIndex:x.Number}),aC=an("MovedDragPointer",{time:x.Number}),cC=an("ReleasedDragPointer"),uC=an("ChangedTimeInput",{trackIndex:x.Number,kind:x.Literals(["Start","End"]),rawValue:x.String}),lC=an("ToggledBoundary",{boundaryIndex:x.Number}),fC=an("ClickedSubmitSplit"),pC=an("ClickedRevertEdits"),dC=an("ClickedResetToAuto"),mS=an("ClickedAudition",{time:x.Number}),hS=an("CompletedEmitAuditionAt"),gS=an("CompletedSubmitSplit",{status:x.Number,body:x.String}),xS=an("CompletedResetSplit",{status:x.Number,body:x.String}),Xq=oe("RestoredEditor",{editor:Um}),ez=oe("NoSavedEditor"),tz=oe("RevertFetchFailed"),SAe=x.Union([Xq,ez,tz]),yS={RestoredEditor:Xq,NoSavedEditor:ez,RevertFetchFailed:tz},$m=an("CompletedRevertEdits",{outcome:SAe}),Bm=an("CompletedResync",{maybeEditor:x.Option(Um)}),mC=an("ChangedPlayhead",{fraction:x.Option(x.Number)}),_4e=x.Union([pS,dS,iC,aC,cC,uC,lC,fC,pC,dC,mS,hS,gS,xS,$m,Bm,mC]);var jm=-1,yu={inbound:{playhead:ps.inbound(x.Number)},outbound:{auditionAt:ps.outbound(x.Number),cardDirty:ps.outbound(x.Void)}};var nz=Pi.define("FetchSplitterData",{concertId:x.Number},pS,dS)(({concertId:e})=>y.gen(function*(){let[t,n]=yield*y.all([y.tryPromise(()=>aS(`/concerts/${e}/split-timestamps`)),y.tryPromise(()=>Rq(`/concerts/${e}/media-info`))],{concurrency:"unbounded"}),r=g.fromNullishOr(n);return pS({maybeEditor:g.fromNullishOr(uS(t)),maybeMediaUrl:g.flatMap(r,o=>g.fromNullishOr(o.url)),playable:g.match(r,{onNone:()=>!1,onSome:o=>!!o.playable})})}).pipe(y.catch(()=>y.succeed(dS())))),EAe=x.Struct({songs:x.Array(x.Struct({title:x.String,start_time:x.Number,end_time:x.Number}))}),rz=(e,t)=>y.gen(function*(){let n=yield*y.tryPromise(()=>Pq(e,t,"POST"));n.status===202&&(yield*ps.emit(yu.outbound.cardDirty,void 0));let r=yield*y.tryPromise(()=>n.text());return{status:n.status,body:r}}).pipe(y.catch(()=>y.succeed({status:0,body:"Network error \u2014 please retry."}))),oz=Pi.define("SubmitSplit",{concertId:x.Number,payload:EAe},gS)(({concertId:e,payload:t})=>rz(`/concerts/${e}/split-timestamps`,t).pipe(y.map(({status:n,body:r})=>gS({status:n,body:r})))),sz=Pi.define("ResetSplit",{concertId:x.Number},xS)(({concertId:e})=>rz(`/concerts/${e}/split-timestamps/reset`,void 0).pipe(y.map(({status:t,body:n})=>xS({status:t,body:n})))),iz=Pi.define("RevertEdits",{concertId:x.Number},$m)(({concertId:e})=>y.tryPromise(()=>aS(`/concerts/${e}/split-timestamps`)).pipe(y.map(t=>{let n=uS(t);return $m({outcome:n===null?yS.NoSavedEditor():yS.RestoredEditor({editor:n})})}),y.catch(()=>y.succeed($m({outcome:yS.RevertFetchFailed()}))))),az=Pi.define("ResyncAfterConflict",{concertId:x.Number},Bm)(({concertId:e})=>y.tryPromise(()=>aS(`/concerts/${e}/split-timestamps`)).pipe(y.map(t=>Bm({maybeEditor:g.fromNullishOr(uS(t))})),y.catch(()=>y.succeed(Bm({maybeEditor:g.none()}))))),cz=Pi.define("EmitAuditionAt",{time:x.Number},hS)(({time:e})=>ps.emit(yu.outbound.auditionAt,e).pipe(y.as(hS())));var hC="splitter-timeline-id",bS=e=>g.fromNullishOr(document.querySelector(`[data-${hC}="${e}"]`)),SS=(e,t,n)=>{let r=t.getBoundingClientRect();return r.width===0?0:cS((e-r.left)/r.width,0,1)*n};var wAe=x.Literals(["Idle","Active"]),TAe=e=>e.dragState._tag==="Dragging"?"Active":"Idle",kAe=e=>e.phase._tag==="Ready"?e.phase.editor.duration:0,uz=iS.make()(e=>({dragPointer:e({dragActivity:wAe,concertId:x.Number,duration:x.Number},{modelToDependencies:t=>({dragActivity:TAe(t),concertId:t.concertId,duration:kAe(t)}),dependenciesToStream:({dragActivity:t,concertId:n,duration:r})=>{let o=te.merge(te.fromEventListener(document,"pointermove").pipe(te.mapEffect(i=>y.sync(()=>g.map(bS(n),a=>aC({time:SS(i.clientX,a,r)})))),te.filter(g.isSome),te.map(i=>i.value)),te.merge(te.fromEventListener(document,"pointerup"),te.fromEventListener(document,"pointercancel")).pipe(te.map(()=>cC()))),s=te.callback(()=>y.acquireRelease(y.sync(()=>{document.documentElement.style.setProperty("user-select","none");let i=document.createElement("style");return i.textContent="* { cursor: ew-resize !important; }",document.head.appendChild(i),i}),i=>y.sync(()=>{document.documentElement.style.removeProperty("user-select"),i.remove()})).pipe(y.flatMap(()=>y.never)));return te.when(te.merge(o,s),y.sync(()=>t==="Active"))}}),playhead:ps.subscription(yu.inbound.playhead,t=>mC({fraction:t===jm?g.none():g.some(t)}))}));var ES=D.withReturnType(),fz=(e,t)=>D.value(e.phase).pipe(ES,D.tag("Ready",n=>t(n.editor)),D.orElse(()=>[e,[]])),xC=(e,t)=>D.value(e.phase).pipe(D.withReturnType(),D.tag("Ready",n=>tt(e,{phase:()=>xu.Ready({editor:t,mediaUrl:n.mediaUrl,playable:n.playable})})),D.orElse(()=>e)),gC=(e,t)=>fz(e,n=>{let r=structuredClone(n);return t(r),[xC(e,r),[]]}),IAe=e=>e.dragState._tag==="Dragging",lz=(e,t,n)=>{if(t===202)return[tt(e,{busy:()=>!1,status:()=>Sr.StatusOk({message:"Splitting\u2026 the track list will update when it finishes."})}),[]];if(t===200)return[tt(e,{busy:()=>!1,status:()=>Sr.StatusOk({message:"Already using the automatic split."})}),[]];let r=tt(e,{busy:()=>!1,status:()=>Sr.StatusError({message:n||`Request failed (${t})`})});return t===409?[r,[az({concertId:e.concertId})]]:[r,[]]},pz=(e,t)=>D.value(t).pipe(ES,D.tagsExhaustive({SucceededFetchSplitterData:({maybeEditor:n,maybeMediaUrl:r,playable:o})=>[tt(e,{phase:()=>g.match(n,{onNone:()=>xu.Empty(),onSome:s=>xu.Ready({editor:s,mediaUrl:r,playable:o})})}),[]],FailedFetchSplitterData:()=>[tt(e,{phase:()=>xu.LoadFailed()}),[]],PressedHandle:({handleIndex:n})=>[tt(e,{dragState:()=>Lm.Dragging({handleIndex:n})}),[]],MovedDragPointer:({time:n})=>D.value(e.dragState).pipe(ES,D.tag("Dragging",({handleIndex:r})=>gC(e,o=>{g.match(A.get(lS(o),r),{onNone:()=>{},onSome:s=>Bq(o,s,n)})})),D.orElse(()=>[e,[]])),ReleasedDragPointer:()=>[tt(e,{dragState:()=>Lm.NotDragging()}),[]],ChangedTimeInput:({trackIndex:n,kind:r,rawValue:o})=>{let s=Fq(o);if(!Number.isFinite(s))return[tt(e,{status:()=>Sr.StatusError({message:"Enter a time like 2:05.0"})}),[]];let i=e.status._tag==="StatusError"?tt(e,{status:()=>Sr.NoStatus()}):e;return gC(i,a=>{r==="Start"?oC(a,n,s):sC(a,n,s)})},ToggledBoundary:({boundaryIndex:n})=>gC(e,r=>{g.getOrElse(A.get(r.linked,n),()=>!1)?Uq(r,n):Lq(r,n)}),ClickedAudition:({time:n})=>IAe(e)?[e,[]]:[e,[cz({time:n})]],ClickedSubmitSplit:()=>fz(e,n=>{let r=A.get(fS(n),0);return g.match(r,{onSome:o=>[tt(e,{status:()=>Sr.StatusError({message:o})}),[]],onNone:()=>[tt(e,{busy:()=>!0,status:()=>Sr.NoStatus()}),[oz({concertId:e.concertId,payload:jq(n)})]]})}),ClickedResetToAuto:()=>[tt(e,{busy:()=>!0,status:()=>Sr.NoStatus()}),[sz({concertId:e.concertId})]],ClickedRevertEdits:()=>[tt(e,{busy:()=>!0,status:()=>Sr.StatusOk({message:"Discarding edits\u2026"})}),[iz({concertId:e.concertId})]],CompletedSubmitSplit:({status:n,body:r})=>lz(e,n,r),CompletedResetSplit:({status:n,body:r})=>lz(e,n,r),CompletedRevertEdits:({outcome:n})=>D.value(n).pipe(ES,D.tag("RestoredEditor",({editor:r})=>[tt(xC(e,r),{busy:()=>!1,status:()=>Sr.StatusOk({message:"Restored the last saved times."})}),[]]),D.tag("NoSavedEditor",()=>[tt(e,{busy:()=>!1,status:()=>Sr.StatusError({message:"No saved times to restore."})}),[]]),D.tag("RevertFetchFailed",()=>[tt(e,{busy:()=>!1,status:()=>Sr.StatusError({message:"Could not load saved times \u2014 please retry."})}),[]]),D.exhaustive),CompletedResync:({maybeEditor:n})=>g.match(n,{onNone:()=>[e,[]],onSome:r=>[xC(e,r),[]]}),ChangedPlayhead:({fraction:n})=>[tt(e,{playheadFraction:()=>n}),[]],CompletedEmitAuditionAt:()=>[e,[]]}));var qm=(e,t)=>e>0?t/e*100:0,AAe=e=>D.value(e).pipe(D.withReturnType(),D.tag("StatusOk",()=>"splitter-status splitter-status-ok"),D.tag("StatusError",()=>"splitter-status splitter-status-error"),D.tag("NoStatus",()=>"splitter-status"),D.exhaustive),vAe=e=>D.value(e).pipe(D.withReturnType(),D.tag("StatusOk",({message:t})=>t),D.tag("StatusError",({message:t})=>t),D.tag("NoStatus",()=>""),D.exhaustive),CAe=(e,t,n)=>{let r=Vn(),o=A.isArrayNonEmpty(fS(e));return r.div([r.Class("splitter-toolbar")],[r.span([r.Class(AAe(n)),...n._tag==="StatusError"?[r.Role("alert")]:[r.AriaLive("polite")]],[vAe(n)]),r.button([r.Class("splitter-submit"),r.Type("button"),r.Disabled(t||o),r.OnClick(fC())],["Split with these times"]),r.button([r.Class("splitter-revert"),r.Type("button"),r.Disabled(t),r.OnClick(pC())],["Discard my edits"]),r.button([r.Class("splitter-reset"),r.Type("button"),r.Disabled(t),r.OnClick(dC())],["Reset to auto"])])},OAe=(e,t,n)=>{let r=Vn();return r.keyed("div")(`seg-${t}`,[r.Class("splitter-seg"),r.Title(e.title),r.Style({left:`${qm(n,e.start)}%`,width:`${qm(n,e.end-e.start)}%`})],[r.span([r.Class("splitter-seg-label")],[`${t+1}. ${e.title}`])])},MAe=1e-6,_Ae=(e,t)=>{let n=Vn(),r=g.getOrElse(g.map(A.get(e.tracks,t),a=>a.end),()=>0),s=g.getOrElse(g.map(A.get(e.tracks,t+1),a=>a.start),()=>0)-r,i=s>MAe;return n.keyed("div")(`gap-${t}`,[n.Class("splitter-gap"),n.Style(i?{display:"block",left:`${qm(e.duration,r)}%`,width:`${qm(e.duration,s)}%`}:{display:"none"})],[])},RAe=(e,t,n,r)=>{let o=Vn(),s=r._tag==="Dragging"&&r.handleIndex===n;return o.keyed("div")(`handle-${n}`,[o.Class(`splitter-handle splitter-handle-${t.kind}${s?" dragging":""}`),o.Style({left:`${qm(e.duration,$q(e,t))}%`}),o.OnPointerDown(()=>g.some(iC({handleIndex:n})))],[])},PAe=(e,t,n)=>g.map(bS(e),r=>mS({time:SS(n,r,t)})),NAe=(e,t,n,r,o)=>{let s=Vn(),i=r?[s.OnPointerDown((a,c,u,f,p,d)=>n._tag==="Dragging"?g.none():PAe(e,t.duration,d))]:[];return s.div([s.Class("splitter-timeline"),s.DataAttribute(hC,String(e)),...i],[s.div([s.Class("splitter-playhead"),s.Style(g.match(o,{onNone:()=>({display:"none"}),onSome:a=>({display:"block",left:`${a*100}%`})}))],[]),...A.map(t.tracks,(a,c)=>OAe(a,c,t.duration)),...A.makeBy(Math.max(0,t.tracks.length-1),a=>_Ae(t,a)),...A.map(lS(t),(a,c)=>RAe(t,a,c,n))])},FAe=e=>{let t=Vn(),n=g.isSome(e)?"Audio preview unavailable for this file format.":"Audio preview unavailable \u2014 source file not found.";return[t.keyed("p")("preview-note",[t.Class("splitter-note")],[n])]},DAe=(e,t)=>{let n=Vn(),r=Math.max(0,e.tracks.length-1);return n.div([n.Class("splitter-boundaries")],A.makeBy(r,o=>{let s=g.match(A.get(e.tracks,o),{onNone:()=>"",onSome:c=>c.title}),i=g.match(A.get(e.tracks,o+1),{onNone:()=>"",onSome:c=>c.title}),a=g.getOrElse(A.get(e.linked,o),()=>!0);return n.keyed("div")(`boundary-${o}`,[n.Class("splitter-boundary")],[n.span([n.Class("splitter-boundary-label")],[`${s} \u2192 ${i}`]),n.button([n.Class("splitter-detach"),n.Type("button"),n.Disabled(t),n.OnClick(lC({boundaryIndex:o}))],[a?"Detach (add gap)":"Link (remove gap)"])])}))},dz=(e,t)=>{let n=Vn();return n.button([n.Class("splitter-play"),n.Type("button"),n.Title("Play from here"),n.AriaLabel("Play from here"),n.Disabled(!t),...t?[n.OnClick(mS({time:e}))]:[]],["\u25B6"])},mz=(e,t,n)=>{let r=Vn();return r.input([r.Class("splitter-time"),r.Type("text"),r.Attribute("inputmode","decimal"),r.Value(Dq(n)),r.OnChange(o=>uC({trackIndex:e,kind:t,rawValue:o}))])},UAe=3,LAe=(e,t,n)=>{let r=Vn();return r.keyed("tr")(`row-${t}`,[],[r.td([r.Class("splitter-num")],[String(t+1)]),r.td([r.Class("splitter-title")],[e.title]),r.td([],[mz(t,"Start",e.start),dz(e.start,n)]),r.td([],[mz(t,"End",e.end),dz(Math.max(0,e.end-UAe),n)])])},$Ae=(e,t)=>{let n=Vn();return n.table([n.Class("splitter-table")],[n.thead([],[n.tr([],A.map(["#","Track","Start","End (\u25B6 auditions last 3s)"],r=>n.th([],[r])))]),n.tbody([],A.map(e.tracks,(r,o)=>LAe(r,o,t)))])},BAe=(e,t,n,r)=>Vn().keyed("div")("ready",[],[CAe(t,e.busy,e.status),NAe(e.concertId,t,e.dragState,r,e.playheadFraction),...r?[]:FAe(n),DAe(t,e.busy),$Ae(t,r)]),hz=e=>{let t=Vn();return D.value(e.phase).pipe(D.withReturnType(),D.tag("Loading",()=>t.keyed("p")("loading",[t.Class("splitter-status")],["Loading\u2026"])),D.tag("Empty",()=>t.keyed("p")("empty",[t.Class("splitter-status")],["No split points yet \u2014 run an automatic split first, then come back to fine-tune them."])),D.tag("LoadFailed",()=>t.keyed("p")("load-failed",[t.Class("splitter-status splitter-status-error")],["Could not load split timestamps."])),D.tag("Ready",n=>BAe(e,n.editor,n.mediaUrl,n.playable)),D.exhaustive)};var jAe=e=>[{concertId:e.concertId,phase:xu.Loading(),busy:!1,status:Sr.NoStatus(),dragState:Lm.NotDragging(),playheadFraction:g.none()},[nz({concertId:e.concertId})]],gz=(e,t)=>kf.makeElement({Model:Yq,Flags:Qq,flags:y.succeed(t),init:jAe,update:pz,view:hz,subscriptions:uz,ports:yu,container:e});var qAe=(e,t)=>kf.embed(gz(e,{concertId:t})),zm=null;function zAe(e,t){if(!window.Player||t.paused||!Number.isFinite(t.duration)||t.duration<=0)return jm;let n=window.Player.nowPlaying();return n.concertId!==e||n.trackIdx!==null?jm:t.currentTime/t.duration}function HAe(e){document.getElementById("concert-"+e)&&window.htmx&&window.htmx.ajax("GET",`/concerts/${e}/status`,{target:"#concert-"+e,swap:"outerHTML"})}function WAe(e,t,n){e.classList.add("splitter-open"),t&&(t.textContent="Hide track splitter");let r=document.createElement("div");r.id=`splitter-widget-${n}`,e.replaceChildren(r);let o=qAe(r,n);o.ports.auditionAt.subscribe(a=>{window.Player?.playAlbumAt(n,a)}),o.ports.cardDirty.subscribe(()=>HAe(n));let s=Mq("player-audio",HTMLMediaElement),i=s?()=>o.ports.playhead.send(zAe(n,s)):()=>{};s&&s.addEventListener("timeupdate",i),zm={concertId:n,handle:o,playheadHandler:i}}function VAe(e,t){if(zm){let n=document.getElementById("player-audio");n&&n.removeEventListener("timeupdate",zm.playheadHandler),zm.handle.dispose()}e.replaceChildren(),e.classList.remove("splitter-open"),t&&(t.textContent="Edit track splits"),zm=null}function GAe(e){let t=document.getElementById("splitter");if(!t)return;if(t.classList.contains("splitter-open")){VAe(t,e);return}let n=Number(t.getAttribute("data-concert-id"));Number.isNaN(n)||WAe(t,e,n)}var KAe={toggle:GAe};window.Splitter=KAe;})();
+Starting without HMR support.`),y.succeed(void 0)))):y.succeed(void 0)},vq=e=>{Jb.runMain(Iq(y.flatMap(Aq(e.runtimeId),e.start)))},fAe=(e,t)=>{let n={};return G.isNotUndefined(e)&&(Object.entries(e.inbound??{}).forEach(([r,o])=>{n[r]={send:s=>t.sendInbound(r,o,s)}}),Object.entries(e.outbound??{}).forEach(([r,o])=>{n[r]={subscribe:s=>t.addListener(o,s)}})),n},Cq=e=>{let t=wq.get(e);if(G.isUndefined(t))throw new Error("[foldkit] embed expects a program created by makeApplication or makeElement.");let n=t;if(n.isEmbedActive)throw new Error("[foldkit] This program is already embedded. Dispose the existing handle first, or create a separate program: each program owns one container.");n.isEmbedActive=!0;let r=XIe(),o=be(g.match(n.maybeActiveFiber,{onNone:()=>y.void,onSome:u=>y.asVoid(Kr.await(u))}),y.andThen(Aq(e.runtimeId)),y.flatMap(u=>n.startWith(g.some(r),u))),s=y.runFork(Iq(o));n.maybeActiveFiber=g.some(s);let i=!1,a=()=>{i||(i=!0,r.dispose(),n.isEmbedActive=!1,y.runFork(Kr.interrupt(s)))};return{ports:fAe(e.ports,r),dispose:a}};var iS={};Be(iS,{aggregate:()=>kj,animationFrame:()=>Oq,lift:()=>Ij,make:()=>Tj,persistent:()=>Wb});var mAe=e=>te.callback(t=>y.acquireRelease(y.sync(()=>{let n={frameId:0,lastTime:performance.now()},r=o=>{let s=o-n.lastTime;n.lastTime=o,jn.offerUnsafe(t,e(s)),n.frameId=requestAnimationFrame(r)};return n.frameId=requestAnimationFrame(r),n}),n=>y.sync(()=>cancelAnimationFrame(n.frameId))).pipe(y.flatMap(()=>y.never))),Oq=e=>({dependenciesSchema:x.Struct({isActive:x.Boolean}),modelToDependencies:t=>({isActive:e.isActive(t)}),dependenciesToStream:({isActive:t})=>te.when(mAe(e.toMessage),y.sync(()=>t))});function hAe(e){return document.getElementById(e)}function Mq(e,t){let n=hAe(e);if(n===null)return null;if(!(n instanceof t))throw new Error(`expected #${e} to be a ${t.name}`);return n}var rC=class extends Error{constructor(t,n){super(n??`HTTP ${t}`),this.name="ApiError",this.status=t}};async function _q(e){return await e.json()}async function aS(e,t){let n=await fetch(e,t);if(!n.ok)throw new rC(n.status);return _q(n)}async function Rq(e,t){let n=await fetch(e,t);return n.ok?_q(n):null}async function Pq(e,t,n="POST"){let r={method:n};return t!==void 0&&(r.headers={"Content-Type":"application/json"},r.body=JSON.stringify(t)),fetch(e,r)}function cS(e,t,n){return n<t||e<t?t:e>n?n:e}function Nq(e){return Math.round(e*1e3)/1e3}function Fq(e){if(typeof e!="string")return NaN;let t=e.trim();if(t==="")return NaN;if(t.indexOf(":")===-1){let o=Number(t);return Number.isFinite(o)?o:NaN}let n=t.split(":");if(n.length>3)return NaN;let r=0;for(let o of n){if(o.trim()==="")return NaN;let s=Number(o);if(!Number.isFinite(s)||s<0)return NaN;r=r*60+s}return r}function Dq(e){(!Number.isFinite(e)||e<0)&&(e=0);let t=Math.floor(e/60),r=(e-t*60).toFixed(1).padStart(4,"0");return t+":"+r}function uS(e){let t=e.user??e.auto;if(!t||t.length===0)return null;let n=t.map(a=>({title:a.title,start:a.start_time,end:a.end_time})),r=n[n.length-1];if(!r)return null;let o=r.end,s=e.media_duration;(s==null||!Number.isFinite(s)||s<o)&&(s=o);let i=[];for(let a=0;a<n.length-1;a++)i.push(Math.abs(n[a].end-n[a+1].start)<=.05);return{duration:s,tracks:n,linked:i,review:t.map(a=>{let c=a.boundary;return!c||c.confidence>=.6?null:`Low confidence (${Math.round(c.confidence*100)}%): ${{recording_start:"start of recording",supplied:"supplied timestamp",overlay:"title overlay",fuzzy_overlay:"approximate title overlay",title_only:"title without artist overlay",overlay_cluster:"unreadable title overlay",silence:"silence",crowd:"end of applause/talk",equal_split:"equal split"}[c.source]}`})}}function oC(e,t,n){let r=e.tracks,o=t===0?0:e.linked[t-1]?r[t-1].start+1:r[t-1].end,s=r[t].end-1,i=cS(n,o,s);return r[t].start=i,t>0&&e.linked[t-1]&&(r[t-1].end=i),e}function sC(e,t,n){let r=e.tracks,o=r.length-1,s=t===o?e.duration:e.linked[t]?r[t+1].end-1:r[t+1].start,i=r[t].start+1,a=cS(n,i,s);return r[t].end=a,t<o&&e.linked[t]&&(r[t+1].start=a),e}function Uq(e,t){return e.linked[t]=!1,e}function Lq(e,t){return e.linked[t]=!0,e.tracks[t+1].start=e.tracks[t].end,e}function lS(e){let n=e.tracks.length-1,r=[{kind:"start",track:0}];for(let o=0;o<n;o++)e.linked[o]?r.push({kind:"end",track:o,boundary:o,linked:!0}):(r.push({kind:"end",track:o,boundary:o,linked:!1}),r.push({kind:"start",track:o+1,boundary:o,linked:!1}));return r.push({kind:"end",track:n}),r}function $q(e,t){return t.kind==="start"?e.tracks[t.track].start:e.tracks[t.track].end}function Bq(e,t,n){return t.kind==="start"?oC(e,t.track,n):sC(e,t.track,n)}function fS(e){let t=[],n=e.tracks;for(let r=0;r<n.length;r++){let o=n[r];o.start<-1e-6&&t.push(`${o.title}: starts before 0`),o.end-o.start<1-1e-6&&t.push(`${o.title}: shorter than 1s`),o.end>e.duration+1e-6&&t.push(`${o.title}: ends past media duration`),r<n.length-1&&o.end>n[r+1].start+1e-6&&t.push(`${o.title} overlaps ${n[r+1].title}`)}return t}function jq(e){return{songs:e.tracks.map(t=>({title:t.title,start_time:Nq(t.start),end_time:Nq(t.end)}))}}var gAe=x.Struct({title:x.String,start:x.Number,end:x.Number}),Um=x.Struct({duration:x.Number,tracks:x.mutable(x.Array(gAe)),linked:x.mutable(x.Array(x.Boolean)),review:x.mutable(x.Array(x.NullOr(x.String)))}),qq=oe("NoStatus"),zq=oe("StatusOk",{message:x.String}),Hq=oe("StatusError",{message:x.String}),xAe=x.Union([qq,zq,Hq]),Sr={NoStatus:qq,StatusOk:zq,StatusError:Hq},Wq=oe("NotDragging"),Vq=oe("Dragging",{handleIndex:x.Number}),yAe=x.Union([Wq,Vq]),Lm={NotDragging:Wq,Dragging:Vq},Gq=oe("Ready",{editor:Um,mediaUrl:x.Option(x.String),playable:x.Boolean}),Kq=oe("Loading"),Jq=oe("Empty"),Zq=oe("LoadFailed"),bAe=x.Union([Kq,Jq,Zq,Gq]),xu={Loading:Kq,Empty:Jq,LoadFailed:Zq,Ready:Gq},Yq=x.Struct({concertId:x.Number,phase:bAe,busy:x.Boolean,status:xAe,dragState:yAe,playheadFraction:x.Option(x.Number)}),Qq=x.Struct({concertId:x.Number});var pS=an("SucceededFetchSplitterData",{maybeEditor:x.Option(Um),maybeMediaUrl:x.Option(x.String),playable:x.Boolean}),dS=an("FailedFetchSplitterData"),iC=an("PressedHandle",{handleIndex:x.Number}),aC=an("MovedDragPointer",{time:x.Number}),cC=an("ReleasedDragPointer"),uC=an("ChangedTimeInput",{trackIndex:x.Number,kind:x.Literals(["Start","End"]),rawValue:x.String}),lC=an("ToggledBoundary",{boundaryIndex:x.Number}),fC=an("ClickedSubmitSplit"),pC=an("ClickedRevertEdits"),dC=an("ClickedResetToAuto"),mS=an("ClickedAudition",{time:x.Number}),hS=an("CompletedEmitAuditionAt"),gS=an("CompletedSubmitSplit",{status:x.Number,body:x.String}),xS=an("CompletedResetSplit",{status:x.Number,body:x.String}),Xq=oe("RestoredEditor",{editor:Um}),ez=oe("NoSavedEditor"),tz=oe("RevertFetchFailed"),SAe=x.Union([Xq,ez,tz]),yS={RestoredEditor:Xq,NoSavedEditor:ez,RevertFetchFailed:tz},$m=an("CompletedRevertEdits",{outcome:SAe}),Bm=an("CompletedResync",{maybeEditor:x.Option(Um)}),mC=an("ChangedPlayhead",{fraction:x.Option(x.Number)}),_4e=x.Union([pS,dS,iC,aC,cC,uC,lC,fC,pC,dC,mS,hS,gS,xS,$m,Bm,mC]);var jm=-1,yu={inbound:{playhead:ps.inbound(x.Number)},outbound:{auditionAt:ps.outbound(x.Number),cardDirty:ps.outbound(x.Void)}};var nz=Pi.define("FetchSplitterData",{concertId:x.Number},pS,dS)(({concertId:e})=>y.gen(function*(){let[t,n]=yield*y.all([y.tryPromise(()=>aS(`/concerts/${e}/split-timestamps`)),y.tryPromise(()=>Rq(`/concerts/${e}/media-info`))],{concurrency:"unbounded"}),r=g.fromNullishOr(n);return pS({maybeEditor:g.fromNullishOr(uS(t)),maybeMediaUrl:g.flatMap(r,o=>g.fromNullishOr(o.url)),playable:g.match(r,{onNone:()=>!1,onSome:o=>!!o.playable})})}).pipe(y.catch(()=>y.succeed(dS())))),EAe=x.Struct({songs:x.Array(x.Struct({title:x.String,start_time:x.Number,end_time:x.Number}))}),rz=(e,t)=>y.gen(function*(){let n=yield*y.tryPromise(()=>Pq(e,t,"POST"));n.status===202&&(yield*ps.emit(yu.outbound.cardDirty,void 0));let r=yield*y.tryPromise(()=>n.text());return{status:n.status,body:r}}).pipe(y.catch(()=>y.succeed({status:0,body:"Network error \u2014 please retry."}))),oz=Pi.define("SubmitSplit",{concertId:x.Number,payload:EAe},gS)(({concertId:e,payload:t})=>rz(`/concerts/${e}/split-timestamps`,t).pipe(y.map(({status:n,body:r})=>gS({status:n,body:r})))),sz=Pi.define("ResetSplit",{concertId:x.Number},xS)(({concertId:e})=>rz(`/concerts/${e}/split-timestamps/reset`,void 0).pipe(y.map(({status:t,body:n})=>xS({status:t,body:n})))),iz=Pi.define("RevertEdits",{concertId:x.Number},$m)(({concertId:e})=>y.tryPromise(()=>aS(`/concerts/${e}/split-timestamps`)).pipe(y.map(t=>{let n=uS(t);return $m({outcome:n===null?yS.NoSavedEditor():yS.RestoredEditor({editor:n})})}),y.catch(()=>y.succeed($m({outcome:yS.RevertFetchFailed()}))))),az=Pi.define("ResyncAfterConflict",{concertId:x.Number},Bm)(({concertId:e})=>y.tryPromise(()=>aS(`/concerts/${e}/split-timestamps`)).pipe(y.map(t=>Bm({maybeEditor:g.fromNullishOr(uS(t))})),y.catch(()=>y.succeed(Bm({maybeEditor:g.none()}))))),cz=Pi.define("EmitAuditionAt",{time:x.Number},hS)(({time:e})=>ps.emit(yu.outbound.auditionAt,e).pipe(y.as(hS())));var hC="splitter-timeline-id",bS=e=>g.fromNullishOr(document.querySelector(`[data-${hC}="${e}"]`)),SS=(e,t,n)=>{let r=t.getBoundingClientRect();return r.width===0?0:cS((e-r.left)/r.width,0,1)*n};var wAe=x.Literals(["Idle","Active"]),TAe=e=>e.dragState._tag==="Dragging"?"Active":"Idle",kAe=e=>e.phase._tag==="Ready"?e.phase.editor.duration:0,uz=iS.make()(e=>({dragPointer:e({dragActivity:wAe,concertId:x.Number,duration:x.Number},{modelToDependencies:t=>({dragActivity:TAe(t),concertId:t.concertId,duration:kAe(t)}),dependenciesToStream:({dragActivity:t,concertId:n,duration:r})=>{let o=te.merge(te.fromEventListener(document,"pointermove").pipe(te.mapEffect(i=>y.sync(()=>g.map(bS(n),a=>aC({time:SS(i.clientX,a,r)})))),te.filter(g.isSome),te.map(i=>i.value)),te.merge(te.fromEventListener(document,"pointerup"),te.fromEventListener(document,"pointercancel")).pipe(te.map(()=>cC()))),s=te.callback(()=>y.acquireRelease(y.sync(()=>{document.documentElement.style.setProperty("user-select","none");let i=document.createElement("style");return i.textContent="* { cursor: ew-resize !important; }",document.head.appendChild(i),i}),i=>y.sync(()=>{document.documentElement.style.removeProperty("user-select"),i.remove()})).pipe(y.flatMap(()=>y.never)));return te.when(te.merge(o,s),y.sync(()=>t==="Active"))}}),playhead:ps.subscription(yu.inbound.playhead,t=>mC({fraction:t===jm?g.none():g.some(t)}))}));var ES=D.withReturnType(),fz=(e,t)=>D.value(e.phase).pipe(ES,D.tag("Ready",n=>t(n.editor)),D.orElse(()=>[e,[]])),xC=(e,t)=>D.value(e.phase).pipe(D.withReturnType(),D.tag("Ready",n=>tt(e,{phase:()=>xu.Ready({editor:t,mediaUrl:n.mediaUrl,playable:n.playable})})),D.orElse(()=>e)),gC=(e,t)=>fz(e,n=>{let r=structuredClone(n);return t(r),[xC(e,r),[]]}),IAe=e=>e.dragState._tag==="Dragging",lz=(e,t,n)=>{if(t===202)return[tt(e,{busy:()=>!1,status:()=>Sr.StatusOk({message:"Splitting\u2026 the track list will update when it finishes."})}),[]];if(t===200)return[tt(e,{busy:()=>!1,status:()=>Sr.StatusOk({message:"Already using the automatic split."})}),[]];let r=tt(e,{busy:()=>!1,status:()=>Sr.StatusError({message:n||`Request failed (${t})`})});return t===409?[r,[az({concertId:e.concertId})]]:[r,[]]},pz=(e,t)=>D.value(t).pipe(ES,D.tagsExhaustive({SucceededFetchSplitterData:({maybeEditor:n,maybeMediaUrl:r,playable:o})=>[tt(e,{phase:()=>g.match(n,{onNone:()=>xu.Empty(),onSome:s=>xu.Ready({editor:s,mediaUrl:r,playable:o})})}),[]],FailedFetchSplitterData:()=>[tt(e,{phase:()=>xu.LoadFailed()}),[]],PressedHandle:({handleIndex:n})=>[tt(e,{dragState:()=>Lm.Dragging({handleIndex:n})}),[]],MovedDragPointer:({time:n})=>D.value(e.dragState).pipe(ES,D.tag("Dragging",({handleIndex:r})=>gC(e,o=>{g.match(A.get(lS(o),r),{onNone:()=>{},onSome:s=>Bq(o,s,n)})})),D.orElse(()=>[e,[]])),ReleasedDragPointer:()=>[tt(e,{dragState:()=>Lm.NotDragging()}),[]],ChangedTimeInput:({trackIndex:n,kind:r,rawValue:o})=>{let s=Fq(o);if(!Number.isFinite(s))return[tt(e,{status:()=>Sr.StatusError({message:"Enter a time like 2:05.0"})}),[]];let i=e.status._tag==="StatusError"?tt(e,{status:()=>Sr.NoStatus()}):e;return gC(i,a=>{r==="Start"?oC(a,n,s):sC(a,n,s)})},ToggledBoundary:({boundaryIndex:n})=>gC(e,r=>{g.getOrElse(A.get(r.linked,n),()=>!1)?Uq(r,n):Lq(r,n)}),ClickedAudition:({time:n})=>IAe(e)?[e,[]]:[e,[cz({time:n})]],ClickedSubmitSplit:()=>fz(e,n=>{let r=A.get(fS(n),0);return g.match(r,{onSome:o=>[tt(e,{status:()=>Sr.StatusError({message:o})}),[]],onNone:()=>[tt(e,{busy:()=>!0,status:()=>Sr.NoStatus()}),[oz({concertId:e.concertId,payload:jq(n)})]]})}),ClickedResetToAuto:()=>[tt(e,{busy:()=>!0,status:()=>Sr.NoStatus()}),[sz({concertId:e.concertId})]],ClickedRevertEdits:()=>[tt(e,{busy:()=>!0,status:()=>Sr.StatusOk({message:"Discarding edits\u2026"})}),[iz({concertId:e.concertId})]],CompletedSubmitSplit:({status:n,body:r})=>lz(e,n,r),CompletedResetSplit:({status:n,body:r})=>lz(e,n,r),CompletedRevertEdits:({outcome:n})=>D.value(n).pipe(ES,D.tag("RestoredEditor",({editor:r})=>[tt(xC(e,r),{busy:()=>!1,status:()=>Sr.StatusOk({message:"Restored the last saved times."})}),[]]),D.tag("NoSavedEditor",()=>[tt(e,{busy:()=>!1,status:()=>Sr.StatusError({message:"No saved times to restore."})}),[]]),D.tag("RevertFetchFailed",()=>[tt(e,{busy:()=>!1,status:()=>Sr.StatusError({message:"Could not load saved times \u2014 please retry."})}),[]]),D.exhaustive),CompletedResync:({maybeEditor:n})=>g.match(n,{onNone:()=>[e,[]],onSome:r=>[xC(e,r),[]]}),ChangedPlayhead:({fraction:n})=>[tt(e,{playheadFraction:()=>n}),[]],CompletedEmitAuditionAt:()=>[e,[]]}));var qm=(e,t)=>e>0?t/e*100:0,AAe=e=>D.value(e).pipe(D.withReturnType(),D.tag("StatusOk",()=>"splitter-status splitter-status-ok"),D.tag("StatusError",()=>"splitter-status splitter-status-error"),D.tag("NoStatus",()=>"splitter-status"),D.exhaustive),vAe=e=>D.value(e).pipe(D.withReturnType(),D.tag("StatusOk",({message:t})=>t),D.tag("StatusError",({message:t})=>t),D.tag("NoStatus",()=>""),D.exhaustive),CAe=(e,t,n)=>{let r=Vn(),o=A.isArrayNonEmpty(fS(e));return r.div([r.Class("splitter-toolbar")],[r.span([r.Class(AAe(n)),...n._tag==="StatusError"?[r.Role("alert")]:[r.AriaLive("polite")]],[vAe(n)]),r.button([r.Class("splitter-submit"),r.Type("button"),r.Disabled(t||o),r.OnClick(fC())],["Split with these times"]),r.button([r.Class("splitter-revert"),r.Type("button"),r.Disabled(t),r.OnClick(pC())],["Discard my edits"]),r.button([r.Class("splitter-reset"),r.Type("button"),r.Disabled(t),r.OnClick(dC())],["Reset to auto"])])},OAe=(e,t,n)=>{let r=Vn();return r.keyed("div")(`seg-${t}`,[r.Class("splitter-seg"),r.Title(e.title),r.Style({left:`${qm(n,e.start)}%`,width:`${qm(n,e.end-e.start)}%`})],[r.span([r.Class("splitter-seg-label")],[`${t+1}. ${e.title}`])])},MAe=1e-6,_Ae=(e,t)=>{let n=Vn(),r=g.getOrElse(g.map(A.get(e.tracks,t),a=>a.end),()=>0),s=g.getOrElse(g.map(A.get(e.tracks,t+1),a=>a.start),()=>0)-r,i=s>MAe;return n.keyed("div")(`gap-${t}`,[n.Class("splitter-gap"),n.Style(i?{display:"block",left:`${qm(e.duration,r)}%`,width:`${qm(e.duration,s)}%`}:{display:"none"})],[])},RAe=(e,t,n,r)=>{let o=Vn(),s=r._tag==="Dragging"&&r.handleIndex===n,c=g.flatMap(t.kind==="start"?g.some(t.track):t.linked?g.some(t.track+1):g.none(),u=>g.flatMap(A.get(e.review,u),g.fromNullishOr));return o.keyed("div")(`handle-${n}`,[o.Class(`splitter-handle splitter-handle-${t.kind}${s?" dragging":""}${g.isSome(c)?" splitter-handle-review":""}`),...g.match(c,{onNone:()=>[],onSome:u=>[o.Title(u)]}),o.Style({left:`${qm(e.duration,$q(e,t))}%`}),o.OnPointerDown(()=>g.some(iC({handleIndex:n})))],[])},PAe=(e,t,n)=>g.map(bS(e),r=>mS({time:SS(n,r,t)})),NAe=(e,t,n,r,o)=>{let s=Vn(),i=r?[s.OnPointerDown((a,c,u,f,p,d)=>n._tag==="Dragging"?g.none():PAe(e,t.duration,d))]:[];return s.div([s.Class("splitter-timeline"),s.DataAttribute(hC,String(e)),...i],[s.div([s.Class("splitter-playhead"),s.Style(g.match(o,{onNone:()=>({display:"none"}),onSome:a=>({display:"block",left:`${a*100}%`})}))],[]),...A.map(t.tracks,(a,c)=>OAe(a,c,t.duration)),...A.makeBy(Math.max(0,t.tracks.length-1),a=>_Ae(t,a)),...A.map(lS(t),(a,c)=>RAe(t,a,c,n))])},FAe=e=>{let t=Vn(),n=g.isSome(e)?"Audio preview unavailable for this file format.":"Audio preview unavailable \u2014 source file not found.";return[t.keyed("p")("preview-note",[t.Class("splitter-note")],[n])]},DAe=(e,t)=>{let n=Vn(),r=Math.max(0,e.tracks.length-1);return n.div([n.Class("splitter-boundaries")],A.makeBy(r,o=>{let s=g.match(A.get(e.tracks,o),{onNone:()=>"",onSome:c=>c.title}),i=g.match(A.get(e.tracks,o+1),{onNone:()=>"",onSome:c=>c.title}),a=g.getOrElse(A.get(e.linked,o),()=>!0);return n.keyed("div")(`boundary-${o}`,[n.Class("splitter-boundary")],[n.span([n.Class("splitter-boundary-label")],[`${s} \u2192 ${i}`]),...g.match(g.flatMap(A.get(e.review,o+1),g.fromNullishOr),{onNone:()=>[],onSome:c=>[n.span([n.Class("splitter-review")],[c])]}),n.button([n.Class("splitter-detach"),n.Type("button"),n.Disabled(t),n.OnClick(lC({boundaryIndex:o}))],[a?"Detach (add gap)":"Link (remove gap)"])])}))},dz=(e,t)=>{let n=Vn();return n.button([n.Class("splitter-play"),n.Type("button"),n.Title("Play from here"),n.AriaLabel("Play from here"),n.Disabled(!t),...t?[n.OnClick(mS({time:e}))]:[]],["\u25B6"])},mz=(e,t,n)=>{let r=Vn();return r.input([r.Class("splitter-time"),r.Type("text"),r.Attribute("inputmode","decimal"),r.Value(Dq(n)),r.OnChange(o=>uC({trackIndex:e,kind:t,rawValue:o}))])},UAe=3,LAe=(e,t,n)=>{let r=Vn();return r.keyed("tr")(`row-${t}`,[],[r.td([r.Class("splitter-num")],[String(t+1)]),r.td([r.Class("splitter-title")],[e.title]),r.td([],[mz(t,"Start",e.start),dz(e.start,n)]),r.td([],[mz(t,"End",e.end),dz(Math.max(0,e.end-UAe),n)])])},$Ae=(e,t)=>{let n=Vn();return n.table([n.Class("splitter-table")],[n.thead([],[n.tr([],A.map(["#","Track","Start","End (\u25B6 auditions last 3s)"],r=>n.th([],[r])))]),n.tbody([],A.map(e.tracks,(r,o)=>LAe(r,o,t)))])},BAe=(e,t,n,r)=>Vn().keyed("div")("ready",[],[CAe(t,e.busy,e.status),NAe(e.concertId,t,e.dragState,r,e.playheadFraction),...r?[]:FAe(n),DAe(t,e.busy),$Ae(t,r)]),hz=e=>{let t=Vn();return D.value(e.phase).pipe(D.withReturnType(),D.tag("Loading",()=>t.keyed("p")("loading",[t.Class("splitter-status")],["Loading\u2026"])),D.tag("Empty",()=>t.keyed("p")("empty",[t.Class("splitter-status")],["No split points yet \u2014 run an automatic split first, then come back to fine-tune them."])),D.tag("LoadFailed",()=>t.keyed("p")("load-failed",[t.Class("splitter-status splitter-status-error")],["Could not load split timestamps."])),D.tag("Ready",n=>BAe(e,n.editor,n.mediaUrl,n.playable)),D.exhaustive)};var jAe=e=>[{concertId:e.concertId,phase:xu.Loading(),busy:!1,status:Sr.NoStatus(),dragState:Lm.NotDragging(),playheadFraction:g.none()},[nz({concertId:e.concertId})]],gz=(e,t)=>kf.makeElement({Model:Yq,Flags:Qq,flags:y.succeed(t),init:jAe,update:pz,view:hz,subscriptions:uz,ports:yu,container:e});var qAe=(e,t)=>kf.embed(gz(e,{concertId:t})),zm=null;function zAe(e,t){if(!window.Player||t.paused||!Number.isFinite(t.duration)||t.duration<=0)return jm;let n=window.Player.nowPlaying();return n.concertId!==e||n.trackIdx!==null?jm:t.currentTime/t.duration}function HAe(e){document.getElementById("concert-"+e)&&window.htmx&&window.htmx.ajax("GET",`/concerts/${e}/status`,{target:"#concert-"+e,swap:"outerHTML"})}function WAe(e,t,n){e.classList.add("splitter-open"),t&&(t.textContent="Hide track splitter");let r=document.createElement("div");r.id=`splitter-widget-${n}`,e.replaceChildren(r);let o=qAe(r,n);o.ports.auditionAt.subscribe(a=>{window.Player?.playAlbumAt(n,a)}),o.ports.cardDirty.subscribe(()=>HAe(n));let s=Mq("player-audio",HTMLMediaElement),i=s?()=>o.ports.playhead.send(zAe(n,s)):()=>{};s&&s.addEventListener("timeupdate",i),zm={concertId:n,handle:o,playheadHandler:i}}function VAe(e,t){if(zm){let n=document.getElementById("player-audio");n&&n.removeEventListener("timeupdate",zm.playheadHandler),zm.handle.dispose()}e.replaceChildren(),e.classList.remove("splitter-open"),t&&(t.textContent="Edit track splits"),zm=null}function GAe(e){let t=document.getElementById("splitter");if(!t)return;if(t.classList.contains("splitter-open")){VAe(t,e);return}let n=Number(t.getAttribute("data-concert-id"));Number.isNaN(n)||WAe(t,e,n)}var KAe={toggle:GAe};window.Splitter=KAe;})();
@@ -197,3 +197,38 @@ async fn served_openapi_spec_matches_built_api_doc() {
          (openapi-dump's source) exactly"
     );
 }
+
+/// `/jobs/progress` is an SSE stream whose first event is a `reset` snapshot
+/// (empty here: no jobs). The stream never ends, so only the first frame is read.
+#[tokio::test]
+async fn jobs_progress_streams_a_reset_snapshot_first() {
+    use futures_util::StreamExt;
+
+    let conn = db::connection::open_in_memory().unwrap();
+    let resp = router(test_state(conn))
+        .oneshot(
+            Request::builder()
+                .uri("/jobs/progress")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()
+            .get(axum::http::header::CONTENT_TYPE)
+            .unwrap(),
+        "text/event-stream"
+    );
+    let mut body = resp.into_body().into_data_stream();
+    let frame = tokio::time::timeout(std::time::Duration::from_secs(5), body.next())
+        .await
+        .expect("first SSE frame")
+        .unwrap()
+        .unwrap();
+    assert_eq!(
+        String::from_utf8_lossy(&frame),
+        "event: reset\ndata: []\n\n"
+    );
+}
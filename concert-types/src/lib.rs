@@ -25,6 +25,74 @@ pub struct SongTimestamp {
     pub start_time: f64,
     pub end_time: f64,
     pub duration: f64,
+    /// How the splitter placed this song's start, when it wrote the timestamp.
+    /// Absent for hand-entered timestamps and for files from older splitters.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub boundary: Option<BoundaryProvenance>,
+}
+
+/// Confidence below which a boundary is worth a second look.
+pub const LOW_CONFIDENCE: f64 = 0.6;
+
+/// Where a song's start came from and how much to trust it.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, ToSchema)]
+pub struct BoundaryProvenance {
+    pub source: BoundarySource,
+    /// 0 (a guess) to 1 (certain).
+    pub confidence: f64,
+    /// The adjustment made after the start was found, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub refinement: Option<BoundaryRefinement>,
+}
+
+impl BoundaryProvenance {
+    pub fn new(source: BoundarySource, confidence: f64) -> Self {
+        BoundaryProvenance {
+            source,
+            confidence,
+            refinement: None,
+        }
+    }
+
+    pub fn is_low_confidence(&self) -> bool {
+        self.confidence < LOW_CONFIDENCE
+    }
+}
+
+/// How a song's start was found.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundarySource {
+    /// The first song starts with the recording.
+    RecordingStart,
+    /// Taken from supplied timestamps.
+    Supplied,
+    /// The overlay title matched the set list exactly.
+    Overlay,
+    /// The overlay title matched the set list approximately.
+    FuzzyOverlay,
+    /// The overlay showed only the title, without the artist line.
+    TitleOnly,
+    /// A run of unmatched overlay frames between two detected songs.
+    OverlayCluster,
+    /// A silence in the audio.
+    Silence,
+    /// The end of applause or talk.
+    Crowd,
+    /// The span between known songs was divided equally.
+    EqualSplit,
+}
+
+/// An adjustment applied to a start after it was found.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum BoundaryRefinement {
+    /// Moved to the end of a nearby silence.
+    SnappedToSilence,
+    /// Moved to where music resumed after applause or talk.
+    AfterCrowd,
+    /// Pulled back because the overlay appeared after the song began.
+    PulledBack,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
@@ -48,6 +116,10 @@ pub struct ConcertInfo {
     pub teaser: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub timestamps: Option<Vec<SongTimestamp>>,
+    /// The timeline's interludes (see [`derive_interludes`]) with what the
+    /// splitter heard in them. Written alongside `timestamps`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub interludes: Option<Vec<Interlude>>,
 }
 
 impl ConcertInfo {
@@ -72,6 +144,18 @@ pub struct Interlude {
     pub index: usize,
     pub start_time: f64,
     pub end_time: f64,
+    /// What mostly fills the span, when the splitter could tell.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub label: Option<InterludeLabel>,
+}
+
+/// What an interlude mostly holds: the crowd applauding, or someone talking
+/// between songs.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum InterludeLabel {
+    Applause,
+    Talk,
 }
 
 /// Filename stem (no extension) for the interlude at `index`. The single
@@ -93,6 +177,7 @@ pub fn derive_interludes(songs: &[SongTimestamp], media_duration: f64) -> Vec<In
                 index: interludes.len() + 1,
                 start_time: start,
                 end_time: end,
+                label: None,
             });
         }
     };
@@ -107,6 +192,50 @@ pub fn derive_interludes(songs: &[SongTimestamp], media_duration: f64) -> Vec<In
     interludes
 }
 
+/// File name of the loudness sidecar the splitter publishes next to the
+/// tracks when loudness analysis is enabled.
+pub const LOUDNESS_FILE: &str = "loudness.json";
+
+/// ReplayGain 2.0 reference level: gains bring a track to -18 LUFS.
+pub const REPLAYGAIN_REFERENCE_LUFS: f64 = -18.0;
+
+/// The `loudness.json` sidecar: EBU R128 measurements and the derived
+/// ReplayGain gains for every produced track, plus the concert as a whole.
+/// Written by the splitter, read by concert-tracker's player.
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct LoudnessFile {
+    pub reference_lufs: f64,
+    pub album: GainMeasurement,
+    pub tracks: Vec<TrackLoudness>,
+}
+
+/// Integrated loudness, the gain to the reference level, and the linear
+/// true peak (1.0 = full scale).
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq)]
+pub struct GainMeasurement {
+    pub integrated_lufs: f64,
+    pub gain_db: f64,
+    pub peak: f64,
+}
+
+/// One produced track's measurement, keyed by its file stem (the name shared
+/// by its `.mp4`/`.m4a`).
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq)]
+pub struct TrackLoudness {
+    pub stem: String,
+    #[serde(flatten)]
+    pub measurement: GainMeasurement,
+}
+
+impl LoudnessFile {
+    pub fn track(&self, stem: &str) -> Option<&GainMeasurement> {
+        self.tracks
+            .iter()
+            .find(|track| track.stem == stem)
+            .map(|track| &track.measurement)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -117,6 +246,7 @@ mod tests {
             start_time: start,
             end_time: end,
             duration: end - start,
+            boundary: None,
         }
     }
 
@@ -135,7 +265,8 @@ mod tests {
             vec![Interlude {
                 index: 1,
                 start_time: 0.0,
-                end_time: 10.0
+                end_time: 10.0,
+                label: None
             }]
         );
     }
@@ -149,7 +280,8 @@ mod tests {
             vec![Interlude {
                 index: 1,
                 start_time: 190.0,
-                end_time: 200.0
+                end_time: 200.0,
+                label: None
             }]
         );
     }
@@ -163,7 +295,8 @@ mod tests {
             vec![Interlude {
                 index: 1,
                 start_time: 90.0,
-                end_time: 100.0
+                end_time: 100.0,
+                label: None
             }]
         );
     }
@@ -77,6 +77,33 @@ test("initState returns null with no timestamps and falls back on duration", ()
   assert.equal(st.duration, 180); // falls back to last end time
 });
 
+test("initState flags low-confidence automatic starts for review", () => {
+  const st = P.initState({
+    set_list: ["A", "B", "C"],
+    auto: [
+      {
+        title: "A",
+        start_time: 0,
+        end_time: 100,
+        duration: 100,
+        boundary: { source: "recording_start", confidence: 1 },
+      },
+      {
+        title: "B",
+        start_time: 100,
+        end_time: 200,
+        duration: 100,
+        boundary: { source: "equal_split", confidence: 0.2 },
+      },
+      { title: "C", start_time: 200, end_time: 300, duration: 100 },
+    ],
+    user: null,
+    media_duration: 300,
+  });
+  assert.ok(st);
+  assert.deepEqual(st.review, [null, "Low confidence (20%): equal split", null]);
+});
+
 test("setEnd on a linked boundary moves the next track's start too", () => {
   const st = P.initState(sampleResp());
   assert.ok(st);
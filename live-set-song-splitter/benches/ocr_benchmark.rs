@@ -118,15 +118,79 @@ fn bench_leptess_ocr_fresh_instance(c: &mut Criterion) {
     group.finish();
 }
 
+/// The detection pass over the fixture frames (repeated to a short clip), with one
+/// OCR thread and with a worker pool (`--ocr-threads`).
+#[cfg(any(feature = "leptess-ocr", feature = "paddle-ocr"))]
+fn bench_parallel_detection_ocr(c: &mut Criterion) {
+    use live_set_splitter::decode::Frame;
+    use live_set_splitter::ocr_backend::{default_ocr_choice, OcrPhase};
+    use live_set_splitter::ocr_pool::FrameScanner;
+
+    let fixtures = fixtures_dir();
+    let images: Vec<_> = test_cases()
+        .iter()
+        .filter(|case| !case.file.ends_with("_bw.png"))
+        .map(|case| image::open(fixtures.join(case.file)).unwrap())
+        .collect();
+    let clip: Vec<Frame> = images
+        .iter()
+        .cycle()
+        .take(24)
+        .enumerate()
+        .map(|(index, image)| Frame {
+            index,
+            timestamp: index as f64,
+            image: image.clone(),
+        })
+        .collect();
+
+    let mut group = c.benchmark_group("detection_scan");
+    group.sample_size(10);
+    for threads in [1, 4] {
+        let id = BenchmarkId::new("threads", threads);
+        group.bench_with_input(id, &threads, |b, &threads| {
+            b.iter_batched(
+                || {
+                    let frames = clip.clone().into_iter().map(Ok);
+                    FrameScanner::new(
+                        Box::new(frames),
+                        default_ocr_choice(),
+                        OcrPhase::Detection,
+                        threads,
+                        "bloc party",
+                    )
+                    .unwrap()
+                },
+                |mut scanner| {
+                    let bw = scanner.options().black_and_white;
+                    while let Some(scanned) = scanner.next() {
+                        let mut scanned = scanned.unwrap();
+                        scanner.candidates(&mut scanned, false).unwrap();
+                        if bw {
+                            scanner.candidates(&mut scanned, true).unwrap();
+                        }
+                    }
+                },
+                criterion::BatchSize::PerIteration,
+            );
+        });
+    }
+    group.finish();
+}
+
 #[cfg(feature = "leptess-ocr")]
 criterion_group!(
     benches,
     bench_subprocess_ocr,
     bench_leptess_ocr,
     bench_leptess_ocr_fresh_instance,
+    bench_parallel_detection_ocr,
 );
 
-#[cfg(not(feature = "leptess-ocr"))]
+#[cfg(all(not(feature = "leptess-ocr"), feature = "paddle-ocr"))]
+criterion_group!(benches, bench_subprocess_ocr, bench_parallel_detection_ocr,);
+
+#[cfg(not(any(feature = "leptess-ocr", feature = "paddle-ocr")))]
 criterion_group!(benches, bench_subprocess_ocr,);
 
 criterion_main!(benches);
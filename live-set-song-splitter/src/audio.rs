@@ -1,7 +1,5 @@
-use crate::ffmpeg::create_ffmpeg_command;
-use anyhow::{Context, Result};
-use std::io::Read;
-use std::process::Stdio;
+use crate::decode;
+use anyhow::Result;
 
 pub const SAMPLE_RATE: u32 = 44100;
 pub const WINDOW_SIZE: usize = 4096;
@@ -27,72 +25,13 @@ pub struct SilenceSpan {
     pub duration_seconds: f64,
 }
 
-/// Convert raw little-endian 16-bit mono PCM bytes to f32 samples normalized
-/// to [-1.0, 1.0]. A trailing odd byte (truncated sample) is ignored.
-fn pcm_s16le_to_samples(pcm_data: &[u8]) -> Vec<f32> {
-    pcm_data
-        .chunks_exact(2)
-        .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
-        .collect()
-}
-
+/// The whole soundtrack of `input_file` as mono samples at [`SAMPLE_RATE`]
+/// (see [`crate::decode::audio_samples`]).
 pub fn extract_audio_waveform(input_file: &str) -> Result<Vec<f32>> {
-    // Stream raw PCM from ffmpeg's stdout instead of going through a temp
-    // file: concurrent splitter processes share a working directory, so any
-    // fixed temp path is a race (one process deletes or overwrites the file
-    // while another is reading it).
-    let mut cmd = create_ffmpeg_command().cmd();
-    cmd.args([
-        "-i",
-        input_file,
-        "-vn", // No video
-        "-acodec",
-        "pcm_s16le", // PCM signed 16-bit little-endian
-        "-ar",
-        &SAMPLE_RATE.to_string(), // Sample rate
-        "-ac",
-        "1", // Mono channel
-        "-f",
-        "s16le", // Raw samples, no container header
-        "-",     // Write to stdout
-    ]);
-    cmd.stdout(Stdio::piped()); // create_ffmpeg_command defaults stdout to null
-
-    let mut child = cmd
-        .spawn()
-        .with_context(|| format!("Failed to spawn ffmpeg to decode audio from {}", input_file))?;
-    let mut pcm_data = Vec::new();
-    // Read stdout to EOF before wait(): stderr is inherited, so this single
-    // pipe cannot deadlock. Check the exit status before trusting the bytes —
-    // a failed ffmpeg may have produced a truncated stream.
-    let read_result = child
-        .stdout
-        .take()
-        .ok_or_else(|| anyhow::anyhow!("ffmpeg child process has no stdout pipe"))?
-        .read_to_end(&mut pcm_data);
-    let status = child
-        .wait()
-        .with_context(|| format!("Failed waiting for ffmpeg decoding {}", input_file))?;
-    if !status.success() {
-        return Err(anyhow::anyhow!(
-            "ffmpeg failed to decode audio from {} (exit {:?})",
-            input_file,
-            status.code()
-        ));
+    let samples = decode::audio_samples(input_file, SAMPLE_RATE)?;
+    if samples.is_empty() {
+        log::info!("no audio decoded from {}", input_file);
     }
-    read_result
-        .with_context(|| format!("Failed to read PCM stream from ffmpeg for {}", input_file))?;
-
-    if pcm_data.is_empty() {
-        log::info!("ffmpeg produced no audio data for {}", input_file);
-    }
-    let samples = pcm_s16le_to_samples(&pcm_data);
-    log::debug!(
-        "Extracted {} PCM bytes ({} samples) from {}",
-        pcm_data.len(),
-        samples.len(),
-        input_file
-    );
     Ok(samples)
 }
 
@@ -180,30 +119,6 @@ mod tests {
     use super::*;
     use std::path::{Path, PathBuf};
 
-    #[test]
-    fn test_pcm_s16le_to_samples_known_values() {
-        let bytes = [
-            0x00, 0x00, // 0
-            0xFF, 0x7F, // i16::MAX = 32767
-            0x00, 0x80, // i16::MIN = -32768
-            0x00, 0x40, // 16384
-        ];
-        let samples = pcm_s16le_to_samples(&bytes);
-        assert_eq!(samples.len(), 4);
-        assert_eq!(samples[0], 0.0);
-        assert!((samples[1] - 32767.0 / 32768.0).abs() < 1e-6);
-        assert_eq!(samples[2], -1.0);
-        assert_eq!(samples[3], 0.5);
-    }
-
-    #[test]
-    fn test_pcm_s16le_to_samples_empty_and_odd_input() {
-        assert!(pcm_s16le_to_samples(&[]).is_empty());
-        // A lone trailing byte is a truncated sample and is dropped.
-        assert!(pcm_s16le_to_samples(&[0x12]).is_empty());
-        assert_eq!(pcm_s16le_to_samples(&[0x00, 0x40, 0x7F]), vec![0.5]);
-    }
-
     /// Generate a 1-second sine-wave audio file for extraction tests.
     fn generate_sine_file(frequency: u32) -> PathBuf {
         let path = std::env::temp_dir().join(format!("lsss_test_sine_{}.wav", frequency));
@@ -284,6 +199,8 @@ mod tests {
             "error should name the input: {}",
             message
         );
+        // Only the CLI decoder has an exit code to report.
+        #[cfg(not(feature = "ffmpeg-decode"))]
         assert!(
             message.contains("exit"),
             "error should include the exit code: {}",
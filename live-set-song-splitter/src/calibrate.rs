@@ -0,0 +1,358 @@
+//! Per-concert calibration of the overlay crop.
+//!
+//! Detection OCRs only the strip of each frame where the artist/title card sits.
+//! Studio Tiny Desk concerts put it in the bottom-left corner
+//! ([`DEFAULT_OVERLAY_CROP`]), but Tiny Desk Home concerts, the Juniors series and
+//! re-framed uploads place it elsewhere. Before detection, a text detector looks
+//! at full frames from the start of the video, keeps the text lines that read as
+//! the artist's name with a title line below, and derives the crop from where
+//! those lines recur.
+
+use ::image::DynamicImage;
+use anyhow::Result;
+
+use crate::concert_split::ConcertSplitProgress;
+use crate::decode::{self, Crop, Frame, FrameSpec};
+use crate::ocr::fuzzy_match_artist;
+
+/// Frames are scaled to this size before cropping to the overlay.
+pub(crate) const OVERLAY_SCALE: (u32, u32) = (400, 200);
+
+/// The bottom-left strip of the scaled frame where the studio artist/title card
+/// sits — ffmpeg's `crop=iw/1.5:ih/4:0:160`, whose `y` it clamps to 150 so the
+/// 50-pixel strip fits the 200-pixel frame. Used when calibration finds nothing.
+pub const DEFAULT_OVERLAY_CROP: Crop = Crop {
+    x: 0,
+    y: 150,
+    width: 266,
+    height: 50,
+};
+
+/// Calibration frames are twice the overlay scale so the detector can still read
+/// the card's text on a full frame.
+const CALIBRATION_SCALE: (u32, u32) = (OVERLAY_SCALE.0 * 2, OVERLAY_SCALE.1 * 2);
+
+/// Seconds between calibration samples. A card stays up for several seconds, so
+/// every other second still sees each one more than once.
+const SAMPLE_INTERVAL_SECONDS: usize = 2;
+
+/// How far into the video calibration looks. The first cards appear within the
+/// first couple of songs.
+const CALIBRATION_WINDOW_SECONDS: f64 = 600.0;
+
+/// Artist-line sightings in one place before that place is trusted.
+const MIN_SIGHTINGS: usize = 3;
+
+/// One line of text found on a frame, in that frame's pixels.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextRegion {
+    pub left: u32,
+    pub top: u32,
+    pub width: u32,
+    pub height: u32,
+    pub text: String,
+}
+
+impl TextRegion {
+    fn right(&self) -> u32 {
+        self.left + self.width
+    }
+
+    fn bottom(&self) -> u32 {
+        self.top + self.height
+    }
+}
+
+/// Finds and reads the text lines on a full frame.
+pub trait TextRegionDetector {
+    fn text_regions(&mut self, image: &DynamicImage) -> Result<Vec<TextRegion>>;
+}
+
+/// The detector calibration runs with; `None` when none was compiled in (no
+/// `paddle-ocr` feature).
+fn create_text_region_detector() -> Result<Option<Box<dyn TextRegionDetector>>> {
+    #[cfg(feature = "paddle-ocr")]
+    {
+        Ok(Some(Box::new(crate::ocr_paddle::PaddleTextRegions::new()?)))
+    }
+    #[cfg(not(feature = "paddle-ocr"))]
+    {
+        Ok(None)
+    }
+}
+
+/// The overlay crop detection should use for this concert: `override_crop` when
+/// the caller gave one, otherwise the calibrated crop, otherwise
+/// [`DEFAULT_OVERLAY_CROP`]. The choice and where it came from are reported as a
+/// diagnostic; a calibration failure is a warning, not an error. A build without
+/// a text detector skips calibration without a warning.
+pub(crate) fn resolve_overlay_crop(
+    input_file: &str,
+    artist: &str,
+    override_crop: Option<Crop>,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> Crop {
+    let (crop, source) = match override_crop {
+        Some(crop) => (crop, "from options"),
+        None => match create_text_region_detector().and_then(|detector| {
+            detector
+                .map(|mut detector| calibrate_overlay_crop(input_file, artist, &mut *detector))
+                .transpose()
+        }) {
+            Ok(None) => (DEFAULT_OVERLAY_CROP, "default; no text detector"),
+            Ok(Some(Some(crop))) => (crop, "calibrated"),
+            Ok(Some(None)) => (
+                DEFAULT_OVERLAY_CROP,
+                "default; calibration found no artist overlay",
+            ),
+            Err(e) => {
+                progress(ConcertSplitProgress::Warning(format!(
+                    "Overlay calibration failed, using the default crop: {:#}",
+                    e
+                )));
+                (DEFAULT_OVERLAY_CROP, "default")
+            }
+        },
+    };
+    progress(ConcertSplitProgress::Diagnostic(format!(
+        "Overlay crop: {} in {}x{} frames ({})",
+        crop, OVERLAY_SCALE.0, OVERLAY_SCALE.1, source
+    )));
+    crop
+}
+
+/// Run `detector` over frames from the start of `input_file` and derive the
+/// overlay crop, in [`OVERLAY_SCALE`] pixels, from where `artist` appears. `None`
+/// when the artist's card was not seen often enough.
+pub fn calibrate_overlay_crop(
+    input_file: &str,
+    artist: &str,
+    detector: &mut dyn TextRegionDetector,
+) -> Result<Option<Crop>> {
+    let spec = FrameSpec {
+        start: 0.0,
+        end: Some(CALIBRATION_WINDOW_SECONDS),
+        fps: 1,
+        scale: CALIBRATION_SCALE,
+        crop: None,
+        negate: false,
+    };
+    let frames = decode::video_frames(input_file, spec)?.step_by(SAMPLE_INTERVAL_SECONDS);
+    calibrate_from_frames(frames, artist, detector)
+}
+
+/// [`calibrate_overlay_crop`] over already-decoded [`CALIBRATION_SCALE`] frames.
+/// Stops reading frames as soon as one place has enough sightings.
+fn calibrate_from_frames(
+    frames: impl Iterator<Item = Result<Frame>>,
+    artist: &str,
+    detector: &mut dyn TextRegionDetector,
+) -> Result<Option<Crop>> {
+    let mut sightings = Vec::new();
+    for frame in frames {
+        let regions = detector.text_regions(&frame?.image)?;
+        sightings.extend(artist_lines(&regions, artist).cloned());
+        if let Some(card) = recurring_card(&sightings, CALIBRATION_SCALE.0) {
+            return Ok(Some(to_overlay_scale(card_crop(&card, CALIBRATION_SCALE))));
+        }
+    }
+    Ok(None)
+}
+
+/// Lines of one frame that read as `artist` and have a title line just below,
+/// left-aligned with them — the shape of the card. A band name painted on a kick
+/// drum reads as the artist too, but nothing sits under it.
+fn artist_lines<'a>(
+    regions: &'a [TextRegion],
+    artist: &'a str,
+) -> impl Iterator<Item = &'a TextRegion> {
+    regions.iter().filter(move |line| {
+        fuzzy_match_artist(&line.text, artist)
+            && regions.iter().any(|title| {
+                title.top >= line.top + line.height / 2
+                    && title.top <= line.bottom() + line.height * 2
+                    && title.left.abs_diff(line.left) <= line.height.max(1) * 2
+                    && title != *line
+            })
+    })
+}
+
+/// The typical artist line of the place where at least [`MIN_SIGHTINGS`] of
+/// `sightings` agree: the same top within half a line and the same left edge
+/// within a tenth of the frame width. Outliers (a misread elsewhere on the
+/// frame) form their own smaller groups and are ignored.
+fn recurring_card(sightings: &[TextRegion], frame_width: u32) -> Option<TextRegion> {
+    let near = |a: &TextRegion, b: &TextRegion| {
+        a.top.abs_diff(b.top) <= a.height.max(b.height) / 2
+            && a.left.abs_diff(b.left) <= frame_width / 10
+    };
+    let group = sightings
+        .iter()
+        .map(|anchor| {
+            sightings
+                .iter()
+                .filter(|other| near(anchor, other))
+                .collect::<Vec<_>>()
+        })
+        .max_by_key(|group| group.len())?;
+    if group.len() < MIN_SIGHTINGS {
+        return None;
+    }
+    let median = |value: fn(&TextRegion) -> u32| {
+        let mut values: Vec<u32> = group.iter().map(|r| value(r)).collect();
+        values.sort_unstable();
+        values[values.len() / 2]
+    };
+    let left = median(|r| r.left);
+    let right = median(TextRegion::right).max(left + 1);
+    Some(TextRegion {
+        left,
+        top: median(|r| r.top),
+        width: right - left,
+        height: median(|r| r.height).max(1),
+        text: group[0].text.clone(),
+    })
+}
+
+/// The crop around a card whose artist line is `artist_line`, in the pixels of a
+/// `frame`-sized image: half a line of margin, room for the artist line and two
+/// title lines, and at least as wide as the studio crop (two thirds of the frame)
+/// since titles run longer than names.
+fn card_crop(artist_line: &TextRegion, frame: (u32, u32)) -> Crop {
+    let (frame_width, frame_height) = frame;
+    let margin = artist_line.height / 2;
+    let x = artist_line.left.saturating_sub(margin).min(frame_width - 1);
+    let y = artist_line.top.saturating_sub(margin).min(frame_height - 1);
+    let right = (artist_line.right() + margin).max(x + frame_width * 2 / 3);
+    let bottom = artist_line.top + artist_line.height * 3 + margin;
+    Crop {
+        x,
+        y,
+        width: right.min(frame_width) - x,
+        height: bottom.min(frame_height) - y,
+    }
+}
+
+/// A [`CALIBRATION_SCALE`] crop in [`OVERLAY_SCALE`] pixels.
+fn to_overlay_scale(crop: Crop) -> Crop {
+    let factor = CALIBRATION_SCALE.0 / OVERLAY_SCALE.0;
+    Crop {
+        x: crop.x / factor,
+        y: crop.y / factor,
+        width: (crop.width / factor).max(1),
+        height: (crop.height / factor).max(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn region(left: u32, top: u32, width: u32, height: u32, text: &str) -> TextRegion {
+        TextRegion {
+            left,
+            top,
+            width,
+            height,
+            text: text.to_string(),
+        }
+    }
+
+    /// A card at `(left, top)`: the artist line and a title line below it.
+    fn card(left: u32, top: u32) -> Vec<TextRegion> {
+        vec![
+            region(left, top, 180, 24, "Tank and the Bangas"),
+            region(left, top + 30, 140, 18, "Quick"),
+        ]
+    }
+
+    /// Replays one list of regions per frame.
+    struct Scripted {
+        frames: Vec<Vec<TextRegion>>,
+        calls: usize,
+    }
+
+    impl TextRegionDetector for Scripted {
+        fn text_regions(&mut self, _image: &DynamicImage) -> Result<Vec<TextRegion>> {
+            let regions = self.frames.get(self.calls).cloned().unwrap_or_default();
+            self.calls += 1;
+            Ok(regions)
+        }
+    }
+
+    fn calibrate(frames: Vec<Vec<TextRegion>>) -> (Option<Crop>, usize) {
+        let count = frames.len();
+        let mut detector = Scripted { frames, calls: 0 };
+        let decoded = (0..count).map(|index| {
+            Ok(Frame {
+                index,
+                timestamp: index as f64,
+                image: DynamicImage::new_rgb8(CALIBRATION_SCALE.0, CALIBRATION_SCALE.1),
+            })
+        });
+        let crop = calibrate_from_frames(decoded, "Tank and the Bangas", &mut detector).unwrap();
+        (crop, detector.calls)
+    }
+
+    #[test]
+    fn studio_card_calibrates_to_the_bottom_left_strip() {
+        let mut frames = vec![vec![]; 4];
+        frames.extend(vec![card(20, 310); 3]);
+        frames.push(card(20, 310));
+        let (crop, calls) = calibrate(frames);
+        assert_eq!(
+            crop,
+            Some(Crop {
+                x: 4,
+                y: 149,
+                width: 266,
+                height: 48
+            })
+        );
+        assert_eq!(calls, 7, "stops once the card was seen often enough");
+    }
+
+    #[test]
+    fn card_elsewhere_moves_the_crop() {
+        let (crop, _) = calibrate(vec![card(420, 40); 3]);
+        let crop = crop.unwrap();
+        assert_eq!((crop.x, crop.y), (204, 14));
+        assert!(crop.x + crop.width <= OVERLAY_SCALE.0);
+        assert!(crop.y + crop.height <= OVERLAY_SCALE.1);
+    }
+
+    #[test]
+    fn artist_name_without_a_title_line_is_not_a_card() {
+        let drum_head = vec![region(300, 250, 120, 20, "Tank and the Bangas")];
+        assert_eq!(calibrate(vec![drum_head; 5]).0, None);
+    }
+
+    #[cfg(not(feature = "paddle-ocr"))]
+    #[test]
+    fn no_text_detector_uses_the_default_crop_without_a_warning() {
+        let mut events = Vec::new();
+        let crop = resolve_overlay_crop("/nonexistent/concert.mp4", "Artist", None, &mut |e| {
+            events.push(e)
+        });
+        assert_eq!(crop, DEFAULT_OVERLAY_CROP);
+        assert!(!events
+            .iter()
+            .any(|e| matches!(e, ConcertSplitProgress::Warning(_))));
+        assert!(events.iter().any(|e| matches!(
+            e,
+            ConcertSplitProgress::Diagnostic(message) if message.ends_with("(default; no text detector)")
+        )));
+    }
+
+    #[test]
+    fn the_place_most_sightings_agree_on_wins() {
+        let mut sightings: Vec<TextRegion> = [310, 312, 309, 311]
+            .iter()
+            .map(|&top| region(20, top, 180, 24, "Tank and the Bangas"))
+            .collect();
+        sightings.push(region(500, 40, 180, 24, "Tank and the Bangas"));
+        let card = recurring_card(&sightings, CALIBRATION_SCALE.0).unwrap();
+        assert_eq!((card.left, card.top, card.height), (20, 311, 24));
+        assert_eq!(recurring_card(&sightings[..2], CALIBRATION_SCALE.0), None);
+    }
+}
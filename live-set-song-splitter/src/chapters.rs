@@ -0,0 +1,241 @@
+//! Chaptered remux: a stream copy of the whole source with one chapter per
+//! song and interlude, for players that navigate chapters (VLC, mpv, Apple
+//! apps) instead of a folder of per-track files. No re-encoding — the output is
+//! the source's streams plus an FFmetadata chapter table.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use concert_types::{derive_interludes, ConcertInfo, SongTimestamp};
+
+use crate::concert_split::folder_name;
+use crate::ffmpeg::create_ffmpeg_command;
+
+/// Infix between the concert's folder name and the source extension, e.g.
+/// `Album.chapters.mp4`. Distinct from the downloaded source (`Album.mp4`),
+/// whose lookup matches the file stem exactly.
+pub const CHAPTERS_INFIX: &str = "chapters";
+
+/// One chapter in the remuxed file, in seconds.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Chapter {
+    pub title: String,
+    pub start_time: f64,
+    pub end_time: f64,
+}
+
+/// Filename stem (no extension) of the chaptered copy for `concert`, e.g.
+/// `Album.chapters`.
+pub fn chaptered_file_stem(concert: &ConcertInfo) -> String {
+    format!("{}.{CHAPTERS_INFIX}", folder_name(concert))
+}
+
+/// Filename of the chaptered copy of `input_file` for `concert`. Keeps the
+/// source's container (chapters survive a stream copy into the same muxer);
+/// falls back to Matroska when the source has no extension.
+pub fn chaptered_file_name(concert: &ConcertInfo, input_file: &Path) -> String {
+    let ext = input_file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("mkv");
+    format!("{}.{ext}", chaptered_file_stem(concert))
+}
+
+/// Songs plus, when `media_duration` is known, the interludes between them
+/// (the same spans `--emit-interludes` would cut), in timeline order.
+pub fn chapters_for(timestamps: &[SongTimestamp], media_duration: Option<f64>) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = timestamps
+        .iter()
+        .map(|song| Chapter {
+            title: song.title.clone(),
+            start_time: song.start_time,
+            end_time: song.end_time,
+        })
+        .collect();
+    if let Some(duration) = media_duration {
+        chapters.extend(
+            derive_interludes(timestamps, duration)
+                .into_iter()
+                .map(|interlude| Chapter {
+                    title: format!("Interlude {}", interlude.index),
+                    start_time: interlude.start_time,
+                    end_time: interlude.end_time,
+                }),
+        );
+    }
+    chapters.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    chapters
+}
+
+/// Render an FFmetadata file: global tags from the concert, then one
+/// `[CHAPTER]` per entry with a millisecond timebase.
+pub fn ffmetadata(concert: &ConcertInfo, chapters: &[Chapter]) -> String {
+    let mut out = String::from(";FFMETADATA1\n");
+    let mut tag = |key: &str, value: &str| {
+        if !value.is_empty() {
+            let _ = writeln!(out, "{key}={}", escape_ffmetadata(value));
+        }
+    };
+    tag("title", &concert.album);
+    tag("artist", &concert.artist);
+    tag("album", &concert.album);
+    tag("date", &concert.year().unwrap_or_default());
+    for chapter in chapters {
+        let _ = write!(
+            out,
+            "\n[CHAPTER]\nTIMEBASE=1/1000\nSTART={}\nEND={}\ntitle={}\n",
+            (chapter.start_time * 1000.0).round() as i64,
+            (chapter.end_time * 1000.0).round() as i64,
+            escape_ffmetadata(&chapter.title),
+        );
+    }
+    out
+}
+
+/// FFmetadata treats `=`, `;`, `#`, `\` and newlines as syntax; each must be
+/// backslash-escaped inside a value.
+fn escape_ffmetadata(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for ch in value.chars() {
+        if matches!(ch, '=' | ';' | '#' | '\\' | '\n') {
+            escaped.push('\\');
+        }
+        escaped.push(ch);
+    }
+    escaped
+}
+
+/// Stream-copy `input_file` to `output_file` with `chapters` attached. The
+/// output is written in place; callers that need atomicity write to a
+/// temporary name and rename.
+pub fn write_chaptered_copy(
+    input_file: &Path,
+    output_file: &Path,
+    concert: &ConcertInfo,
+    chapters: &[Chapter],
+) -> Result<()> {
+    anyhow::ensure!(!chapters.is_empty(), "no chapters to write");
+    let metadata = tempfile::Builder::new()
+        .prefix(".chapters-")
+        .suffix(".txt")
+        .tempfile()
+        .context("Failed to create FFmetadata file")?;
+    fs::write(metadata.path(), ffmetadata(concert, chapters))
+        .context("Failed to write FFmetadata file")?;
+
+    let mut ffmpeg = create_ffmpeg_command();
+    ffmpeg
+        .arg("-i")
+        .args([input_file.as_os_str()])
+        .args(["-f", "ffmetadata", "-i"])
+        .args([metadata.path().as_os_str()])
+        .args([
+            "-map",
+            "0",
+            "-map_metadata",
+            "1",
+            "-map_chapters",
+            "1",
+            "-c",
+            "copy",
+            "-y",
+        ])
+        .args([output_file.as_os_str()]);
+    let status = ffmpeg
+        .cmd()
+        .status()
+        .context("Failed to run ffmpeg for chaptered remux")?;
+    if !status.success() {
+        return Err(anyhow!(
+            "Failed to write chaptered copy to {}",
+            output_file.display()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use concert_types::Song;
+
+    fn concert() -> ConcertInfo {
+        ConcertInfo {
+            artist: "Artist; Band".to_string(),
+            source: String::new(),
+            show: String::new(),
+            date: Some("2024-05-01".to_string()),
+            album: "Album".to_string(),
+            description: None,
+            set_list: vec![Song {
+                title: "One".to_string(),
+            }],
+            musicians: Vec::new(),
+            preview_image_url: None,
+            teaser: None,
+            timestamps: None,
+            interludes: None,
+        }
+    }
+
+    fn ts(title: &str, start_time: f64, end_time: f64) -> SongTimestamp {
+        SongTimestamp {
+            title: title.to_string(),
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            boundary: None,
+        }
+    }
+
+    #[test]
+    fn chapters_interleave_interludes_in_timeline_order() {
+        let songs = vec![ts("One", 30.0, 200.0), ts("Two", 260.0, 400.0)];
+        let titles: Vec<String> = chapters_for(&songs, Some(500.0))
+            .into_iter()
+            .map(|chapter| chapter.title)
+            .collect();
+        assert_eq!(
+            titles,
+            ["Interlude 1", "One", "Interlude 2", "Two", "Interlude 3"]
+        );
+    }
+
+    #[test]
+    fn chapters_without_duration_are_songs_only() {
+        let songs = vec![ts("One", 30.0, 200.0), ts("Two", 260.0, 400.0)];
+        assert_eq!(chapters_for(&songs, None).len(), 2);
+    }
+
+    #[test]
+    fn ffmetadata_uses_millisecond_chapters_and_escapes_values() {
+        let chapters = vec![Chapter {
+            title: "A=B #1".to_string(),
+            start_time: 1.2345,
+            end_time: 61.0,
+        }];
+        assert_eq!(
+            ffmetadata(&concert(), &chapters),
+            ";FFMETADATA1\n\
+             title=Album\n\
+             artist=Artist\\; Band\n\
+             album=Album\n\
+             date=2024\n\
+             \n[CHAPTER]\nTIMEBASE=1/1000\nSTART=1235\nEND=61000\ntitle=A\\=B \\#1\n"
+        );
+    }
+
+    #[test]
+    fn chaptered_file_name_keeps_the_source_container() {
+        assert_eq!(
+            chaptered_file_name(&concert(), Path::new("/x/Album.webm")),
+            "Album.chapters.webm"
+        );
+        assert_eq!(
+            chaptered_file_name(&concert(), Path::new("/x/Album")),
+            "Album.chapters.mkv"
+        );
+    }
+}
@@ -0,0 +1,348 @@
+//! Classifying the soundtrack into music, applause, speech and silence, so
+//! boundary refinement and recovery can tell the crowd and between-song banter
+//! from the songs around them, and interludes can be labeled.
+//!
+//! Each half-second window is described by spectral features of its FFT
+//! frames: flatness (broadband noise such as applause is flat, tones are
+//! peaky), centroid, zero-crossing rate and how much the frames' loudness
+//! varies (speech comes in syllables and pauses, music is sustained).
+
+use rustfft::num_complex::Complex;
+use rustfft::FftPlanner;
+
+use crate::audio;
+use crate::recover::adaptive_silence_threshold;
+use concert_types::InterludeLabel;
+
+/// Seconds of audio per classified window.
+const CLASS_WINDOW_SECONDS: f64 = 0.5;
+
+/// Samples per FFT frame (~46ms at 44.1kHz).
+const FFT_SIZE: usize = 2048;
+
+/// Spectral flatness above which a window counts as noise-like (applause).
+const APPLAUSE_MIN_FLATNESS: f64 = 0.25;
+
+/// Applause is broadband; its spectral centroid sits well above a voice's.
+const APPLAUSE_MIN_CENTROID_HZ: f64 = 2000.0;
+
+/// Frame-to-frame loudness variation (standard deviation over mean) above which
+/// a tonal window counts as speech.
+const SPEECH_MIN_RMS_VARIATION: f64 = 0.5;
+
+/// Speech energy sits in the voice band.
+const SPEECH_MAX_CENTROID_HZ: f64 = 3000.0;
+
+/// Zero crossings per sample above which a window is too noisy for speech.
+const SPEECH_MAX_ZERO_CROSSING_RATE: f64 = 0.25;
+
+/// Windows in the majority vote that smooths the raw classes.
+const SMOOTHING_WINDOWS: usize = 5;
+
+/// Applause or talk shorter than this is ignored as a boundary candidate.
+const MIN_CROWD_SECONDS: f64 = 2.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SoundClass {
+    Music,
+    Applause,
+    Speech,
+    Silence,
+}
+
+/// A run of equally classified windows, in seconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct SoundSpan {
+    pub start: f64,
+    pub end: f64,
+    pub class: SoundClass,
+}
+
+/// Spectral features of one classification window.
+#[derive(Clone, Copy, Debug, Default)]
+struct WindowFeatures {
+    rms: f64,
+    flatness: f64,
+    centroid_hz: f64,
+    zero_crossing_rate: f64,
+    rms_variation: f64,
+}
+
+/// Classify `samples` (mono at [`audio::SAMPLE_RATE`]) into runs of
+/// [`SoundClass`], covering the whole input.
+pub(crate) fn classify(samples: &[f32]) -> Vec<SoundSpan> {
+    let features = window_features(samples);
+    if features.is_empty() {
+        return Vec::new();
+    }
+    let rms: Vec<f64> = features.iter().map(|f| f.rms).collect();
+    let silence_threshold = adaptive_silence_threshold(&rms);
+    let classes: Vec<SoundClass> = features
+        .iter()
+        .map(|f| classify_window(f, silence_threshold))
+        .collect();
+    spans(&smooth(&classes))
+}
+
+fn window_features(samples: &[f32]) -> Vec<WindowFeatures> {
+    let window = (CLASS_WINDOW_SECONDS * audio::SAMPLE_RATE as f64) as usize;
+    let hann: Vec<f32> = (0..FFT_SIZE)
+        .map(|i| {
+            let phase = 2.0 * std::f32::consts::PI * i as f32 / (FFT_SIZE - 1) as f32;
+            0.5 - 0.5 * phase.cos()
+        })
+        .collect();
+    let fft = FftPlanner::<f32>::new().plan_fft_forward(FFT_SIZE);
+    let bin_hz = audio::SAMPLE_RATE as f64 / FFT_SIZE as f64;
+    let mut buffer = vec![Complex::new(0.0, 0.0); FFT_SIZE];
+
+    samples
+        .chunks_exact(window)
+        .map(|chunk| {
+            let mut frame_rms = Vec::new();
+            // Spectral features are weighted by frame energy, so the near-silent
+            // pauses in speech (whose spectrum is flat noise) don't read as applause.
+            let (mut flatness, mut centroid, mut weight, mut crossings) = (0.0, 0.0, 0.0, 0usize);
+            for frame in chunk.chunks_exact(FFT_SIZE) {
+                let sum_squared: f64 = frame.iter().map(|&s| (s * s) as f64).sum();
+                frame_rms.push((sum_squared / FFT_SIZE as f64).sqrt());
+                crossings += frame
+                    .windows(2)
+                    .filter(|pair| (pair[0] >= 0.0) != (pair[1] >= 0.0))
+                    .count();
+
+                for ((slot, &sample), &taper) in buffer.iter_mut().zip(frame).zip(&hann) {
+                    *slot = Complex::new(sample * taper, 0.0);
+                }
+                fft.process(&mut buffer);
+                // Skip DC; the upper half mirrors the lower.
+                let power: Vec<f64> = buffer[1..FFT_SIZE / 2]
+                    .iter()
+                    .map(|c| c.norm_sqr() as f64 + 1e-12)
+                    .collect();
+                let mean = power.iter().sum::<f64>() / power.len() as f64;
+                let log_mean = power.iter().map(|p| p.ln()).sum::<f64>() / power.len() as f64;
+                flatness += sum_squared * log_mean.exp() / mean;
+                centroid += sum_squared
+                    * power
+                        .iter()
+                        .enumerate()
+                        .map(|(i, p)| (i + 1) as f64 * bin_hz * p)
+                        .sum::<f64>()
+                    / (mean * power.len() as f64);
+                weight += sum_squared;
+            }
+            let frames = frame_rms.len().max(1) as f64;
+            let rms = frame_rms.iter().sum::<f64>() / frames;
+            let spread = (frame_rms.iter().map(|r| (r - rms).powi(2)).sum::<f64>() / frames).sqrt();
+            let weight = if weight > 0.0 { weight } else { 1.0 };
+            WindowFeatures {
+                rms,
+                flatness: flatness / weight,
+                centroid_hz: centroid / weight,
+                zero_crossing_rate: crossings as f64 / (frames * FFT_SIZE as f64),
+                rms_variation: if rms > 0.0 { spread / rms } else { 0.0 },
+            }
+        })
+        .collect()
+}
+
+fn classify_window(features: &WindowFeatures, silence_threshold: f64) -> SoundClass {
+    if features.rms < silence_threshold {
+        SoundClass::Silence
+    } else if features.flatness >= APPLAUSE_MIN_FLATNESS
+        && features.centroid_hz >= APPLAUSE_MIN_CENTROID_HZ
+    {
+        SoundClass::Applause
+    } else if features.rms_variation >= SPEECH_MIN_RMS_VARIATION
+        && features.centroid_hz < SPEECH_MAX_CENTROID_HZ
+        && features.zero_crossing_rate < SPEECH_MAX_ZERO_CROSSING_RATE
+    {
+        SoundClass::Speech
+    } else {
+        SoundClass::Music
+    }
+}
+
+/// Majority vote over [`SMOOTHING_WINDOWS`] windows, so a drum break or a
+/// single shout doesn't split a run.
+fn smooth(classes: &[SoundClass]) -> Vec<SoundClass> {
+    let half = SMOOTHING_WINDOWS / 2;
+    (0..classes.len())
+        .map(|i| {
+            let around = &classes[i.saturating_sub(half)..(i + half + 1).min(classes.len())];
+            let count = |class| around.iter().filter(|&&c| c == class).count();
+            [
+                SoundClass::Music,
+                SoundClass::Applause,
+                SoundClass::Speech,
+                SoundClass::Silence,
+            ]
+            .into_iter()
+            // Ties keep the window's own class.
+            .max_by_key(|&class| (count(class), class == classes[i]))
+            .unwrap_or(classes[i])
+        })
+        .collect()
+}
+
+fn spans(classes: &[SoundClass]) -> Vec<SoundSpan> {
+    let mut spans: Vec<SoundSpan> = Vec::new();
+    for (i, &class) in classes.iter().enumerate() {
+        let start = i as f64 * CLASS_WINDOW_SECONDS;
+        let end = start + CLASS_WINDOW_SECONDS;
+        match spans.last_mut() {
+            Some(last) if last.class == class => last.end = end,
+            _ => spans.push(SoundSpan { start, end, class }),
+        }
+    }
+    spans
+}
+
+/// Times where music resumes after at least [`MIN_CROWD_SECONDS`] of applause
+/// or talk (a silence in between still counts): the likeliest song starts
+/// that aren't silences.
+pub(crate) fn music_onsets(spans: &[SoundSpan]) -> Vec<f64> {
+    let mut onsets = Vec::new();
+    let mut crowd_seconds = 0.0;
+    for span in spans {
+        match span.class {
+            SoundClass::Applause | SoundClass::Speech => crowd_seconds += span.end - span.start,
+            SoundClass::Silence => {}
+            SoundClass::Music => {
+                if crowd_seconds >= MIN_CROWD_SECONDS {
+                    onsets.push(span.start);
+                }
+                crowd_seconds = 0.0;
+            }
+        }
+    }
+    onsets
+}
+
+/// The label for the interlude `start..end` seconds: whichever of applause and
+/// talk fills more of it, provided that is at least a quarter of the span.
+pub(crate) fn interlude_label(spans: &[SoundSpan], start: f64, end: f64) -> Option<InterludeLabel> {
+    let overlap = |class| -> f64 {
+        spans
+            .iter()
+            .filter(|span| span.class == class)
+            .map(|span| (span.end.min(end) - span.start.max(start)).max(0.0))
+            .sum()
+    };
+    let applause = overlap(SoundClass::Applause);
+    let talk = overlap(SoundClass::Speech);
+    let (label, seconds) = if applause >= talk {
+        (InterludeLabel::Applause, applause)
+    } else {
+        (InterludeLabel::Talk, talk)
+    };
+    (seconds > 0.0 && seconds >= (end - start) / 4.0).then_some(label)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RATE: f64 = audio::SAMPLE_RATE as f64;
+
+    /// Deterministic white noise, like a clapping crowd.
+    fn noise(seconds: f64, amplitude: f32) -> Vec<f32> {
+        let mut state: u32 = 0x2545_f491;
+        (0..(seconds * RATE) as usize)
+            .map(|_| {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                amplitude * (state as f32 / u32::MAX as f32 * 2.0 - 1.0)
+            })
+            .collect()
+    }
+
+    /// A sustained chord.
+    fn chord(seconds: f64) -> Vec<f32> {
+        (0..(seconds * RATE) as usize)
+            .map(|i| {
+                let t = i as f64 / RATE;
+                [220.0, 277.2, 329.6]
+                    .iter()
+                    .map(|f| (2.0 * std::f64::consts::PI * f * t).sin() * 0.15)
+                    .sum::<f64>() as f32
+            })
+            .collect()
+    }
+
+    /// A low voice-like buzz in ~4 syllables a second with pauses between.
+    fn talk(seconds: f64) -> Vec<f32> {
+        (0..(seconds * RATE) as usize)
+            .map(|i| {
+                let t = i as f64 / RATE;
+                if (t * 4.0).fract() < 0.5 {
+                    [150.0, 300.0, 450.0]
+                        .iter()
+                        .map(|f| (2.0 * std::f64::consts::PI * f * t).sin() * 0.2)
+                        .sum::<f64>() as f32
+                } else {
+                    0.0
+                }
+            })
+            .collect()
+    }
+
+    fn class_at(spans: &[SoundSpan], at: f64) -> SoundClass {
+        spans
+            .iter()
+            .find(|span| span.start <= at && at < span.end)
+            .map(|span| span.class)
+            .unwrap()
+    }
+
+    #[test]
+    fn music_applause_talk_and_silence_are_told_apart() {
+        let mut samples = chord(10.0);
+        samples.extend(noise(6.0, 0.3));
+        samples.extend(vec![0.0; (4.0 * RATE) as usize]);
+        samples.extend(talk(6.0));
+        samples.extend(chord(10.0));
+        let spans = classify(&samples);
+        assert_eq!(class_at(&spans, 5.0), SoundClass::Music);
+        assert_eq!(class_at(&spans, 13.0), SoundClass::Applause);
+        assert_eq!(class_at(&spans, 18.0), SoundClass::Silence);
+        assert_eq!(class_at(&spans, 23.0), SoundClass::Speech);
+        assert_eq!(class_at(&spans, 31.0), SoundClass::Music);
+    }
+
+    #[test]
+    fn music_resuming_after_the_crowd_is_an_onset() {
+        let span = |start, end, class| SoundSpan { start, end, class };
+        let spans = [
+            span(0.0, 100.0, SoundClass::Music),
+            span(100.0, 101.0, SoundClass::Applause), // too short
+            span(101.0, 150.0, SoundClass::Music),
+            span(150.0, 158.0, SoundClass::Applause),
+            span(158.0, 160.0, SoundClass::Silence),
+            span(160.0, 170.0, SoundClass::Speech),
+            span(170.0, 300.0, SoundClass::Music),
+        ];
+        assert_eq!(music_onsets(&spans), vec![170.0]);
+    }
+
+    #[test]
+    fn interludes_take_the_label_that_fills_them() {
+        let span = |start, end, class| SoundSpan { start, end, class };
+        let spans = [
+            span(0.0, 6.0, SoundClass::Applause),
+            span(6.0, 20.0, SoundClass::Speech),
+            span(20.0, 40.0, SoundClass::Music),
+        ];
+        assert_eq!(
+            interlude_label(&spans, 0.0, 20.0),
+            Some(InterludeLabel::Talk)
+        );
+        assert_eq!(
+            interlude_label(&spans, 0.0, 8.0),
+            Some(InterludeLabel::Applause)
+        );
+        assert_eq!(interlude_label(&spans, 18.0, 40.0), None);
+    }
+}
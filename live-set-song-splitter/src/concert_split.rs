@@ -12,9 +12,13 @@
 //!
 //! See `docs/concert-split.md` for the phase state diagram.
 
+use crate::calibrate::{self, DEFAULT_OVERLAY_CROP};
+use crate::cut::{self, Rendition, VideoCutMode};
+use crate::decode::Crop;
 use crate::detect::{self, Settings};
 use crate::ocr_backend::{default_ocr_choice, ensure_ocr_choice_available, OcrChoice};
 use crate::produce::{self, CutContext};
+use crate::provenance;
 pub use crate::publication::RecoveryStatus;
 use crate::publication::{
     self, PartialExpectedTrack, PartialPublicationRequest, PartialTrackOutput, PublicationRequest,
@@ -22,9 +26,15 @@ use crate::publication::{
 };
 use crate::recover::{self, RecoveryResult};
 use crate::refine;
+use crate::report::{RecoveryDecision, SplitReport};
+use crate::set_list_free;
+use crate::tags::TagTemplate;
 use crate::video::VideoInfo;
-use crate::{audio, cut::VideoCutMode, io};
-use concert_types::{derive_interludes, interlude_filename_stem, ConcertInfo, Song, SongTimestamp};
+use crate::{audio, chapters, classify, cue, ffmpeg, hls, io, loudness, poster};
+use concert_types::{
+    derive_interludes, interlude_filename_stem, BoundaryProvenance, BoundarySource, ConcertInfo,
+    Interlude, InterludeLabel, Song, SongTimestamp,
+};
 
 use anyhow::{anyhow, Context, Result};
 use std::collections::BTreeSet;
@@ -51,6 +61,11 @@ pub(crate) struct SongSegment {
     /// silence can't relocate them. Recovered/silence-placed and JSON-loaded starts
     /// are not overlay estimates and must not be pulled back.
     pub start_from_overlay: bool,
+    /// Time of a frame the song's title overlay was read on, when detection
+    /// saw one; posters are taken once the overlay has left the screen.
+    pub overlay_at: Option<f64>,
+    /// How the start was found, carried into `timestamps.json`.
+    pub boundary: BoundaryProvenance,
 }
 
 /// Output format for extracted segments.
@@ -59,13 +74,185 @@ pub(crate) struct SongSegment {
 pub enum OutputFormat {
     /// Output video files (mp4)
     Video,
-    /// Output audio files (m4a)
+    /// Output audio files (see [`AudioFormat`])
     Audio,
     /// Output both video and audio files
     #[default]
     Both,
 }
 
+/// Container/codec for the per-track audio files. Several may be written per
+/// track (e.g. FLAC for an archive and Opus for phones).
+#[derive(clap::Parser, Debug, Clone, Copy, clap::ValueEnum, Default, PartialEq, Eq)]
+#[clap(rename_all = "lowercase")]
+pub enum AudioFormat {
+    /// AAC in MP4 (copies the source AAC; ALAC for lossless sources)
+    #[default]
+    M4a,
+    /// Lossless FLAC encoded from the decoded source
+    Flac,
+    /// Opus in Ogg
+    Opus,
+    /// MP3 (LAME VBR)
+    Mp3,
+}
+
+impl AudioFormat {
+    pub const ALL: [AudioFormat; 4] = [
+        AudioFormat::M4a,
+        AudioFormat::Flac,
+        AudioFormat::Opus,
+        AudioFormat::Mp3,
+    ];
+
+    /// File extension, which is also the name used on the CLI and in settings.
+    pub fn extension(self) -> &'static str {
+        match self {
+            AudioFormat::M4a => "m4a",
+            AudioFormat::Flac => "flac",
+            AudioFormat::Opus => "opus",
+            AudioFormat::Mp3 => "mp3",
+        }
+    }
+
+    pub fn from_extension(ext: &str) -> Option<AudioFormat> {
+        AudioFormat::ALL
+            .into_iter()
+            .find(|format| format.extension().eq_ignore_ascii_case(ext))
+    }
+}
+
+/// Per-format encoder settings for [`AudioFormat`]s that re-encode. M4a has
+/// none: it copies AAC sources and picks ALAC/AAC otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AudioEncoderSettings {
+    pub opus_bitrate_kbps: u32,
+    /// LAME VBR quality, 0 (best) to 9.
+    pub mp3_quality: u8,
+    /// FLAC compression level, 0 (fastest) to 12.
+    pub flac_compression_level: u8,
+}
+
+impl Default for AudioEncoderSettings {
+    fn default() -> Self {
+        AudioEncoderSettings {
+            opus_bitrate_kbps: 128,
+            mp3_quality: 2,
+            flac_compression_level: 5,
+        }
+    }
+}
+
+/// Whether to write a chaptered remux of the source (one chapter per song and
+/// interlude, stream-copied) and whether per-track files are still cut.
+#[derive(clap::Parser, Debug, Clone, Copy, clap::ValueEnum, Default, PartialEq, Eq)]
+#[clap(rename_all = "lowercase")]
+pub enum ChapterOutput {
+    /// Per-track files only
+    #[default]
+    None,
+    /// Per-track files plus a chaptered copy of the source
+    Alongside,
+    /// Only the chaptered copy; no per-track files are cut
+    Only,
+}
+
+/// Whether to write the whole concert's audio as one file with a CUE sheet
+/// (see [`crate::cue`]) and whether per-track files are still cut.
+#[derive(clap::Parser, Debug, Clone, Copy, clap::ValueEnum, Default, PartialEq, Eq)]
+#[clap(rename_all = "lowercase")]
+pub enum AlbumOutput {
+    /// Per-track files only
+    #[default]
+    None,
+    /// Per-track files plus the album file and its CUE sheet
+    Alongside,
+    /// Only the album file and its CUE sheet; no per-track files are cut
+    Only,
+}
+
+/// Format of the single-file album.
+#[derive(clap::Parser, Debug, Clone, Copy, clap::ValueEnum, Default, PartialEq, Eq)]
+#[clap(rename_all = "lowercase")]
+pub enum AlbumFormat {
+    /// Lossless FLAC
+    #[default]
+    Flac,
+    /// AAC in MP4, copied from AAC sources
+    M4a,
+}
+
+impl AlbumFormat {
+    pub fn audio_format(self) -> AudioFormat {
+        match self {
+            AlbumFormat::Flac => AudioFormat::Flac,
+            AlbumFormat::M4a => AudioFormat::M4a,
+        }
+    }
+}
+
+/// Whether to package the source for HLS streaming (see [`crate::hls`]) and
+/// whether per-track files are still cut.
+#[derive(clap::Parser, Debug, Clone, Copy, clap::ValueEnum, Default, PartialEq, Eq)]
+#[clap(rename_all = "lowercase")]
+pub enum HlsOutput {
+    /// Per-track files only
+    #[default]
+    None,
+    /// Per-track files plus the HLS package
+    Alongside,
+    /// Only the HLS package; no per-track files are cut
+    Only,
+}
+
+/// Whether to run the EBU R128 loudness pass over the cut tracks.
+#[derive(clap::Parser, Debug, Clone, Copy, clap::ValueEnum, Default, PartialEq, Eq)]
+#[clap(rename_all = "lowercase")]
+pub enum LoudnessMode {
+    /// No loudness analysis
+    #[default]
+    Off,
+    /// Measure and write `loudness.json` next to the tracks
+    Measure,
+    /// Also write ReplayGain/R128 tags into every track file
+    Tag,
+}
+
+/// How the text-overlay detection pass samples the video.
+#[derive(clap::Parser, Debug, Clone, Copy, clap::ValueEnum, Default, PartialEq, Eq)]
+#[clap(rename_all = "lowercase")]
+pub enum DetectionSampling {
+    /// OCR one frame every second
+    #[default]
+    Dense,
+    /// OCR one frame every few seconds, and every second around a title card
+    Coarse,
+}
+
+/// Splitting without a set list: the song-length limits boundaries are chosen
+/// under, and whether to name tracks from title overlays. See
+/// [`ConcertSplitOptions::set_list_free`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SetListFree {
+    /// No boundary is placed closer than this to another (or to either end).
+    pub min_song_seconds: f64,
+    /// Tracks longer than this are split at their quietest point.
+    pub max_song_seconds: f64,
+    /// Scan the video for title overlays and use the title read inside a track
+    /// instead of its `Track NN` placeholder.
+    pub overlay_titles: bool,
+}
+
+impl Default for SetListFree {
+    fn default() -> Self {
+        SetListFree {
+            min_song_seconds: 90.0,
+            max_song_seconds: 900.0,
+            overlay_titles: false,
+        }
+    }
+}
+
 /// Tuning options mirroring the CLI's existing flags 1:1, so behavior stays
 /// compatible whether the caller is the CLI adapter or an in-process library
 /// caller (e.g. `concert-web`, wired up in a later ticket).
@@ -74,13 +261,51 @@ pub struct ConcertSplitOptions {
     pub no_save_songs: bool,
     pub refine_timestamps: bool,
     pub output_format: OutputFormat,
+    /// Audio files written per track when `output_format` includes audio.
+    /// Must not be empty.
+    pub audio_formats: Vec<AudioFormat>,
+    pub audio_encoder: AudioEncoderSettings,
     pub video_cut_mode: VideoCutMode,
+    /// Downscaled copies written beside each video track (see
+    /// [`Rendition`]). Those at or above the source height are skipped.
+    pub renditions: Vec<Rendition>,
     pub analyze_images: bool,
     pub reuse_frames: bool,
     pub keep_frames: bool,
     pub ocr_engine: Option<OcrChoice>,
+    /// Parallel OCR workers for overlay detection, each loading its own model.
+    /// 0 or 1 keeps detection single-threaded.
+    pub ocr_threads: usize,
+    pub detection_sampling: DetectionSampling,
+    /// Where detection looks for the artist/title card, in 400x200 scaled-frame
+    /// pixels. `None` calibrates it from the video (see [`crate::calibrate`]).
+    pub overlay_crop: Option<Crop>,
+    /// Derive the songs from the audio alone, for recordings without a set list.
+    /// The concert's `set_list` must be empty; the derived one (placeholder or
+    /// overlay titles) is written to `timestamps.json` like a detected split's.
+    pub set_list_free: Option<SetListFree>,
     pub emit_interludes: bool,
     pub media_duration: Option<f64>,
+    pub chapters: ChapterOutput,
+    pub album: AlbumOutput,
+    /// Format of the album file when `album` asks for one; FLAC is encoded
+    /// with `audio_encoder.flac_compression_level`.
+    pub album_format: AlbumFormat,
+    /// HLS package of the whole source, with `renditions` as extra variants.
+    pub hls: HlsOutput,
+    /// Write a [`poster`] frame per cut song and a contact sheet of the
+    /// concert. Skipped for audio-only input; a failure is a warning.
+    pub posters: bool,
+    /// Tags written on every cut track; see [`TagTemplate`].
+    pub tag_template: TagTemplate,
+    /// Image embedded as cover art on the video and audio tracks (typically
+    /// the scraped `preview.jpg`); Opus files get none. A missing file is a warning, not
+    /// an error.
+    pub cover_art: Option<PathBuf>,
+    pub loudness: LoudnessMode,
+    /// Write a diagnostic [`report`](crate::report) of the split to this
+    /// directory. A failure to write it is a warning.
+    pub report_dir: Option<PathBuf>,
 }
 
 /// Typed input to a Concert Split. `concert` may already carry embedded
@@ -109,6 +334,11 @@ pub enum SplitPhase {
     RefineAudio,
     WriteMetadata,
     Cut,
+    MeasureLoudness,
+    WriteChapters,
+    WriteAlbum,
+    WriteHls,
+    WritePosters,
     ValidateOutput,
     Publish,
     Cleanup,
@@ -148,6 +378,9 @@ pub struct ProducedTrack {
     pub kind: TrackKind,
     pub start_time: f64,
     pub end_time: f64,
+    /// What fills an interlude track, when the audio told (always `None` for
+    /// songs).
+    pub label: Option<InterludeLabel>,
 }
 
 /// A complete Concert Split result.
@@ -156,6 +389,19 @@ pub struct ConcertSplitOutput {
     pub timestamps: Vec<SongTimestamp>,
     pub tracks: Vec<ProducedTrack>,
     pub output_dir: PathBuf,
+    /// Filename (within `output_dir`) of the chaptered copy of the source,
+    /// when `options.chapters` asked for one.
+    pub chapters_file: Option<PathBuf>,
+    /// Filenames (within `output_dir`) of the single-file album and its CUE
+    /// sheet, when `options.album` asked for them.
+    pub album_file: Option<PathBuf>,
+    pub cue_file: Option<PathBuf>,
+    /// Filename (within `output_dir`) of the HLS master playlist, when
+    /// `options.hls` asked for one.
+    pub hls_playlist: Option<PathBuf>,
+    /// Filename (within `output_dir`) of the contact sheet, when
+    /// `options.posters` asked for one and it was written.
+    pub contact_sheet: Option<PathBuf>,
 }
 
 /// Why a Concert Split produced no output.
@@ -233,9 +479,26 @@ fn validate_request(request: &ConcertSplitRequest) -> Result<()> {
     if let Some(choice) = request.options.ocr_engine {
         ensure_ocr_choice_available(choice)?;
     }
-    if request.concert.set_list.is_empty() {
+    if let Some(free) = &request.options.set_list_free {
+        anyhow::ensure!(
+            request.concert.set_list.is_empty(),
+            "set-list-free splitting derives the set list; the concert's must be empty"
+        );
+        anyhow::ensure!(
+            request.timestamps.is_none() && request.concert.timestamps.is_none(),
+            "set-list-free splitting cannot use supplied timestamps"
+        );
+        anyhow::ensure!(
+            free.min_song_seconds > 0.0 && free.max_song_seconds > free.min_song_seconds,
+            "set-list-free song lengths need 0 < minimum < maximum"
+        );
+    } else if request.concert.set_list.is_empty() {
         return Err(anyhow!("Concert set list is empty"));
     }
+    anyhow::ensure!(
+        !request.options.audio_formats.is_empty(),
+        "at least one audio format is required"
+    );
     if let Some(ts) = request
         .timestamps
         .as_ref()
@@ -289,27 +552,84 @@ fn segments_from_timestamps(timestamps: &[SongTimestamp]) -> Vec<SongSegment> {
             },
             // Loaded from JSON, not a fresh overlay estimate.
             start_from_overlay: false,
+            overlay_at: None,
+            boundary: provenance::from_source(BoundarySource::Supplied),
         })
         .collect()
 }
 
-fn song_output_files(title: &str, format: OutputFormat) -> Vec<PathBuf> {
-    let stem = io::sanitize_filename(title);
-    match format {
-        OutputFormat::Video => vec![PathBuf::from(format!("{stem}.mp4"))],
-        OutputFormat::Audio => vec![PathBuf::from(format!("{stem}.m4a"))],
-        OutputFormat::Both => vec![
-            PathBuf::from(format!("{stem}.mp4")),
-            PathBuf::from(format!("{stem}.m4a")),
-        ],
+/// Extensions of the files cut per track: the video first, then its
+/// renditions, then each audio format in order.
+pub(crate) fn track_extensions(
+    format: OutputFormat,
+    renditions: &[Rendition],
+    audio_formats: &[AudioFormat],
+) -> Vec<&'static str> {
+    let video: &[Rendition] = match format {
+        OutputFormat::Video | OutputFormat::Both => renditions,
+        OutputFormat::Audio => &[],
+    };
+    let audio = match format {
+        OutputFormat::Audio | OutputFormat::Both => audio_formats,
+        OutputFormat::Video => &[],
+    };
+    matches!(format, OutputFormat::Video | OutputFormat::Both)
+        .then_some("mp4")
+        .into_iter()
+        .chain(video.iter().map(|rendition| rendition.extension()))
+        .chain(audio.iter().map(|audio| audio.extension()))
+        .collect()
+}
+
+/// The requested renditions that apply, tallest first: none without video
+/// tracks, and only those shorter than the source (no upscaling).
+fn plan_renditions(
+    input_file: &str,
+    output_format: OutputFormat,
+    requested: &[Rendition],
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> Result<Vec<Rendition>> {
+    if requested.is_empty() {
+        return Ok(Vec::new());
     }
+    if matches!(output_format, OutputFormat::Audio) {
+        progress(ConcertSplitProgress::Diagnostic(
+            "no video tracks are cut; skipping renditions".to_string(),
+        ));
+        return Ok(Vec::new());
+    }
+    let height = cut::probe_video_height(input_file)?;
+    let mut planned = Vec::new();
+    for rendition in Rendition::ALL
+        .into_iter()
+        .filter(|rendition| requested.contains(rendition))
+    {
+        if rendition.height() < height {
+            planned.push(rendition);
+        } else {
+            progress(ConcertSplitProgress::Diagnostic(format!(
+                "skipping the {} rendition of a {}p source",
+                rendition.name(),
+                height
+            )));
+        }
+    }
+    Ok(planned)
+}
+
+fn song_output_files(title: &str, extensions: &[&str]) -> Vec<PathBuf> {
+    let stem = io::sanitize_filename(title);
+    extensions
+        .iter()
+        .map(|ext| PathBuf::from(format!("{stem}.{ext}")))
+        .collect()
 }
 
 fn salvage_or_error(
     canonical_dir: &Path,
     staging_dir: &Path,
     set_list: &[Song],
-    format: OutputFormat,
+    extensions: &[&str],
     timestamps: Vec<SongTimestamp>,
     completed_tracks: Vec<ProducedTrack>,
     original_error: anyhow::Error,
@@ -325,7 +645,7 @@ fn salvage_or_error(
         .iter()
         .map(|song| PartialExpectedTrack {
             title: song.title.clone(),
-            files: song_output_files(&song.title, format),
+            files: song_output_files(&song.title, extensions),
         })
         .collect();
     let partial_tracks = completed_tracks
@@ -334,7 +654,7 @@ fn salvage_or_error(
             title: track.title.clone(),
             start_time: track.start_time,
             end_time: track.end_time,
-            files: song_output_files(&track.title, format),
+            files: song_output_files(&track.title, extensions),
         })
         .collect();
     let published = match publication::publish_partial(&PartialPublicationRequest {
@@ -362,12 +682,18 @@ fn salvage_or_error(
             kind: TrackKind::Song,
             start_time: track.start_time,
             end_time: track.end_time,
+            label: None,
         })
         .collect();
     Ok(ConcertSplitOutcome::Partial(ConcertSplitOutput {
         timestamps,
         tracks,
         output_dir: canonical_dir.to_path_buf(),
+        chapters_file: None,
+        album_file: None,
+        cue_file: None,
+        hls_playlist: None,
+        contact_sheet: None,
     }))
 }
 
@@ -392,6 +718,61 @@ fn write_timestamps_json(output_dir: &str, concert: &ConcertInfo) -> Result<()>
     Ok(())
 }
 
+/// What recovery decided for each song of `set_list`, with the start it placed.
+fn recovery_decisions(
+    set_list: &[Song],
+    results: &[RecoveryResult],
+    segments: &[SongSegment],
+) -> Vec<RecoveryDecision> {
+    set_list
+        .iter()
+        .zip(results)
+        .map(|(song, result)| {
+            let segment = segments.iter().find(|s| s.song.title == song.title);
+            RecoveryDecision {
+                title: song.title.clone(),
+                result: *result,
+                start_time: segment.map(|s| s.segment.start_time),
+                boundary: segment.map(|s| s.boundary),
+            }
+        })
+        .collect()
+}
+
+/// Write the `--report`, reading the waveform into `audio_data` if no phase
+/// has yet. The report is a diagnostic, so a failure only warns.
+fn write_split_report(
+    options: &ConcertSplitOptions,
+    report: &SplitReport,
+    audio_data: &mut Option<Vec<f32>>,
+    input_file: &str,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) {
+    let Some(dir) = &options.report_dir else {
+        return;
+    };
+    let written = (|| -> Result<PathBuf> {
+        let waveform =
+            match audio_data {
+                Some(w) => w,
+                None => audio_data.insert(audio::extract_audio_waveform(input_file).with_context(
+                    || format!("Failed to extract audio waveform from {}", input_file),
+                )?),
+            };
+        report.write(dir, waveform)
+    })();
+    match written {
+        Ok(path) => progress(ConcertSplitProgress::Diagnostic(format!(
+            "Split report written to {}",
+            path.display()
+        ))),
+        Err(error) => progress(ConcertSplitProgress::Warning(format!(
+            "split report not written: {:#}",
+            error
+        ))),
+    }
+}
+
 fn cleanup_temp_dir(
     temp_dir: &str,
     keep_frames: bool,
@@ -471,11 +852,34 @@ pub fn run(
         .ok_or_else(|| anyhow!("staging directory path is not valid UTF-8"))?
         .to_string();
 
-    let num_songs = concert.set_list.len();
-
     progress(ConcertSplitProgress::PhaseStarted(SplitPhase::Inspect));
     let video_info = VideoInfo::from_ffprobe_file(&input_file_str)
         .with_context(|| format!("Failed to get video information from {}", input_file_str))?;
+    // An audio-only input (podcast, radio rip, audio-only download) can only be
+    // cut into audio tracks.
+    let output_format = match (video_info.has_video, options.output_format) {
+        (true, format) | (false, format @ OutputFormat::Audio) => format,
+        (false, OutputFormat::Both) => {
+            progress(ConcertSplitProgress::Diagnostic(format!(
+                "{} has no video stream; cutting audio tracks only",
+                input_file_str
+            )));
+            OutputFormat::Audio
+        }
+        (false, OutputFormat::Video) => {
+            return Err(anyhow!(
+                "{} has no video stream to cut video tracks from",
+                input_file_str
+            ));
+        }
+    };
+    let renditions = plan_renditions(
+        &input_file_str,
+        output_format,
+        &options.renditions,
+        progress,
+    )?;
+    let extensions = track_extensions(output_format, &renditions, &options.audio_formats);
 
     // If timestamps were supplied (explicitly, or embedded in the concert JSON),
     // load segments from them instead of detecting. Mirrors the CLI's two
@@ -493,13 +897,90 @@ pub fn run(
     let temp_dir = format!("temp_frames/{}", folder_name(&concert));
     io::ensure_dir(&temp_dir)?;
 
-    if segments.is_empty() {
+    let mut settings = Settings {
+        analyze_images: options.analyze_images,
+        reuse_frames: options.reuse_frames,
+        keep_frames: options.keep_frames,
+        ocr_choice: options.ocr_engine.unwrap_or_else(default_ocr_choice),
+        ocr_threads: options.ocr_threads,
+        sampling: options.detection_sampling,
+        overlay_crop: options.overlay_crop.unwrap_or(DEFAULT_OVERLAY_CROP),
+        report: options.report_dir.is_some(),
+    };
+    let mut report = options.report_dir.as_ref().map(|_| SplitReport {
+        artist: concert.artist.clone(),
+        input_file: input_file_str.clone(),
+        duration: video_info.duration,
+        ..SplitReport::default()
+    });
+
+    // Cache for the audio waveform — extracted at most once, regardless of
+    // whether silence-based recovery and/or refinement need it.
+    let mut audio_data: Option<Vec<f32>> = None;
+    // Its applause/talk/music classification, likewise computed at most once and
+    // shared by recovery, refinement and interlude labeling.
+    let mut sound_spans: Option<Vec<classify::SoundSpan>> = None;
+
+    // Boundaries placed on silences need no snapping back to one.
+    let mut boundaries_on_silences = false;
+    if let Some(free) = &options.set_list_free {
         progress(ConcertSplitProgress::PhaseStarted(SplitPhase::Detect));
-        let settings = Settings {
-            analyze_images: options.analyze_images,
-            reuse_frames: options.reuse_frames,
-            ocr_choice: options.ocr_engine.unwrap_or_else(default_ocr_choice),
+        if free.overlay_titles && !video_info.has_video {
+            progress(ConcertSplitProgress::Warning(
+                "audio-only input has no overlay titles to read; using placeholder titles"
+                    .to_string(),
+            ));
+        }
+        let overlay_titles = if free.overlay_titles && video_info.has_video {
+            settings.overlay_crop = calibrate::resolve_overlay_crop(
+                &input_file_str,
+                &concert.artist,
+                options.overlay_crop,
+                progress,
+            );
+            detect::overlay_titles(
+                &input_file_str,
+                &concert.artist,
+                &settings,
+                &temp_dir,
+                progress,
+            )?
+        } else {
+            Vec::new()
         };
+        let waveform = audio::extract_audio_waveform(&input_file_str)
+            .with_context(|| format!("Failed to extract audio waveform from {}", input_file_str))?;
+        let derived = set_list_free::derive_set_list(
+            &waveform,
+            video_info.duration,
+            free,
+            &overlay_titles,
+            progress,
+        );
+        concert.set_list = derived.set_list;
+        segments = derived.segments;
+        boundaries_on_silences = true;
+        audio_data = Some(waveform);
+    } else if segments.is_empty() && !video_info.has_video {
+        progress(ConcertSplitProgress::PhaseStarted(SplitPhase::Detect));
+        let waveform = audio::extract_audio_waveform(&input_file_str)
+            .with_context(|| format!("Failed to extract audio waveform from {}", input_file_str))?;
+        segments = set_list_free::split_at_silences(
+            &waveform,
+            video_info.duration,
+            &concert.set_list,
+            progress,
+        );
+        boundaries_on_silences = true;
+        audio_data = Some(waveform);
+    } else if segments.is_empty() {
+        progress(ConcertSplitProgress::PhaseStarted(SplitPhase::Detect));
+        settings.overlay_crop = calibrate::resolve_overlay_crop(
+            &input_file_str,
+            &concert.artist,
+            options.overlay_crop,
+            progress,
+        );
         let detection = detect::detect_song_boundaries_from_text(
             &input_file_str,
             &concert.artist,
@@ -511,27 +992,37 @@ pub fn run(
         )?;
         segments = detection.segments;
         overlay_clusters = detection.unmatched_overlay_clusters;
+        if let Some(report) = &mut report {
+            report.overlay_matches = detection.overlay_matches;
+            report.unmatched_overlay_clusters = overlay_clusters.clone();
+        }
     }
 
-    // Cache for the audio waveform — extracted at most once, regardless of
-    // whether silence-based recovery and/or refinement need it.
-    let mut audio_data: Option<Vec<f32>> = None;
-
     // If text detection came up short, try silence-based recovery before giving up.
+    let num_songs = concert.set_list.len();
     if segments.iter().filter(|s| s.segment.is_song).count() < num_songs {
         progress(ConcertSplitProgress::PhaseStarted(
             SplitPhase::RecoverSilence,
         ));
-        let waveform = audio::extract_audio_waveform(&input_file_str)
-            .with_context(|| format!("Failed to extract audio waveform from {}", input_file_str))?;
+        let waveform = match audio_data.take() {
+            Some(w) => w,
+            None => audio::extract_audio_waveform(&input_file_str).with_context(|| {
+                format!("Failed to extract audio waveform from {}", input_file_str)
+            })?,
+        };
+        let spans = sound_spans.get_or_insert_with(|| classify::classify(&waveform));
         let results = recover::recover_missing_songs(
             &mut segments,
             &concert.set_list,
             &overlay_clusters,
             &waveform,
+            spans,
             progress,
         );
         audio_data = Some(waveform);
+        if let Some(report) = &mut report {
+            report.recovery = recovery_decisions(&concert.set_list, &results, &segments);
+        }
 
         let still_missing: Vec<String> = concert
             .set_list
@@ -547,6 +1038,10 @@ pub fn run(
             .collect();
 
         if !still_missing.is_empty() {
+            if let Some(report) = &mut report {
+                report.missing = still_missing.clone();
+                write_split_report(&options, report, &mut audio_data, &input_file_str, progress);
+            }
             // No cleanup here: mirrors the original CLI's hard error at this
             // point, which returned before reaching cleanup.
             return Ok(ConcertSplitOutcome::NoOutput {
@@ -563,28 +1058,37 @@ pub fn run(
     let refine_now = timestamps.is_none() || options.refine_timestamps;
     if refine_now {
         progress(ConcertSplitProgress::PhaseStarted(SplitPhase::RefineAudio));
-        let audio_samples = match audio_data.take() {
-            Some(w) => w,
-            None => audio::extract_audio_waveform(&input_file_str).with_context(|| {
-                format!("Failed to extract audio waveform from {}", input_file_str)
-            })?,
-        };
-        segments = refine::refine_segments_with_audio_analysis(
-            &segments,
-            &audio_samples,
-            video_info.duration,
-            progress,
-        )
-        .with_context(|| "Failed to refine segments with audio analysis")?;
-        segments = refine::refine_last_song_end_time(
-            &input_file_str,
-            segments,
-            video_info.duration,
-            options.reuse_frames,
-            &temp_dir,
-            progress,
-        )
-        .with_context(|| "Failed to refine last song end time")?;
+        // Silence-derived boundaries already sit on silences; snapping them back
+        // to an earlier one would only move them off.
+        if !boundaries_on_silences {
+            let audio_samples = match audio_data.take() {
+                Some(w) => w,
+                None => audio::extract_audio_waveform(&input_file_str).with_context(|| {
+                    format!("Failed to extract audio waveform from {}", input_file_str)
+                })?,
+            };
+            let audio_samples = audio_data.insert(audio_samples);
+            let spans = sound_spans.get_or_insert_with(|| classify::classify(audio_samples));
+            segments = refine::refine_segments_with_audio_analysis(
+                &segments,
+                audio_samples,
+                spans,
+                video_info.duration,
+                progress,
+            )
+            .with_context(|| "Failed to refine segments with audio analysis")?;
+        }
+        if video_info.has_video {
+            segments = refine::refine_last_song_end_time(
+                &input_file_str,
+                segments,
+                &video_info,
+                &settings,
+                &temp_dir,
+                progress,
+            )
+            .with_context(|| "Failed to refine last song end time")?;
+        }
     }
 
     // Outcome timestamps are always computed (so a library caller gets them
@@ -593,6 +1097,57 @@ pub fn run(
     // would add a file reset-to-auto/user-timestamp runs never wrote before.
     let outcome_timestamps = produce::create_song_timestamps(&segments, &concert.set_list);
     concert.timestamps = Some(outcome_timestamps.clone());
+    if let Some(report) = &mut report {
+        report.boundaries = outcome_timestamps.clone();
+        write_split_report(&options, report, &mut audio_data, &input_file_str, progress);
+    }
+
+    // `ChapterOutput::Only`, `AlbumOutput::Only` and `HlsOutput::Only` replace
+    // the per-track files with the chaptered copy, the album or the HLS
+    // package, so they skip cutting the same way `no_save_songs` does.
+    let cut_tracks = !options.no_save_songs
+        && options.chapters != ChapterOutput::Only
+        && options.album != AlbumOutput::Only
+        && options.hls != HlsOutput::Only;
+
+    // Interludes are labeled by what fills them. That takes the waveform, so
+    // only when they are written to `timestamps.json` or cut as tracks.
+    concert.interludes = None;
+    let interludes = derive_interludes(
+        &outcome_timestamps,
+        options.media_duration.unwrap_or(video_info.duration),
+    );
+    if !interludes.is_empty() && (refine_now || (cut_tracks && options.emit_interludes)) {
+        let spans = match sound_spans.take() {
+            Some(spans) => Ok(spans),
+            None => match audio_data.take() {
+                Some(w) => Ok(w),
+                None => audio::extract_audio_waveform(&input_file_str),
+            }
+            .map(|waveform| classify::classify(&waveform)),
+        };
+        match spans {
+            Ok(spans) => {
+                concert.interludes = Some(
+                    interludes
+                        .into_iter()
+                        .map(|interlude| Interlude {
+                            label: classify::interlude_label(
+                                &spans,
+                                interlude.start_time,
+                                interlude.end_time,
+                            ),
+                            ..interlude
+                        })
+                        .collect(),
+                );
+            }
+            Err(error) => progress(ConcertSplitProgress::Warning(format!(
+                "interludes left unlabeled; could not read the audio: {:#}",
+                error
+            ))),
+        }
+    }
 
     if refine_now {
         progress(ConcertSplitProgress::PhaseStarted(
@@ -602,7 +1157,7 @@ pub fn run(
     }
 
     let mut tracks: Vec<ProducedTrack> = Vec::new();
-    if !options.no_save_songs {
+    if cut_tracks {
         progress(ConcertSplitProgress::PhaseStarted(SplitPhase::Cut));
         // Resolve the media duration needed for interlude derivation. Prefer the
         // explicit `media_duration` option (avoids a second ffprobe), fall back
@@ -614,19 +1169,51 @@ pub fn run(
         };
 
         // Smart cutting probes the source's stream properties once for the run.
-        let source_params = match (options.output_format, options.video_cut_mode) {
+        let source_params = match (output_format, options.video_cut_mode) {
             (OutputFormat::Video | OutputFormat::Both, VideoCutMode::Smart) => {
                 Some(crate::cut::probe_source_video_params(&input_file_str)?)
             }
             _ => None,
         };
+        let audio_outputs = match output_format {
+            OutputFormat::Audio | OutputFormat::Both => {
+                let source_codec = ffmpeg::probe_audio_codec(&input_file_str)?;
+                options
+                    .audio_formats
+                    .iter()
+                    .map(|&format| produce::AudioOutput {
+                        format,
+                        encoder_args: ffmpeg::audio_encoder_args(
+                            format,
+                            &source_codec,
+                            &options.audio_encoder,
+                        ),
+                    })
+                    .collect()
+            }
+            OutputFormat::Video => Vec::new(),
+        };
+        let cover_art = options.cover_art.as_deref().filter(|cover| {
+            let exists = cover.is_file();
+            if !exists {
+                progress(ConcertSplitProgress::Warning(format!(
+                    "cover art {} not found; tracks will have no embedded cover",
+                    cover.display()
+                )));
+            }
+            exists
+        });
         let ctx = CutContext {
             input_file: &input_file_str,
             output_dir: &output_dir_str,
-            output_format: options.output_format,
+            output_format,
+            audio_outputs,
             source_params,
             video_cut_mode: options.video_cut_mode,
+            renditions: renditions.clone(),
             concert: &concert,
+            tag_template: &options.tag_template,
+            cover_art,
         };
         tracks = match produce::process_segments(
             &segments,
@@ -645,7 +1232,7 @@ pub fn run(
                     &output_dir,
                     &staging_dir,
                     &concert.set_list,
-                    options.output_format,
+                    &extensions,
                     outcome_timestamps,
                     completed_tracks,
                     error,
@@ -654,24 +1241,192 @@ pub fn run(
         };
     }
 
+    // Loudness is an enhancement to tracks that were already cut, so a failed
+    // measurement only warns and publishes them without `loudness.json`.
+    let mut loudness_written = false;
+    if cut_tracks && options.loudness != LoudnessMode::Off && !tracks.is_empty() {
+        progress(ConcertSplitProgress::PhaseStarted(
+            SplitPhase::MeasureLoudness,
+        ));
+        match loudness::analyze(
+            &staging_dir,
+            &tracks,
+            &extensions,
+            options.loudness,
+            progress,
+        ) {
+            Ok(file) => {
+                progress(ConcertSplitProgress::Diagnostic(format!(
+                    "Album loudness {:.1} LUFS (gain {:+.2} dB)",
+                    file.album.integrated_lufs, file.album.gain_db
+                )));
+                loudness_written = true;
+            }
+            Err(error) => progress(ConcertSplitProgress::Warning(format!(
+                "loudness analysis failed; publishing without {}: {:#}",
+                concert_types::LOUDNESS_FILE,
+                error
+            ))),
+        }
+    }
+
+    let mut chapters_file: Option<PathBuf> = None;
+    if !options.no_save_songs && options.chapters != ChapterOutput::None {
+        progress(ConcertSplitProgress::PhaseStarted(
+            SplitPhase::WriteChapters,
+        ));
+        let name = PathBuf::from(chapters::chaptered_file_name(&concert, &input_file));
+        let chapter_list = chapters::chapters_for(
+            &outcome_timestamps,
+            Some(options.media_duration.unwrap_or(video_info.duration)),
+        );
+        if let Err(error) = chapters::write_chaptered_copy(
+            &input_file,
+            &staging_dir.join(&name),
+            &concert,
+            &chapter_list,
+        ) {
+            return salvage_or_error(
+                &output_dir,
+                &staging_dir,
+                &concert.set_list,
+                &extensions,
+                outcome_timestamps,
+                tracks,
+                error,
+            );
+        }
+        chapters_file = Some(name);
+    }
+
+    let mut album_file: Option<PathBuf> = None;
+    let mut cue_file: Option<PathBuf> = None;
+    if !options.no_save_songs && options.album != AlbumOutput::None {
+        progress(ConcertSplitProgress::PhaseStarted(SplitPhase::WriteAlbum));
+        let album_result = write_album(
+            &input_file_str,
+            &staging_dir,
+            &concert,
+            &outcome_timestamps,
+            options.media_duration.unwrap_or(video_info.duration),
+            &options,
+            progress,
+        );
+        match album_result {
+            Ok((album, cue)) => {
+                album_file = Some(album);
+                cue_file = Some(cue);
+            }
+            Err(error) => {
+                return salvage_or_error(
+                    &output_dir,
+                    &staging_dir,
+                    &concert.set_list,
+                    &extensions,
+                    outcome_timestamps,
+                    tracks,
+                    error,
+                );
+            }
+        }
+    }
+
+    let mut hls_files: Vec<PathBuf> = Vec::new();
+    if !options.no_save_songs && options.hls != HlsOutput::None {
+        progress(ConcertSplitProgress::PhaseStarted(SplitPhase::WriteHls));
+        let ranges = hls::date_ranges_for(
+            &outcome_timestamps,
+            Some(options.media_duration.unwrap_or(video_info.duration)),
+        );
+        match hls::write_hls_package(
+            &input_file_str,
+            &staging_dir,
+            &concert,
+            &ranges,
+            &renditions,
+        ) {
+            Ok(files) => hls_files = files,
+            Err(error) => {
+                return salvage_or_error(
+                    &output_dir,
+                    &staging_dir,
+                    &concert.set_list,
+                    &extensions,
+                    outcome_timestamps,
+                    tracks,
+                    error,
+                );
+            }
+        }
+    }
+    let hls_playlist = hls_files.first().cloned();
+
+    // Posters and the contact sheet only decorate the split, so a failure
+    // warns and publishes without them.
+    let mut poster_files: Vec<PathBuf> = Vec::new();
+    let mut contact_sheet: Option<PathBuf> = None;
+    if options.posters && !options.no_save_songs && video_info.has_video {
+        progress(ConcertSplitProgress::PhaseStarted(SplitPhase::WritePosters));
+        if cut_tracks {
+            let overlay_at = |song: &SongTimestamp| {
+                segments
+                    .iter()
+                    .find(|segment| segment.song.title.eq_ignore_ascii_case(&song.title))
+                    .and_then(|segment| segment.overlay_at)
+            };
+            match poster::write_posters(
+                &input_file_str,
+                &staging_dir,
+                (video_info.width, video_info.height),
+                &outcome_timestamps,
+                overlay_at,
+            ) {
+                Ok(files) => poster_files = files,
+                Err(error) => progress(ConcertSplitProgress::Warning(format!(
+                    "publishing without poster frames: {:#}",
+                    error
+                ))),
+            }
+        }
+        match poster::write_contact_sheet(
+            &input_file_str,
+            &staging_dir,
+            &concert,
+            (video_info.width, video_info.height),
+            options.media_duration.unwrap_or(video_info.duration),
+        ) {
+            Ok(file) => contact_sheet = Some(file),
+            Err(error) => progress(ConcertSplitProgress::Warning(format!(
+                "publishing without a contact sheet: {:#}",
+                error
+            ))),
+        }
+    }
+
     let publication_result = (|| -> Result<()> {
         let mut replacement_files = Vec::new();
         if refine_now {
             replacement_files.push(PathBuf::from("timestamps.json"));
         }
+        replacement_files.extend(chapters_file.clone());
+        replacement_files.extend(album_file.clone());
+        replacement_files.extend(cue_file.clone());
+        replacement_files.extend(hls_files.iter().cloned());
+        replacement_files.extend(poster_files.iter().cloned());
+        replacement_files.extend(contact_sheet.clone());
+        if loudness_written {
+            replacement_files.push(PathBuf::from(concert_types::LOUDNESS_FILE));
+        }
         for track in &tracks {
             let stem = match track.kind {
                 TrackKind::Song => io::sanitize_filename(&track.title),
                 TrackKind::Interlude => track.title.clone(),
             };
-            match options.output_format {
-                OutputFormat::Video => replacement_files.push(PathBuf::from(format!("{stem}.mp4"))),
-                OutputFormat::Audio => replacement_files.push(PathBuf::from(format!("{stem}.m4a"))),
-                OutputFormat::Both => {
-                    replacement_files.push(PathBuf::from(format!("{stem}.mp4")));
-                    replacement_files.push(PathBuf::from(format!("{stem}.m4a")));
-                }
-            }
+            replacement_files.extend(
+                extensions
+                    .iter()
+                    .map(|ext| PathBuf::from(format!("{stem}.{ext}"))),
+            );
         }
         if !replacement_files.is_empty() {
             progress(ConcertSplitProgress::PhaseStarted(
@@ -687,7 +1442,7 @@ pub fn run(
                 .iter()
                 .map(|song| song.title.as_str())
                 .collect();
-            if !options.no_save_songs {
+            if cut_tracks {
                 anyhow::ensure!(
                     produced_songs == expected_songs,
                     "produced song set does not match the concert set list"
@@ -717,7 +1472,7 @@ pub fn run(
                 .filter(|track| track.kind == TrackKind::Interlude)
                 .map(|track| track.title.as_str())
                 .collect();
-            if !options.no_save_songs {
+            if cut_tracks {
                 anyhow::ensure!(
                     produced_interludes
                         == expected_interludes
@@ -741,7 +1496,7 @@ pub fn run(
             &output_dir,
             &staging_dir,
             &concert.set_list,
-            options.output_format,
+            &extensions,
             outcome_timestamps,
             tracks,
             error,
@@ -760,11 +1515,53 @@ pub fn run(
             timestamps: outcome_timestamps,
             tracks,
             output_dir,
+            chapters_file,
+            album_file,
+            cue_file,
+            hls_playlist,
+            contact_sheet,
         })
     };
     Ok(outcome)
 }
 
+/// Stage the single-file album and its CUE sheet in `staging_dir`, returning
+/// their filenames. Cover art is embedded when available; failing to embed it
+/// only warns, like on the cut tracks.
+fn write_album(
+    input_file: &str,
+    staging_dir: &Path,
+    concert: &ConcertInfo,
+    timestamps: &[SongTimestamp],
+    media_duration: f64,
+    options: &ConcertSplitOptions,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> Result<(PathBuf, PathBuf)> {
+    let format = options.album_format.audio_format();
+    let album_name = cue::album_file_name(concert, format);
+    let album_path = staging_dir.join(&album_name);
+    let source_codec = ffmpeg::probe_audio_codec(input_file)?;
+    let encoder_args = ffmpeg::audio_encoder_args(format, &source_codec, &options.audio_encoder);
+    let tags = options
+        .tag_template
+        .render(concert, &concert.album, None, concert.set_list.len());
+    cue::write_album_file(Path::new(input_file), &album_path, &encoder_args, &tags)?;
+    if let Some(cover) = options.cover_art.as_deref().filter(|cover| cover.is_file()) {
+        if let Err(error) = ffmpeg::embed_cover_art(&album_path.to_string_lossy(), cover) {
+            progress(ConcertSplitProgress::Warning(format!(
+                "cover art not embedded in {}: {:#}",
+                album_name, error
+            )));
+        }
+    }
+
+    let cue_name = cue::cue_file_name(concert);
+    let sheet = cue::CueSheet::for_concert(concert, &album_name, timestamps, Some(media_duration));
+    fs::write(staging_dir.join(&cue_name), sheet.render())
+        .with_context(|| format!("Failed to write {}", cue_name))?;
+    Ok((PathBuf::from(album_name), PathBuf::from(cue_name)))
+}
+
 /// Resolve one interrupted complete publication before the directory is read
 /// or used for another Concert Split.
 pub fn recover_publication(output_dir: &Path) -> Result<RecoveryStatus> {
@@ -823,21 +1620,62 @@ mod tests {
             preview_image_url: None,
             teaser: None,
             timestamps: None,
+            interludes: None,
         }
     }
 
+    #[test]
+    fn track_extensions_put_the_video_first_then_each_audio_format() {
+        let audio = [AudioFormat::Flac, AudioFormat::Opus];
+        assert_eq!(
+            track_extensions(OutputFormat::Both, &[], &audio),
+            ["mp4", "flac", "opus"]
+        );
+        assert_eq!(
+            track_extensions(OutputFormat::Audio, &[Rendition::P720], &audio),
+            ["flac", "opus"]
+        );
+        assert_eq!(track_extensions(OutputFormat::Video, &[], &audio), ["mp4"]);
+        assert_eq!(
+            track_extensions(
+                OutputFormat::Both,
+                &[Rendition::P720, Rendition::P480],
+                &audio
+            ),
+            ["mp4", "720p.mp4", "480p.mp4", "flac", "opus"]
+        );
+        assert_eq!(AudioFormat::from_extension("MP3"), Some(AudioFormat::Mp3));
+        assert_eq!(AudioFormat::from_extension("wav"), None);
+    }
+
     fn default_options() -> ConcertSplitOptions {
         ConcertSplitOptions {
             no_save_songs: false,
             refine_timestamps: false,
             output_format: OutputFormat::Audio,
+            audio_formats: vec![AudioFormat::M4a],
+            audio_encoder: AudioEncoderSettings::default(),
             video_cut_mode: VideoCutMode::Copy,
+            renditions: Vec::new(),
             analyze_images: false,
             reuse_frames: false,
             keep_frames: false,
             ocr_engine: None,
+            ocr_threads: 1,
+            detection_sampling: DetectionSampling::Dense,
+            overlay_crop: None,
+            set_list_free: None,
             emit_interludes: false,
             media_duration: None,
+            chapters: ChapterOutput::None,
+            album: AlbumOutput::None,
+            album_format: AlbumFormat::Flac,
+            hls: HlsOutput::None,
+            posters: false,
+            tag_template: TagTemplate::default(),
+            cover_art: None,
+            loudness: LoudnessMode::Off,
+            report_dir: None,
         }
     }
 
@@ -935,12 +1773,14 @@ mod tests {
                     start_time: 0.0,
                     end_time: 2.0,
                     duration: 2.0,
+                    boundary: None,
                 },
                 SongTimestamp {
                     title: "Second".to_string(),
                     start_time: 7.0,
                     end_time: 6.0,
                     duration: -1.0,
+                    boundary: None,
                 },
             ]),
             options: default_options(),
@@ -979,12 +1819,14 @@ mod tests {
                     start_time: 0.0,
                     end_time: 2.0,
                     duration: 2.0,
+                    boundary: None,
                 },
                 SongTimestamp {
                     title: "Second".to_string(),
                     start_time: 2.0,
                     end_time: 4.0,
                     duration: 2.0,
+                    boundary: None,
                 },
             ]),
             options: default_options(),
@@ -1006,12 +1848,14 @@ mod tests {
                     start_time: 0.0,
                     end_time: 3.0,
                     duration: 3.0,
+                    boundary: None,
                 },
                 SongTimestamp {
                     title: "Second".to_string(),
                     start_time: 7.0,
                     end_time: 6.0,
                     duration: -1.0,
+                    boundary: None,
                 },
             ]),
             options: default_options(),
@@ -1043,6 +1887,27 @@ mod tests {
         assert!(err.to_string().contains("set list is empty"));
     }
 
+    #[test]
+    fn set_list_free_split_takes_no_set_list() {
+        let dir = tempfile::tempdir().unwrap();
+        let options = ConcertSplitOptions {
+            set_list_free: Some(SetListFree::default()),
+            ..default_options()
+        };
+        let request = |songs: &[&str]| ConcertSplitRequest {
+            concert: test_concert("SetListFree", songs),
+            input_file: dir.path().join("missing.mp4"),
+            output_dir: dir.path().join("out"),
+            timestamps: None,
+            options: options.clone(),
+        };
+        let err = run(request(&["Intro"]), &mut no_progress).unwrap_err();
+        assert!(err.to_string().contains("the concert's must be empty"));
+        // Without a set list validation gets as far as the missing input.
+        let err = run(request(&[]), &mut no_progress).unwrap_err();
+        assert!(err.to_string().contains("Input file does not exist"));
+    }
+
     #[test]
     fn empty_explicit_timestamps_are_rejected() {
         let dir = tempfile::tempdir().unwrap();
@@ -1071,6 +1936,7 @@ mod tests {
                 start_time: 0.0,
                 end_time: 4.0,
                 duration: 4.0,
+                boundary: None,
             }]),
             options: default_options(),
         };
@@ -1114,12 +1980,14 @@ mod tests {
                     start_time: 0.0,
                     end_time: 4.0,
                     duration: 4.0,
+                    boundary: None,
                 },
                 SongTimestamp {
                     title: "Outro".to_string(),
                     start_time: 4.0,
                     end_time: 8.0,
                     duration: 4.0,
+                    boundary: None,
                 },
             ]),
             options: default_options(), // refine_timestamps: false
@@ -1167,6 +2035,97 @@ mod tests {
         );
     }
 
+    #[test]
+    fn chapters_only_publishes_a_chaptered_copy_without_cutting_tracks() {
+        let dir = tempfile::tempdir().unwrap();
+        let media = fixture(dir.path());
+        let output_dir = dir.path().join("out");
+
+        let mut options = default_options();
+        options.chapters = ChapterOutput::Only;
+        let request = ConcertSplitRequest {
+            concert: test_concert("Chaptered", &["Intro", "Outro"]),
+            input_file: media,
+            output_dir: output_dir.clone(),
+            timestamps: Some(vec![
+                SongTimestamp {
+                    title: "Intro".to_string(),
+                    start_time: 0.0,
+                    end_time: 4.0,
+                    duration: 4.0,
+                    boundary: None,
+                },
+                SongTimestamp {
+                    title: "Outro".to_string(),
+                    start_time: 4.0,
+                    end_time: 8.0,
+                    duration: 4.0,
+                    boundary: None,
+                },
+            ]),
+            options,
+        };
+
+        let outcome = run(request, &mut no_progress).expect("run should succeed");
+        let output = match outcome {
+            ConcertSplitOutcome::Complete(output) => output,
+            other => panic!("expected Complete, got {other:?}"),
+        };
+        assert!(output.tracks.is_empty());
+        assert_eq!(
+            output.chapters_file,
+            Some(PathBuf::from("Chaptered.chapters.mp4"))
+        );
+        assert!(output_dir.join("Chaptered.chapters.mp4").exists());
+        assert!(!output_dir.join("Intro.m4a").exists());
+    }
+
+    #[test]
+    fn album_only_publishes_the_album_and_cue_sheet_without_cutting_tracks() {
+        let dir = tempfile::tempdir().unwrap();
+        let media = fixture(dir.path());
+        let output_dir = dir.path().join("out");
+
+        let mut options = default_options();
+        options.album = AlbumOutput::Only;
+        let request = ConcertSplitRequest {
+            concert: test_concert("Album", &["Intro", "Outro"]),
+            input_file: media,
+            output_dir: output_dir.clone(),
+            timestamps: Some(vec![
+                SongTimestamp {
+                    title: "Intro".to_string(),
+                    start_time: 1.0,
+                    end_time: 4.0,
+                    duration: 3.0,
+                    boundary: None,
+                },
+                SongTimestamp {
+                    title: "Outro".to_string(),
+                    start_time: 4.0,
+                    end_time: 8.0,
+                    duration: 4.0,
+                    boundary: None,
+                },
+            ]),
+            options,
+        };
+
+        let outcome = run(request, &mut no_progress).expect("run should succeed");
+        let output = match outcome {
+            ConcertSplitOutcome::Complete(output) => output,
+            other => panic!("expected Complete, got {other:?}"),
+        };
+        assert!(output.tracks.is_empty());
+        assert_eq!(output.album_file, Some(PathBuf::from("Album.album.flac")));
+        assert!(output_dir.join("Album.album.flac").exists());
+        let sheet = fs::read_to_string(output_dir.join("Album.album.cue")).unwrap();
+        let sheet = cue::CueSheet::parse(&sheet).unwrap();
+        assert_eq!(sheet.file, "Album.album.flac");
+        assert_eq!(sheet.track_span(0), Some((1.0, Some(4.0))));
+        assert!(!output_dir.join("Intro.m4a").exists());
+    }
+
     #[test]
     fn no_save_songs_with_explicit_timestamps_yields_analysis_only_and_no_files() {
         let dir = tempfile::tempdir().unwrap();
@@ -1185,12 +2144,14 @@ mod tests {
                     start_time: 0.0,
                     end_time: 4.0,
                     duration: 4.0,
+                    boundary: None,
                 },
                 SongTimestamp {
                     title: "Outro".to_string(),
                     start_time: 4.0,
                     end_time: 8.0,
                     duration: 4.0,
+                    boundary: None,
                 },
             ]),
             options,
@@ -1225,12 +2186,14 @@ mod tests {
                 start_time: 0.0,
                 end_time: 4.0,
                 duration: 4.0,
+                boundary: None,
             },
             SongTimestamp {
                 title: "Outro".to_string(),
                 start_time: 4.0,
                 end_time: 8.0,
                 duration: 4.0,
+                boundary: None,
             },
         ]);
 
@@ -1282,6 +2245,7 @@ mod tests {
                 start_time: 4.0,
                 end_time: 8.0,
                 duration: 4.0,
+                boundary: None,
             }]),
             options: default_options(),
         };
@@ -0,0 +1,338 @@
+//! Single-file album: the whole concert's audio as one FLAC or `.m4a` plus a
+//! CUE sheet indexing each song, for players that prefer one gapless file
+//! (foobar2000, DeaDBeeF, Kodi) over a folder of tracks. Interludes are not
+//! tracks of their own: the gap before a song is its pregap (`INDEX 00`), and
+//! anything after the last song belongs to the last track.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use concert_types::{derive_interludes, ConcertInfo, SongTimestamp};
+
+use crate::concert_split::{folder_name, AudioFormat};
+use crate::ffmpeg::create_ffmpeg_command;
+use crate::tags::TrackTags;
+
+/// Infix between the concert's folder name and the extension, e.g.
+/// `Album.album.flac` / `Album.album.cue`. Distinct from the downloaded source
+/// (`Album.mp4`) and the chaptered copy (`Album.chapters.mp4`).
+pub const ALBUM_INFIX: &str = "album";
+
+/// Extension of the CUE sheet.
+pub const CUE_EXTENSION: &str = "cue";
+
+/// CUE time codes count 75 frames per second.
+const FRAMES_PER_SECOND: f64 = 75.0;
+
+/// Filename stem (no extension) of the album file and its sheet, e.g.
+/// `Album.album`.
+pub fn album_file_stem(concert: &ConcertInfo) -> String {
+    format!("{}.{ALBUM_INFIX}", folder_name(concert))
+}
+
+pub fn album_file_name(concert: &ConcertInfo, format: AudioFormat) -> String {
+    format!("{}.{}", album_file_stem(concert), format.extension())
+}
+
+pub fn cue_file_name(concert: &ConcertInfo) -> String {
+    format!("{}.{CUE_EXTENSION}", album_file_stem(concert))
+}
+
+/// One `TRACK` of a sheet, in seconds from the start of its file.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CueTrack {
+    pub title: String,
+    pub performer: Option<String>,
+    /// `INDEX 00`: where the gap before the song starts, when it has one.
+    pub pregap_start: Option<f64>,
+    /// `INDEX 01`: where the song starts.
+    pub start_time: f64,
+}
+
+/// A single-file CUE sheet.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CueSheet {
+    pub performer: Option<String>,
+    pub title: Option<String>,
+    /// The indexed audio file, relative to the sheet.
+    pub file: String,
+    pub tracks: Vec<CueTrack>,
+}
+
+impl CueSheet {
+    /// The sheet for `file` from the final song timestamps. With
+    /// `media_duration`, each interlude ending where a song starts becomes
+    /// that song's pregap (the same spans `--emit-interludes` would cut).
+    pub fn for_concert(
+        concert: &ConcertInfo,
+        file: &str,
+        timestamps: &[SongTimestamp],
+        media_duration: Option<f64>,
+    ) -> CueSheet {
+        let interludes = media_duration
+            .map(|duration| derive_interludes(timestamps, duration))
+            .unwrap_or_default();
+        let performer = Some(concert.artist.clone()).filter(|artist| !artist.is_empty());
+        let tracks = timestamps
+            .iter()
+            .map(|song| CueTrack {
+                title: song.title.clone(),
+                performer: performer.clone(),
+                pregap_start: interludes
+                    .iter()
+                    .find(|interlude| (interlude.end_time - song.start_time).abs() < 1e-6)
+                    .map(|interlude| interlude.start_time),
+                start_time: song.start_time,
+            })
+            .collect();
+        CueSheet {
+            performer,
+            title: Some(concert.album.clone()).filter(|album| !album.is_empty()),
+            file: file.to_string(),
+            tracks,
+        }
+    }
+
+    /// When `index` (0-based) plays: from its `INDEX 01` to the next track's
+    /// `INDEX 00` (or `INDEX 01`). The last track runs to the end of the file,
+    /// so its end is `None`.
+    pub fn track_span(&self, index: usize) -> Option<(f64, Option<f64>)> {
+        let track = self.tracks.get(index)?;
+        let end = self
+            .tracks
+            .get(index + 1)
+            .map(|next| next.pregap_start.unwrap_or(next.start_time));
+        Some((track.start_time, end))
+    }
+
+    /// Render the sheet. CUE has no escaping, so double quotes in values
+    /// become single quotes.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if let Some(performer) = &self.performer {
+            let _ = writeln!(out, "PERFORMER \"{}\"", quote(performer));
+        }
+        if let Some(title) = &self.title {
+            let _ = writeln!(out, "TITLE \"{}\"", quote(title));
+        }
+        let _ = writeln!(out, "FILE \"{}\" WAVE", quote(&self.file));
+        for (i, track) in self.tracks.iter().enumerate() {
+            let _ = writeln!(out, "  TRACK {:02} AUDIO", i + 1);
+            let _ = writeln!(out, "    TITLE \"{}\"", quote(&track.title));
+            if let Some(performer) = &track.performer {
+                let _ = writeln!(out, "    PERFORMER \"{}\"", quote(performer));
+            }
+            if let Some(pregap) = track.pregap_start {
+                let _ = writeln!(out, "    INDEX 00 {}", time_code(pregap));
+            }
+            let _ = writeln!(out, "    INDEX 01 {}", time_code(track.start_time));
+        }
+        out
+    }
+
+    /// Parse a single-file sheet. `REM` and unknown commands are ignored; a
+    /// second `FILE` is an error, since tracks are looked up in one file.
+    pub fn parse(text: &str) -> Result<CueSheet> {
+        let mut sheet = CueSheet {
+            performer: None,
+            title: None,
+            file: String::new(),
+            tracks: Vec::new(),
+        };
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            let (command, rest) = line.split_once(' ').unwrap_or((line, ""));
+            let track = sheet.tracks.last_mut();
+            match (command.to_ascii_uppercase().as_str(), track) {
+                ("FILE", _) => {
+                    anyhow::ensure!(
+                        sheet.file.is_empty(),
+                        "line {}: only single-file sheets are supported",
+                        number + 1
+                    );
+                    sheet.file = quoted(rest);
+                }
+                ("TRACK", _) => sheet.tracks.push(CueTrack {
+                    title: String::new(),
+                    performer: None,
+                    pregap_start: None,
+                    start_time: f64::NAN,
+                }),
+                ("TITLE", Some(track)) => track.title = quoted(rest),
+                ("TITLE", None) => sheet.title = Some(quoted(rest)),
+                ("PERFORMER", Some(track)) => track.performer = Some(quoted(rest)),
+                ("PERFORMER", None) => sheet.performer = Some(quoted(rest)),
+                ("INDEX", Some(track)) => {
+                    let (index, time) = rest
+                        .split_once(' ')
+                        .ok_or_else(|| anyhow!("line {}: malformed INDEX", number + 1))?;
+                    let seconds = parse_time_code(time.trim())
+                        .with_context(|| format!("line {}: bad INDEX time", number + 1))?;
+                    match index.parse::<u32>() {
+                        Ok(0) => track.pregap_start = Some(seconds),
+                        Ok(1) => track.start_time = seconds,
+                        _ => {}
+                    }
+                }
+                _ => {}
+            }
+        }
+        anyhow::ensure!(!sheet.file.is_empty(), "CUE sheet has no FILE");
+        if let Some(i) = sheet.tracks.iter().position(|t| t.start_time.is_nan()) {
+            return Err(anyhow!("track {} has no INDEX 01", i + 1));
+        }
+        Ok(sheet)
+    }
+}
+
+fn quote(value: &str) -> String {
+    value.replace('"', "'")
+}
+
+/// A value that may be `"quoted"` and followed by more words (`FILE "x" WAVE`).
+fn quoted(rest: &str) -> String {
+    let rest = rest.trim();
+    match rest.strip_prefix('"').and_then(|s| s.split_once('"')) {
+        Some((value, _)) => value.to_string(),
+        None => rest.split(' ').next().unwrap_or_default().to_string(),
+    }
+}
+
+/// `MM:SS:FF` with 75 frames per second; minutes are not capped at 99.
+fn time_code(seconds: f64) -> String {
+    let frames = (seconds.max(0.0) * FRAMES_PER_SECOND).round() as u64;
+    let (minutes, rest) = (frames / (60 * 75), frames % (60 * 75));
+    format!("{:02}:{:02}:{:02}", minutes, rest / 75, rest % 75)
+}
+
+fn parse_time_code(code: &str) -> Result<f64> {
+    let parts: Vec<u64> = code
+        .split(':')
+        .map(str::parse)
+        .collect::<Result<_, _>>()
+        .with_context(|| format!("not a MM:SS:FF time: {code:?}"))?;
+    match parts[..] {
+        [minutes, seconds, frames] if seconds < 60 && frames < 75 => {
+            Ok((minutes * 60 + seconds) as f64 + frames as f64 / FRAMES_PER_SECOND)
+        }
+        _ => Err(anyhow!("not a MM:SS:FF time: {code:?}")),
+    }
+}
+
+/// Write the source's whole first audio stream to `output_file` with
+/// `encoder_args` (see [`crate::ffmpeg::audio_encoder_args`]) and `tags`.
+pub fn write_album_file(
+    input_file: &Path,
+    output_file: &Path,
+    encoder_args: &[String],
+    tags: &TrackTags,
+) -> Result<()> {
+    let mut ffmpeg = create_ffmpeg_command();
+    ffmpeg
+        .arg("-i")
+        .args([input_file.as_os_str()])
+        .args(["-vn", "-map", "0:a:0"])
+        .args(encoder_args);
+    let mut cmd = ffmpeg.cmd();
+    tags.apply(&mut cmd);
+    cmd.arg("-y").arg(output_file);
+    let status = cmd
+        .status()
+        .context("Failed to run ffmpeg for the album file")?;
+    if !status.success() {
+        return Err(anyhow!(
+            "Failed to write album file {}",
+            output_file.display()
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use concert_types::Song;
+
+    fn concert() -> ConcertInfo {
+        ConcertInfo {
+            artist: "The \"Band\"".to_string(),
+            source: String::new(),
+            show: String::new(),
+            date: None,
+            album: "Album: Live".to_string(),
+            description: None,
+            set_list: vec![
+                Song {
+                    title: "One".to_string(),
+                },
+                Song {
+                    title: "Two".to_string(),
+                },
+            ],
+            musicians: Vec::new(),
+            preview_image_url: None,
+            teaser: None,
+            timestamps: None,
+            interludes: None,
+        }
+    }
+
+    fn ts(title: &str, start_time: f64, end_time: f64) -> SongTimestamp {
+        SongTimestamp {
+            title: title.to_string(),
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            boundary: None,
+        }
+    }
+
+    #[test]
+    fn sheet_puts_interludes_in_pregaps_and_round_trips() {
+        let concert = concert();
+        let songs = vec![ts("One", 30.0, 200.0), ts("Two", 200.0, 400.0)];
+        let sheet = CueSheet::for_concert(
+            &concert,
+            &album_file_name(&concert, AudioFormat::Flac),
+            &songs,
+            Some(500.0),
+        );
+        let text = sheet.render();
+        assert_eq!(
+            text,
+            "PERFORMER \"The 'Band'\"\n\
+             TITLE \"Album: Live\"\n\
+             FILE \"Album Live.album.flac\" WAVE\n\
+             \x20 TRACK 01 AUDIO\n\
+             \x20   TITLE \"One\"\n\
+             \x20   PERFORMER \"The 'Band'\"\n\
+             \x20   INDEX 00 00:00:00\n\
+             \x20   INDEX 01 00:30:00\n\
+             \x20 TRACK 02 AUDIO\n\
+             \x20   TITLE \"Two\"\n\
+             \x20   PERFORMER \"The 'Band'\"\n\
+             \x20   INDEX 01 03:20:00\n"
+        );
+
+        let parsed = CueSheet::parse(&text).unwrap();
+        assert_eq!(parsed.file, "Album Live.album.flac");
+        assert_eq!(parsed.tracks.len(), 2);
+        assert_eq!(parsed.track_span(0), Some((30.0, Some(200.0))));
+        assert_eq!(parsed.track_span(1), Some((200.0, None)));
+    }
+
+    #[test]
+    fn time_codes_use_75_frames_per_second() {
+        assert_eq!(time_code(6001.5), "100:01:38");
+        assert_eq!(parse_time_code("100:01:38").unwrap(), 6001.0 + 38.0 / 75.0);
+        assert!(parse_time_code("01:60:00").is_err());
+    }
+
+    #[test]
+    fn parse_rejects_multi_file_sheets() {
+        let text = "FILE \"a.flac\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n\
+                    FILE \"b.flac\" WAVE\n";
+        assert!(CueSheet::parse(text).is_err());
+    }
+}
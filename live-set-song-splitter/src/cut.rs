@@ -32,7 +32,7 @@ use anyhow::{anyhow, Context, Result};
 use clap::{Parser, ValueEnum};
 
 use crate::ffmpeg;
-use concert_types::ConcertInfo;
+use crate::tags::TrackTags;
 
 /// How to cut the video stream for each track. All modes keep audio and video in
 /// sync; they trade cut precision against speed/quality.
@@ -57,6 +57,70 @@ pub enum VideoCutMode {
 pub const REENCODE_PRESET: &str = "veryfast";
 pub const REENCODE_CRF: &str = "18";
 
+/// A downscaled H.264 copy of each video track, for streaming to phones. Written
+/// beside the full-resolution `<stem>.mp4` as `<stem>.<height>p.mp4`.
+#[derive(Debug, Clone, Copy, ValueEnum, PartialEq, Eq)]
+pub enum Rendition {
+    #[value(name = "1080p")]
+    P1080,
+    #[value(name = "720p")]
+    P720,
+    #[value(name = "480p")]
+    P480,
+    #[value(name = "360p")]
+    P360,
+}
+
+impl Rendition {
+    /// The ladder, tallest first.
+    pub const ALL: [Rendition; 4] = [
+        Rendition::P1080,
+        Rendition::P720,
+        Rendition::P480,
+        Rendition::P360,
+    ];
+
+    pub fn height(self) -> u32 {
+        match self {
+            Rendition::P1080 => 1080,
+            Rendition::P720 => 720,
+            Rendition::P480 => 480,
+            Rendition::P360 => 360,
+        }
+    }
+
+    /// What follows the track stem, e.g. `720p.mp4`.
+    pub fn extension(self) -> &'static str {
+        match self {
+            Rendition::P1080 => "1080p.mp4",
+            Rendition::P720 => "720p.mp4",
+            Rendition::P480 => "480p.mp4",
+            Rendition::P360 => "360p.mp4",
+        }
+    }
+
+    /// The name used on the CLI and in settings, e.g. `720p`.
+    pub fn name(self) -> &'static str {
+        self.extension().trim_end_matches(".mp4")
+    }
+
+    pub fn from_name(name: &str) -> Option<Rendition> {
+        Rendition::ALL
+            .into_iter()
+            .find(|rendition| rendition.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn from_height(height: u32) -> Option<Rendition> {
+        Rendition::ALL
+            .into_iter()
+            .find(|rendition| rendition.height() == height)
+    }
+}
+
+/// x264 quality for renditions: lighter than [`REENCODE_CRF`], since they are
+/// for streaming rather than keeping.
+pub const RENDITION_CRF: &str = "23";
+
 /// How far past the cut point to look for the next keyframe. Must exceed the
 /// source's GOP length (NPR sources use 4s keyframe intervals).
 const KEYFRAME_PROBE_WINDOW_SECS: f64 = 30.0;
@@ -444,6 +508,37 @@ pub fn probe_source_video_params(input_file: &str) -> Result<SourceVideoParams>
     Ok(params)
 }
 
+/// Height in pixels of the source's first video stream. Renditions at or above
+/// it are not written.
+pub fn probe_video_height(input_file: &str) -> Result<u32> {
+    let output = ffmpeg::create_ffprobe_command()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "v:0",
+            "-show_entries",
+            "stream=height",
+            "-of",
+            "csv=p=0",
+            input_file,
+        ])
+        .output()
+        .context("running ffprobe for video height")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "ffprobe failed probing the video height of {}: {}",
+            input_file,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let stdout = String::from_utf8(output.stdout)?;
+    stdout
+        .trim()
+        .parse::<u32>()
+        .with_context(|| format!("unexpected ffprobe height {:?} for {}", stdout, input_file))
+}
+
 /// Parse an ffprobe rational frame rate like "24/1" into frames per second.
 fn parse_frame_rate(rate: &str) -> Option<f64> {
     let (num, den) = rate.split_once('/')?;
@@ -542,16 +637,13 @@ fn run_ffmpeg(args: &[String], output_file: &str, what: &str) -> Result<()> {
 
 /// Extract `[start_time, end_time]` with a single ffmpeg command
 /// ([`VideoCutMode::Copy`] or [`VideoCutMode::Reencode`]).
-#[allow(clippy::too_many_arguments)] // All arguments are required ffmpeg segment parameters
 pub fn extract_segment(
     input_file: &str,
     output_file: &str,
     start_time: f64,
     end_time: f64,
     cut_mode: VideoCutMode,
-    song_title: Option<&str>,
-    concertdata: &ConcertInfo,
-    track_number: Option<usize>,
+    tags: &TrackTags,
 ) -> Result<()> {
     if cut_mode == VideoCutMode::Smart {
         return Err(anyhow!(
@@ -563,7 +655,7 @@ pub fn extract_segment(
     let mut cmd = ffmpeg_cmd.cmd();
 
     // Add metadata
-    ffmpeg::add_metadata_to_cmd(&mut cmd, song_title, concertdata, track_number);
+    tags.apply(&mut cmd);
 
     cmd.args([
         "-y", // Overwrite output file
@@ -579,19 +671,54 @@ pub fn extract_segment(
     Ok(())
 }
 
+/// Build the ffmpeg arguments that transcode an already-cut `track_file` into
+/// `rendition`. Working from the cut track keeps its exact boundaries and
+/// tags; the audio is stream-copied. `+faststart` lets a browser start playing
+/// before the whole file arrives.
+pub fn build_rendition_args(track_file: &str, rendition: Rendition) -> Vec<String> {
+    vec![
+        "-i".into(),
+        track_file.into(),
+        "-map".into(),
+        "0:v:0".into(),
+        "-map".into(),
+        "0:a?".into(),
+        "-vf".into(),
+        format!("scale=-2:{}", rendition.height()),
+        "-c:v".into(),
+        "libx264".into(),
+        "-preset".into(),
+        REENCODE_PRESET.into(),
+        "-crf".into(),
+        RENDITION_CRF.into(),
+        "-pix_fmt".into(),
+        "yuv420p".into(),
+        "-c:a".into(),
+        "copy".into(),
+        "-movflags".into(),
+        "+faststart".into(),
+    ]
+}
+
+/// Write `rendition` of the cut video track `track_file` to `output_file`.
+pub fn extract_rendition(track_file: &str, output_file: &str, rendition: Rendition) -> Result<()> {
+    run_ffmpeg(
+        &build_rendition_args(track_file, rendition),
+        output_file,
+        &format!("{} rendition", rendition.name()),
+    )
+}
+
 /// Extract `[start_time, end_time]` under [`VideoCutMode::Smart`]: plan against the
 /// next keyframe, then either delegate to a single-command mode or build the
 /// head/tail/audio splice in a `<output>.work` directory (removed afterwards).
-#[allow(clippy::too_many_arguments)] // All arguments are required ffmpeg segment parameters
 pub fn extract_segment_smart(
     input_file: &str,
     output_file: &str,
     start_time: f64,
     end_time: f64,
     params: &SourceVideoParams,
-    song_title: Option<&str>,
-    concertdata: &ConcertInfo,
-    track_number: Option<usize>,
+    tags: &TrackTags,
 ) -> Result<()> {
     let next_keyframe = probe_next_keyframe(input_file, start_time)?;
     let plan = plan_smart_cut(start_time, end_time, next_keyframe, params);
@@ -608,15 +735,13 @@ pub fn extract_segment_smart(
                 start_time,
                 end_time,
                 VideoCutMode::Copy,
-                song_title,
-                concertdata,
-                track_number,
+                tags,
             );
         }
         SmartCutPlan::ReencodeWhole => {
             println!(
                 "Smart cut falling back to a full re-encode for \"{}\"",
-                song_title.unwrap_or("?")
+                tags.get("title").unwrap_or("?")
             );
             return extract_segment(
                 input_file,
@@ -624,9 +749,7 @@ pub fn extract_segment_smart(
                 start_time,
                 end_time,
                 VideoCutMode::Reencode,
-                song_title,
-                concertdata,
-                track_number,
+                tags,
             );
         }
         SmartCutPlan::Spliced { keyframe } => keyframe,
@@ -643,9 +766,7 @@ pub fn extract_segment_smart(
         keyframe,
         params,
         &work_dir,
-        song_title,
-        concertdata,
-        track_number,
+        tags,
     );
     if let Err(e) = fs::remove_dir_all(&work_dir) {
         println!("Warning: failed to remove {}: {}", work_dir, e);
@@ -664,9 +785,7 @@ fn splice_segment(
     keyframe: f64,
     params: &SourceVideoParams,
     work_dir: &str,
-    song_title: Option<&str>,
-    concertdata: &ConcertInfo,
-    track_number: Option<usize>,
+    tags: &TrackTags,
 ) -> Result<()> {
     let head_file = format!("{}/{}", work_dir, HEAD_FILE_NAME);
     let tail_file = format!("{}/{}", work_dir, TAIL_FILE_NAME);
@@ -715,7 +834,7 @@ fn splice_segment(
     let mut ffmpeg_cmd = ffmpeg::create_ffmpeg_command();
     ffmpeg_cmd.args(build_smart_concat_args(&list_file, &audio_file));
     let mut cmd = ffmpeg_cmd.cmd();
-    ffmpeg::add_metadata_to_cmd(&mut cmd, song_title, concertdata, track_number);
+    tags.apply(&mut cmd);
     cmd.args(["-y", output_file]);
     let status = cmd
         .status()
@@ -789,6 +908,20 @@ mod tests_cut_args {
         assert!(!args.iter().any(|a| a == "-to"));
     }
 
+    // Renditions scale the cut track to the ladder height, keeping the aspect
+    // ratio at an even width, and copy its audio.
+    #[test]
+    fn rendition_args_scale_to_the_ladder_height() {
+        let args = build_rendition_args("Song.mp4", Rendition::P480);
+        assert_eq!(value_after(&args, "-i"), Some("Song.mp4"));
+        assert_eq!(value_after(&args, "-vf"), Some("scale=-2:480"));
+        assert_eq!(value_after(&args, "-crf"), Some(RENDITION_CRF));
+        assert_eq!(value_after(&args, "-c:a"), Some("copy"));
+        assert_eq!(Rendition::P480.extension(), "480p.mp4");
+        assert_eq!(Rendition::from_name("720P"), Some(Rendition::P720));
+        assert_eq!(Rendition::from_height(360), Some(Rendition::P360));
+    }
+
     #[test]
     fn smart_is_the_default_mode() {
         assert_eq!(VideoCutMode::default(), VideoCutMode::Smart);
@@ -0,0 +1,977 @@
+//! Decoding video frames and audio samples for analysis.
+//!
+//! Overlay detection, start-time refinement and the end-of-set black-frame search
+//! each sample small, scaled (and optionally cropped/negated) frames out of the
+//! concert video; silence analysis needs the whole soundtrack as mono PCM. With the
+//! default `ffmpeg-decode` feature both are decoded in-process through `ffmpeg-next`;
+//! without it the `ffmpeg` CLI streams raw RGB / PCM through a pipe. Either way the
+//! frames arrive as [`DynamicImage`]s and nothing is written to disk.
+//!
+//! PNG files are only involved through [`FrameCache`]: `--keep-frames` writes each
+//! decoded frame out for building OCR test data, and `--reuse-frames` reads such a
+//! directory back instead of decoding again.
+
+use std::path::{Path, PathBuf};
+
+use ::image::DynamicImage;
+use anyhow::{Context, Result};
+
+use crate::io;
+
+/// A rectangle cut out of the scaled frame, in scaled-frame pixels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Crop {
+    pub x: u32,
+    pub y: u32,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Written and parsed as ffmpeg's `crop` filter arguments: `width:height:x:y`.
+impl std::fmt::Display for Crop {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}:{}:{}", self.width, self.height, self.x, self.y)
+    }
+}
+
+impl std::str::FromStr for Crop {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        let parts = s
+            .split(':')
+            .map(|part| part.trim().parse::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .with_context(|| format!("Invalid crop '{}': expected width:height:x:y", s))?;
+        let [width, height, x, y] = parts[..] else {
+            anyhow::bail!("Invalid crop '{}': expected width:height:x:y", s);
+        };
+        if width == 0 || height == 0 {
+            anyhow::bail!("Invalid crop '{}': width and height must be positive", s);
+        }
+        Ok(Crop {
+            x,
+            y,
+            width,
+            height,
+        })
+    }
+}
+
+/// Which frames to sample from a video, and how to shape them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FrameSpec {
+    /// Seconds into the video of the first sample.
+    pub start: f64,
+    /// Stop before this many seconds into the video; `None` runs to the end.
+    pub end: Option<f64>,
+    /// Samples per second. One frame is taken per `1/fps` seconds of video — the
+    /// first decoded frame at or after each sample time, as ffmpeg's `fps` filter
+    /// does. At the video's own frame rate every frame is kept.
+    pub fps: u32,
+    /// Every frame is scaled to this size (width, height) first.
+    pub scale: (u32, u32),
+    /// Then cropped to this region of the scaled frame.
+    pub crop: Option<Crop>,
+    /// Then color-inverted (overlay text becomes dark on light, which OCR prefers).
+    pub negate: bool,
+}
+
+impl FrameSpec {
+    /// Size of the frames this spec yields.
+    pub fn output_size(&self) -> (u32, u32) {
+        match self.crop {
+            Some(crop) => (crop.width, crop.height),
+            None => self.scale,
+        }
+    }
+
+    /// Video time of sample `index`.
+    pub fn sample_time(&self, index: usize) -> f64 {
+        self.start + index as f64 / f64::from(self.fps)
+    }
+}
+
+/// One sampled frame.
+#[derive(Debug, Clone)]
+pub struct Frame {
+    /// Position in the sample grid: sample `index` is the frame for
+    /// [`FrameSpec::sample_time`]`(index)`. At 1 fps from 0s this is the second.
+    pub index: usize,
+    /// Presentation time of the decoded frame, in seconds.
+    pub timestamp: f64,
+    pub image: DynamicImage,
+}
+
+/// Decode the frames `spec` selects from `input_file`, lazily and in order.
+pub fn video_frames(
+    input_file: &str,
+    spec: FrameSpec,
+) -> Result<Box<dyn Iterator<Item = Result<Frame>>>> {
+    #[cfg(feature = "ffmpeg-decode")]
+    {
+        Ok(Box::new(native::VideoFrames::open(input_file, spec)?))
+    }
+    #[cfg(not(feature = "ffmpeg-decode"))]
+    {
+        Ok(Box::new(pipe::VideoFrames::open(input_file, spec)?))
+    }
+}
+
+/// Decode `input_file`'s audio as mono f32 samples in [-1.0, 1.0] at `sample_rate`.
+pub fn audio_samples(input_file: &str, sample_rate: u32) -> Result<Vec<f32>> {
+    #[cfg(feature = "ffmpeg-decode")]
+    {
+        native::audio_samples(input_file, sample_rate)
+    }
+    #[cfg(not(feature = "ffmpeg-decode"))]
+    {
+        pipe::audio_samples(input_file, sample_rate)
+    }
+}
+
+/// File in a [`FrameCache`] directory recording the [`FrameSpec`] its frames
+/// were decoded with.
+const FRAME_SPEC_FILE: &str = "frame_spec.txt";
+
+/// A directory of `N.png` frames (`N` = [`Frame::index`]) shadowing a decode.
+#[derive(Debug, Clone, Copy)]
+pub struct FrameCache<'a> {
+    pub dir: &'a str,
+    /// Read frames back from `dir` when it has any and they were kept with the
+    /// same [`FrameSpec`] (`--reuse-frames`).
+    pub reuse: bool,
+    /// Write every decoded frame into `dir` (`--keep-frames`).
+    pub keep: bool,
+}
+
+impl FrameCache<'_> {
+    /// The frames `spec` selects: read back from the cache when reusing a populated
+    /// directory kept with the same spec, otherwise decoded (and written out, with
+    /// the spec, when keeping).
+    pub fn frames(
+        &self,
+        input_file: &str,
+        spec: FrameSpec,
+    ) -> Result<Box<dyn Iterator<Item = Result<Frame>>>> {
+        if self.reuse && self.kept_with(&spec) {
+            let cached = cached_frame_paths(Path::new(self.dir))?;
+            if !cached.is_empty() {
+                return Ok(Box::new(cached.into_iter().map(move |(index, path)| {
+                    let image = ::image::open(&path)
+                        .with_context(|| format!("Failed to read frame {}", path.display()))?;
+                    Ok(Frame {
+                        index,
+                        timestamp: spec.sample_time(index),
+                        image,
+                    })
+                })));
+            }
+        }
+        let frames = video_frames(input_file, spec)?;
+        if !self.keep {
+            return Ok(frames);
+        }
+        io::overwrite_dir(self.dir)?;
+        let spec_path = Path::new(self.dir).join(FRAME_SPEC_FILE);
+        std::fs::write(&spec_path, spec_line(&spec))
+            .with_context(|| format!("Failed to write {}", spec_path.display()))?;
+        let dir = PathBuf::from(self.dir);
+        Ok(Box::new(frames.map(move |frame| {
+            let frame = frame?;
+            let path = dir.join(format!("{}.png", frame.index));
+            frame
+                .image
+                .save(&path)
+                .with_context(|| format!("Failed to keep frame {}", path.display()))?;
+            Ok(frame)
+        })))
+    }
+
+    /// Whether the directory records `spec` as the one its frames were kept with.
+    /// Frames kept before the spec was recorded don't count.
+    fn kept_with(&self, spec: &FrameSpec) -> bool {
+        std::fs::read_to_string(Path::new(self.dir).join(FRAME_SPEC_FILE))
+            .is_ok_and(|kept| kept == spec_line(spec))
+    }
+}
+
+/// The [`FRAME_SPEC_FILE`] contents for `spec`. Debug formatting prints every
+/// field, floats in round-trip form.
+fn spec_line(spec: &FrameSpec) -> String {
+    format!("{:?}", spec)
+}
+
+/// Frame number of a cached `N.png`; `None` for anything else in the directory
+/// (subdirectories, B/W variants such as `Nbw.png` written by older versions).
+pub fn cached_frame_number(path: &Path) -> Option<usize> {
+    if path.extension().is_some_and(|ext| ext == "png") {
+        path.file_stem()?.to_str()?.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// The cached frames in `dir`, by ascending frame number. A missing directory
+/// has none.
+fn cached_frame_paths(dir: &Path) -> Result<Vec<(usize, PathBuf)>> {
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut frames = std::fs::read_dir(dir)
+        .with_context(|| format!("Failed to list frames in {}", dir.display()))?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter_map(|path| cached_frame_number(&path).map(|index| (index, path)))
+        .collect::<Vec<_>>();
+    frames.sort_by_key(|(index, _)| *index);
+    Ok(frames)
+}
+
+/// Which decoded frames fill the `fps` sample grid: the first frame at or after
+/// each sample time. A frame that arrives after several sample times (a gap in a
+/// variable-frame-rate source) fills the latest of them. The CLI decoder leaves
+/// this to ffmpeg's `fps` filter.
+#[cfg_attr(not(feature = "ffmpeg-decode"), allow(dead_code))]
+struct Sampler {
+    spec: FrameSpec,
+    next: usize,
+}
+
+#[cfg_attr(not(feature = "ffmpeg-decode"), allow(dead_code))]
+impl Sampler {
+    /// Half a millisecond of slack so a frame stamped a rounding error before its
+    /// sample time still counts as at it.
+    const EPSILON: f64 = 0.0005;
+
+    fn new(spec: FrameSpec) -> Self {
+        Sampler { spec, next: 0 }
+    }
+
+    /// Whether the range is over at `timestamp`.
+    fn past_end(&self, timestamp: f64) -> bool {
+        self.spec.end.is_some_and(|end| timestamp >= end)
+    }
+
+    /// The sample index a frame at `timestamp` fills, or `None` to drop it.
+    fn sample(&mut self, timestamp: f64) -> Option<usize> {
+        if timestamp + Self::EPSILON < self.spec.sample_time(self.next) {
+            return None;
+        }
+        let mut index = self.next;
+        while timestamp + Self::EPSILON >= self.spec.sample_time(index + 1) {
+            index += 1;
+        }
+        self.next = index + 1;
+        Some(index)
+    }
+}
+
+/// Scale, crop and negate an RGB frame per `spec`. `rgb` is already at
+/// `spec.scale`.
+fn shape(rgb: ::image::RgbImage, spec: &FrameSpec) -> DynamicImage {
+    let mut image = DynamicImage::ImageRgb8(rgb);
+    if let Some(crop) = spec.crop {
+        image = image.crop_imm(crop.x, crop.y, crop.width, crop.height);
+    }
+    if spec.negate {
+        image.invert();
+    }
+    image
+}
+
+#[cfg(feature = "ffmpeg-decode")]
+mod native {
+    use std::sync::Once;
+
+    use ::image::RgbImage;
+    use anyhow::{anyhow, Context, Result};
+    use ffmpeg::format::{sample, Pixel, Sample};
+    use ffmpeg::software::{resampling, scaling};
+    use ffmpeg::{codec, decoder, format, frame, media, ChannelLayout, Packet};
+    use ffmpeg_next as ffmpeg;
+
+    use super::{shape, Frame, FrameSpec, Sampler};
+
+    static INIT: Once = Once::new();
+
+    /// Initialize libav* once, at the same log level the CLI runs with
+    /// (`-loglevel warning`).
+    fn init() -> Result<()> {
+        ffmpeg::init().context("Failed to initialize ffmpeg")?;
+        INIT.call_once(|| ffmpeg::log::set_level(ffmpeg::log::Level::Warning));
+        Ok(())
+    }
+
+    /// Feed `packet` to `decoder`. A packet the decoder rejects as corrupt is
+    /// logged and skipped, as the ffmpeg CLI does, so one damaged packet in a
+    /// long recording doesn't end the decode.
+    fn send_packet(decoder: &mut decoder::Opened, packet: &Packet, input_file: &str) -> Result<()> {
+        match decoder.send_packet(packet) {
+            Err(ffmpeg::Error::InvalidData) => {
+                log::warn!(
+                    "Skipping a corrupt packet at pts {:?} in {}",
+                    packet.pts(),
+                    input_file
+                );
+                Ok(())
+            }
+            result => result.context("Failed to decode a packet"),
+        }
+    }
+
+    /// Let the decoder pick its own thread count, as the CLI does.
+    fn threaded(mut context: codec::Context) -> codec::Context {
+        context.set_threading(codec::threading::Config {
+            kind: codec::threading::Type::Frame,
+            count: 0,
+            ..Default::default()
+        });
+        context
+    }
+
+    pub(super) struct VideoFrames {
+        input: format::context::Input,
+        input_file: String,
+        stream_index: usize,
+        time_base: f64,
+        decoder: decoder::Video,
+        scaler: Option<scaling::Context>,
+        spec: FrameSpec,
+        sampler: Sampler,
+        draining: bool,
+        done: bool,
+    }
+
+    impl VideoFrames {
+        pub(super) fn open(input_file: &str, spec: FrameSpec) -> Result<Self> {
+            init()?;
+            let mut input = format::input(&input_file)
+                .with_context(|| format!("Failed to open {} to decode frames", input_file))?;
+            let (stream_index, time_base, parameters) = {
+                let stream = input
+                    .streams()
+                    .best(media::Type::Video)
+                    .ok_or_else(|| anyhow!("{} has no video stream", input_file))?;
+                (
+                    stream.index(),
+                    f64::from(stream.time_base()),
+                    stream.parameters(),
+                )
+            };
+            let decoder = threaded(codec::Context::from_parameters(parameters)?)
+                .decoder()
+                .video()
+                .with_context(|| format!("Failed to open a video decoder for {}", input_file))?;
+            if spec.start > 0.0 {
+                // Like `-ss` before `-i`: seek to the keyframe at or before the start
+                // and let the sampler drop the frames decoded ahead of it.
+                let position = (spec.start * f64::from(ffmpeg::ffi::AV_TIME_BASE)) as i64;
+                input
+                    .seek(position, ..position)
+                    .with_context(|| format!("Failed to seek {} to {}s", input_file, spec.start))?;
+            }
+            Ok(VideoFrames {
+                input,
+                input_file: input_file.to_string(),
+                stream_index,
+                time_base,
+                decoder,
+                scaler: None,
+                spec,
+                sampler: Sampler::new(spec),
+                draining: false,
+                done: false,
+            })
+        }
+
+        /// The next decoded frame and its time in seconds, or `None` at the end of
+        /// the stream.
+        fn next_decoded(&mut self) -> Result<Option<(f64, frame::Video)>> {
+            let mut decoded = frame::Video::empty();
+            loop {
+                if self.decoder.receive_frame(&mut decoded).is_ok() {
+                    let timestamp = decoded.timestamp().unwrap_or(0) as f64 * self.time_base;
+                    return Ok(Some((timestamp, decoded)));
+                }
+                if self.draining {
+                    return Ok(None);
+                }
+                let mut packet = Packet::empty();
+                match packet.read(&mut self.input) {
+                    Ok(()) if packet.stream() == self.stream_index => {
+                        send_packet(&mut self.decoder, &packet, &self.input_file)?
+                    }
+                    Ok(()) => {}
+                    Err(ffmpeg::Error::Eof) => {
+                        self.decoder.send_eof()?;
+                        self.draining = true;
+                    }
+                    Err(e) => return Err(e).context("Failed to read a video packet"),
+                }
+            }
+        }
+
+        fn to_rgb(&mut self, decoded: &frame::Video) -> Result<RgbImage> {
+            let (width, height) = self.spec.scale;
+            // A stream can change resolution or pixel format mid-way (a spliced
+            // upload); the scaler only accepts the input it was built for.
+            let source = (decoded.format(), decoded.width(), decoded.height());
+            if self.scaler.as_ref().is_some_and(|scaler| {
+                let input = scaler.input();
+                (input.format, input.width, input.height) != source
+            }) {
+                log::debug!(
+                    "Video of {} changed to {:?} {}x{}; rebuilding the scaler",
+                    self.input_file,
+                    source.0,
+                    source.1,
+                    source.2
+                );
+                self.scaler = None;
+            }
+            let scaler = match &mut self.scaler {
+                Some(scaler) => scaler,
+                // Bicubic, the `scale` filter's default.
+                None => self.scaler.insert(scaling::Context::get(
+                    decoded.format(),
+                    decoded.width(),
+                    decoded.height(),
+                    Pixel::RGB24,
+                    width,
+                    height,
+                    scaling::Flags::BICUBIC,
+                )?),
+            };
+            let mut rgb = frame::Video::empty();
+            scaler.run(decoded, &mut rgb)?;
+            // Rows are padded to the frame's stride; copy out the pixels.
+            let stride = rgb.stride(0);
+            let row = width as usize * 3;
+            let mut pixels = Vec::with_capacity(row * height as usize);
+            for line in rgb.data(0).chunks(stride).take(height as usize) {
+                pixels.extend_from_slice(&line[..row]);
+            }
+            RgbImage::from_raw(width, height, pixels)
+                .ok_or_else(|| anyhow!("scaled frame has the wrong size"))
+        }
+
+        fn next_frame(&mut self) -> Result<Option<Frame>> {
+            while let Some((timestamp, decoded)) = self.next_decoded()? {
+                if self.sampler.past_end(timestamp) {
+                    return Ok(None);
+                }
+                let Some(index) = self.sampler.sample(timestamp) else {
+                    continue;
+                };
+                let image = shape(self.to_rgb(&decoded)?, &self.spec);
+                return Ok(Some(Frame {
+                    index,
+                    timestamp,
+                    image,
+                }));
+            }
+            Ok(None)
+        }
+    }
+
+    impl Iterator for VideoFrames {
+        type Item = Result<Frame>;
+
+        fn next(&mut self) -> Option<Result<Frame>> {
+            if self.done {
+                return None;
+            }
+            let next = self.next_frame().transpose();
+            self.done = !matches!(next, Some(Ok(_)));
+            next
+        }
+    }
+
+    pub(super) fn audio_samples(input_file: &str, sample_rate: u32) -> Result<Vec<f32>> {
+        init()?;
+        let mut input = format::input(&input_file)
+            .with_context(|| format!("Failed to open {} to decode audio", input_file))?;
+        let (stream_index, parameters) = {
+            let stream = input
+                .streams()
+                .best(media::Type::Audio)
+                .ok_or_else(|| anyhow!("{} has no audio stream", input_file))?;
+            (stream.index(), stream.parameters())
+        };
+        let mut decoder = threaded(codec::Context::from_parameters(parameters)?)
+            .decoder()
+            .audio()
+            .with_context(|| format!("Failed to open an audio decoder for {}", input_file))?;
+
+        let mut mono = Mono {
+            sample_rate,
+            resampler: None,
+            samples: Vec::new(),
+        };
+        for (stream, packet) in input.packets() {
+            if stream.index() == stream_index {
+                send_packet(&mut decoder, &packet, input_file)?;
+                mono.receive(&mut decoder)?;
+            }
+        }
+        decoder.send_eof()?;
+        mono.receive(&mut decoder)?;
+        mono.flush()?;
+        log::debug!(
+            "Decoded {} mono samples from {}",
+            mono.samples.len(),
+            input_file
+        );
+        Ok(mono.samples)
+    }
+
+    /// Downmixes and resamples decoded audio to packed mono f32 (the CLI's
+    /// `-ac 1 -ar <rate>`).
+    struct Mono {
+        sample_rate: u32,
+        resampler: Option<resampling::Context>,
+        samples: Vec<f32>,
+    }
+
+    impl Mono {
+        const FORMAT: Sample = Sample::F32(sample::Type::Packed);
+
+        fn receive(&mut self, decoder: &mut decoder::Audio) -> Result<()> {
+            let mut decoded = frame::Audio::empty();
+            while decoder.receive_frame(&mut decoded).is_ok() {
+                let resampler = match &mut self.resampler {
+                    Some(resampler) => resampler,
+                    None => {
+                        let mut layout = decoded.channel_layout();
+                        if layout.is_empty() {
+                            layout = ChannelLayout::default(i32::from(decoded.channels()));
+                        }
+                        self.resampler.insert(resampling::Context::get(
+                            decoded.format(),
+                            layout,
+                            decoded.rate(),
+                            Self::FORMAT,
+                            ChannelLayout::MONO,
+                            self.sample_rate,
+                        )?)
+                    }
+                };
+                // Room for the resampled frame plus whatever the resampler buffered.
+                let capacity = decoded.samples() * self.sample_rate as usize
+                    / decoded.rate().max(1) as usize
+                    + 1024;
+                let mut out = frame::Audio::new(Self::FORMAT, capacity, ChannelLayout::MONO);
+                resampler.run(&decoded, &mut out)?;
+                self.samples.extend_from_slice(out.plane::<f32>(0));
+            }
+            Ok(())
+        }
+
+        /// Collect the samples still buffered in the resampler.
+        fn flush(&mut self) -> Result<()> {
+            let Some(resampler) = &mut self.resampler else {
+                return Ok(());
+            };
+            loop {
+                let mut out = frame::Audio::new(Self::FORMAT, 4096, ChannelLayout::MONO);
+                resampler.flush(&mut out)?;
+                if out.samples() == 0 {
+                    return Ok(());
+                }
+                self.samples.extend_from_slice(out.plane::<f32>(0));
+            }
+        }
+    }
+}
+
+#[cfg(not(feature = "ffmpeg-decode"))]
+mod pipe {
+    use std::io::Read;
+    use std::process::{Child, ChildStdout, Stdio};
+
+    use ::image::RgbImage;
+    use anyhow::{anyhow, Context, Result};
+
+    use super::{shape, Frame, FrameSpec};
+    use crate::ffmpeg::create_ffmpeg_command;
+
+    /// Frames streamed as raw RGB from an `ffmpeg` child. The `fps`, `scale`,
+    /// `crop` and `negate` filters run in ffmpeg; frames come out on a constant
+    /// grid, so a frame's index is its position in the stream.
+    pub(super) struct VideoFrames {
+        child: Child,
+        stdout: ChildStdout,
+        input_file: String,
+        spec: FrameSpec,
+        next: usize,
+        done: bool,
+    }
+
+    impl VideoFrames {
+        pub(super) fn open(input_file: &str, spec: FrameSpec) -> Result<Self> {
+            let mut filters = vec![
+                format!("fps={}", spec.fps),
+                format!("scale={}:{}", spec.scale.0, spec.scale.1),
+            ];
+            if let Some(crop) = spec.crop {
+                filters.push(format!(
+                    "crop={}:{}:{}:{}",
+                    crop.width, crop.height, crop.x, crop.y
+                ));
+            }
+            if spec.negate {
+                filters.push("negate".to_string());
+            }
+            let mut ffmpeg = create_ffmpeg_command();
+            match spec.end {
+                Some(end) => {
+                    ffmpeg.time_range(spec.start, end);
+                }
+                None if spec.start > 0.0 => {
+                    ffmpeg.args(["-ss", &format!("{:.3}", spec.start)]);
+                }
+                None => {}
+            }
+            ffmpeg.args(["-i", input_file, "-an", "-vf", &filters.join(",")]);
+            ffmpeg.args(["-pix_fmt", "rgb24", "-f", "rawvideo", "-"]);
+            let mut cmd = ffmpeg.cmd();
+            cmd.stdout(Stdio::piped());
+            let mut child = cmd.spawn().with_context(|| {
+                format!(
+                    "Failed to spawn ffmpeg to decode frames from {}",
+                    input_file
+                )
+            })?;
+            let stdout = child
+                .stdout
+                .take()
+                .ok_or_else(|| anyhow!("ffmpeg child process has no stdout pipe"))?;
+            Ok(VideoFrames {
+                child,
+                stdout,
+                input_file: input_file.to_string(),
+                spec,
+                next: 0,
+                done: false,
+            })
+        }
+
+        fn next_frame(&mut self) -> Result<Option<Frame>> {
+            let (width, height) = self.spec.output_size();
+            let mut pixels = vec![0u8; width as usize * height as usize * 3];
+            match self.stdout.read_exact(&mut pixels) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => {
+                    let status = self.child.wait().with_context(|| {
+                        format!("Failed waiting for ffmpeg decoding {}", self.input_file)
+                    })?;
+                    if !status.success() {
+                        return Err(anyhow!(
+                            "ffmpeg failed to decode frames from {} (exit {:?})",
+                            self.input_file,
+                            status.code()
+                        ));
+                    }
+                    return Ok(None);
+                }
+                Err(e) => return Err(e).context("Failed to read a frame from ffmpeg"),
+            }
+            let index = self.next;
+            self.next += 1;
+            // ffmpeg already cropped and negated; only wrap the pixels.
+            let rgb = RgbImage::from_raw(width, height, pixels)
+                .ok_or_else(|| anyhow!("ffmpeg frame has the wrong size"))?;
+            let spec = FrameSpec {
+                crop: None,
+                negate: false,
+                ..self.spec
+            };
+            Ok(Some(Frame {
+                index,
+                timestamp: self.spec.sample_time(index),
+                image: shape(rgb, &spec),
+            }))
+        }
+    }
+
+    impl Iterator for VideoFrames {
+        type Item = Result<Frame>;
+
+        fn next(&mut self) -> Option<Result<Frame>> {
+            if self.done {
+                return None;
+            }
+            let next = self.next_frame().transpose();
+            self.done = !matches!(next, Some(Ok(_)));
+            next
+        }
+    }
+
+    impl Drop for VideoFrames {
+        /// A consumer that stops early (detection breaks once every song is
+        /// matched) leaves ffmpeg blocked on the pipe; stop it.
+        fn drop(&mut self) {
+            if !self.done {
+                let _ = self.child.kill();
+                let _ = self.child.wait();
+            }
+        }
+    }
+
+    /// Convert raw little-endian 16-bit mono PCM bytes to f32 samples normalized
+    /// to [-1.0, 1.0]. A trailing odd byte (truncated sample) is ignored.
+    fn pcm_s16le_to_samples(pcm_data: &[u8]) -> Vec<f32> {
+        pcm_data
+            .chunks_exact(2)
+            .map(|pair| i16::from_le_bytes([pair[0], pair[1]]) as f32 / 32768.0)
+            .collect()
+    }
+
+    pub(super) fn audio_samples(input_file: &str, sample_rate: u32) -> Result<Vec<f32>> {
+        // Stream raw PCM from ffmpeg's stdout instead of going through a temp
+        // file: concurrent splitter processes share a working directory, so any
+        // fixed temp path is a race (one process deletes or overwrites the file
+        // while another is reading it).
+        let mut cmd = create_ffmpeg_command().cmd();
+        cmd.args([
+            "-i",
+            input_file,
+            "-vn", // No video
+            "-acodec",
+            "pcm_s16le", // PCM signed 16-bit little-endian
+            "-ar",
+            &sample_rate.to_string(), // Sample rate
+            "-ac",
+            "1", // Mono channel
+            "-f",
+            "s16le", // Raw samples, no container header
+            "-",     // Write to stdout
+        ]);
+        cmd.stdout(Stdio::piped()); // create_ffmpeg_command defaults stdout to null
+
+        let mut child = cmd.spawn().with_context(|| {
+            format!("Failed to spawn ffmpeg to decode audio from {}", input_file)
+        })?;
+        let mut pcm_data = Vec::new();
+        // Read stdout to EOF before wait(): stderr is inherited, so this single
+        // pipe cannot deadlock. Check the exit status before trusting the bytes —
+        // a failed ffmpeg may have produced a truncated stream.
+        let read_result = child
+            .stdout
+            .take()
+            .ok_or_else(|| anyhow!("ffmpeg child process has no stdout pipe"))?
+            .read_to_end(&mut pcm_data);
+        let status = child
+            .wait()
+            .with_context(|| format!("Failed waiting for ffmpeg decoding {}", input_file))?;
+        if !status.success() {
+            return Err(anyhow!(
+                "ffmpeg failed to decode audio from {} (exit {:?})",
+                input_file,
+                status.code()
+            ));
+        }
+        read_result
+            .with_context(|| format!("Failed to read PCM stream from ffmpeg for {}", input_file))?;
+
+        let samples = pcm_s16le_to_samples(&pcm_data);
+        log::debug!(
+            "Extracted {} PCM bytes ({} samples) from {}",
+            pcm_data.len(),
+            samples.len(),
+            input_file
+        );
+        Ok(samples)
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn test_pcm_s16le_to_samples_known_values() {
+            let bytes = [
+                0x00, 0x00, // 0
+                0xFF, 0x7F, // i16::MAX = 32767
+                0x00, 0x80, // i16::MIN = -32768
+                0x00, 0x40, // 16384
+            ];
+            let samples = pcm_s16le_to_samples(&bytes);
+            assert_eq!(samples.len(), 4);
+            assert_eq!(samples[0], 0.0);
+            assert!((samples[1] - 32767.0 / 32768.0).abs() < 1e-6);
+            assert_eq!(samples[2], -1.0);
+            assert_eq!(samples[3], 0.5);
+        }
+
+        #[test]
+        fn test_pcm_s16le_to_samples_empty_and_odd_input() {
+            assert!(pcm_s16le_to_samples(&[]).is_empty());
+            // A lone trailing byte is a truncated sample and is dropped.
+            assert!(pcm_s16le_to_samples(&[0x12]).is_empty());
+            assert_eq!(pcm_s16le_to_samples(&[0x00, 0x40, 0x7F]), vec![0.5]);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn crop_round_trips_through_ffmpeg_syntax() {
+        let crop: Crop = "266:50:0:150".parse().unwrap();
+        assert_eq!(
+            crop,
+            Crop {
+                x: 0,
+                y: 150,
+                width: 266,
+                height: 50
+            }
+        );
+        assert_eq!(crop.to_string(), "266:50:0:150");
+        assert!("266:50:0".parse::<Crop>().is_err());
+        assert!("0:50:0:150".parse::<Crop>().is_err());
+        assert!("a:b:c:d".parse::<Crop>().is_err());
+    }
+
+    fn spec(start: f64, fps: u32) -> FrameSpec {
+        FrameSpec {
+            start,
+            end: None,
+            fps,
+            scale: (4, 2),
+            crop: None,
+            negate: false,
+        }
+    }
+
+    /// Feed frame times through a sampler; `(timestamp, sample index)` per kept frame.
+    fn sampled(spec: FrameSpec, timestamps: &[f64]) -> Vec<(f64, usize)> {
+        let mut sampler = Sampler::new(spec);
+        timestamps
+            .iter()
+            .filter_map(|&t| sampler.sample(t).map(|index| (t, index)))
+            .collect()
+    }
+
+    #[test]
+    fn sampler_takes_the_first_frame_at_each_second() {
+        // 4 fps source, sampled at 1 fps from 0s.
+        let times: Vec<f64> = (0..10).map(|i| i as f64 * 0.25).collect();
+        assert_eq!(
+            sampled(spec(0.0, 1), &times),
+            vec![(0.0, 0), (1.0, 1), (2.0, 2)]
+        );
+    }
+
+    #[test]
+    fn sampler_skips_frames_before_the_start_and_fills_gaps_with_the_latest_sample() {
+        // Frames decoded from the keyframe before a 10s seek are dropped; a gap
+        // from 11.0 to 13.5 fills sample 3 (13s), not 2.
+        assert_eq!(
+            sampled(spec(10.0, 1), &[8.0, 9.5, 10.02, 11.0, 13.5, 13.9, 14.0]),
+            vec![(10.02, 0), (11.0, 1), (13.5, 3), (14.0, 4)]
+        );
+    }
+
+    #[test]
+    fn sampler_at_the_source_rate_keeps_every_frame() {
+        // 29.97 fps sampled on a 30 fps grid: frames are slightly further apart
+        // than samples, so each frame fills one (occasionally skipping an index).
+        let times: Vec<f64> = (0..100).map(|i| i as f64 / 29.97).collect();
+        let kept = sampled(spec(0.0, 30), &times);
+        assert_eq!(kept.len(), times.len());
+        assert!(kept.windows(2).all(|pair| pair[1].1 > pair[0].1));
+    }
+
+    #[test]
+    fn sampler_past_end_is_exclusive() {
+        let sampler = Sampler::new(FrameSpec {
+            end: Some(20.0),
+            ..spec(10.0, 30)
+        });
+        assert!(!sampler.past_end(19.99));
+        assert!(sampler.past_end(20.0));
+        assert!(!Sampler::new(spec(0.0, 1)).past_end(1e9));
+    }
+
+    #[test]
+    fn shape_crops_then_negates() {
+        let mut rgb = ::image::RgbImage::new(4, 2);
+        rgb.put_pixel(1, 1, ::image::Rgb([255, 255, 255]));
+        let shaped = shape(
+            rgb,
+            &FrameSpec {
+                crop: Some(Crop {
+                    x: 1,
+                    y: 1,
+                    width: 2,
+                    height: 1,
+                }),
+                negate: true,
+                ..spec(0.0, 1)
+            },
+        )
+        .to_rgb8();
+        assert_eq!(shaped.dimensions(), (2, 1));
+        assert_eq!(shaped.get_pixel(0, 0).0, [0, 0, 0]);
+        assert_eq!(shaped.get_pixel(1, 0).0, [255, 255, 255]);
+    }
+
+    #[test]
+    fn cached_frames_are_numbered_pngs_only() {
+        assert_eq!(cached_frame_number(Path::new("dir/73.png")), Some(73));
+        assert_eq!(cached_frame_number(Path::new("dir/73bw.png")), None);
+        assert_eq!(cached_frame_number(Path::new("dir/73.jpg")), None);
+        assert_eq!(cached_frame_number(Path::new("dir/refined_blue")), None);
+    }
+
+    #[test]
+    fn frame_cache_reuses_kept_frames_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        for index in [10usize, 2, 1] {
+            let gray = ::image::RgbImage::from_pixel(4, 2, ::image::Rgb([index as u8; 3]));
+            gray.save(dir.path().join(format!("{index}.png"))).unwrap();
+        }
+        std::fs::write(dir.path().join("1bw.png"), b"not a frame").unwrap();
+        std::fs::write(dir.path().join(FRAME_SPEC_FILE), spec_line(&spec(5.0, 2))).unwrap();
+        let cache = FrameCache {
+            dir: dir.path().to_str().unwrap(),
+            reuse: true,
+            keep: false,
+        };
+        // The input is never opened when the cache is populated.
+        let frames = cache
+            .frames("/nonexistent/concert.mp4", spec(5.0, 2))
+            .unwrap()
+            .collect::<Result<Vec<_>>>()
+            .unwrap();
+        let seen: Vec<(usize, f64, u8)> = frames
+            .iter()
+            .map(|f| (f.index, f.timestamp, f.image.to_rgb8().get_pixel(0, 0).0[0]))
+            .collect();
+        assert_eq!(seen, vec![(1, 5.5, 1), (2, 6.0, 2), (10, 10.0, 10)]);
+    }
+
+    #[test]
+    fn frame_cache_is_reused_only_for_the_kept_spec() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = FrameCache {
+            dir: dir.path().to_str().unwrap(),
+            reuse: true,
+            keep: false,
+        };
+        // Frames kept without a recorded spec are decoded again.
+        assert!(!cache.kept_with(&spec(5.0, 2)));
+        std::fs::write(dir.path().join(FRAME_SPEC_FILE), spec_line(&spec(5.0, 1))).unwrap();
+        assert!(cache.kept_with(&spec(5.0, 1)));
+        assert!(!cache.kept_with(&spec(5.0, 2)));
+        assert!(!cache.kept_with(&FrameSpec {
+            negate: true,
+            ..spec(5.0, 1)
+        }));
+    }
+}
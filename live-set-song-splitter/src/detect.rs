@@ -1,24 +1,60 @@
 //! Text-overlay detection: find song boundaries by OCR-matching the artist/title
 //! overlay Tiny Desk concerts show a few seconds into each song.
 
-use crate::concert_split::{AudioSegment, ConcertSplitProgress, SongSegment};
+use crate::calibrate::OVERLAY_SCALE;
+use crate::concert_split::{AudioSegment, ConcertSplitProgress, DetectionSampling, SongSegment};
+use crate::decode::{Crop, FrameCache, FrameSpec};
+use crate::io;
 use crate::ocr::{matches_song_title, matches_song_title_weighted, song_title_candidate_lines};
 use crate::ocr_backend::{create_ocr_backend, OcrChoice, OcrPhase};
+use crate::ocr_pool::{FrameScanner, ScannedFrame};
+use crate::provenance;
+use crate::report::{self, OverlayMatch};
 use crate::video::VideoInfo;
-use crate::{ffmpeg, io};
-use concert_types::Song;
+use ::image::DynamicImage;
+use concert_types::{BoundaryProvenance, BoundarySource, Song};
 
 use anyhow::{anyhow, Context, Result};
 use std::collections::HashMap;
 use std::fs;
 
-pub(crate) const CROP_TO_TEXT: &str = "scale=400:200,crop=iw/1.5:ih/4:0:160";
+/// Overlay frames sampled at `fps` from `start` to `end`, cropped to `crop`.
+fn overlay_spec(start: f64, end: Option<f64>, fps: u32, crop: Crop, negate: bool) -> FrameSpec {
+    FrameSpec {
+        start,
+        end,
+        fps,
+        scale: OVERLAY_SCALE,
+        crop: Some(crop),
+        negate,
+    }
+}
 
 /// Tuning shared by the detection and refinement passes.
+#[derive(Clone)]
 pub(crate) struct Settings {
     pub analyze_images: bool,
     pub reuse_frames: bool,
+    pub keep_frames: bool,
     pub ocr_choice: OcrChoice,
+    /// OCR workers for the detection pass; 1 OCRs on the calling thread.
+    pub ocr_threads: usize,
+    pub sampling: DetectionSampling,
+    /// Where the artist/title card sits in the scaled frame; see [`crate::calibrate`].
+    pub overlay_crop: Crop,
+    /// Collect [`TextDetection::overlay_matches`] for the `--report`.
+    pub report: bool,
+}
+
+impl Settings {
+    /// The `--reuse-frames`/`--keep-frames` cache for the frames of one pass.
+    pub(crate) fn frame_cache<'a>(&self, dir: &'a str) -> FrameCache<'a> {
+        FrameCache {
+            dir,
+            reuse: self.reuse_frames,
+            keep: self.keep_frames,
+        }
+    }
 }
 
 /// Result of the text-overlay detection pass.
@@ -31,6 +67,19 @@ pub(crate) struct TextDetection {
     /// the preferred boundary anchors for still-missing songs in
     /// [`crate::recover::recover_missing_songs`].
     pub unmatched_overlay_clusters: Vec<f64>,
+    /// Every title match, with its frame, when `Settings::report` is set.
+    pub overlay_matches: Vec<OverlayMatch>,
+}
+
+/// A set-list title read on one frame by [`match_song_titles`].
+struct TitleMatch {
+    song: String,
+    time: f64,
+    /// The artist overlay was on the frame too.
+    overlay: bool,
+    boundary: BoundaryProvenance,
+    /// The `MatchReason` and edit distance, for the report.
+    reason: String,
 }
 
 /// A title card stays on screen for several seconds, so an "artist overlay seen but
@@ -102,28 +151,6 @@ fn first_song_missing_fallback(
     }
 }
 
-pub(crate) fn frame_number_from_image_filename(frame_path: &std::path::Path) -> usize {
-    let frame_name = frame_path.file_name().unwrap().to_string_lossy();
-    frame_name
-        .strip_suffix(".png")
-        .and_then(|s| s.parse::<usize>().ok())
-        .unwrap_or(0)
-}
-
-/// True for a primary extracted frame (`N.png`), false for anything else,
-/// including the black-and-white variants (`Nbw.png`) some passes also write into
-/// the same directory. Mirrors the filter the detection pass uses (see
-/// `extract_frames`): the `bw` suffix makes the stem unparseable, so those files
-/// must be excluded from a frame listing or they inflate `frames.len()` and break
-/// the index arithmetic in [`refined_match_to_source_frame`].
-pub(crate) fn is_source_frame(path: &std::path::Path) -> bool {
-    path.extension().is_some_and(|ext| ext == "png")
-        && path
-            .file_stem()
-            .and_then(|s| s.to_str())
-            .is_some_and(|s| s.parse::<usize>().is_ok())
-}
-
 /// Map a 1-based index into the refined extraction back to a source-video frame
 /// index. `earliest_match` is the matched extraction frame (1..=`frame_count`),
 /// `frame_count` is the number of extracted frames (which aligns with
@@ -132,7 +159,8 @@ pub(crate) fn is_source_frame(path: &std::path::Path) -> bool {
 ///
 /// `frame_count` MUST be the count of source frames only — counting B/W variants
 /// here over-subtracts and pushes the boundary earlier (this was the cause of a
-/// song boundary landing ~3s before the overlay actually appeared).
+/// song boundary landing ~3s before the overlay actually appeared, back when the
+/// refined frames were listed from a directory that also held `Nbw.png` files).
 pub(crate) fn refined_match_to_source_frame(
     end_frame_num: usize,
     frame_count: usize,
@@ -141,79 +169,111 @@ pub(crate) fn refined_match_to_source_frame(
     end_frame_num - (frame_count - earliest_match)
 }
 
-pub(crate) fn extract_frames(
+/// Seconds between probes under [`DetectionSampling::Coarse`]. Title cards stay up
+/// for several seconds, so a card is on screen for at least one probe.
+const COARSE_STRIDE_SECONDS: usize = 3;
+
+fn sampling_stride(sampling: DetectionSampling) -> usize {
+    match sampling {
+        DetectionSampling::Dense => 1,
+        DetectionSampling::Coarse => COARSE_STRIDE_SECONDS,
+    }
+}
+
+/// A song must be at least 30 seconds, so no title card follows a song's start
+/// this soon.
+fn within_min_song_length(last_song_start_time: Option<f64>, frame_num: usize) -> bool {
+    last_song_start_time.is_some_and(|last_start_time| (frame_num as f64) - last_start_time < 30.0)
+}
+
+/// Whether the artist overlay is on `scanned`: the color pass, then the B/W pass
+/// when the backend uses one. The passes stay on the frame for matching.
+fn overlay_visible(
+    scanner: &mut FrameScanner,
+    scanned: &mut ScannedFrame,
+    do_bw: bool,
+) -> Result<bool> {
+    let overlay = |parses: Vec<crate::ocr::OcrParse>| parses.iter().any(|(_, overlay)| *overlay);
+    if overlay(scanner.candidates(scanned, false)?) {
+        return Ok(true);
+    }
+    Ok(do_bw && overlay(scanner.candidates(scanned, true)?))
+}
+
+/// The detection pass's frames: one per second, cropped to the overlay and
+/// negated so the overlay text will be black, which tesseract prefers. Decoded
+/// lazily, so the pass stops decoding once every song is matched.
+fn detection_frames(
     input_file: &str,
     temp_dir: &str,
-    reuse_frames: bool,
+    settings: &Settings,
     progress: &mut dyn FnMut(ConcertSplitProgress),
-) -> Result<Vec<std::path::PathBuf>> {
-    if reuse_frames {
-        progress(ConcertSplitProgress::Diagnostic(format!(
+) -> Result<Box<dyn Iterator<Item = Result<crate::decode::Frame>>>> {
+    let cache = settings.frame_cache(temp_dir);
+    progress(ConcertSplitProgress::Diagnostic(if cache.reuse {
+        format!(
             "Reusing existing frames from {} for song title detection...",
             temp_dir
-        )));
-        io::ensure_dir(temp_dir)?;
+        )
     } else {
-        // Only overwrite directory if not reusing frames or if no frames exist
-        io::overwrite_dir(temp_dir)?;
-
-        progress(ConcertSplitProgress::Diagnostic(
-            "Extracting frames every 1 seconds for song title detection...".to_string(),
-        ));
-
-        let every_few_seconds = "fps=1,select='not(mod(t,1))'";
-
-        // Extract 1 frame every few seconds
-        // focus on the text area
-        // Invert colors so the overlay text will be black, which tesseract prefers
-        let filters = format!("{},{},{}", every_few_seconds, CROP_TO_TEXT, "negate");
-
-        // Extract frames every 1 seconds with potential text overlays
-        let mut ffmpeg = ffmpeg::create_ffmpeg_command();
-        // Add command line options to invert the colors
-        ffmpeg.args([
-            "-i",
-            input_file,
-            "-c:v",
-            "png",
-            "-frame_pts",
-            "1",
-            "-fps_mode",
-            "passthrough", // Use original timestamps (replaces -vsync 0)
-            "-vf",
-            &filters,
-            &format!("{}/%d.png", temp_dir), // Use sequential numbering
-        ]);
-        let status = ffmpeg.cmd().status()?;
-
-        progress(ConcertSplitProgress::Diagnostic(
-            "Frames extracted successfully for image detection.".to_string(),
-        ));
+        "Decoding frames every 1 seconds for song title detection...".to_string()
+    }));
+    cache.frames(
+        input_file,
+        overlay_spec(0.0, None, 1, settings.overlay_crop, true),
+    )
+}
 
-        if !status.success() {
-            return Err(anyhow!("Failed to extract frames"));
+/// Title cards read for set-list-free splitting: `(timestamp, title)` for each
+/// frame showing the artist overlay, where the title is the first line under the
+/// artist, as read. With coarse sampling only probe frames are OCR'd; a card is
+/// up for several of them.
+pub(crate) fn overlay_titles(
+    input_file: &str,
+    artist: &str,
+    settings: &Settings,
+    temp_dir: &str,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> Result<Vec<(f64, String)>> {
+    let frames = detection_frames(input_file, temp_dir, settings, progress)?;
+    let artist_cmp = artist.to_lowercase();
+    let mut scanner = FrameScanner::new(
+        frames,
+        settings.ocr_choice,
+        OcrPhase::Detection,
+        settings.ocr_threads,
+        &artist_cmp,
+    )?;
+    let do_bw = scanner.options().black_and_white;
+    let stride = sampling_stride(settings.sampling);
+    scanner.speculate_every(stride);
+
+    let mut titles = Vec::new();
+    while let Some(scanned) = scanner.next() {
+        let mut scanned = scanned?;
+        if !scanned.frame.index.is_multiple_of(stride) {
+            continue;
+        }
+        for black_and_white in [false, true] {
+            if black_and_white && !do_bw {
+                break;
+            }
+            let title = scanner
+                .candidates(&mut scanned, black_and_white)?
+                .iter()
+                .filter(|(_, overlay)| *overlay)
+                .find_map(|parse| song_title_candidate_lines(parse).first().cloned());
+            if let Some(title) = title {
+                titles.push((scanned.frame.timestamp, title));
+                break;
+            }
         }
     }
-
-    // Get list of extracted frames, excluding BW variants and refined subdirectories
-    let frames = fs::read_dir(temp_dir)?
-        .filter_map(Result::ok)
-        .filter(|entry| {
-            let path = entry.path();
-            path.extension().is_some_and(|ext| ext == "png")
-                && path
-                    .file_stem()
-                    .and_then(|s| s.to_str())
-                    .is_some_and(|s| s.parse::<usize>().is_ok())
-        })
-        .map(|entry| entry.path())
-        .collect::<Vec<_>>();
-
     progress(ConcertSplitProgress::Diagnostic(format!(
-        "Extracted {} frames, analyzing for text...",
-        frames.len()
+        "Read {} title overlay frames",
+        titles.len()
     )));
-    Ok(frames)
+    Ok(titles)
 }
 
 #[allow(clippy::too_many_arguments)] // All arguments are required for the detection pass
@@ -226,7 +286,7 @@ pub(crate) fn detect_song_boundaries_from_text(
     temp_dir: &str,
     progress: &mut dyn FnMut(ConcertSplitProgress),
 ) -> Result<TextDetection> {
-    let mut frames = extract_frames(input_file, temp_dir, settings.reuse_frames, progress)?;
+    let frames = detection_frames(input_file, temp_dir, settings, progress)?;
 
     let total_duration = video_info.duration;
     let artist_cmp = artist.to_lowercase();
@@ -242,19 +302,27 @@ pub(crate) fn detect_song_boundaries_from_text(
 
     // Map to store detected song start times
     let mut song_title_matched: HashMap<String, f64> = HashMap::new();
+    // How each of those starts was matched
+    let mut song_boundaries: HashMap<String, BoundaryProvenance> = HashMap::new();
+    // The frame each title overlay was read on
+    let mut song_overlay_frames: HashMap<String, f64> = HashMap::new();
 
     // Store potential title-only matches for fallback
     let mut title_only_matches: Vec<(String, f64, usize)> = Vec::new();
-
-    // Process each frame to detect text
-    frames.sort_by(|a, b| {
-        frame_number_from_image_filename(a).cmp(&frame_number_from_image_filename(b))
-    });
-
-    let mut backend = create_ocr_backend(settings.ocr_choice, OcrPhase::Detection)?;
+    let mut overlay_matches: Vec<OverlayMatch> = Vec::new();
+
+    // With `ocr_threads` > 1 a worker pool OCRs frames ahead of this loop; frames
+    // still arrive here in time order with the same candidates.
+    let mut scanner = FrameScanner::new(
+        frames,
+        settings.ocr_choice,
+        OcrPhase::Detection,
+        settings.ocr_threads,
+        &artist_cmp,
+    )?;
     // Whether to try a binarized fallback pass when the color pass finds no overlay
     // (tesseract: yes; paddle: no).
-    let do_bw = backend.options().black_and_white;
+    let do_bw = scanner.options().black_and_white;
 
     // Frames where the artist overlay was detected but no title matched (e.g. a
     // short/stylized title the OCR couldn't read). Clustered and returned so missing
@@ -262,105 +330,166 @@ pub(crate) fn detect_song_boundaries_from_text(
     let mut unmatched_overlay_frames: Vec<usize> = Vec::new();
 
     let mut last_song_start_time: Option<f64> = None;
-    for mut frame_path in frames {
-        // Extract frame number to calculate timestamp
-        let frame_num = frame_number_from_image_filename(&frame_path);
-
-        if !song_title_matched.is_empty() {
-            if song_title_matched.len() == songs.len() {
+    let mut frames_scanned = 0usize;
+    let mut frames_ocrd = 0usize;
+
+    // Coarse sampling OCRs only every `stride`-th frame (a probe) until one shows
+    // the artist overlay. The title card may have come up on any frame since the
+    // previous probe, so those held-back frames are then matched in order, and so
+    // is every following frame while the overlay stays up. Around a title card the
+    // pass thus sees the same frames as dense sampling, and refinement still looks
+    // for the earliest overlay frame from the first match.
+    let stride = sampling_stride(settings.sampling);
+    scanner.speculate_every(stride);
+    let mut held_back: Vec<ScannedFrame> = Vec::new();
+    let mut densified = false;
+
+    // Process each frame (in time order) to detect text
+    'frames: while let Some(scanned) = scanner.next() {
+        let mut scanned = scanned?;
+        frames_scanned += 1;
+
+        let mut batch = Vec::new();
+        if stride > 1 && !densified {
+            let frame_num = scanned.frame.index;
+            if !frame_num.is_multiple_of(stride) {
+                held_back.push(scanned);
+                continue;
+            }
+            if !song_title_matched.is_empty() && song_title_matched.len() == songs.len() {
                 break;
             }
-            if let Some(last_start_time) = last_song_start_time {
-                // A song must be at least 30 seconds
-                if (frame_num as f64) - last_start_time < 30.0 {
-                    continue;
-                }
+            if within_min_song_length(last_song_start_time, frame_num) {
+                held_back.clear();
+                continue;
             }
-        }
-
-        let song_titles_to_match = &sorted_songs
-            .iter()
-            .filter(|song|
-            // skip already matched songs
-            !song_title_matched.contains_key(&song.title))
-            .map(|song| &song.title)
-            .cloned()
-            .collect::<Vec<_>>();
-
-        // Candidates accumulate ACROSS the color and (optional) B/W passes so that, when
-        // the color pass finds no overlay, the union is matched with the B/W-derived
-        // overlay flag (a color-pass line can match with the overlay bonus). The backend
-        // owns the OCR fan-out; the pipeline only decides whether to run the B/W pass.
-        let mut all_ocr_results: Vec<crate::ocr::OcrParse> = Vec::new();
-
-        // Track, for this frame, whether the artist overlay was seen at all and
-        // whether it produced a title match. An overlay seen with no match means a
-        // title card we couldn't read — recorded as an unmatched-overlay frame.
-        let mut overlay_seen_this_frame = false;
-        let mut overlay_matched_this_frame = false;
-
-        let passes: &[bool] = if do_bw { &[false, true] } else { &[false] };
-        'convert: for &convert in passes {
-            if convert {
-                let orig_path = frame_path.clone();
-                frame_path.set_file_name(format!("{}bw.png", frame_num));
-                crate::image::write_black_and_white(&orig_path, &frame_path)?;
+            if overlay_visible(&mut scanner, &mut scanned, do_bw)? {
+                densified = true;
+                batch = std::mem::take(&mut held_back);
+            } else {
+                held_back.clear();
             }
-            let frame_path_str = frame_path.to_str().unwrap();
-
-            // OCR this pass (backend fans out internally); propagate the first error.
-            let candidates = backend
-                .ocr_image_path(frame_path_str, &artist_cmp)
-                .into_iter()
-                .collect::<Result<Vec<_>>>()?;
-            all_ocr_results.extend(candidates.into_iter().map(|c| c.parse));
+        }
+        batch.push(scanned);
 
-            // Check if any OCR result contains the artist name (indicates overlay)
-            let has_artist_overlay = all_ocr_results.iter().any(|(_, overlay)| *overlay);
-            overlay_seen_this_frame |= has_artist_overlay;
+        for mut scanned in batch {
+            // One frame per second from 0s, so the sample index is the timestamp
+            let frame_num = scanned.frame.index;
 
-            // If we haven't found the overlay, first do the B/W conversion and look for it.
-            if !has_artist_overlay && !convert && do_bw {
-                continue;
+            if !song_title_matched.is_empty() {
+                if song_title_matched.len() == songs.len() {
+                    break 'frames;
+                }
+                if within_min_song_length(last_song_start_time, frame_num) {
+                    densified = false;
+                    continue;
+                }
             }
-            let ocr_results = std::mem::take(&mut all_ocr_results);
-
-            for ocr_result in &ocr_results {
-                // Create a modified OCR result that indicates overlay presence
-                let modified_ocr = (ocr_result.0.clone(), has_artist_overlay);
-
-                let title_time = match_song_titles(
-                    input_file,
-                    temp_dir,
-                    &modified_ocr,
-                    song_titles_to_match,
-                    &artist_cmp,
-                    frame_num,
-                    video_info,
-                    settings,
-                    progress,
-                )?;
-
-                if let Some((song, time, overlay)) = title_time {
-                    if overlay {
-                        song_title_matched.insert(song, time);
-                        last_song_start_time = Some(time);
-                        overlay_matched_this_frame = true;
-                        break 'convert; // Found a match, no need to try other OCR results
-                    } else {
-                        // Store title-only match for potential fallback
-                        title_only_matches.push((song, time, frame_num));
+            frames_ocrd += 1;
+
+            let song_titles_to_match = &sorted_songs
+                .iter()
+                .filter(|song|
+                // skip already matched songs
+                !song_title_matched.contains_key(&song.title))
+                .map(|song| &song.title)
+                .cloned()
+                .collect::<Vec<_>>();
+
+            // Candidates accumulate ACROSS the color and (optional) B/W passes so that, when
+            // the color pass finds no overlay, the union is matched with the B/W-derived
+            // overlay flag (a color-pass line can match with the overlay bonus). The backend
+            // owns the OCR fan-out; the pipeline only decides whether to run the B/W pass.
+            let mut all_ocr_results: Vec<crate::ocr::OcrParse> = Vec::new();
+
+            // Track, for this frame, whether the artist overlay was seen at all and
+            // whether it produced a title match. An overlay seen with no match means a
+            // title card we couldn't read — recorded as an unmatched-overlay frame.
+            let mut overlay_seen_this_frame = false;
+            let mut overlay_matched_this_frame = false;
+
+            let passes: &[bool] = if do_bw { &[false, true] } else { &[false] };
+            'convert: for &convert in passes {
+                // OCR this pass (backend fans out internally); propagate the first error.
+                let candidates = scanner.candidates(&mut scanned, convert)?;
+                all_ocr_results.extend(candidates);
+
+                // Check if any OCR result contains the artist name (indicates overlay)
+                let has_artist_overlay = all_ocr_results.iter().any(|(_, overlay)| *overlay);
+                overlay_seen_this_frame |= has_artist_overlay;
+
+                // If we haven't found the overlay, first do the B/W conversion and look for it.
+                if !has_artist_overlay && !convert && do_bw {
+                    continue;
+                }
+                let ocr_results = std::mem::take(&mut all_ocr_results);
+
+                for ocr_result in &ocr_results {
+                    // Create a modified OCR result that indicates overlay presence
+                    let modified_ocr = (ocr_result.0.clone(), has_artist_overlay);
+
+                    let title_time = match_song_titles(
+                        input_file,
+                        temp_dir,
+                        &scanned.frame.image,
+                        &modified_ocr,
+                        song_titles_to_match,
+                        &artist_cmp,
+                        frame_num,
+                        video_info,
+                        settings,
+                        progress,
+                    )?;
+
+                    if let Some(TitleMatch {
+                        song,
+                        time,
+                        overlay,
+                        boundary,
+                        reason,
+                    }) = title_time
+                    {
+                        if settings.report {
+                            overlay_matches.push(OverlayMatch {
+                                frame_num,
+                                time,
+                                lines: ocr_result.0.clone(),
+                                song: song.clone(),
+                                reason,
+                                overlay,
+                                thumbnail: report::thumbnail(&scanned.frame.image),
+                            });
+                        }
+                        if overlay {
+                            song_boundaries.insert(song.clone(), boundary);
+                            song_overlay_frames.insert(song.clone(), frame_num as f64);
+                            song_title_matched.insert(song, time);
+                            last_song_start_time = Some(time);
+                            // A song must be at least 30 seconds (see above)
+                            scanner.skip_before(time + 30.0);
+                            overlay_matched_this_frame = true;
+                            break 'convert; // Found a match, no need to try other OCR results
+                        } else {
+                            // Store title-only match for potential fallback
+                            title_only_matches.push((song, time, frame_num));
+                        }
                     }
                 }
             }
-        }
 
-        // Overlay present but no title matched: an unreadable title card. Record it
-        // as a boundary candidate for a missing song (see `recover_missing_songs`).
-        if overlay_seen_this_frame && !overlay_matched_this_frame {
-            unmatched_overlay_frames.push(frame_num);
+            // Overlay present but no title matched: an unreadable title card. Record it
+            // as a boundary candidate for a missing song (see `recover_missing_songs`).
+            if overlay_seen_this_frame && !overlay_matched_this_frame {
+                unmatched_overlay_frames.push(frame_num);
+            }
+            // Coarse sampling: keep matching every frame while the overlay is up.
+            densified = overlay_seen_this_frame;
         }
     }
+    progress(ConcertSplitProgress::Diagnostic(format!(
+        "OCR'd {} of {} frames ({:?} sampling)",
+        frames_ocrd, frames_scanned, settings.sampling
+    )));
 
     // Check if we need to use fallback matches (title-only) for missing songs
     let total_songs = songs.len() as i32;
@@ -396,6 +525,10 @@ pub(crate) fn detect_song_boundaries_from_text(
                     "Using fallback title-only match for '{}' at frame {} since all other songs have been matched",
                     song, frame_num
                 )));
+                song_boundaries.insert(
+                    song.clone(),
+                    provenance::from_source(BoundarySource::TitleOnly),
+                );
                 song_title_matched.insert(song, time);
             }
         }
@@ -427,6 +560,7 @@ pub(crate) fn detect_song_boundaries_from_text(
         return Ok(TextDetection {
             segments: Vec::new(),
             unmatched_overlay_clusters,
+            overlay_matches,
         });
     }
 
@@ -468,11 +602,24 @@ pub(crate) fn detect_song_boundaries_from_text(
             is_song: true,
         };
 
+        // The first song starts with the recording whatever was matched; a song
+        // added by `first_song_missing_fallback` has no match of its own.
+        let boundary = if i == 0 {
+            provenance::from_source(BoundarySource::RecordingStart)
+        } else {
+            song_boundaries
+                .get(song_title)
+                .copied()
+                .unwrap_or_else(|| provenance::from_source(BoundarySource::Overlay))
+        };
+
         segments.push(SongSegment {
             song: song_obj,
             segment,
             // Start came from the title overlay (~OVERLAY_DELAY_SECONDS late).
             start_from_overlay: true,
+            overlay_at: song_overlay_frames.get(song_title).copied(),
+            boundary,
         });
     }
 
@@ -484,6 +631,7 @@ pub(crate) fn detect_song_boundaries_from_text(
     Ok(TextDetection {
         segments,
         unmatched_overlay_clusters,
+        overlay_matches,
     })
 }
 
@@ -491,6 +639,7 @@ pub(crate) fn detect_song_boundaries_from_text(
 fn match_song_titles(
     input_file: &str,
     temp_dir: &str,
+    frame: &DynamicImage,
     ocr_parse: &crate::ocr::OcrParse,
     song_titles_to_match: &Vec<String>,
     artist_cmp: &str,
@@ -498,7 +647,7 @@ fn match_song_titles(
     video_info: &VideoInfo,
     settings: &Settings,
     progress: &mut dyn FnMut(ConcertSplitProgress),
-) -> Result<Option<(String, f64, bool)>> {
+) -> Result<Option<TitleMatch>> {
     let (lines, overlay) = ocr_parse;
 
     // Format text for display
@@ -548,7 +697,9 @@ fn match_song_titles(
     }
 
     // The best match is the first one after sorting
-    let (song_title, _) = &all_matches[0];
+    let (song_title, (best_reason, _, best_dist)) = &all_matches[0];
+    let boundary = provenance::from_overlay_match(best_reason);
+    let reason = format!("{} (dist {})", best_reason, best_dist);
 
     // Report all matches, with the best match indicated
     for (i, (match_title, (match_reason, match_line, match_dist))) in all_matches.iter().enumerate()
@@ -576,20 +727,31 @@ fn match_song_titles(
 
     // If analyze_images flag is enabled, save the matched image
     if settings.analyze_images {
-        let frame_path = std::path::PathBuf::from(format!("{}/{}.png", temp_dir, frame_num));
-        save_matched_image(&frame_path, song_title, frame_num, "initial")?;
+        save_matched_image(frame, song_title, frame_num, "initial")?;
     }
 
     // Don't bother refining
     // TODO: if we don't match a song then look at refined images to see if there is an overlay
     if !*overlay {
-        return Ok(Some((song_title.to_string(), frame_num as f64, *overlay)));
+        return Ok(Some(TitleMatch {
+            song: song_title.to_string(),
+            time: frame_num as f64,
+            overlay: *overlay,
+            boundary,
+            reason,
+        }));
     }
 
     match timestamp_for_song(
         input_file, temp_dir, artist_cmp, song_title, frame_num, video_info, settings, progress,
     ) {
-        Ok(timestamp) => Ok(Some((song_title.to_string(), timestamp, *overlay))),
+        Ok(timestamp) => Ok(Some(TitleMatch {
+            song: song_title.to_string(),
+            time: timestamp,
+            overlay: *overlay,
+            boundary,
+            reason,
+        })),
         Err(e) => Err(e),
     }
 }
@@ -664,60 +826,25 @@ fn refine_song_start_time(
         end_frame_num, end_timestamp, initial_timestamp
     )));
 
-    // Create a subdirectory for the refined frames
+    // Kept/reused refined frames live in a subdirectory per song
     let refined_dir = format!("{}/refined_{}", temp_dir, io::sanitize_filename(song_title));
 
-    // Check if we should reuse existing refined frames
-    let frames_exist = std::path::Path::new(&refined_dir).exists()
-        && std::fs::read_dir(&refined_dir)
-            .map(|entries| entries.count() > 0)
-            .unwrap_or(false);
-
-    // Get the original video framerate
+    // Decode every frame of the window at the original video framerate for accuracy.
+    // The window runs through the frame at `end_timestamp` (as `-to` did), which
+    // `refined_match_to_source_frame` counts back from.
     let fps = video_info.framerate;
-
-    if !settings.reuse_frames || !frames_exist {
-        // Only overwrite directory if not reusing frames or if no frames exist
-        io::overwrite_dir(&refined_dir)?;
-
-        // Extract frames at original video framerate for accuracy.
-        // Only the primary `N.png` frames are needed: the matching loop below keys
-        // off the frame number parsed from the filename, and a `Nbw.png` variant
-        // parses to 0 (see `is_source_frame`), so it could never be selected as the
-        // earliest match — extracting it was wasted work that also inflated the
-        // frame count used by `refined_match_to_source_frame`.
-        let mut ffmpeg = ffmpeg::create_ffmpeg_command();
-        ffmpeg
-            .time_range(start_time, end_timestamp)
-            .args(["-i", input_file])
-            .png()
-            .video_filter(
-                &format!("{}/%d.png", refined_dir), // Sequential numbering starting from 1
-                vec![&format!("fps={}", fps), CROP_TO_TEXT], // Use original video framerate
-            );
-        let status = ffmpeg.cmd().status()?;
-
-        if !status.success() {
-            return Err(anyhow!("Failed to extract refined frames"));
-        }
-
-        progress(ConcertSplitProgress::Diagnostic(format!(
-            "Extracted refined frames for '{}'",
-            song_title
-        )));
-    } else {
-        progress(ConcertSplitProgress::Diagnostic(format!(
-            "Reusing existing refined frames from {} for '{}'",
-            refined_dir, song_title
-        )));
-    }
-
-    // Read the refined frames and analyze them
-    let mut frames = fs::read_dir(&refined_dir)?
-        .filter_map(Result::ok)
-        .map(|entry| entry.path())
-        .filter(|path| is_source_frame(path))
-        .collect::<Vec<_>>();
+    let spec = overlay_spec(
+        start_time,
+        Some(end_timestamp + 0.5 / f64::from(fps)),
+        fps,
+        settings.overlay_crop,
+        false,
+    );
+    let frames = settings
+        .frame_cache(&refined_dir)
+        .frames(input_file, spec)?
+        .collect::<Result<Vec<_>>>()
+        .with_context(|| format!("Failed to decode refined frames for '{}'", song_title))?;
 
     progress(ConcertSplitProgress::Diagnostic(format!(
         "Analyzing {} refined frames for song title '{}' from {}s to {}s at {} fps",
@@ -730,29 +857,19 @@ fn refine_song_start_time(
 
     let mut earliest_match: Option<usize> = None;
 
-    frames.sort_by(|a, b| {
-        frame_number_from_image_filename(a)
-            .cmp(&frame_number_from_image_filename(b))
-            .reverse()
-    });
-    // `frames` now contains only the primary `N.png` source frames (the listing
-    // above filters out B/W variants via `is_source_frame`), so this count is the
-    // source-frame count required by `refined_match_to_source_frame`.
+    // Every decoded frame is a source frame, so this count is the source-frame
+    // count required by `refined_match_to_source_frame`.
     let frame_count = frames.len();
 
     // The backend fans out internally; each candidate carries the match-leniency to use
     // for it (tesseract: per-PSM stingy/greedy; paddle: its single parse under both).
     let mut backend = create_ocr_backend(settings.ocr_choice, OcrPhase::Refine)?;
 
-    // Process each refined frame
-    for frame_path in frames {
-        let frame_file = frame_path.to_str().unwrap();
-        // Extract frame number
-        let frame_num = frame_number_from_image_filename(&frame_path);
-
+    // Process each refined frame, latest first; frames are numbered from 1
+    for (frame_num, frame) in frames.iter().enumerate().map(|(i, f)| (i + 1, f)).rev() {
         let mut earliest_match_found = false;
         let candidates = backend
-            .ocr_image_path(frame_file, artist)
+            .ocr_image(&frame.image, artist)
             .into_iter()
             .collect::<Result<Vec<_>>>()?;
         for candidate in &candidates {
@@ -768,7 +885,7 @@ fn refine_song_start_time(
 
                 // If analyze_images flag is enabled, save the matched image
                 if settings.analyze_images {
-                    save_matched_image(&frame_path, song_title, frame_num, "refined")?;
+                    save_matched_image(&frame.image, song_title, frame_num, "refined")?;
                 }
             }
         }
@@ -817,7 +934,7 @@ fn refine_song_start_time(
 
 /// Save a matched image to the analysis directory
 fn save_matched_image(
-    frame_path: &std::path::PathBuf,
+    image: &DynamicImage,
     song_title: &str,
     frame_num: usize,
     prefix: &str,
@@ -834,14 +951,9 @@ fn save_matched_image(
         analysis_dir, prefix, safe_title, frame_num
     );
 
-    // Copy the image file to the analysis directory
-    fs::copy(frame_path, &target_path).with_context(|| {
-        format!(
-            "Failed to copy matched image from {} to {}",
-            frame_path.display(),
-            target_path
-        )
-    })?;
+    image
+        .save(&target_path)
+        .with_context(|| format!("Failed to save matched image to {}", target_path))?;
 
     Ok(())
 }
@@ -867,31 +979,20 @@ mod tests_back_search_offset {
     }
 
     #[test]
-    fn is_source_frame_excludes_bw_variants() {
-        // The `bw` suffix makes the stem unparseable; such files must be excluded
-        // from a refined-frames listing or they inflate the frame count.
-        assert!(is_source_frame(Path::new("dir/73.png")));
-        assert!(!is_source_frame(Path::new("dir/73bw.png")));
-        assert!(!is_source_frame(Path::new("dir/notes.txt")));
-        // And the underlying parse quirk that motivates the filter:
-        assert_eq!(
-            frame_number_from_image_filename(&PathBuf::from("dir/73bw.png")),
-            0
-        );
-    }
-
-    #[test]
-    fn refined_listing_filter_drops_bw_variants() {
-        // testdata/blue_back_search mirrors a refined-frames directory: the real
-        // "Bloc Party / Blue" overlay frames 73,74,75 each alongside a `bw` variant.
+    fn kept_frame_listing_skips_bw_variants() {
+        // testdata/blue_back_search mirrors a kept refined-frames directory from an
+        // older version: the real "Bloc Party / Blue" overlay frames 73,74,75 each
+        // alongside a `bw` variant. Reusing it must see only the three source frames
+        // or the frame count is inflated.
         let all_png = list_png(&fixture_dir());
         assert_eq!(all_png.len(), 6, "fixture has a plain + bw file per frame");
-        let source_count = all_png
+        let source_frames: Vec<usize> = all_png
             .iter()
-            .filter(|p| is_source_frame(p.as_path()))
-            .count();
+            .filter_map(|p| crate::decode::cached_frame_number(p))
+            .collect();
         assert_eq!(
-            source_count, 3,
+            source_frames,
+            vec![73, 74, 75],
             "only the three N.png frames are source frames"
         );
     }
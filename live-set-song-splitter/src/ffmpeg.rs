@@ -1,7 +1,10 @@
 use std::ffi::OsStr;
+use std::fs;
+use std::path::Path;
 use std::process::Command;
 
-use concert_types::ConcertInfo;
+use crate::concert_split::{AudioEncoderSettings, AudioFormat};
+use crate::tags::TrackTags;
 
 use anyhow::{anyhow, Result};
 
@@ -90,28 +93,108 @@ fn _extract_segment_mp4box(
     Ok(())
 }
 
-// Extract audio-only segment using stream copy (no re-encoding)
+/// The codec of `input_file`'s first audio stream, as ffprobe names it.
+pub fn probe_audio_codec(input_file: &str) -> Result<String> {
+    let output = create_ffprobe_command()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "a:0",
+            "-show_entries",
+            "stream=codec_name",
+            "-of",
+            "csv=print_section=0",
+            input_file,
+        ])
+        .output()?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "ffprobe failed probing the audio codec of {}: {}",
+            input_file,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let codec = String::from_utf8(output.stdout)?.trim().to_string();
+    if codec.is_empty() {
+        return Err(anyhow!("{} has no audio stream", input_file));
+    }
+    Ok(codec)
+}
+
+/// The `-acodec` for cutting a `source_codec` stream into `.m4a`: a stream copy
+/// when MP4 can hold it, ALAC for lossless sources (FLAC, PCM) and AAC for the
+/// rest (Vorbis, Opus from audio-only downloads).
+pub fn m4a_audio_codec(source_codec: &str) -> &'static str {
+    match source_codec {
+        "aac" | "alac" | "mp3" | "ac3" | "eac3" => "copy",
+        codec if codec == "flac" || codec.starts_with("pcm_") => "alac",
+        _ => "aac",
+    }
+}
+
+/// Output codec arguments for cutting a `source_codec` stream into `format`.
+/// A source already in the target codec is stream-copied; otherwise FLAC is a
+/// lossless encode of the decoded audio and Opus/MP3 use `settings`.
+pub fn audio_encoder_args(
+    format: AudioFormat,
+    source_codec: &str,
+    settings: &AudioEncoderSettings,
+) -> Vec<String> {
+    let args: Vec<String> = match format {
+        AudioFormat::M4a => vec!["-c:a".into(), m4a_audio_codec(source_codec).into()],
+        AudioFormat::Flac if source_codec == "flac" => vec!["-c:a".into(), "copy".into()],
+        AudioFormat::Flac => vec![
+            "-c:a".into(),
+            "flac".into(),
+            "-compression_level".into(),
+            settings.flac_compression_level.to_string(),
+        ],
+        AudioFormat::Opus if source_codec == "opus" => vec!["-c:a".into(), "copy".into()],
+        AudioFormat::Opus => vec![
+            "-c:a".into(),
+            "libopus".into(),
+            "-b:a".into(),
+            format!("{}k", settings.opus_bitrate_kbps),
+        ],
+        AudioFormat::Mp3 if source_codec == "mp3" => vec!["-c:a".into(), "copy".into()],
+        AudioFormat::Mp3 => vec![
+            "-c:a".into(),
+            "libmp3lame".into(),
+            "-q:a".into(),
+            settings.mp3_quality.to_string(),
+        ],
+    };
+    match format {
+        // ID3v2.3 is what older players and car stereos read.
+        AudioFormat::Mp3 => args
+            .into_iter()
+            .chain(["-id3v2_version".into(), "3".into()])
+            .collect(),
+        _ => args,
+    }
+}
+
+// Extract audio-only segment with `encoder_args` (see `audio_encoder_args`)
 pub fn extract_audio_segment(
     input_file: &str,
     output_file: &str,
     start_time: f64,
     end_time: f64,
-    song_title: Option<&str>,
-    concertdata: &ConcertInfo,
-    track_number: Option<usize>,
+    encoder_args: &[String],
+    tags: &TrackTags,
 ) -> Result<()> {
     let mut ffmpeg = create_ffmpeg_command();
     ffmpeg
         .args([
             "-i", input_file, "-vn", // No video
-            "-acodec", "copy", // Copy audio stream without re-encoding
             "-map", "0:a",
         ])
+        .args(encoder_args)
         .time_range(start_time, end_time);
     let mut cmd = ffmpeg.cmd();
 
-    // Add metadata
-    add_metadata_to_cmd(&mut cmd, song_title, concertdata, track_number);
+    tags.apply(&mut cmd);
 
     cmd.args([
         "-y", // Overwrite output file
@@ -130,34 +213,131 @@ pub fn extract_audio_segment(
     Ok(())
 }
 
-// Add common metadata fields to an FFmpeg command
-pub fn add_metadata_to_cmd(
-    cmd: &mut std::process::Command,
-    song_title: Option<&str>,
-    concertdata: &ConcertInfo,
-    track_number: Option<usize>,
-) {
-    // Add artist metadata
-    cmd.args(["-metadata", &format!("artist={}", concertdata.artist)]);
-
-    // Add title metadata if available
-    if let Some(title) = song_title {
-        cmd.args(["-metadata", &format!("title={}", title)]);
+/// Attach `cover` to an already-cut track as its cover art (an `attached_pic`
+/// stream). A separate remux rather than a second input on the cut itself: the
+/// cut's output-side `-ss` would drop the image's only frame. The image is
+/// re-encoded to MJPEG so a PNG/WebP preview still lands in a format MP4's
+/// `covr` atom (and FLAC/ID3 pictures) accept; the track's own streams are
+/// stream-copied. The result replaces `track_file` via rename. Ogg cannot hold
+/// an `attached_pic`, so callers skip `.opus` files.
+pub fn embed_cover_art(track_file: &str, cover: &Path) -> Result<()> {
+    let track_path = Path::new(track_file);
+    let ext = track_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("m4a");
+    let staged = track_path.with_extension(format!("cover.{ext}"));
+    let mut ffmpeg = create_ffmpeg_command();
+    ffmpeg
+        .args(["-i", track_file, "-i"])
+        .args([cover.as_os_str()])
+        .args(cover_art_stream_args(ext));
+    ffmpeg.args(["-y"]).args([staged.as_os_str()]);
+    let status = ffmpeg.cmd().status()?;
+    if !status.success() {
+        let _ = fs::remove_file(&staged);
+        return Err(anyhow!(
+            "Failed to embed cover art {} into {}",
+            cover.display(),
+            track_file
+        ));
     }
+    fs::rename(&staged, track_path)?;
+    Ok(())
+}
 
-    if !concertdata.album.is_empty() {
-        cmd.args(["-metadata", &format!("album={}", concertdata.album)]);
+/// Stream mapping for [`embed_cover_art`] on a track with extension `ext`. A
+/// video track keeps its own video as `v:0` and the picture becomes `v:1`, so
+/// the HLS pass (which maps `0:V:0`) and players still pick the real video.
+fn cover_art_stream_args(ext: &str) -> Vec<String> {
+    let (source, picture) = match ext {
+        "mp4" => ("0", "v:1"),
+        _ => ("0:a", "v:0"),
+    };
+    let mut args: Vec<String> = vec![
+        "-map".into(),
+        source.into(),
+        "-map".into(),
+        "1:v:0".into(),
+        "-c".into(),
+        "copy".into(),
+        format!("-c:{picture}"),
+        "mjpeg".into(),
+        format!("-disposition:{picture}"),
+        "attached_pic".into(),
+    ];
+    match ext {
+        "mp3" => args.extend(["-id3v2_version".into(), "3".into()]),
+        // The remux would otherwise move the moov atom back behind the media.
+        "mp4" => args.extend(["-movflags".into(), "+faststart".into()]),
+        _ => {}
     }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-    // Add year metadata if available
-    if let Some(year_value) = concertdata.year() {
-        if !year_value.is_empty() {
-            cmd.args(["-metadata", &format!("date={}", year_value)]);
-        }
+    #[test]
+    fn audio_encoder_args_copy_matching_sources_and_encode_the_rest() {
+        let settings = AudioEncoderSettings::default();
+        assert_eq!(
+            audio_encoder_args(AudioFormat::M4a, "aac", &settings),
+            ["-c:a", "copy"]
+        );
+        assert_eq!(
+            audio_encoder_args(AudioFormat::Flac, "aac", &settings),
+            ["-c:a", "flac", "-compression_level", "5"]
+        );
+        assert_eq!(
+            audio_encoder_args(AudioFormat::Flac, "flac", &settings),
+            ["-c:a", "copy"]
+        );
+        assert_eq!(
+            audio_encoder_args(AudioFormat::Opus, "aac", &settings),
+            ["-c:a", "libopus", "-b:a", "128k"]
+        );
+        assert_eq!(
+            audio_encoder_args(AudioFormat::Opus, "opus", &settings),
+            ["-c:a", "copy"]
+        );
+        assert_eq!(
+            audio_encoder_args(
+                AudioFormat::Mp3,
+                "aac",
+                &AudioEncoderSettings {
+                    mp3_quality: 0,
+                    ..settings
+                }
+            ),
+            ["-c:a", "libmp3lame", "-q:a", "0", "-id3v2_version", "3"]
+        );
     }
 
-    // Add track number metadata if available
-    if let Some(track) = track_number {
-        cmd.args(["-metadata", &format!("track={}", track)]);
+    #[test]
+    fn cover_art_follows_the_video_stream_of_video_tracks() {
+        assert_eq!(
+            cover_art_stream_args("m4a"),
+            [
+                "-map",
+                "0:a",
+                "-map",
+                "1:v:0",
+                "-c",
+                "copy",
+                "-c:v:0",
+                "mjpeg",
+                "-disposition:v:0",
+                "attached_pic"
+            ]
+        );
+        let video = cover_art_stream_args("mp4");
+        assert_eq!(video[1], "0");
+        assert!(video
+            .windows(2)
+            .any(|w| w == ["-disposition:v:1", "attached_pic"]));
+        assert!(video.windows(2).any(|w| w == ["-movflags", "+faststart"]));
+        assert!(cover_art_stream_args("mp3").ends_with(&["-id3v2_version".into(), "3".into()]));
     }
 }
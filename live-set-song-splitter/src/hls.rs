@@ -0,0 +1,681 @@
+//! HLS packaging: the whole source segmented into fMP4 for streaming, with a
+//! master playlist and an `EXT-X-DATERANGE` per song and interlude. A player
+//! seeks a long concert by fetching a few seconds of segments instead of
+//! buffering the whole file. The source video is stream-copied, so its segments
+//! break at the source's keyframes; each rendition is re-encoded with a
+//! keyframe at every segment boundary.
+//!
+//! All files sit flat beside the tracks (publication only handles plain
+//! filenames): `Album.hls.m3u8` is the master playlist, and each variant is
+//! `Album.hls.<variant>.m3u8` with its `.init.mp4` and numbered `.m4s`
+//! segments.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, Context, Result};
+use concert_types::{derive_interludes, ConcertInfo, SongTimestamp};
+
+use crate::concert_split::folder_name;
+use crate::cut::{Rendition, REENCODE_PRESET, RENDITION_CRF};
+use crate::ffmpeg::{self, create_ffmpeg_command};
+
+/// Infix between the concert's folder name and the playlist extension, e.g.
+/// `Album.hls.m3u8`.
+pub const HLS_INFIX: &str = "hls";
+
+/// Target segment length in seconds. Stream-copied segments can run longer
+/// when the source's keyframes are further apart.
+pub const SEGMENT_SECONDS: u32 = 6;
+
+/// Variant name of the stream-copied source.
+const SOURCE_VARIANT: &str = "source";
+
+/// `EXT-X-DATERANGE` needs wall-clock dates, so media time 0 is pinned to the
+/// Unix epoch with `EXT-X-PROGRAM-DATE-TIME`. Concerts are far shorter than
+/// the month this format covers.
+const MAX_PROGRAM_SECONDS: f64 = 31.0 * 86_400.0;
+
+/// Filename prefix shared by every HLS file of `concert`, e.g. `Album.hls`.
+pub fn hls_file_stem(concert: &ConcertInfo) -> String {
+    format!("{}.{HLS_INFIX}", folder_name(concert))
+}
+
+/// Filename of the master playlist for `concert`, e.g. `Album.hls.m3u8`.
+pub fn master_playlist_name(concert: &ConcertInfo) -> String {
+    format!("{}.m3u8", hls_file_stem(concert))
+}
+
+/// `EXT-X-DATERANGE` id of the song at 0-based set-list `index`.
+pub fn song_range_id(index: usize) -> String {
+    format!("song-{}", index + 1)
+}
+
+/// `EXT-X-DATERANGE` id of the interlude numbered `index` (as in
+/// [`concert_types::interlude_filename_stem`]).
+pub fn interlude_range_id(index: usize) -> String {
+    format!("interlude-{index}")
+}
+
+/// A titled span of the concert, written as an `EXT-X-DATERANGE`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct DateRange {
+    pub id: String,
+    pub title: String,
+    pub start_time: f64,
+    pub duration: f64,
+}
+
+/// Songs and, when `media_duration` is known, the interludes between them, in
+/// timeline order.
+pub fn date_ranges_for(
+    timestamps: &[SongTimestamp],
+    media_duration: Option<f64>,
+) -> Vec<DateRange> {
+    let mut ranges: Vec<DateRange> = timestamps
+        .iter()
+        .enumerate()
+        .map(|(index, song)| DateRange {
+            id: song_range_id(index),
+            title: song.title.clone(),
+            start_time: song.start_time,
+            duration: song.end_time - song.start_time,
+        })
+        .collect();
+    if let Some(duration) = media_duration {
+        ranges.extend(
+            derive_interludes(timestamps, duration)
+                .into_iter()
+                .map(|interlude| DateRange {
+                    id: interlude_range_id(interlude.index),
+                    title: format!("Interlude {}", interlude.index),
+                    start_time: interlude.start_time,
+                    duration: interlude.end_time - interlude.start_time,
+                }),
+        );
+    }
+    ranges.sort_by(|a, b| a.start_time.total_cmp(&b.start_time));
+    ranges
+}
+
+/// One media segment of a variant playlist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Segment {
+    pub duration: f64,
+    pub uri: String,
+}
+
+/// A variant's VOD media playlist: fMP4 segments after one init section.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MediaPlaylist {
+    pub target_duration: u32,
+    pub init_uri: String,
+    /// Media time of the first segment; non-zero for a clipped playlist.
+    pub start_time: f64,
+    /// Where playback starts, relative to the first segment.
+    pub start_offset: Option<f64>,
+    pub segments: Vec<Segment>,
+    pub ranges: Vec<DateRange>,
+}
+
+impl MediaPlaylist {
+    /// Parse a playlist written by ffmpeg or by [`MediaPlaylist::render`].
+    /// Tags it does not model are dropped.
+    pub fn parse(text: &str) -> Result<MediaPlaylist> {
+        anyhow::ensure!(
+            text.lines().next().map(str::trim) == Some("#EXTM3U"),
+            "not an M3U playlist"
+        );
+        let mut playlist = MediaPlaylist {
+            target_duration: SEGMENT_SECONDS,
+            init_uri: String::new(),
+            start_time: 0.0,
+            start_offset: None,
+            segments: Vec::new(),
+            ranges: Vec::new(),
+        };
+        let mut pending_duration: Option<f64> = None;
+        for line in text.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            if let Some(value) = line.strip_prefix("#EXT-X-TARGETDURATION:") {
+                playlist.target_duration = value.parse().context("bad EXT-X-TARGETDURATION")?;
+            } else if let Some(attributes) = line.strip_prefix("#EXT-X-MAP:") {
+                playlist.init_uri =
+                    attribute(attributes, "URI").ok_or_else(|| anyhow!("EXT-X-MAP without URI"))?;
+            } else if let Some(value) = line.strip_prefix("#EXT-X-PROGRAM-DATE-TIME:") {
+                playlist.start_time = parse_program_date(value)?;
+            } else if let Some(attributes) = line.strip_prefix("#EXT-X-START:") {
+                playlist.start_offset = attribute(attributes, "TIME-OFFSET")
+                    .map(|offset| offset.parse())
+                    .transpose()
+                    .context("bad EXT-X-START")?;
+            } else if let Some(attributes) = line.strip_prefix("#EXT-X-DATERANGE:") {
+                let id = attribute(attributes, "ID")
+                    .ok_or_else(|| anyhow!("EXT-X-DATERANGE without ID"))?;
+                let start = attribute(attributes, "START-DATE")
+                    .ok_or_else(|| anyhow!("EXT-X-DATERANGE {id} without START-DATE"))?;
+                playlist.ranges.push(DateRange {
+                    title: attribute(attributes, "X-TITLE").unwrap_or_else(|| id.clone()),
+                    start_time: parse_program_date(&start)?,
+                    duration: attribute(attributes, "DURATION")
+                        .map(|duration| duration.parse())
+                        .transpose()
+                        .context("bad EXT-X-DATERANGE DURATION")?
+                        .unwrap_or(0.0),
+                    id,
+                });
+            } else if let Some(value) = line.strip_prefix("#EXTINF:") {
+                let duration = value.split(',').next().unwrap_or_default();
+                pending_duration = Some(duration.parse().context("bad EXTINF")?);
+            } else if !line.starts_with('#') {
+                let duration = pending_duration
+                    .take()
+                    .ok_or_else(|| anyhow!("segment {line} without EXTINF"))?;
+                playlist.segments.push(Segment {
+                    duration,
+                    uri: line.to_string(),
+                });
+            }
+        }
+        anyhow::ensure!(!playlist.init_uri.is_empty(), "playlist has no EXT-X-MAP");
+        Ok(playlist)
+    }
+
+    /// Render as a VOD playlist with the program clock and date ranges.
+    pub fn render(&self) -> String {
+        let mut out = String::from("#EXTM3U\n#EXT-X-VERSION:7\n");
+        let _ = writeln!(out, "#EXT-X-TARGETDURATION:{}", self.target_duration);
+        out.push_str("#EXT-X-MEDIA-SEQUENCE:0\n#EXT-X-PLAYLIST-TYPE:VOD\n");
+        out.push_str("#EXT-X-INDEPENDENT-SEGMENTS\n");
+        if let Some(offset) = self.start_offset {
+            let _ = writeln!(out, "#EXT-X-START:TIME-OFFSET={offset:.3},PRECISE=YES");
+        }
+        let _ = writeln!(out, "#EXT-X-MAP:URI=\"{}\"", self.init_uri);
+        let _ = writeln!(
+            out,
+            "#EXT-X-PROGRAM-DATE-TIME:{}",
+            program_date(self.start_time)
+        );
+        for range in &self.ranges {
+            let _ = writeln!(
+                out,
+                "#EXT-X-DATERANGE:ID=\"{}\",START-DATE=\"{}\",DURATION={:.3},X-TITLE=\"{}\"",
+                range.id,
+                program_date(range.start_time),
+                range.duration,
+                quoted_value(&range.title),
+            );
+        }
+        for segment in &self.segments {
+            let _ = writeln!(out, "#EXTINF:{:.6},\n{}", segment.duration, segment.uri);
+        }
+        out.push_str("#EXT-X-ENDLIST\n");
+        out
+    }
+
+    /// The segments covering the date range `id`, starting playback at its
+    /// start. `None` when the playlist has no such range. Playback runs to the
+    /// end of the last segment, up to one segment past the range.
+    pub fn clipped(&self, id: &str) -> Option<MediaPlaylist> {
+        let range = self.ranges.iter().find(|range| range.id == id)?;
+        let end = range.start_time + range.duration;
+        let mut segment_start = self.start_time;
+        let mut first_start = None;
+        let mut segments = Vec::new();
+        for segment in &self.segments {
+            let segment_end = segment_start + segment.duration;
+            if segment_end > range.start_time && segment_start < end {
+                first_start.get_or_insert(segment_start);
+                segments.push(segment.clone());
+            }
+            segment_start = segment_end;
+        }
+        let first_start = first_start?;
+        Some(MediaPlaylist {
+            target_duration: self.target_duration,
+            init_uri: self.init_uri.clone(),
+            start_time: first_start,
+            start_offset: Some(range.start_time - first_start),
+            segments,
+            ranges: vec![range.clone()],
+        })
+    }
+}
+
+/// One entry of the master playlist.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Variant {
+    /// Peak segment bitrate in bits per second.
+    pub bandwidth: u64,
+    pub average_bandwidth: u64,
+    /// Video height, unknown for the stream-copied source.
+    pub height: Option<u32>,
+    pub uri: String,
+}
+
+/// Render the master playlist, listing `variants` in the given order (the
+/// source first, so players without bandwidth estimates start there).
+pub fn render_master(variants: &[Variant]) -> String {
+    let mut out = String::from("#EXTM3U\n#EXT-X-VERSION:7\n#EXT-X-INDEPENDENT-SEGMENTS\n");
+    for variant in variants {
+        let _ = write!(
+            out,
+            "#EXT-X-STREAM-INF:BANDWIDTH={},AVERAGE-BANDWIDTH={}",
+            variant.bandwidth, variant.average_bandwidth
+        );
+        if let Some(height) = variant.height {
+            let _ = write!(out, ",NAME=\"{height}p\"");
+        }
+        let _ = writeln!(out, "\n{}", variant.uri);
+    }
+    out
+}
+
+/// Append `query` to every URI line of a master playlist, so each variant
+/// request carries it (e.g. which date range to clip to).
+pub fn with_variant_query(master: &str, query: &str) -> String {
+    let mut out = String::with_capacity(master.len());
+    for line in master.lines() {
+        out.push_str(line);
+        if !line.is_empty() && !line.starts_with('#') {
+            out.push('?');
+            out.push_str(query);
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Media time `seconds` as an `EXT-X-PROGRAM-DATE-TIME` on the epoch clock.
+fn program_date(seconds: f64) -> String {
+    let millis = (seconds.clamp(0.0, MAX_PROGRAM_SECONDS) * 1000.0).round() as u64;
+    let (days, millis) = (millis / 86_400_000, millis % 86_400_000);
+    format!(
+        "1970-01-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        days + 1,
+        millis / 3_600_000,
+        millis / 60_000 % 60,
+        millis / 1000 % 60,
+        millis % 1000
+    )
+}
+
+/// Inverse of [`program_date`].
+fn parse_program_date(value: &str) -> Result<f64> {
+    let invalid = || anyhow!("unsupported program date {value:?}");
+    let rest = value.strip_prefix("1970-01-").ok_or_else(invalid)?;
+    let rest = rest.strip_suffix('Z').ok_or_else(invalid)?;
+    let (day, time) = rest.split_once('T').ok_or_else(invalid)?;
+    let mut parts = time.split(':');
+    let (Some(hours), Some(minutes), Some(seconds), None) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+    let day: f64 = day.parse().map_err(|_| invalid())?;
+    let hours: f64 = hours.parse().map_err(|_| invalid())?;
+    let minutes: f64 = minutes.parse().map_err(|_| invalid())?;
+    let seconds: f64 = seconds.parse().map_err(|_| invalid())?;
+    Ok((day - 1.0) * 86_400.0 + hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// A quoted-string attribute cannot contain `"` or line breaks.
+fn quoted_value(value: &str) -> String {
+    value
+        .chars()
+        .map(|ch| match ch {
+            '"' => '\'',
+            '\r' | '\n' => ' ',
+            ch => ch,
+        })
+        .collect()
+}
+
+/// The value of `name` in an attribute list, unquoted.
+fn attribute(attributes: &str, name: &str) -> Option<String> {
+    let mut rest = attributes;
+    while !rest.is_empty() {
+        let (key, after) = rest.split_once('=')?;
+        let (value, after) = match after.strip_prefix('"') {
+            Some(quoted) => {
+                let (value, after) = quoted.split_once('"')?;
+                (value, after.strip_prefix(',').unwrap_or(after))
+            }
+            None => after.split_once(',').unwrap_or((after, "")),
+        };
+        if key == name {
+            return Some(value.to_string());
+        }
+        rest = after;
+    }
+    None
+}
+
+/// The codec of `input_file`'s first video stream, ignoring attached cover
+/// art; `None` for audio-only sources.
+fn probe_video_codec(input_file: &str) -> Result<Option<String>> {
+    let output = ffmpeg::create_ffprobe_command()
+        .args([
+            "-v",
+            "error",
+            "-select_streams",
+            "V:0",
+            "-show_entries",
+            "stream=codec_name",
+            "-of",
+            "csv=print_section=0",
+            input_file,
+        ])
+        .output()
+        .context("running ffprobe for the video codec")?;
+    if !output.status.success() {
+        return Err(anyhow!(
+            "ffprobe failed probing the video codec of {}: {}",
+            input_file,
+            String::from_utf8_lossy(&output.stderr)
+        ));
+    }
+    let codec = String::from_utf8(output.stdout)?.trim().to_string();
+    Ok((!codec.is_empty()).then_some(codec))
+}
+
+/// ffmpeg arguments segmenting `input_file` into one variant: the source's
+/// video stream-copied when `rendition` is `None` and fMP4 HLS can carry its
+/// codec, re-encoded to H.264 otherwise.
+pub fn build_variant_args(
+    input_file: &str,
+    output_dir: &Path,
+    variant_stem: &str,
+    video_codec: Option<&str>,
+    audio_codec: &str,
+    rendition: Option<Rendition>,
+) -> Vec<String> {
+    let mut args: Vec<String> = vec!["-i".into(), input_file.into()];
+    if video_codec.is_some() {
+        args.extend(["-map".into(), "0:V:0".into()]);
+    }
+    args.extend(["-map".into(), "0:a:0?".into()]);
+    let copy_video = rendition.is_none() && matches!(video_codec, Some("h264" | "hevc"));
+    if copy_video {
+        args.extend(["-c:v".into(), "copy".into()]);
+    } else if video_codec.is_some() {
+        if let Some(rendition) = rendition {
+            args.extend(["-vf".into(), format!("scale=-2:{}", rendition.height())]);
+        }
+        args.extend(
+            [
+                "-c:v",
+                "libx264",
+                "-preset",
+                REENCODE_PRESET,
+                "-crf",
+                RENDITION_CRF,
+                "-pix_fmt",
+                "yuv420p",
+            ]
+            .map(String::from),
+        );
+        args.extend([
+            "-force_key_frames".into(),
+            format!("expr:gte(t,n_forced*{SEGMENT_SECONDS})"),
+        ]);
+    }
+    let audio = match audio_codec {
+        "aac" | "mp3" | "ac3" | "eac3" => "copy",
+        _ => "aac",
+    };
+    args.extend(["-c:a".into(), audio.into()]);
+    args.extend(
+        [
+            "-f",
+            "hls",
+            "-hls_playlist_type",
+            "vod",
+            "-hls_segment_type",
+            "fmp4",
+            "-hls_flags",
+            "independent_segments",
+        ]
+        .map(String::from),
+    );
+    args.extend(["-hls_time".into(), SEGMENT_SECONDS.to_string()]);
+    args.extend([
+        "-hls_fmp4_init_filename".into(),
+        format!("{variant_stem}.init.mp4"),
+    ]);
+    args.extend([
+        "-hls_segment_filename".into(),
+        output_dir
+            .join(format!("{variant_stem}.%05d.m4s"))
+            .to_string_lossy()
+            .into_owned(),
+    ]);
+    args
+}
+
+/// Segment `input_file` into `output_dir`: the stream-copied source plus one
+/// variant per rendition, each carrying `ranges`, and a master playlist
+/// listing them. Returns every file written, master playlist first.
+pub fn write_hls_package(
+    input_file: &str,
+    output_dir: &Path,
+    concert: &ConcertInfo,
+    ranges: &[DateRange],
+    renditions: &[Rendition],
+) -> Result<Vec<PathBuf>> {
+    let video_codec = probe_video_codec(input_file)?;
+    let audio_codec = ffmpeg::probe_audio_codec(input_file).unwrap_or_default();
+    let stem = hls_file_stem(concert);
+    let renditions: &[Rendition] = if video_codec.is_some() {
+        renditions
+    } else {
+        &[]
+    };
+
+    let mut files = Vec::new();
+    let mut variants = Vec::new();
+    let ladder = std::iter::once(None).chain(renditions.iter().copied().map(Some));
+    for rendition in ladder {
+        let name = rendition.map_or(SOURCE_VARIANT, Rendition::name);
+        let variant_stem = format!("{stem}.{name}");
+        let playlist_name = format!("{variant_stem}.m3u8");
+        let playlist_path = output_dir.join(&playlist_name);
+        let mut ffmpeg = create_ffmpeg_command();
+        ffmpeg.args(build_variant_args(
+            input_file,
+            output_dir,
+            &variant_stem,
+            video_codec.as_deref(),
+            &audio_codec,
+            rendition,
+        ));
+        ffmpeg.arg("-y").args([playlist_path.as_os_str()]);
+        let status = ffmpeg
+            .cmd()
+            .status()
+            .context("Failed to run ffmpeg for HLS segmenting")?;
+        if !status.success() {
+            return Err(anyhow!("Failed to write HLS variant {}", playlist_name));
+        }
+
+        let mut playlist = MediaPlaylist::parse(
+            &fs::read_to_string(&playlist_path)
+                .with_context(|| format!("Failed to read {}", playlist_name))?,
+        )?;
+        playlist.ranges = ranges.to_vec();
+        fs::write(&playlist_path, playlist.render())
+            .with_context(|| format!("Failed to write {}", playlist_name))?;
+
+        let mut total_bytes = 0u64;
+        let mut peak = 0u64;
+        for segment in &playlist.segments {
+            let bytes = fs::metadata(output_dir.join(&segment.uri))
+                .with_context(|| format!("missing HLS segment {}", segment.uri))?
+                .len();
+            total_bytes += bytes;
+            if segment.duration > 0.0 {
+                peak = peak.max((bytes as f64 * 8.0 / segment.duration).ceil() as u64);
+            }
+            files.push(PathBuf::from(&segment.uri));
+        }
+        let total_duration: f64 = playlist.segments.iter().map(|s| s.duration).sum();
+        variants.push(Variant {
+            bandwidth: peak,
+            average_bandwidth: if total_duration > 0.0 {
+                (total_bytes as f64 * 8.0 / total_duration).ceil() as u64
+            } else {
+                peak
+            },
+            height: rendition.map(Rendition::height),
+            uri: playlist_name.clone(),
+        });
+        files.push(PathBuf::from(&playlist.init_uri));
+        files.push(PathBuf::from(playlist_name));
+    }
+
+    let master = master_playlist_name(concert);
+    fs::write(output_dir.join(&master), render_master(&variants))
+        .with_context(|| format!("Failed to write {}", master))?;
+    files.insert(0, PathBuf::from(master));
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ts(title: &str, start_time: f64, end_time: f64) -> SongTimestamp {
+        SongTimestamp {
+            title: title.to_string(),
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            boundary: None,
+        }
+    }
+
+    const FFMPEG_PLAYLIST: &str = "#EXTM3U\n\
+        #EXT-X-VERSION:7\n\
+        #EXT-X-TARGETDURATION:6\n\
+        #EXT-X-MEDIA-SEQUENCE:0\n\
+        #EXT-X-PLAYLIST-TYPE:VOD\n\
+        #EXT-X-INDEPENDENT-SEGMENTS\n\
+        #EXT-X-MAP:URI=\"Album.hls.source.init.mp4\"\n\
+        #EXTINF:6.006000,\n\
+        Album.hls.source.00000.m4s\n\
+        #EXTINF:6.006000,\n\
+        Album.hls.source.00001.m4s\n\
+        #EXTINF:6.006000,\n\
+        Album.hls.source.00002.m4s\n\
+        #EXTINF:2.000000,\n\
+        Album.hls.source.00003.m4s\n\
+        #EXT-X-ENDLIST\n";
+
+    #[test]
+    fn date_ranges_interleave_songs_and_interludes() {
+        let songs = vec![ts("One \"Live\"", 8.0, 12.5), ts("Two", 14.0, 20.0)];
+        let ids: Vec<String> = date_ranges_for(&songs, Some(30.0))
+            .into_iter()
+            .map(|range| range.id)
+            .collect();
+        assert_eq!(
+            ids,
+            [
+                "interlude-1",
+                "song-1",
+                "interlude-2",
+                "song-2",
+                "interlude-3"
+            ]
+        );
+    }
+
+    #[test]
+    fn playlist_round_trips_with_date_ranges() {
+        let mut playlist = MediaPlaylist::parse(FFMPEG_PLAYLIST).unwrap();
+        assert_eq!(playlist.segments.len(), 4);
+        assert_eq!(playlist.init_uri, "Album.hls.source.init.mp4");
+        playlist.ranges = date_ranges_for(&[ts("One \"Live\"", 8.0, 12.5)], None);
+
+        let rendered = playlist.render();
+        assert!(rendered.contains("#EXT-X-PROGRAM-DATE-TIME:1970-01-01T00:00:00.000Z\n"));
+        assert!(rendered.contains(
+            "#EXT-X-DATERANGE:ID=\"song-1\",START-DATE=\"1970-01-01T00:00:08.000Z\",\
+             DURATION=4.500,X-TITLE=\"One 'Live'\"\n"
+        ));
+        let mut expected = playlist.ranges.clone();
+        expected[0].title = "One 'Live'".to_string();
+        assert_eq!(MediaPlaylist::parse(&rendered).unwrap().ranges, expected);
+    }
+
+    #[test]
+    fn clipped_playlist_keeps_the_covering_segments_and_starts_at_the_range() {
+        let mut playlist = MediaPlaylist::parse(FFMPEG_PLAYLIST).unwrap();
+        playlist.ranges = date_ranges_for(&[ts("One", 8.0, 12.5)], None);
+
+        let clipped = playlist.clipped("song-1").unwrap();
+        let uris: Vec<&str> = clipped.segments.iter().map(|s| s.uri.as_str()).collect();
+        assert_eq!(
+            uris,
+            ["Album.hls.source.00001.m4s", "Album.hls.source.00002.m4s"]
+        );
+        assert!((clipped.start_time - 6.006).abs() < 1e-9);
+        assert!((clipped.start_offset.unwrap() - 1.994).abs() < 1e-9);
+        let reparsed = MediaPlaylist::parse(&clipped.render()).unwrap();
+        assert_eq!(reparsed.segments, clipped.segments);
+        assert!((reparsed.start_time - 6.006).abs() < 1e-9);
+        assert!(playlist.clipped("song-2").is_none());
+    }
+
+    #[test]
+    fn master_lists_variants_and_takes_a_query() {
+        let master = render_master(&[
+            Variant {
+                bandwidth: 5_000_000,
+                average_bandwidth: 4_000_000,
+                height: None,
+                uri: "Album.hls.source.m3u8".to_string(),
+            },
+            Variant {
+                bandwidth: 1_200_000,
+                average_bandwidth: 1_000_000,
+                height: Some(480),
+                uri: "Album.hls.480p.m3u8".to_string(),
+            },
+        ]);
+        assert_eq!(
+            with_variant_query(&master, "range=song-1"),
+            "#EXTM3U\n#EXT-X-VERSION:7\n#EXT-X-INDEPENDENT-SEGMENTS\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=5000000,AVERAGE-BANDWIDTH=4000000\n\
+             Album.hls.source.m3u8?range=song-1\n\
+             #EXT-X-STREAM-INF:BANDWIDTH=1200000,AVERAGE-BANDWIDTH=1000000,NAME=\"480p\"\n\
+             Album.hls.480p.m3u8?range=song-1\n"
+        );
+    }
+
+    #[test]
+    fn source_variant_copies_h264_and_renditions_force_segment_keyframes() {
+        let dir = Path::new("/out");
+        let source = build_variant_args("in.mp4", dir, "A.hls.source", Some("h264"), "aac", None);
+        let joined = source.join(" ");
+        assert!(joined.contains("-map 0:V:0 -map 0:a:0? -c:v copy -c:a copy"));
+        assert!(joined.contains("-hls_segment_filename /out/A.hls.source.%05d.m4s"));
+
+        let rendition = build_variant_args(
+            "in.mp4",
+            dir,
+            "A.hls.480p",
+            Some("h264"),
+            "opus",
+            Some(Rendition::P480),
+        )
+        .join(" ");
+        assert!(rendition.contains("-vf scale=-2:480 -c:v libx264"));
+        assert!(rendition.contains("-force_key_frames expr:gte(t,n_forced*6)"));
+        assert!(rendition.contains("-c:a aac"));
+
+        let audio = build_variant_args("in.m4a", dir, "A.hls.source", None, "aac", None).join(" ");
+        assert!(!audio.contains("0:V:0"));
+        assert!(!audio.contains("-c:v"));
+    }
+}
@@ -1,18 +1,31 @@
 pub mod audio;
+pub mod calibrate;
+pub mod chapters;
+mod classify;
 pub mod concert_split;
+pub mod cue;
 pub mod cut;
+pub mod decode;
 mod detect;
 pub mod ffmpeg;
+pub mod hls;
 pub mod image;
 pub mod io;
+pub mod loudness;
 pub mod ocr;
 pub mod ocr_backend;
 #[cfg(feature = "leptess-ocr")]
 pub mod ocr_leptess;
 #[cfg(feature = "paddle-ocr")]
 pub mod ocr_paddle;
+pub mod ocr_pool;
+pub mod poster;
 mod produce;
+mod provenance;
 pub mod publication;
 mod recover;
 mod refine;
+pub mod report;
+mod set_list_free;
+pub mod tags;
 pub mod video;
@@ -0,0 +1,345 @@
+//! EBU R128 loudness analysis of cut tracks (`options.loudness`): an ffmpeg
+//! `ebur128` pass per track, ReplayGain 2.0 track/album gains derived from it,
+//! the `loudness.json` sidecar concert-tracker's player reads, and optionally
+//! ReplayGain/R128 tags written back into the track files.
+
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{anyhow, Context, Result};
+use concert_types::{GainMeasurement, LoudnessFile, TrackLoudness, REPLAYGAIN_REFERENCE_LUFS};
+
+use crate::concert_split::{ConcertSplitProgress, LoudnessMode, ProducedTrack, TrackKind};
+use crate::{ffmpeg, io};
+
+/// Reference level of the Opus `R128_*_GAIN` tags (EBU R128 itself).
+const R128_REFERENCE_LUFS: f64 = -23.0;
+
+/// One file's `ebur128` summary.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Loudness {
+    pub integrated_lufs: f64,
+    /// True peak in dBFS; `-inf` for digital silence.
+    pub true_peak_dbfs: f64,
+}
+
+impl Loudness {
+    pub fn measurement(&self) -> GainMeasurement {
+        GainMeasurement {
+            integrated_lufs: self.integrated_lufs,
+            gain_db: REPLAYGAIN_REFERENCE_LUFS - self.integrated_lufs,
+            peak: 10f64.powf(self.true_peak_dbfs / 20.0),
+        }
+    }
+}
+
+/// Measure integrated loudness and true peak of `file`'s first audio stream.
+pub fn measure(file: &Path) -> Result<Loudness> {
+    // Not `create_ffmpeg_command`: the summary is logged at info level, which
+    // its `-loglevel warning` would suppress.
+    let output = Command::new("ffmpeg")
+        .args(["-hide_banner", "-nostats", "-i"])
+        .arg(file)
+        .args([
+            "-map",
+            "0:a:0",
+            "-af",
+            "ebur128=peak=true",
+            "-f",
+            "null",
+            "-",
+        ])
+        .output()
+        .with_context(|| format!("Failed to spawn ffmpeg to measure {}", file.display()))?;
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !output.status.success() {
+        return Err(anyhow!(
+            "ffmpeg loudness analysis of {} failed: {}",
+            file.display(),
+            stderr.lines().last().unwrap_or("")
+        ));
+    }
+    parse_ebur128_summary(&stderr)
+        .with_context(|| format!("No loudness summary for {}", file.display()))
+}
+
+/// Pull `I:` and `Peak:` out of the `Summary:` block the `ebur128` filter logs
+/// when the stream ends.
+pub(crate) fn parse_ebur128_summary(stderr: &str) -> Result<Loudness> {
+    let summary = stderr
+        .rfind("Summary:")
+        .map(|at| &stderr[at..])
+        .ok_or_else(|| anyhow!("ebur128 summary missing"))?;
+    let value = |label: &str| -> Result<f64> {
+        summary
+            .lines()
+            .map(str::trim)
+            .find_map(|line| line.strip_prefix(label))
+            .and_then(|rest| rest.split_whitespace().next())
+            .ok_or_else(|| anyhow!("ebur128 summary has no {label}"))?
+            .parse::<f64>()
+            .map_err(|e| anyhow!("unparseable ebur128 {label} value: {e}"))
+    };
+    Ok(Loudness {
+        integrated_lufs: value("I:")?,
+        true_peak_dbfs: value("Peak:")?,
+    })
+}
+
+/// Concert-wide loudness from per-song measurements: the duration-weighted
+/// power mean of the songs' integrated loudness (an approximation of measuring
+/// the concatenated songs, without R128's cross-track gating) and the highest
+/// peak.
+pub fn album_loudness(songs: &[(Loudness, f64)]) -> Option<Loudness> {
+    let total: f64 = songs.iter().map(|(_, duration)| duration).sum();
+    if songs.is_empty() || total <= 0.0 {
+        return None;
+    }
+    let energy: f64 = songs
+        .iter()
+        .map(|(loudness, duration)| duration * 10f64.powf(loudness.integrated_lufs / 10.0))
+        .sum::<f64>()
+        / total;
+    let peak = songs
+        .iter()
+        .map(|(loudness, _)| loudness.true_peak_dbfs)
+        .fold(f64::NEG_INFINITY, f64::max);
+    Some(Loudness {
+        integrated_lufs: 10.0 * energy.log10(),
+        true_peak_dbfs: peak,
+    })
+}
+
+/// `R128_*_GAIN` value: Q7.8 fixed-point dB relative to -23 LUFS.
+fn r128_gain(integrated_lufs: f64) -> i32 {
+    ((R128_REFERENCE_LUFS - integrated_lufs) * 256.0)
+        .round()
+        .clamp(i16::MIN as f64, i16::MAX as f64) as i32
+}
+
+/// `-metadata` pairs for a track's ReplayGain and R128 tags.
+pub(crate) fn replaygain_tags(
+    track: &GainMeasurement,
+    album: &GainMeasurement,
+) -> Vec<(&'static str, String)> {
+    vec![
+        ("REPLAYGAIN_TRACK_GAIN", format!("{:.2} dB", track.gain_db)),
+        ("REPLAYGAIN_TRACK_PEAK", format!("{:.6}", track.peak)),
+        ("REPLAYGAIN_ALBUM_GAIN", format!("{:.2} dB", album.gain_db)),
+        ("REPLAYGAIN_ALBUM_PEAK", format!("{:.6}", album.peak)),
+        (
+            "R128_TRACK_GAIN",
+            r128_gain(track.integrated_lufs).to_string(),
+        ),
+        (
+            "R128_ALBUM_GAIN",
+            r128_gain(album.integrated_lufs).to_string(),
+        ),
+    ]
+}
+
+/// Rewrite `file` (stream copy) with ReplayGain/R128 tags added to its existing
+/// ones. The MP4 muxer drops tags it has no iTunes atom for, so this switches
+/// MP4/M4A files to QuickTime `mdta` keys (`-movflags use_metadata_tags`), which
+/// ffmpeg-based readers (Jellyfin, mpv, VLC) understand but iTunes-style
+/// taggers may not — hence opt-in via [`LoudnessMode::Tag`]. FLAC, Opus and
+/// MP3 carry them as ordinary Vorbis comments / ID3 `TXXX` frames.
+pub fn write_replaygain_tags(
+    file: &Path,
+    track: &GainMeasurement,
+    album: &GainMeasurement,
+) -> Result<()> {
+    let ext = file
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("m4a");
+    let staged = file.with_extension(format!("replaygain.{ext}"));
+    let mut ffmpeg = ffmpeg::create_ffmpeg_command();
+    ffmpeg
+        .args(["-i"])
+        .args([file.as_os_str()])
+        .args(["-map", "0", "-c", "copy"]);
+    match ext {
+        "mp4" | "m4a" => {
+            ffmpeg.args(["-movflags", "+use_metadata_tags"]);
+        }
+        "mp3" => {
+            ffmpeg.args(["-id3v2_version", "3"]);
+        }
+        _ => {}
+    }
+    let mut cmd = ffmpeg.cmd();
+    for (key, value) in replaygain_tags(track, album) {
+        cmd.args(["-metadata", &format!("{key}={value}")]);
+    }
+    cmd.arg("-y").arg(&staged);
+    let status = cmd.status()?;
+    if !status.success() {
+        let _ = fs::remove_file(&staged);
+        return Err(anyhow!(
+            "Failed to write ReplayGain tags to {}",
+            file.display()
+        ));
+    }
+    fs::rename(&staged, file)?;
+    Ok(())
+}
+
+/// Measure every produced track in `output_dir`, write `loudness.json` there,
+/// and (for [`LoudnessMode::Tag`]) tag each track file. The audio file is
+/// measured when one was cut (the first audio format), else the video. Album gain comes from the songs
+/// only, so spoken interludes don't pull it around; interludes still get
+/// their own track gain.
+pub(crate) fn analyze(
+    output_dir: &Path,
+    tracks: &[ProducedTrack],
+    extensions: &[&str],
+    mode: LoudnessMode,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> Result<LoudnessFile> {
+    let measured_ext = extensions
+        .iter()
+        .find(|&&ext| !ext.ends_with("mp4"))
+        .or(extensions.first())
+        .ok_or_else(|| anyhow!("no track files to measure"))?;
+    let mut measured = Vec::with_capacity(tracks.len());
+    for track in tracks {
+        let stem = match track.kind {
+            TrackKind::Song => io::sanitize_filename(&track.title),
+            TrackKind::Interlude => track.title.clone(),
+        };
+        let file = output_dir.join(format!("{stem}.{measured_ext}"));
+        let loudness = measure(&file)?;
+        progress(ConcertSplitProgress::Diagnostic(format!(
+            "{}: {:.1} LUFS, true peak {:.1} dBFS",
+            stem, loudness.integrated_lufs, loudness.true_peak_dbfs
+        )));
+        measured.push((stem, track, loudness));
+    }
+
+    let songs: Vec<(Loudness, f64)> = measured
+        .iter()
+        .filter(|(_, track, _)| track.kind == TrackKind::Song)
+        .map(|(_, track, loudness)| (*loudness, track.end_time - track.start_time))
+        .collect();
+    let album = album_loudness(&songs)
+        .ok_or_else(|| anyhow!("no songs to derive album loudness from"))?
+        .measurement();
+
+    let tracks: Vec<TrackLoudness> = measured
+        .into_iter()
+        .map(|(stem, _, loudness)| TrackLoudness {
+            stem,
+            measurement: loudness.measurement(),
+        })
+        .collect();
+    if mode == LoudnessMode::Tag {
+        for track in &tracks {
+            for ext in extensions {
+                let file = output_dir.join(format!("{}.{ext}", track.stem));
+                write_replaygain_tags(&file, &track.measurement, &album)?;
+            }
+        }
+    }
+
+    let file = LoudnessFile {
+        reference_lufs: REPLAYGAIN_REFERENCE_LUFS,
+        album,
+        tracks,
+    };
+    let json = serde_json::to_string_pretty(&file)?;
+    fs::write(output_dir.join(concert_types::LOUDNESS_FILE), json)?;
+    Ok(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUMMARY: &str = "\
+[Parsed_ebur128_0 @ 0x600] t: 181.4    TARGET:-23 LUFS    M: -21.2 S: -20.9     I: -19.6 LUFS
+[Parsed_ebur128_0 @ 0x600] Summary:
+
+  Integrated loudness:
+    I:         -19.3 LUFS
+    Threshold: -29.5 LUFS
+
+  Loudness range:
+    LRA:         6.4 LU
+    Threshold: -39.6 LUFS
+    LRA low:   -24.1 LUFS
+    LRA high:  -17.7 LUFS
+
+  True peak:
+    Peak:       -0.5 dBFS
+";
+
+    #[test]
+    fn parses_the_final_summary_not_the_running_values() {
+        let loudness = parse_ebur128_summary(SUMMARY).unwrap();
+        assert_eq!(loudness.integrated_lufs, -19.3);
+        assert_eq!(loudness.true_peak_dbfs, -0.5);
+    }
+
+    #[test]
+    fn missing_summary_is_an_error() {
+        assert!(parse_ebur128_summary("Stream #0:0: Audio: aac").is_err());
+    }
+
+    #[test]
+    fn silence_parses_to_a_zero_peak() {
+        let silent = SUMMARY.replace("-0.5 dBFS", "-inf dBFS");
+        let loudness = parse_ebur128_summary(&silent).unwrap();
+        assert_eq!(loudness.measurement().peak, 0.0);
+    }
+
+    #[test]
+    fn gain_targets_the_replaygain_reference() {
+        let m = Loudness {
+            integrated_lufs: -14.0,
+            true_peak_dbfs: 0.0,
+        }
+        .measurement();
+        assert_eq!(m.gain_db, -4.0);
+        assert_eq!(m.peak, 1.0);
+    }
+
+    #[test]
+    fn album_loudness_is_a_duration_weighted_power_mean() {
+        let loud = Loudness {
+            integrated_lufs: -10.0,
+            true_peak_dbfs: -1.0,
+        };
+        let quiet = Loudness {
+            integrated_lufs: -30.0,
+            true_peak_dbfs: -12.0,
+        };
+        // Equal durations: the loud song dominates the energy.
+        let album = album_loudness(&[(loud, 100.0), (quiet, 100.0)]).unwrap();
+        assert!((album.integrated_lufs - -12.97).abs() < 0.01, "{album:?}");
+        assert_eq!(album.true_peak_dbfs, -1.0);
+        assert!(album_loudness(&[]).is_none());
+    }
+
+    #[test]
+    fn replaygain_tags_format_gain_peak_and_r128() {
+        let track = Loudness {
+            integrated_lufs: -20.5,
+            true_peak_dbfs: -6.0,
+        }
+        .measurement();
+        let album = Loudness {
+            integrated_lufs: -16.0,
+            true_peak_dbfs: -0.1,
+        }
+        .measurement();
+        let tags = replaygain_tags(&track, &album);
+        let get = |key: &str| tags.iter().find(|(k, _)| *k == key).unwrap().1.clone();
+        assert_eq!(get("REPLAYGAIN_TRACK_GAIN"), "2.50 dB");
+        assert_eq!(get("REPLAYGAIN_TRACK_PEAK"), "0.501187");
+        assert_eq!(get("REPLAYGAIN_ALBUM_GAIN"), "-2.00 dB");
+        assert_eq!(get("R128_TRACK_GAIN"), "-640");
+        assert_eq!(get("R128_ALBUM_GAIN"), "-1792");
+    }
+}
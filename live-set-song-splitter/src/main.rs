@@ -4,11 +4,15 @@
 //! See `docs/concert-split.md` for the interface and state diagram.
 
 use live_set_splitter::concert_split::{
-    self, ConcertSplitOptions, ConcertSplitOutcome, ConcertSplitProgress, ConcertSplitReport,
-    ConcertSplitRequest, NoOutputReason, OutputFormat, SplitPhase, TrackKind,
+    self, AlbumFormat, AlbumOutput, AudioEncoderSettings, AudioFormat, ChapterOutput,
+    ConcertSplitOptions, ConcertSplitOutcome, ConcertSplitProgress, ConcertSplitReport,
+    ConcertSplitRequest, DetectionSampling, HlsOutput, LoudnessMode, NoOutputReason, OutputFormat,
+    SetListFree, SplitPhase, TrackKind,
 };
-use live_set_splitter::cut::VideoCutMode;
+use live_set_splitter::cut::{Rendition, VideoCutMode};
+use live_set_splitter::decode::Crop;
 use live_set_splitter::ocr_backend::OcrChoice;
+use live_set_splitter::tags::TagTemplate;
 
 use concert_types::{ConcertInfo, TimestampsFile};
 
@@ -43,6 +47,24 @@ struct Cli {
     #[arg(long, value_enum, default_value_t = OutputFormat::Both)]
     output_format: OutputFormat,
 
+    /// Audio files to write per track when the output includes audio,
+    /// comma-separated: `m4a` (copies the source AAC), `flac` (lossless from
+    /// the decoded source), `opus`, `mp3`.
+    #[arg(long, value_enum, value_delimiter = ',', default_value = "m4a")]
+    audio_format: Vec<AudioFormat>,
+
+    /// Opus bitrate in kbit/s.
+    #[arg(long, default_value_t = 128, value_parser = clap::value_parser!(u32).range(6..=510))]
+    opus_bitrate: u32,
+
+    /// MP3 VBR quality, 0 (best) to 9.
+    #[arg(long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(0..=9))]
+    mp3_quality: u8,
+
+    /// FLAC compression level, 0 (fastest) to 12.
+    #[arg(long, default_value_t = 5, value_parser = clap::value_parser!(u8).range(0..=12))]
+    flac_compression: u8,
+
     /// How to cut the video stream: `smart` (frame-accurate at the detected start,
     /// near-copy speed; re-encodes only up to the first keyframe of each track),
     /// `copy` (fastest, lossless; snaps each cut back to the nearest preceding
@@ -52,6 +74,12 @@ struct Cli {
     #[arg(long, value_enum, default_value_t = VideoCutMode::Smart)]
     video_cut_mode: VideoCutMode,
 
+    /// Downscaled H.264 copies to write beside each video track as
+    /// `<title>.<height>p.mp4`, comma-separated: `1080p`, `720p`, `480p`,
+    /// `360p`. Those at or above the source height are skipped.
+    #[arg(long, value_enum, value_delimiter = ',')]
+    renditions: Vec<Rendition>,
+
     /// Custom output directory for generated audio/video files
     #[arg(long)]
     output_dir: Option<String>,
@@ -60,12 +88,13 @@ struct Cli {
     #[arg(long)]
     analyze_images: bool,
 
-    /// Reuse previously extracted frames if they exist
+    /// Reuse frames written by an earlier --keep-frames run instead of decoding again
     #[arg(long)]
     reuse_frames: bool,
 
-    /// Keep the extracted temp_frames/ directory after the run instead of deleting it.
-    /// Useful for building OCR test data (frames + the --analyze_images matches).
+    /// Write every analyzed frame as a PNG under temp_frames/ and keep the directory
+    /// after the run. Useful for building OCR test data (frames + the --analyze_images
+    /// matches). Without it frames are decoded in memory and never written.
     #[arg(long)]
     keep_frames: bool,
 
@@ -75,8 +104,47 @@ struct Cli {
     #[arg(long, value_enum)]
     ocr_engine: Option<OcrChoice>,
 
+    /// OCR worker threads for overlay detection, each loading its own OCR model.
+    /// Frames are still matched in time order, so results match a single thread.
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u16).range(1..))]
+    ocr_threads: u16,
+
+    /// How overlay detection samples the video: `dense` OCRs a frame every second;
+    /// `coarse` OCRs one every few seconds and densifies around title cards (fewer
+    /// frames OCR'd, same boundaries unless a title card is very short).
+    #[arg(long, value_enum, default_value_t = DetectionSampling::Dense)]
+    detection_sampling: DetectionSampling,
+
+    /// Where overlay detection looks for the artist/title card, as ffmpeg crop
+    /// arguments `width:height:x:y` in the 400x200 scaled frame. By default the
+    /// card is located from the video's first minutes (falling back to the studio
+    /// layout, `266:50:0:150`); set this when that picks the wrong place.
+    #[arg(long)]
+    overlay_crop: Option<Crop>,
+
+    /// Split a recording with no set list (the concert JSON's `set_list` must be
+    /// empty): songs are found from silences in the audio and named `Track 01`,
+    /// `Track 02`, …
+    #[arg(long)]
+    set_list_free: bool,
+
+    /// With `--set-list-free`, the shortest song: no boundary is placed closer
+    /// than this many seconds to another.
+    #[arg(long, requires = "set_list_free", default_value_t = 90.0)]
+    min_song_seconds: f64,
+
+    /// With `--set-list-free`, the longest song: longer tracks are split at their
+    /// quietest point.
+    #[arg(long, requires = "set_list_free", default_value_t = 900.0)]
+    max_song_seconds: f64,
+
+    /// With `--set-list-free`, OCR the title overlays and name each track after
+    /// the title read inside it (falling back to the placeholder).
+    #[arg(long, requires = "set_list_free")]
+    overlay_titles: bool,
+
     /// Cut and save interlude (gap) files for every span between song tracks that
-    /// is not covered by a song. Interlude files are named `interlude_NN.mp4|.m4a`
+    /// is not covered by a song. Interlude files are named `interlude_NN.<ext>`
     /// and share the output directory with song tracks. Any previously written
     /// `interlude_NN.*` files in the output directory are removed before writing.
     /// Requires either `--media-duration` or that the source file is present for
@@ -89,6 +157,64 @@ struct Cli {
     #[arg(long)]
     media_duration: Option<f64>,
 
+    /// Also (`alongside`) or instead (`only`) write `<album>.chapters.<ext>`: the
+    /// source remuxed without re-encoding, with one chapter per song and
+    /// interlude. `only` replaces any previously published track files.
+    #[arg(long, value_enum, default_value_t = ChapterOutput::None)]
+    chapters: ChapterOutput,
+
+    /// Also (`alongside`) or instead (`only`) write the whole concert's audio as
+    /// one `<album>.album.<ext>` plus `<album>.album.cue` indexing each song.
+    /// `only` replaces any previously published track files.
+    #[arg(long, value_enum, default_value_t = AlbumOutput::None)]
+    album: AlbumOutput,
+
+    /// Format of the `--album` file.
+    #[arg(long, value_enum, default_value_t = AlbumFormat::Flac)]
+    album_format: AlbumFormat,
+
+    /// Also (`alongside`) or instead (`only`) package the source for HLS
+    /// streaming: fMP4 segments with `<album>.hls.m3u8` as the master playlist
+    /// and a date range per song. `--renditions` become extra variants.
+    /// `only` replaces any previously published track files.
+    #[arg(long, value_enum, default_value_t = HlsOutput::None)]
+    hls: HlsOutput,
+
+    /// Write a poster frame per song (`<song>.poster.jpg`, taken just after
+    /// the title card) and a contact sheet of the concert
+    /// (`<album>.contact.jpg`). Needs a video source.
+    #[arg(long)]
+    posters: bool,
+
+    /// Override one tag of the default template as `KEY=PATTERN` (repeatable).
+    /// Patterns may use `{title}`, `{artist}`, `{album}`, `{year}`, `{date}`,
+    /// `{show}`, `{source}`, `{track}`, `{track_total}`, `{musicians}` and
+    /// `{performers}`; an empty pattern (`--tag performer=`) drops the tag.
+    #[arg(long = "tag", value_name = "KEY=PATTERN")]
+    tags: Vec<String>,
+
+    /// Image to embed as cover art in the audio tracks (not Opus). Defaults to the scraped
+    /// `preview.jpg` in the output directory, or next to the concert file.
+    #[arg(long, conflicts_with = "no_cover_art")]
+    cover_art: Option<PathBuf>,
+
+    /// Don't embed cover art, even when a `preview.jpg` is present.
+    #[arg(long)]
+    no_cover_art: bool,
+
+    /// Run an EBU R128 loudness pass over the cut tracks. `measure` writes
+    /// `loudness.json` (per-track and album ReplayGain gains and peaks) next to
+    /// the tracks; `tag` also writes ReplayGain/R128 tags into each file, which
+    /// stores all of an MP4's tags as QuickTime `mdta` keys.
+    #[arg(long, value_enum, default_value_t = LoudnessMode::Off)]
+    loudness: LoudnessMode,
+
+    /// Write a diagnostic `report.html` of the split to this directory: the
+    /// energy profile with its silences, the matched overlay frames, recovery
+    /// decisions and the final boundaries.
+    #[arg(long, value_name = "DIR")]
+    report: Option<PathBuf>,
+
     /// Structured result transport for subprocess adapters.
     #[arg(long, hide = true)]
     outcome_file: Option<PathBuf>,
@@ -161,17 +287,54 @@ fn build_request(cli: &Cli) -> Result<ConcertSplitRequest> {
         None
     };
 
+    let mut tag_template = TagTemplate::default();
+    for spec in &cli.tags {
+        tag_template.apply_override(spec)?;
+    }
+
+    let cover_art = if cli.no_cover_art {
+        None
+    } else if let Some(path) = &cli.cover_art {
+        Some(path.clone())
+    } else {
+        default_cover_art(Path::new(&output_dir), Path::new(concert_path))
+    };
+
     let options = ConcertSplitOptions {
         no_save_songs: cli.no_save_songs,
         refine_timestamps: cli.refine_timestamps,
         output_format: cli.output_format,
+        audio_formats: cli.audio_format.clone(),
+        audio_encoder: AudioEncoderSettings {
+            opus_bitrate_kbps: cli.opus_bitrate,
+            mp3_quality: cli.mp3_quality,
+            flac_compression_level: cli.flac_compression,
+        },
         video_cut_mode: cli.video_cut_mode,
+        renditions: cli.renditions.clone(),
         analyze_images: cli.analyze_images,
         reuse_frames: cli.reuse_frames,
         keep_frames: cli.keep_frames,
         ocr_engine: cli.ocr_engine,
+        ocr_threads: usize::from(cli.ocr_threads),
+        detection_sampling: cli.detection_sampling,
+        overlay_crop: cli.overlay_crop,
+        set_list_free: cli.set_list_free.then_some(SetListFree {
+            min_song_seconds: cli.min_song_seconds,
+            max_song_seconds: cli.max_song_seconds,
+            overlay_titles: cli.overlay_titles,
+        }),
         emit_interludes: cli.emit_interludes,
         media_duration: cli.media_duration,
+        chapters: cli.chapters,
+        album: cli.album,
+        album_format: cli.album_format,
+        hls: cli.hls,
+        posters: cli.posters,
+        tag_template,
+        cover_art,
+        loudness: cli.loudness,
+        report_dir: cli.report.clone(),
     };
 
     Ok(ConcertSplitRequest {
@@ -183,6 +346,15 @@ fn build_request(cli: &Cli) -> Result<ConcertSplitRequest> {
     })
 }
 
+/// The scraped `preview.jpg`, looked up in the output directory (the concert
+/// directory under concert-tracker's layout) and then beside the concert file.
+fn default_cover_art(output_dir: &Path, concert_path: &Path) -> Option<PathBuf> {
+    let beside_concert = concert_path.parent().map(|dir| dir.join("preview.jpg"));
+    std::iter::once(output_dir.join("preview.jpg"))
+        .chain(beside_concert)
+        .find(|path| path.is_file())
+}
+
 /// `concert.json` is a byte-for-byte copy of the caller's input file (only
 /// written if not already present) — a transport artifact the CLI owns because
 /// only it has the original file path; the library only produces
@@ -211,6 +383,11 @@ fn render_progress(event: ConcertSplitProgress) {
                 SplitPhase::RefineAudio => "Refining song boundaries using audio analysis...",
                 SplitPhase::WriteMetadata => "Writing timestamps metadata",
                 SplitPhase::Cut => "Cutting tracks",
+                SplitPhase::MeasureLoudness => "Measuring loudness",
+                SplitPhase::WriteChapters => "Writing chaptered copy",
+                SplitPhase::WriteAlbum => "Writing album and CUE sheet",
+                SplitPhase::WriteHls => "Packaging for HLS",
+                SplitPhase::WritePosters => "Writing poster frames",
                 SplitPhase::ValidateOutput => "Validating split output",
                 SplitPhase::Publish => "Publishing split output",
                 SplitPhase::Cleanup => "Cleaning up",
@@ -291,6 +468,21 @@ fn main() -> Result<()> {
         copy_concert_json(&output_dir, &concert_path)?;
     }
 
+    if let ConcertSplitOutcome::Complete(output) = &outcome {
+        if let Some(file) = &output.chapters_file {
+            println!("Chaptered copy: {}", output.output_dir.join(file).display());
+        }
+        if let Some(file) = &output.cue_file {
+            println!("CUE sheet: {}", output.output_dir.join(file).display());
+        }
+        if let Some(file) = &output.hls_playlist {
+            println!("HLS playlist: {}", output.output_dir.join(file).display());
+        }
+        if let Some(file) = &output.contact_sheet {
+            println!("Contact sheet: {}", output.output_dir.join(file).display());
+        }
+    }
+
     match &outcome {
         ConcertSplitOutcome::Complete(_)
         | ConcertSplitOutcome::NoOutput {
@@ -324,6 +516,11 @@ mod tests {
             timestamps: Vec::new(),
             tracks: Vec::new(),
             output_dir: PathBuf::new(),
+            chapters_file: None,
+            album_file: None,
+            cue_file: None,
+            hls_playlist: None,
+            contact_sheet: None,
         }
     }
 
@@ -152,7 +152,7 @@ impl fmt::Display for YesArtistMatchReason {
     }
 }
 
-fn fuzzy_match_artist(line_input: &str, artist_input: &str) -> bool {
+pub(crate) fn fuzzy_match_artist(line_input: &str, artist_input: &str) -> bool {
     match fuzzy_match_artist_reason(line_input, artist_input) {
         ArtistMatchReason::Yes(..) => true,
         ArtistMatchReason::No(..) => false,
@@ -9,7 +9,8 @@
 //! Backends are built for a specific [`OcrPhase`] (detection vs. refinement), which fixes
 //! their PSM set, per-candidate match weights, and options.
 
-use anyhow::Result;
+use ::image::DynamicImage;
+use anyhow::{Context, Result};
 use stringmetrics::LevWeights;
 
 use crate::ocr::OcrParse;
@@ -47,12 +48,20 @@ pub enum OcrChoice {
 }
 
 pub trait OcrBackend {
-    /// OCR a single image path and return parsed candidates (tesseract: one per PSM;
+    /// OCR a decoded frame and return parsed candidates (tesseract: one per PSM;
     /// Paddle: one or more). Per-element `Result` so the caller can decide how to handle a
     /// failing engine; the pipeline collects to `Result<Vec<_>>` to propagate the first
     /// error (matching the previous abort-on-error behavior). Empty/too-short parses are
     /// simply absent — candidates carry their own weights, so dropping them is safe.
-    fn ocr_image_path(&mut self, image_path: &str, artist: &str) -> Vec<Result<OcrCandidate>>;
+    fn ocr_image(&mut self, image: &DynamicImage, artist: &str) -> Vec<Result<OcrCandidate>>;
+
+    /// [`OcrBackend::ocr_image`] on an image file (fixtures, saved frames).
+    fn ocr_image_path(&mut self, image_path: &str, artist: &str) -> Vec<Result<OcrCandidate>> {
+        match ::image::open(image_path).with_context(|| format!("opening {} for OCR", image_path)) {
+            Ok(image) => self.ocr_image(&image, artist),
+            Err(e) => vec![Err(e)],
+        }
+    }
 
     fn options(&self) -> OcrBackendOptions;
 }
@@ -1,3 +1,6 @@
+use std::io::Cursor;
+
+use ::image::{DynamicImage, ImageFormat};
 use anyhow::Result;
 use leptess::{LepTess, Variable};
 use stringmetrics::LevWeights;
@@ -32,6 +35,19 @@ impl LeptessOcr {
     }
 }
 
+impl LeptessOcr {
+    /// OCR an encoded image (any format leptonica reads) held in memory.
+    fn ocr_encoded(&mut self, image: &[u8]) -> Result<String> {
+        self.lt
+            .set_image_from_mem(image)
+            .map_err(|e| anyhow::anyhow!("Failed to set in-memory image: {}", e))?;
+
+        self.lt
+            .get_utf8_text()
+            .map_err(|e| anyhow::anyhow!("Failed to get OCR text: {}", e))
+    }
+}
+
 impl OcrEngine for LeptessOcr {
     fn ocr_text(&mut self, image_path: &str) -> Result<String> {
         self.lt
@@ -98,10 +114,19 @@ impl TesseractBackend {
 }
 
 impl OcrBackend for TesseractBackend {
-    fn ocr_image_path(&mut self, image_path: &str, artist: &str) -> Vec<Result<OcrCandidate>> {
+    fn ocr_image(&mut self, image: &DynamicImage, artist: &str) -> Vec<Result<OcrCandidate>> {
+        // Leptonica takes encoded images; encode the frame once (in memory) for
+        // every PSM engine.
+        let mut png = Vec::new();
+        if let Err(e) = image.write_to(&mut Cursor::new(&mut png), ImageFormat::Png) {
+            return vec![Err(anyhow::anyhow!(
+                "Failed to encode frame for OCR: {}",
+                e
+            ))];
+        }
         let mut out = Vec::new();
         for (engine, weights) in self.engines.iter_mut().zip(self.weights.iter()) {
-            match engine.ocr_text(image_path) {
+            match engine.ocr_encoded(&png) {
                 // Drop None (empty/too-short) parses, as the old per-PSM loop did.
                 Ok(text) => {
                     if let Some(parse) = parse_tesseract_output(&text, artist) {
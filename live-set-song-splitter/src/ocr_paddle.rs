@@ -21,8 +21,9 @@
 use std::path::{Path, PathBuf};
 
 use anyhow::{Context, Result};
-use ocr_rs::{DetModel, RecModel};
+use ocr_rs::{DetModel, DetOnlyEngine, RecModel};
 
+use crate::calibrate::{TextRegion, TextRegionDetector};
 use crate::ocr::{
     parse_tesseract_output, weights_for_greedy_extractor, weights_for_stingy_extractor, OcrEngine,
 };
@@ -107,18 +108,29 @@ pub struct PaddleOcr {
     title_crop: bool,
 }
 
+/// The recognition model and charset named by `PADDLE_OCR_REC_MODEL`/`PADDLE_OCR_KEYS`
+/// (see [`DEFAULT_REC_MODEL`]), loaded from `dir`.
+fn load_rec_model(dir: &Path) -> Result<RecModel> {
+    let rec_model =
+        std::env::var("PADDLE_OCR_REC_MODEL").unwrap_or_else(|_| DEFAULT_REC_MODEL.to_string());
+    let keys_file =
+        std::env::var("PADDLE_OCR_KEYS").unwrap_or_else(|_| DEFAULT_KEYS_FILE.to_string());
+
+    let rec_path = dir.join(&rec_model);
+    let keys_path = dir.join(&keys_file);
+    RecModel::from_file(&rec_path, &keys_path, None).map_err(|e| {
+        anyhow::anyhow!(
+            "loading paddle recognition model {}: {}",
+            rec_path.display(),
+            e
+        )
+    })
+}
+
 impl PaddleOcr {
     pub fn new() -> Result<Self> {
         let dir = resolve_model_dir()?;
-
-        let rec_model =
-            std::env::var("PADDLE_OCR_REC_MODEL").unwrap_or_else(|_| DEFAULT_REC_MODEL.to_string());
-        let keys_file =
-            std::env::var("PADDLE_OCR_KEYS").unwrap_or_else(|_| DEFAULT_KEYS_FILE.to_string());
-
         let det_path = dir.join(DET_MODEL);
-        let rec_path = dir.join(&rec_model);
-        let keys_path = dir.join(&keys_file);
 
         // `None` config = library defaults (CPU backend, default thread count, and
         // default DetOptions — see the tuning note above).
@@ -129,13 +141,7 @@ impl PaddleOcr {
                 e
             )
         })?;
-        let rec = RecModel::from_file(&rec_path, &keys_path, None).map_err(|e| {
-            anyhow::anyhow!(
-                "loading paddle recognition model {}: {}",
-                rec_path.display(),
-                e
-            )
-        })?;
+        let rec = load_rec_model(&dir)?;
 
         // Title-crop is ON by default (the 100%-recall config); opt out with =0/false/no.
         let title_crop = std::env::var("PADDLE_OCR_TITLE_CROP")
@@ -194,8 +200,14 @@ impl OcrEngine for PaddleOcr {
     fn ocr_text(&mut self, image_path: &str) -> Result<String> {
         let img = ::image::open(image_path)
             .with_context(|| format!("opening {} for PaddleOCR", image_path))?;
+        self.ocr_image_text(&img)
+    }
+}
 
-        let mut items = self.detect_recognize(&img)?;
+impl PaddleOcr {
+    /// The text of a decoded frame, one detected line per line, top to bottom.
+    pub fn ocr_image_text(&mut self, img: &::image::DynamicImage) -> Result<String> {
+        let mut items = self.detect_recognize(img)?;
 
         // Optional title-crop pass: the bold artist line can suppress detection of a
         // fainter title line below it. Crop below the topmost detected box and re-detect
@@ -255,8 +267,12 @@ impl PaddleBackend {
 }
 
 impl OcrBackend for PaddleBackend {
-    fn ocr_image_path(&mut self, image_path: &str, artist: &str) -> Vec<Result<OcrCandidate>> {
-        let text = match self.ocr.ocr_text(image_path) {
+    fn ocr_image(
+        &mut self,
+        image: &::image::DynamicImage,
+        artist: &str,
+    ) -> Vec<Result<OcrCandidate>> {
+        let text = match self.ocr.ocr_image_text(image) {
             Ok(text) => text,
             Err(e) => return vec![Err(e)],
         };
@@ -289,6 +305,62 @@ impl OcrBackend for PaddleBackend {
     }
 }
 
+/// Overlay calibration's view of a full frame: the detection model alone
+/// (`det_only`) finds every text box, then each box is read so the caller can tell
+/// the artist line from the rest. No title-crop pass — calibration only needs the
+/// boxes' positions.
+pub struct PaddleTextRegions {
+    det: DetOnlyEngine,
+    rec: RecModel,
+}
+
+impl PaddleTextRegions {
+    pub fn new() -> Result<Self> {
+        let dir = resolve_model_dir()?;
+        let det_path = dir.join(DET_MODEL);
+        let det = ocr_rs::OcrEngine::det_only(&det_path, None).map_err(|e| {
+            anyhow::anyhow!(
+                "loading paddle detection model {}: {}",
+                det_path.display(),
+                e
+            )
+        })?;
+        Ok(Self {
+            det,
+            rec: load_rec_model(&dir)?,
+        })
+    }
+}
+
+impl TextRegionDetector for PaddleTextRegions {
+    fn text_regions(&mut self, image: &::image::DynamicImage) -> Result<Vec<TextRegion>> {
+        let dets = self
+            .det
+            .detect_and_crop(image)
+            .map_err(|e| anyhow::anyhow!("paddle detection failed: {}", e))?;
+        if dets.is_empty() {
+            return Ok(Vec::new());
+        }
+        let crops: Vec<_> = dets.iter().map(|(crop, _)| crop.clone()).collect();
+        let results = self
+            .rec
+            .recognize_batch(&crops)
+            .map_err(|e| anyhow::anyhow!("paddle recognition failed: {}", e))?;
+        Ok(dets
+            .iter()
+            .zip(results)
+            .map(|((_, bbox), r)| TextRegion {
+                left: bbox.rect.left().max(0) as u32,
+                top: bbox.rect.top().max(0) as u32,
+                width: bbox.rect.width(),
+                height: bbox.rect.height(),
+                text: r.text.trim().to_string(),
+            })
+            .filter(|region| !region.text.is_empty())
+            .collect())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,587 @@
+//! Parallel OCR for the detection pass (`--ocr-threads`).
+//!
+//! Detection walks frames in time order and its decisions depend on earlier frames
+//! (already-matched songs, the 30-second minimum song length, stopping once every
+//! song is found), so the walk itself stays sequential. What runs in parallel is the
+//! OCR: [`FrameScanner`] hands upcoming frames to a pool of worker threads, each
+//! owning its own backend (and so its own model), and gives the frames back to the
+//! detection loop strictly in order. The loop then sees exactly the candidates it
+//! would have computed itself, so matching and overlay clustering are unchanged.
+//!
+//! Workers OCR ahead of the loop speculatively: every pass the backend might need
+//! (the color pass, plus the B/W pass when [`OcrBackendOptions::black_and_white`])
+//! is run up front. Frames the loop is known to skip (see
+//! [`FrameScanner::skip_before`] and [`FrameScanner::speculate_every`]) are left
+//! un-OCR'd; should the loop need one after all, [`FrameScanner::candidates`] runs
+//! it on a backend of its own.
+//!
+//! With one thread there is no pool: frames are OCR'd on demand on the calling
+//! thread, exactly as before.
+
+use std::collections::BTreeMap;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use ::image::DynamicImage;
+use anyhow::{anyhow, Context, Result};
+
+use crate::decode::Frame;
+use crate::ocr::OcrParse;
+use crate::ocr_backend::{create_ocr_backend, OcrBackend, OcrBackendOptions, OcrChoice, OcrPhase};
+
+/// Frames handed to the pool ahead of the one the caller is on, per worker.
+const LOOKAHEAD_PER_WORKER: usize = 2;
+
+/// Builds one backend; called once per worker (and for the calling thread's own).
+type BackendFactory = Arc<dyn Fn() -> Result<Box<dyn OcrBackend>> + Send + Sync>;
+
+/// A frame from [`FrameScanner`], with whatever OCR a worker already ran on it.
+pub struct ScannedFrame {
+    pub frame: Frame,
+    /// Per pass (color, B/W): the worker's result, until taken.
+    passes: [Option<Result<Vec<OcrParse>>>; 2],
+}
+
+impl ScannedFrame {
+    fn unscanned(frame: Frame) -> Self {
+        ScannedFrame {
+            frame,
+            passes: [None, None],
+        }
+    }
+}
+
+/// OCR one pass over `image`: as is, or binarized for the B/W pass. The first
+/// failing engine's error is returned.
+fn ocr_pass(
+    backend: &mut dyn OcrBackend,
+    image: &DynamicImage,
+    black_and_white: bool,
+    artist: &str,
+) -> Result<Vec<OcrParse>> {
+    let bw;
+    let image = if black_and_white {
+        bw = DynamicImage::ImageLuma8(crate::image::to_black_and_white(image));
+        &bw
+    } else {
+        image
+    };
+    backend
+        .ocr_image(image, artist)
+        .into_iter()
+        .map(|candidate| candidate.map(|c| c.parse))
+        .collect()
+}
+
+/// Frames decoded in order and OCR'd either on demand or by a worker pool.
+pub struct FrameScanner {
+    frames: Box<dyn Iterator<Item = Result<Frame>>>,
+    factory: BackendFactory,
+    artist: String,
+    options: OcrBackendOptions,
+    /// The calling thread's backend: always present without a pool, created only if
+    /// needed (a frame the workers skipped) with one.
+    backend: Option<Box<dyn OcrBackend>>,
+    pool: Option<Pool>,
+}
+
+impl FrameScanner {
+    /// Scan `frames` for `artist`'s overlay with `threads` OCR workers; one thread
+    /// (or zero) OCRs on the calling thread instead. Backend construction errors
+    /// surface here.
+    pub fn new(
+        frames: Box<dyn Iterator<Item = Result<Frame>>>,
+        choice: OcrChoice,
+        phase: OcrPhase,
+        threads: usize,
+        artist: &str,
+    ) -> Result<Self> {
+        let factory: BackendFactory = Arc::new(move || create_ocr_backend(choice, phase));
+        Self::with_factory(frames, factory, threads, artist)
+    }
+
+    fn with_factory(
+        frames: Box<dyn Iterator<Item = Result<Frame>>>,
+        factory: BackendFactory,
+        threads: usize,
+        artist: &str,
+    ) -> Result<Self> {
+        let (backend, pool, options) = if threads > 1 {
+            let pool = Pool::spawn(&factory, threads, artist)?;
+            let options = pool.options;
+            (None, Some(pool), options)
+        } else {
+            let backend = factory()?;
+            let options = backend.options();
+            (Some(backend), None, options)
+        };
+        Ok(FrameScanner {
+            frames,
+            factory,
+            artist: artist.to_string(),
+            options,
+            backend,
+            pool,
+        })
+    }
+
+    pub fn options(&self) -> OcrBackendOptions {
+        self.options
+    }
+
+    /// Tell the workers the caller will skip every frame whose index is below
+    /// `frame_index`, so they need not OCR them. Only a hint: a frame the caller
+    /// does want is OCR'd by [`FrameScanner::candidates`] regardless.
+    pub fn skip_before(&self, frame_index: f64) {
+        if let Some(pool) = &self.pool {
+            pool.hints
+                .skip_before
+                .store(frame_index.to_bits(), Ordering::Relaxed);
+        }
+    }
+
+    /// Tell the workers the caller only OCRs frames whose index is a multiple of
+    /// `stride` unless something on them calls for a closer look (coarse sampling).
+    /// Also only a hint, like [`FrameScanner::skip_before`].
+    pub fn speculate_every(&self, stride: usize) {
+        if let Some(pool) = &self.pool {
+            pool.hints.stride.store(stride.max(1), Ordering::Relaxed);
+        }
+    }
+
+    /// The OCR candidates of one pass over `scanned`: the worker's, or run now.
+    /// Successful passes are kept on the frame, so asking again is free.
+    pub fn candidates(
+        &mut self,
+        scanned: &mut ScannedFrame,
+        black_and_white: bool,
+    ) -> Result<Vec<OcrParse>> {
+        let pass = usize::from(black_and_white);
+        match scanned.passes[pass].take() {
+            Some(Ok(parses)) => {
+                scanned.passes[pass] = Some(Ok(parses.clone()));
+                return Ok(parses);
+            }
+            Some(Err(e)) => return Err(e),
+            None => {}
+        }
+        let backend = match &mut self.backend {
+            Some(backend) => backend,
+            None => self.backend.insert((self.factory)()?),
+        };
+        let parses = ocr_pass(
+            backend.as_mut(),
+            &scanned.frame.image,
+            black_and_white,
+            &self.artist,
+        )?;
+        scanned.passes[pass] = Some(Ok(parses.clone()));
+        Ok(parses)
+    }
+}
+
+impl Iterator for FrameScanner {
+    type Item = Result<ScannedFrame>;
+
+    fn next(&mut self) -> Option<Result<ScannedFrame>> {
+        match &mut self.pool {
+            None => self
+                .frames
+                .next()
+                .map(|frame| frame.map(ScannedFrame::unscanned)),
+            Some(pool) => pool.next(&mut self.frames),
+        }
+    }
+}
+
+struct Job {
+    seq: usize,
+    frame: Frame,
+}
+
+/// What the caller has said it won't need; workers read these before each frame.
+struct Hints {
+    /// f64 bits of the frame index below which workers skip OCR.
+    skip_before: AtomicU64,
+    /// Workers only OCR frames whose index is a multiple of this.
+    stride: AtomicUsize,
+}
+
+impl Hints {
+    fn wants(&self, index: usize) -> bool {
+        let skip_before = f64::from_bits(self.skip_before.load(Ordering::Relaxed));
+        index as f64 >= skip_before && index.is_multiple_of(self.stride.load(Ordering::Relaxed))
+    }
+}
+
+/// OCR worker threads plus the bookkeeping that puts their results back in order.
+struct Pool {
+    jobs: Option<Sender<Job>>,
+    results: Receiver<(usize, ScannedFrame)>,
+    workers: Vec<JoinHandle<()>>,
+    hints: Arc<Hints>,
+    options: OcrBackendOptions,
+    /// Results (or the decode error) received ahead of `next_seq`.
+    pending: BTreeMap<usize, Result<ScannedFrame>>,
+    /// Sequence number of the next frame to return.
+    next_seq: usize,
+    /// Frames taken from the decoder so far (the next sequence number to assign).
+    taken: usize,
+    lookahead: usize,
+    exhausted: bool,
+}
+
+impl Pool {
+    fn spawn(factory: &BackendFactory, threads: usize, artist: &str) -> Result<Self> {
+        let (job_tx, job_rx) = channel::<Job>();
+        let job_rx = Arc::new(Mutex::new(job_rx));
+        let (result_tx, result_rx) = channel();
+        let (ready_tx, ready_rx) = channel();
+        let hints = Arc::new(Hints {
+            skip_before: AtomicU64::new(0f64.to_bits()),
+            stride: AtomicUsize::new(1),
+        });
+
+        let mut workers = Vec::with_capacity(threads);
+        for n in 0..threads {
+            let worker = Worker {
+                artist: artist.to_string(),
+                jobs: job_rx.clone(),
+                results: result_tx.clone(),
+                hints: hints.clone(),
+            };
+            let factory = factory.clone();
+            let ready = ready_tx.clone();
+            let handle = std::thread::Builder::new()
+                .name(format!("ocr-{}", n))
+                .spawn(move || worker.run(&*factory, ready))
+                .context("Failed to spawn OCR worker")?;
+            workers.push(handle);
+        }
+        drop(ready_tx);
+
+        let mut pool = Pool {
+            jobs: Some(job_tx),
+            results: result_rx,
+            workers,
+            hints,
+            options: OcrBackendOptions {
+                black_and_white: false,
+            },
+            pending: BTreeMap::new(),
+            next_seq: 0,
+            taken: 0,
+            lookahead: threads * LOOKAHEAD_PER_WORKER,
+            exhausted: false,
+        };
+        // Wait for every backend, so a model that fails to load fails the run now
+        // (dropping the pool stops the workers that did start).
+        for _ in 0..threads {
+            pool.options = ready_rx
+                .recv()
+                .map_err(|_| anyhow!("OCR worker exited during startup"))??;
+        }
+        Ok(pool)
+    }
+
+    /// Keep `lookahead` frames with the workers. A decode error takes the place of
+    /// its frame, so it surfaces only if the caller gets that far.
+    fn fill(&mut self, frames: &mut dyn Iterator<Item = Result<Frame>>) {
+        while !self.exhausted && self.taken - self.next_seq < self.lookahead {
+            let seq = self.taken;
+            match frames.next() {
+                Some(Ok(frame)) => {
+                    let sent = self
+                        .jobs
+                        .as_ref()
+                        .is_some_and(|jobs| jobs.send(Job { seq, frame }).is_ok());
+                    if !sent {
+                        self.pending
+                            .insert(seq, Err(anyhow!("OCR workers have stopped")));
+                        self.exhausted = true;
+                    }
+                }
+                Some(Err(e)) => {
+                    self.pending.insert(seq, Err(e));
+                    self.exhausted = true;
+                }
+                None => {
+                    self.exhausted = true;
+                    return;
+                }
+            }
+            self.taken += 1;
+        }
+    }
+
+    fn next(
+        &mut self,
+        frames: &mut dyn Iterator<Item = Result<Frame>>,
+    ) -> Option<Result<ScannedFrame>> {
+        self.fill(frames);
+        loop {
+            if let Some(scanned) = self.pending.remove(&self.next_seq) {
+                self.next_seq += 1;
+                return Some(scanned);
+            }
+            if self.next_seq == self.taken {
+                return None;
+            }
+            match self.results.recv() {
+                Ok((seq, scanned)) => {
+                    self.pending.insert(seq, Ok(scanned));
+                }
+                Err(_) => return Some(Err(anyhow!("OCR workers have stopped"))),
+            }
+        }
+    }
+}
+
+impl Drop for Pool {
+    /// Close the job queue and wait for the workers to finish their current frame.
+    fn drop(&mut self) {
+        self.jobs = None;
+        for worker in self.workers.drain(..) {
+            let _ = worker.join();
+        }
+    }
+}
+
+struct Worker {
+    artist: String,
+    jobs: Arc<Mutex<Receiver<Job>>>,
+    results: Sender<(usize, ScannedFrame)>,
+    hints: Arc<Hints>,
+}
+
+impl Worker {
+    fn run(
+        self,
+        factory: &(dyn Fn() -> Result<Box<dyn OcrBackend>> + Send + Sync),
+        ready: Sender<Result<OcrBackendOptions>>,
+    ) {
+        let mut backend = match factory() {
+            Ok(backend) => backend,
+            Err(e) => {
+                let _ = ready.send(Err(e));
+                return;
+            }
+        };
+        let options = backend.options();
+        let _ = ready.send(Ok(options));
+        drop(ready);
+
+        loop {
+            let Ok(Job { seq, frame }) = self.jobs.lock().unwrap().recv() else {
+                return;
+            };
+            let mut scanned = ScannedFrame::unscanned(frame);
+            if self.hints.wants(scanned.frame.index) {
+                let passes: &[bool] = if options.black_and_white {
+                    &[false, true]
+                } else {
+                    &[false]
+                };
+                for &black_and_white in passes {
+                    // A panicking engine fails this frame rather than hanging the
+                    // caller waiting for it.
+                    let result = catch_unwind(AssertUnwindSafe(|| {
+                        ocr_pass(
+                            backend.as_mut(),
+                            &scanned.frame.image,
+                            black_and_white,
+                            &self.artist,
+                        )
+                    }))
+                    .unwrap_or_else(|_| {
+                        Err(anyhow!(
+                            "OCR worker panicked on frame {}",
+                            scanned.frame.index
+                        ))
+                    });
+                    scanned.passes[usize::from(black_and_white)] = Some(result);
+                }
+            }
+            if self.results.send((seq, scanned)).is_err() {
+                return;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ocr_backend::OcrCandidate;
+    use ::image::{Rgb, RgbImage};
+    use std::sync::atomic::AtomicUsize;
+    use stringmetrics::LevWeights;
+
+    /// Reads the frame number back out of the red channel; B/W passes are tagged.
+    struct FakeBackend {
+        black_and_white: bool,
+        calls: Arc<AtomicUsize>,
+    }
+
+    impl OcrBackend for FakeBackend {
+        fn ocr_image(&mut self, image: &DynamicImage, artist: &str) -> Vec<Result<OcrCandidate>> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let parse = match image {
+                DynamicImage::ImageLuma8(_) => (vec!["bw".to_string()], false),
+                _ => {
+                    let n = image.to_rgb8().get_pixel(0, 0)[0];
+                    if n == 13 {
+                        return vec![Err(anyhow!("engine failed on 13"))];
+                    }
+                    // Finish out of order: later frames come back first.
+                    std::thread::sleep(std::time::Duration::from_millis(u64::from(8 - n % 8)));
+                    (vec![format!("{} {}", artist, n)], n.is_multiple_of(3))
+                }
+            };
+            vec![Ok(OcrCandidate {
+                parse,
+                weights: LevWeights::default(),
+            })]
+        }
+
+        fn options(&self) -> OcrBackendOptions {
+            OcrBackendOptions {
+                black_and_white: self.black_and_white,
+            }
+        }
+    }
+
+    fn factory(black_and_white: bool, calls: &Arc<AtomicUsize>) -> BackendFactory {
+        let calls = calls.clone();
+        Arc::new(move || {
+            Ok(Box::new(FakeBackend {
+                black_and_white,
+                calls: calls.clone(),
+            }) as Box<dyn OcrBackend>)
+        })
+    }
+
+    fn frames(count: usize) -> Box<dyn Iterator<Item = Result<Frame>>> {
+        Box::new((0..count).map(|index| {
+            Ok(Frame {
+                index,
+                timestamp: index as f64,
+                image: DynamicImage::ImageRgb8(RgbImage::from_pixel(
+                    2,
+                    2,
+                    Rgb([index as u8, 0, 0]),
+                )),
+            })
+        }))
+    }
+
+    /// Every frame's index and both passes' candidates, as the detection loop reads them.
+    fn scan(scanner: &mut FrameScanner) -> Vec<(usize, Vec<OcrParse>, Vec<OcrParse>)> {
+        let mut out = Vec::new();
+        while let Some(scanned) = scanner.next() {
+            let mut scanned = scanned.unwrap();
+            let color = scanner.candidates(&mut scanned, false).unwrap();
+            let bw = scanner.candidates(&mut scanned, true).unwrap();
+            out.push((scanned.frame.index, color, bw));
+        }
+        out
+    }
+
+    #[test]
+    fn pooled_scan_matches_sequential_scan_in_frame_order() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut sequential =
+            FrameScanner::with_factory(frames(12), factory(true, &calls), 1, "artist").unwrap();
+        let mut pooled =
+            FrameScanner::with_factory(frames(12), factory(true, &calls), 4, "artist").unwrap();
+        assert!(pooled.options().black_and_white);
+
+        let expected = scan(&mut sequential);
+        assert_eq!(
+            expected.iter().map(|(i, _, _)| *i).collect::<Vec<_>>(),
+            (0..12).collect::<Vec<_>>()
+        );
+        assert_eq!(expected[3].1, vec![(vec!["artist 3".to_string()], true)]);
+        assert_eq!(expected[3].2, vec![(vec!["bw".to_string()], false)]);
+        assert_eq!(scan(&mut pooled), expected);
+    }
+
+    #[test]
+    fn errors_surface_at_their_frame() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut frames = frames(20).collect::<Vec<_>>();
+        frames[16] = Err(anyhow!("decode failed at 16"));
+        let mut scanner = FrameScanner::with_factory(
+            Box::new(frames.into_iter()),
+            factory(false, &calls),
+            3,
+            "artist",
+        )
+        .unwrap();
+
+        for index in 0..13 {
+            let mut scanned = scanner.next().unwrap().unwrap();
+            assert_eq!(scanned.frame.index, index);
+            scanner.candidates(&mut scanned, false).unwrap();
+        }
+        // The engine error belongs to frame 13, the decode error comes after 15.
+        let mut scanned = scanner.next().unwrap().unwrap();
+        let error = scanner.candidates(&mut scanned, false).unwrap_err();
+        assert!(error.to_string().contains("engine failed on 13"));
+        assert_eq!(scanner.next().unwrap().unwrap().frame.index, 14);
+        assert_eq!(scanner.next().unwrap().unwrap().frame.index, 15);
+        let error = scanner.next().unwrap().err().unwrap();
+        assert!(error.to_string().contains("decode failed at 16"));
+    }
+
+    #[test]
+    fn skipped_frames_are_still_ocrd_on_demand() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut scanner =
+            FrameScanner::with_factory(frames(40), factory(false, &calls), 2, "artist").unwrap();
+        scanner.skip_before(f64::MAX);
+        // Frames already handed to the workers may have been OCR'd; the rest are not.
+        let scanned: Vec<ScannedFrame> = scanner.by_ref().map(Result::unwrap).collect();
+        assert!(calls.load(Ordering::SeqCst) < 40);
+
+        let mut last = scanned.into_iter().last().unwrap();
+        assert!(last.passes[0].is_none());
+        assert_eq!(
+            scanner.candidates(&mut last, false).unwrap(),
+            vec![(vec!["artist 39".to_string()], true)]
+        );
+    }
+
+    #[test]
+    fn workers_only_speculate_on_the_stride_and_passes_are_kept() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let mut scanner =
+            FrameScanner::with_factory(frames(12), factory(false, &calls), 2, "artist").unwrap();
+        scanner.speculate_every(4);
+        let mut scanned: Vec<ScannedFrame> = scanner.by_ref().map(Result::unwrap).collect();
+        let speculated: Vec<usize> = scanned
+            .iter()
+            .filter(|s| s.passes[0].is_some())
+            .map(|s| s.frame.index)
+            .collect();
+        assert!(speculated.iter().all(|i| i % 4 == 0), "{:?}", speculated);
+        assert!(scanned[8].passes[0].is_some());
+
+        let before = calls.load(Ordering::SeqCst);
+        let first = scanner.candidates(&mut scanned[5], false).unwrap();
+        assert_eq!(scanner.candidates(&mut scanned[5], false).unwrap(), first);
+        assert_eq!(calls.load(Ordering::SeqCst), before + 1);
+    }
+
+    #[test]
+    fn backend_startup_failure_fails_the_scanner() {
+        let failing: BackendFactory = Arc::new(|| Err(anyhow!("no model")));
+        let error = FrameScanner::with_factory(frames(1), failing, 3, "artist")
+            .err()
+            .unwrap();
+        assert_eq!(error.to_string(), "no model");
+    }
+}
@@ -0,0 +1,217 @@
+//! Poster frames and the contact sheet: a still per song for track lists and
+//! the player bar, and a grid of stills across the whole concert.
+//!
+//! Both sit flat beside the tracks: `<song>.poster.jpg` next to the song's
+//! files, and `Album.contact.jpg` for the concert. Each frame is decoded with
+//! [`decode::video_frames`] from a seek to its time, so it costs one keyframe
+//! decode rather than a pass over the video.
+
+use std::path::{Path, PathBuf};
+
+use ::image::codecs::jpeg::JpegEncoder;
+use ::image::imageops::{self, FilterType};
+use ::image::{DynamicImage, Rgb, RgbImage};
+use anyhow::{anyhow, Context, Result};
+use concert_types::{ConcertInfo, SongTimestamp};
+
+use crate::concert_split::folder_name;
+use crate::decode::{self, FrameSpec};
+use crate::io;
+
+/// Infix between a song's filename stem and the extension, e.g.
+/// `Song.poster.jpg`.
+pub const POSTER_INFIX: &str = "poster";
+
+/// Infix between the concert's folder name and the extension, e.g.
+/// `Album.contact.jpg`.
+pub const CONTACT_SHEET_INFIX: &str = "contact";
+
+/// How long after a frame its title overlay was read on the poster is taken,
+/// by which the card has usually left the screen, so the frame shows the band.
+pub const OVERLAY_CLEAR_SECONDS: f64 = 6.0;
+
+/// How far into a song without a detected overlay its poster is taken: past
+/// a title card shown from the song's start.
+pub const POSTER_OFFSET_SECONDS: f64 = 10.0;
+
+/// Width of a poster frame; the height keeps the source's aspect ratio.
+const POSTER_WIDTH: u32 = 640;
+
+/// Contact sheet grid: columns, rows and the width of each tile.
+const CONTACT_COLUMNS: u32 = 4;
+const CONTACT_ROWS: u32 = 4;
+const CONTACT_TILE_WIDTH: u32 = 320;
+
+const JPEG_QUALITY: u8 = 85;
+
+pub fn poster_file_name(title: &str) -> String {
+    format!("{}.{POSTER_INFIX}.jpg", io::sanitize_filename(title))
+}
+
+pub fn contact_sheet_name(concert: &ConcertInfo) -> String {
+    format!("{}.{CONTACT_SHEET_INFIX}.jpg", folder_name(concert))
+}
+
+/// When the poster of a song from `start` to `end` is taken: once the title
+/// overlay read at `overlay_at` has gone, or past a card at its start when none
+/// was read, but no later than the song's midpoint for a short song.
+pub fn poster_time(start: f64, end: f64, overlay_at: Option<f64>) -> f64 {
+    let clear = match overlay_at {
+        Some(at) => at.max(start) + OVERLAY_CLEAR_SECONDS,
+        None => start + POSTER_OFFSET_SECONDS,
+    };
+    clear.min(start + (end - start).max(0.0) / 2.0)
+}
+
+/// The centers of `count` equal slices of `duration`.
+pub fn contact_sheet_times(duration: f64, count: u32) -> Vec<f64> {
+    let slice = duration / f64::from(count);
+    (0..count)
+        .map(|index| slice * (f64::from(index) + 0.5))
+        .collect()
+}
+
+/// The frame size `width` pixels wide keeping a `source` (width, height)
+/// aspect ratio, with an even height as the encoders want.
+pub fn scaled_size(source: (u32, u32), width: u32) -> (u32, u32) {
+    let (source_width, source_height) = source;
+    let height = (f64::from(width) * f64::from(source_height) / f64::from(source_width.max(1)))
+        .round() as u32;
+    (width, (height / 2 * 2).max(2))
+}
+
+fn extract_frame(input_file: &str, time: f64, scale: (u32, u32)) -> Result<DynamicImage> {
+    let spec = FrameSpec {
+        start: time,
+        end: None,
+        fps: 1,
+        scale,
+        crop: None,
+        negate: false,
+    };
+    let frame = decode::video_frames(input_file, spec)?
+        .next()
+        .ok_or_else(|| anyhow!("no frame at {:.3}s of {}", time, input_file))??;
+    Ok(frame.image)
+}
+
+fn write_jpeg(image: &DynamicImage, path: &Path) -> Result<()> {
+    let mut bytes = Vec::new();
+    JpegEncoder::new_with_quality(&mut bytes, JPEG_QUALITY)
+        .encode_image(&image.to_rgb8())
+        .context("encoding a JPEG")?;
+    std::fs::write(path, bytes).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Write a poster per song of `timestamps` into `output_dir` from the
+/// `source_size` video, returning their filenames in set-list order.
+/// `overlay_at` gives the time a song's title overlay was read, if it was.
+pub fn write_posters(
+    input_file: &str,
+    output_dir: &Path,
+    source_size: (u32, u32),
+    timestamps: &[SongTimestamp],
+    overlay_at: impl Fn(&SongTimestamp) -> Option<f64>,
+) -> Result<Vec<PathBuf>> {
+    let scale = scaled_size(source_size, POSTER_WIDTH);
+    timestamps
+        .iter()
+        .map(|song| {
+            let time = poster_time(song.start_time, song.end_time, overlay_at(song));
+            let frame = extract_frame(input_file, time, scale)?;
+            let name = PathBuf::from(poster_file_name(&song.title));
+            write_jpeg(&frame, &output_dir.join(&name))?;
+            Ok(name)
+        })
+        .collect()
+}
+
+/// Lay `frames` out left to right, top to bottom, `columns` to a row. Each
+/// cell is sized to the first frame; a frame of another size is fitted into
+/// its cell.
+pub fn tile(frames: &[DynamicImage], columns: u32) -> RgbImage {
+    let Some(first) = frames.first() else {
+        return RgbImage::new(0, 0);
+    };
+    let (cell_width, cell_height) = (first.width(), first.height());
+    let rows = (frames.len() as u32).div_ceil(columns);
+    let mut sheet = RgbImage::from_pixel(cell_width * columns, cell_height * rows, Rgb([0, 0, 0]));
+    for (index, frame) in frames.iter().enumerate() {
+        let index = index as u32;
+        let cell = if (frame.width(), frame.height()) == (cell_width, cell_height) {
+            frame.to_rgb8()
+        } else {
+            frame
+                .resize(cell_width, cell_height, FilterType::Triangle)
+                .to_rgb8()
+        };
+        imageops::overlay(
+            &mut sheet,
+            &cell,
+            i64::from(index % columns * cell_width),
+            i64::from(index / columns * cell_height),
+        );
+    }
+    sheet
+}
+
+/// Write the contact sheet of the `duration`-second, `source_size` video into
+/// `output_dir`, returning its filename.
+pub fn write_contact_sheet(
+    input_file: &str,
+    output_dir: &Path,
+    concert: &ConcertInfo,
+    source_size: (u32, u32),
+    duration: f64,
+) -> Result<PathBuf> {
+    let scale = scaled_size(source_size, CONTACT_TILE_WIDTH);
+    let frames = contact_sheet_times(duration, CONTACT_COLUMNS * CONTACT_ROWS)
+        .into_iter()
+        .map(|time| extract_frame(input_file, time, scale))
+        .collect::<Result<Vec<_>>>()?;
+    let name = PathBuf::from(contact_sheet_name(concert));
+    write_jpeg(
+        &DynamicImage::ImageRgb8(tile(&frames, CONTACT_COLUMNS)),
+        &output_dir.join(&name),
+    )?;
+    Ok(name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posters_clear_the_title_card_but_stay_in_short_songs() {
+        assert_eq!(poster_time(100.0, 300.0, None), 110.0);
+        assert_eq!(poster_time(100.0, 108.0, None), 104.0);
+        assert_eq!(poster_time(100.0, 300.0, Some(103.0)), 109.0);
+        assert_eq!(poster_time(100.0, 300.0, Some(120.0)), 126.0);
+        assert_eq!(poster_time(100.0, 110.0, Some(103.0)), 105.0);
+        assert_eq!(
+            contact_sheet_times(160.0, 4),
+            vec![20.0, 60.0, 100.0, 140.0]
+        );
+    }
+
+    #[test]
+    fn frames_keep_the_source_aspect_ratio_at_an_even_height() {
+        assert_eq!(scaled_size((1920, 1080), 640), (640, 360));
+        assert_eq!(scaled_size((1280, 714), 320), (320, 178));
+        assert_eq!(scaled_size((0, 0), 320), (320, 2));
+        assert_eq!(poster_file_name("Song: One "), "Song_ One.poster.jpg");
+    }
+
+    #[test]
+    fn tile_fills_rows_and_fits_odd_sized_frames() {
+        let frame = |shade: u8, width: u32| {
+            DynamicImage::ImageRgb8(RgbImage::from_pixel(width, 2, Rgb([shade; 3])))
+        };
+        let sheet = tile(&[frame(10, 4), frame(20, 4), frame(30, 8)], 2);
+        assert_eq!(sheet.dimensions(), (8, 4));
+        assert_eq!(sheet.get_pixel(0, 0), &Rgb([10; 3]));
+        assert_eq!(sheet.get_pixel(4, 0), &Rgb([20; 3]));
+        assert_eq!(sheet.get_pixel(0, 2), &Rgb([30; 3]));
+        assert_eq!(sheet.get_pixel(4, 2), &Rgb([0; 3]));
+    }
+}
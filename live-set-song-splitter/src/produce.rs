@@ -2,14 +2,16 @@
 //! known, and writing the resulting timestamps.
 
 use crate::concert_split::{
-    ConcertSplitProgress, OutputFormat, ProducedTrack, SongSegment, TrackKind,
+    AudioFormat, ConcertSplitProgress, OutputFormat, ProducedTrack, SongSegment, TrackKind,
 };
-use crate::cut::{self, VideoCutMode};
+use crate::cut::{self, Rendition, VideoCutMode};
+use crate::tags::TagTemplate;
 use crate::{ffmpeg, io};
 use concert_types::{derive_interludes, interlude_filename_stem, ConcertInfo, Song, SongTimestamp};
 
 use anyhow::{anyhow, Result};
 use std::fs;
+use std::path::Path;
 
 /// Parameters shared by every track cut within a single splitter run. Grouping
 /// them avoids threading seven scalar args through every call.
@@ -17,9 +19,25 @@ pub(crate) struct CutContext<'a> {
     pub input_file: &'a str,
     pub output_dir: &'a str,
     pub output_format: OutputFormat,
+    /// Audio files cut per track; empty for video-only output.
+    pub audio_outputs: Vec<AudioOutput>,
     pub source_params: Option<cut::SourceVideoParams>,
     pub video_cut_mode: VideoCutMode,
+    /// Downscaled copies transcoded from each video track; already limited to
+    /// those below the source height.
+    pub renditions: Vec<Rendition>,
     pub concert: &'a ConcertInfo,
+    pub tag_template: &'a TagTemplate,
+    /// Image embedded as cover art on the video and audio tracks; `None` skips
+    /// embedding.
+    pub cover_art: Option<&'a Path>,
+}
+
+/// One audio file written per track, with its codec arguments from
+/// [`ffmpeg::audio_encoder_args`].
+pub(crate) struct AudioOutput {
+    pub format: AudioFormat,
+    pub encoder_args: Vec<String>,
 }
 
 pub(crate) enum SegmentProduction {
@@ -34,7 +52,8 @@ pub(crate) enum SegmentProduction {
 ///
 /// `stem` is the filename without extension (already sanitized).
 /// `track_number` is `Some(n)` for songs (embedded as ffmpeg metadata) and
-/// `None` for interludes.
+/// `None` for interludes. A cover-art failure is reported as a warning and
+/// leaves the tagged track in place; it never fails the cut.
 fn extract_track(
     ctx: &CutContext<'_>,
     stem: &str,
@@ -42,7 +61,12 @@ fn extract_track(
     end_time: f64,
     title: &str,
     track_number: Option<usize>,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
 ) -> Result<()> {
+    let tags =
+        ctx.tag_template
+            .render(ctx.concert, title, track_number, ctx.concert.set_list.len());
+
     match ctx.output_format {
         OutputFormat::Video | OutputFormat::Both => {
             let output_file = format!("{}/{}.mp4", ctx.output_dir, stem);
@@ -53,9 +77,7 @@ fn extract_track(
                     start_time,
                     end_time,
                     params,
-                    Some(title),
-                    ctx.concert,
-                    track_number,
+                    &tags,
                 )?,
                 None => cut::extract_segment(
                     ctx.input_file,
@@ -63,44 +85,85 @@ fn extract_track(
                     start_time,
                     end_time,
                     ctx.video_cut_mode,
-                    Some(title),
-                    ctx.concert,
-                    track_number,
+                    &tags,
                 )?,
             }
+            for &rendition in &ctx.renditions {
+                let rendition_file =
+                    format!("{}/{}.{}", ctx.output_dir, stem, rendition.extension());
+                cut::extract_rendition(&output_file, &rendition_file, rendition)?;
+            }
+            // After the renditions, which are transcoded from this file and
+            // have no use for the picture.
+            embed_cover_art(ctx, &output_file, progress);
         }
         _ => {}
     }
 
-    match ctx.output_format {
-        OutputFormat::Audio | OutputFormat::Both => {
-            let output_file = format!("{}/{}.m4a", ctx.output_dir, stem);
-            ffmpeg::extract_audio_segment(
-                ctx.input_file,
-                &output_file,
-                start_time,
-                end_time,
-                Some(title),
-                ctx.concert,
-                track_number,
-            )?;
+    for audio in &ctx.audio_outputs {
+        let output_file = format!("{}/{}.{}", ctx.output_dir, stem, audio.format.extension());
+        ffmpeg::extract_audio_segment(
+            ctx.input_file,
+            &output_file,
+            start_time,
+            end_time,
+            &audio.encoder_args,
+            &tags,
+        )?;
+        if audio.format == AudioFormat::Opus {
+            if ctx.cover_art.is_some() {
+                progress(ConcertSplitProgress::Diagnostic(format!(
+                    "cover art not embedded in {}: Ogg has no attached pictures",
+                    output_file
+                )));
+            }
+        } else {
+            embed_cover_art(ctx, &output_file, progress);
         }
-        _ => {}
     }
 
     Ok(())
 }
 
+/// Embed the run's cover art, if any, into `track_file`. A failure is a
+/// warning: the tagged track is still usable without its picture.
+fn embed_cover_art(
+    ctx: &CutContext<'_>,
+    track_file: &str,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) {
+    let Some(cover) = ctx.cover_art else {
+        return;
+    };
+    if let Err(e) = ffmpeg::embed_cover_art(track_file, cover) {
+        progress(ConcertSplitProgress::Warning(format!(
+            "cover art not embedded in {}: {}",
+            track_file, e
+        )));
+    }
+}
+
+/// Matches `interlude_NN` with any extension a track can be written with: the
+/// full-resolution video, its renditions, and every audio format.
+fn stale_interlude_pattern() -> regex::Regex {
+    let extensions: Vec<String> = std::iter::once("mp4")
+        .chain(Rendition::ALL.map(Rendition::extension))
+        .chain(AudioFormat::ALL.map(AudioFormat::extension))
+        .map(regex::escape)
+        .collect();
+    regex::Regex::new(&format!(r"^interlude_\d{{2}}\.({})$", extensions.join("|")))
+        .expect("escaped extensions form a valid regex")
+}
+
 /// Remove any previously written interlude files from `output_dir` before
 /// (re-)cutting interludes, to avoid stale orphans when the number of interludes
 /// changes.  Only files whose names match the anchored pattern
-/// `interlude_NN.mp4|.m4a` are removed.
+/// `interlude_NN` plus a video or audio track extension are removed.
 fn remove_stale_interlude_files(
     output_dir: &str,
     progress: &mut dyn FnMut(ConcertSplitProgress),
 ) -> Result<()> {
-    let pattern =
-        regex::Regex::new(r"^interlude_\d{2}\.(mp4|m4a)$").expect("static regex is valid");
+    let pattern = stale_interlude_pattern();
     let dir = match fs::read_dir(output_dir) {
         Ok(d) => d,
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(()),
@@ -164,6 +227,7 @@ pub(crate) fn create_song_timestamps(
             start_time: segment.segment.start_time,
             end_time: segment.segment.end_time,
             duration: segment.segment.end_time - segment.segment.start_time,
+            boundary: Some(segment.boundary),
         });
     }
 
@@ -260,6 +324,7 @@ pub(crate) fn process_segments(
             segment.segment.end_time,
             song_title,
             Some(song_counter),
+            progress,
         ) {
             return SegmentProduction::Failed {
                 completed_tracks: tracks,
@@ -277,6 +342,7 @@ pub(crate) fn process_segments(
             kind: TrackKind::Song,
             start_time: segment.segment.start_time,
             end_time: segment.segment.end_time,
+            label: None,
         });
     }
 
@@ -313,6 +379,7 @@ pub(crate) fn process_segments(
                 interlude.end_time,
                 "interlude",
                 None, // no track number for interludes
+                progress,
             ) {
                 return SegmentProduction::Failed {
                     completed_tracks: tracks,
@@ -330,6 +397,14 @@ pub(crate) fn process_segments(
                 kind: TrackKind::Interlude,
                 start_time: interlude.start_time,
                 end_time: interlude.end_time,
+                // Labeled by the run before cutting (see `ConcertInfo::interludes`).
+                label: ctx
+                    .concert
+                    .interludes
+                    .iter()
+                    .flatten()
+                    .find(|labeled| labeled.index == interlude.index)
+                    .and_then(|labeled| labeled.label),
             });
         }
     }
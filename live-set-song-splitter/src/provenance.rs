@@ -0,0 +1,92 @@
+//! Confidence scores for song boundaries, by how each boundary was found.
+//!
+//! The scores are a fixed ranking of the evidence rather than a calibrated
+//! probability: an exact overlay match outranks a fuzzy one, which outranks a
+//! silence, which outranks dividing a gap equally. Anything below
+//! [`concert_types::LOW_CONFIDENCE`] is flagged for review in concert-tracker.
+
+use crate::ocr::MatchReason;
+use concert_types::{BoundaryProvenance, BoundaryRefinement, BoundarySource};
+
+/// Confidence of a silence boundary taken from the quietest window because no
+/// real silence was long enough (see `set_list_free`).
+pub(crate) const QUIETEST_POINT_CONFIDENCE: f64 = 0.3;
+
+/// The default confidence for a boundary found by `source`.
+pub(crate) fn from_source(source: BoundarySource) -> BoundaryProvenance {
+    let confidence = match source {
+        BoundarySource::RecordingStart | BoundarySource::Supplied => 1.0,
+        BoundarySource::Overlay => 0.95,
+        BoundarySource::FuzzyOverlay => 0.8,
+        BoundarySource::OverlayCluster => 0.7,
+        BoundarySource::Silence => 0.6,
+        BoundarySource::TitleOnly | BoundarySource::Crowd => 0.5,
+        BoundarySource::EqualSplit => 0.2,
+    };
+    BoundaryProvenance::new(source, confidence)
+}
+
+/// A boundary from an overlay whose title matched the set list for `reason`.
+/// A prefix match or an edit distance is a fuzzy match, scored lower the
+/// further the text was from the title.
+pub(crate) fn from_overlay_match(reason: &MatchReason) -> BoundaryProvenance {
+    match reason {
+        MatchReason::Contains | MatchReason::Levenshtein(0) => from_source(BoundarySource::Overlay),
+        MatchReason::StartsWith => from_source(BoundarySource::FuzzyOverlay),
+        MatchReason::Levenshtein(dist) => BoundaryProvenance::new(
+            BoundarySource::FuzzyOverlay,
+            (0.9 - 0.05 * *dist as f64).max(0.65),
+        ),
+    }
+}
+
+/// Record `refinement` on `boundary`. Snapping to a silence or to the end of
+/// the crowd corroborates the start from the audio; the blind overlay-delay
+/// pullback makes it less certain.
+pub(crate) fn refined(
+    boundary: BoundaryProvenance,
+    refinement: BoundaryRefinement,
+) -> BoundaryProvenance {
+    let adjustment = match refinement {
+        BoundaryRefinement::SnappedToSilence => 0.1,
+        BoundaryRefinement::AfterCrowd => 0.05,
+        BoundaryRefinement::PulledBack => -0.1,
+    };
+    BoundaryProvenance {
+        confidence: (boundary.confidence + adjustment).clamp(0.0, 1.0),
+        refinement: Some(refinement),
+        ..boundary
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fuzzy_overlay_matches_rank_below_exact_ones() {
+        let exact = from_overlay_match(&MatchReason::Contains);
+        let close = from_overlay_match(&MatchReason::Levenshtein(1));
+        let far = from_overlay_match(&MatchReason::Levenshtein(9));
+        assert_eq!(exact.source, BoundarySource::Overlay);
+        assert_eq!(close.source, BoundarySource::FuzzyOverlay);
+        assert!(exact.confidence > close.confidence);
+        assert!(close.confidence > far.confidence);
+        assert!(!far.is_low_confidence());
+        assert!(from_source(BoundarySource::EqualSplit).is_low_confidence());
+    }
+
+    #[test]
+    fn refinement_adjusts_confidence_within_bounds() {
+        let overlay = from_source(BoundarySource::Overlay);
+        let snapped = refined(overlay, BoundaryRefinement::SnappedToSilence);
+        assert_eq!(snapped.confidence, 1.0);
+        assert_eq!(
+            snapped.refinement,
+            Some(BoundaryRefinement::SnappedToSilence)
+        );
+        assert_eq!(snapped.source, BoundarySource::Overlay);
+        let pulled = refined(overlay, BoundaryRefinement::PulledBack);
+        assert!(pulled.confidence < overlay.confidence);
+    }
+}
@@ -67,6 +67,44 @@ const LOCK_POLL_INTERVAL: Duration = Duration::from_millis(25);
 #[derive(Clone, Debug, Deserialize, Serialize)]
 struct PublishedManifest {
     files: BTreeSet<PathBuf>,
+    /// Heights of the downscaled video renditions published beside the tracks.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    renditions: Vec<u32>,
+}
+
+impl PublishedManifest {
+    fn new(files: BTreeSet<PathBuf>) -> Self {
+        let mut renditions: Vec<u32> = files
+            .iter()
+            .filter_map(|relative| rendition_height(relative))
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        renditions.reverse();
+        PublishedManifest { files, renditions }
+    }
+}
+
+fn rendition_height(relative: &Path) -> Option<u32> {
+    let name = relative.file_name()?.to_str()?;
+    let stem = name.strip_suffix("p.mp4")?;
+    let (_, height) = stem.rsplit_once('.')?;
+    height.parse().ok()
+}
+
+/// Heights of the video renditions recorded in the Published Concert Split
+/// manifest, tallest first. Empty when nothing is published.
+pub fn published_renditions(canonical_dir: &Path) -> Result<Vec<u32>> {
+    let path = canonical_dir.join(MANIFEST_NAME);
+    match fs::read(&path) {
+        Ok(bytes) => {
+            let manifest: PublishedManifest = serde_json::from_slice(&bytes)
+                .with_context(|| format!("could not parse {}", path.display()))?;
+            Ok(manifest.renditions)
+        }
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(error) => Err(error).with_context(|| format!("could not read {}", path.display())),
+    }
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize)]
@@ -308,7 +346,8 @@ fn read_manifest(
             Ok(manifest)
         }
         Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
-            let interlude = regex::Regex::new(r"^interlude_\d{2}\.(mp4|m4a)$")?;
+            let interlude =
+                regex::Regex::new(r"^interlude_\d{2}\.((\d+p\.)?mp4|m4a|flac|opus|mp3)$")?;
             let mut files: BTreeSet<PathBuf> = replacement
                 .iter()
                 .filter(|relative| canonical_dir.join(relative).is_file())
@@ -324,7 +363,7 @@ fn read_manifest(
                     files.insert(PathBuf::from(name));
                 }
             }
-            Ok(PublishedManifest { files })
+            Ok(PublishedManifest::new(files))
         }
         Err(error) => Err(error).with_context(|| format!("could not read {}", path.display())),
     }
@@ -515,9 +554,7 @@ fn finish_journaled_publication(canonical_dir: &Path, journal: &PublicationJourn
             sync_directory(canonical_dir)?;
         }
     }
-    let manifest = PublishedManifest {
-        files: journal.replacement_files.clone(),
-    };
+    let manifest = PublishedManifest::new(journal.replacement_files.clone());
     #[cfg(test)]
     maybe_fail(FailurePoint::ManifestInstall)?;
     write_json_atomically(
@@ -551,9 +588,7 @@ fn restore_journaled_prior(canonical_dir: &Path, journal: &PublicationJournal) -
             write_json_atomically(
                 &canonical_dir.join(MANIFEST_NAME),
                 &canonical_dir.join(MANIFEST_TEMP_NAME),
-                &PublishedManifest {
-                    files: files.clone(),
-                },
+                &PublishedManifest::new(files.clone()),
             )?;
             files.clone()
         }
@@ -835,9 +870,7 @@ pub fn publish(request: &PublicationRequest) -> Result<()> {
         "Published and Recoverable Partial Split manifests both exist"
     );
     let prior = if partial_prior.is_some() {
-        PublishedManifest {
-            files: BTreeSet::new(),
-        }
+        PublishedManifest::new(BTreeSet::new())
     } else {
         read_manifest(&request.canonical_dir, &replacement)?
     };
@@ -1221,6 +1254,33 @@ mod tests {
         assert!(manifest.contains("First.m4a"));
         assert!(manifest.contains("Second.m4a"));
         assert!(!canonical.join(BACKUP_DIR_NAME).exists());
+        assert!(published_renditions(&canonical).unwrap().is_empty());
+    }
+
+    #[test]
+    fn manifest_records_published_rendition_heights() {
+        let tmp = tempfile::tempdir().unwrap();
+        let canonical = tmp.path().join("concert");
+        let staging = tmp.path().join("staging");
+        let files = [
+            "First.mp4",
+            "First.480p.mp4",
+            "First.720p.mp4",
+            "interlude_01.mp4",
+            "interlude_01.480p.mp4",
+        ];
+        for file in files {
+            write(&staging.join(file), file.as_bytes());
+        }
+
+        publish(&PublicationRequest {
+            canonical_dir: canonical.clone(),
+            staging_dir: staging,
+            replacement_files: files.iter().map(PathBuf::from).collect(),
+        })
+        .unwrap();
+
+        assert_eq!(published_renditions(&canonical).unwrap(), vec![720, 480]);
     }
 
     #[test]
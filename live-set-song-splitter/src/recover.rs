@@ -1,8 +1,8 @@
 //! Silence-based recovery for songs that text-overlay detection missed.
 
-use crate::audio;
 use crate::concert_split::{AudioSegment, ConcertSplitProgress, SongSegment};
-use concert_types::Song;
+use crate::{audio, classify, provenance};
+use concert_types::{BoundaryProvenance, BoundarySource, Song};
 
 /// Status of each expected song after recovery, in set-list order.
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -33,10 +33,24 @@ enum RecoverySource {
     Overlay,
     /// The midpoint of an audio silence span inside the gap.
     Silence,
+    /// Where music resumes after applause or talk (see [`classify::music_onsets`]):
+    /// a song start without a silence before it.
+    Crowd,
     /// No candidate available; the gap was equally divided.
     EqualSplit,
 }
 
+impl RecoverySource {
+    fn boundary(self) -> BoundaryProvenance {
+        provenance::from_source(match self {
+            RecoverySource::Overlay => BoundarySource::OverlayCluster,
+            RecoverySource::Silence => BoundarySource::Silence,
+            RecoverySource::Crowd => BoundarySource::Crowd,
+            RecoverySource::EqualSplit => BoundarySource::EqualSplit,
+        })
+    }
+}
+
 /// Fill still-empty (`None`) slots of `chosen` from `candidates`, assigning each
 /// candidate to the empty slot whose `expected` position it is closest to (iterating
 /// slots in order, matching the original silence-only behavior). Enforces
@@ -98,7 +112,8 @@ pub(crate) fn candidates_in_gap(candidates: &[f64], gap_start: f64, gap_end: f64
 /// order of preference, from (1) `overlay_clusters` — frames where the artist
 /// overlay was detected but the (short/stylized) title was unreadable, so the song
 /// was dropped from text detection — then (2) the longest audio silences in the
-/// gap, and finally (3) equal-spacing the gap when neither is available.
+/// gap, then (3) points where music resumes after applause or talk, and finally
+/// (4) equal-spacing the gap when none is available.
 ///
 /// Overlay clusters are by far the most reliable signal (they mark where a real
 /// title card appeared), so they win over silence within a gap. This is what
@@ -109,6 +124,9 @@ pub(crate) fn candidates_in_gap(candidates: &[f64], gap_start: f64, gap_end: f64
 /// the last) are not recovered here — the head case is handled separately by
 /// `first_song_missing_fallback`, and the tail case is out of scope.
 ///
+/// `sound_spans` is [`classify::classify`] of `audio_data`, computed once by
+/// the caller and shared with refinement.
+///
 /// Returns one `RecoveryResult` per song in `set_list` order so the caller can
 /// build the still-missing list.
 pub(crate) fn recover_missing_songs(
@@ -116,6 +134,7 @@ pub(crate) fn recover_missing_songs(
     set_list: &[Song],
     overlay_clusters: &[f64],
     audio_data: &[f32],
+    sound_spans: &[classify::SoundSpan],
     progress: &mut dyn FnMut(ConcertSplitProgress),
 ) -> Vec<RecoveryResult> {
     let mut results: Vec<RecoveryResult> = set_list
@@ -137,6 +156,7 @@ pub(crate) fn recover_missing_songs(
     let threshold = adaptive_silence_threshold(&energy_profile);
     let silence_spans = audio::find_silence_spans(&energy_profile, threshold);
     let silence_midpoints: Vec<f64> = silence_spans.iter().map(|s| s.midpoint_seconds).collect();
+    let crowd_onsets = classify::music_onsets(sound_spans);
 
     let mut i = 0;
     while i < set_list.len() {
@@ -182,8 +202,9 @@ pub(crate) fn recover_missing_songs(
             .map(|slot| gap_start + ((slot + 1) as f64) * gap_size / ((missing_count + 1) as f64))
             .collect();
 
-        // Two-tier candidate selection: prefer overlay clusters (a real title card
-        // appeared there), then audio silences. Each tier fills only still-empty
+        // Tiered candidate selection: prefer overlay clusters (a real title card
+        // appeared there), then audio silences, then music resuming after the
+        // crowd. Each tier fills only still-empty
         // slots and respects spacing against boundaries an earlier tier chose.
         let mut chosen: Vec<Option<(f64, RecoverySource)>> = vec![None; missing_count];
         fill_slots_by_proximity(
@@ -198,13 +219,19 @@ pub(crate) fn recover_missing_songs(
             candidates_in_gap(&silence_midpoints, gap_start, gap_end),
             RecoverySource::Silence,
         );
+        fill_slots_by_proximity(
+            &mut chosen,
+            &expected,
+            candidates_in_gap(&crowd_onsets, gap_start, gap_end),
+            RecoverySource::Crowd,
+        );
 
         let unfilled_count = chosen.iter().filter(|c| c.is_none()).count();
         if unfilled_count > 0 {
             let missing_titles: Vec<&str> =
                 (i..=run_end).map(|j| set_list[j].title.as_str()).collect();
             progress(ConcertSplitProgress::Warning(format!(
-                "overlay/silence recovery only filled {}/{} boundaries in gap {:.2}s–{:.2}s; equally spacing remaining songs: {:?}",
+                "overlay/silence/crowd recovery only filled {}/{} boundaries in gap {:.2}s–{:.2}s; equally spacing remaining songs: {:?}",
                 missing_count - unfilled_count,
                 missing_count,
                 gap_start,
@@ -246,6 +273,7 @@ pub(crate) fn recover_missing_songs(
             let source_label = match source {
                 RecoverySource::Overlay => "title overlay",
                 RecoverySource::Silence => "audio silence",
+                RecoverySource::Crowd => "end of applause/talk",
                 RecoverySource::EqualSplit => "equal-split",
             };
             progress(ConcertSplitProgress::Diagnostic(format!(
@@ -265,8 +293,11 @@ pub(crate) fn recover_missing_songs(
                 },
                 // An overlay-sourced boundary is an overlay estimate (~OVERLAY_DELAY
                 // late), so it gets the same audio pullback as a detected overlay.
-                // Silence and equal-split boundaries are not overlay estimates.
+                // Silence, crowd and equal-split boundaries are not overlay estimates.
                 start_from_overlay: source == RecoverySource::Overlay,
+                // The cluster's first frame showed the overlay.
+                overlay_at: (source == RecoverySource::Overlay).then_some(start_time),
+                boundary: source.boundary(),
             });
             results[song_idx] = RecoveryResult::Recovered;
         }
@@ -324,6 +355,8 @@ mod tests_recover_missing_songs {
                 is_song: true,
             },
             start_from_overlay: false,
+            overlay_at: None,
+            boundary: provenance::from_source(BoundarySource::Overlay),
         }
     }
 
@@ -364,7 +397,14 @@ mod tests_recover_missing_songs {
         ]);
         let set_list = songs(&["A", "B"]);
         let mut segments = vec![segment("A", 0.0), segment("B", 60.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
 
         // Both songs reported as already-found (we seeded both), so nothing to do.
         assert_eq!(results, vec![RecoveryResult::AlreadyFound; 2]);
@@ -372,7 +412,14 @@ mod tests_recover_missing_songs {
         // Now drop B and put a missing song between them.
         let set_list = songs(&["A", "B", "C"]);
         let mut segments = vec![segment("A", 0.0), segment("C", 60.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(
             results,
             vec![
@@ -390,6 +437,43 @@ mod tests_recover_missing_songs {
         );
     }
 
+    #[test]
+    fn recovers_where_music_resumes_after_applause_without_a_silence() {
+        // Song A, then 6s of applause (broadband noise) straight into the missing
+        // song B at ~34s: no silence anywhere in the gap.
+        let mut audio = synth_audio(&[(28.0, false)]);
+        let mut state: u32 = 0x2545_f491;
+        audio.extend((0..(6.0 * audio::SAMPLE_RATE as f64) as usize).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            0.3 * (state as f32 / u32::MAX as f32 * 2.0 - 1.0)
+        }));
+        audio.extend(synth_audio(&[(26.0, false)]));
+
+        let set_list = songs(&["A", "B", "C"]);
+        let mut segments = vec![segment("A", 0.0), segment("C", 60.0)];
+        let mut diagnostics = Vec::new();
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |e| diagnostics.push(format!("{e:?}")),
+        );
+        assert_eq!(results[1], RecoveryResult::Recovered);
+        let b = segments.iter().find(|s| s.song.title == "B").unwrap();
+        assert!(
+            (b.segment.start_time - 34.0).abs() < 1.5,
+            "B placed at {:.2}s",
+            b.segment.start_time
+        );
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.contains("end of applause/talk")));
+    }
+
     #[test]
     fn k1_picks_silence_closest_to_expected_midpoint() {
         // Two silences in a 100s gap. Gap midpoint is 50s. A silence sits at
@@ -410,7 +494,14 @@ mod tests_recover_missing_songs {
         // gap_start=0, gap_end=100, expected midpoint=50.
         let set_list = songs(&["A", "B", "C"]);
         let mut segments = vec![segment("A", 0.0), segment("C", 100.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results[1], RecoveryResult::Recovered);
         let b = segments.iter().find(|s| s.song.title == "B").unwrap();
         assert!(
@@ -434,7 +525,14 @@ mod tests_recover_missing_songs {
         ]);
         let set_list = songs(&["A", "B", "C", "D"]);
         let mut segments = vec![segment("A", 0.0), segment("D", 105.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results[1], RecoveryResult::Recovered);
         assert_eq!(results[2], RecoveryResult::Recovered);
 
@@ -463,7 +561,14 @@ mod tests_recover_missing_songs {
         ]);
         let set_list = songs(&["A", "B", "C", "D"]);
         let mut segments = vec![segment("A", 0.0), segment("D", 200.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         // Both B and C should be recovered, but C via equal-split since the
         // second silence is within MIN_SONG_GAP_SECONDS=20s of the first.
         assert_eq!(results[1], RecoveryResult::Recovered);
@@ -485,7 +590,14 @@ mod tests_recover_missing_songs {
         let audio = synth_audio(&[(60.0, false)]);
         let set_list = songs(&["A", "B", "C"]);
         let mut segments = vec![segment("A", 0.0), segment("C", 60.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results[1], RecoveryResult::Recovered);
         let b = segments.iter().find(|s| s.song.title == "B").unwrap();
         // Equal split between 0 and 60 puts B at 30.
@@ -498,7 +610,14 @@ mod tests_recover_missing_songs {
         let set_list = songs(&["A", "B"]);
         // B is found at 30s but A is missing — no anchor before A.
         let mut segments = vec![segment("B", 30.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results[0], RecoveryResult::StillMissing);
         assert_eq!(results[1], RecoveryResult::AlreadyFound);
         assert_eq!(
@@ -513,7 +632,14 @@ mod tests_recover_missing_songs {
         let audio = synth_audio(&[(60.0, false)]);
         let set_list = songs(&["A", "B"]);
         let mut segments = vec![segment("A", 0.0)];
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results[0], RecoveryResult::AlreadyFound);
         assert_eq!(results[1], RecoveryResult::StillMissing);
         assert_eq!(segments.len(), 1);
@@ -525,7 +651,14 @@ mod tests_recover_missing_songs {
         let set_list = songs(&["A", "B"]);
         let mut segments = vec![segment("A", 0.0), segment("B", 30.0)];
         let before = segments.clone();
-        let results = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results, vec![RecoveryResult::AlreadyFound; 2]);
         assert_eq!(segments.len(), before.len());
         for (a, b) in segments.iter().zip(before.iter()) {
@@ -538,7 +671,14 @@ mod tests_recover_missing_songs {
         let audio = synth_audio(&[(10.0, false), (5.0, true), (15.0, false)]);
         let set_list = songs(&["A", "B", "C"]);
         let mut segments = vec![segment("A", 0.0), segment("C", 30.0)];
-        let _ = recover_missing_songs(&mut segments, &set_list, &[], &audio, &mut |_| {});
+        let _ = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
 
         // After recovery, segments should be sorted by start_time and chained:
         // A.end == B.start, B.end == C.start.
@@ -583,8 +723,14 @@ mod tests_recover_missing_songs {
         let audio = synth_audio(&[(498.0, false), (4.0, true), (98.0, false)]); // silence ~500s
         let set_list = songs(&["A", "B", "C"]);
         let mut segments = vec![segment("A", 0.0), segment("C", 600.0)];
-        let results =
-            recover_missing_songs(&mut segments, &set_list, &[200.0], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[200.0],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results[1], RecoveryResult::Recovered);
         let b = segments.iter().find(|s| s.song.title == "B").unwrap();
         assert!(
@@ -597,6 +743,7 @@ mod tests_recover_missing_songs {
             b.start_from_overlay,
             "overlay-recovered song must be marked start_from_overlay"
         );
+        assert_eq!(b.boundary.source, BoundarySource::OverlayCluster);
     }
 
     #[test]
@@ -607,8 +754,14 @@ mod tests_recover_missing_songs {
         let set_list = songs(&["A", "B", "C"]);
         let mut segments = vec![segment("A", 0.0), segment("C", 600.0)];
         // Cluster at 800s is past C — irrelevant to B's gap (0,600).
-        let results =
-            recover_missing_songs(&mut segments, &set_list, &[800.0], &audio, &mut |_| {});
+        let results = recover_missing_songs(
+            &mut segments,
+            &set_list,
+            &[800.0],
+            &audio,
+            &classify::classify(&audio),
+            &mut |_| {},
+        );
         assert_eq!(results[1], RecoveryResult::Recovered);
         let b = segments.iter().find(|s| s.song.title == "B").unwrap();
         assert!(
@@ -620,6 +773,7 @@ mod tests_recover_missing_songs {
             !b.start_from_overlay,
             "silence-recovered song must not be marked start_from_overlay"
         );
+        assert_eq!(b.boundary.source, BoundarySource::Silence);
     }
 
     #[test]
@@ -1,12 +1,14 @@
 //! Audio-analysis refinement of detected/recovered song boundaries.
 
+use crate::classify;
 use crate::concert_split::{ConcertSplitProgress, SongSegment};
-use crate::detect::frame_number_from_image_filename;
+use crate::decode::FrameSpec;
+use crate::detect::Settings;
 use crate::recover::adaptive_silence_threshold;
-use crate::{audio, ffmpeg, io};
+use crate::{audio, provenance, video::VideoInfo};
+use concert_types::BoundaryRefinement;
 
-use anyhow::{anyhow, Result};
-use std::fs;
+use anyhow::{Context, Result};
 
 /// The title overlay typically appears this many seconds AFTER the song actually
 /// starts, so an overlay-derived start sits ~this late. When audio silence can't
@@ -17,11 +19,18 @@ const OVERLAY_DELAY_SECONDS: f64 = 3.0;
 /// (Distinct from `OVERLAY_DELAY_SECONDS`, which happens to share the value today.)
 const SILENCE_LOOKBACK_SECONDS: f64 = 3.0;
 
+/// How far back from a detected start to look for music resuming after applause
+/// or talk. Wider than the silence look-back: the crowd can carry on for a while
+/// before the band starts, and the title overlay follows the start.
+const CROWD_LOOKBACK_SECONDS: f64 = 8.0;
+
 /// Outcome of refining a single song's start time.
 #[derive(Debug, PartialEq)]
 enum StartRefinement {
     /// Snap back to a real audio silence at this time.
     Snapped(f64),
+    /// Snap back to where music resumes after applause or talk.
+    AfterCrowd(f64),
     /// No silence; pull back to this best-effort start to undo the overlay delay.
     PulledBack(f64),
     /// Leave the start at the originally detected time.
@@ -31,33 +40,51 @@ enum StartRefinement {
 /// Decide a song's refined start time.
 ///
 /// `nearby_silence` are silence midpoints already filtered to the look-back window
-/// `[song_start - SILENCE_LOOKBACK_SECONDS, song_start)`. `prev_song_start` is the
-/// previous song's start (None when the previous segment is a gap or absent); the
-/// pullback is clamped so it can't shrink the previous song below
-/// `audio::MIN_SONG_GAP_SECONDS`. `allow_overlay_pullback` is true only for
-/// overlay-derived starts — recovered/silence-placed starts must not be pulled back.
+/// `[song_start - SILENCE_LOOKBACK_SECONDS, song_start)`, and `crowd_onsets` the
+/// [`classify::music_onsets`] in `[song_start - CROWD_LOOKBACK_SECONDS, song_start)`.
+/// `prev_song_start` is the previous song's start (None when the previous segment
+/// is a gap or absent); the pullback and the after-crowd start are clamped so they
+/// can't shrink the previous song below `audio::MIN_SONG_GAP_SECONDS`.
+/// `allow_overlay_pullback` is true only for overlay-derived starts —
+/// recovered/silence-placed starts must not be pulled back, and only move to a
+/// crowd onset that follows a real silence.
 fn refine_start(
     song_start: f64,
     prev_song_start: Option<f64>,
     nearby_silence: &[f64],
+    crowd_onsets: &[f64],
     allow_overlay_pullback: bool,
 ) -> StartRefinement {
+    // Music resuming after the crowd is where the song starts; a silence before
+    // that onset would leave the applause or talk at the head of this song
+    // instead of the tail of the previous one.
+    let latest_silence = nearby_silence.iter().copied().max_by(|a, b| a.total_cmp(b));
+    let floor = prev_song_start
+        .map(|p| p + audio::MIN_SONG_GAP_SECONDS)
+        .unwrap_or(0.0);
+    if let Some(onset) = crowd_onsets.iter().copied().max_by(|a, b| a.total_cmp(b)) {
+        let after_silence = latest_silence.is_none_or(|silence| onset > silence);
+        if after_silence && (allow_overlay_pullback || latest_silence.is_some()) {
+            let new_start = onset.max(floor);
+            if new_start < song_start {
+                return StartRefinement::AfterCrowd(new_start);
+            }
+        }
+    }
+
     // Prefer snapping to the latest real silence in the window. A detected silence
     // is hard evidence of a real boundary, so — unlike the speculative pullback
     // below — it is intentionally NOT floor-clamped against the previous song's
     // length: we trust the audio over the min-length heuristic. (In practice the
     // window is only SILENCE_LOOKBACK_SECONDS wide, so a snap can't move the start
     // far anyway.)
-    if let Some(&silence) = nearby_silence.iter().max_by(|a, b| a.total_cmp(b)) {
+    if let Some(silence) = latest_silence {
         return StartRefinement::Snapped(silence);
     }
 
     // No silence to snap to: for an overlay-derived start, pull back by the overlay
     // delay, but not so far that the previous song drops below the minimum length.
     if allow_overlay_pullback {
-        let floor = prev_song_start
-            .map(|p| p + audio::MIN_SONG_GAP_SECONDS)
-            .unwrap_or(0.0);
         let new_start = (song_start - OVERLAY_DELAY_SECONDS).max(floor);
         if new_start < song_start {
             return StartRefinement::PulledBack(new_start);
@@ -67,9 +94,11 @@ fn refine_start(
     StartRefinement::Unchanged
 }
 
+/// `sound_spans` is [`classify::classify`] of `audio_data`, as recovery saw it.
 pub(crate) fn refine_segments_with_audio_analysis(
     segments: &[SongSegment],
     audio_data: &[f32],
+    sound_spans: &[classify::SoundSpan],
     total_duration: f64,
     progress: &mut dyn FnMut(ConcertSplitProgress),
 ) -> Result<Vec<SongSegment>> {
@@ -92,9 +121,12 @@ pub(crate) fn refine_segments_with_audio_analysis(
     let silence_spans = audio::find_silence_spans(&energy_profile, threshold);
     let silence_timestamps: Vec<f64> = silence_spans.iter().map(|s| s.midpoint_seconds).collect();
 
+    let onsets = classify::music_onsets(sound_spans);
+
     progress(ConcertSplitProgress::Diagnostic(format!(
-        "Found {} potential silence points for refinement",
-        silence_timestamps.len()
+        "Found {} potential silence points and {} ends of applause or talk for refinement",
+        silence_timestamps.len(),
+        onsets.len()
     )));
 
     // Create refined segments
@@ -116,6 +148,11 @@ pub(crate) fn refine_segments_with_audio_analysis(
             .filter(|&&ts| ts >= search_start && ts < song_start)
             .cloned()
             .collect();
+        let crowd_onsets: Vec<f64> = onsets
+            .iter()
+            .copied()
+            .filter(|&ts| ts >= song_start - CROWD_LOOKBACK_SECONDS && ts < song_start)
+            .collect();
 
         // Previous (already-finalized) segment's start, only if it is a song.
         let prev_song_start = refined_segments
@@ -128,6 +165,7 @@ pub(crate) fn refine_segments_with_audio_analysis(
             song_start,
             prev_song_start,
             &nearby_silence,
+            &crowd_onsets,
             segment.start_from_overlay,
         ) {
             StartRefinement::Snapped(t) => {
@@ -138,7 +176,17 @@ pub(crate) fn refine_segments_with_audio_analysis(
                     t,
                     song_start - t
                 )));
-                Some(t)
+                Some((t, BoundaryRefinement::SnappedToSilence))
+            }
+            StartRefinement::AfterCrowd(t) => {
+                progress(ConcertSplitProgress::Diagnostic(format!(
+                    "Refined song {} start: music resumes after applause/talk, {:.2}s -> {:.2}s (-{:.2}s)",
+                    i,
+                    song_start,
+                    t,
+                    song_start - t
+                )));
+                Some((t, BoundaryRefinement::AfterCrowd))
             }
             StartRefinement::PulledBack(t) => {
                 progress(ConcertSplitProgress::Diagnostic(format!(
@@ -148,13 +196,14 @@ pub(crate) fn refine_segments_with_audio_analysis(
                     song_start,
                     t
                 )));
-                Some(t)
+                Some((t, BoundaryRefinement::PulledBack))
             }
             StartRefinement::Unchanged => None,
         };
 
-        if let Some(new_start) = new_start {
+        if let Some((new_start, refinement)) = new_start {
             refined.segment.start_time = new_start;
+            refined.boundary = provenance::refined(refined.boundary, refinement);
             // Keep the previous song's end chained to this start.
             if let Some(prev) = refined_segments.last_mut() {
                 if prev.segment.is_song {
@@ -176,8 +225,8 @@ pub(crate) fn refine_segments_with_audio_analysis(
 pub(crate) fn refine_last_song_end_time(
     input_file: &str,
     segments: Vec<SongSegment>,
-    total_duration: f64,
-    reuse_frames: bool,
+    video_info: &VideoInfo,
+    settings: &Settings,
     temp_dir: &str,
     progress: &mut dyn FnMut(ConcertSplitProgress),
 ) -> Result<Vec<SongSegment>> {
@@ -193,7 +242,7 @@ pub(crate) fn refine_last_song_end_time(
 
         // Try to find a black frame to use as the end time
         if let Some(black_frame_time) =
-            find_black_frame_end_time(input_file, total_duration, reuse_frames, temp_dir, progress)?
+            find_black_frame_end_time(input_file, video_info, settings, temp_dir, progress)?
         {
             progress(ConcertSplitProgress::Diagnostic(format!(
                 "Adjusted last song end time from {:.2}s to {:.2}s (found black frame)",
@@ -208,8 +257,8 @@ pub(crate) fn refine_last_song_end_time(
 
 fn find_black_frame_end_time(
     input_file: &str,
-    total_duration: f64,
-    reuse_frames: bool,
+    video_info: &VideoInfo,
+    settings: &Settings,
     temp_dir: &str,
     progress: &mut dyn FnMut(ConcertSplitProgress),
 ) -> Result<Option<f64>> {
@@ -219,104 +268,49 @@ fn find_black_frame_end_time(
 
     // Define the search window (last 40 seconds)
     let search_duration = 40.0;
-    let search_start = (total_duration - search_duration).max(0.0);
-    let temp_dir = format!("{}/end_frames", temp_dir);
-
-    if reuse_frames {
-        progress(ConcertSplitProgress::Diagnostic(format!(
-            "Reusing existing end frames from {} for black frame detection",
-            temp_dir
-        )));
-    } else {
-        io::ensure_dir(&temp_dir)?;
-        // Only overwrite directory if not reusing frames or if no frames exist
-        io::overwrite_dir(&temp_dir)?;
-
-        // Extract frames at full framerate for the last 40 seconds
-        let mut ffmpeg = ffmpeg::create_ffmpeg_command();
-        ffmpeg
-            .time_range(search_start, total_duration)
-            .args(["-i", input_file])
-            .png()
-            .args([
-                "-frame_pts",
-                "1",
-                "-fps_mode",
-                "passthrough", // Use original timestamps
-            ])
-            .video_filter(&format!("{}/%d.png", temp_dir), vec!["scale=200:100"]);
-        let status = ffmpeg
-            .cmd()
-            // TODO: can we get rid of this particular error without just silencing stderr?
-            // [image2 @ 0x132e08570] Application provided invalid, non monotonically increasing dts to muxer in stream 0: 928 >= 928
-            .stderr(std::process::Stdio::null())
-            .status()?;
-
-        if !status.success() {
-            return Err(anyhow!("Failed to extract end frames"));
-        }
-
-        progress(ConcertSplitProgress::Diagnostic(format!(
-            "Extracted {} end frames for black frame detection",
-            search_duration
-        )));
-    }
-
-    // Get list of extracted frames
-    let mut frames = fs::read_dir(&temp_dir)?
-        .filter_map(Result::ok)
-        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "png"))
-        .map(|entry| entry.path())
-        .collect::<Vec<_>>();
-
-    progress(ConcertSplitProgress::Diagnostic(format!(
-        "Extracted {} frames for end detection",
-        frames.len()
-    )));
-
-    // Sort frames by frame number
-    frames.sort_by(|a, b| {
-        frame_number_from_image_filename(a).cmp(&frame_number_from_image_filename(b))
-    });
+    let search_start = (video_info.duration - search_duration).max(0.0);
+    let end_dir = format!("{}/end_frames", temp_dir);
+
+    // Every frame of the window, at the video's own framerate, shrunk for speed
+    let spec = FrameSpec {
+        start: search_start,
+        end: None,
+        fps: video_info.framerate,
+        scale: (200, 100),
+        crop: None,
+        negate: false,
+    };
+    let frames = settings.frame_cache(&end_dir).frames(input_file, spec)?;
 
     // Analyze frames to find black frame
     let mut black_frame_time = None;
+    let mut frames_scanned = 0;
     let threshold = 25; // Pixel brightness threshold (0-255)
 
-    for frame_path in frames {
-        // Parse frame number to get timestamp
-        let frame_num = frame_number_from_image_filename(&frame_path);
-        let frame_time = search_start + (frame_num as f64 / 30.0); // Approximate timestamp
-
-        // Open image and check if it's black
-        match ::image::open(&frame_path) {
-            Ok(img) => {
-                // Convert to grayscale and analyze pixels
-                let pixel_data = img.as_rgb8().unwrap().as_raw();
-                let dark_ratio = crate::image::grayscale_darkness(pixel_data, threshold);
-
-                // Check if most pixels are black
-                if dark_ratio > 0.80 {
-                    progress(ConcertSplitProgress::Diagnostic(format!(
-                        "Found black frame at {:.2}s (frame {})",
-                        frame_time, frame_num
-                    )));
-                    black_frame_time = Some(frame_time);
-                    break;
-                }
-            }
-            Err(e) => {
-                progress(ConcertSplitProgress::Warning(format!(
-                    "Error analyzing frame: {}",
-                    e
-                )));
-                continue;
-            }
+    for frame in frames {
+        let frame = frame.context("Failed to decode end frames")?;
+        frames_scanned += 1;
+
+        // Convert to grayscale and analyze pixels
+        let rgb = frame.image.to_rgb8();
+        let dark_ratio = crate::image::grayscale_darkness(rgb.as_raw(), threshold);
+
+        // Check if most pixels are black
+        if dark_ratio > 0.80 {
+            progress(ConcertSplitProgress::Diagnostic(format!(
+                "Found black frame at {:.2}s (frame {})",
+                frame.timestamp, frame.index
+            )));
+            black_frame_time = Some(frame.timestamp);
+            break;
         }
     }
 
-    // Clean up temporary files
-    // fs::remove_dir_all(temp_dir)
+    progress(ConcertSplitProgress::Diagnostic(format!(
+        "Scanned {} frames for end detection",
+        frames_scanned
+    )));
+
     Ok(black_frame_time)
 }
 
@@ -326,7 +320,7 @@ mod tests_refine_start {
 
     #[test]
     fn snaps_to_latest_silence_in_window() {
-        let r = refine_start(100.0, Some(40.0), &[97.5, 98.9, 98.2], true);
+        let r = refine_start(100.0, Some(40.0), &[97.5, 98.9, 98.2], &[], true);
         assert_eq!(r, StartRefinement::Snapped(98.9));
     }
 
@@ -334,15 +328,70 @@ mod tests_refine_start {
     fn silence_snap_applies_even_to_non_overlay_starts() {
         // A recovered start still snaps to a real silence if one is present.
         assert_eq!(
-            refine_start(100.0, Some(40.0), &[98.0], false),
+            refine_start(100.0, Some(40.0), &[98.0], &[], false),
             StartRefinement::Snapped(98.0)
         );
     }
 
+    #[test]
+    fn music_resuming_after_the_crowd_beats_an_earlier_silence() {
+        assert_eq!(
+            refine_start(100.0, Some(40.0), &[97.5], &[98.5], true),
+            StartRefinement::AfterCrowd(98.5)
+        );
+        // A silence after the crowd died down is the later, better start.
+        assert_eq!(
+            refine_start(100.0, Some(40.0), &[98.9], &[95.0], true),
+            StartRefinement::Snapped(98.9)
+        );
+    }
+
+    #[test]
+    fn crowd_onsets_apply_to_overlay_starts_without_any_silence() {
+        assert_eq!(
+            refine_start(100.0, Some(40.0), &[], &[94.0], true),
+            StartRefinement::AfterCrowd(94.0)
+        );
+    }
+
+    #[test]
+    fn non_overlay_starts_take_a_crowd_onset_only_after_a_silence() {
+        assert_eq!(
+            refine_start(100.0, Some(40.0), &[], &[94.0], false),
+            StartRefinement::Unchanged
+        );
+        assert_eq!(
+            refine_start(100.0, Some(40.0), &[97.5], &[98.5], false),
+            StartRefinement::AfterCrowd(98.5)
+        );
+    }
+
+    #[test]
+    fn after_crowd_is_floor_clamped_like_the_pullback() {
+        // The previous song started within the crowd look-back: the onset would
+        // leave it shorter than MIN_SONG_GAP_SECONDS, so the start stops at the floor.
+        let prev = 100.0 - audio::MIN_SONG_GAP_SECONDS - 2.0; // floor = 98.0
+        assert_eq!(
+            refine_start(100.0, Some(prev), &[], &[94.0], true),
+            StartRefinement::AfterCrowd(98.0)
+        );
+        // The previous song started inside the look-back window, after the onset:
+        // the start can't move before it, and a silence still snaps.
+        let prev = 100.0 - CROWD_LOOKBACK_SECONDS + 3.0; // 95.0, floor past song_start
+        assert_eq!(
+            refine_start(100.0, Some(prev), &[], &[94.0], true),
+            StartRefinement::Unchanged
+        );
+        assert_eq!(
+            refine_start(100.0, Some(prev), &[97.5], &[98.5], true),
+            StartRefinement::Snapped(97.5)
+        );
+    }
+
     #[test]
     fn pulls_back_overlay_start_when_no_silence() {
         assert_eq!(
-            refine_start(100.0, Some(40.0), &[], true),
+            refine_start(100.0, Some(40.0), &[], &[], true),
             StartRefinement::PulledBack(97.0)
         );
     }
@@ -351,7 +400,7 @@ mod tests_refine_start {
     fn does_not_pull_back_non_overlay_start() {
         // Recovered / silence-placed / JSON-loaded starts must not be pulled back.
         assert_eq!(
-            refine_start(100.0, Some(40.0), &[], false),
+            refine_start(100.0, Some(40.0), &[], &[], false),
             StartRefinement::Unchanged
         );
     }
@@ -363,7 +412,7 @@ mod tests_refine_start {
         // Here the pullback floor (prev + gap = 119) is past song_start, so a
         // pullback would be Unchanged — but the snap still applies.
         assert_eq!(
-            refine_start(100.0, Some(99.0), &[98.5], true),
+            refine_start(100.0, Some(99.0), &[98.5], &[], true),
             StartRefinement::Snapped(98.5)
         );
     }
@@ -372,7 +421,7 @@ mod tests_refine_start {
     fn pulls_back_with_no_previous_song() {
         // No previous song -> floor is 0.0, so the full overlay delay is applied.
         assert_eq!(
-            refine_start(50.0, None, &[], true),
+            refine_start(50.0, None, &[], &[], true),
             StartRefinement::PulledBack(47.0)
         );
     }
@@ -383,7 +432,7 @@ mod tests_refine_start {
         // shorter than MIN_SONG_GAP_SECONDS, and even the floor is past song_start.
         let prev = 100.0 - audio::MIN_SONG_GAP_SECONDS + 1.0; // floor = prev + gap = 101.0
         assert_eq!(
-            refine_start(100.0, Some(prev), &[], true),
+            refine_start(100.0, Some(prev), &[], &[], true),
             StartRefinement::Unchanged
         );
     }
@@ -394,7 +443,7 @@ mod tests_refine_start {
         // back only as far as the floor keeps the previous song long enough.
         let prev = 100.0 - audio::MIN_SONG_GAP_SECONDS - 1.0; // floor = 99.0
         assert_eq!(
-            refine_start(100.0, Some(prev), &[], true),
+            refine_start(100.0, Some(prev), &[], &[], true),
             StartRefinement::PulledBack(99.0)
         );
     }
@@ -404,7 +453,7 @@ mod tests_refine_start {
 mod tests_refine_segments {
     use super::*;
     use crate::concert_split::AudioSegment;
-    use concert_types::Song;
+    use concert_types::{BoundarySource, Song};
 
     fn overlay_segment(title: &str, start: f64, end: f64) -> SongSegment {
         SongSegment {
@@ -417,6 +466,8 @@ mod tests_refine_segments {
                 is_song: true,
             },
             start_from_overlay: true,
+            overlay_at: Some(start),
+            boundary: provenance::from_source(BoundarySource::Overlay),
         }
     }
 
@@ -453,8 +504,14 @@ mod tests_refine_segments {
             overlay_segment("b", 30.0, 60.0),
         ];
 
-        let refined =
-            refine_segments_with_audio_analysis(&segments, &audio, 60.0, &mut |_| {}).unwrap();
+        let refined = refine_segments_with_audio_analysis(
+            &segments,
+            &audio,
+            &classify::classify(&audio),
+            60.0,
+            &mut |_| {},
+        )
+        .unwrap();
 
         assert_eq!(refined[0].segment.start_time, 0.0, "first song untouched");
         assert_eq!(
@@ -466,6 +523,11 @@ mod tests_refine_segments {
             refined[0].segment.end_time, refined[1].segment.start_time,
             "previous end chained to the new start (no gap/overlap)"
         );
+        assert_eq!(
+            refined[1].boundary.refinement,
+            Some(BoundaryRefinement::PulledBack)
+        );
+        assert_eq!(refined[0].boundary.refinement, None);
         assert_eq!(
             refined[1].segment.end_time, 60.0,
             "last song extends to total duration"
@@ -0,0 +1,490 @@
+//! The `--report` diagnostic report: one self-contained HTML file (inline SVG
+//! plot, embedded frame thumbnails) showing what each phase saw and decided,
+//! so a bad split can be debugged without `--keep-frames` and stderr.
+//!
+//! The plot is drawn with `plotters`' SVG backend without any text, so it needs
+//! no system fonts; times and labels are in the HTML around it.
+
+use std::fmt::Write as _;
+use std::fs;
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use ::image::{DynamicImage, ImageFormat};
+use anyhow::{anyhow, Context, Result};
+use base64::Engine as _;
+use plotters::prelude::*;
+
+use crate::audio::{self, SilenceSpan};
+use crate::recover::{adaptive_silence_threshold, RecoveryResult};
+use concert_types::{BoundaryProvenance, SongTimestamp};
+
+/// The report's filename inside the `--report` directory. Each split replaces it.
+pub const REPORT_FILENAME: &str = "report.html";
+
+const PLOT_WIDTH: u32 = 1200;
+const PLOT_HEIGHT: u32 = 260;
+/// At most this many energy points are plotted; longer profiles are bucketed
+/// by their maximum so short peaks stay visible.
+const MAX_PLOT_POINTS: usize = 1500;
+/// Bounding box of the embedded frame thumbnails.
+const THUMBNAIL_SIZE: (u32, u32) = (360, 180);
+
+/// A set-list title read on one detection frame.
+#[derive(Clone, Debug)]
+pub(crate) struct OverlayMatch {
+    pub frame_num: usize,
+    pub time: f64,
+    /// The OCR lines of the frame.
+    pub lines: Vec<String>,
+    pub song: String,
+    /// The `MatchReason` and edit distance of the best match.
+    pub reason: String,
+    /// Whether the artist overlay was on the frame too; a title alone is only a
+    /// fallback.
+    pub overlay: bool,
+    /// PNG thumbnail of the frame.
+    pub thumbnail: Option<Vec<u8>>,
+}
+
+/// What silence-based recovery did for one song of the set list.
+#[derive(Clone, Debug)]
+pub(crate) struct RecoveryDecision {
+    pub title: String,
+    pub result: RecoveryResult,
+    pub start_time: Option<f64>,
+    pub boundary: Option<BoundaryProvenance>,
+}
+
+/// Everything the report shows, collected as `run` goes.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SplitReport {
+    pub artist: String,
+    pub input_file: String,
+    pub duration: f64,
+    pub overlay_matches: Vec<OverlayMatch>,
+    pub unmatched_overlay_clusters: Vec<f64>,
+    /// Empty when recovery did not run.
+    pub recovery: Vec<RecoveryDecision>,
+    /// The final boundaries; empty when the split found nothing usable.
+    pub boundaries: Vec<SongTimestamp>,
+    /// Songs still missing after recovery, when the split gave up.
+    pub missing: Vec<String>,
+}
+
+/// A PNG thumbnail of `image` for [`OverlayMatch::thumbnail`].
+pub(crate) fn thumbnail(image: &DynamicImage) -> Option<Vec<u8>> {
+    let mut png = Vec::new();
+    image
+        .thumbnail(THUMBNAIL_SIZE.0, THUMBNAIL_SIZE.1)
+        .write_to(&mut Cursor::new(&mut png), ImageFormat::Png)
+        .ok()?;
+    Some(png)
+}
+
+impl SplitReport {
+    /// Write the report for the soundtrack `audio_data` to
+    /// `dir/`[`REPORT_FILENAME`], creating `dir`.
+    pub(crate) fn write(&self, dir: &Path, audio_data: &[f32]) -> Result<PathBuf> {
+        let energy_profile = audio::calculate_energy_profile(audio_data);
+        let (threshold, silence_spans) = if energy_profile.is_empty() {
+            (0.0, Vec::new())
+        } else {
+            let threshold = adaptive_silence_threshold(&energy_profile);
+            let spans = audio::find_silence_spans(&energy_profile, threshold);
+            (threshold, spans)
+        };
+        let plot = self.energy_plot(&energy_profile, threshold, &silence_spans)?;
+        let html = self.render(&plot, threshold, &silence_spans);
+
+        fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create report directory {}", dir.display()))?;
+        let path = dir.join(REPORT_FILENAME);
+        fs::write(&path, html)
+            .with_context(|| format!("Failed to write report {}", path.display()))?;
+        Ok(path)
+    }
+
+    /// The energy profile as an SVG: silences shaded, the silence threshold in
+    /// orange, overlay sightings as ticks along the top, and the final
+    /// boundaries as vertical lines (red when low confidence).
+    fn energy_plot(
+        &self,
+        energy_profile: &[f64],
+        threshold: f64,
+        silence_spans: &[SilenceSpan],
+    ) -> Result<String> {
+        let fps = audio::frames_per_second();
+        let duration = self
+            .duration
+            .max(energy_profile.len() as f64 / fps)
+            .max(1.0);
+        let bucket = energy_profile.len().div_ceil(MAX_PLOT_POINTS).max(1);
+        let points: Vec<(f64, f64)> = energy_profile
+            .chunks(bucket)
+            .enumerate()
+            .map(|(i, chunk)| {
+                let peak = chunk.iter().copied().fold(0.0, f64::max);
+                ((i * bucket) as f64 / fps, peak)
+            })
+            .collect();
+        let top = points
+            .iter()
+            .map(|&(_, energy)| energy)
+            .fold(threshold * 2.0, f64::max)
+            .max(f64::EPSILON)
+            * 1.05;
+
+        let mut svg = String::new();
+        {
+            let root =
+                SVGBackend::with_string(&mut svg, (PLOT_WIDTH, PLOT_HEIGHT)).into_drawing_area();
+            let draw_error = |e| anyhow!("Failed to draw the energy plot: {e:?}");
+            root.fill(&WHITE).map_err(draw_error)?;
+            let mut chart = ChartBuilder::on(&root)
+                .margin(6)
+                .build_cartesian_2d(0f64..duration, 0f64..top)
+                .map_err(draw_error)?;
+            let silence = RGBColor(225, 225, 225).filled();
+            chart
+                .draw_series(silence_spans.iter().map(|span| {
+                    let half = span.duration_seconds / 2.0;
+                    Rectangle::new(
+                        [
+                            (span.midpoint_seconds - half, 0.0),
+                            (span.midpoint_seconds + half, top),
+                        ],
+                        silence,
+                    )
+                }))
+                .map_err(draw_error)?;
+            chart
+                .draw_series(LineSeries::new(points, &RGBColor(40, 80, 160)))
+                .map_err(draw_error)?;
+            chart
+                .draw_series(LineSeries::new(
+                    [(0.0, threshold), (duration, threshold)],
+                    RGBColor(230, 120, 0).stroke_width(2),
+                ))
+                .map_err(draw_error)?;
+            let tick = |time: f64, color: RGBColor| {
+                PathElement::new([(time, top), (time, top * 0.9)], color.stroke_width(3))
+            };
+            chart
+                .draw_series(
+                    self.overlay_matches
+                        .iter()
+                        .map(|m| {
+                            let color = if m.overlay {
+                                RGBColor(30, 150, 60)
+                            } else {
+                                RGBColor(200, 170, 0)
+                            };
+                            tick(m.time, color)
+                        })
+                        .chain(
+                            self.unmatched_overlay_clusters
+                                .iter()
+                                .map(|&time| tick(time, RGBColor(140, 60, 170))),
+                        ),
+                )
+                .map_err(draw_error)?;
+            chart
+                .draw_series(self.boundaries.iter().map(|song| {
+                    let color = if low_confidence(song) {
+                        RGBColor(210, 30, 30)
+                    } else {
+                        RGBColor(20, 20, 20)
+                    };
+                    PathElement::new(
+                        [(song.start_time, 0.0), (song.start_time, top)],
+                        color.stroke_width(2),
+                    )
+                }))
+                .map_err(draw_error)?;
+            root.present().map_err(draw_error)?;
+        }
+        Ok(svg)
+    }
+
+    fn render(&self, plot: &str, threshold: f64, silence_spans: &[SilenceSpan]) -> String {
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html><head><meta charset=\"utf-8\">\
+             <title>Split report: {artist}</title><style>{STYLE}</style></head><body>\
+             <h1>Split report: {artist}</h1><p>{input} &middot; {duration}</p>",
+            artist = escape(&self.artist),
+            input = escape(&self.input_file),
+            duration = clock(self.duration),
+        );
+        if !self.missing.is_empty() {
+            let _ = write!(
+                html,
+                "<p class=\"failed\">No split: still missing {}</p>",
+                escape(&self.missing.join(", "))
+            );
+        }
+
+        let _ = write!(
+            html,
+            "<h2>Energy</h2><div class=\"plot\">{plot}</div><p class=\"legend\">\
+             0:00 to {end}. <span class=\"energy\">energy</span>, \
+             <span class=\"threshold\">silence threshold {threshold:.6}</span>, \
+             <span class=\"silence\">silences</span>, ticks for \
+             <span class=\"overlay\">overlay matches</span>, \
+             <span class=\"title-only\">title-only matches</span> and \
+             <span class=\"cluster\">unreadable overlays</span>; lines for \
+             boundaries, <span class=\"low\">red when low confidence</span>.</p>",
+            end = clock(self.duration),
+        );
+
+        let _ = write!(
+            html,
+            "<h2>Silences ({})</h2><table><tr><th>Midpoint</th><th>Length</th></tr>",
+            silence_spans.len()
+        );
+        for span in silence_spans {
+            let _ = write!(
+                html,
+                "<tr><td>{}</td><td>{:.1}s</td></tr>",
+                clock(span.midpoint_seconds),
+                span.duration_seconds
+            );
+        }
+        html.push_str("</table>");
+
+        let _ = write!(
+            html,
+            "<h2>Overlay matches ({})</h2>",
+            self.overlay_matches.len()
+        );
+        if !self.overlay_matches.is_empty() {
+            html.push_str(
+                "<table><tr><th>Frame</th><th>Time</th><th>OCR text</th>\
+                 <th>Song</th><th>Match</th></tr>",
+            );
+            for m in &self.overlay_matches {
+                let image = m
+                    .thumbnail
+                    .as_ref()
+                    .map(|png| {
+                        format!(
+                            "<br><img alt=\"frame {}\" src=\"data:image/png;base64,{}\">",
+                            m.frame_num,
+                            base64::engine::general_purpose::STANDARD.encode(png)
+                        )
+                    })
+                    .unwrap_or_default();
+                let _ = write!(
+                    html,
+                    "<tr><td>{frame}{image}</td><td>{time}</td><td><pre>{text}</pre></td>\
+                     <td>{song}</td><td>{reason}{kind}</td></tr>",
+                    frame = m.frame_num,
+                    time = clock(m.time),
+                    text = escape(&m.lines.join("\n")),
+                    song = escape(&m.song),
+                    reason = escape(&m.reason),
+                    kind = if m.overlay { "" } else { ", title only" },
+                );
+            }
+            html.push_str("</table>");
+        }
+        if !self.unmatched_overlay_clusters.is_empty() {
+            let times: Vec<String> = self
+                .unmatched_overlay_clusters
+                .iter()
+                .map(|&time| clock(time))
+                .collect();
+            let _ = write!(
+                html,
+                "<p>Artist overlay without a readable title at {}</p>",
+                times.join(", ")
+            );
+        }
+
+        html.push_str("<h2>Recovery</h2>");
+        if self.recovery.is_empty() {
+            html.push_str("<p>Not run: no song was missing.</p>");
+        } else {
+            html.push_str(
+                "<table><tr><th>Song</th><th>Result</th><th>Start</th><th>From</th></tr>",
+            );
+            for decision in &self.recovery {
+                let result = match decision.result {
+                    RecoveryResult::AlreadyFound => "detected",
+                    RecoveryResult::Recovered => "recovered",
+                    RecoveryResult::StillMissing => "missing",
+                };
+                let _ = write!(
+                    html,
+                    "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                    escape(&decision.title),
+                    result,
+                    decision.start_time.map(clock).unwrap_or_default(),
+                    decision.boundary.map(source_text).unwrap_or_default(),
+                );
+            }
+            html.push_str("</table>");
+        }
+
+        html.push_str("<h2>Boundaries</h2>");
+        if self.boundaries.is_empty() {
+            html.push_str("<p>None.</p>");
+        } else {
+            html.push_str(
+                "<table><tr><th>#</th><th>Song</th><th>Start</th><th>End</th>\
+                 <th>From</th><th>Confidence</th></tr>",
+            );
+            for (i, song) in self.boundaries.iter().enumerate() {
+                let _ = write!(
+                    html,
+                    "<tr{class}><td>{n}</td><td>{title}</td><td>{start}</td><td>{end}</td>\
+                     <td>{from}</td><td>{confidence}</td></tr>",
+                    class = if low_confidence(song) {
+                        " class=\"low\""
+                    } else {
+                        ""
+                    },
+                    n = i + 1,
+                    title = escape(&song.title),
+                    start = clock(song.start_time),
+                    end = clock(song.end_time),
+                    from = song.boundary.map(source_text).unwrap_or_default(),
+                    confidence = song
+                        .boundary
+                        .map(|b| format!("{:.0}%", b.confidence * 100.0))
+                        .unwrap_or_default(),
+                );
+            }
+            html.push_str("</table>");
+        }
+        html.push_str("</body></html>\n");
+        html
+    }
+}
+
+const STYLE: &str = "body{font-family:sans-serif;margin:1.5rem;color:#222}\
+table{border-collapse:collapse;margin-bottom:1rem}\
+th,td{border:1px solid #ddd;padding:.25rem .5rem;text-align:left;vertical-align:top}\
+pre{margin:0;font-size:.85em}.plot svg{max-width:100%;height:auto;border:1px solid #ddd}\
+.legend{font-size:.85em}.energy{color:#2850a0}.threshold{color:#e67800}\
+.silence{background:#e1e1e1}.overlay{color:#1e963c}.title-only{color:#c8aa00}\
+.cluster{color:#8c3caa}.low{color:#d21e1e}.failed{color:#d21e1e;font-weight:bold}";
+
+fn low_confidence(song: &SongTimestamp) -> bool {
+    song.boundary.is_some_and(|b| b.is_low_confidence())
+}
+
+/// `equal_split`, plus the refinement if any: `overlay, snapped_to_silence`.
+fn source_text(boundary: BoundaryProvenance) -> String {
+    match boundary.refinement {
+        Some(refinement) => format!(
+            "{}, {}",
+            serde_name(boundary.source),
+            serde_name(refinement)
+        ),
+        None => serde_name(boundary.source),
+    }
+}
+
+/// The name `value` (a unit enum variant) has in `timestamps.json`.
+fn serde_name(value: impl serde::Serialize) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// `m:ss.s`.
+fn clock(seconds: f64) -> String {
+    let seconds = seconds.max(0.0);
+    let minutes = (seconds / 60.0).floor();
+    format!("{}:{:04.1}", minutes as u64, seconds - minutes * 60.0)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provenance;
+    use concert_types::BoundarySource;
+
+    /// `blocks` of (seconds, is_silent): a 100Hz tone or silence.
+    fn synth_audio(blocks: &[(f64, bool)]) -> Vec<f32> {
+        let sr = audio::SAMPLE_RATE as f64;
+        let mut samples = Vec::new();
+        for &(seconds, is_silent) in blocks {
+            for i in 0..(seconds * sr) as usize {
+                let phase = i as f64 / sr * 2.0 * std::f64::consts::PI * 100.0;
+                samples.push(if is_silent {
+                    0.0
+                } else {
+                    0.5 * phase.sin() as f32
+                });
+            }
+        }
+        samples
+    }
+
+    fn song(title: &str, start_time: f64, end_time: f64, source: BoundarySource) -> SongTimestamp {
+        SongTimestamp {
+            title: title.to_string(),
+            start_time,
+            end_time,
+            duration: end_time - start_time,
+            boundary: Some(provenance::from_source(source)),
+        }
+    }
+
+    #[test]
+    fn report_shows_silences_matches_and_boundaries() {
+        let dir = tempfile::tempdir().unwrap();
+        let report = SplitReport {
+            artist: "Tom & Jerry".to_string(),
+            input_file: "concert.mp4".to_string(),
+            duration: 40.0,
+            overlay_matches: vec![OverlayMatch {
+                frame_num: 22,
+                time: 22.0,
+                lines: vec!["TOM & JERRY".to_string(), "<Second>".to_string()],
+                song: "<Second>".to_string(),
+                reason: "Levenshtein(0) (dist 0)".to_string(),
+                overlay: true,
+                thumbnail: thumbnail(&DynamicImage::new_rgb8(400, 200)),
+            }],
+            boundaries: vec![
+                song("First", 0.0, 20.0, BoundarySource::RecordingStart),
+                song("<Second>", 20.0, 40.0, BoundarySource::EqualSplit),
+            ],
+            ..SplitReport::default()
+        };
+        let audio = synth_audio(&[(18.0, false), (4.0, true), (18.0, false)]);
+
+        let path = report.write(dir.path(), &audio).unwrap();
+        assert_eq!(path, dir.path().join(REPORT_FILENAME));
+        let html = fs::read_to_string(path).unwrap();
+        assert!(html.contains("<svg"));
+        assert!(html.contains("Split report: Tom &amp; Jerry"));
+        assert!(html.contains("<h2>Silences (1)</h2>"));
+        assert!(html.contains("<td>0:20.0</td>"));
+        assert!(html.contains("data:image/png;base64,"));
+        assert!(html.contains("<td>&lt;Second&gt;</td>"));
+        assert!(html.contains("<tr class=\"low\"><td>2</td>"));
+        assert!(html.contains("<td>equal_split</td>"));
+        assert!(html.contains("<p>Not run: no song was missing.</p>"));
+    }
+}
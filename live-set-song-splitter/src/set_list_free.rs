@@ -0,0 +1,444 @@
+//! Splitting a recording whose set list is unknown (festival streams, bootlegs,
+//! rehearsals): song boundaries come from the soundtrack's silences alone,
+//! bounded by the song-length limits in [`SetListFree`], and tracks get
+//! placeholder titles unless an overlay title was read inside them.
+//!
+//! Audio-only inputs with a known set list have no overlays to detect either;
+//! [`split_at_silences`] places exactly one boundary per song change on the
+//! longest silences.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::audio::{self, SilenceSpan};
+use crate::concert_split::{AudioSegment, ConcertSplitProgress, SetListFree, SongSegment};
+use crate::recover::adaptive_silence_threshold;
+use crate::{io, provenance};
+use concert_types::{BoundaryProvenance, BoundarySource, Song};
+
+/// Seconds of audio averaged when looking for the quietest point of an overlong
+/// track.
+const QUIET_WINDOW_SECONDS: f64 = 2.0;
+
+/// The set list and song segments derived for one recording.
+pub(crate) struct DerivedSetList {
+    pub set_list: Vec<Song>,
+    pub segments: Vec<SongSegment>,
+}
+
+/// `Track 01`, `Track 02`, …
+pub(crate) fn placeholder_title(track: usize) -> String {
+    format!("Track {:02}", track)
+}
+
+/// Split `audio_data` (the whole soundtrack, `duration` seconds long) into
+/// back-to-back tracks covering the recording. `overlay_titles` are
+/// `(timestamp, title)` sightings of title cards, possibly empty.
+pub(crate) fn derive_set_list(
+    audio_data: &[f32],
+    duration: f64,
+    limits: &SetListFree,
+    overlay_titles: &[(f64, String)],
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> DerivedSetList {
+    let energy_profile = audio::calculate_energy_profile(audio_data);
+    let silence_spans = if energy_profile.is_empty() {
+        Vec::new()
+    } else {
+        let threshold = adaptive_silence_threshold(&energy_profile);
+        audio::find_silence_spans(&energy_profile, threshold)
+    };
+    progress(ConcertSplitProgress::Diagnostic(format!(
+        "Found {} silences for set-list-free splitting",
+        silence_spans.len()
+    )));
+
+    let boundaries = choose_boundaries(&silence_spans, &energy_profile, duration, limits);
+    for piece in boundaries.windows(2) {
+        if piece[1] - piece[0] > limits.max_song_seconds {
+            progress(ConcertSplitProgress::Warning(format!(
+                "No place to split the {:.0}s track at {:.2}s under the {:.0}s maximum",
+                piece[1] - piece[0],
+                piece[0],
+                limits.max_song_seconds
+            )));
+        }
+    }
+
+    let titles = track_titles(&boundaries, overlay_titles);
+    let mut set_list = Vec::new();
+    let mut segments = Vec::new();
+    for (piece, title) in boundaries.windows(2).zip(titles) {
+        progress(ConcertSplitProgress::Diagnostic(format!(
+            "'{}': {:.2}s–{:.2}s",
+            title, piece[0], piece[1]
+        )));
+        let song = Song { title };
+        segments.push(SongSegment {
+            song: song.clone(),
+            segment: AudioSegment {
+                start_time: piece[0],
+                end_time: piece[1],
+                is_song: true,
+            },
+            start_from_overlay: false,
+            overlay_at: None,
+            boundary: boundary_at(piece[0], &silence_spans),
+        });
+        set_list.push(song);
+    }
+    DerivedSetList { set_list, segments }
+}
+
+/// Split `audio_data` (the whole soundtrack, `duration` seconds long) into one
+/// back-to-back segment per song of `set_list`, at the `set_list.len() - 1`
+/// longest silences at least [`audio::MIN_SONG_GAP_SECONDS`] apart. Short of
+/// silences, the longest track is split at its quietest point.
+pub(crate) fn split_at_silences(
+    audio_data: &[f32],
+    duration: f64,
+    set_list: &[Song],
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> Vec<SongSegment> {
+    let energy_profile = audio::calculate_energy_profile(audio_data);
+    let silence_spans = if energy_profile.is_empty() {
+        Vec::new()
+    } else {
+        let threshold = adaptive_silence_threshold(&energy_profile);
+        audio::find_silence_spans(&energy_profile, threshold)
+    };
+    progress(ConcertSplitProgress::Diagnostic(format!(
+        "Found {} silences for {} song changes",
+        silence_spans.len(),
+        set_list.len().saturating_sub(1)
+    )));
+
+    let boundaries = song_change_boundaries(
+        &silence_spans,
+        &energy_profile,
+        duration,
+        set_list.len(),
+        progress,
+    );
+    boundaries
+        .windows(2)
+        .zip(set_list)
+        .map(|(piece, song)| {
+            progress(ConcertSplitProgress::Diagnostic(format!(
+                "'{}': {:.2}s–{:.2}s",
+                song.title, piece[0], piece[1]
+            )));
+            SongSegment {
+                song: song.clone(),
+                segment: AudioSegment {
+                    start_time: piece[0],
+                    end_time: piece[1],
+                    is_song: true,
+                },
+                start_from_overlay: false,
+                overlay_at: None,
+                boundary: boundary_at(piece[0], &silence_spans),
+            }
+        })
+        .collect()
+}
+
+/// Provenance of a track starting at `at`: the recording's start, one of
+/// `silence_spans`, or else a quietest-point split.
+fn boundary_at(at: f64, silence_spans: &[SilenceSpan]) -> BoundaryProvenance {
+    if at == 0.0 {
+        provenance::from_source(BoundarySource::RecordingStart)
+    } else if silence_spans.iter().any(|span| span.midpoint_seconds == at) {
+        provenance::from_source(BoundarySource::Silence)
+    } else {
+        BoundaryProvenance::new(
+            BoundarySource::Silence,
+            provenance::QUIETEST_POINT_CONFIDENCE,
+        )
+    }
+}
+
+/// `songs + 1` boundaries from `0` to `duration`, inclusive, for
+/// [`split_at_silences`].
+fn song_change_boundaries(
+    silence_spans: &[SilenceSpan],
+    energy_profile: &[f64],
+    duration: f64,
+    songs: usize,
+    progress: &mut dyn FnMut(ConcertSplitProgress),
+) -> Vec<f64> {
+    let gap = audio::MIN_SONG_GAP_SECONDS;
+    let mut boundaries = vec![0.0, duration];
+    let mut spans = silence_spans.to_vec();
+    spans.sort_by(|a, b| b.duration_seconds.total_cmp(&a.duration_seconds));
+    for span in spans {
+        if boundaries.len() > songs {
+            break;
+        }
+        let at = span.midpoint_seconds;
+        let next = boundaries.partition_point(|&b| b < at);
+        if next == 0 || next == boundaries.len() {
+            continue;
+        }
+        if at - boundaries[next - 1] >= gap && boundaries[next] - at >= gap {
+            boundaries.insert(next, at);
+        }
+    }
+
+    while boundaries.len() <= songs {
+        let (i, _) = boundaries
+            .windows(2)
+            .enumerate()
+            .max_by(|(_, a), (_, b)| (a[1] - a[0]).total_cmp(&(b[1] - b[0])))
+            .expect("boundaries always hold the start and end");
+        let (start, end) = (boundaries[i], boundaries[i + 1]);
+        let at =
+            quietest_point(energy_profile, start + gap, end - gap).unwrap_or((start + end) / 2.0);
+        progress(ConcertSplitProgress::Warning(format!(
+            "Not enough silences between songs; splitting {:.2}s–{:.2}s at {:.2}s",
+            start, end, at
+        )));
+        boundaries.insert(i + 1, at);
+    }
+    boundaries
+}
+
+/// Track boundaries from `0` to `duration`, inclusive. Silences are taken
+/// longest first wherever both tracks they leave are at least
+/// `min_song_seconds` long. A track still longer than `max_song_seconds` is
+/// then split at its quietest point that keeps both halves above the minimum;
+/// one too short to split that way stays whole.
+fn choose_boundaries(
+    silence_spans: &[SilenceSpan],
+    energy_profile: &[f64],
+    duration: f64,
+    limits: &SetListFree,
+) -> Vec<f64> {
+    let min = limits.min_song_seconds;
+    let mut boundaries = vec![0.0, duration];
+
+    let mut spans = silence_spans.to_vec();
+    spans.sort_by(|a, b| b.duration_seconds.total_cmp(&a.duration_seconds));
+    for span in spans {
+        let at = span.midpoint_seconds;
+        let next = boundaries.partition_point(|&b| b < at);
+        if next == 0 || next == boundaries.len() {
+            continue;
+        }
+        if at - boundaries[next - 1] >= min && boundaries[next] - at >= min {
+            boundaries.insert(next, at);
+        }
+    }
+
+    let mut i = 0;
+    while i + 1 < boundaries.len() {
+        let (start, end) = (boundaries[i], boundaries[i + 1]);
+        if end - start > limits.max_song_seconds {
+            if let Some(at) = quietest_point(energy_profile, start + min, end - min) {
+                boundaries.insert(i + 1, at);
+                continue;
+            }
+        }
+        i += 1;
+    }
+    boundaries
+}
+
+/// The centre of the quietest [`QUIET_WINDOW_SECONDS`] of `energy_profile`
+/// centred between `from` and `to` seconds, or `None` when that range is empty.
+fn quietest_point(energy_profile: &[f64], from: f64, to: f64) -> Option<f64> {
+    let fps = audio::frames_per_second();
+    let half = ((QUIET_WINDOW_SECONDS * fps) as usize / 2).max(1);
+    let first = (from * fps).ceil().max(0.0) as usize;
+    let last = ((to * fps).floor() as usize).min(energy_profile.len().saturating_sub(1));
+    if from > to || first > last {
+        return None;
+    }
+    let mut prefix = Vec::with_capacity(energy_profile.len() + 1);
+    prefix.push(0.0);
+    for energy in energy_profile {
+        prefix.push(prefix.last().unwrap() + energy);
+    }
+    let window_mean = |centre: usize| {
+        let lo = centre.saturating_sub(half);
+        let hi = (centre + half + 1).min(energy_profile.len());
+        (prefix[hi] - prefix[lo]) / (hi - lo) as f64
+    };
+    (first..=last)
+        .min_by(|&a, &b| window_mean(a).total_cmp(&window_mean(b)))
+        .map(|centre| centre as f64 / fps)
+}
+
+/// One title per track between consecutive `boundaries`: the overlay title read
+/// most often inside the track (earliest first on a tie), else
+/// [`placeholder_title`]. A title whose filename would collide with another
+/// track's falls back to the placeholder too.
+fn track_titles(boundaries: &[f64], overlay_titles: &[(f64, String)]) -> Vec<String> {
+    let tracks = boundaries.len().saturating_sub(1);
+    let mut taken: HashSet<String> = (1..=tracks)
+        .map(|track| io::sanitize_filename(&placeholder_title(track)))
+        .collect();
+    boundaries
+        .windows(2)
+        .enumerate()
+        .map(|(i, piece)| {
+            let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+            for (order, (_, title)) in overlay_titles
+                .iter()
+                .filter(|(at, _)| *at >= piece[0] && *at < piece[1])
+                .enumerate()
+            {
+                let title = title.trim();
+                if !title.is_empty() {
+                    counts.entry(title.to_string()).or_insert((0, order)).0 += 1;
+                }
+            }
+            counts
+                .into_iter()
+                .max_by(|(_, (a, a_first)), (_, (b, b_first))| a.cmp(b).then(b_first.cmp(a_first)))
+                .map(|(title, _)| title)
+                .filter(|title| {
+                    let stem = io::sanitize_filename(title);
+                    !stem.is_empty() && taken.insert(stem)
+                })
+                .unwrap_or_else(|| placeholder_title(i + 1))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limits(min: f64, max: f64) -> SetListFree {
+        SetListFree {
+            min_song_seconds: min,
+            max_song_seconds: max,
+            overlay_titles: false,
+        }
+    }
+
+    fn silence(at: f64, duration: f64) -> SilenceSpan {
+        SilenceSpan {
+            midpoint_seconds: at,
+            duration_seconds: duration,
+        }
+    }
+
+    /// A flat energy profile of `seconds`, with `quiet` seconds near silent.
+    fn profile(seconds: f64, quiet: &[f64]) -> Vec<f64> {
+        let fps = audio::frames_per_second();
+        let mut energy = vec![0.1; (seconds * fps) as usize];
+        for &at in quiet {
+            let centre = (at * fps) as usize;
+            for frame in energy.iter_mut().skip(centre.saturating_sub(20)).take(40) {
+                *frame = 0.001;
+            }
+        }
+        energy
+    }
+
+    #[test]
+    fn silences_become_boundaries_unless_they_leave_a_short_track() {
+        let spans = [
+            silence(200.0, 3.0),
+            silence(230.0, 2.5), // 30s after a boundary: a quiet passage
+            silence(410.0, 4.0),
+            silence(590.0, 2.0), // 10s before the end
+        ];
+        let boundaries = choose_boundaries(&spans, &[], 600.0, &limits(60.0, 900.0));
+        assert_eq!(boundaries, vec![0.0, 200.0, 410.0, 600.0]);
+    }
+
+    #[test]
+    fn longer_silences_win_when_two_compete() {
+        let spans = [silence(180.0, 2.0), silence(215.0, 6.0)];
+        let boundaries = choose_boundaries(&spans, &[], 500.0, &limits(60.0, 900.0));
+        assert_eq!(boundaries, vec![0.0, 215.0, 500.0]);
+    }
+
+    #[test]
+    fn overlong_tracks_split_at_their_quietest_point() {
+        let energy = profile(1000.0, &[30.0, 520.0]);
+        let boundaries = choose_boundaries(&[], &energy, 1000.0, &limits(60.0, 600.0));
+        assert_eq!(boundaries.len(), 3);
+        assert!(
+            (boundaries[1] - 520.0).abs() < 1.0,
+            "split near the quiet spot, not the one inside the minimum: {boundaries:?}"
+        );
+    }
+
+    #[test]
+    fn a_track_too_short_to_split_stays_whole() {
+        let energy = profile(100.0, &[50.0]);
+        let boundaries = choose_boundaries(&[], &energy, 100.0, &limits(60.0, 90.0));
+        assert_eq!(boundaries, vec![0.0, 100.0]);
+    }
+
+    #[test]
+    fn overlay_titles_name_their_tracks_and_the_rest_are_placeholders() {
+        let boundaries = [0.0, 200.0, 400.0, 600.0];
+        let sightings = [
+            (20.0, "Quick".to_string()),
+            (21.0, "Quiek".to_string()),
+            (22.0, "Quick".to_string()),
+            (450.0, "Quick".to_string()), // would collide with track 1
+            (460.0, "Track 02".to_string()),
+        ];
+        assert_eq!(
+            track_titles(&boundaries, &sightings),
+            vec!["Quick", "Track 02", "Track 03"]
+        );
+    }
+
+    #[test]
+    fn a_known_set_list_splits_at_its_longest_silences() {
+        let spans = [
+            silence(200.0, 3.0),
+            silence(210.0, 5.0), // too close to the longer silence before it
+            silence(410.0, 4.0),
+            silence(300.0, 2.0), // one song change too many
+        ];
+        let boundaries = song_change_boundaries(&spans, &[], 600.0, 3, &mut |_| {});
+        assert_eq!(boundaries, vec![0.0, 210.0, 410.0, 600.0]);
+    }
+
+    #[test]
+    fn missing_silences_fall_back_to_splitting_the_longest_track() {
+        let mut warnings = 0;
+        let boundaries = song_change_boundaries(&[silence(100.0, 3.0)], &[], 500.0, 3, &mut |e| {
+            if matches!(e, ConcertSplitProgress::Warning(_)) {
+                warnings += 1;
+            }
+        });
+        assert_eq!(boundaries, vec![0.0, 100.0, 300.0, 500.0]);
+        assert_eq!(warnings, 1);
+    }
+
+    #[test]
+    fn quietest_point_splits_are_low_confidence() {
+        let spans = [silence(100.0, 3.0)];
+        assert_eq!(
+            boundary_at(0.0, &spans).source,
+            BoundarySource::RecordingStart
+        );
+        assert!(!boundary_at(100.0, &spans).is_low_confidence());
+        assert!(boundary_at(300.0, &spans).is_low_confidence());
+    }
+
+    #[test]
+    fn derived_segments_cover_the_recording() {
+        let mut events = Vec::new();
+        let derived = derive_set_list(&[], 300.0, &limits(60.0, 900.0), &[], &mut |e| {
+            events.push(e)
+        });
+        assert_eq!(
+            derived.set_list,
+            vec![Song {
+                title: "Track 01".to_string()
+            }]
+        );
+        assert_eq!(derived.segments.len(), 1);
+        assert_eq!(derived.segments[0].segment.start_time, 0.0);
+        assert_eq!(derived.segments[0].segment.end_time, 300.0);
+    }
+}
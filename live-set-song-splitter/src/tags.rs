@@ -0,0 +1,295 @@
+//! Container tags written on every cut track, driven by a [`TagTemplate`]: an
+//! ordered list of `key = pattern` pairs whose `{placeholder}`s are filled from
+//! the concert and the track. Media servers (Jellyfin, Navidrome) group and
+//! label tracks from these, so the default template fills in album-artist,
+//! genre, track/disc totals and the NPR source URL, not just artist/title.
+
+use std::process::Command;
+
+use anyhow::{anyhow, Result};
+use concert_types::ConcertInfo;
+
+/// Placeholders a pattern may use. A tag whose pattern references a
+/// placeholder that is empty for this track (e.g. `{track}` on an interlude,
+/// `{year}` on an undated concert) is skipped rather than written half-filled.
+pub const PLACEHOLDERS: &[&str] = &[
+    "title",
+    "artist",
+    "album",
+    "year",
+    "date",
+    "show",
+    "source",
+    "track",
+    "track_total",
+    "musicians",
+    "performers",
+];
+
+/// Ordered `key = pattern` tag definitions. Keys are ffmpeg metadata keys;
+/// the MP4 muxer maps the standard ones (`album_artist`, `composer`, `genre`,
+/// `comment`, `track`, `disc`, …) onto iTunes atoms and silently drops keys it
+/// has no atom for (`performer` survives only in containers with free-form
+/// tags, e.g. Vorbis comments).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TagTemplate {
+    fields: Vec<(String, String)>,
+}
+
+impl Default for TagTemplate {
+    fn default() -> Self {
+        let fields = [
+            ("title", "{title}"),
+            ("artist", "{artist}"),
+            ("album_artist", "{artist}"),
+            ("album", "{album}"),
+            ("date", "{year}"),
+            ("genre", "Tiny Desk"),
+            ("track", "{track}/{track_total}"),
+            ("disc", "1/1"),
+            ("comment", "{source}"),
+            ("composer", "{musicians}"),
+            ("performer", "{performers}"),
+        ];
+        TagTemplate {
+            fields: fields
+                .into_iter()
+                .map(|(key, pattern)| (key.to_string(), pattern.to_string()))
+                .collect(),
+        }
+    }
+}
+
+impl TagTemplate {
+    /// A template that writes no tags at all.
+    pub fn empty() -> Self {
+        TagTemplate { fields: Vec::new() }
+    }
+
+    /// Set `key`'s pattern, replacing an existing entry in place (so ordering
+    /// stays stable) or appending a new one. An empty pattern removes the key.
+    pub fn set(&mut self, key: &str, pattern: &str) -> Result<()> {
+        validate_pattern(pattern)?;
+        let existing = self.fields.iter().position(|(k, _)| k == key);
+        match (existing, pattern.is_empty()) {
+            (Some(i), true) => {
+                self.fields.remove(i);
+            }
+            (Some(i), false) => self.fields[i].1 = pattern.to_string(),
+            (None, true) => {}
+            (None, false) => self.fields.push((key.to_string(), pattern.to_string())),
+        }
+        Ok(())
+    }
+
+    /// Apply a `KEY=PATTERN` override, the form the CLI's `--tag` takes.
+    pub fn apply_override(&mut self, spec: &str) -> Result<()> {
+        let (key, pattern) = spec
+            .split_once('=')
+            .ok_or_else(|| anyhow!("tag override must be KEY=PATTERN: {spec:?}"))?;
+        let key = key.trim();
+        anyhow::ensure!(!key.is_empty(), "tag override has an empty key: {spec:?}");
+        self.set(key, pattern)
+    }
+
+    pub fn fields(&self) -> &[(String, String)] {
+        &self.fields
+    }
+
+    /// Fill every pattern for one track. `track_number` is `None` for
+    /// interludes; `track_total` is the set-list length.
+    pub fn render(
+        &self,
+        concert: &ConcertInfo,
+        title: &str,
+        track_number: Option<usize>,
+        track_total: usize,
+    ) -> TrackTags {
+        let value = |name: &str| -> String {
+            match name {
+                "title" => title.to_string(),
+                "artist" => concert.artist.clone(),
+                "album" => concert.album.clone(),
+                "year" => concert.year().unwrap_or_default(),
+                "date" => concert.date.clone().unwrap_or_default(),
+                "show" => concert.show.clone(),
+                "source" => concert.source.clone(),
+                "track" => track_number.map(|n| n.to_string()).unwrap_or_default(),
+                "track_total" => track_total.to_string(),
+                "musicians" => concert
+                    .musicians
+                    .iter()
+                    .map(|m| m.name.as_str())
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                "performers" => concert
+                    .musicians
+                    .iter()
+                    .map(|m| {
+                        if m.instruments.is_empty() {
+                            m.name.clone()
+                        } else {
+                            format!("{} ({})", m.name, m.instruments.join(", "))
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join("; "),
+                _ => String::new(),
+            }
+        };
+        let tags = self
+            .fields
+            .iter()
+            .filter_map(|(key, pattern)| fill(pattern, &value).map(|filled| (key.clone(), filled)))
+            .collect();
+        TrackTags(tags)
+    }
+}
+
+/// Rendered `key=value` tags for one track, in template order.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TrackTags(Vec<(String, String)>);
+
+impl TrackTags {
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Append `-metadata key=value` for every tag.
+    pub fn apply(&self, cmd: &mut Command) {
+        for (key, value) in &self.0 {
+            cmd.args(["-metadata", &format!("{key}={value}")]);
+        }
+    }
+}
+
+fn validate_pattern(pattern: &str) -> Result<()> {
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        let after = &rest[open + 1..];
+        let close = after
+            .find('}')
+            .ok_or_else(|| anyhow!("unclosed placeholder in tag pattern {pattern:?}"))?;
+        let name = &after[..close];
+        anyhow::ensure!(
+            PLACEHOLDERS.contains(&name),
+            "unknown placeholder {{{name}}} in tag pattern {pattern:?} (known: {})",
+            PLACEHOLDERS.join(", ")
+        );
+        rest = &after[close + 1..];
+    }
+    Ok(())
+}
+
+/// Substitute placeholders; `None` when any referenced placeholder is empty or
+/// the result is empty.
+fn fill(pattern: &str, value: &dyn Fn(&str) -> String) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = pattern;
+    while let Some(open) = rest.find('{') {
+        out.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let close = after.find('}')?;
+        let filled = value(&after[..close]);
+        if filled.is_empty() {
+            return None;
+        }
+        out.push_str(&filled);
+        rest = &after[close + 1..];
+    }
+    out.push_str(rest);
+    (!out.is_empty()).then_some(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use concert_types::{Musician, Song};
+
+    fn concert() -> ConcertInfo {
+        ConcertInfo {
+            artist: "Artist".to_string(),
+            source: "https://www.npr.org/x".to_string(),
+            show: "Tiny Desk Concerts".to_string(),
+            date: Some("2024-05-01".to_string()),
+            album: "Artist: Tiny Desk Concert".to_string(),
+            description: None,
+            set_list: vec![Song {
+                title: "One".to_string(),
+            }],
+            musicians: vec![
+                Musician {
+                    name: "Ann".to_string(),
+                    instruments: vec!["vocals".to_string(), "guitar".to_string()],
+                },
+                Musician {
+                    name: "Bo".to_string(),
+                    instruments: vec![],
+                },
+            ],
+            preview_image_url: None,
+            teaser: None,
+            timestamps: None,
+            interludes: None,
+        }
+    }
+
+    #[test]
+    fn default_template_fills_rich_tags_for_a_song() {
+        let tags = TagTemplate::default().render(&concert(), "One", Some(2), 5);
+        assert_eq!(tags.get("title"), Some("One"));
+        assert_eq!(tags.get("album_artist"), Some("Artist"));
+        assert_eq!(tags.get("date"), Some("2024"));
+        assert_eq!(tags.get("genre"), Some("Tiny Desk"));
+        assert_eq!(tags.get("track"), Some("2/5"));
+        assert_eq!(tags.get("disc"), Some("1/1"));
+        assert_eq!(tags.get("comment"), Some("https://www.npr.org/x"));
+        assert_eq!(tags.get("composer"), Some("Ann; Bo"));
+        assert_eq!(tags.get("performer"), Some("Ann (vocals, guitar); Bo"));
+    }
+
+    #[test]
+    fn tags_with_empty_placeholders_are_skipped() {
+        let mut c = concert();
+        c.date = None;
+        c.musicians.clear();
+        let tags = TagTemplate::default().render(&c, "interlude", None, 5);
+        assert_eq!(tags.get("track"), None);
+        assert_eq!(tags.get("date"), None);
+        assert_eq!(tags.get("composer"), None);
+        assert_eq!(tags.get("genre"), Some("Tiny Desk"));
+    }
+
+    #[test]
+    fn overrides_replace_remove_and_append() {
+        let mut template = TagTemplate::default();
+        template.apply_override("genre=Live").unwrap();
+        template.apply_override("performer=").unwrap();
+        template.apply_override("grouping={show}").unwrap();
+        let tags = template.render(&concert(), "One", Some(1), 1);
+        assert_eq!(tags.get("genre"), Some("Live"));
+        assert_eq!(tags.get("performer"), None);
+        assert_eq!(tags.get("grouping"), Some("Tiny Desk Concerts"));
+        // Replaced in place, not moved to the end.
+        let keys: Vec<&str> = template.fields().iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys.iter().position(|k| *k == "genre"), Some(5));
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected() {
+        let mut template = TagTemplate::default();
+        assert!(template.apply_override("genre").is_err());
+        assert!(template.apply_override("=x").is_err());
+        assert!(template.apply_override("genre={nope}").is_err());
+        assert!(template.apply_override("genre={title").is_err());
+    }
+
+    #[test]
+    fn empty_template_writes_nothing() {
+        let tags = TagTemplate::empty().render(&concert(), "One", Some(1), 1);
+        assert_eq!(tags, TrackTags::default());
+    }
+}
@@ -13,6 +13,12 @@ pub struct VideoInfo {
     // Basic information
     pub duration: f64,
     pub framerate: u32, // Integer frames per second
+    // Frame size in pixels; 0 for audio-only inputs.
+    pub width: u32,
+    pub height: u32,
+    // False for audio-only inputs (podcasts, radio rips, a music file with
+    // cover art): framerate is 0 and there are no frames.
+    pub has_video: bool,
 
     // Frame information
     pub frames: Vec<FrameInfo>,
@@ -108,7 +114,7 @@ impl VideoInfo {
                 "-select_streams",
                 "v:0",
                 "-show_entries",
-                "stream=r_frame_rate:format=duration,start_time",
+                "stream=r_frame_rate,width,height:stream_disposition=attached_pic:format=duration,start_time",
                 "-of",
                 "json",
                 input_file,
@@ -147,18 +153,21 @@ impl VideoInfo {
         }
 
         // Extract framerate
-        let fps_str = info["streams"][0]["r_frame_rate"]
-            .as_str()
-            .ok_or_else(|| anyhow::anyhow!("Missing framerate in video metadata"))?;
-        let mut fps: u32 = 24; // Default fallback value
-        if let Some((num, den)) = fps_str.split_once('/') {
-            if let (Ok(n), Ok(d)) = (num.parse::<f64>(), den.parse::<f64>()) {
-                if d > 0.0 {
-                    // Calculate framerate and round to nearest integer
-                    fps = (n / d).round() as u32;
-                }
-            }
-        }
+        let Some(fps) = video_stream_framerate(&info)? else {
+            println!(
+                "Audio-only input: duration: {}s, start time: {}s",
+                duration, start_time
+            );
+            return Ok(VideoInfo {
+                duration,
+                framerate: 0,
+                width: 0,
+                height: 0,
+                has_video: false,
+                frames: Vec::new(),
+                keyframe_indices: Vec::new(),
+            });
+        };
 
         println!(
             "Video duration: {}s, start time: {}s, framerate: {} fps",
@@ -213,11 +222,70 @@ impl VideoInfo {
             keyframe_indices.len()
         );
 
+        let (width, height) = video_stream_size(&info);
         Ok(VideoInfo {
             duration,
             framerate: fps,
+            width,
+            height,
+            has_video: true,
             frames,
             keyframe_indices,
         })
     }
 }
+
+/// The integer framerate of the first video stream in ffprobe's JSON, or `None`
+/// when there is no video stream or it is only embedded cover art.
+fn video_stream_framerate(info: &serde_json::Value) -> Result<Option<u32>> {
+    let Some(stream) = info["streams"].as_array().and_then(|s| s.first()) else {
+        return Ok(None);
+    };
+    if stream["disposition"]["attached_pic"].as_i64() == Some(1) {
+        return Ok(None);
+    }
+    let fps_str = stream["r_frame_rate"]
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("Missing framerate in video metadata"))?;
+    let mut fps: u32 = 24; // Default fallback value
+    if let Some((num, den)) = fps_str.split_once('/') {
+        if let (Ok(n), Ok(d)) = (num.parse::<f64>(), den.parse::<f64>()) {
+            if d > 0.0 {
+                // Calculate framerate and round to nearest integer
+                fps = (n / d).round() as u32;
+            }
+        }
+    }
+    Ok(Some(fps))
+}
+
+/// The first video stream's frame size in ffprobe's JSON, 0 when missing.
+fn video_stream_size(info: &serde_json::Value) -> (u32, u32) {
+    let stream = &info["streams"][0];
+    let dimension = |key: &str| stream[key].as_u64().unwrap_or(0) as u32;
+    (dimension("width"), dimension("height"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn audio_only_inputs_have_no_video_framerate() {
+        let video = json!({"streams": [{"r_frame_rate": "30000/1001", "disposition": {"attached_pic": 0}}]});
+        assert_eq!(video_stream_framerate(&video).unwrap(), Some(30));
+        let audio = json!({"streams": [], "format": {"duration": "1800.0"}});
+        assert_eq!(video_stream_framerate(&audio).unwrap(), None);
+        let cover_art =
+            json!({"streams": [{"r_frame_rate": "90000/1", "disposition": {"attached_pic": 1}}]});
+        assert_eq!(video_stream_framerate(&cover_art).unwrap(), None);
+    }
+
+    #[test]
+    fn video_size_comes_from_the_first_stream() {
+        let video = json!({"streams": [{"width": 1920, "height": 1080}]});
+        assert_eq!(video_stream_size(&video), (1920, 1080));
+        assert_eq!(video_stream_size(&json!({"streams": []})), (0, 0));
+    }
+}
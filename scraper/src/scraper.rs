@@ -126,6 +126,7 @@ pub fn parse_concert_info(html: &str, source_url: &str) -> Result<ConcertInfo> {
         preview_image_url,
         teaser,
         timestamps: None,
+        interludes: None,
     };
 
     Ok(concert_info)
@@ -389,11 +390,13 @@ pub fn save_concert_info_to(
 
     let mut info = concert_info.clone();
 
-    // Preserve timestamps from the existing file if present (written by the splitter).
+    // Preserve timestamps (and their interludes) from the existing file if
+    // present (written by the splitter).
     if info.timestamps.is_none() {
         if let Ok(existing) = fs::read_to_string(&output_file) {
             if let Ok(existing_info) = serde_json::from_str::<ConcertInfo>(&existing) {
                 info.timestamps = existing_info.timestamps;
+                info.interludes = existing_info.interludes;
             }
         }
     }
@@ -425,6 +425,7 @@ fn save_concert_info_preserves_timestamps() {
         preview_image_url: None,
         teaser: None,
         timestamps: None,
+        interludes: None,
     };
 
     // First save — no existing file.
@@ -76,6 +76,15 @@ function startServer() {
       // No-op opener: this suite never exercises watch/Open.
       "--open-cmd",
       "true",
+      // Unlimited job concurrency: files run in parallel against this one
+      // server, and a Job Driver run that a file never resolves would
+      // otherwise hold a slot another file is waiting on.
+      "--max-downloads",
+      "0",
+      "--max-splits",
+      "0",
+      "--max-archives",
+      "0",
     ],
     { stdio: ["ignore", "pipe", "pipe"] }
   );